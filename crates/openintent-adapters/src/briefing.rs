@@ -0,0 +1,494 @@
+//! Briefing adapter -- LLM-written reports composed from other adapters.
+//!
+//! A [`BriefingAdapter`] holds a list of [`BriefingSource`]s, each naming an
+//! adapter tool to call (today's calendar events, the unread inbox, a
+//! collector skill's trending items, ...).  The `generate_briefing` tool
+//! calls the selected sources concurrently, hands their results to the LLM,
+//! and returns the summary as Markdown.  A source that fails or times out is
+//! reported as unavailable instead of failing the briefing.
+//!
+//! To deliver the briefing every morning, create a cron job from
+//! [`BriefingAdapter::cron_params`] (e.g. with the configured
+//! [`BriefingConfig::briefing_time`](crate::BriefingConfig)); its command
+//! asks the agent to run `generate_briefing`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Local;
+use openintent_agent::{ChatRequest, LlmClient, LlmResponse, Message};
+use serde_json::{Value, json};
+use tracing::{debug, info, warn};
+
+use crate::error::{AdapterError, Result};
+use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
+
+/// How long a single source may take.
+const SOURCE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Longest source result passed to the LLM, in characters.
+const MAX_SOURCE_CHARS: usize = 8_000;
+
+/// Token budget for the written briefing.
+const MAX_BRIEFING_TOKENS: u32 = 1_024;
+
+/// Command of the cron job created by [`BriefingAdapter::cron_params`].
+pub const BRIEFING_CRON_COMMAND: &str =
+    "Generate my morning briefing with the generate_briefing tool and send it to me.";
+
+/// Instructions given to the LLM along with the source data.
+const SYSTEM_PROMPT: &str = "You write a short morning briefing from the data below. \
+Use Markdown: a `# Morning Briefing` title with the date, then one `##` section per source \
+that has something worth mentioning, with concise bullet points. Highlight what needs \
+attention today (meetings, important or unread mail, notable trends). Mention unavailable \
+sources in a single line at the end. Do not invent facts that are not in the data.";
+
+// ---------------------------------------------------------------------------
+// Sources
+// ---------------------------------------------------------------------------
+
+/// One input to a briefing: a tool call on another adapter.
+#[derive(Clone)]
+pub struct BriefingSource {
+    /// Name shown to the LLM and used to select the source.
+    name: String,
+    /// Adapter the tool is called on.
+    adapter: Arc<dyn Adapter>,
+    /// Tool to call.
+    tool: String,
+    /// Parameters passed to the tool.
+    params: Value,
+}
+
+impl BriefingSource {
+    /// A source calling `tool` on `adapter` with `params`, e.g. a collector
+    /// skill's trending tool.
+    pub fn new(
+        name: impl Into<String>,
+        adapter: Arc<dyn Adapter>,
+        tool: impl Into<String>,
+        params: Value,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            adapter,
+            tool: tool.into(),
+            params,
+        }
+    }
+
+    /// Today's events from a [`CalendarAdapter`](crate::CalendarAdapter).
+    pub fn calendar(adapter: Arc<dyn Adapter>) -> Self {
+        Self::new(
+            "calendar",
+            adapter,
+            "calendar_list_events",
+            json!({ "days_ahead": 1 }),
+        )
+    }
+
+    /// Recent inbox messages from an [`EmailAdapter`](crate::EmailAdapter).
+    /// `params` carries the account settings `email_list_inbox` expects.
+    pub fn email(adapter: Arc<dyn Adapter>, params: Value) -> Self {
+        Self::new("email", adapter, "email_list_inbox", params)
+    }
+
+    /// The source name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Call the tool, bounded by [`SOURCE_TIMEOUT`].
+    async fn fetch(&self) -> std::result::Result<Value, String> {
+        debug!(source = %self.name, tool = %self.tool, "fetching briefing source");
+        match tokio::time::timeout(
+            SOURCE_TIMEOUT,
+            self.adapter.execute_tool(&self.tool, self.params.clone()),
+        )
+        .await
+        {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(e)) => Err(e.to_string()),
+            Err(_) => Err(format!("timed out after {}s", SOURCE_TIMEOUT.as_secs())),
+        }
+    }
+}
+
+impl std::fmt::Debug for BriefingSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BriefingSource")
+            .field("name", &self.name)
+            .field("adapter", &self.adapter.id())
+            .field("tool", &self.tool)
+            .finish()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Adapter
+// ---------------------------------------------------------------------------
+
+/// Composes briefings from other adapters and summarizes them with an LLM.
+pub struct BriefingAdapter {
+    /// Unique identifier for this adapter instance.
+    id: String,
+    /// Whether the adapter has been connected.
+    connected: bool,
+    /// LLM that writes the briefing.
+    llm: Arc<LlmClient>,
+    /// Sources in the order they are presented to the LLM.
+    sources: Vec<BriefingSource>,
+}
+
+impl BriefingAdapter {
+    /// Create a briefing adapter with no sources.
+    pub fn new(id: impl Into<String>, llm: Arc<LlmClient>) -> Self {
+        Self {
+            id: id.into(),
+            connected: false,
+            llm,
+            sources: Vec::new(),
+        }
+    }
+
+    /// Add a source, replacing any existing source with the same name.
+    pub fn with_source(mut self, source: BriefingSource) -> Self {
+        self.sources.retain(|s| s.name != source.name);
+        self.sources.push(source);
+        self
+    }
+
+    /// `cron_create` parameters for a daily briefing at `time` (`HH:MM`,
+    /// 24-hour) in the IANA `timezone`.
+    pub fn cron_params(time: &str, timezone: &str) -> Result<Value> {
+        let invalid = || AdapterError::InvalidParams {
+            tool_name: "cron_create".into(),
+            reason: format!("invalid briefing time `{time}`: expected HH:MM"),
+        };
+        let (hour, minute) = time.trim().split_once(':').ok_or_else(invalid)?;
+        let hour: u8 = hour.parse().ok().filter(|h| *h < 24).ok_or_else(invalid)?;
+        let minute: u8 = minute
+            .parse()
+            .ok()
+            .filter(|m| *m < 60)
+            .ok_or_else(invalid)?;
+        Ok(json!({
+            "name": "morning-briefing",
+            "schedule": format!("{minute} {hour} * * *"),
+            "timezone": timezone,
+            "command": BRIEFING_CRON_COMMAND,
+        }))
+    }
+
+    /// Build the user message holding every source's result.
+    fn render_sources(
+        date: &str,
+        results: &[(&BriefingSource, std::result::Result<Value, String>)],
+    ) -> String {
+        let mut prompt = format!("Date: {date}\n");
+        for (source, result) in results {
+            prompt.push_str(&format!("\n### Source: {}\n", source.name));
+            match result {
+                Ok(value) => {
+                    let text = serde_json::to_string_pretty(value).unwrap_or_default();
+                    match text.char_indices().nth(MAX_SOURCE_CHARS) {
+                        Some((end, _)) => {
+                            prompt.push_str(&text[..end]);
+                            prompt.push_str("\n[truncated]");
+                        }
+                        None => prompt.push_str(&text),
+                    }
+                }
+                Err(e) => prompt.push_str(&format!("Unavailable: {e}")),
+            }
+            prompt.push('\n');
+        }
+        prompt
+    }
+
+    // -- Tool implementations ------------------------------------------------
+
+    /// Gather the selected sources and have the LLM summarize them.
+    async fn tool_generate_briefing(&self, params: Value) -> Result<Value> {
+        let selected: Vec<&BriefingSource> = match params.get("sources") {
+            None | Some(Value::Null) => self.sources.iter().collect(),
+            Some(Value::Array(names)) => {
+                let mut selected = Vec::new();
+                for name in names {
+                    let name = name.as_str().ok_or_else(|| AdapterError::InvalidParams {
+                        tool_name: "generate_briefing".into(),
+                        reason: "`sources` must be an array of source names".into(),
+                    })?;
+                    let source = self
+                        .sources
+                        .iter()
+                        .find(|s| s.name == name)
+                        .ok_or_else(|| AdapterError::InvalidParams {
+                            tool_name: "generate_briefing".into(),
+                            reason: format!(
+                                "unknown source `{name}`; available: {}",
+                                self.source_names().join(", ")
+                            ),
+                        })?;
+                    selected.push(source);
+                }
+                selected
+            }
+            Some(_) => {
+                return Err(AdapterError::InvalidParams {
+                    tool_name: "generate_briefing".into(),
+                    reason: "`sources` must be an array of source names".into(),
+                });
+            }
+        };
+        if selected.is_empty() {
+            return Err(AdapterError::InvalidParams {
+                tool_name: "generate_briefing".into(),
+                reason: "no briefing sources are configured".into(),
+            });
+        }
+
+        let fetched = futures::future::join_all(selected.iter().map(|s| s.fetch())).await;
+        let results: Vec<_> = selected.iter().copied().zip(fetched).collect();
+        for (source, result) in &results {
+            if let Err(e) = result {
+                warn!(source = %source.name, error = %e, "briefing source unavailable");
+            }
+        }
+
+        let date = Local::now().format("%A, %B %-d, %Y").to_string();
+        let request = ChatRequest {
+            model: self.llm.current_default_model(),
+            messages: vec![
+                Message::system(SYSTEM_PROMPT),
+                Message::user(Self::render_sources(&date, &results)),
+            ],
+            tools: vec![],
+            temperature: Some(0.3),
+            max_tokens: Some(MAX_BRIEFING_TOKENS),
+            stream: false,
+        };
+        let markdown = match self.llm.chat(&request).await {
+            Ok(LlmResponse::Text(text)) if !text.trim().is_empty() => text,
+            Ok(_) => {
+                return Err(AdapterError::ExecutionFailed {
+                    tool_name: "generate_briefing".into(),
+                    reason: "the LLM returned no briefing text".into(),
+                });
+            }
+            Err(e) => {
+                return Err(AdapterError::ExecutionFailed {
+                    tool_name: "generate_briefing".into(),
+                    reason: format!("failed to summarize the briefing: {e}"),
+                });
+            }
+        };
+
+        let sources: Vec<Value> = results
+            .iter()
+            .map(|(source, result)| match result {
+                Ok(_) => json!({ "name": source.name, "status": "ok" }),
+                Err(e) => json!({ "name": source.name, "status": "unavailable", "error": e }),
+            })
+            .collect();
+        info!(sources = sources.len(), "briefing generated");
+        Ok(json!({ "markdown": markdown, "date": date, "sources": sources }))
+    }
+
+    fn source_names(&self) -> Vec<&str> {
+        self.sources.iter().map(|s| s.name.as_str()).collect()
+    }
+}
+
+#[async_trait]
+impl Adapter for BriefingAdapter {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        AdapterType::Productivity
+    }
+
+    async fn connect(&mut self) -> Result<()> {
+        info!(id = %self.id, sources = ?self.source_names(), "briefing adapter connected");
+        self.connected = true;
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        info!(id = %self.id, "briefing adapter disconnected");
+        self.connected = false;
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        if !self.connected {
+            Ok(HealthStatus::Unhealthy)
+        } else if self.sources.is_empty() {
+            Ok(HealthStatus::Degraded)
+        } else {
+            Ok(HealthStatus::Healthy)
+        }
+    }
+
+    fn tools(&self) -> Vec<ToolDefinition> {
+        vec![ToolDefinition {
+            name: "generate_briefing".into(),
+            description: format!(
+                "Compose a Markdown briefing summarizing today's information from the \
+                 configured sources ({})",
+                self.source_names().join(", ")
+            ),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "sources": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Source names to include (default: all)"
+                    }
+                },
+                "required": []
+            }),
+            destructive: false,
+        }]
+    }
+
+    async fn execute_tool(&self, name: &str, params: Value) -> Result<Value> {
+        if !self.connected {
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: name.to_string(),
+                reason: format!("adapter `{}` is not connected", self.id),
+            });
+        }
+        match name {
+            "generate_briefing" => self.tool_generate_briefing(params).await,
+            _ => Err(AdapterError::ToolNotFound {
+                adapter_id: self.id.clone(),
+                tool_name: name.to_string(),
+            }),
+        }
+    }
+
+    fn required_auth(&self) -> Option<AuthRequirement> {
+        None
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openintent_agent::{LlmClientConfig, ScriptedBackend};
+
+    /// Adapter answering one tool with a fixed result.
+    struct MockSource {
+        tool: &'static str,
+        result: Option<Value>,
+    }
+
+    #[async_trait]
+    impl Adapter for MockSource {
+        fn id(&self) -> &str {
+            "mock"
+        }
+
+        fn adapter_type(&self) -> AdapterType {
+            AdapterType::Productivity
+        }
+
+        async fn connect(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn disconnect(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn health_check(&self) -> Result<HealthStatus> {
+            Ok(HealthStatus::Healthy)
+        }
+
+        fn tools(&self) -> Vec<ToolDefinition> {
+            Vec::new()
+        }
+
+        async fn execute_tool(&self, name: &str, _params: Value) -> Result<Value> {
+            assert_eq!(name, self.tool);
+            self.result
+                .clone()
+                .ok_or_else(|| AdapterError::ExecutionFailed {
+                    tool_name: name.to_string(),
+                    reason: "IMAP login failed".into(),
+                })
+        }
+
+        fn required_auth(&self) -> Option<AuthRequirement> {
+            None
+        }
+    }
+
+    #[tokio::test]
+    async fn composes_briefing_from_sources() {
+        let backend = Arc::new(ScriptedBackend::new([LlmResponse::Text(
+            "# Morning Briefing\n\n## Calendar\n- 09:30 Standup\n\n## Trending\n- Rust 2024".into(),
+        )]));
+        let llm = LlmClient::new(LlmClientConfig::anthropic("test-key", "test-model"))
+            .unwrap()
+            .with_backend(backend.clone());
+
+        let calendar = Arc::new(MockSource {
+            tool: "calendar_list_events",
+            result: Some(json!({ "events": [{ "summary": "Standup", "start": "09:30" }] })),
+        });
+        let email = Arc::new(MockSource {
+            tool: "email_list_inbox",
+            result: None,
+        });
+        let trending = Arc::new(MockSource {
+            tool: "skill_collector_trending",
+            result: Some(json!({ "items": ["Rust 2024"] })),
+        });
+        let mut adapter = BriefingAdapter::new("briefing", Arc::new(llm))
+            .with_source(BriefingSource::calendar(calendar))
+            .with_source(BriefingSource::email(email, json!({})))
+            .with_source(BriefingSource::new(
+                "trending",
+                trending,
+                "skill_collector_trending",
+                json!({}),
+            ));
+        adapter.connect().await.unwrap();
+
+        let result = adapter
+            .execute_tool("generate_briefing", json!({}))
+            .await
+            .unwrap();
+        let markdown = result["markdown"].as_str().unwrap();
+        assert!(markdown.starts_with("# Morning Briefing"));
+        assert_eq!(result["sources"][0]["status"], "ok");
+        assert_eq!(result["sources"][1]["status"], "unavailable");
+        assert_eq!(result["sources"][2]["status"], "ok");
+
+        let requests = backend.requests();
+        assert_eq!(requests.len(), 1);
+        let prompt = format!("{:?}", requests[0].messages);
+        assert!(prompt.contains("Standup"));
+        assert!(prompt.contains("Rust 2024"));
+        assert!(prompt.contains("IMAP login failed"));
+    }
+
+    #[test]
+    fn cron_params_schedule_daily_run() {
+        let params = BriefingAdapter::cron_params("07:00", "Europe/Berlin").unwrap();
+        assert_eq!(params["schedule"], "0 7 * * *");
+        assert_eq!(params["timezone"], "Europe/Berlin");
+        assert!(BriefingAdapter::cron_params("7am", "UTC").is_err());
+        assert!(BriefingAdapter::cron_params("24:00", "UTC").is_err());
+    }
+}
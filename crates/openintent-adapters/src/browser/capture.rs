@@ -0,0 +1,325 @@
+//! Page capture tools: screenshots and PDF export.
+
+use serde_json::{Value, json};
+use tracing::debug;
+
+use super::BrowserAdapter;
+use super::cdp::extract_runtime_value;
+use crate::error::{AdapterError, Result};
+
+/// Allowed range for the `scale` option of `Page.printToPDF`.
+const PDF_SCALE_RANGE: std::ops::RangeInclusive<f64> = 0.1..=2.0;
+
+impl BrowserAdapter {
+    /// Take a screenshot of the current page, a single element, or the
+    /// full scrollable page.
+    pub(super) async fn tool_browser_screenshot(&self, params: Value) -> Result<Value> {
+        let format = params
+            .get("format")
+            .and_then(|v| v.as_str())
+            .unwrap_or("png");
+
+        // Validate format.
+        if format != "png" && format != "jpeg" {
+            return Err(AdapterError::InvalidParams {
+                tool_name: "browser_screenshot".into(),
+                reason: format!("unsupported format `{format}`; use \"png\" or \"jpeg\""),
+            });
+        }
+
+        let selector = params.get("selector").and_then(|v| v.as_str());
+        let full_page = params
+            .get("full_page")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        if selector.is_some() && full_page {
+            return Err(AdapterError::InvalidParams {
+                tool_name: "browser_screenshot".into(),
+                reason: "`selector` and `full_page` are mutually exclusive".into(),
+            });
+        }
+
+        debug!(format = format, selector = ?selector, full_page, "taking screenshot");
+
+        let mut cdp_params = json!({ "format": format });
+        let clip = match selector {
+            Some(selector) => Some(self.element_clip(selector).await?),
+            None if full_page => Some(self.full_page_clip().await?),
+            None => None,
+        };
+        if let Some(clip) = clip {
+            cdp_params["clip"] = clip;
+            cdp_params["captureBeyondViewport"] = json!(true);
+        }
+
+        let result = self
+            .send_cdp_command("Page.captureScreenshot", cdp_params)
+            .await?;
+
+        let data = result.get("data").and_then(|v| v.as_str()).unwrap_or("");
+
+        Ok(json!({
+            "format": format,
+            "data": data,
+            "encoding": "base64",
+            "length": data.len(),
+            "selector": selector,
+            "full_page": full_page,
+        }))
+    }
+
+    /// Render the current page to PDF.
+    ///
+    /// Chrome only supports `Page.printToPDF` in headless mode; a headed
+    /// browser reports a CDP error.
+    pub(super) async fn tool_browser_print_to_pdf(&self, params: Value) -> Result<Value> {
+        let mut cdp_params = json!({
+            "landscape": params.get("landscape").and_then(|v| v.as_bool()).unwrap_or(false),
+            "printBackground": params
+                .get("print_background")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true),
+        });
+
+        if let Some(scale) = params.get("scale").and_then(|v| v.as_f64()) {
+            if !PDF_SCALE_RANGE.contains(&scale) {
+                return Err(AdapterError::InvalidParams {
+                    tool_name: "browser_print_to_pdf".into(),
+                    reason: format!("`scale` must be between 0.1 and 2.0, got {scale}"),
+                });
+            }
+            cdp_params["scale"] = json!(scale);
+        }
+
+        for (param, cdp_name) in [
+            ("paper_width", "paperWidth"),
+            ("paper_height", "paperHeight"),
+            ("margin", "marginTop"),
+            ("margin", "marginBottom"),
+            ("margin", "marginLeft"),
+            ("margin", "marginRight"),
+        ] {
+            if let Some(inches) = params.get(param).and_then(|v| v.as_f64()) {
+                if inches < 0.0 {
+                    return Err(AdapterError::InvalidParams {
+                        tool_name: "browser_print_to_pdf".into(),
+                        reason: format!("`{param}` must not be negative"),
+                    });
+                }
+                cdp_params[cdp_name] = json!(inches);
+            }
+        }
+
+        if let Some(ranges) = params.get("page_ranges").and_then(|v| v.as_str()) {
+            cdp_params["pageRanges"] = json!(ranges);
+        }
+
+        debug!(params = %cdp_params, "printing page to PDF");
+
+        let result = self.send_cdp_command("Page.printToPDF", cdp_params).await?;
+        let data = result.get("data").and_then(|v| v.as_str()).unwrap_or("");
+
+        Ok(json!({
+            "format": "pdf",
+            "data": data,
+            "encoding": "base64",
+            "length": data.len(),
+        }))
+    }
+
+    /// Compute a page-coordinate clip rectangle for the element matching
+    /// `selector`.
+    async fn element_clip(&self, selector: &str) -> Result<Value> {
+        let js = format!(
+            r#"(() => {{
+                const el = document.querySelector({selector});
+                if (!el) return JSON.stringify({{ error: "element not found" }});
+                const r = el.getBoundingClientRect();
+                return JSON.stringify({{
+                    x: r.left + window.scrollX,
+                    y: r.top + window.scrollY,
+                    width: r.width,
+                    height: r.height
+                }});
+            }})()"#,
+            selector = serde_json::to_string(selector).map_err(AdapterError::from)?
+        );
+
+        let result = self
+            .send_cdp_command(
+                "Runtime.evaluate",
+                json!({
+                    "expression": js,
+                    "returnByValue": true,
+                }),
+            )
+            .await?;
+        let rect: Value = serde_json::from_str(&extract_runtime_value(&result)?)?;
+
+        if rect.get("error").is_some() {
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: "browser_screenshot".into(),
+                reason: format!("element not found for selector `{selector}`"),
+            });
+        }
+
+        let width = rect.get("width").and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let height = rect.get("height").and_then(|v| v.as_f64()).unwrap_or(0.0);
+        if width <= 0.0 || height <= 0.0 {
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: "browser_screenshot".into(),
+                reason: format!("element `{selector}` has no visible area"),
+            });
+        }
+
+        Ok(json!({
+            "x": rect.get("x").and_then(|v| v.as_f64()).unwrap_or(0.0),
+            "y": rect.get("y").and_then(|v| v.as_f64()).unwrap_or(0.0),
+            "width": width,
+            "height": height,
+            "scale": 1,
+        }))
+    }
+
+    /// Compute a clip rectangle covering the whole scrollable page.
+    async fn full_page_clip(&self) -> Result<Value> {
+        let metrics = self
+            .send_cdp_command("Page.getLayoutMetrics", json!({}))
+            .await?;
+
+        // `cssContentSize` is reported in CSS pixels; older Chrome versions
+        // only provide the deprecated `contentSize`.
+        let size = metrics
+            .get("cssContentSize")
+            .or_else(|| metrics.get("contentSize"))
+            .ok_or_else(|| AdapterError::ExecutionFailed {
+                tool_name: "browser_screenshot".into(),
+                reason: "CDP layout metrics missing content size".into(),
+            })?;
+
+        Ok(json!({
+            "x": 0,
+            "y": 0,
+            "width": size.get("width").and_then(|v| v.as_f64()).unwrap_or(0.0),
+            "height": size.get("height").and_then(|v| v.as_f64()).unwrap_or(0.0),
+            "scale": 1,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use super::*;
+    use crate::traits::Adapter;
+
+    /// A browser adapter marked connected, pointed at a port nothing listens on.
+    fn adapter_without_chrome() -> BrowserAdapter {
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|l| l.local_addr())
+            .map(|a| a.port())
+            .expect("should bind an ephemeral port in tests");
+        let adapter = BrowserAdapter::with_port("test-browser", port);
+        adapter.connected.store(true, Ordering::Release);
+        adapter
+    }
+
+    #[tokio::test]
+    async fn browser_adapter_screenshot_rejects_invalid_format() {
+        let adapter = BrowserAdapter::new("test-browser");
+        adapter.connected.store(true, Ordering::Release);
+
+        let result = adapter
+            .execute_tool("browser_screenshot", json!({"format": "bmp"}))
+            .await;
+        assert!(result.is_err());
+        match result.unwrap_err() {
+            AdapterError::InvalidParams { tool_name, reason } => {
+                assert_eq!(tool_name, "browser_screenshot");
+                assert!(reason.contains("bmp"));
+            }
+            other => panic!("expected InvalidParams, got: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn screenshot_rejects_selector_with_full_page() {
+        let adapter = adapter_without_chrome();
+        let result = adapter
+            .execute_tool(
+                "browser_screenshot",
+                json!({"selector": "#main", "full_page": true}),
+            )
+            .await;
+        assert!(matches!(result, Err(AdapterError::InvalidParams { .. })));
+    }
+
+    #[tokio::test]
+    async fn print_to_pdf_rejects_out_of_range_scale() {
+        let adapter = adapter_without_chrome();
+        let result = adapter
+            .execute_tool("browser_print_to_pdf", json!({"scale": 5.0}))
+            .await;
+        match result {
+            Err(AdapterError::InvalidParams { tool_name, reason }) => {
+                assert_eq!(tool_name, "browser_print_to_pdf");
+                assert!(reason.contains("scale"));
+            }
+            other => panic!("expected InvalidParams, got: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn capture_without_chrome_reports_not_connected() {
+        let adapter = adapter_without_chrome();
+
+        let screenshot = adapter.execute_tool("browser_screenshot", json!({})).await;
+        assert!(matches!(screenshot, Err(AdapterError::NotConnected { .. })));
+
+        let pdf = adapter
+            .execute_tool("browser_print_to_pdf", json!({}))
+            .await;
+        assert!(matches!(pdf, Err(AdapterError::NotConnected { .. })));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a local Chrome/Chromium — run manually with --ignored"]
+    async fn screenshot_of_data_url_is_png() {
+        use base64::Engine;
+
+        let mut adapter = BrowserAdapter::with_port("test-browser", 9339);
+        adapter
+            .connect()
+            .await
+            .expect("headless Chrome should launch");
+
+        adapter
+            .execute_tool(
+                "browser_navigate",
+                json!({"url": "data:text/html,<h1 id='t'>hello</h1>"}),
+            )
+            .await
+            .expect("navigation should succeed");
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+        let shot = adapter
+            .execute_tool("browser_screenshot", json!({"full_page": true}))
+            .await
+            .expect("screenshot should succeed");
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(shot["data"].as_str().expect("data should be a string"))
+            .expect("data should be base64");
+        assert!(bytes.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+        let pdf = adapter
+            .execute_tool("browser_print_to_pdf", json!({}))
+            .await
+            .expect("PDF export should succeed");
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(pdf["data"].as_str().expect("data should be a string"))
+            .expect("data should be base64");
+        assert!(bytes.starts_with(b"%PDF"));
+    }
+}
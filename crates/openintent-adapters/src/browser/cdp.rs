@@ -0,0 +1,357 @@
+//! CDP transport: page-target discovery and one-shot WebSocket commands.
+
+use futures::{SinkExt, StreamExt};
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
+use tracing::debug;
+
+use super::{BrowserAdapter, CDP_TIMEOUT_SECS, MAX_CDP_RESPONSE_BYTES};
+use crate::error::{AdapterError, Result};
+
+impl BrowserAdapter {
+    /// Get the list of page targets from the DevTools endpoint.
+    pub(super) async fn get_page_targets(&self) -> Result<Vec<Value>> {
+        let url = format!("{}/json", self.devtools_base_url());
+        let response = self.client.get(&url).send().await.map_err(|e| {
+            if e.is_connect() {
+                AdapterError::NotConnected {
+                    adapter_id: self.id.clone(),
+                    reason: format!(
+                        "Chrome DevTools is not reachable at {}",
+                        self.devtools_base_url()
+                    ),
+                }
+            } else {
+                AdapterError::ExecutionFailed {
+                    tool_name: "browser".into(),
+                    reason: format!("failed to list DevTools targets: {e}"),
+                }
+            }
+        })?;
+
+        let targets: Vec<Value> =
+            response
+                .json()
+                .await
+                .map_err(|e| AdapterError::ExecutionFailed {
+                    tool_name: "browser".into(),
+                    reason: format!("failed to parse target list: {e}"),
+                })?;
+
+        // Filter to page targets only.
+        let pages: Vec<Value> = targets
+            .into_iter()
+            .filter(|t| t.get("type").and_then(|v| v.as_str()) == Some("page"))
+            .collect();
+
+        if pages.is_empty() {
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: "browser".into(),
+                reason: "no page targets available in Chrome".into(),
+            });
+        }
+
+        Ok(pages)
+    }
+
+    /// Get the WebSocket debugger URL for the first page target.
+    pub(super) async fn get_ws_url(&self) -> Result<String> {
+        let pages = self.get_page_targets().await?;
+        let first_page = &pages[0];
+
+        first_page
+            .get("webSocketDebuggerUrl")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| AdapterError::ExecutionFailed {
+                tool_name: "browser".into(),
+                reason: "page target has no webSocketDebuggerUrl".into(),
+            })
+    }
+
+    /// Send a CDP command over WebSocket and return the result.
+    ///
+    /// Opens a new WebSocket connection, sends the command, waits for the
+    /// matching response (by message ID), and closes the connection.
+    pub(super) async fn send_cdp_command(&self, method: &str, params: Value) -> Result<Value> {
+        let ws_url = self.get_ws_url().await?;
+        let msg_id = self.next_id();
+
+        debug!(
+            method = method,
+            msg_id = msg_id,
+            ws_url = %ws_url,
+            "sending CDP command"
+        );
+
+        let cdp_message = json!({
+            "id": msg_id,
+            "method": method,
+            "params": params,
+        });
+
+        // Connect to the WebSocket with a timeout.
+        let (ws_stream, _response) = tokio::time::timeout(
+            Duration::from_secs(CDP_TIMEOUT_SECS),
+            connect_async(&ws_url),
+        )
+        .await
+        .map_err(|_| AdapterError::Timeout {
+            seconds: CDP_TIMEOUT_SECS,
+            reason: format!("WebSocket connection to `{ws_url}` timed out"),
+        })?
+        .map_err(|e| AdapterError::ExecutionFailed {
+            tool_name: "browser".into(),
+            reason: format!("WebSocket connection failed: {e}"),
+        })?;
+
+        let (mut sink, mut stream) = ws_stream.split();
+
+        // Send the CDP command.
+        let msg_text = serde_json::to_string(&cdp_message).map_err(AdapterError::from)?;
+        sink.send(Message::Text(msg_text.into()))
+            .await
+            .map_err(|e| AdapterError::ExecutionFailed {
+                tool_name: "browser".into(),
+                reason: format!("failed to send CDP message: {e}"),
+            })?;
+
+        // Wait for the matching response.
+        let result = tokio::time::timeout(Duration::from_secs(CDP_TIMEOUT_SECS), async {
+            while let Some(msg_result) = stream.next().await {
+                let msg = msg_result.map_err(|e| AdapterError::ExecutionFailed {
+                    tool_name: "browser".into(),
+                    reason: format!("WebSocket receive error: {e}"),
+                })?;
+
+                match msg {
+                    Message::Text(text) => {
+                        if text.len() > MAX_CDP_RESPONSE_BYTES {
+                            return Err(AdapterError::ExecutionFailed {
+                                tool_name: "browser".into(),
+                                reason: format!(
+                                    "CDP response too large: {} bytes (max {})",
+                                    text.len(),
+                                    MAX_CDP_RESPONSE_BYTES
+                                ),
+                            });
+                        }
+
+                        let response: Value =
+                            serde_json::from_str(&text).map_err(AdapterError::from)?;
+
+                        // Check if this response matches our message ID.
+                        if response.get("id").and_then(|v| v.as_u64()) == Some(msg_id) {
+                            // Check for CDP errors.
+                            if let Some(error) = response.get("error") {
+                                let error_msg = error
+                                    .get("message")
+                                    .and_then(|v| v.as_str())
+                                    .unwrap_or("unknown CDP error");
+                                return Err(AdapterError::ExecutionFailed {
+                                    tool_name: "browser".into(),
+                                    reason: format!("CDP error: {error_msg}"),
+                                });
+                            }
+
+                            return Ok(response.get("result").cloned().unwrap_or(json!({})));
+                        }
+                        // Not our message; continue reading.
+                    }
+                    Message::Close(_) => {
+                        return Err(AdapterError::ExecutionFailed {
+                            tool_name: "browser".into(),
+                            reason: "WebSocket closed before receiving CDP response".into(),
+                        });
+                    }
+                    // Ignore ping, pong, binary frames.
+                    _ => {}
+                }
+            }
+
+            Err(AdapterError::ExecutionFailed {
+                tool_name: "browser".into(),
+                reason: "WebSocket stream ended without CDP response".into(),
+            })
+        })
+        .await
+        .map_err(|_| AdapterError::Timeout {
+            seconds: CDP_TIMEOUT_SECS,
+            reason: format!("waiting for CDP response to `{method}`"),
+        })?;
+
+        // Attempt to close the WebSocket cleanly (best-effort).
+        let _ = sink.send(Message::Close(None)).await;
+
+        result
+    }
+}
+
+/// Extract the string value from a `Runtime.evaluate` CDP response.
+///
+/// The CDP response shape is: `{ "result": { "type": "string", "value": "..." } }`.
+pub(super) fn extract_runtime_value(cdp_result: &Value) -> Result<String> {
+    let result_obj = cdp_result
+        .get("result")
+        .ok_or_else(|| AdapterError::ExecutionFailed {
+            tool_name: "browser".into(),
+            reason: "CDP response missing `result` field".into(),
+        })?;
+
+    // Check for exceptions in the evaluation.
+    if let Some(exception) = cdp_result.get("exceptionDetails") {
+        let exception_text = exception
+            .get("text")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown exception");
+        return Err(AdapterError::ExecutionFailed {
+            tool_name: "browser".into(),
+            reason: format!("JavaScript exception: {exception_text}"),
+        });
+    }
+
+    // The value can be of different types: string, number, boolean, object.
+    match result_obj.get("value") {
+        Some(Value::String(s)) => Ok(s.clone()),
+        Some(other) => Ok(other.to_string()),
+        None => {
+            // Some evaluations return undefined.
+            let result_type = result_obj
+                .get("type")
+                .and_then(|v| v.as_str())
+                .unwrap_or("undefined");
+            Ok(result_type.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::build_cdp_message;
+
+    #[test]
+    fn cdp_message_construction() {
+        let msg = build_cdp_message(1, "Page.navigate", json!({"url": "https://example.com"}));
+        assert_eq!(msg.get("id").and_then(|v| v.as_u64()), Some(1));
+        assert_eq!(
+            msg.get("method").and_then(|v| v.as_str()),
+            Some("Page.navigate")
+        );
+        assert_eq!(
+            msg.get("params")
+                .and_then(|v| v.get("url"))
+                .and_then(|v| v.as_str()),
+            Some("https://example.com")
+        );
+    }
+
+    #[test]
+    fn cdp_message_runtime_evaluate() {
+        let msg = build_cdp_message(
+            42,
+            "Runtime.evaluate",
+            json!({
+                "expression": "document.title",
+                "returnByValue": true,
+            }),
+        );
+        assert_eq!(msg.get("id").and_then(|v| v.as_u64()), Some(42));
+        assert_eq!(
+            msg.get("method").and_then(|v| v.as_str()),
+            Some("Runtime.evaluate")
+        );
+        assert_eq!(
+            msg.get("params")
+                .and_then(|v| v.get("expression"))
+                .and_then(|v| v.as_str()),
+            Some("document.title")
+        );
+        assert_eq!(
+            msg.get("params")
+                .and_then(|v| v.get("returnByValue"))
+                .and_then(|v| v.as_bool()),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn cdp_message_screenshot() {
+        let msg = build_cdp_message(5, "Page.captureScreenshot", json!({"format": "png"}));
+        assert_eq!(msg.get("id").and_then(|v| v.as_u64()), Some(5));
+        assert_eq!(
+            msg.get("method").and_then(|v| v.as_str()),
+            Some("Page.captureScreenshot")
+        );
+        assert_eq!(
+            msg.get("params")
+                .and_then(|v| v.get("format"))
+                .and_then(|v| v.as_str()),
+            Some("png")
+        );
+    }
+
+    #[test]
+    fn extract_runtime_value_string() {
+        let cdp_response = json!({
+            "result": {
+                "type": "string",
+                "value": "Hello, World!"
+            }
+        });
+        let value = extract_runtime_value(&cdp_response);
+        assert!(value.is_ok());
+        assert_eq!(value.expect("should be ok in tests"), "Hello, World!");
+    }
+
+    #[test]
+    fn extract_runtime_value_number() {
+        let cdp_response = json!({
+            "result": {
+                "type": "number",
+                "value": 42
+            }
+        });
+        let value = extract_runtime_value(&cdp_response);
+        assert!(value.is_ok());
+        assert_eq!(value.expect("should be ok in tests"), "42");
+    }
+
+    #[test]
+    fn extract_runtime_value_undefined() {
+        let cdp_response = json!({
+            "result": {
+                "type": "undefined"
+            }
+        });
+        let value = extract_runtime_value(&cdp_response);
+        assert!(value.is_ok());
+        assert_eq!(value.expect("should be ok in tests"), "undefined");
+    }
+
+    #[test]
+    fn extract_runtime_value_exception() {
+        let cdp_response = json!({
+            "result": {
+                "type": "object",
+                "subtype": "error"
+            },
+            "exceptionDetails": {
+                "text": "ReferenceError: foo is not defined"
+            }
+        });
+        let value = extract_runtime_value(&cdp_response);
+        assert!(value.is_err());
+        let err_str = value.unwrap_err().to_string();
+        assert!(err_str.contains("JavaScript exception"));
+    }
+
+    #[test]
+    fn extract_runtime_value_missing_result() {
+        let cdp_response = json!({});
+        let value = extract_runtime_value(&cdp_response);
+        assert!(value.is_err());
+    }
+}
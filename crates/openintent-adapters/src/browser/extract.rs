@@ -0,0 +1,337 @@
+//! Waiting and extraction tools for dynamic pages.
+//!
+//! These let the agent wait for client-rendered content to appear and then
+//! read it, instead of guessing sleep durations on single-page apps.
+
+use std::time::Duration;
+
+use serde_json::{Value, json};
+use tracing::debug;
+
+use super::cdp::extract_runtime_value;
+use super::{BrowserAdapter, CDP_TIMEOUT_SECS};
+use crate::error::{AdapterError, Result};
+
+/// Default wait for `browser_wait_for_selector` and `browser_eval_js`.
+const DEFAULT_WAIT_MS: u64 = 5_000;
+
+/// Upper bound on waits, kept below the CDP response timeout so the page-side
+/// timer always fires first.
+const MAX_WAIT_MS: u64 = (CDP_TIMEOUT_SECS - 5) * 1000;
+
+impl BrowserAdapter {
+    /// Wait until an element matching a CSS selector is present in the DOM.
+    pub(super) async fn tool_browser_wait_for_selector(&self, params: Value) -> Result<Value> {
+        let selector = required_str(&params, "selector", "browser_wait_for_selector")?;
+        let timeout_ms = wait_timeout_ms(&params);
+
+        debug!(selector = selector, timeout_ms, "waiting for selector");
+
+        let js = format!(
+            r#"new Promise((resolve) => {{
+                const selector = {selector};
+                const started = performance.now();
+                const done = (found) => resolve(JSON.stringify({{
+                    found,
+                    elapsed_ms: Math.round(performance.now() - started)
+                }}));
+                if (document.querySelector(selector)) return done(true);
+                const observer = new MutationObserver(() => {{
+                    if (document.querySelector(selector)) {{
+                        observer.disconnect();
+                        clearTimeout(timer);
+                        done(true);
+                    }}
+                }});
+                observer.observe(document.documentElement, {{
+                    childList: true, subtree: true, attributes: true
+                }});
+                const timer = setTimeout(() => {{ observer.disconnect(); done(false); }}, {timeout_ms});
+            }})"#,
+            selector = serde_json::to_string(selector)?,
+        );
+
+        let result = self
+            .send_cdp_command(
+                "Runtime.evaluate",
+                json!({
+                    "expression": js,
+                    "returnByValue": true,
+                    "awaitPromise": true,
+                }),
+            )
+            .await?;
+        let outcome: Value = serde_json::from_str(&extract_runtime_value(&result)?)?;
+
+        if outcome.get("found").and_then(|v| v.as_bool()) != Some(true) {
+            return Err(AdapterError::Timeout {
+                seconds: timeout_ms.div_ceil(1000),
+                reason: format!("no element matched selector `{selector}` within {timeout_ms}ms"),
+            });
+        }
+
+        Ok(json!({
+            "found": true,
+            "selector": selector,
+            "elapsed_ms": outcome.get("elapsed_ms").cloned().unwrap_or(json!(0)),
+        }))
+    }
+
+    /// Read the rendered text of the first element matching a selector.
+    pub(super) async fn tool_browser_get_text(&self, params: Value) -> Result<Value> {
+        let selector = required_str(&params, "selector", "browser_get_text")?;
+
+        let js = format!(
+            r#"(() => {{
+                const el = document.querySelector({selector});
+                if (!el) return JSON.stringify({{ found: false }});
+                return JSON.stringify({{ found: true, text: el.innerText ?? el.textContent ?? "" }});
+            }})()"#,
+            selector = serde_json::to_string(selector)?,
+        );
+        let found = self
+            .query_element("browser_get_text", selector, &js)
+            .await?;
+        let text = found.get("text").and_then(|v| v.as_str()).unwrap_or("");
+
+        Ok(json!({
+            "selector": selector,
+            "text": text,
+            "length": text.len(),
+        }))
+    }
+
+    /// Read an attribute of the first element matching a selector.
+    ///
+    /// A missing attribute is reported as `null`, not as an error.
+    pub(super) async fn tool_browser_get_attribute(&self, params: Value) -> Result<Value> {
+        let selector = required_str(&params, "selector", "browser_get_attribute")?;
+        let attribute = required_str(&params, "attribute", "browser_get_attribute")?;
+
+        let js = format!(
+            r#"(() => {{
+                const el = document.querySelector({selector});
+                if (!el) return JSON.stringify({{ found: false }});
+                return JSON.stringify({{ found: true, value: el.getAttribute({attribute}) }});
+            }})()"#,
+            selector = serde_json::to_string(selector)?,
+            attribute = serde_json::to_string(attribute)?,
+        );
+        let found = self
+            .query_element("browser_get_attribute", selector, &js)
+            .await?;
+
+        Ok(json!({
+            "selector": selector,
+            "attribute": attribute,
+            "value": found.get("value").cloned().unwrap_or(Value::Null),
+        }))
+    }
+
+    /// Evaluate a JavaScript expression with a time limit and return a typed
+    /// result.
+    ///
+    /// Promises are awaited.  Values that cannot be serialized to JSON (DOM
+    /// nodes, functions) are returned as their CDP description.
+    pub(super) async fn tool_browser_eval_js(&self, params: Value) -> Result<Value> {
+        let expression = required_str(&params, "expression", "browser_eval_js")?;
+        let timeout_ms = wait_timeout_ms(&params);
+
+        debug!(
+            expression_length = expression.len(),
+            timeout_ms, "evaluating JavaScript with timeout"
+        );
+
+        // CDP's own `timeout` interrupts synchronous execution; the outer
+        // timeout also covers promises that never settle.
+        let command = self.send_cdp_command(
+            "Runtime.evaluate",
+            json!({
+                "expression": expression,
+                "returnByValue": true,
+                "awaitPromise": true,
+                "timeout": timeout_ms,
+            }),
+        );
+        let result = tokio::time::timeout(Duration::from_millis(timeout_ms), command)
+            .await
+            .map_err(|_| AdapterError::Timeout {
+                seconds: timeout_ms.div_ceil(1000),
+                reason: format!("JavaScript evaluation did not finish within {timeout_ms}ms"),
+            })??;
+
+        if let Some(exception) = result.get("exceptionDetails") {
+            let text = exception
+                .get("exception")
+                .and_then(|e| e.get("description"))
+                .or_else(|| exception.get("text"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown exception");
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: "browser_eval_js".into(),
+                reason: format!("JavaScript exception: {text}"),
+            });
+        }
+
+        let remote = result.get("result").cloned().unwrap_or(json!({}));
+        let value = remote
+            .get("value")
+            .cloned()
+            .or_else(|| remote.get("description").cloned())
+            .unwrap_or(Value::Null);
+
+        Ok(json!({
+            "type": remote.get("type").cloned().unwrap_or(json!("undefined")),
+            "subtype": remote.get("subtype").cloned().unwrap_or(Value::Null),
+            "value": value,
+        }))
+    }
+
+    /// Run a lookup script returning `{ found, ... }` as JSON and fail with a
+    /// clear error when no element matched.
+    async fn query_element(&self, tool_name: &str, selector: &str, js: &str) -> Result<Value> {
+        let result = self
+            .send_cdp_command(
+                "Runtime.evaluate",
+                json!({
+                    "expression": js,
+                    "returnByValue": true,
+                }),
+            )
+            .await?;
+        let found: Value = serde_json::from_str(&extract_runtime_value(&result)?)?;
+
+        if found.get("found").and_then(|v| v.as_bool()) != Some(true) {
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: tool_name.into(),
+                reason: format!("element not found for selector `{selector}`"),
+            });
+        }
+        Ok(found)
+    }
+}
+
+/// Extract a required string parameter.
+fn required_str<'a>(params: &'a Value, field: &str, tool_name: &str) -> Result<&'a str> {
+    params
+        .get(field)
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| AdapterError::InvalidParams {
+            tool_name: tool_name.into(),
+            reason: format!("missing required string field `{field}`"),
+        })
+}
+
+/// Read `timeout_ms`, falling back to the default and clamping to the maximum.
+fn wait_timeout_ms(params: &Value) -> u64 {
+    params
+        .get("timeout_ms")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(DEFAULT_WAIT_MS)
+        .min(MAX_WAIT_MS)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use super::*;
+    use crate::traits::Adapter;
+
+    /// Fixture page that inserts its content 300ms after load.
+    const DYNAMIC_FIXTURE: &str = "data:text/html,<body><script>\
+        setTimeout(() => { const p = document.createElement('p'); p.id = 'late'; \
+        p.dataset.state = 'ready'; p.textContent = 'loaded later'; \
+        document.body.appendChild(p); }, 300);</script></body>";
+
+    #[test]
+    fn wait_timeout_defaults_and_clamps() {
+        assert_eq!(wait_timeout_ms(&json!({})), DEFAULT_WAIT_MS);
+        assert_eq!(wait_timeout_ms(&json!({"timeout_ms": 250})), 250);
+        assert_eq!(
+            wait_timeout_ms(&json!({"timeout_ms": 600_000})),
+            MAX_WAIT_MS
+        );
+    }
+
+    #[tokio::test]
+    async fn extraction_tools_require_their_params() {
+        let adapter = BrowserAdapter::new("test-browser");
+        adapter.connected.store(true, Ordering::Release);
+
+        for (tool, params, field) in [
+            ("browser_wait_for_selector", json!({}), "selector"),
+            ("browser_get_text", json!({}), "selector"),
+            (
+                "browser_get_attribute",
+                json!({"selector": "a"}),
+                "attribute",
+            ),
+            ("browser_eval_js", json!({}), "expression"),
+        ] {
+            match adapter.execute_tool(tool, params).await {
+                Err(AdapterError::InvalidParams { tool_name, reason }) => {
+                    assert_eq!(tool_name, tool);
+                    assert!(reason.contains(field), "{tool}: {reason}");
+                }
+                other => panic!("{tool}: expected InvalidParams, got: {other:?}"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a local Chrome/Chromium — run manually with --ignored"]
+    async fn waits_for_dynamic_element_and_reads_it() {
+        let mut adapter = BrowserAdapter::with_port("test-browser", 9339);
+        adapter
+            .connect()
+            .await
+            .expect("headless Chrome should launch");
+        adapter
+            .execute_tool("browser_navigate", json!({"url": DYNAMIC_FIXTURE}))
+            .await
+            .expect("navigation should succeed");
+
+        let waited = adapter
+            .execute_tool(
+                "browser_wait_for_selector",
+                json!({"selector": "#late", "timeout_ms": 5000}),
+            )
+            .await
+            .expect("element should appear");
+        assert_eq!(waited["found"], true);
+
+        let text = adapter
+            .execute_tool("browser_get_text", json!({"selector": "#late"}))
+            .await
+            .expect("text should be readable");
+        assert_eq!(text["text"], "loaded later");
+
+        let attr = adapter
+            .execute_tool(
+                "browser_get_attribute",
+                json!({"selector": "#late", "attribute": "data-state"}),
+            )
+            .await
+            .expect("attribute should be readable");
+        assert_eq!(attr["value"], "ready");
+
+        let eval = adapter
+            .execute_tool(
+                "browser_eval_js",
+                json!({"expression": "document.querySelectorAll('p').length"}),
+            )
+            .await
+            .expect("evaluation should succeed");
+        assert_eq!(eval["type"], "number");
+        assert_eq!(eval["value"], 1);
+
+        let missing = adapter
+            .execute_tool(
+                "browser_wait_for_selector",
+                json!({"selector": "#never", "timeout_ms": 200}),
+            )
+            .await;
+        assert!(matches!(missing, Err(AdapterError::Timeout { .. })));
+    }
+}
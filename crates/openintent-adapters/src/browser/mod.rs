@@ -0,0 +1,725 @@
+//! Browser adapter -- control a Chromium-based browser via the Chrome DevTools Protocol.
+//!
+//! This adapter communicates with Chrome/Chromium over its remote debugging port
+//! using the CDP (Chrome DevTools Protocol) over WebSocket.  It provides tools for
+//! navigation, content extraction, screenshots, element interaction, and JavaScript
+//! evaluation.
+//!
+//! # Architecture
+//!
+//! 1. Connect to Chrome's HTTP endpoint at `http://localhost:{port}/json/version`
+//!    to verify the browser is reachable.
+//! 2. For each tool execution, discover page targets via `GET /json`, connect to
+//!    the first page target's WebSocket URL, send a CDP command, receive the
+//!    response, and close the connection.
+//!
+//! The adapter can optionally launch Chrome with `--remote-debugging-port` if it
+//! is not already running.
+
+mod capture;
+mod cdp;
+mod extract;
+mod page;
+
+use async_trait::async_trait;
+
+use serde_json::{Value, json};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::error::{AdapterError, Result};
+use crate::http_client::{self, HttpClientFactory};
+use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
+
+/// Default Chrome DevTools Protocol debug port.
+const DEFAULT_DEBUG_PORT: u16 = 9222;
+
+/// Timeout for CDP WebSocket operations in seconds.
+const CDP_TIMEOUT_SECS: u64 = 30;
+
+/// Timeout for HTTP requests to the DevTools endpoint in seconds.
+const HTTP_TIMEOUT_SECS: u64 = 10;
+
+/// Timeout waiting for Chrome to start up in seconds.
+const CHROME_STARTUP_TIMEOUT_SECS: u64 = 10;
+
+/// Maximum response body size from CDP in bytes (20 MB), sized for
+/// base64-encoded full-page screenshots and PDFs.
+const MAX_CDP_RESPONSE_BYTES: usize = 20 * 1024 * 1024;
+
+/// Browser service adapter using Chrome DevTools Protocol.
+pub struct BrowserAdapter {
+    /// Unique identifier for this adapter instance.
+    id: String,
+    /// Whether the adapter has been connected.
+    connected: AtomicBool,
+    /// Optional path to the Chrome/Chromium executable.
+    chrome_path: Option<String>,
+    /// The remote debugging port.
+    debug_port: u16,
+    /// Monotonically increasing CDP message ID.
+    next_message_id: AtomicU64,
+    /// HTTP client for DevTools REST endpoints.
+    client: reqwest::Client,
+}
+
+// Explicit Send + Sync: all fields are atomic or Send+Sync.
+// AtomicBool and AtomicU64 are Send + Sync, reqwest::Client is Send + Sync.
+unsafe impl Send for BrowserAdapter {}
+unsafe impl Sync for BrowserAdapter {}
+
+impl BrowserAdapter {
+    /// Create a new browser adapter with the default debug port (9222).
+    pub fn new(id: impl Into<String>) -> Self {
+        Self::with_port(id, DEFAULT_DEBUG_PORT)
+    }
+
+    /// Create a new browser adapter with a custom debug port.
+    pub fn with_port(id: impl Into<String>, port: u16) -> Self {
+        let client = HttpClientFactory::default()
+            .with_timeout(Duration::from_secs(HTTP_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            id: id.into(),
+            connected: AtomicBool::new(false),
+            chrome_path: None,
+            debug_port: port,
+            next_message_id: AtomicU64::new(1),
+            client,
+        }
+    }
+
+    /// Set a custom Chrome/Chromium executable path.
+    pub fn with_chrome_path(mut self, path: impl Into<String>) -> Self {
+        self.chrome_path = Some(path.into());
+        self
+    }
+
+    /// Return the base URL for the DevTools HTTP endpoint.
+    fn devtools_base_url(&self) -> String {
+        format!("http://localhost:{}", self.debug_port)
+    }
+
+    /// Allocate the next CDP message ID.
+    fn next_id(&self) -> u64 {
+        self.next_message_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Check if the DevTools endpoint is reachable.
+    async fn is_devtools_reachable(&self) -> bool {
+        let url = format!("{}/json/version", self.devtools_base_url());
+        self.client.get(&url).send().await.is_ok()
+    }
+
+    /// Attempt to launch Chrome with remote debugging enabled.
+    async fn try_launch_chrome(&self) -> Result<()> {
+        let chrome_path = self.find_chrome_path()?;
+
+        info!(
+            chrome_path = %chrome_path,
+            port = self.debug_port,
+            "launching Chrome with remote debugging"
+        );
+
+        let mut cmd = tokio::process::Command::new(&chrome_path);
+        cmd.arg(format!("--remote-debugging-port={}", self.debug_port))
+            .arg("--no-first-run")
+            .arg("--no-default-browser-check")
+            .arg("--headless=new")
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null());
+
+        cmd.spawn().map_err(|e| AdapterError::ExecutionFailed {
+            tool_name: "connect".into(),
+            reason: format!("failed to launch Chrome at `{chrome_path}`: {e}"),
+        })?;
+
+        // Wait for Chrome to become reachable.
+        let deadline =
+            tokio::time::Instant::now() + Duration::from_secs(CHROME_STARTUP_TIMEOUT_SECS);
+        loop {
+            if self.is_devtools_reachable().await {
+                info!("Chrome DevTools endpoint is reachable");
+                return Ok(());
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(AdapterError::Timeout {
+                    seconds: CHROME_STARTUP_TIMEOUT_SECS,
+                    reason: "Chrome did not start in time".into(),
+                });
+            }
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        }
+    }
+
+    /// Find the Chrome/Chromium executable path.
+    fn find_chrome_path(&self) -> Result<String> {
+        if let Some(ref path) = self.chrome_path {
+            return Ok(path.clone());
+        }
+
+        // Platform-specific default paths.
+        let candidates = if cfg!(target_os = "macos") {
+            vec![
+                "/Applications/Google Chrome.app/Contents/MacOS/Google Chrome",
+                "/Applications/Chromium.app/Contents/MacOS/Chromium",
+                "/Applications/Microsoft Edge.app/Contents/MacOS/Microsoft Edge",
+            ]
+        } else if cfg!(target_os = "linux") {
+            vec![
+                "google-chrome",
+                "google-chrome-stable",
+                "chromium",
+                "chromium-browser",
+            ]
+        } else {
+            vec![]
+        };
+
+        for candidate in &candidates {
+            let path = std::path::Path::new(candidate);
+            if path.exists() || which_exists(candidate) {
+                return Ok((*candidate).to_string());
+            }
+        }
+
+        Err(AdapterError::ExecutionFailed {
+            tool_name: "connect".into(),
+            reason: "could not find Chrome/Chromium executable; set chrome_path manually".into(),
+        })
+    }
+}
+
+/// Check whether a command exists on the system PATH (non-blocking best-effort).
+fn which_exists(name: &str) -> bool {
+    std::process::Command::new("which")
+        .arg(name)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+/// Build a CDP message JSON object (useful for testing).
+pub fn build_cdp_message(id: u64, method: &str, params: Value) -> Value {
+    json!({
+        "id": id,
+        "method": method,
+        "params": params,
+    })
+}
+
+#[async_trait]
+impl Adapter for BrowserAdapter {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        AdapterType::Productivity
+    }
+
+    async fn connect(&mut self) -> Result<()> {
+        info!(
+            id = %self.id,
+            port = self.debug_port,
+            "connecting browser adapter"
+        );
+
+        if self.is_devtools_reachable().await {
+            info!("DevTools endpoint already reachable");
+            self.connected.store(true, Ordering::Release);
+            return Ok(());
+        }
+
+        // Try to launch Chrome.
+        self.try_launch_chrome().await?;
+        self.connected.store(true, Ordering::Release);
+        info!(id = %self.id, "browser adapter connected");
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        info!(id = %self.id, "browser adapter disconnected");
+        self.connected.store(false, Ordering::Release);
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        if !self.connected.load(Ordering::Acquire) {
+            return Ok(HealthStatus::Unhealthy);
+        }
+
+        // Verify DevTools is still reachable.
+        if self.is_devtools_reachable().await {
+            Ok(HealthStatus::Healthy)
+        } else {
+            warn!(id = %self.id, "DevTools endpoint unreachable during health check");
+            Ok(HealthStatus::Degraded)
+        }
+    }
+
+    fn tools(&self) -> Vec<ToolDefinition> {
+        vec![
+            ToolDefinition {
+                name: "browser_navigate".into(),
+                description: "Navigate the browser to a URL".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "url": {
+                            "type": "string",
+                            "description": "The URL to navigate to"
+                        }
+                    },
+                    "required": ["url"]
+                }),
+                destructive: true,
+            },
+            ToolDefinition {
+                name: "browser_get_page_content".into(),
+                description: "Get the current page's text content (innerText of body)".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {},
+                    "required": []
+                }),
+                destructive: false,
+            },
+            ToolDefinition {
+                name: "browser_screenshot".into(),
+                description: "Take a screenshot of the current page, one element, or the full \
+                              scrollable page (returns base64-encoded image)"
+                    .into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "format": {
+                            "type": "string",
+                            "description": "Image format: \"png\" (default) or \"jpeg\"",
+                            "enum": ["png", "jpeg"]
+                        },
+                        "selector": {
+                            "type": "string",
+                            "description": "CSS selector of a single element to capture"
+                        },
+                        "full_page": {
+                            "type": "boolean",
+                            "description": "Capture the full scrollable page instead of the viewport (default: false)"
+                        }
+                    },
+                    "required": []
+                }),
+                destructive: false,
+            },
+            ToolDefinition {
+                name: "browser_print_to_pdf".into(),
+                description: "Render the current page to PDF (returns base64-encoded PDF; \
+                              requires headless Chrome)"
+                    .into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "landscape": {
+                            "type": "boolean",
+                            "description": "Landscape orientation (default: false)"
+                        },
+                        "print_background": {
+                            "type": "boolean",
+                            "description": "Include background graphics (default: true)"
+                        },
+                        "scale": {
+                            "type": "number",
+                            "description": "Rendering scale between 0.1 and 2.0 (default: 1.0)"
+                        },
+                        "paper_width": {
+                            "type": "number",
+                            "description": "Paper width in inches (default: 8.5)"
+                        },
+                        "paper_height": {
+                            "type": "number",
+                            "description": "Paper height in inches (default: 11)"
+                        },
+                        "margin": {
+                            "type": "number",
+                            "description": "Margin on all sides in inches (default: ~0.4)"
+                        },
+                        "page_ranges": {
+                            "type": "string",
+                            "description": "Pages to print, e.g. \"1-5, 8\" (default: all)"
+                        }
+                    },
+                    "required": []
+                }),
+                destructive: false,
+            },
+            ToolDefinition {
+                name: "browser_click".into(),
+                description: "Click an element identified by CSS selector".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "selector": {
+                            "type": "string",
+                            "description": "CSS selector for the element to click"
+                        }
+                    },
+                    "required": ["selector"]
+                }),
+                destructive: true,
+            },
+            ToolDefinition {
+                name: "browser_type_text".into(),
+                description: "Type text into the currently focused element".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "text": {
+                            "type": "string",
+                            "description": "The text to type"
+                        }
+                    },
+                    "required": ["text"]
+                }),
+                destructive: true,
+            },
+            ToolDefinition {
+                name: "browser_evaluate".into(),
+                description: "Evaluate a JavaScript expression in the page context".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "expression": {
+                            "type": "string",
+                            "description": "The JavaScript expression to evaluate"
+                        }
+                    },
+                    "required": ["expression"]
+                }),
+                destructive: true,
+            },
+            ToolDefinition {
+                name: "browser_wait_for_selector".into(),
+                description: "Wait until an element matching a CSS selector appears in the page"
+                    .into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "selector": {
+                            "type": "string",
+                            "description": "CSS selector to wait for"
+                        },
+                        "timeout_ms": {
+                            "type": "integer",
+                            "description": "Maximum time to wait in milliseconds (default: 5000, max: 25000)"
+                        }
+                    },
+                    "required": ["selector"]
+                }),
+                destructive: false,
+            },
+            ToolDefinition {
+                name: "browser_get_text".into(),
+                description: "Get the rendered text of the first element matching a CSS selector"
+                    .into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "selector": {
+                            "type": "string",
+                            "description": "CSS selector of the element to read"
+                        }
+                    },
+                    "required": ["selector"]
+                }),
+                destructive: false,
+            },
+            ToolDefinition {
+                name: "browser_get_attribute".into(),
+                description: "Get an attribute of the first element matching a CSS selector"
+                    .into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "selector": {
+                            "type": "string",
+                            "description": "CSS selector of the element to read"
+                        },
+                        "attribute": {
+                            "type": "string",
+                            "description": "Attribute name, e.g. \"href\" or \"data-id\""
+                        }
+                    },
+                    "required": ["selector", "attribute"]
+                }),
+                destructive: false,
+            },
+            ToolDefinition {
+                name: "browser_eval_js".into(),
+                description: "Evaluate JavaScript with a time limit, awaiting promises and returning a typed result".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "expression": {
+                            "type": "string",
+                            "description": "The JavaScript expression to evaluate"
+                        },
+                        "timeout_ms": {
+                            "type": "integer",
+                            "description": "Maximum evaluation time in milliseconds (default: 5000, max: 25000)"
+                        }
+                    },
+                    "required": ["expression"]
+                }),
+                destructive: true,
+            },
+        ]
+    }
+
+    async fn execute_tool(&self, name: &str, params: Value) -> Result<Value> {
+        if !self.connected.load(Ordering::Acquire) {
+            return Err(AdapterError::NotConnected {
+                adapter_id: self.id.clone(),
+                reason: "call connect() first; Chrome may not be running".into(),
+            });
+        }
+        http_client::ensure_online()?;
+
+        match name {
+            "browser_navigate" => self.tool_browser_navigate(params).await,
+            "browser_get_page_content" => self.tool_browser_get_page_content(params).await,
+            "browser_screenshot" => self.tool_browser_screenshot(params).await,
+            "browser_print_to_pdf" => self.tool_browser_print_to_pdf(params).await,
+            "browser_click" => self.tool_browser_click(params).await,
+            "browser_type_text" => self.tool_browser_type_text(params).await,
+            "browser_evaluate" => self.tool_browser_evaluate(params).await,
+            "browser_wait_for_selector" => self.tool_browser_wait_for_selector(params).await,
+            "browser_get_text" => self.tool_browser_get_text(params).await,
+            "browser_get_attribute" => self.tool_browser_get_attribute(params).await,
+            "browser_eval_js" => self.tool_browser_eval_js(params).await,
+            _ => Err(AdapterError::ToolNotFound {
+                adapter_id: self.id.clone(),
+                tool_name: name.to_string(),
+            }),
+        }
+    }
+
+    fn required_auth(&self) -> Option<AuthRequirement> {
+        None
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn browser_adapter_new_default_port() {
+        let adapter = BrowserAdapter::new("test-browser");
+        assert_eq!(adapter.id(), "test-browser");
+        assert_eq!(adapter.debug_port, DEFAULT_DEBUG_PORT);
+        assert!(!adapter.connected.load(Ordering::Relaxed));
+        assert!(adapter.chrome_path.is_none());
+    }
+
+    #[test]
+    fn browser_adapter_with_custom_port() {
+        let adapter = BrowserAdapter::with_port("test-browser", 9333);
+        assert_eq!(adapter.debug_port, 9333);
+    }
+
+    #[test]
+    fn browser_adapter_with_chrome_path() {
+        let adapter = BrowserAdapter::new("test-browser").with_chrome_path("/usr/bin/chromium");
+        assert_eq!(adapter.chrome_path.as_deref(), Some("/usr/bin/chromium"));
+    }
+
+    #[test]
+    fn browser_adapter_type_is_productivity() {
+        let adapter = BrowserAdapter::new("test-browser");
+        assert_eq!(adapter.adapter_type(), AdapterType::Productivity);
+    }
+
+    #[test]
+    fn browser_adapter_no_auth_required() {
+        let adapter = BrowserAdapter::new("test-browser");
+        assert!(adapter.required_auth().is_none());
+    }
+
+    #[test]
+    fn browser_adapter_tools_count() {
+        let adapter = BrowserAdapter::new("test-browser");
+        let tools = adapter.tools();
+        assert_eq!(tools.len(), 11);
+    }
+
+    #[test]
+    fn browser_adapter_tool_names() {
+        let adapter = BrowserAdapter::new("test-browser");
+        let tools = adapter.tools();
+        let names: Vec<&str> = tools.iter().map(|t| t.name.as_str()).collect();
+        assert!(names.contains(&"browser_navigate"));
+        assert!(names.contains(&"browser_get_page_content"));
+        assert!(names.contains(&"browser_screenshot"));
+        assert!(names.contains(&"browser_print_to_pdf"));
+        assert!(names.contains(&"browser_click"));
+        assert!(names.contains(&"browser_type_text"));
+        assert!(names.contains(&"browser_evaluate"));
+        assert!(names.contains(&"browser_wait_for_selector"));
+        assert!(names.contains(&"browser_get_text"));
+        assert!(names.contains(&"browser_get_attribute"));
+        assert!(names.contains(&"browser_eval_js"));
+    }
+
+    #[test]
+    fn browser_adapter_tool_parameters_have_required_fields() {
+        let adapter = BrowserAdapter::new("test-browser");
+        let tools = adapter.tools();
+
+        // browser_navigate requires "url".
+        let nav = tools.iter().find(|t| t.name == "browser_navigate");
+        assert!(nav.is_some());
+        let nav = nav.expect("should exist in tests");
+        let required = nav.parameters.get("required").and_then(|v| v.as_array());
+        assert!(required.is_some());
+        assert!(
+            required
+                .expect("should exist in tests")
+                .contains(&json!("url"))
+        );
+
+        // browser_get_page_content has no required params.
+        let content = tools
+            .iter()
+            .find(|t| t.name == "browser_get_page_content")
+            .expect("should exist in tests");
+        let required = content
+            .parameters
+            .get("required")
+            .and_then(|v| v.as_array())
+            .expect("should exist in tests");
+        assert!(required.is_empty());
+
+        // browser_click requires "selector".
+        let click = tools
+            .iter()
+            .find(|t| t.name == "browser_click")
+            .expect("should exist in tests");
+        let required = click
+            .parameters
+            .get("required")
+            .and_then(|v| v.as_array())
+            .expect("should exist in tests");
+        assert!(required.contains(&json!("selector")));
+
+        // browser_type_text requires "text".
+        let type_text = tools
+            .iter()
+            .find(|t| t.name == "browser_type_text")
+            .expect("should exist in tests");
+        let required = type_text
+            .parameters
+            .get("required")
+            .and_then(|v| v.as_array())
+            .expect("should exist in tests");
+        assert!(required.contains(&json!("text")));
+
+        // browser_evaluate requires "expression".
+        let evaluate = tools
+            .iter()
+            .find(|t| t.name == "browser_evaluate")
+            .expect("should exist in tests");
+        let required = evaluate
+            .parameters
+            .get("required")
+            .and_then(|v| v.as_array())
+            .expect("should exist in tests");
+        assert!(required.contains(&json!("expression")));
+    }
+
+    #[tokio::test]
+    async fn browser_adapter_health_when_not_connected() {
+        let adapter = BrowserAdapter::new("test-browser");
+        let status = adapter.health_check().await;
+        assert!(status.is_ok());
+        assert_eq!(
+            status.expect("should be ok in tests"),
+            HealthStatus::Unhealthy
+        );
+    }
+
+    #[tokio::test]
+    async fn browser_adapter_rejects_tool_when_not_connected() {
+        let adapter = BrowserAdapter::new("test-browser");
+        let result = adapter
+            .execute_tool("browser_navigate", json!({"url": "https://example.com"}))
+            .await;
+        assert!(result.is_err());
+        let err = result.unwrap_err();
+        let err_str = err.to_string();
+        assert!(err_str.contains("not connected"));
+        assert!(matches!(err, AdapterError::NotConnected { .. }));
+    }
+
+    #[tokio::test]
+    async fn browser_adapter_rejects_unknown_tool() {
+        let adapter = BrowserAdapter::new("test-browser");
+        // Manually set connected to test tool dispatch.
+        adapter.connected.store(true, Ordering::Release);
+
+        let result = adapter.execute_tool("nonexistent_tool", json!({})).await;
+        assert!(result.is_err());
+        match result.unwrap_err() {
+            AdapterError::ToolNotFound {
+                adapter_id,
+                tool_name,
+            } => {
+                assert_eq!(adapter_id, "test-browser");
+                assert_eq!(tool_name, "nonexistent_tool");
+            }
+            other => panic!("expected ToolNotFound, got: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn browser_adapter_connect_disconnect() {
+        let mut adapter = BrowserAdapter::new("test-browser");
+        assert!(!adapter.connected.load(Ordering::Relaxed));
+
+        adapter
+            .disconnect()
+            .await
+            .expect("disconnect should succeed in tests");
+        assert!(!adapter.connected.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn devtools_base_url_default_port() {
+        let adapter = BrowserAdapter::new("test");
+        assert_eq!(adapter.devtools_base_url(), "http://localhost:9222");
+    }
+
+    #[test]
+    fn devtools_base_url_custom_port() {
+        let adapter = BrowserAdapter::with_port("test", 9333);
+        assert_eq!(adapter.devtools_base_url(), "http://localhost:9333");
+    }
+
+    #[test]
+    fn next_id_increments() {
+        let adapter = BrowserAdapter::new("test");
+        let id1 = adapter.next_id();
+        let id2 = adapter.next_id();
+        let id3 = adapter.next_id();
+        assert_eq!(id1 + 1, id2);
+        assert_eq!(id2 + 1, id3);
+    }
+}
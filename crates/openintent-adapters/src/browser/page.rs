@@ -0,0 +1,310 @@
+//! Page navigation, content, and interaction tools.
+
+use serde_json::{Value, json};
+use tracing::{debug, info};
+
+use super::BrowserAdapter;
+use super::cdp::extract_runtime_value;
+use crate::error::{AdapterError, Result};
+
+impl BrowserAdapter {
+    /// Navigate the browser to a URL.
+    pub(super) async fn tool_browser_navigate(&self, params: Value) -> Result<Value> {
+        let url_str = params.get("url").and_then(|v| v.as_str()).ok_or_else(|| {
+            AdapterError::InvalidParams {
+                tool_name: "browser_navigate".into(),
+                reason: "missing required string field `url`".into(),
+            }
+        })?;
+
+        // Validate URL.
+        let _parsed = url::Url::parse(url_str).map_err(|e| AdapterError::InvalidParams {
+            tool_name: "browser_navigate".into(),
+            reason: format!("invalid URL `{url_str}`: {e}"),
+        })?;
+
+        debug!(url = url_str, "navigating browser");
+
+        let result = self
+            .send_cdp_command("Page.navigate", json!({ "url": url_str }))
+            .await?;
+
+        // Extract frame ID and loader ID from response.
+        let frame_id = result
+            .get("frameId")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown");
+
+        info!(url = url_str, frame_id = frame_id, "navigation complete");
+
+        Ok(json!({
+            "success": true,
+            "url": url_str,
+            "frame_id": frame_id,
+        }))
+    }
+
+    /// Get the current page's text content.
+    pub(super) async fn tool_browser_get_page_content(&self, _params: Value) -> Result<Value> {
+        debug!("getting page content");
+
+        let expression = "document.body.innerText";
+        let result = self
+            .send_cdp_command(
+                "Runtime.evaluate",
+                json!({
+                    "expression": expression,
+                    "returnByValue": true,
+                }),
+            )
+            .await?;
+
+        let content = extract_runtime_value(&result)?;
+
+        Ok(json!({
+            "content": content,
+            "length": content.len(),
+        }))
+    }
+
+    /// Click an element identified by CSS selector.
+    pub(super) async fn tool_browser_click(&self, params: Value) -> Result<Value> {
+        let selector = params
+            .get("selector")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| AdapterError::InvalidParams {
+                tool_name: "browser_click".into(),
+                reason: "missing required string field `selector`".into(),
+            })?;
+
+        debug!(selector = selector, "clicking element");
+
+        // Use Runtime.evaluate to find and click the element.
+        let js = format!(
+            r#"(() => {{
+                const el = document.querySelector({selector});
+                if (!el) return JSON.stringify({{ error: "element not found", selector: {selector} }});
+                el.click();
+                return JSON.stringify({{ success: true, tag: el.tagName, selector: {selector} }});
+            }})()"#,
+            selector = serde_json::to_string(selector).map_err(AdapterError::from)?
+        );
+
+        let result = self
+            .send_cdp_command(
+                "Runtime.evaluate",
+                json!({
+                    "expression": js,
+                    "returnByValue": true,
+                }),
+            )
+            .await?;
+
+        let value_str = extract_runtime_value(&result)?;
+
+        // Parse the JSON returned by the script.
+        let click_result: Value =
+            serde_json::from_str(&value_str).unwrap_or_else(|_| json!({ "result": value_str }));
+
+        if click_result.get("error").is_some() {
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: "browser_click".into(),
+                reason: format!("element not found for selector `{selector}`"),
+            });
+        }
+
+        Ok(click_result)
+    }
+
+    /// Type text into the currently focused element.
+    pub(super) async fn tool_browser_type_text(&self, params: Value) -> Result<Value> {
+        let text = params.get("text").and_then(|v| v.as_str()).ok_or_else(|| {
+            AdapterError::InvalidParams {
+                tool_name: "browser_type_text".into(),
+                reason: "missing required string field `text`".into(),
+            }
+        })?;
+
+        debug!(text_length = text.len(), "typing text into focused element");
+
+        // Use Runtime.evaluate to set the value of the focused element and
+        // dispatch an input event for frameworks that listen to events.
+        let js = format!(
+            r#"(() => {{
+                const el = document.activeElement;
+                if (!el || el === document.body) {{
+                    return JSON.stringify({{ error: "no element focused" }});
+                }}
+                const text = {text};
+                if ('value' in el) {{
+                    el.value += text;
+                    el.dispatchEvent(new Event('input', {{ bubbles: true }}));
+                    el.dispatchEvent(new Event('change', {{ bubbles: true }}));
+                }} else {{
+                    el.textContent += text;
+                    el.dispatchEvent(new Event('input', {{ bubbles: true }}));
+                }}
+                return JSON.stringify({{ success: true, tag: el.tagName, typed_length: text.length }});
+            }})()"#,
+            text = serde_json::to_string(text).map_err(AdapterError::from)?
+        );
+
+        let result = self
+            .send_cdp_command(
+                "Runtime.evaluate",
+                json!({
+                    "expression": js,
+                    "returnByValue": true,
+                }),
+            )
+            .await?;
+
+        let value_str = extract_runtime_value(&result)?;
+        let type_result: Value =
+            serde_json::from_str(&value_str).unwrap_or_else(|_| json!({ "result": value_str }));
+
+        if type_result.get("error").is_some() {
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: "browser_type_text".into(),
+                reason: "no element focused to receive text input".into(),
+            });
+        }
+
+        Ok(type_result)
+    }
+
+    /// Evaluate arbitrary JavaScript in the page context.
+    pub(super) async fn tool_browser_evaluate(&self, params: Value) -> Result<Value> {
+        let expression = params
+            .get("expression")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| AdapterError::InvalidParams {
+                tool_name: "browser_evaluate".into(),
+                reason: "missing required string field `expression`".into(),
+            })?;
+
+        debug!(
+            expression_length = expression.len(),
+            "evaluating JavaScript"
+        );
+
+        let result = self
+            .send_cdp_command(
+                "Runtime.evaluate",
+                json!({
+                    "expression": expression,
+                    "returnByValue": true,
+                    "awaitPromise": true,
+                }),
+            )
+            .await?;
+
+        // Check for exception.
+        if let Some(exception) = result.get("exceptionDetails") {
+            let exception_text = exception
+                .get("text")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown exception");
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: "browser_evaluate".into(),
+                reason: format!("JavaScript exception: {exception_text}"),
+            });
+        }
+
+        let value = result.get("result").cloned().unwrap_or(json!(null));
+
+        Ok(json!({
+            "result": value,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use super::*;
+    use crate::traits::Adapter;
+
+    #[tokio::test]
+    async fn browser_adapter_navigate_validates_url() {
+        let adapter = BrowserAdapter::new("test-browser");
+        adapter.connected.store(true, Ordering::Release);
+
+        // Invalid URL should fail with InvalidParams, not a network error.
+        let result = adapter
+            .execute_tool("browser_navigate", json!({"url": "not a valid url"}))
+            .await;
+        assert!(result.is_err());
+        match result.unwrap_err() {
+            AdapterError::InvalidParams { tool_name, reason } => {
+                assert_eq!(tool_name, "browser_navigate");
+                assert!(reason.contains("invalid URL"));
+            }
+            other => panic!("expected InvalidParams, got: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn browser_adapter_navigate_requires_url_param() {
+        let adapter = BrowserAdapter::new("test-browser");
+        adapter.connected.store(true, Ordering::Release);
+
+        let result = adapter.execute_tool("browser_navigate", json!({})).await;
+        assert!(result.is_err());
+        match result.unwrap_err() {
+            AdapterError::InvalidParams { tool_name, .. } => {
+                assert_eq!(tool_name, "browser_navigate");
+            }
+            other => panic!("expected InvalidParams, got: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn browser_adapter_click_requires_selector_param() {
+        let adapter = BrowserAdapter::new("test-browser");
+        adapter.connected.store(true, Ordering::Release);
+
+        let result = adapter.execute_tool("browser_click", json!({})).await;
+        assert!(result.is_err());
+        match result.unwrap_err() {
+            AdapterError::InvalidParams { tool_name, reason } => {
+                assert_eq!(tool_name, "browser_click");
+                assert!(reason.contains("selector"));
+            }
+            other => panic!("expected InvalidParams, got: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn browser_adapter_type_text_requires_text_param() {
+        let adapter = BrowserAdapter::new("test-browser");
+        adapter.connected.store(true, Ordering::Release);
+
+        let result = adapter.execute_tool("browser_type_text", json!({})).await;
+        assert!(result.is_err());
+        match result.unwrap_err() {
+            AdapterError::InvalidParams { tool_name, reason } => {
+                assert_eq!(tool_name, "browser_type_text");
+                assert!(reason.contains("text"));
+            }
+            other => panic!("expected InvalidParams, got: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn browser_adapter_evaluate_requires_expression_param() {
+        let adapter = BrowserAdapter::new("test-browser");
+        adapter.connected.store(true, Ordering::Release);
+
+        let result = adapter.execute_tool("browser_evaluate", json!({})).await;
+        assert!(result.is_err());
+        match result.unwrap_err() {
+            AdapterError::InvalidParams { tool_name, reason } => {
+                assert_eq!(tool_name, "browser_evaluate");
+                assert!(reason.contains("expression"));
+            }
+            other => panic!("expected InvalidParams, got: {other:?}"),
+        }
+    }
+}
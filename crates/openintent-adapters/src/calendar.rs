@@ -12,6 +12,7 @@ use tracing::{debug, info};
 use uuid::Uuid;
 
 use crate::error::{AdapterError, Result};
+use crate::http_client::{self, HttpClientFactory};
 use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
 
 /// Default number of days ahead to look for events.
@@ -39,10 +40,7 @@ pub struct CalendarAdapter {
 impl CalendarAdapter {
     /// Create a new calendar adapter with default configuration.
     pub fn new(id: impl Into<String>) -> Self {
-        let client = reqwest::Client::builder()
-            .user_agent("OpenIntentOS/0.1")
-            .build()
-            .unwrap_or_default();
+        let client = HttpClientFactory::default().build().unwrap_or_default();
 
         Self {
             id: id.into(),
@@ -631,6 +629,7 @@ impl Adapter for CalendarAdapter {
                     },
                     "required": []
                 }),
+                destructive: false,
             },
             ToolDefinition {
                 name: "calendar_create_event".into(),
@@ -661,6 +660,7 @@ impl Adapter for CalendarAdapter {
                     },
                     "required": ["summary", "start", "end"]
                 }),
+                destructive: true,
             },
             ToolDefinition {
                 name: "calendar_delete_event".into(),
@@ -675,6 +675,7 @@ impl Adapter for CalendarAdapter {
                     },
                     "required": ["uid"]
                 }),
+                destructive: true,
             },
             ToolDefinition {
                 name: "calendar_search_events".into(),
@@ -693,6 +694,7 @@ impl Adapter for CalendarAdapter {
                     },
                     "required": ["query"]
                 }),
+                destructive: false,
             },
             ToolDefinition {
                 name: "calendar_get_event".into(),
@@ -707,6 +709,7 @@ impl Adapter for CalendarAdapter {
                     },
                     "required": ["uid"]
                 }),
+                destructive: false,
             },
         ]
     }
@@ -718,6 +721,7 @@ impl Adapter for CalendarAdapter {
                 reason: format!("adapter `{}` is not connected", self.id),
             });
         }
+        http_client::ensure_online()?;
 
         match name {
             "calendar_list_events" => self.tool_list_events(params).await,
@@ -0,0 +1,287 @@
+//! Retries and circuit breaking for adapter calls.
+//!
+//! A dead upstream makes every call to its adapter wait for a timeout before
+//! failing.  [`CircuitBreakerAdapter`] wraps an adapter and reports each tool
+//! call to a kernel [`AdapterRegistry`] built with
+//! [`AdapterRegistry::with_circuit_breaker`].  Once the registry opens the
+//! adapter's circuit, calls fail immediately with
+//! [`AdapterError::CircuitOpen`] until the cooldown has passed and a probe
+//! call succeeds.
+//!
+//! Only upstream and connection failures count against the breaker; bad
+//! parameters and missing credentials are the caller's problem.  Those same
+//! failures can optionally be retried, but only for tools that are not
+//! mutating (see [`Adapter::is_mutating`]), so a side effect is never
+//! repeated.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde_json::Value;
+use tracing::warn;
+
+use openintent_agent::{ErrorCategory, PromptFn};
+use openintent_kernel::{AdapterRegistry, KernelError};
+
+use crate::error::{AdapterError, Result};
+use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
+
+/// Adapter wrapper that retries transient failures and honours the
+/// registry's circuit breaker.
+pub struct CircuitBreakerAdapter {
+    /// The wrapped adapter.
+    inner: Box<dyn Adapter>,
+    /// Registry holding the breaker state for `inner`.
+    registry: AdapterRegistry,
+    /// Extra attempts for failed calls to non-mutating tools.
+    retries: u32,
+    /// Base delay before a retry; the n-th retry waits `n * backoff`.
+    backoff: Duration,
+}
+
+impl CircuitBreakerAdapter {
+    /// Wrap `inner`, registering it in `registry` if it is not there yet.
+    pub fn new(inner: Box<dyn Adapter>, registry: AdapterRegistry) -> Self {
+        if registry.get(inner.id()).is_err() {
+            registry.register(inner.id(), format!("{} adapter", inner.adapter_type()));
+        }
+        Self {
+            inner,
+            registry,
+            retries: 0,
+            backoff: Duration::from_millis(500),
+        }
+    }
+
+    /// Retry failed calls to non-mutating tools up to `retries` times.
+    pub fn with_retries(mut self, retries: u32, backoff: Duration) -> Self {
+        self.retries = retries;
+        self.backoff = backoff;
+        self
+    }
+
+    /// The wrapped adapter.
+    pub fn inner(&self) -> &dyn Adapter {
+        self.inner.as_ref()
+    }
+
+    /// Ask the breaker whether a call may go through.
+    fn admit(&self) -> Result<()> {
+        match self.registry.begin_call(self.inner.id()) {
+            Err(KernelError::CircuitOpen {
+                adapter_id,
+                retry_after_secs,
+            }) => Err(AdapterError::CircuitOpen {
+                adapter_id,
+                retry_after_secs,
+            }),
+            // An adapter removed from the registry is no longer tracked.
+            Ok(()) | Err(_) => Ok(()),
+        }
+    }
+}
+
+/// Whether `err` suggests the backing service is unhealthy.
+fn is_service_failure(err: &AdapterError) -> bool {
+    matches!(
+        err.category(),
+        ErrorCategory::Upstream | ErrorCategory::NotConnected
+    )
+}
+
+#[async_trait]
+impl Adapter for CircuitBreakerAdapter {
+    fn id(&self) -> &str {
+        self.inner.id()
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        self.inner.adapter_type()
+    }
+
+    async fn connect(&mut self) -> Result<()> {
+        self.inner.connect().await
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        self.inner.disconnect().await
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        self.inner.health_check().await
+    }
+
+    fn tools(&self) -> Vec<ToolDefinition> {
+        self.inner.tools()
+    }
+
+    async fn execute_tool(&self, name: &str, params: Value) -> Result<Value> {
+        self.execute_tool_with_prompt(name, params, None).await
+    }
+
+    async fn execute_tool_with_prompt(
+        &self,
+        name: &str,
+        params: Value,
+        prompt: Option<PromptFn>,
+    ) -> Result<Value> {
+        let retries = if self.inner.is_mutating(name) {
+            0
+        } else {
+            self.retries
+        };
+        let mut attempt = 0;
+        loop {
+            self.admit()?;
+            let result = self
+                .inner
+                .execute_tool_with_prompt(name, params.clone(), prompt.clone())
+                .await;
+            let failed = result.as_ref().is_err_and(is_service_failure);
+            let _ = self.registry.record_call(self.inner.id(), !failed);
+
+            match result {
+                Err(e) if failed && attempt < retries => {
+                    attempt += 1;
+                    warn!(
+                        adapter = self.inner.id(),
+                        tool = name,
+                        attempt,
+                        error = %e,
+                        "tool call failed, retrying"
+                    );
+                    tokio::time::sleep(self.backoff * attempt).await;
+                }
+                other => return other,
+            }
+        }
+    }
+
+    fn required_auth(&self) -> Option<AuthRequirement> {
+        self.inner.required_auth()
+    }
+
+    fn has_credentials(&self) -> bool {
+        self.inner.has_credentials()
+    }
+
+    fn is_mutating(&self, tool_name: &str) -> bool {
+        self.inner.is_mutating(tool_name)
+    }
+
+    fn redact_params(&self, tool_name: &str, params: &Value) -> Value {
+        self.inner.redact_params(tool_name, params)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openintent_kernel::{CircuitBreakerConfig, CircuitState};
+    use serde_json::json;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Adapter whose `fetch` tool fails its first `failures` calls.
+    struct FlakyAdapter {
+        calls: Arc<AtomicUsize>,
+        failures: usize,
+    }
+
+    #[async_trait]
+    impl Adapter for FlakyAdapter {
+        fn id(&self) -> &str {
+            "flaky"
+        }
+
+        fn adapter_type(&self) -> AdapterType {
+            AdapterType::DevTools
+        }
+
+        async fn connect(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn disconnect(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn health_check(&self) -> Result<HealthStatus> {
+            Ok(HealthStatus::Healthy)
+        }
+
+        fn tools(&self) -> Vec<ToolDefinition> {
+            Vec::new()
+        }
+
+        async fn execute_tool(&self, name: &str, _params: Value) -> Result<Value> {
+            let n = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if n <= self.failures {
+                return Err(AdapterError::ExecutionFailed {
+                    tool_name: name.to_string(),
+                    reason: "upstream unavailable".into(),
+                });
+            }
+            Ok(json!({ "call": n }))
+        }
+
+        fn required_auth(&self) -> Option<AuthRequirement> {
+            None
+        }
+    }
+
+    fn setup(failures: usize) -> (CircuitBreakerAdapter, AdapterRegistry, Arc<AtomicUsize>) {
+        let registry = AdapterRegistry::new().with_circuit_breaker(CircuitBreakerConfig {
+            failure_threshold: 2,
+            cooldown: Duration::from_millis(50),
+        });
+        let calls = Arc::new(AtomicUsize::new(0));
+        let adapter = CircuitBreakerAdapter::new(
+            Box::new(FlakyAdapter {
+                calls: calls.clone(),
+                failures,
+            }),
+            registry.clone(),
+        );
+        (adapter, registry, calls)
+    }
+
+    fn state(registry: &AdapterRegistry) -> CircuitState {
+        registry.get("flaky").unwrap().breaker.unwrap().state
+    }
+
+    #[tokio::test]
+    async fn breaker_trips_and_recovers() {
+        let (adapter, registry, calls) = setup(2);
+
+        for _ in 0..2 {
+            let err = adapter.execute_tool("fetch", json!({})).await.unwrap_err();
+            assert!(matches!(err, AdapterError::ExecutionFailed { .. }));
+        }
+        assert_eq!(state(&registry), CircuitState::Open);
+
+        let err = adapter.execute_tool("fetch", json!({})).await.unwrap_err();
+        assert!(matches!(err, AdapterError::CircuitOpen { .. }));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        let result = adapter.execute_tool("fetch", json!({})).await.unwrap();
+        assert_eq!(result["call"], 3);
+        assert_eq!(state(&registry), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn retries_recover_without_tripping() {
+        let (adapter, registry, calls) = setup(1);
+        let adapter = adapter.with_retries(2, Duration::ZERO);
+
+        let result = adapter.execute_tool("fetch", json!({})).await.unwrap();
+        assert_eq!(result["call"], 2);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(state(&registry), CircuitState::Closed);
+    }
+}
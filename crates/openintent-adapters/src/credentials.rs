@@ -0,0 +1,32 @@
+//! Credential checks shared by the adapters' [`Adapter::has_credentials`].
+//!
+//! A secret counts as configured only when it is present and not blank, so
+//! an empty `TELEGRAM_BOT_TOKEN=` or config value is reported as missing up
+//! front instead of failing on the first API call.
+//!
+//! [`Adapter::has_credentials`]: crate::traits::Adapter::has_credentials
+
+/// Whether `secret` is present and not blank.
+pub(crate) fn is_set(secret: Option<&str>) -> bool {
+    secret.is_some_and(|s| !s.trim().is_empty())
+}
+
+/// Whether every one of `secrets` is set.
+pub(crate) fn all_set(secrets: &[Option<&str>]) -> bool {
+    secrets.iter().all(|secret| is_set(*secret))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blank_secrets_are_not_set() {
+        assert!(is_set(Some("123:abc")));
+        assert!(!is_set(Some("  ")));
+        assert!(!is_set(None));
+
+        assert!(all_set(&[Some("id"), Some("secret")]));
+        assert!(!all_set(&[Some("id"), Some("")]));
+    }
+}
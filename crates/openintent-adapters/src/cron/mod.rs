@@ -0,0 +1,612 @@
+//! Cron adapter -- schedule, list, delete, and toggle recurring jobs.
+//!
+//! Jobs live in an in-memory registry guarded by [`RwLock<HashMap>`].  When
+//! a [`CronJobStore`] is attached via [`CronAdapter::with_store`], every
+//! change is written through to SQLite and the registry is reloaded on
+//! [`connect`](Adapter::connect), so schedules survive a restart.
+//!
+//! The adapter only keeps the schedule; a runner polls [`CronAdapter::due_jobs`]
+//! and reports executions back with [`CronAdapter::mark_run`].
+//!
+//! **Tools:**
+//! - `cron_create` -- register a new cron job.
+//! - `cron_list` -- list all registered jobs with their next run time.
+//! - `cron_delete` -- remove a job by ID.
+//! - `cron_toggle` -- enable or disable a job.
+//! - `cron_pause` / `cron_resume` -- shorthands for toggling a job.
+
+mod schedule;
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use openintent_store::{CronJobStore, StoredCronJob};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::error::{AdapterError, Result};
+use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
+
+// ---------------------------------------------------------------------------
+// Cron job model
+// ---------------------------------------------------------------------------
+
+/// A scheduled recurring job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CronJob {
+    /// Unique job identifier.
+    pub id: String,
+    /// Human-readable name for the job.
+    pub name: String,
+    /// Cron expression (e.g. `"0 */5 * * *"`).
+    pub schedule: String,
+    /// IANA timezone whose wall clock the schedule follows (e.g.
+    /// `"America/New_York"`).
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+    /// The command or intent to execute when the job fires.
+    pub command: String,
+    /// Whether the job is active.
+    pub enabled: bool,
+    /// Unix epoch timestamp when the job was created.
+    pub created_at: i64,
+    /// Unix epoch timestamp of the most recent execution, if any.
+    pub last_run: Option<i64>,
+    /// Unix epoch timestamp of the next planned execution, if known.
+    pub next_run: Option<i64>,
+}
+
+impl CronJob {
+    /// The next planned execution as a UTC datetime.
+    pub fn next_run_at(&self) -> Option<DateTime<Utc>> {
+        self.next_run
+            .and_then(|t| DateTime::<Utc>::from_timestamp(t, 0))
+    }
+
+    /// JSON representation returned by the tools.
+    fn to_json(&self) -> Value {
+        json!({
+            "id": self.id,
+            "name": self.name,
+            "schedule": self.schedule,
+            "timezone": self.timezone,
+            "command": self.command,
+            "enabled": self.enabled,
+            "last_run": self.last_run,
+            "next_run": self.next_run,
+            "next_run_at": self.next_run_at().map(|t| t.to_rfc3339()),
+        })
+    }
+}
+
+/// Serde default for jobs serialized before timezones were supported.
+fn default_timezone() -> String {
+    schedule::DEFAULT_TIMEZONE.to_string()
+}
+
+impl From<StoredCronJob> for CronJob {
+    fn from(job: StoredCronJob) -> Self {
+        Self {
+            id: job.id,
+            name: job.name,
+            schedule: job.schedule,
+            timezone: job.timezone,
+            command: job.command,
+            enabled: job.enabled,
+            created_at: job.created_at,
+            last_run: job.last_run,
+            next_run: job.next_run,
+        }
+    }
+}
+
+impl From<CronJob> for StoredCronJob {
+    fn from(job: CronJob) -> Self {
+        Self {
+            id: job.id,
+            name: job.name,
+            schedule: job.schedule,
+            timezone: job.timezone,
+            command: job.command,
+            enabled: job.enabled,
+            created_at: job.created_at,
+            last_run: job.last_run,
+            next_run: job.next_run,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Adapter
+// ---------------------------------------------------------------------------
+
+/// Cron scheduling adapter backed by an in-memory registry, optionally
+/// persisted to SQLite.
+pub struct CronAdapter {
+    /// Unique adapter instance identifier.
+    id: String,
+    /// Whether the adapter has been connected (initialised).
+    connected: bool,
+    /// In-memory job registry.
+    jobs: Arc<RwLock<HashMap<String, CronJob>>>,
+    /// Write-through persistence; `None` keeps jobs in memory only.
+    store: Option<CronJobStore>,
+    /// Whether runs missed while offline fire once on startup.
+    catch_up_missed: bool,
+}
+
+impl CronAdapter {
+    /// Create a new cron adapter.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            connected: false,
+            jobs: Arc::new(RwLock::new(HashMap::new())),
+            store: None,
+            catch_up_missed: false,
+        }
+    }
+
+    /// Persist jobs to `store` and reload them on connect.
+    pub fn with_store(mut self, store: CronJobStore) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Make runs missed while the process was down fire once on startup
+    /// instead of being skipped.  Defaults to `false`.
+    pub fn with_catch_up_missed(mut self, catch_up: bool) -> Self {
+        self.catch_up_missed = catch_up;
+        self
+    }
+
+    /// All registered jobs in creation order.
+    pub fn list_jobs(&self) -> Result<Vec<CronJob>> {
+        let jobs = self.jobs.read().map_err(|e| {
+            AdapterError::Internal(format!("failed to acquire read lock on cron jobs: {e}"))
+        })?;
+        let mut list: Vec<CronJob> = jobs.values().cloned().collect();
+        list.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+        Ok(list)
+    }
+
+    /// The next planned execution of a job, or `None` if it is paused.
+    pub fn next_run_at(&self, job_id: &str) -> Result<Option<DateTime<Utc>>> {
+        let jobs = self.jobs.read().map_err(|e| {
+            AdapterError::Internal(format!("failed to acquire read lock on cron jobs: {e}"))
+        })?;
+        jobs.get(job_id)
+            .map(CronJob::next_run_at)
+            .ok_or_else(|| Self::not_found("cron_list", job_id))
+    }
+
+    /// Pause a job so it no longer fires.
+    pub async fn pause(&self, job_id: &str) -> Result<CronJob> {
+        self.set_enabled(job_id, false, "cron_pause").await
+    }
+
+    /// Resume a paused job; its next run is computed from now.
+    pub async fn resume(&self, job_id: &str) -> Result<CronJob> {
+        self.set_enabled(job_id, true, "cron_resume").await
+    }
+
+    /// Enabled jobs whose next run is at or before `now`.
+    pub fn due_jobs(&self, now: DateTime<Utc>) -> Result<Vec<CronJob>> {
+        let now = now.timestamp();
+        Ok(self
+            .list_jobs()?
+            .into_iter()
+            .filter(|job| job.enabled && job.next_run.is_some_and(|t| t <= now))
+            .collect())
+    }
+
+    /// Record that a job ran at `at` and schedule its next run.
+    pub async fn mark_run(&self, job_id: &str, at: DateTime<Utc>) -> Result<CronJob> {
+        let job = {
+            let mut jobs = self.jobs.write().map_err(|e| {
+                AdapterError::Internal(format!("failed to acquire write lock on cron jobs: {e}"))
+            })?;
+            let job = jobs
+                .get_mut(job_id)
+                .ok_or_else(|| Self::not_found("cron_mark_run", job_id))?;
+            job.last_run = Some(at.timestamp());
+            job.next_run = schedule::next_run_after(job, at);
+            job.clone()
+        };
+
+        if let Some(store) = &self.store {
+            store
+                .update_runs(&job.id, job.last_run, job.next_run)
+                .await
+                .map_err(|e| Self::store_error("cron_mark_run", e))?;
+        }
+        Ok(job)
+    }
+
+    /// Reload persisted jobs, reconciling runs missed while offline.
+    async fn load_from_store(&self) -> Result<()> {
+        let Some(store) = &self.store else {
+            return Ok(());
+        };
+
+        let stored = store
+            .list()
+            .await
+            .map_err(|e| AdapterError::Internal(format!("failed to load cron jobs: {e}")))?;
+        let now = Utc::now();
+
+        let mut loaded = HashMap::with_capacity(stored.len());
+        for job in stored {
+            let mut job = CronJob::from(job);
+            let previous_next_run = job.next_run;
+            if schedule::reconcile_on_startup(&mut job, now, self.catch_up_missed) {
+                info!(
+                    job_id = %job.id,
+                    catch_up = self.catch_up_missed,
+                    "cron job missed a run while offline"
+                );
+            }
+            if job.enabled && job.next_run.is_none() {
+                warn!(job_id = %job.id, schedule = %job.schedule, "cron job has no upcoming run");
+            }
+            if job.next_run != previous_next_run {
+                store
+                    .update_runs(&job.id, job.last_run, job.next_run)
+                    .await
+                    .map_err(|e| {
+                        AdapterError::Internal(format!("failed to update cron job: {e}"))
+                    })?;
+            }
+            loaded.insert(job.id.clone(), job);
+        }
+
+        info!(id = %self.id, count = loaded.len(), "cron jobs restored");
+        let mut jobs = self.jobs.write().map_err(|e| {
+            AdapterError::Internal(format!("failed to acquire write lock on cron jobs: {e}"))
+        })?;
+        *jobs = loaded;
+        Ok(())
+    }
+
+    /// Enable or disable a job and persist the change.
+    async fn set_enabled(&self, job_id: &str, enabled: bool, tool_name: &str) -> Result<CronJob> {
+        let job = {
+            let mut jobs = self.jobs.write().map_err(|e| {
+                AdapterError::Internal(format!("failed to acquire write lock on cron jobs: {e}"))
+            })?;
+            let job = jobs
+                .get_mut(job_id)
+                .ok_or_else(|| Self::not_found(tool_name, job_id))?;
+            job.enabled = enabled;
+            job.next_run = if enabled {
+                schedule::next_run_after(job, Utc::now())
+            } else {
+                None
+            };
+            job.clone()
+        };
+
+        if let Some(store) = &self.store {
+            store
+                .save(&job.clone().into())
+                .await
+                .map_err(|e| Self::store_error(tool_name, e))?;
+        }
+        Ok(job)
+    }
+
+    /// Error for an unknown job ID.
+    fn not_found(tool_name: &str, job_id: &str) -> AdapterError {
+        AdapterError::ExecutionFailed {
+            tool_name: tool_name.to_string(),
+            reason: format!("cron job `{job_id}` not found"),
+        }
+    }
+
+    /// Error for a failed write-through to the store.
+    fn store_error(tool_name: &str, e: openintent_store::StoreError) -> AdapterError {
+        AdapterError::ExecutionFailed {
+            tool_name: tool_name.to_string(),
+            reason: format!("failed to persist cron job: {e}"),
+        }
+    }
+
+    /// Extract a required string field from JSON params.
+    fn require_str<'a>(params: &'a Value, field: &str, tool_name: &str) -> Result<&'a str> {
+        params
+            .get(field)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| AdapterError::InvalidParams {
+                tool_name: tool_name.to_string(),
+                reason: format!("missing required string field `{field}`"),
+            })
+    }
+
+    // -- Tool implementations ------------------------------------------------
+
+    /// Create a new cron job.
+    async fn tool_cron_create(&self, params: Value) -> Result<Value> {
+        let name = Self::require_str(&params, "name", "cron_create")?;
+        let schedule = Self::require_str(&params, "schedule", "cron_create")?;
+        let command = Self::require_str(&params, "command", "cron_create")?;
+        let timezone = params
+            .get("timezone")
+            .and_then(|v| v.as_str())
+            .unwrap_or(schedule::DEFAULT_TIMEZONE);
+
+        schedule::parse_schedule(schedule, "cron_create")?;
+        let timezone = schedule::parse_timezone(timezone, "cron_create")?.name();
+
+        let job_id = Uuid::now_v7().to_string();
+        let now = Utc::now();
+
+        let mut job = CronJob {
+            id: job_id.clone(),
+            name: name.to_string(),
+            schedule: schedule.to_string(),
+            timezone: timezone.to_string(),
+            command: command.to_string(),
+            enabled: true,
+            created_at: now.timestamp(),
+            last_run: None,
+            next_run: None,
+        };
+        job.next_run = schedule::next_run_after(&job, now);
+
+        debug!(job_id = %job_id, name, schedule, timezone, "creating cron job");
+
+        if let Some(store) = &self.store {
+            store
+                .save(&job.clone().into())
+                .await
+                .map_err(|e| Self::store_error("cron_create", e))?;
+        }
+
+        let next_run_at = job.next_run_at().map(|t| t.to_rfc3339());
+        let mut jobs = self.jobs.write().map_err(|e| {
+            AdapterError::Internal(format!("failed to acquire write lock on cron jobs: {e}"))
+        })?;
+        jobs.insert(job_id.clone(), job);
+
+        Ok(json!({ "id": job_id, "created": true, "next_run_at": next_run_at }))
+    }
+
+    /// List all registered cron jobs.
+    fn tool_cron_list(&self) -> Result<Value> {
+        let job_list: Vec<Value> = self.list_jobs()?.iter().map(CronJob::to_json).collect();
+        Ok(json!({ "jobs": job_list }))
+    }
+
+    /// Delete a cron job by ID.
+    async fn tool_cron_delete(&self, params: Value) -> Result<Value> {
+        let job_id = Self::require_str(&params, "id", "cron_delete")?;
+
+        debug!(job_id, "deleting cron job");
+
+        let removed = {
+            let mut jobs = self.jobs.write().map_err(|e| {
+                AdapterError::Internal(format!("failed to acquire write lock on cron jobs: {e}"))
+            })?;
+            jobs.remove(job_id)
+        };
+
+        if removed.is_none() {
+            warn!(job_id, "attempted to delete non-existent cron job");
+            return Err(Self::not_found("cron_delete", job_id));
+        }
+
+        if let Some(store) = &self.store {
+            store
+                .delete(job_id)
+                .await
+                .map_err(|e| Self::store_error("cron_delete", e))?;
+        }
+
+        Ok(json!({ "deleted": true }))
+    }
+
+    /// Enable or disable a cron job.
+    async fn tool_cron_toggle(&self, params: Value) -> Result<Value> {
+        let job_id = Self::require_str(&params, "id", "cron_toggle")?;
+
+        let enabled = params
+            .get("enabled")
+            .and_then(|v| v.as_bool())
+            .ok_or_else(|| AdapterError::InvalidParams {
+                tool_name: "cron_toggle".to_string(),
+                reason: "missing required boolean field `enabled`".to_string(),
+            })?;
+
+        debug!(job_id, enabled, "toggling cron job");
+
+        let job = self.set_enabled(job_id, enabled, "cron_toggle").await?;
+
+        Ok(json!({
+            "id": job_id,
+            "enabled": enabled,
+            "next_run_at": job.next_run_at().map(|t| t.to_rfc3339()),
+        }))
+    }
+
+    /// Pause or resume a cron job.
+    async fn tool_cron_pause_resume(&self, params: Value, tool_name: &str) -> Result<Value> {
+        let job_id = Self::require_str(&params, "id", tool_name)?;
+        let job = if tool_name == "cron_pause" {
+            self.pause(job_id).await?
+        } else {
+            self.resume(job_id).await?
+        };
+        Ok(job.to_json())
+    }
+}
+
+#[async_trait]
+impl Adapter for CronAdapter {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        AdapterType::System
+    }
+
+    async fn connect(&mut self) -> Result<()> {
+        self.load_from_store().await?;
+        info!(id = %self.id, "cron adapter connected");
+        self.connected = true;
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        info!(id = %self.id, "cron adapter disconnected");
+        self.connected = false;
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        if !self.connected {
+            return Ok(HealthStatus::Unhealthy);
+        }
+        Ok(HealthStatus::Healthy)
+    }
+
+    fn tools(&self) -> Vec<ToolDefinition> {
+        vec![
+            ToolDefinition {
+                name: "cron_create".into(),
+                description: "Create a new recurring cron job".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "name": {
+                            "type": "string",
+                            "description": "Human-readable name for the job"
+                        },
+                        "schedule": {
+                            "type": "string",
+                            "description": "Cron expression (e.g. '0 */5 * * *')"
+                        },
+                        "timezone": {
+                            "type": "string",
+                            "description": "IANA timezone the schedule follows, e.g. 'Europe/Berlin' (default: UTC)"
+                        },
+                        "command": {
+                            "type": "string",
+                            "description": "The command or intent to execute"
+                        }
+                    },
+                    "required": ["name", "schedule", "command"]
+                }),
+                destructive: true,
+            },
+            ToolDefinition {
+                name: "cron_list".into(),
+                description: "List all registered cron jobs".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {}
+                }),
+                destructive: false,
+            },
+            ToolDefinition {
+                name: "cron_delete".into(),
+                description: "Delete a cron job by ID".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "id": {
+                            "type": "string",
+                            "description": "The ID of the cron job to delete"
+                        }
+                    },
+                    "required": ["id"]
+                }),
+                destructive: true,
+            },
+            ToolDefinition {
+                name: "cron_toggle".into(),
+                description: "Enable or disable a cron job".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "id": {
+                            "type": "string",
+                            "description": "The ID of the cron job to toggle"
+                        },
+                        "enabled": {
+                            "type": "boolean",
+                            "description": "Whether to enable (true) or disable (false) the job"
+                        }
+                    },
+                    "required": ["id", "enabled"]
+                }),
+                destructive: true,
+            },
+            ToolDefinition {
+                name: "cron_pause".into(),
+                description: "Pause a cron job so it stops firing".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "id": {
+                            "type": "string",
+                            "description": "The ID of the cron job to pause"
+                        }
+                    },
+                    "required": ["id"]
+                }),
+                destructive: true,
+            },
+            ToolDefinition {
+                name: "cron_resume".into(),
+                description: "Resume a paused cron job".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "id": {
+                            "type": "string",
+                            "description": "The ID of the cron job to resume"
+                        }
+                    },
+                    "required": ["id"]
+                }),
+                destructive: true,
+            },
+        ]
+    }
+
+    async fn execute_tool(&self, name: &str, params: Value) -> Result<Value> {
+        if !self.connected {
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: name.to_string(),
+                reason: format!("adapter `{}` is not connected", self.id),
+            });
+        }
+        match name {
+            "cron_create" => self.tool_cron_create(params).await,
+            "cron_list" => self.tool_cron_list(),
+            "cron_delete" => self.tool_cron_delete(params).await,
+            "cron_toggle" => self.tool_cron_toggle(params).await,
+            "cron_pause" | "cron_resume" => self.tool_cron_pause_resume(params, name).await,
+            _ => Err(AdapterError::ToolNotFound {
+                adapter_id: self.id.clone(),
+                tool_name: name.to_string(),
+            }),
+        }
+    }
+
+    fn required_auth(&self) -> Option<AuthRequirement> {
+        None
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
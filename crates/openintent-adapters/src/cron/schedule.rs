@@ -0,0 +1,267 @@
+//! Cron expression parsing and next-run computation.
+//!
+//! Schedules are evaluated against the wall clock of the job's IANA
+//! timezone.  Around DST transitions a job fires once per matching wall-clock
+//! time: a time skipped by a spring-forward gap runs at the first valid
+//! minute after the gap, and a time repeated by a fall-back runs only on its
+//! first occurrence.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, LocalResult, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use super::CronJob;
+use crate::error::{AdapterError, Result};
+
+/// Timezone used when a job does not specify one.
+pub(super) const DEFAULT_TIMEZONE: &str = "UTC";
+
+/// Upper bound on schedule candidates inspected per lookup; only DST edge
+/// cases ever need more than one.
+const MAX_CANDIDATES: usize = 8;
+
+/// Longest DST gap searched for the first valid wall-clock minute.
+const MAX_GAP_MINUTES: i64 = 180;
+
+/// Normalize a cron expression to the 6/7-field format expected by the
+/// `cron` crate.  A standard 5-field expression gets `0` prepended as the
+/// seconds field.
+fn normalize_cron_expr(expr: &str) -> String {
+    if expr.split_whitespace().count() == 5 {
+        format!("0 {expr}")
+    } else {
+        expr.to_string()
+    }
+}
+
+/// Parse a cron expression, reporting failures against `tool_name`.
+pub(super) fn parse_schedule(expr: &str, tool_name: &str) -> Result<cron::Schedule> {
+    cron::Schedule::from_str(&normalize_cron_expr(expr)).map_err(|e| AdapterError::InvalidParams {
+        tool_name: tool_name.to_string(),
+        reason: format!("invalid cron expression `{expr}`: {e}"),
+    })
+}
+
+/// Parse an IANA timezone name, reporting failures against `tool_name`.
+pub(super) fn parse_timezone(name: &str, tool_name: &str) -> Result<Tz> {
+    Tz::from_str(name).map_err(|e| AdapterError::InvalidParams {
+        tool_name: tool_name.to_string(),
+        reason: format!("invalid timezone `{name}`: {e}"),
+    })
+}
+
+/// The first run of `job` strictly after `after`, as a Unix timestamp.
+///
+/// Returns `None` for unparseable schedules or timezones and for schedules
+/// with no future occurrence.
+pub(super) fn next_run_after(job: &CronJob, after: DateTime<Utc>) -> Option<i64> {
+    let schedule = parse_schedule(&job.schedule, "cron").ok()?;
+    let tz = parse_timezone(&job.timezone, "cron").ok()?;
+
+    // Walk the schedule over local wall-clock times.  UTC stands in as a
+    // zone without DST so the cron crate sees every wall-clock minute once.
+    let local_after = Utc.from_utc_datetime(&after.with_timezone(&tz).naive_local());
+
+    schedule
+        .after(&local_after)
+        .take(MAX_CANDIDATES)
+        .filter_map(|wall| {
+            let wall = wall.naive_utc();
+            match tz.from_local_datetime(&wall) {
+                LocalResult::Single(t) => Some(t),
+                // Repeated wall-clock time: fire on the first pass only.
+                LocalResult::Ambiguous(first, _) => Some(first),
+                // Skipped wall-clock time: fire as soon as the gap ends.
+                LocalResult::None => (1..=MAX_GAP_MINUTES).find_map(|m| {
+                    tz.from_local_datetime(&(wall + Duration::minutes(m)))
+                        .earliest()
+                }),
+            }
+        })
+        .map(|t| t.timestamp())
+        .find(|&t| t > after.timestamp())
+}
+
+/// Bring a job reloaded from storage up to date with the current time.
+///
+/// An enabled job whose stored `next_run` lies in the past missed a run while
+/// the process was down.  With `catch_up` the job is made due immediately so
+/// it fires exactly once; otherwise the missed occurrences are skipped.
+/// Returns `true` if the job missed a run.
+pub(super) fn reconcile_on_startup(job: &mut CronJob, now: DateTime<Utc>, catch_up: bool) -> bool {
+    let now_ts = now.timestamp();
+    let missed = job.enabled && job.next_run.is_some_and(|t| t <= now_ts);
+
+    job.next_run = if missed && catch_up {
+        Some(now_ts)
+    } else if job.enabled {
+        match job.next_run {
+            Some(t) if t > now_ts => Some(t),
+            _ => next_run_after(job, now),
+        }
+    } else {
+        None
+    };
+    missed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(schedule: &str, next_run: Option<i64>) -> CronJob {
+        zoned_job(schedule, DEFAULT_TIMEZONE, next_run)
+    }
+
+    fn zoned_job(schedule: &str, timezone: &str, next_run: Option<i64>) -> CronJob {
+        CronJob {
+            id: "job".into(),
+            name: "job".into(),
+            schedule: schedule.into(),
+            timezone: timezone.into(),
+            command: "noop".into(),
+            enabled: true,
+            created_at: 0,
+            last_run: None,
+            next_run,
+        }
+    }
+
+    fn at(h: u32, m: u32) -> DateTime<Utc> {
+        utc(2025, 3, 1, h, m)
+    }
+
+    fn utc(y: i32, mo: u32, d: u32, h: u32, m: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, m, 0)
+            .single()
+            .expect("valid test timestamp")
+    }
+
+    /// Consecutive runs of `job` starting after `from`, in UTC.
+    fn runs(job: &CronJob, from: DateTime<Utc>, count: usize) -> Vec<DateTime<Utc>> {
+        std::iter::successors(Some(from), |&prev| {
+            next_run_after(job, prev).and_then(|t| DateTime::from_timestamp(t, 0))
+        })
+        .skip(1)
+        .take(count)
+        .collect()
+    }
+
+    #[test]
+    fn five_and_six_field_expressions_parse() {
+        assert!(parse_schedule("0 9 * * *", "cron_create").is_ok());
+        assert!(parse_schedule("30 0 9 * * *", "cron_create").is_ok());
+        assert!(matches!(
+            parse_schedule("not a cron", "cron_create"),
+            Err(AdapterError::InvalidParams { .. })
+        ));
+    }
+
+    #[test]
+    fn unknown_timezone_is_rejected() {
+        assert!(parse_timezone("America/New_York", "cron_create").is_ok());
+        assert!(matches!(
+            parse_timezone("Mars/Olympus_Mons", "cron_create"),
+            Err(AdapterError::InvalidParams { .. })
+        ));
+    }
+
+    #[test]
+    fn next_run_is_strictly_after() {
+        let daily = job("0 9 * * *", None);
+        assert_eq!(next_run_after(&daily, at(8, 0)), Some(at(9, 0).timestamp()));
+        assert_eq!(
+            next_run_after(&daily, at(9, 0)),
+            Some(at(9, 0).timestamp() + 86_400)
+        );
+    }
+
+    #[test]
+    fn daily_job_keeps_local_time_across_spring_forward() {
+        // New York moves from EST (UTC-5) to EDT (UTC-4) on 2025-03-09.
+        let daily = zoned_job("0 9 * * *", "America/New_York", None);
+        assert_eq!(
+            runs(&daily, utc(2025, 3, 7, 15, 0), 3),
+            vec![
+                utc(2025, 3, 8, 14, 0),
+                utc(2025, 3, 9, 13, 0),
+                utc(2025, 3, 10, 13, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn daily_job_keeps_local_time_across_fall_back() {
+        // New York moves from EDT (UTC-4) back to EST (UTC-5) on 2025-11-02.
+        let daily = zoned_job("0 9 * * *", "America/New_York", None);
+        assert_eq!(
+            runs(&daily, utc(2025, 10, 31, 14, 0), 3),
+            vec![
+                utc(2025, 11, 1, 13, 0),
+                utc(2025, 11, 2, 14, 0),
+                utc(2025, 11, 3, 14, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn time_skipped_by_gap_runs_when_gap_ends() {
+        // 02:30 does not exist in New York on 2025-03-09; run at 03:00 EDT.
+        let nightly = zoned_job("30 2 * * *", "America/New_York", None);
+        assert_eq!(
+            runs(&nightly, utc(2025, 3, 8, 12, 0), 2),
+            vec![utc(2025, 3, 9, 7, 0), utc(2025, 3, 10, 6, 30)]
+        );
+    }
+
+    #[test]
+    fn repeated_time_runs_once() {
+        // 01:30 happens twice in New York on 2025-11-02; run on the first pass.
+        let nightly = zoned_job("30 1 * * *", "America/New_York", None);
+        assert_eq!(
+            runs(&nightly, utc(2025, 11, 1, 12, 0), 2),
+            vec![utc(2025, 11, 2, 5, 30), utc(2025, 11, 3, 6, 30)]
+        );
+    }
+
+    #[test]
+    fn hourly_job_across_fall_back_skips_repeated_hour() {
+        let hourly = zoned_job("0 * * * *", "America/New_York", None);
+        // 00:00 EDT, 01:00 EDT, then 02:00 EST (01:00 EST repeats and is skipped).
+        assert_eq!(
+            runs(&hourly, utc(2025, 11, 2, 3, 30), 3),
+            vec![
+                utc(2025, 11, 2, 4, 0),
+                utc(2025, 11, 2, 5, 0),
+                utc(2025, 11, 2, 7, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn missed_run_fires_once_with_catch_up() {
+        let mut j = job("0 9 * * *", Some(at(9, 0).timestamp()));
+        assert!(reconcile_on_startup(&mut j, at(12, 0), true));
+        assert_eq!(j.next_run, Some(at(12, 0).timestamp()));
+    }
+
+    #[test]
+    fn missed_run_is_skipped_without_catch_up() {
+        let mut j = job("0 9 * * *", Some(at(9, 0).timestamp()));
+        assert!(reconcile_on_startup(&mut j, at(12, 0), false));
+        assert_eq!(j.next_run, Some(at(9, 0).timestamp() + 86_400));
+    }
+
+    #[test]
+    fn future_and_disabled_jobs_are_not_missed() {
+        let mut future = job("0 9 * * *", Some(at(9, 0).timestamp()));
+        assert!(!reconcile_on_startup(&mut future, at(8, 0), true));
+        assert_eq!(future.next_run, Some(at(9, 0).timestamp()));
+
+        let mut disabled = job("0 9 * * *", Some(at(9, 0).timestamp()));
+        disabled.enabled = false;
+        assert!(!reconcile_on_startup(&mut disabled, at(12, 0), true));
+        assert_eq!(disabled.next_run, None);
+    }
+}
@@ -0,0 +1,408 @@
+use super::*;
+
+async fn setup() -> CronAdapter {
+    let mut adapter = CronAdapter::new("cron-test");
+    adapter.connect().await.unwrap_or_else(|e| {
+        panic!("failed to connect cron adapter: {e}");
+    });
+    adapter
+}
+
+#[tokio::test]
+async fn cron_adapter_has_six_tools() {
+    let adapter = setup().await;
+    assert_eq!(adapter.tools().len(), 6);
+}
+
+#[tokio::test]
+async fn cron_adapter_health_when_disconnected() {
+    let adapter = CronAdapter::new("cron-test");
+    let status = adapter.health_check().await.unwrap_or_else(|e| {
+        panic!("health check failed: {e}");
+    });
+    assert_eq!(status, HealthStatus::Unhealthy);
+}
+
+#[tokio::test]
+async fn cron_adapter_rejects_when_not_connected() {
+    let adapter = CronAdapter::new("cron-test");
+    let result = adapter
+        .execute_tool(
+            "cron_create",
+            json!({"name": "test", "schedule": "* * * * *", "command": "echo hi"}),
+        )
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn cron_create_and_list() {
+    let adapter = setup().await;
+
+    let create_result = adapter
+        .execute_tool(
+            "cron_create",
+            json!({
+                "name": "hourly backup",
+                "schedule": "0 * * * *",
+                "command": "backup_all"
+            }),
+        )
+        .await
+        .unwrap_or_else(|e| panic!("create failed: {e}"));
+
+    assert_eq!(create_result["created"], true);
+    let job_id = create_result["id"]
+        .as_str()
+        .unwrap_or_else(|| panic!("create should return an id string"));
+    assert!(!job_id.is_empty());
+
+    let list_result = adapter
+        .execute_tool("cron_list", json!({}))
+        .await
+        .unwrap_or_else(|e| panic!("list failed: {e}"));
+
+    let jobs = list_result["jobs"]
+        .as_array()
+        .unwrap_or_else(|| panic!("jobs should be an array"));
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0]["name"], "hourly backup");
+    assert_eq!(jobs[0]["schedule"], "0 * * * *");
+    assert_eq!(jobs[0]["enabled"], true);
+}
+
+#[tokio::test]
+async fn cron_create_and_delete() {
+    let adapter = setup().await;
+
+    let create_result = adapter
+        .execute_tool(
+            "cron_create",
+            json!({"name": "temp job", "schedule": "* * * * *", "command": "noop"}),
+        )
+        .await
+        .unwrap_or_else(|e| panic!("create failed: {e}"));
+
+    let job_id = create_result["id"]
+        .as_str()
+        .unwrap_or_else(|| panic!("create should return an id"));
+
+    let delete_result = adapter
+        .execute_tool("cron_delete", json!({"id": job_id}))
+        .await
+        .unwrap_or_else(|e| panic!("delete failed: {e}"));
+
+    assert_eq!(delete_result["deleted"], true);
+
+    // Verify it is gone.
+    let list_result = adapter
+        .execute_tool("cron_list", json!({}))
+        .await
+        .unwrap_or_else(|e| panic!("list failed: {e}"));
+
+    let jobs = list_result["jobs"]
+        .as_array()
+        .unwrap_or_else(|| panic!("jobs should be an array"));
+    assert!(jobs.is_empty());
+}
+
+#[tokio::test]
+async fn cron_delete_nonexistent_fails() {
+    let adapter = setup().await;
+    let result = adapter
+        .execute_tool("cron_delete", json!({"id": "nonexistent-id"}))
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn cron_toggle_enable_disable() {
+    let adapter = setup().await;
+
+    let create_result = adapter
+        .execute_tool(
+            "cron_create",
+            json!({"name": "toggle test", "schedule": "0 0 * * *", "command": "daily_report"}),
+        )
+        .await
+        .unwrap_or_else(|e| panic!("create failed: {e}"));
+
+    let job_id = create_result["id"]
+        .as_str()
+        .unwrap_or_else(|| panic!("create should return an id"));
+
+    // Disable.
+    let toggle_result = adapter
+        .execute_tool("cron_toggle", json!({"id": job_id, "enabled": false}))
+        .await
+        .unwrap_or_else(|e| panic!("toggle failed: {e}"));
+
+    assert_eq!(toggle_result["enabled"], false);
+
+    // Verify via list.
+    let list_result = adapter
+        .execute_tool("cron_list", json!({}))
+        .await
+        .unwrap_or_else(|e| panic!("list failed: {e}"));
+
+    let jobs = list_result["jobs"]
+        .as_array()
+        .unwrap_or_else(|| panic!("jobs should be an array"));
+    assert_eq!(jobs[0]["enabled"], false);
+
+    // Re-enable.
+    let toggle_result = adapter
+        .execute_tool("cron_toggle", json!({"id": job_id, "enabled": true}))
+        .await
+        .unwrap_or_else(|e| panic!("re-enable failed: {e}"));
+
+    assert_eq!(toggle_result["enabled"], true);
+}
+
+#[tokio::test]
+async fn cron_toggle_nonexistent_fails() {
+    let adapter = setup().await;
+    let result = adapter
+        .execute_tool("cron_toggle", json!({"id": "nope", "enabled": true}))
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn cron_create_missing_name_fails() {
+    let adapter = setup().await;
+    let result = adapter
+        .execute_tool(
+            "cron_create",
+            json!({"schedule": "* * * * *", "command": "echo hi"}),
+        )
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn unknown_tool_returns_error() {
+    let adapter = setup().await;
+    let result = adapter.execute_tool("cron_nonexistent", json!({})).await;
+    assert!(result.is_err());
+}
+
+async fn test_store() -> CronJobStore {
+    let db = openintent_store::Database::open_in_memory()
+        .unwrap_or_else(|e| panic!("failed to open database: {e}"));
+    db.run_migrations()
+        .await
+        .unwrap_or_else(|e| panic!("failed to migrate database: {e}"));
+    CronJobStore::new(db)
+}
+
+#[tokio::test]
+async fn cron_create_rejects_invalid_schedule() {
+    let adapter = setup().await;
+    let result = adapter
+        .execute_tool(
+            "cron_create",
+            json!({"name": "bad", "schedule": "every tuesday", "command": "noop"}),
+        )
+        .await;
+    assert!(matches!(result, Err(AdapterError::InvalidParams { .. })));
+}
+
+#[tokio::test]
+async fn cron_pause_and_resume() {
+    let adapter = setup().await;
+    let created = adapter
+        .execute_tool(
+            "cron_create",
+            json!({"name": "pausable", "schedule": "*/5 * * * *", "command": "noop"}),
+        )
+        .await
+        .unwrap_or_else(|e| panic!("create failed: {e}"));
+    let job_id = created["id"].as_str().unwrap_or_default().to_string();
+    assert!(created["next_run_at"].is_string());
+
+    let paused = adapter
+        .execute_tool("cron_pause", json!({"id": job_id}))
+        .await
+        .unwrap_or_else(|e| panic!("pause failed: {e}"));
+    assert_eq!(paused["enabled"], false);
+    assert!(paused["next_run_at"].is_null());
+
+    let resumed = adapter
+        .execute_tool("cron_resume", json!({"id": job_id}))
+        .await
+        .unwrap_or_else(|e| panic!("resume failed: {e}"));
+    assert_eq!(resumed["enabled"], true);
+    let next = adapter
+        .next_run_at(&job_id)
+        .unwrap_or_else(|e| panic!("next_run_at failed: {e}"))
+        .unwrap_or_else(|| panic!("resumed job should have a next run"));
+    assert!(next > Utc::now());
+}
+
+#[tokio::test]
+async fn scheduled_job_is_reloaded_after_restart() {
+    let store = test_store().await;
+
+    let job_id = {
+        let mut adapter = CronAdapter::new("cron-test").with_store(store.clone());
+        adapter
+            .connect()
+            .await
+            .unwrap_or_else(|e| panic!("connect failed: {e}"));
+        let created = adapter
+            .execute_tool(
+                "cron_create",
+                json!({"name": "nightly", "schedule": "0 2 * * *", "command": "report"}),
+            )
+            .await
+            .unwrap_or_else(|e| panic!("create failed: {e}"));
+        created["id"].as_str().unwrap_or_default().to_string()
+    };
+
+    // Simulate a restart with a fresh adapter over the same database.
+    let mut restarted = CronAdapter::new("cron-test").with_store(store);
+    restarted
+        .connect()
+        .await
+        .unwrap_or_else(|e| panic!("reconnect failed: {e}"));
+
+    let jobs = restarted
+        .list_jobs()
+        .unwrap_or_else(|e| panic!("list failed: {e}"));
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0].id, job_id);
+    assert_eq!(jobs[0].schedule, "0 2 * * *");
+
+    let next = restarted
+        .next_run_at(&job_id)
+        .unwrap_or_else(|e| panic!("next_run_at failed: {e}"))
+        .unwrap_or_else(|| panic!("reloaded job should have a next run"));
+    assert!(next > Utc::now());
+    assert_eq!(next.format("%H:%M:%S").to_string(), "02:00:00");
+}
+
+#[tokio::test]
+async fn missed_run_fires_once_on_startup_when_enabled() {
+    let store = test_store().await;
+    let overdue = Utc::now().timestamp() - 3_600;
+    for (id, created_at) in [("a", 1), ("b", 2)] {
+        store
+            .save(&StoredCronJob {
+                id: id.into(),
+                name: id.into(),
+                schedule: "0 * * * *".into(),
+                timezone: "UTC".into(),
+                command: "noop".into(),
+                enabled: true,
+                created_at,
+                last_run: None,
+                next_run: Some(overdue),
+            })
+            .await
+            .unwrap_or_else(|e| panic!("seed failed: {e}"));
+    }
+
+    let mut skipping = CronAdapter::new("cron-test").with_store(store.clone());
+    skipping
+        .connect()
+        .await
+        .unwrap_or_else(|e| panic!("connect failed: {e}"));
+    let due = skipping
+        .due_jobs(Utc::now())
+        .unwrap_or_else(|e| panic!("due_jobs failed: {e}"));
+    assert!(due.is_empty(), "missed runs are skipped by default");
+
+    // Reset one job to overdue and restart with catch-up enabled.
+    store
+        .update_runs("a", None, Some(overdue))
+        .await
+        .unwrap_or_else(|e| panic!("reset failed: {e}"));
+    let mut catching_up = CronAdapter::new("cron-test")
+        .with_store(store.clone())
+        .with_catch_up_missed(true);
+    catching_up
+        .connect()
+        .await
+        .unwrap_or_else(|e| panic!("connect failed: {e}"));
+    let now = Utc::now();
+    let due = catching_up
+        .due_jobs(now)
+        .unwrap_or_else(|e| panic!("due_jobs failed: {e}"));
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].id, "a");
+
+    let ran = catching_up
+        .mark_run("a", now)
+        .await
+        .unwrap_or_else(|e| panic!("mark_run failed: {e}"));
+    assert_eq!(ran.last_run, Some(now.timestamp()));
+    assert!(ran.next_run.is_some_and(|t| t > now.timestamp()));
+    assert!(
+        catching_up
+            .due_jobs(now)
+            .unwrap_or_else(|e| panic!("due_jobs failed: {e}"))
+            .is_empty()
+    );
+
+    let stored = store
+        .get("a")
+        .await
+        .unwrap_or_else(|e| panic!("get failed: {e}"))
+        .unwrap_or_else(|| panic!("job should be stored"));
+    assert_eq!(stored.last_run, ran.last_run);
+    assert_eq!(stored.next_run, ran.next_run);
+}
+
+#[tokio::test]
+async fn cron_create_uses_timezone_for_next_run() {
+    let adapter = setup().await;
+    let created = adapter
+        .execute_tool(
+            "cron_create",
+            json!({
+                "name": "standup",
+                "schedule": "0 9 * * *",
+                "timezone": "Asia/Tokyo",
+                "command": "standup_reminder"
+            }),
+        )
+        .await
+        .unwrap_or_else(|e| panic!("create failed: {e}"));
+    let job_id = created["id"].as_str().unwrap_or_default();
+
+    let list = adapter
+        .execute_tool("cron_list", json!({}))
+        .await
+        .unwrap_or_else(|e| panic!("list failed: {e}"));
+    assert_eq!(list["jobs"][0]["timezone"], "Asia/Tokyo");
+
+    // 09:00 in Tokyo (UTC+9, no DST) is always 00:00 UTC.
+    let next = adapter
+        .next_run_at(job_id)
+        .unwrap_or_else(|e| panic!("next_run_at failed: {e}"))
+        .unwrap_or_else(|| panic!("job should have a next run"));
+    assert_eq!(next.format("%H:%M").to_string(), "00:00");
+}
+
+#[tokio::test]
+async fn cron_create_rejects_unknown_timezone() {
+    let adapter = setup().await;
+    let result = adapter
+        .execute_tool(
+            "cron_create",
+            json!({
+                "name": "bad zone",
+                "schedule": "0 9 * * *",
+                "timezone": "Somewhere/Else",
+                "command": "noop"
+            }),
+        )
+        .await;
+    match result {
+        Err(AdapterError::InvalidParams { reason, .. }) => {
+            assert!(reason.contains("Somewhere/Else"));
+        }
+        other => panic!("expected InvalidParams, got: {other:?}"),
+    }
+}
@@ -9,6 +9,7 @@ use chrono::NaiveDate;
 use tracing::{info, warn};
 
 use crate::error::AdapterError;
+use crate::http_client::HttpClientFactory;
 
 // ---------------------------------------------------------------------------
 // Configuration
@@ -58,7 +59,7 @@ impl DailyBriefingAdapter {
     pub fn new() -> Self {
         Self {
             config: BriefingConfig::default(),
-            http: reqwest::Client::new(),
+            http: HttpClientFactory::default().build().unwrap_or_default(),
         }
     }
 
@@ -66,7 +67,7 @@ impl DailyBriefingAdapter {
     pub fn with_config(config: BriefingConfig) -> Self {
         Self {
             config,
-            http: reqwest::Client::new(),
+            http: HttpClientFactory::default().build().unwrap_or_default(),
         }
     }
 
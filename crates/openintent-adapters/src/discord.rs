@@ -8,7 +8,9 @@ use async_trait::async_trait;
 use serde_json::{Value, json};
 use tracing::{debug, info, warn};
 
+use crate::credentials;
 use crate::error::{AdapterError, Result};
+use crate::http_client::{self, HttpClientFactory};
 use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
 
 /// Discord API v10 base URL.
@@ -33,10 +35,7 @@ pub struct DiscordAdapter {
 impl DiscordAdapter {
     /// Create a new Discord adapter with default configuration and no token.
     pub fn new(id: impl Into<String>) -> Self {
-        let http = reqwest::Client::builder()
-            .user_agent("OpenIntentOS/0.1")
-            .build()
-            .unwrap_or_default();
+        let http = HttpClientFactory::default().build().unwrap_or_default();
 
         Self {
             id: id.into(),
@@ -415,6 +414,7 @@ impl Adapter for DiscordAdapter {
                     },
                     "required": ["channel_id", "content"]
                 }),
+                destructive: true,
             },
             ToolDefinition {
                 name: "discord_get_messages".into(),
@@ -433,6 +433,7 @@ impl Adapter for DiscordAdapter {
                     },
                     "required": ["channel_id"]
                 }),
+                destructive: false,
             },
             ToolDefinition {
                 name: "discord_get_channel".into(),
@@ -447,6 +448,7 @@ impl Adapter for DiscordAdapter {
                     },
                     "required": ["channel_id"]
                 }),
+                destructive: false,
             },
             ToolDefinition {
                 name: "discord_get_guild".into(),
@@ -461,6 +463,7 @@ impl Adapter for DiscordAdapter {
                     },
                     "required": ["guild_id"]
                 }),
+                destructive: false,
             },
             ToolDefinition {
                 name: "discord_create_reaction".into(),
@@ -483,6 +486,7 @@ impl Adapter for DiscordAdapter {
                     },
                     "required": ["channel_id", "message_id", "emoji"]
                 }),
+                destructive: true,
             },
         ]
     }
@@ -494,6 +498,7 @@ impl Adapter for DiscordAdapter {
                 reason: format!("adapter `{}` is not connected", self.id),
             });
         }
+        http_client::ensure_online()?;
 
         match name {
             "discord_send_message" => self.tool_send_message(params).await,
@@ -520,6 +525,10 @@ impl Adapter for DiscordAdapter {
             ],
         })
     }
+
+    fn has_credentials(&self) -> bool {
+        credentials::is_set(self.bot_token.as_deref())
+    }
 }
 
 // ---------------------------------------------------------------------------
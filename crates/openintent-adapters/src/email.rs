@@ -20,6 +20,7 @@ use tokio_rustls::TlsConnector;
 use tracing::{debug, info};
 
 use crate::error::{AdapterError, Result};
+use crate::http_client;
 use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
 
 /// Default IMAP TLS port.
@@ -1083,6 +1084,7 @@ impl Adapter for EmailAdapter {
                     },
                     "required": ["username", "password"]
                 }),
+                destructive: false,
             },
             ToolDefinition {
                 name: "email_read".into(),
@@ -1109,6 +1111,7 @@ impl Adapter for EmailAdapter {
                     },
                     "required": ["message_id", "username", "password"]
                 }),
+                destructive: false,
             },
             ToolDefinition {
                 name: "email_send".into(),
@@ -1143,6 +1146,7 @@ impl Adapter for EmailAdapter {
                     },
                     "required": ["to", "subject", "body", "username", "password"]
                 }),
+                destructive: true,
             },
             ToolDefinition {
                 name: "email_search".into(),
@@ -1173,6 +1177,7 @@ impl Adapter for EmailAdapter {
                     },
                     "required": ["query", "username", "password"]
                 }),
+                destructive: false,
             },
         ]
     }
@@ -1184,6 +1189,7 @@ impl Adapter for EmailAdapter {
                 reason: format!("adapter `{}` is not connected", self.id),
             });
         }
+        http_client::ensure_online()?;
 
         match name {
             "email_list_inbox" => self.tool_email_list_inbox(params).await,
@@ -2,7 +2,11 @@
 //!
 //! All adapter subsystems surface errors through [`AdapterError`].  Each
 //! variant carries enough context for callers to decide how to handle the
-//! failure without inspecting opaque strings.
+//! failure without inspecting opaque strings.  Errors crossing into the
+//! agent runtime keep their [`ErrorCategory`] via `From<AdapterError> for
+//! AgentError`.
+
+use openintent_agent::{AgentError, ErrorCategory};
 
 /// Unified error type for OpenIntentOS adapters.
 #[derive(Debug, thiserror::Error)]
@@ -33,6 +37,28 @@ pub enum AdapterError {
         provider: String,
     },
 
+    /// The adapter is not connected, or its backing service is unreachable.
+    #[error("adapter `{adapter_id}` is not connected: {reason}")]
+    NotConnected { adapter_id: String, reason: String },
+
+    /// The backing service is throttling requests.
+    #[error("adapter `{adapter_id}` is rate limited{}", retry_hint(*retry_after_secs))]
+    RateLimited {
+        adapter_id: String,
+        retry_after_secs: Option<u64>,
+    },
+
+    /// The adapter's circuit breaker is open after repeated failures.
+    #[error("circuit open for adapter `{adapter_id}`, retry after {retry_after_secs}s")]
+    CircuitOpen {
+        adapter_id: String,
+        retry_after_secs: u64,
+    },
+
+    /// Network access is disabled by the process-wide network mode.
+    #[error("network access is disabled (offline mode)")]
+    NetworkDisabled,
+
     /// JSON serialization or deserialization failed.
     #[error("serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
@@ -60,4 +86,98 @@ pub enum AdapterError {
 }
 
 /// Convenience alias used throughout the adapters crate.
-pub type Result<T> = std::result::Result<T, AdapterError>;
\ No newline at end of file
+pub type Result<T> = std::result::Result<T, AdapterError>;
+
+impl AdapterError {
+    /// Classify this error so the agent runtime can react appropriately.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::AuthRequired { .. } => ErrorCategory::AuthRequired,
+            Self::NotConnected { .. }
+            | Self::CircuitOpen { .. }
+            | Self::NetworkDisabled
+            | Self::ConfigError(_) => ErrorCategory::NotConnected,
+            Self::RateLimited { .. } => ErrorCategory::RateLimited,
+            Self::ToolNotFound { .. } | Self::InvalidParams { .. } | Self::InvalidInput(_) => {
+                ErrorCategory::InvalidInput
+            }
+            Self::IoError(_)
+            | Self::ExecutionFailed { .. }
+            | Self::SerializationError(_)
+            | Self::Timeout { .. }
+            | Self::ExecutionError(_)
+            | Self::Internal(_) => ErrorCategory::Upstream,
+        }
+    }
+}
+
+impl From<AdapterError> for AgentError {
+    fn from(err: AdapterError) -> Self {
+        Self::AdapterFailed {
+            category: err.category(),
+            reason: err.to_string(),
+        }
+    }
+}
+
+fn retry_hint(retry_after_secs: Option<u64>) -> String {
+    retry_after_secs
+        .map(|secs| format!(", retry after {secs}s"))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limited_maps_to_retryable_agent_error() {
+        let err = AgentError::from(AdapterError::RateLimited {
+            adapter_id: "github".into(),
+            retry_after_secs: Some(30),
+        });
+
+        assert_eq!(err.category(), Some(ErrorCategory::RateLimited));
+        assert!(err.is_retryable());
+        assert_eq!(
+            err.to_string(),
+            "adapter error (rate_limited): adapter `github` is rate limited, retry after 30s"
+        );
+    }
+
+    #[test]
+    fn categories_survive_conversion() {
+        let cases = [
+            (
+                AdapterError::InvalidParams {
+                    tool_name: "fs_read_file".into(),
+                    reason: "missing path".into(),
+                },
+                ErrorCategory::InvalidInput,
+            ),
+            (
+                AdapterError::AuthRequired {
+                    adapter_id: "email".into(),
+                    provider: "google".into(),
+                },
+                ErrorCategory::AuthRequired,
+            ),
+            (
+                AdapterError::NotConnected {
+                    adapter_id: "mqtt".into(),
+                    reason: "broker unreachable".into(),
+                },
+                ErrorCategory::NotConnected,
+            ),
+            (
+                AdapterError::Internal("boom".into()),
+                ErrorCategory::Upstream,
+            ),
+        ];
+        for (adapter_err, category) in cases {
+            let err = AgentError::from(adapter_err);
+            assert_eq!(err.category(), Some(category));
+            assert!(!err.is_retryable());
+        }
+    }
+}
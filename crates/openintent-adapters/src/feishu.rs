@@ -9,7 +9,9 @@ use async_trait::async_trait;
 use serde_json::{Value, json};
 use tracing::{debug, info, warn};
 
+use crate::credentials;
 use crate::error::{AdapterError, Result};
+use crate::http_client::{self, HttpClientFactory};
 use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
 
 /// Default Feishu Open Platform API base URL.
@@ -40,10 +42,7 @@ pub struct FeishuAdapter {
 impl FeishuAdapter {
     /// Create a new Feishu adapter with default configuration and no credentials.
     pub fn new(id: impl Into<String>) -> Self {
-        let client = reqwest::Client::builder()
-            .user_agent("OpenIntentOS/0.1")
-            .build()
-            .unwrap_or_default();
+        let client = HttpClientFactory::default().build().unwrap_or_default();
 
         Self {
             id: id.into(),
@@ -616,6 +615,7 @@ impl Adapter for FeishuAdapter {
                     },
                     "required": ["receive_id", "receive_id_type", "msg_type", "content"]
                 }),
+                destructive: true,
             },
             ToolDefinition {
                 name: "feishu_list_chats".into(),
@@ -634,6 +634,7 @@ impl Adapter for FeishuAdapter {
                     },
                     "required": []
                 }),
+                destructive: false,
             },
             ToolDefinition {
                 name: "feishu_get_chat_messages".into(),
@@ -652,6 +653,7 @@ impl Adapter for FeishuAdapter {
                     },
                     "required": ["container_id"]
                 }),
+                destructive: false,
             },
             ToolDefinition {
                 name: "feishu_create_doc".into(),
@@ -670,6 +672,7 @@ impl Adapter for FeishuAdapter {
                     },
                     "required": ["title"]
                 }),
+                destructive: true,
             },
             ToolDefinition {
                 name: "feishu_search_users".into(),
@@ -688,6 +691,7 @@ impl Adapter for FeishuAdapter {
                     },
                     "required": ["query"]
                 }),
+                destructive: false,
             },
             ToolDefinition {
                 name: "feishu_get_user_info".into(),
@@ -707,6 +711,7 @@ impl Adapter for FeishuAdapter {
                     },
                     "required": ["user_id"]
                 }),
+                destructive: false,
             },
         ]
     }
@@ -718,6 +723,7 @@ impl Adapter for FeishuAdapter {
                 reason: format!("adapter `{}` is not connected", self.id),
             });
         }
+        http_client::ensure_online()?;
 
         match name {
             "feishu_send_message" => self.tool_send_message(params).await,
@@ -744,6 +750,11 @@ impl Adapter for FeishuAdapter {
             ],
         })
     }
+
+    fn has_credentials(&self) -> bool {
+        credentials::is_set(self.tenant_access_token.as_deref())
+            || credentials::all_set(&[self.app_id.as_deref(), self.app_secret.as_deref()])
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -1,16 +1,47 @@
-//! Filesystem adapter -- read, write, list, create, delete, and inspect files.
+//! Filesystem adapter -- read, write, list, create, delete, inspect, diff,
+//! and watch files.
 //!
 //! This adapter provides safe, async filesystem operations using `tokio::fs`.
 //! All paths are resolved relative to an optional `root_dir` and validated
 //! against path traversal attacks (e.g. `../../etc/passwd`).
+//!
+//! With an [`IpcBus`] attached ([`FilesystemAdapter::with_bus`]), the
+//! `fs_watch_path` tool watches a path and publishes every change under it
+//! as an [`Event::AdapterEvent`] of kind `file_created`, `file_modified`,
+//! `file_removed`, or `file_changed`, whose payload holds the changed paths.
+//!
+//! When the caller supplies a [`PromptFn`]
+//! ([`Adapter::execute_tool_with_prompt`]), `fs_delete` and overwriting
+//! `fs_write_file` calls ask the user first and are skipped with
+//! `"cancelled": true` unless they answer yes.
+
+#[cfg(test)]
+mod tests;
 
 /// Maximum characters returned per file read to limit token usage.
 /// Approximately 4 000 tokens at typical tokenization rates.
 const MAX_FILE_READ_CHARS: usize = 16_000;
 
+/// Largest file `fs_diff_files` will compare, in bytes.
+const MAX_DIFF_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Unchanged lines shown around each change in a diff.
+const DIFF_CONTEXT_LINES: usize = 3;
+
+/// Most paths that can be watched at once.
+const MAX_WATCHED_PATHS: usize = 32;
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+
 use async_trait::async_trait;
+use chrono::Utc;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use openintent_agent::{PromptFn, confirm};
+use openintent_kernel::ipc::{Event, IpcBus};
 use serde_json::{Value, json};
-use tracing::{debug, info};
+use similar::{ChangeTag, TextDiff};
+use tracing::{debug, info, warn};
 
 use crate::error::{AdapterError, Result};
 use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
@@ -24,6 +55,11 @@ pub struct FilesystemAdapter {
     root_dir: std::path::PathBuf,
     /// Whether the adapter has been connected (initialised).
     connected: bool,
+    /// Bus that file-change events are published to; watching is only
+    /// offered when set.
+    bus: Option<IpcBus>,
+    /// Active watchers, keyed by the resolved path they watch.
+    watchers: Mutex<HashMap<std::path::PathBuf, RecommendedWatcher>>,
 }
 
 impl FilesystemAdapter {
@@ -33,43 +69,29 @@ impl FilesystemAdapter {
             id: id.into(),
             root_dir: root_dir.into(),
             connected: false,
+            bus: None,
+            watchers: Mutex::new(HashMap::new()),
         }
     }
 
+    /// Publish file-change events from `fs_watch_path` onto `bus`.
+    pub fn with_bus(mut self, bus: IpcBus) -> Self {
+        self.bus = Some(bus);
+        self
+    }
+
     /// Resolve a user-supplied path against the root directory and validate
     /// that the result does not escape the root (path traversal protection).
     ///
     /// Returns the canonicalized absolute path on success or an error if the
     /// resolved path would leave the root directory.
     fn safe_resolve(&self, raw_path: &str, tool_name: &str) -> Result<std::path::PathBuf> {
-        let candidate = if std::path::Path::new(raw_path).is_absolute() {
-            std::path::PathBuf::from(raw_path)
-        } else {
-            self.root_dir.join(raw_path)
-        };
-
-        // Build a normalized path without touching the filesystem (the target
-        // may not exist yet, so canonicalize() would fail).
-        let normalized = normalize_path(&candidate);
-
-        // Canonicalize the root so the prefix check is reliable.
-        let canon_root = self
-            .root_dir
-            .canonicalize()
-            .unwrap_or_else(|_| self.root_dir.clone());
-
-        if !normalized.starts_with(&canon_root) {
-            return Err(AdapterError::InvalidParams {
-                tool_name: tool_name.to_string(),
-                reason: format!(
-                    "path `{raw_path}` resolves to `{}` which is outside the root directory `{}`",
-                    normalized.display(),
-                    canon_root.display(),
-                ),
-            });
-        }
+        resolve_within(&self.root_dir, raw_path, tool_name)
+    }
 
-        Ok(normalized)
+    /// Lock the active watchers, recovering from a poisoned lock.
+    fn watchers(&self) -> MutexGuard<'_, HashMap<std::path::PathBuf, RecommendedWatcher>> {
+        self.watchers.lock().unwrap_or_else(|e| e.into_inner())
     }
 
     /// Extract a required string field from JSON params.
@@ -83,6 +105,12 @@ impl FilesystemAdapter {
             })
     }
 
+    /// Whether a destructive step may go ahead: always without a prompt,
+    /// otherwise only when the user answers yes to `question`.
+    async fn approved(prompt: Option<&PromptFn>, question: &str) -> bool {
+        prompt.is_none() || confirm(prompt, &format!("{question} [y/N]")).await
+    }
+
     // -- Tool implementations ------------------------------------------------
 
     async fn tool_fs_read_file(&self, params: Value) -> Result<Value> {
@@ -119,12 +147,18 @@ impl FilesystemAdapter {
         }))
     }
 
-    async fn tool_fs_write_file(&self, params: Value) -> Result<Value> {
+    async fn tool_fs_write_file(&self, params: Value, prompt: Option<&PromptFn>) -> Result<Value> {
         let path_str = Self::require_str(&params, "path", "fs_write_file")?;
         let content = Self::require_str(&params, "content", "fs_write_file")?;
         let full_path = self.safe_resolve(path_str, "fs_write_file")?;
         debug!(path = %full_path.display(), "writing file");
 
+        if tokio::fs::try_exists(&full_path).await?
+            && !Self::approved(prompt, &format!("Overwrite `{}`?", full_path.display())).await
+        {
+            return Ok(cancelled(&full_path));
+        }
+
         // Ensure parent directory exists.
         if let Some(parent) = full_path.parent() {
             tokio::fs::create_dir_all(parent).await?;
@@ -178,12 +212,15 @@ impl FilesystemAdapter {
         }))
     }
 
-    async fn tool_fs_delete(&self, params: Value) -> Result<Value> {
+    async fn tool_fs_delete(&self, params: Value, prompt: Option<&PromptFn>) -> Result<Value> {
         let path_str = Self::require_str(&params, "path", "fs_delete")?;
         let full_path = self.safe_resolve(path_str, "fs_delete")?;
         debug!(path = %full_path.display(), "deleting");
 
         let meta = tokio::fs::metadata(&full_path).await?;
+        if !Self::approved(prompt, &format!("Delete `{}`?", full_path.display())).await {
+            return Ok(cancelled(&full_path));
+        }
         if meta.is_dir() {
             tokio::fs::remove_dir_all(&full_path).await?;
         } else {
@@ -262,6 +299,261 @@ impl FilesystemAdapter {
             "created_epoch": created,
         }))
     }
+
+    async fn tool_fs_diff_files(&self, params: Value) -> Result<Value> {
+        let path_a = Self::require_str(&params, "path_a", "fs_diff_files")?;
+        let path_b = Self::require_str(&params, "path_b", "fs_diff_files")?;
+        let mode = params
+            .get("mode")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unified");
+        if !matches!(mode, "unified" | "hunks") {
+            return Err(AdapterError::InvalidParams {
+                tool_name: "fs_diff_files".to_string(),
+                reason: format!("unknown mode `{mode}` (expected `unified` or `hunks`)"),
+            });
+        }
+        let full_a = self.safe_resolve(path_a, "fs_diff_files")?;
+        let full_b = self.safe_resolve(path_b, "fs_diff_files")?;
+        debug!(a = %full_a.display(), b = %full_b.display(), "diffing files");
+
+        for path in [&full_a, &full_b] {
+            let size = tokio::fs::metadata(path).await?.len();
+            if size > MAX_DIFF_FILE_BYTES {
+                return Err(AdapterError::ExecutionFailed {
+                    tool_name: "fs_diff_files".to_string(),
+                    reason: format!(
+                        "`{}` is {size} bytes; files larger than {MAX_DIFF_FILE_BYTES} bytes \
+                         cannot be diffed",
+                        path.display(),
+                    ),
+                });
+            }
+        }
+        let old = tokio::fs::read_to_string(&full_a).await?;
+        let new = tokio::fs::read_to_string(&full_b).await?;
+
+        let diff = TextDiff::from_lines(&old, &new);
+        let (mut lines_added, mut lines_removed) = (0, 0);
+        for change in diff.iter_all_changes() {
+            match change.tag() {
+                ChangeTag::Insert => lines_added += 1,
+                ChangeTag::Delete => lines_removed += 1,
+                ChangeTag::Equal => {}
+            }
+        }
+        let mut unified = diff.unified_diff();
+        unified
+            .context_radius(DIFF_CONTEXT_LINES)
+            .header(path_a, path_b);
+
+        let mut result = json!({
+            "path_a": full_a.display().to_string(),
+            "path_b": full_b.display().to_string(),
+            "identical": lines_added == 0 && lines_removed == 0,
+            "lines_added": lines_added,
+            "lines_removed": lines_removed,
+            "hunk_count": unified.iter_hunks().count(),
+        });
+
+        if mode == "hunks" {
+            let hunks: Vec<Value> = unified
+                .iter_hunks()
+                .map(|hunk| {
+                    let changes: Vec<Value> = hunk
+                        .iter_changes()
+                        .map(|change| {
+                            json!({
+                                "op": match change.tag() {
+                                    ChangeTag::Insert => "insert",
+                                    ChangeTag::Delete => "delete",
+                                    ChangeTag::Equal => "equal",
+                                },
+                                "old_line": change.old_index().map(|i| i + 1),
+                                "new_line": change.new_index().map(|i| i + 1),
+                                "text": change.value().trim_end_matches(['\r', '\n']),
+                            })
+                        })
+                        .collect();
+                    json!({
+                        "header": hunk.header().to_string().trim_end(),
+                        "changes": changes,
+                    })
+                })
+                .collect();
+            result["hunks"] = json!(hunks);
+        } else {
+            let mut text = unified.to_string();
+            let total_chars = text.len();
+            if total_chars > MAX_FILE_READ_CHARS {
+                let mut end = MAX_FILE_READ_CHARS;
+                while !text.is_char_boundary(end) {
+                    end -= 1;
+                }
+                text.truncate(end);
+                text.push_str(&format!(
+                    "\n\n[... diff truncated at {end} chars ({total_chars} total). \
+                     Use mode `hunks` or compare smaller files.]"
+                ));
+            }
+            result["diff"] = json!(text);
+        }
+
+        Ok(result)
+    }
+
+    async fn tool_fs_watch_path(&self, params: Value) -> Result<Value> {
+        let path_str = Self::require_str(&params, "path", "fs_watch_path")?;
+        let recursive = params
+            .get("recursive")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let Some(bus) = self.bus.clone() else {
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: "fs_watch_path".to_string(),
+                reason: "no event bus is configured for file watching".to_string(),
+            });
+        };
+        let full_path = self.safe_resolve(path_str, "fs_watch_path")?;
+        tokio::fs::metadata(&full_path).await?;
+
+        let mut watchers = self.watchers();
+        if !watchers.contains_key(&full_path) && watchers.len() >= MAX_WATCHED_PATHS {
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: "fs_watch_path".to_string(),
+                reason: format!(
+                    "already watching {MAX_WATCHED_PATHS} paths; unwatch one with fs_unwatch_path first"
+                ),
+            });
+        }
+
+        let adapter_id = self.id.clone();
+        let watched = full_path.display().to_string();
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+                Ok(event) => publish_change(&bus, &adapter_id, &watched, event),
+                Err(e) => warn!(error = %e, "file watcher error"),
+            })
+            .map_err(|e| watch_error("fs_watch_path", e))?;
+        let mode = if recursive {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        watcher
+            .watch(&full_path, mode)
+            .map_err(|e| watch_error("fs_watch_path", e))?;
+
+        debug!(path = %full_path.display(), recursive, "watching path");
+        // Replacing an existing watcher drops (and stops) the old one.
+        watchers.insert(full_path.clone(), watcher);
+        Ok(json!({
+            "path": full_path.display().to_string(),
+            "recursive": recursive,
+            "watching": watchers.len(),
+            "success": true,
+        }))
+    }
+
+    async fn tool_fs_unwatch_path(&self, params: Value) -> Result<Value> {
+        let path_str = Self::require_str(&params, "path", "fs_unwatch_path")?;
+        let full_path = self.safe_resolve(path_str, "fs_unwatch_path")?;
+
+        let mut watchers = self.watchers();
+        if watchers.remove(&full_path).is_none() {
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: "fs_unwatch_path".to_string(),
+                reason: format!("`{}` is not being watched", full_path.display()),
+            });
+        }
+        debug!(path = %full_path.display(), "stopped watching path");
+        Ok(json!({
+            "path": full_path.display().to_string(),
+            "watching": watchers.len(),
+            "success": true,
+        }))
+    }
+}
+
+/// Publish one watcher event on `bus`; access events are ignored.
+fn publish_change(bus: &IpcBus, adapter_id: &str, watched: &str, event: notify::Event) {
+    let kind = match event.kind {
+        EventKind::Access(_) => return,
+        EventKind::Create(_) => "file_created",
+        EventKind::Modify(_) => "file_modified",
+        EventKind::Remove(_) => "file_removed",
+        EventKind::Any | EventKind::Other => "file_changed",
+    };
+    let paths: Vec<String> = event
+        .paths
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect();
+    let payload = json!({ "watched": watched, "paths": paths });
+    let published = bus.publish(Event::AdapterEvent {
+        adapter_id: adapter_id.to_string(),
+        kind: kind.to_string(),
+        payload: payload.to_string(),
+        timestamp: Utc::now(),
+    });
+    if let Err(e) = published {
+        warn!(error = %e, "failed to publish file change event");
+    }
+}
+
+/// Result of a destructive step the user declined.
+fn cancelled(path: &std::path::Path) -> Value {
+    json!({
+        "path": path.display().to_string(),
+        "success": false,
+        "cancelled": true,
+    })
+}
+
+fn watch_error(tool_name: &str, e: notify::Error) -> AdapterError {
+    AdapterError::ExecutionFailed {
+        tool_name: tool_name.to_string(),
+        reason: format!("cannot watch path: {e}"),
+    }
+}
+
+/// Resolve `raw_path` against `root_dir` and reject results outside it.
+///
+/// Relative paths are joined onto the canonicalized root; absolute paths
+/// are accepted only when they already point inside it.  Also confines the
+/// S3 adapter's local file transfers.
+pub(crate) fn resolve_within(
+    root_dir: &std::path::Path,
+    raw_path: &str,
+    tool_name: &str,
+) -> Result<std::path::PathBuf> {
+    // Canonicalize the root so the prefix check is reliable.
+    let canon_root = root_dir
+        .canonicalize()
+        .unwrap_or_else(|_| root_dir.to_path_buf());
+
+    let candidate = if std::path::Path::new(raw_path).is_absolute() {
+        std::path::PathBuf::from(raw_path)
+    } else {
+        canon_root.join(raw_path)
+    };
+
+    // Build a normalized path without touching the filesystem (the target
+    // may not exist yet, so canonicalize() would fail).
+    let normalized = normalize_path(&candidate);
+
+    if !normalized.starts_with(&canon_root) {
+        return Err(AdapterError::InvalidParams {
+            tool_name: tool_name.to_string(),
+            reason: format!(
+                "path `{raw_path}` resolves to `{}` which is outside the root directory `{}`",
+                normalized.display(),
+                canon_root.display(),
+            ),
+        });
+    }
+
+    Ok(normalized)
 }
 
 /// Normalize a path by resolving `.` and `..` components without touching the
@@ -303,6 +595,7 @@ impl Adapter for FilesystemAdapter {
     }
 
     async fn disconnect(&mut self) -> Result<()> {
+        self.watchers().clear();
         info!(id = %self.id, "filesystem adapter disconnected");
         self.connected = false;
         Ok(())
@@ -321,7 +614,7 @@ impl Adapter for FilesystemAdapter {
     }
 
     fn tools(&self) -> Vec<ToolDefinition> {
-        vec![
+        let mut tools = vec![
             ToolDefinition {
                 name: "fs_read_file".into(),
                 description: "Read the contents of a file".into(),
@@ -332,6 +625,7 @@ impl Adapter for FilesystemAdapter {
                     },
                     "required": ["path"]
                 }),
+                destructive: false,
             },
             ToolDefinition {
                 name: "fs_write_file".into(),
@@ -344,6 +638,7 @@ impl Adapter for FilesystemAdapter {
                     },
                     "required": ["path", "content"]
                 }),
+                destructive: true,
             },
             ToolDefinition {
                 name: "fs_list_directory".into(),
@@ -354,6 +649,7 @@ impl Adapter for FilesystemAdapter {
                         "path": { "type": "string", "description": "Directory path (default: root dir)" }
                     }
                 }),
+                destructive: false,
             },
             ToolDefinition {
                 name: "fs_create_directory".into(),
@@ -365,6 +661,7 @@ impl Adapter for FilesystemAdapter {
                     },
                     "required": ["path"]
                 }),
+                destructive: true,
             },
             ToolDefinition {
                 name: "fs_delete".into(),
@@ -376,6 +673,7 @@ impl Adapter for FilesystemAdapter {
                     },
                     "required": ["path"]
                 }),
+                destructive: true,
             },
             ToolDefinition {
                 name: "fs_str_replace".into(),
@@ -389,6 +687,7 @@ impl Adapter for FilesystemAdapter {
                     },
                     "required": ["path", "old_string", "new_string"]
                 }),
+                destructive: true,
             },
             ToolDefinition {
                 name: "fs_file_info".into(),
@@ -400,11 +699,63 @@ impl Adapter for FilesystemAdapter {
                     },
                     "required": ["path"]
                 }),
+                destructive: false,
+            },
+            ToolDefinition {
+                name: "fs_diff_files".into(),
+                description: "Compare two text files line by line. Returns added/removed line counts and a unified diff, or a structured hunk list with mode `hunks`".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "path_a": { "type": "string", "description": "The original file" },
+                        "path_b": { "type": "string", "description": "The changed file" },
+                        "mode": { "type": "string", "enum": ["unified", "hunks"], "description": "Output format (default: unified)" }
+                    },
+                    "required": ["path_a", "path_b"]
+                }),
+                destructive: false,
             },
-        ]
+        ];
+        if self.bus.is_some() {
+            tools.push(ToolDefinition {
+                name: "fs_watch_path".into(),
+                description: "Watch a file or directory and publish an event whenever something under it is created, modified, or removed".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "path": { "type": "string", "description": "File or directory to watch" },
+                        "recursive": { "type": "boolean", "description": "Also watch subdirectories (default: false)" }
+                    },
+                    "required": ["path"]
+                }),
+                destructive: false,
+            });
+            tools.push(ToolDefinition {
+                name: "fs_unwatch_path".into(),
+                description: "Stop watching a path previously passed to fs_watch_path".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "path": { "type": "string", "description": "The watched path" }
+                    },
+                    "required": ["path"]
+                }),
+                destructive: false,
+            });
+        }
+        tools
     }
 
     async fn execute_tool(&self, name: &str, params: Value) -> Result<Value> {
+        self.execute_tool_with_prompt(name, params, None).await
+    }
+
+    async fn execute_tool_with_prompt(
+        &self,
+        name: &str,
+        params: Value,
+        prompt: Option<PromptFn>,
+    ) -> Result<Value> {
         if !self.connected {
             return Err(AdapterError::ExecutionFailed {
                 tool_name: name.to_string(),
@@ -413,12 +764,15 @@ impl Adapter for FilesystemAdapter {
         }
         match name {
             "fs_read_file" => self.tool_fs_read_file(params).await,
-            "fs_write_file" => self.tool_fs_write_file(params).await,
+            "fs_write_file" => self.tool_fs_write_file(params, prompt.as_ref()).await,
             "fs_list_directory" => self.tool_fs_list_directory(params).await,
             "fs_create_directory" => self.tool_fs_create_directory(params).await,
-            "fs_delete" => self.tool_fs_delete(params).await,
+            "fs_delete" => self.tool_fs_delete(params, prompt.as_ref()).await,
             "fs_str_replace" => self.tool_fs_str_replace(params).await,
             "fs_file_info" => self.tool_fs_file_info(params).await,
+            "fs_diff_files" => self.tool_fs_diff_files(params).await,
+            "fs_watch_path" => self.tool_fs_watch_path(params).await,
+            "fs_unwatch_path" => self.tool_fs_unwatch_path(params).await,
             _ => Err(AdapterError::ToolNotFound {
                 adapter_id: self.id.clone(),
                 tool_name: name.to_string(),
@@ -430,138 +784,3 @@ impl Adapter for FilesystemAdapter {
         None
     }
 }
-
-// ---------------------------------------------------------------------------
-// Tests
-// ---------------------------------------------------------------------------
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[tokio::test]
-    async fn filesystem_adapter_tools_not_empty() {
-        let adapter = FilesystemAdapter::new("fs-test", "/tmp");
-        assert_eq!(adapter.tools().len(), 7);
-    }
-
-    #[tokio::test]
-    async fn filesystem_adapter_health_when_disconnected() {
-        let adapter = FilesystemAdapter::new("fs-test", "/tmp");
-        let status = adapter.health_check().await.unwrap();
-        assert_eq!(status, HealthStatus::Unhealthy);
-    }
-
-    #[tokio::test]
-    async fn filesystem_adapter_rejects_when_not_connected() {
-        let adapter = FilesystemAdapter::new("fs-test", "/tmp");
-        let result = adapter
-            .execute_tool("fs_read_file", json!({"path": "/tmp/test.txt"}))
-            .await;
-        assert!(result.is_err());
-    }
-
-    #[tokio::test]
-    async fn path_traversal_is_blocked() {
-        let adapter = FilesystemAdapter::new("fs-test", "/tmp/sandbox");
-        let result = adapter.safe_resolve("../../etc/passwd", "fs_read_file");
-        assert!(result.is_err());
-        let err_msg = format!("{}", result.unwrap_err());
-        assert!(err_msg.contains("outside the root directory"));
-    }
-
-    #[test]
-    fn normalize_path_resolves_parent_components() {
-        let p = std::path::Path::new("/tmp/sandbox/sub/../other");
-        let norm = normalize_path(p);
-        assert_eq!(norm, std::path::PathBuf::from("/tmp/sandbox/other"));
-    }
-
-    #[test]
-    fn normalize_path_resolves_current_dir_components() {
-        let p = std::path::Path::new("/tmp/./sandbox/./file.txt");
-        let norm = normalize_path(p);
-        assert_eq!(norm, std::path::PathBuf::from("/tmp/sandbox/file.txt"));
-    }
-
-    #[tokio::test]
-    async fn fs_str_replace_success() {
-        let dir = tempfile::tempdir().unwrap();
-        let root = dir.path().canonicalize().unwrap();
-        let file_path = root.join("replace_test.txt");
-        tokio::fs::write(&file_path, "Hello, World!").await.unwrap();
-
-        let mut adapter = FilesystemAdapter::new("fs-test", &root);
-        adapter.connect().await.unwrap();
-
-        let result = adapter
-            .execute_tool(
-                "fs_str_replace",
-                json!({
-                    "path": file_path.to_string_lossy(),
-                    "old_string": "World",
-                    "new_string": "Rust",
-                }),
-            )
-            .await
-            .unwrap();
-
-        assert_eq!(result["success"], true);
-        assert_eq!(result["match_count"], 1);
-
-        let content = tokio::fs::read_to_string(&file_path).await.unwrap();
-        assert_eq!(content, "Hello, Rust!");
-    }
-
-    #[tokio::test]
-    async fn fs_str_replace_not_found() {
-        let dir = tempfile::tempdir().unwrap();
-        let root = dir.path().canonicalize().unwrap();
-        let file_path = root.join("replace_test.txt");
-        tokio::fs::write(&file_path, "Hello, World!").await.unwrap();
-
-        let mut adapter = FilesystemAdapter::new("fs-test", &root);
-        adapter.connect().await.unwrap();
-
-        let result = adapter
-            .execute_tool(
-                "fs_str_replace",
-                json!({
-                    "path": file_path.to_string_lossy(),
-                    "old_string": "does_not_exist",
-                    "new_string": "anything",
-                }),
-            )
-            .await;
-
-        assert!(result.is_err());
-        let err_msg = format!("{}", result.unwrap_err());
-        assert!(err_msg.contains("old_string not found"));
-    }
-
-    #[tokio::test]
-    async fn fs_str_replace_ambiguous() {
-        let dir = tempfile::tempdir().unwrap();
-        let root = dir.path().canonicalize().unwrap();
-        let file_path = root.join("replace_test.txt");
-        tokio::fs::write(&file_path, "aaa bbb aaa").await.unwrap();
-
-        let mut adapter = FilesystemAdapter::new("fs-test", &root);
-        adapter.connect().await.unwrap();
-
-        let result = adapter
-            .execute_tool(
-                "fs_str_replace",
-                json!({
-                    "path": file_path.to_string_lossy(),
-                    "old_string": "aaa",
-                    "new_string": "ccc",
-                }),
-            )
-            .await;
-
-        assert!(result.is_err());
-        let err_msg = format!("{}", result.unwrap_err());
-        assert!(err_msg.contains("matches 2 times"));
-    }
-}
@@ -0,0 +1,303 @@
+//! Unit tests for the filesystem adapter.
+
+use super::*;
+
+#[tokio::test]
+async fn filesystem_adapter_tools_not_empty() {
+    let adapter = FilesystemAdapter::new("fs-test", "/tmp");
+    assert_eq!(adapter.tools().len(), 8);
+}
+
+#[tokio::test]
+async fn filesystem_adapter_health_when_disconnected() {
+    let adapter = FilesystemAdapter::new("fs-test", "/tmp");
+    let status = adapter.health_check().await.unwrap();
+    assert_eq!(status, HealthStatus::Unhealthy);
+}
+
+#[tokio::test]
+async fn filesystem_adapter_rejects_when_not_connected() {
+    let adapter = FilesystemAdapter::new("fs-test", "/tmp");
+    let result = adapter
+        .execute_tool("fs_read_file", json!({"path": "/tmp/test.txt"}))
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn path_traversal_is_blocked() {
+    let adapter = FilesystemAdapter::new("fs-test", "/tmp/sandbox");
+    let result = adapter.safe_resolve("../../etc/passwd", "fs_read_file");
+    assert!(result.is_err());
+    let err_msg = format!("{}", result.unwrap_err());
+    assert!(err_msg.contains("outside the root directory"));
+}
+
+#[test]
+fn normalize_path_resolves_parent_components() {
+    let p = std::path::Path::new("/tmp/sandbox/sub/../other");
+    let norm = normalize_path(p);
+    assert_eq!(norm, std::path::PathBuf::from("/tmp/sandbox/other"));
+}
+
+#[test]
+fn normalize_path_resolves_current_dir_components() {
+    let p = std::path::Path::new("/tmp/./sandbox/./file.txt");
+    let norm = normalize_path(p);
+    assert_eq!(norm, std::path::PathBuf::from("/tmp/sandbox/file.txt"));
+}
+
+#[tokio::test]
+async fn fs_str_replace_success() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().canonicalize().unwrap();
+    let file_path = root.join("replace_test.txt");
+    tokio::fs::write(&file_path, "Hello, World!").await.unwrap();
+
+    let mut adapter = FilesystemAdapter::new("fs-test", &root);
+    adapter.connect().await.unwrap();
+
+    let result = adapter
+        .execute_tool(
+            "fs_str_replace",
+            json!({
+                "path": file_path.to_string_lossy(),
+                "old_string": "World",
+                "new_string": "Rust",
+            }),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(result["success"], true);
+    assert_eq!(result["match_count"], 1);
+
+    let content = tokio::fs::read_to_string(&file_path).await.unwrap();
+    assert_eq!(content, "Hello, Rust!");
+}
+
+#[tokio::test]
+async fn fs_str_replace_not_found() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().canonicalize().unwrap();
+    let file_path = root.join("replace_test.txt");
+    tokio::fs::write(&file_path, "Hello, World!").await.unwrap();
+
+    let mut adapter = FilesystemAdapter::new("fs-test", &root);
+    adapter.connect().await.unwrap();
+
+    let result = adapter
+        .execute_tool(
+            "fs_str_replace",
+            json!({
+                "path": file_path.to_string_lossy(),
+                "old_string": "does_not_exist",
+                "new_string": "anything",
+            }),
+        )
+        .await;
+
+    assert!(result.is_err());
+    let err_msg = format!("{}", result.unwrap_err());
+    assert!(err_msg.contains("old_string not found"));
+}
+
+#[tokio::test]
+async fn fs_str_replace_ambiguous() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().canonicalize().unwrap();
+    let file_path = root.join("replace_test.txt");
+    tokio::fs::write(&file_path, "aaa bbb aaa").await.unwrap();
+
+    let mut adapter = FilesystemAdapter::new("fs-test", &root);
+    adapter.connect().await.unwrap();
+
+    let result = adapter
+        .execute_tool(
+            "fs_str_replace",
+            json!({
+                "path": file_path.to_string_lossy(),
+                "old_string": "aaa",
+                "new_string": "ccc",
+            }),
+        )
+        .await;
+
+    assert!(result.is_err());
+    let err_msg = format!("{}", result.unwrap_err());
+    assert!(err_msg.contains("matches 2 times"));
+}
+
+#[tokio::test]
+async fn fs_diff_files_reports_added_and_removed_lines() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().canonicalize().unwrap();
+    tokio::fs::write(root.join("old.txt"), "alpha\nbeta\ngamma\ndelta\n")
+        .await
+        .unwrap();
+    tokio::fs::write(root.join("new.txt"), "alpha\nbeta\nGAMMA\ndelta\nepsilon\n")
+        .await
+        .unwrap();
+
+    let mut adapter = FilesystemAdapter::new("fs-test", &root);
+    adapter.connect().await.unwrap();
+
+    let unified = adapter
+        .execute_tool(
+            "fs_diff_files",
+            json!({"path_a": "old.txt", "path_b": "new.txt"}),
+        )
+        .await
+        .unwrap();
+    assert_eq!(unified["identical"], false);
+    assert_eq!(unified["lines_added"], 2);
+    assert_eq!(unified["lines_removed"], 1);
+    let diff = unified["diff"].as_str().unwrap();
+    assert!(diff.contains("--- old.txt\n+++ new.txt"), "{diff}");
+    assert!(diff.contains("\n-gamma\n"), "{diff}");
+    assert!(diff.contains("\n+GAMMA\n"), "{diff}");
+    assert!(diff.contains("\n+epsilon\n"), "{diff}");
+
+    let hunks = adapter
+        .execute_tool(
+            "fs_diff_files",
+            json!({"path_a": "old.txt", "path_b": "new.txt", "mode": "hunks"}),
+        )
+        .await
+        .unwrap();
+    let changes = hunks["hunks"][0]["changes"].as_array().unwrap();
+    assert!(changes.contains(&json!({
+        "op": "delete", "old_line": 3, "new_line": null, "text": "gamma"
+    })));
+    assert!(changes.contains(&json!({
+        "op": "insert", "old_line": null, "new_line": 5, "text": "epsilon"
+    })));
+
+    let escape = adapter
+        .execute_tool(
+            "fs_diff_files",
+            json!({"path_a": "old.txt", "path_b": "../../etc/passwd"}),
+        )
+        .await;
+    assert!(escape.is_err());
+}
+
+#[tokio::test]
+async fn fs_watch_path_publishes_created_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().canonicalize().unwrap();
+    tokio::fs::create_dir(root.join("inbox")).await.unwrap();
+
+    let bus = IpcBus::new(16);
+    let mut events = bus.subscribe();
+    let mut adapter = FilesystemAdapter::new("fs-test", &root).with_bus(bus);
+    adapter.connect().await.unwrap();
+    assert_eq!(adapter.tools().len(), 10);
+
+    adapter
+        .execute_tool("fs_watch_path", json!({"path": "inbox"}))
+        .await
+        .unwrap();
+    tokio::fs::write(root.join("inbox/report.txt"), "hello")
+        .await
+        .unwrap();
+
+    let created = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        loop {
+            let event = events.recv().await.unwrap();
+            if let Event::AdapterEvent { kind, payload, .. } = &*event
+                && kind == "file_created"
+            {
+                return serde_json::from_str::<Value>(payload).unwrap();
+            }
+        }
+    })
+    .await
+    .expect("no file_created event within 5s");
+    assert!(
+        created["paths"][0]
+            .as_str()
+            .unwrap()
+            .ends_with("inbox/report.txt"),
+        "{created}"
+    );
+
+    adapter
+        .execute_tool("fs_unwatch_path", json!({"path": "inbox"}))
+        .await
+        .unwrap();
+    let escape = adapter
+        .execute_tool("fs_watch_path", json!({"path": "../.."}))
+        .await;
+    assert!(escape.is_err());
+}
+
+/// A prompt that answers `answer` and records every question it is asked.
+fn answering(answer: &'static str) -> (PromptFn, std::sync::Arc<Mutex<Vec<String>>>) {
+    let asked = std::sync::Arc::new(Mutex::new(Vec::new()));
+    let log = std::sync::Arc::clone(&asked);
+    let prompt: PromptFn = std::sync::Arc::new(move |question: &str| {
+        log.lock().unwrap().push(question.to_owned());
+        Some(answer.to_owned())
+    });
+    (prompt, asked)
+}
+
+#[tokio::test]
+async fn fs_delete_asks_before_deleting() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().canonicalize().unwrap();
+    let file_path = root.join("keep.txt");
+    tokio::fs::write(&file_path, "data").await.unwrap();
+
+    let mut adapter = FilesystemAdapter::new("fs-test", &root);
+    adapter.connect().await.unwrap();
+    let params = json!({"path": file_path.to_string_lossy()});
+
+    let (no, asked) = answering("n");
+    let result = adapter
+        .execute_tool_with_prompt("fs_delete", params.clone(), Some(no))
+        .await
+        .unwrap();
+    assert_eq!(result["cancelled"], true);
+    assert!(file_path.exists());
+    assert_eq!(asked.lock().unwrap().len(), 1);
+    assert!(asked.lock().unwrap()[0].starts_with("Delete `"));
+
+    let (yes, _) = answering("y");
+    let result = adapter
+        .execute_tool_with_prompt("fs_delete", params, Some(yes))
+        .await
+        .unwrap();
+    assert_eq!(result["success"], true);
+    assert!(!file_path.exists());
+}
+
+#[tokio::test]
+async fn fs_write_file_asks_only_before_overwriting() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().canonicalize().unwrap();
+    let file_path = root.join("notes.txt");
+
+    let mut adapter = FilesystemAdapter::new("fs-test", &root);
+    adapter.connect().await.unwrap();
+    let params = json!({"path": file_path.to_string_lossy(), "content": "new"});
+
+    let (no, asked) = answering("n");
+    let result = adapter
+        .execute_tool_with_prompt("fs_write_file", params.clone(), Some(no.clone()))
+        .await
+        .unwrap();
+    assert_eq!(result["success"], true);
+    assert!(asked.lock().unwrap().is_empty());
+
+    tokio::fs::write(&file_path, "old").await.unwrap();
+    let result = adapter
+        .execute_tool_with_prompt("fs_write_file", params, Some(no))
+        .await
+        .unwrap();
+    assert_eq!(result["cancelled"], true);
+    assert_eq!(asked.lock().unwrap().len(), 1);
+    let content = tokio::fs::read_to_string(&file_path).await.unwrap();
+    assert_eq!(content, "old");
+}
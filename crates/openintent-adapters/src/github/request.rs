@@ -0,0 +1,174 @@
+//! Requests to the GitHub REST API.
+//!
+//! Every tool call goes through [`GitHubAdapter::send_request`], which turns
+//! GitHub's rate-limit responses into [`AdapterError::RateLimited`] so the
+//! agent runtime can back off and retry, and warns when the remaining quota
+//! runs low.
+
+use serde_json::{Value, json};
+use tracing::warn;
+
+use super::GitHubAdapter;
+use crate::error::{AdapterError, Result};
+
+impl GitHubAdapter {
+    /// Build a GET request with standard GitHub headers.
+    pub(super) fn get_request(&self, url: &str, token: &str) -> reqwest::RequestBuilder {
+        self.client
+            .get(url)
+            .header("Accept", "application/vnd.github+json")
+            .header("Authorization", format!("Bearer {token}"))
+            .header("X-GitHub-Api-Version", "2022-11-28")
+    }
+
+    /// Build a POST request with standard GitHub headers.
+    pub(super) fn post_request(&self, url: &str, token: &str) -> reqwest::RequestBuilder {
+        self.client
+            .post(url)
+            .header("Accept", "application/vnd.github+json")
+            .header("Authorization", format!("Bearer {token}"))
+            .header("X-GitHub-Api-Version", "2022-11-28")
+    }
+
+    /// Send a request and parse the JSON response, handling rate limits.
+    pub(super) async fn send_request(
+        &self,
+        request: reqwest::RequestBuilder,
+        tool_name: &str,
+    ) -> Result<Value> {
+        let response = request.send().await.map_err(|e| {
+            if e.is_timeout() {
+                AdapterError::Timeout {
+                    seconds: 30,
+                    reason: format!("GitHub API request timed out: {e}"),
+                }
+            } else {
+                AdapterError::ExecutionFailed {
+                    tool_name: tool_name.to_string(),
+                    reason: format!("GitHub API request failed: {e}"),
+                }
+            }
+        })?;
+
+        let status = response.status();
+
+        // Check rate limit headers.
+        let rate_remaining = response
+            .headers()
+            .get("x-ratelimit-remaining")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok());
+
+        if let Some(remaining) = rate_remaining
+            && remaining < 10
+        {
+            warn!(
+                remaining = remaining,
+                tool = tool_name,
+                "GitHub API rate limit is low"
+            );
+        }
+
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            || (status == reqwest::StatusCode::FORBIDDEN && rate_remaining == Some(0))
+        {
+            let retry_after_secs = response
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u64>().ok());
+            return Err(AdapterError::RateLimited {
+                adapter_id: self.id.clone(),
+                retry_after_secs,
+            });
+        }
+
+        let body_text = response
+            .text()
+            .await
+            .map_err(|e| AdapterError::ExecutionFailed {
+                tool_name: tool_name.to_string(),
+                reason: format!("failed to read response body: {e}"),
+            })?;
+
+        if !status.is_success() {
+            let error_body: Value = serde_json::from_str(&body_text)
+                .unwrap_or_else(|_| json!({ "message": body_text }));
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: tool_name.to_string(),
+                reason: format!(
+                    "GitHub API returned {}: {}",
+                    status.as_u16(),
+                    error_body
+                        .get("message")
+                        .and_then(|m| m.as_str())
+                        .unwrap_or(&body_text)
+                ),
+            });
+        }
+
+        serde_json::from_str(&body_text).map_err(|e| AdapterError::ExecutionFailed {
+            tool_name: tool_name.to_string(),
+            reason: format!("failed to parse GitHub API response as JSON: {e}"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+
+    /// Answer one request with `status` and extra `headers`.
+    async fn serve_once(
+        listener: tokio::net::TcpListener,
+        status: &'static str,
+        headers: &'static str,
+    ) {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 4096];
+        let _ = stream.read(&mut buf).await.unwrap();
+        let body = r#"{"message":"API rate limit exceeded"}"#;
+        let response = format!(
+            "HTTP/1.1 {status}\r\n{headers}Content-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(response.as_bytes()).await.unwrap();
+    }
+
+    async fn request_against(status: &'static str, headers: &'static str) -> Result<Value> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(serve_once(listener, status, headers));
+        let adapter = GitHubAdapter::with_base_url("github", &base);
+        let request = adapter.get_request(&adapter.api_url("/user/repos"), "TOKEN");
+        adapter.send_request(request, "github_list_repos").await
+    }
+
+    #[tokio::test]
+    async fn rate_limit_responses_are_retryable() {
+        let err = request_against("429 Too Many Requests", "Retry-After: 30\r\n")
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AdapterError::RateLimited {
+                retry_after_secs: Some(30),
+                ..
+            }
+        ));
+
+        let err = request_against("403 Forbidden", "X-RateLimit-Remaining: 0\r\n")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AdapterError::RateLimited { .. }));
+    }
+
+    #[tokio::test]
+    async fn other_errors_carry_the_github_message() {
+        let err = request_against("403 Forbidden", "").await.unwrap_err();
+        assert!(matches!(err, AdapterError::ExecutionFailed { .. }));
+        assert!(err.to_string().contains("API rate limit exceeded"));
+    }
+}
@@ -0,0 +1,339 @@
+//! Unit tests for the GitHub adapter.
+
+use super::*;
+
+// -- Construction tests --
+
+#[test]
+fn new_creates_adapter_with_defaults() {
+    let adapter = GitHubAdapter::new("gh-test");
+    assert_eq!(adapter.id, "gh-test");
+    assert!(!adapter.connected);
+    assert!(adapter.token.is_none());
+    assert_eq!(adapter.base_url, DEFAULT_BASE_URL);
+}
+
+#[test]
+fn with_token_sets_token() {
+    let adapter = GitHubAdapter::with_token("gh-test", "ghp_abc123");
+    assert_eq!(adapter.id, "gh-test");
+    assert_eq!(adapter.token.as_deref(), Some("ghp_abc123"));
+    assert_eq!(adapter.base_url, DEFAULT_BASE_URL);
+}
+
+#[test]
+fn with_base_url_sets_custom_url() {
+    let adapter = GitHubAdapter::with_base_url("gh-ent", "https://github.example.com/api/v3/");
+    assert_eq!(adapter.base_url, "https://github.example.com/api/v3");
+    assert!(adapter.token.is_none());
+}
+
+// -- Adapter trait basics --
+
+#[test]
+fn adapter_id_returns_id() {
+    let adapter = GitHubAdapter::new("my-gh");
+    assert_eq!(adapter.id(), "my-gh");
+}
+
+#[test]
+fn adapter_type_is_devtools() {
+    let adapter = GitHubAdapter::new("gh");
+    assert_eq!(adapter.adapter_type(), AdapterType::DevTools);
+}
+
+#[test]
+fn required_auth_returns_github_scopes() {
+    let adapter = GitHubAdapter::new("gh");
+    let auth = adapter.required_auth().expect("should require auth");
+    assert_eq!(auth.provider, "github");
+    assert!(auth.scopes.contains(&"repo".to_string()));
+    assert!(auth.scopes.contains(&"read:org".to_string()));
+}
+
+// -- Tool definitions --
+
+#[test]
+fn tools_returns_exactly_ten() {
+    let adapter = GitHubAdapter::new("gh");
+    let tools = adapter.tools();
+    assert_eq!(tools.len(), 10);
+}
+
+#[test]
+fn tools_have_expected_names() {
+    let adapter = GitHubAdapter::new("gh");
+    let names: Vec<String> = adapter.tools().iter().map(|t| t.name.clone()).collect();
+    let expected = vec![
+        "github_list_repos",
+        "github_get_repo",
+        "github_list_issues",
+        "github_create_issue",
+        "github_get_issue",
+        "github_list_pull_requests",
+        "github_get_pull_request",
+        "github_create_pull_request",
+        "github_search_code",
+        "github_get_file_content",
+    ];
+    assert_eq!(names, expected);
+}
+
+#[test]
+fn tool_parameters_have_required_fields() {
+    let adapter = GitHubAdapter::new("gh");
+    let tools = adapter.tools();
+
+    // github_get_repo requires owner and repo
+    let get_repo = tools.iter().find(|t| t.name == "github_get_repo").unwrap();
+    let required = get_repo.parameters["required"]
+        .as_array()
+        .expect("required should be an array");
+    assert!(required.contains(&json!("owner")));
+    assert!(required.contains(&json!("repo")));
+
+    // github_create_issue requires owner, repo, title
+    let create_issue = tools
+        .iter()
+        .find(|t| t.name == "github_create_issue")
+        .unwrap();
+    let required = create_issue.parameters["required"]
+        .as_array()
+        .expect("required should be an array");
+    assert!(required.contains(&json!("owner")));
+    assert!(required.contains(&json!("repo")));
+    assert!(required.contains(&json!("title")));
+
+    // github_create_pull_request requires owner, repo, title, head, base
+    let create_pr = tools
+        .iter()
+        .find(|t| t.name == "github_create_pull_request")
+        .unwrap();
+    let required = create_pr.parameters["required"]
+        .as_array()
+        .expect("required should be an array");
+    assert_eq!(required.len(), 5);
+    assert!(required.contains(&json!("head")));
+    assert!(required.contains(&json!("base")));
+
+    // github_search_code requires query
+    let search = tools
+        .iter()
+        .find(|t| t.name == "github_search_code")
+        .unwrap();
+    let required = search.parameters["required"]
+        .as_array()
+        .expect("required should be an array");
+    assert!(required.contains(&json!("query")));
+}
+
+#[test]
+fn tool_parameters_list_repos_has_no_required_fields() {
+    let adapter = GitHubAdapter::new("gh");
+    let tools = adapter.tools();
+    let list_repos = tools
+        .iter()
+        .find(|t| t.name == "github_list_repos")
+        .unwrap();
+    let required = list_repos.parameters["required"]
+        .as_array()
+        .expect("required should be an array");
+    assert!(required.is_empty());
+}
+
+// -- Health check when not connected --
+
+#[tokio::test]
+async fn health_check_returns_unhealthy_when_disconnected() {
+    let adapter = GitHubAdapter::new("gh");
+    let status = adapter.health_check().await.unwrap();
+    assert_eq!(status, HealthStatus::Unhealthy);
+}
+
+// -- Token resolution --
+
+#[test]
+fn resolve_token_uses_configured_token() {
+    let adapter = GitHubAdapter::with_token("gh", "configured-token");
+    let token = adapter.resolve_token(&json!({})).unwrap();
+    assert_eq!(token, "configured-token");
+}
+
+#[test]
+fn resolve_token_per_call_overrides_configured() {
+    let adapter = GitHubAdapter::with_token("gh", "configured-token");
+    let token = adapter
+        .resolve_token(&json!({"token": "per-call-token"}))
+        .unwrap();
+    assert_eq!(token, "per-call-token");
+}
+
+#[test]
+fn resolve_token_fails_when_none_available() {
+    let adapter = GitHubAdapter::new("gh");
+    let result = adapter.resolve_token(&json!({}));
+    assert!(result.is_err());
+}
+
+#[test]
+fn resolve_token_ignores_empty_per_call_token() {
+    let adapter = GitHubAdapter::with_token("gh", "configured-token");
+    let token = adapter.resolve_token(&json!({"token": ""})).unwrap();
+    assert_eq!(token, "configured-token");
+}
+
+// -- URL construction --
+
+#[test]
+fn api_url_constructs_correct_urls() {
+    let adapter = GitHubAdapter::new("gh");
+    assert_eq!(adapter.api_url("/user"), "https://api.github.com/user");
+    assert_eq!(
+        adapter.api_url("/repos/octocat/hello-world"),
+        "https://api.github.com/repos/octocat/hello-world"
+    );
+}
+
+#[test]
+fn api_url_works_with_custom_base_url() {
+    let adapter = GitHubAdapter::with_base_url("gh-ent", "https://github.example.com/api/v3");
+    assert_eq!(
+        adapter.api_url("/user"),
+        "https://github.example.com/api/v3/user"
+    );
+}
+
+// -- Execute tool when not connected --
+
+#[tokio::test]
+async fn execute_tool_rejects_when_not_connected() {
+    let adapter = GitHubAdapter::with_token("gh", "some-token");
+    let result = adapter.execute_tool("github_list_repos", json!({})).await;
+    assert!(result.is_err());
+    let err = result.unwrap_err();
+    assert!(
+        err.to_string().contains("not connected"),
+        "error should mention not connected: {err}"
+    );
+}
+
+// -- Execute tool rejects unknown tool --
+
+#[tokio::test]
+async fn execute_tool_rejects_unknown_tool() {
+    let mut adapter = GitHubAdapter::with_token("gh", "some-token");
+    adapter.connected = true;
+    let result = adapter.execute_tool("nonexistent_tool", json!({})).await;
+    assert!(result.is_err());
+    let err = result.unwrap_err();
+    assert!(
+        err.to_string().contains("tool not found"),
+        "error should mention tool not found: {err}"
+    );
+}
+
+// -- Missing required parameters --
+
+#[tokio::test]
+async fn get_repo_rejects_missing_owner() {
+    let mut adapter = GitHubAdapter::with_token("gh", "token");
+    adapter.connected = true;
+    let result = adapter
+        .execute_tool("github_get_repo", json!({"repo": "test"}))
+        .await;
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("owner"));
+}
+
+#[tokio::test]
+async fn get_repo_rejects_missing_repo() {
+    let mut adapter = GitHubAdapter::with_token("gh", "token");
+    adapter.connected = true;
+    let result = adapter
+        .execute_tool("github_get_repo", json!({"owner": "test"}))
+        .await;
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("repo"));
+}
+
+#[tokio::test]
+async fn create_issue_rejects_missing_title() {
+    let mut adapter = GitHubAdapter::with_token("gh", "token");
+    adapter.connected = true;
+    let result = adapter
+        .execute_tool(
+            "github_create_issue",
+            json!({"owner": "test", "repo": "test"}),
+        )
+        .await;
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("title"));
+}
+
+#[tokio::test]
+async fn search_code_rejects_missing_query() {
+    let mut adapter = GitHubAdapter::with_token("gh", "token");
+    adapter.connected = true;
+    let result = adapter.execute_tool("github_search_code", json!({})).await;
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("query"));
+}
+
+#[tokio::test]
+async fn get_file_content_rejects_missing_path() {
+    let mut adapter = GitHubAdapter::with_token("gh", "token");
+    adapter.connected = true;
+    let result = adapter
+        .execute_tool(
+            "github_get_file_content",
+            json!({"owner": "test", "repo": "test"}),
+        )
+        .await;
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("path"));
+}
+
+#[tokio::test]
+async fn create_pr_rejects_missing_head() {
+    let mut adapter = GitHubAdapter::with_token("gh", "token");
+    adapter.connected = true;
+    let result = adapter
+        .execute_tool(
+            "github_create_pull_request",
+            json!({"owner": "o", "repo": "r", "title": "t", "base": "main"}),
+        )
+        .await;
+    assert!(result.is_err());
+    assert!(result.unwrap_err().to_string().contains("head"));
+}
+
+// -- Connect / disconnect --
+
+#[tokio::test]
+async fn connect_succeeds_without_token() {
+    let mut adapter = GitHubAdapter::new("gh");
+    let result = adapter.connect().await;
+    assert!(result.is_ok());
+    assert!(adapter.connected);
+}
+
+#[tokio::test]
+async fn disconnect_sets_connected_false() {
+    let mut adapter = GitHubAdapter::new("gh");
+    adapter.connected = true;
+    adapter.disconnect().await.unwrap();
+    assert!(!adapter.connected);
+}
+
+// -- URL encoding --
+
+#[test]
+fn urlencoding_encodes_spaces_and_special_chars() {
+    assert_eq!(urlencoding::encode("hello world"), "hello%20world");
+    assert_eq!(urlencoding::encode("a+b"), "a%2Bb");
+    assert_eq!(urlencoding::encode("foo/bar"), "foo%2Fbar");
+    assert_eq!(
+        urlencoding::encode("safe-string_v1.0~beta"),
+        "safe-string_v1.0~beta"
+    );
+}
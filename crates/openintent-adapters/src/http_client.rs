@@ -0,0 +1,431 @@
+//! Shared HTTP client factory for network adapters.
+//!
+//! Every adapter that talks to the network builds its `reqwest::Client`
+//! through [`HttpClientFactory`], so timeouts, the user agent, proxy and
+//! client-certificate settings are configured in one place.
+//!
+//! Egress follows the process-wide [`NetworkMode`], set once at startup
+//! with [`set_network_mode`].  In [`NetworkMode::Direct`] proxies are read
+//! from the standard `HTTP_PROXY`, `HTTPS_PROXY` and `NO_PROXY` environment
+//! variables; in [`NetworkMode::Offline`] network adapters fail fast with
+//! [`AdapterError::NetworkDisabled`].
+//!
+//! When a vault is attached, [`HttpClientFactory::authorize`] looks up the
+//! credential mapped to a request's host and sends it as a bearer token.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use openintent_vault::Vault;
+use reqwest::RequestBuilder;
+use reqwest::header::{AUTHORIZATION, HeaderValue};
+use tracing::{debug, warn};
+use url::Url;
+
+use crate::error::{AdapterError, Result};
+
+/// Default total request timeout.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default TCP/TLS connect timeout.
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default `User-Agent` header.
+pub const DEFAULT_USER_AGENT: &str = concat!("OpenIntentOS/", env!("CARGO_PKG_VERSION"));
+
+/// How network adapters may reach the outside world.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum NetworkMode {
+    /// Connect directly, honouring the proxy environment variables.
+    #[default]
+    Direct,
+    /// Send all traffic through the proxy at this URL.
+    Proxy(String),
+    /// No network access; network tools fail immediately.
+    Offline,
+}
+
+impl FromStr for NetworkMode {
+    type Err = AdapterError;
+
+    /// Parse `direct`, `offline`, or a proxy URL.
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim() {
+            "direct" => Ok(Self::Direct),
+            "offline" => Ok(Self::Offline),
+            url => {
+                reqwest::Proxy::all(url).map_err(|e| {
+                    AdapterError::ConfigError(format!("invalid network mode `{url}`: {e}"))
+                })?;
+                Ok(Self::Proxy(url.to_string()))
+            }
+        }
+    }
+}
+
+/// The process-wide network mode.
+static NETWORK_MODE: OnceLock<NetworkMode> = OnceLock::new();
+
+/// Set the process-wide network mode.
+///
+/// Must be called before any adapter is created; fails if a mode has
+/// already been set or read.
+pub fn set_network_mode(mode: NetworkMode) -> Result<()> {
+    NETWORK_MODE.set(mode).map_err(|_| {
+        AdapterError::ConfigError(format!(
+            "network mode is already set to {:?}",
+            network_mode()
+        ))
+    })
+}
+
+/// The process-wide network mode, [`NetworkMode::Direct`] unless set.
+pub fn network_mode() -> &'static NetworkMode {
+    NETWORK_MODE.get_or_init(NetworkMode::default)
+}
+
+/// Fail with [`AdapterError::NetworkDisabled`] if the process is offline.
+///
+/// Network adapters that do not hold an [`HttpClientFactory`] call this
+/// before touching the network.
+pub fn ensure_online() -> Result<()> {
+    match network_mode() {
+        NetworkMode::Offline => Err(AdapterError::NetworkDisabled),
+        _ => Ok(()),
+    }
+}
+
+/// Builds consistently configured HTTP clients for adapters.
+#[derive(Clone)]
+pub struct HttpClientFactory {
+    timeout: Duration,
+    connect_timeout: Duration,
+    user_agent: String,
+    /// Network mode, taken from the process-wide mode when created.
+    mode: NetworkMode,
+    /// Explicit proxy URL; overrides the network mode's proxy.
+    proxy: Option<String>,
+    /// PEM-encoded client certificate and private key for mutual TLS.
+    identity_pem: Option<Vec<u8>>,
+    /// Vault consulted for per-host credentials.
+    vault: Option<Arc<Mutex<Vault>>>,
+    /// Host name -> vault provider whose credential is sent to that host.
+    host_credentials: HashMap<String, String>,
+}
+
+impl Default for HttpClientFactory {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            mode: network_mode().clone(),
+            proxy: None,
+            identity_pem: None,
+            vault: None,
+            host_credentials: HashMap::new(),
+        }
+    }
+}
+
+impl std::fmt::Debug for HttpClientFactory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpClientFactory")
+            .field("timeout", &self.timeout)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("user_agent", &self.user_agent)
+            .field("mode", &self.mode)
+            .field("proxy", &self.proxy)
+            .field("mtls", &self.identity_pem.is_some())
+            .field("vault", &self.vault.is_some())
+            .field("host_credentials", &self.host_credentials)
+            .finish()
+    }
+}
+
+impl HttpClientFactory {
+    /// Create a factory with the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the total request timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the connect timeout.
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Set the `User-Agent` header.
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Use `mode` instead of the process-wide network mode.
+    pub fn with_network_mode(mut self, mode: NetworkMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Route all requests through `proxy_url`, whatever the network mode.
+    pub fn with_proxy(mut self, proxy_url: impl Into<String>) -> Self {
+        self.proxy = Some(proxy_url.into());
+        self
+    }
+
+    /// Present a client certificate for mutual TLS.
+    ///
+    /// `pem` must contain both the certificate chain and the private key.
+    pub fn with_client_identity(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.identity_pem = Some(pem.into());
+        self
+    }
+
+    /// Attach the vault that per-host credentials are read from.
+    pub fn with_vault(mut self, vault: Arc<Mutex<Vault>>) -> Self {
+        self.vault = Some(vault);
+        self
+    }
+
+    /// Send the vault credential stored under `provider` to `host`.
+    pub fn with_host_credential(
+        mut self,
+        host: impl Into<String>,
+        provider: impl Into<String>,
+    ) -> Self {
+        self.host_credentials
+            .insert(host.into().to_ascii_lowercase(), provider.into());
+        self
+    }
+
+    /// The configured request timeout.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// The configured `User-Agent` header.
+    pub fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+
+    /// The network mode clients from this factory follow.
+    pub fn network_mode(&self) -> &NetworkMode {
+        &self.mode
+    }
+
+    /// Fail with [`AdapterError::NetworkDisabled`] if this factory is offline.
+    pub fn ensure_online(&self) -> Result<()> {
+        match self.mode {
+            NetworkMode::Offline => Err(AdapterError::NetworkDisabled),
+            _ => Ok(()),
+        }
+    }
+
+    /// A client builder with the factory's settings applied.
+    ///
+    /// Adapters that need extra options (redirect policy, DNS overrides)
+    /// start from this instead of `reqwest::Client::builder()`.
+    pub fn builder(&self) -> Result<reqwest::ClientBuilder> {
+        let mut builder = reqwest::Client::builder()
+            .timeout(self.timeout)
+            .connect_timeout(self.connect_timeout)
+            .user_agent(&self.user_agent);
+
+        let proxy_url = match (&self.proxy, &self.mode) {
+            (Some(url), _) | (None, NetworkMode::Proxy(url)) => Some(url),
+            _ => None,
+        };
+        if let Some(url) = proxy_url {
+            let proxy = reqwest::Proxy::all(url.as_str()).map_err(|e| {
+                AdapterError::ConfigError(format!("invalid proxy URL `{url}`: {e}"))
+            })?;
+            builder = builder.proxy(proxy);
+        }
+
+        if let Some(pem) = &self.identity_pem {
+            let identity = reqwest::Identity::from_pem(pem).map_err(|e| {
+                AdapterError::ConfigError(format!("invalid client certificate: {e}"))
+            })?;
+            builder = builder.identity(identity);
+        }
+
+        Ok(builder)
+    }
+
+    /// Build a client without any injected credentials.
+    pub fn build(&self) -> Result<reqwest::Client> {
+        self.builder()?
+            .build()
+            .map_err(|e| AdapterError::ConfigError(format!("failed to build HTTP client: {e}")))
+    }
+
+    /// Attach the vault credential mapped to `url`'s host, if any.
+    ///
+    /// The credential is sent as a bearer token.  `reqwest` drops the header
+    /// when a redirect leaves the host, so it never reaches another server.
+    pub fn authorize(&self, request: RequestBuilder, url: &Url) -> RequestBuilder {
+        match url.host_str().and_then(|host| self.credential_header(host)) {
+            Some(value) => request.header(AUTHORIZATION, value),
+            None => request,
+        }
+    }
+
+    /// The `Authorization` header for `host`, if a credential is configured.
+    fn credential_header(&self, host: &str) -> Option<HeaderValue> {
+        let provider = self.host_credentials.get(&host.to_ascii_lowercase())?;
+        let vault = self.vault.as_ref()?;
+        let credential = match vault
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_credential(provider)
+        {
+            Ok(credential) => credential,
+            Err(e) => {
+                warn!(host, provider, error = %e, "no vault credential for host");
+                return None;
+            }
+        };
+
+        let token = ["access_token", "api_key", "token"]
+            .iter()
+            .find_map(|field| credential.data.get(field).and_then(|v| v.as_str()))?;
+        let mut value = HeaderValue::from_str(&format!("Bearer {token}")).ok()?;
+        value.set_sensitive(true);
+        debug!(host, provider, "injecting vault credential");
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve one request, reply after `delay`, and return the raw request.
+    async fn serve_once(delay: Duration) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap();
+            tokio::time::sleep(delay).await;
+            let _ = socket
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok")
+                .await;
+            String::from_utf8_lossy(&buf[..n]).into_owned()
+        });
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn applies_user_agent() {
+        let (url, server) = serve_once(Duration::ZERO).await;
+        let client = HttpClientFactory::new()
+            .with_user_agent("factory-test/1.0")
+            .build()
+            .unwrap();
+
+        let resp = client.get(&url).send().await.unwrap();
+        assert!(resp.status().is_success());
+        let request = server.await.unwrap().to_ascii_lowercase();
+        assert!(
+            request.contains("user-agent: factory-test/1.0"),
+            "{request}"
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_timeout() {
+        let (url, _server) = serve_once(Duration::from_secs(5)).await;
+        let factory = HttpClientFactory::new().with_timeout(Duration::from_millis(200));
+        assert_eq!(factory.timeout(), Duration::from_millis(200));
+
+        let err = factory.build().unwrap().get(&url).send().await.unwrap_err();
+        assert!(err.is_timeout(), "{err}");
+    }
+
+    #[test]
+    fn parses_network_mode() {
+        assert_eq!(
+            "direct".parse::<NetworkMode>().unwrap(),
+            NetworkMode::Direct
+        );
+        assert_eq!(
+            "offline".parse::<NetworkMode>().unwrap(),
+            NetworkMode::Offline
+        );
+        assert_eq!(
+            "http://proxy.internal:3128".parse::<NetworkMode>().unwrap(),
+            NetworkMode::Proxy("http://proxy.internal:3128".into())
+        );
+        assert!("not a url".parse::<NetworkMode>().is_err());
+    }
+
+    #[tokio::test]
+    async fn proxy_mode_routes_through_proxy() {
+        // The "proxy" is a plain server that records what it receives.
+        let (proxy_url, server) = serve_once(Duration::ZERO).await;
+        let client = HttpClientFactory::new()
+            .with_network_mode(NetworkMode::Proxy(proxy_url))
+            .build()
+            .unwrap();
+
+        client
+            .get("http://upstream.invalid/path")
+            .send()
+            .await
+            .unwrap();
+        let request = server.await.unwrap();
+        assert!(
+            request.starts_with("GET http://upstream.invalid/path"),
+            "{request}"
+        );
+    }
+
+    #[tokio::test]
+    async fn injects_vault_credential_for_mapped_host() {
+        let vault = Vault::open_in_memory(&[7u8; 32]).unwrap();
+        vault
+            .store_credential(
+                "internal-api",
+                openintent_vault::CredentialType::ApiKey,
+                &serde_json::json!({ "api_key": "secret-key" }),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        let factory = HttpClientFactory::new()
+            .with_vault(Arc::new(Mutex::new(vault)))
+            .with_host_credential("127.0.0.1", "internal-api");
+
+        let client = factory.build().unwrap();
+
+        let (url, server) = serve_once(Duration::ZERO).await;
+        let parsed = Url::parse(&url).unwrap();
+        factory
+            .authorize(client.get(&url), &parsed)
+            .send()
+            .await
+            .unwrap();
+        let request = server.await.unwrap().to_ascii_lowercase();
+        assert!(
+            request.contains("authorization: bearer secret-key"),
+            "{request}"
+        );
+
+        let other = Url::parse("https://example.com/").unwrap();
+        let request = factory.authorize(client.get(&url), &other).build().unwrap();
+        assert!(request.headers().get(AUTHORIZATION).is_none());
+    }
+}
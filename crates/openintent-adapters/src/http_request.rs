@@ -11,6 +11,7 @@ use std::time::Instant;
 use tracing::{debug, info};
 
 use crate::error::{AdapterError, Result};
+use crate::http_client::HttpClientFactory;
 use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
 
 /// Default request timeout in seconds.
@@ -27,23 +28,30 @@ pub struct HttpRequestAdapter {
     connected: bool,
     /// HTTP client for making requests.
     client: reqwest::Client,
+    /// Factory the client was built from; also injects vault credentials.
+    http: HttpClientFactory,
 }
 
 impl HttpRequestAdapter {
     /// Create a new HTTP request adapter.
     pub fn new(id: impl Into<String>) -> Self {
-        let client = reqwest::Client::builder()
-            .user_agent("OpenIntentOS/0.1")
-            .build()
-            .unwrap_or_default();
-
+        let http = HttpClientFactory::default();
         Self {
             id: id.into(),
             connected: false,
-            client,
+            client: http.build().unwrap_or_default(),
+            http,
         }
     }
 
+    /// Build the client from `http`, and send the vault credentials it maps
+    /// to each request's host.
+    pub fn with_http_client_factory(mut self, http: HttpClientFactory) -> Result<Self> {
+        self.client = http.build()?;
+        self.http = http;
+        Ok(self)
+    }
+
     /// Execute an HTTP request and return the full response.
     async fn tool_http_request(&self, params: Value) -> Result<Value> {
         let method_str = params
@@ -75,7 +83,7 @@ impl HttpRequestAdapter {
         })?;
 
         // Validate URL.
-        let _parsed_url = url::Url::parse(url_str).map_err(|e| AdapterError::InvalidParams {
+        let parsed_url = url::Url::parse(url_str).map_err(|e| AdapterError::InvalidParams {
             tool_name: "http_request".into(),
             reason: format!("invalid URL `{url_str}`: {e}"),
         })?;
@@ -93,8 +101,19 @@ impl HttpRequestAdapter {
             .request(method, url_str)
             .timeout(std::time::Duration::from_secs(timeout_secs));
 
+        // Inject the vault credential for this host unless the caller sent
+        // their own.
+        let headers = params.get("headers").and_then(|v| v.as_object());
+        let has_auth = headers.is_some_and(|h| {
+            h.keys()
+                .any(|k| k.eq_ignore_ascii_case(reqwest::header::AUTHORIZATION.as_str()))
+        });
+        if !has_auth {
+            request_builder = self.http.authorize(request_builder, &parsed_url);
+        }
+
         // Add custom headers.
-        if let Some(headers) = params.get("headers").and_then(|v| v.as_object()) {
+        if let Some(headers) = headers {
             for (key, value) in headers {
                 if let Some(val_str) = value.as_str() {
                     let header_name = reqwest::header::HeaderName::from_bytes(key.as_bytes())
@@ -261,6 +280,7 @@ impl Adapter for HttpRequestAdapter {
                 },
                 "required": ["method", "url"]
             }),
+            destructive: true,
         }]
     }
 
@@ -271,6 +291,7 @@ impl Adapter for HttpRequestAdapter {
                 reason: format!("adapter `{}` is not connected", self.id),
             });
         }
+        self.http.ensure_online()?;
         match name {
             "http_request" => self.tool_http_request(params).await,
             _ => Err(AdapterError::ToolNotFound {
@@ -283,6 +304,16 @@ impl Adapter for HttpRequestAdapter {
     fn required_auth(&self) -> Option<AuthRequirement> {
         None
     }
+
+    /// Request bodies often carry login forms or tokens, so they are never
+    /// recorded.
+    fn redact_params(&self, _tool_name: &str, params: &Value) -> Value {
+        let mut redacted = openintent_agent::redact_sensitive(params);
+        if let Some(body) = redacted.get_mut("body") {
+            *body = Value::String(openintent_agent::audit::REDACTED.into());
+        }
+        redacted
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -0,0 +1,440 @@
+//! Idempotency keys for mutating tools.
+//!
+//! Retrying a tool call after a timeout or a dropped connection can repeat
+//! its side effect: the same email sent twice, a duplicate issue, a second
+//! MQTT publish.  [`IdempotentAdapter`] wraps any adapter and adds an
+//! optional `idempotency_key` parameter to every tool the adapter reports as
+//! mutating (see [`Adapter::is_mutating`]).  The built-in mutating tools
+//! are listed once, in [`MUTATING_TOOLS`].
+//!
+//! The first successful call with a key records its result in
+//! [`BotStateStore`] under `idempotency/{adapter}/{tool}/{key}`.  A later
+//! call with the same key returns the recorded result without running the
+//! tool again.  Failed calls are not recorded, so they can be retried with
+//! the same key.  Records expire after a TTL (24 hours by default).
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::{Value, json};
+use tracing::{debug, warn};
+
+use openintent_agent::PromptFn;
+use openintent_store::BotStateStore;
+
+use crate::error::{AdapterError, Result};
+use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
+
+/// Name of the parameter carrying the idempotency key.
+pub const IDEMPOTENCY_KEY_PARAM: &str = "idempotency_key";
+
+/// Built-in tools whose side effect must not be repeated when a call is
+/// retried, sorted by name.
+pub const MUTATING_TOOLS: &[&str] = &[
+    "calendar_create_event",
+    "cron_create",
+    "discord_send_message",
+    "email_send",
+    "feishu_create_doc",
+    "feishu_send_message",
+    "github_create_issue",
+    "github_create_pull_request",
+    "memory_save",
+    "mqtt_publish",
+    "notion_append_blocks",
+    "notion_create_page",
+    "postgres_execute",
+    "slack_send_blocks",
+    "slack_send_message",
+    "slack_upload_file",
+    "sqlite_execute",
+    "telegram_send_document",
+    "telegram_send_message",
+    "telegram_send_photo",
+    "telegram_send_video",
+];
+
+/// Whether `tool_name` is one of the [`MUTATING_TOOLS`].
+pub fn is_mutating_tool(tool_name: &str) -> bool {
+    MUTATING_TOOLS.binary_search(&tool_name).is_ok()
+}
+
+/// Prefix of every idempotency record in the bot state table.
+const KEY_PREFIX: &str = "idempotency/";
+
+/// How long a recorded result is replayed by default.
+const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Longest accepted idempotency key, in bytes.
+const MAX_KEY_LEN: usize = 256;
+
+/// Adapter wrapper that deduplicates retried calls to mutating tools.
+pub struct IdempotentAdapter {
+    /// The wrapped adapter.
+    inner: Box<dyn Adapter>,
+    /// Where processed keys and their results are recorded.
+    store: BotStateStore,
+    /// How long a recorded result is replayed.
+    ttl: Duration,
+    /// Storage keys of calls currently executing.
+    in_flight: Mutex<HashSet<String>>,
+}
+
+impl IdempotentAdapter {
+    /// Wrap `inner`, recording processed keys in `store`.
+    pub fn new(inner: Box<dyn Adapter>, store: BotStateStore) -> Self {
+        Self {
+            inner,
+            store,
+            ttl: DEFAULT_TTL,
+            in_flight: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Replay recorded results for `ttl` instead of the default 24 hours.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// The wrapped adapter.
+    pub fn inner(&self) -> &dyn Adapter {
+        self.inner.as_ref()
+    }
+
+    fn store_error(tool_name: &str, e: impl std::fmt::Display) -> AdapterError {
+        AdapterError::ExecutionFailed {
+            tool_name: tool_name.to_string(),
+            reason: format!("idempotency store error: {e}"),
+        }
+    }
+
+    /// The recorded result for `storage_key`, if it has not expired.
+    async fn recorded(&self, tool_name: &str, storage_key: &str) -> Result<Option<Value>> {
+        let Some(raw) = self
+            .store
+            .get(storage_key)
+            .await
+            .map_err(|e| Self::store_error(tool_name, e))?
+        else {
+            return Ok(None);
+        };
+        let record: Value = match serde_json::from_str(&raw) {
+            Ok(record) => record,
+            Err(e) => {
+                warn!(key = storage_key, error = %e, "discarding unreadable idempotency record");
+                return Ok(None);
+            }
+        };
+        let expired = record
+            .get("expires_at")
+            .and_then(Value::as_i64)
+            .is_some_and(|t| t <= Utc::now().timestamp());
+        if expired {
+            self.store
+                .delete(storage_key)
+                .await
+                .map_err(|e| Self::store_error(tool_name, e))?;
+            return Ok(None);
+        }
+        Ok(record.get("result").cloned())
+    }
+
+    /// Run `name` once per key, replaying the recorded result for repeats.
+    async fn execute_once(
+        &self,
+        name: &str,
+        key: &str,
+        params: Value,
+        prompt: Option<PromptFn>,
+    ) -> Result<Value> {
+        if key.is_empty() || key.len() > MAX_KEY_LEN {
+            return Err(AdapterError::InvalidParams {
+                tool_name: name.to_string(),
+                reason: format!(
+                    "`{IDEMPOTENCY_KEY_PARAM}` must be between 1 and {MAX_KEY_LEN} bytes"
+                ),
+            });
+        }
+        let storage_key = format!("{KEY_PREFIX}{}/{name}/{key}", self.inner.id());
+
+        if let Some(result) = self.recorded(name, &storage_key).await? {
+            debug!(tool = name, key, "replaying recorded result");
+            return Ok(result);
+        }
+
+        // Refuse a concurrent call with the same key rather than running the
+        // side effect twice.
+        let inserted = self
+            .in_flight
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(storage_key.clone());
+        if !inserted {
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: name.to_string(),
+                reason: format!("a call with idempotency key `{key}` is already in progress"),
+            });
+        }
+        let outcome = self
+            .run_and_record(name, &storage_key, params, prompt)
+            .await;
+        self.in_flight
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&storage_key);
+        outcome
+    }
+
+    async fn run_and_record(
+        &self,
+        name: &str,
+        storage_key: &str,
+        params: Value,
+        prompt: Option<PromptFn>,
+    ) -> Result<Value> {
+        // Another call with this key may have finished between the first
+        // check and claiming the in-flight slot.
+        if let Some(result) = self.recorded(name, storage_key).await? {
+            return Ok(result);
+        }
+        let result = self
+            .inner
+            .execute_tool_with_prompt(name, params, prompt)
+            .await?;
+
+        let expires_at = Utc::now().timestamp() + self.ttl.as_secs() as i64;
+        let record = json!({ "result": result, "expires_at": expires_at });
+        if let Err(e) = self.store.set(storage_key, &record.to_string()).await {
+            // The side effect already happened; report it rather than fail.
+            warn!(key = storage_key, error = %e, "failed to record idempotency key");
+        }
+        Ok(result)
+    }
+}
+
+/// Add the `idempotency_key` property to a tool's parameter schema.
+fn with_key_param(mut tool: ToolDefinition) -> ToolDefinition {
+    if let Some(properties) = tool
+        .parameters
+        .get_mut("properties")
+        .and_then(Value::as_object_mut)
+    {
+        properties.insert(
+            IDEMPOTENCY_KEY_PARAM.to_string(),
+            json!({
+                "type": "string",
+                "description": "Optional unique key for this action. Retrying with the same \
+                                key returns the first result instead of repeating the action."
+            }),
+        );
+    }
+    tool
+}
+
+#[async_trait]
+impl Adapter for IdempotentAdapter {
+    fn id(&self) -> &str {
+        self.inner.id()
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        self.inner.adapter_type()
+    }
+
+    async fn connect(&mut self) -> Result<()> {
+        self.inner.connect().await
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        self.inner.disconnect().await
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        self.inner.health_check().await
+    }
+
+    fn tools(&self) -> Vec<ToolDefinition> {
+        self.inner
+            .tools()
+            .into_iter()
+            .map(|tool| {
+                if self.inner.is_mutating(&tool.name) {
+                    with_key_param(tool)
+                } else {
+                    tool
+                }
+            })
+            .collect()
+    }
+
+    async fn execute_tool(&self, name: &str, params: Value) -> Result<Value> {
+        self.execute_tool_with_prompt(name, params, None).await
+    }
+
+    async fn execute_tool_with_prompt(
+        &self,
+        name: &str,
+        mut params: Value,
+        prompt: Option<PromptFn>,
+    ) -> Result<Value> {
+        let key = params
+            .as_object_mut()
+            .and_then(|p| p.remove(IDEMPOTENCY_KEY_PARAM));
+        match key {
+            Some(Value::String(key)) if self.inner.is_mutating(name) => {
+                self.execute_once(name, &key, params, prompt).await
+            }
+            Some(Value::String(_)) | None => {
+                self.inner
+                    .execute_tool_with_prompt(name, params, prompt)
+                    .await
+            }
+            Some(_) => Err(AdapterError::InvalidParams {
+                tool_name: name.to_string(),
+                reason: format!("`{IDEMPOTENCY_KEY_PARAM}` must be a string"),
+            }),
+        }
+    }
+
+    fn required_auth(&self) -> Option<AuthRequirement> {
+        self.inner.required_auth()
+    }
+
+    fn has_credentials(&self) -> bool {
+        self.inner.has_credentials()
+    }
+
+    fn is_mutating(&self, tool_name: &str) -> bool {
+        self.inner.is_mutating(tool_name)
+    }
+
+    fn redact_params(&self, tool_name: &str, params: &Value) -> Value {
+        self.inner.redact_params(tool_name, params)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openintent_store::Database;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Adapter with one mutating tool, `send`, that counts its executions.
+    struct MockSender {
+        sent: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Adapter for MockSender {
+        fn id(&self) -> &str {
+            "mock"
+        }
+
+        fn adapter_type(&self) -> AdapterType {
+            AdapterType::Messaging
+        }
+
+        async fn connect(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn disconnect(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn health_check(&self) -> Result<HealthStatus> {
+            Ok(HealthStatus::Healthy)
+        }
+
+        fn tools(&self) -> Vec<ToolDefinition> {
+            ["send", "list"]
+                .into_iter()
+                .map(|name| ToolDefinition {
+                    name: name.into(),
+                    description: String::new(),
+                    parameters: json!({ "type": "object", "properties": {} }),
+                    destructive: false,
+                })
+                .collect()
+        }
+
+        async fn execute_tool(&self, _name: &str, params: Value) -> Result<Value> {
+            assert!(params.get(IDEMPOTENCY_KEY_PARAM).is_none());
+            let n = self.sent.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(json!({ "message_id": n }))
+        }
+
+        fn required_auth(&self) -> Option<AuthRequirement> {
+            None
+        }
+
+        fn is_mutating(&self, tool_name: &str) -> bool {
+            tool_name == "send"
+        }
+    }
+
+    async fn setup() -> (IdempotentAdapter, Arc<AtomicUsize>) {
+        let db = Database::open_in_memory().unwrap();
+        db.run_migrations().await.unwrap();
+        let sent = Arc::new(AtomicUsize::new(0));
+        let adapter = IdempotentAdapter::new(
+            Box::new(MockSender { sent: sent.clone() }),
+            BotStateStore::new(db),
+        );
+        (adapter, sent)
+    }
+
+    #[tokio::test]
+    async fn repeated_key_executes_side_effect_once() {
+        let (adapter, sent) = setup().await;
+
+        let params = json!({ "text": "hi", "idempotency_key": "order-42" });
+        let first = adapter.execute_tool("send", params.clone()).await.unwrap();
+        let second = adapter.execute_tool("send", params).await.unwrap();
+        assert_eq!(sent.load(Ordering::SeqCst), 1);
+        assert_eq!(first, second);
+
+        adapter
+            .execute_tool("send", json!({ "idempotency_key": "order-43" }))
+            .await
+            .unwrap();
+        adapter.execute_tool("send", json!({})).await.unwrap();
+        assert_eq!(sent.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn expired_keys_run_again() {
+        let (adapter, sent) = setup().await;
+        let adapter = adapter.with_ttl(Duration::ZERO);
+
+        let params = json!({ "idempotency_key": "k" });
+        adapter.execute_tool("send", params.clone()).await.unwrap();
+        adapter.execute_tool("send", params).await.unwrap();
+        assert_eq!(sent.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn mutating_tools_table_is_sorted() {
+        assert!(MUTATING_TOOLS.windows(2).all(|w| w[0] < w[1]));
+        assert!(is_mutating_tool("email_send"));
+        assert!(!is_mutating_tool("email_read"));
+        assert!(crate::GitHubAdapter::new("github").is_mutating("github_create_issue"));
+    }
+
+    #[tokio::test]
+    async fn only_mutating_tools_advertise_the_key() {
+        let (adapter, _) = setup().await;
+        let tools = adapter.tools();
+        assert!(tools[0].parameters["properties"][IDEMPOTENCY_KEY_PARAM].is_object());
+        assert!(tools[1].parameters["properties"][IDEMPOTENCY_KEY_PARAM].is_null());
+    }
+}
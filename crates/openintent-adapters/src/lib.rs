@@ -3,8 +3,11 @@
 //! Each adapter implements the [`Adapter`] trait defined in [`traits`],
 //! providing a uniform interface for tool discovery and execution.
 
+pub mod briefing;
 pub mod browser;
 pub mod calendar;
+pub mod circuit_breaker;
+mod credentials;
 pub mod daily_briefing;
 pub mod cron;
 pub mod discord;
@@ -13,21 +16,35 @@ pub mod error;
 pub mod feishu;
 pub mod filesystem;
 pub mod github;
+pub mod http_client;
 pub mod http_request;
+pub mod idempotency;
 pub mod memory_tools;
 pub mod mqtt;
+pub mod network;
+pub mod notion;
+pub mod postgres;
+pub mod rest;
+pub mod s3;
+pub mod scratchpad;
 pub mod shell;
 pub mod skills;
+pub mod slack;
 pub mod sqlite;
+#[cfg(feature = "desktop")]
+pub mod system;
 pub mod telegram;
 pub mod telegram_oauth;
 pub mod traits;
 pub mod web_fetch;
+pub mod webhook;
 pub mod web_search;
 
+pub use briefing::{BriefingAdapter, BriefingSource};
 pub use browser::BrowserAdapter;
 pub use daily_briefing::{BriefingConfig, DailyBriefingAdapter};
 pub use calendar::CalendarAdapter;
+pub use circuit_breaker::CircuitBreakerAdapter;
 pub use cron::{CronAdapter, CronJob};
 pub use discord::DiscordAdapter;
 pub use email::EmailAdapter;
@@ -35,14 +52,26 @@ pub use error::{AdapterError, Result};
 pub use feishu::FeishuAdapter;
 pub use filesystem::FilesystemAdapter;
 pub use github::GitHubAdapter;
+pub use http_client::{HttpClientFactory, NetworkMode, set_network_mode};
 pub use http_request::HttpRequestAdapter;
+pub use idempotency::{IDEMPOTENCY_KEY_PARAM, IdempotentAdapter};
 pub use memory_tools::MemoryToolsAdapter;
 pub use mqtt::MqttAdapter;
+pub use network::{NetworkAdapter, NetworkConfig};
+pub use notion::NotionAdapter;
+pub use postgres::{PostgresAdapter, PostgresPoolConfig};
+pub use rest::{RestAdapter, RestAuth};
+pub use s3::{S3Adapter, S3Config, S3Credentials};
+pub use scratchpad::ScratchpadAdapter;
 pub use shell::ShellAdapter;
 pub use skills::SkillsAdapter;
+pub use slack::{SlackAdapter, SlackEventsConfig};
 pub use sqlite::SqliteAdapter;
+#[cfg(feature = "desktop")]
+pub use system::SystemAdapter;
 pub use telegram::TelegramAdapter;
 pub use telegram_oauth::{TelegramOAuth, TelegramOAuthConfig};
 pub use traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
 pub use web_fetch::WebFetchAdapter;
+pub use webhook::{WebhookAdapter, WebhookConfig};
 pub use web_search::WebSearchAdapter;
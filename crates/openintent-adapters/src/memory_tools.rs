@@ -2,24 +2,32 @@
 //!
 //! This adapter exposes the agent's semantic memory layer as tools that the
 //! LLM can invoke during a ReAct loop.  It wraps
-//! [`openintent_store::SemanticMemory`] and provides four tools:
+//! [`openintent_store::SemanticMemory`] and provides five tools:
 //!
 //! - `memory_save` -- persist a new memory entry.
-//! - `memory_search` -- keyword search across stored memories.
+//! - `memory_search` -- ranked full-text search with category and date filters.
 //! - `memory_list` -- list memories by category.
 //! - `memory_delete` -- remove a memory by ID.
+//! - `memory_consolidate` -- merge near-duplicate memories into one entry.
+//!
+//! With an embedder attached via [`MemoryToolsAdapter::with_embedder`],
+//! saved memories also get an embedding for similarity search.
 
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use serde_json::{Value, json};
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
-use openintent_store::{MemoryCategory, NewMemory, SemanticMemory};
+use openintent_agent::LlmClient;
+use openintent_store::{MemoryCategory, MemoryQuery, NewMemory, SemanticMemory, StoreError};
 
 use crate::error::{AdapterError, Result};
 use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
 
+/// Similarity at or above which `memory_consolidate` merges memories.
+const DEFAULT_SIMILARITY_THRESHOLD: f64 = 0.85;
+
 /// Adapter that exposes semantic memory operations as agent tools.
 pub struct MemoryToolsAdapter {
     /// Unique adapter instance identifier.
@@ -28,6 +36,8 @@ pub struct MemoryToolsAdapter {
     connected: bool,
     /// Handle to the semantic memory layer.
     memory: Arc<SemanticMemory>,
+    /// Client used to embed saved memories; `None` stores them without one.
+    embedder: Option<Arc<LlmClient>>,
 }
 
 impl MemoryToolsAdapter {
@@ -37,6 +47,26 @@ impl MemoryToolsAdapter {
             id: id.into(),
             connected: false,
             memory,
+            embedder: None,
+        }
+    }
+
+    /// Embed the content of saved memories with `llm`.
+    pub fn with_embedder(mut self, llm: Arc<LlmClient>) -> Self {
+        self.embedder = Some(llm);
+        self
+    }
+
+    /// Embed `content`, or `None` without an embedder.  A failed embedding
+    /// is logged and the memory is stored without one.
+    async fn embed(&self, content: &str) -> Option<Vec<f32>> {
+        let embedder = self.embedder.as_ref()?;
+        match embedder.embed(vec![content.to_owned()]).await {
+            Ok(mut vectors) => vectors.pop(),
+            Err(e) => {
+                warn!(error = %e, "failed to embed memory, saving without embedding");
+                None
+            }
         }
     }
 
@@ -83,13 +113,15 @@ impl MemoryToolsAdapter {
 
         debug!(category = category_str, importance, "saving memory");
 
+        let embedding = self.embed(content).await;
         let id = self
             .memory
             .insert(NewMemory {
                 category,
                 content: content.to_string(),
-                embedding: None,
+                embedding,
                 importance,
+                ttl: None,
             })
             .await
             .map_err(|e| AdapterError::ExecutionFailed {
@@ -100,7 +132,27 @@ impl MemoryToolsAdapter {
         Ok(json!({ "id": id, "saved": true }))
     }
 
-    /// Search memories by keyword.
+    /// Parse an optional date bound given as RFC 3339 or `YYYY-MM-DD`
+    /// (midnight UTC) into a Unix timestamp.
+    fn parse_date_bound(params: &Value, field: &str, tool_name: &str) -> Result<Option<i64>> {
+        let Some(raw) = params.get(field).and_then(|v| v.as_str()) else {
+            return Ok(None);
+        };
+        if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(raw) {
+            return Ok(Some(dt.timestamp()));
+        }
+        chrono::NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+            .ok()
+            .and_then(|d| d.and_hms_opt(0, 0, 0))
+            .map(|dt| Some(dt.and_utc().timestamp()))
+            .ok_or_else(|| AdapterError::InvalidParams {
+                tool_name: tool_name.to_string(),
+                reason: format!("`{field}` must be an RFC 3339 timestamp or YYYY-MM-DD date"),
+            })
+    }
+
+    /// Ranked full-text search with category, date-range, and `top_k`
+    /// filters.
     async fn tool_memory_search(&self, params: Value) -> Result<Value> {
         let query = Self::require_str(&params, "query", "memory_search")?;
 
@@ -108,28 +160,58 @@ impl MemoryToolsAdapter {
             Some(cat_str) => Some(Self::parse_category(cat_str, "memory_search")?),
             None => None,
         };
-
-        let limit = params.get("limit").and_then(|v| v.as_u64()).unwrap_or(5) as u32;
-
-        debug!(query, ?category, limit, "searching memories");
-
-        let memories = self
+        let created_after = Self::parse_date_bound(&params, "created_after", "memory_search")?;
+        let created_before = Self::parse_date_bound(&params, "created_before", "memory_search")?;
+
+        // `limit` is accepted for callers written against the keyword search.
+        let top_k = params
+            .get("top_k")
+            .or_else(|| params.get("limit"))
+            .and_then(|v| v.as_u64())
+            .unwrap_or(5)
+            .min(100) as u32;
+
+        debug!(
+            query,
+            ?category,
+            created_after,
+            created_before,
+            top_k,
+            "searching memories"
+        );
+
+        let matches = self
             .memory
-            .search_by_keyword(query, category, limit)
+            .search(&MemoryQuery {
+                text: query.to_string(),
+                embedding: None,
+                category,
+                created_after,
+                created_before,
+                top_k,
+            })
             .await
-            .map_err(|e| AdapterError::ExecutionFailed {
-                tool_name: "memory_search".to_string(),
-                reason: format!("search failed: {e}"),
+            .map_err(|e| match e {
+                StoreError::InvalidArgument(reason) => AdapterError::InvalidParams {
+                    tool_name: "memory_search".to_string(),
+                    reason,
+                },
+                e => AdapterError::ExecutionFailed {
+                    tool_name: "memory_search".to_string(),
+                    reason: format!("search failed: {e}"),
+                },
             })?;
 
-        let results: Vec<Value> = memories
+        let results: Vec<Value> = matches
             .iter()
             .map(|m| {
                 json!({
-                    "id": m.id,
-                    "content": m.content,
-                    "category": format!("{:?}", m.category).to_lowercase(),
-                    "importance": m.importance,
+                    "id": m.memory.id,
+                    "content": m.memory.content,
+                    "category": format!("{:?}", m.memory.category).to_lowercase(),
+                    "importance": m.memory.importance,
+                    "created_at": m.memory.created_at,
+                    "score": m.score,
                 })
             })
             .collect();
@@ -191,6 +273,43 @@ impl MemoryToolsAdapter {
 
         Ok(json!({ "deleted": true }))
     }
+
+    /// Merge clusters of near-duplicate memories and report what was merged.
+    async fn tool_memory_consolidate(&self, params: Value) -> Result<Value> {
+        let threshold = params
+            .get("similarity_threshold")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(DEFAULT_SIMILARITY_THRESHOLD);
+        let category = match params.get("category").and_then(|v| v.as_str()) {
+            Some(cat_str) => Some(Self::parse_category(cat_str, "memory_consolidate")?),
+            None => None,
+        };
+
+        debug!(threshold, ?category, "consolidating memories");
+
+        let report = self
+            .memory
+            .consolidate(threshold, category)
+            .await
+            .map_err(|e| match e {
+                StoreError::InvalidArgument(reason) => AdapterError::InvalidParams {
+                    tool_name: "memory_consolidate".to_string(),
+                    reason,
+                },
+                e => AdapterError::ExecutionFailed {
+                    tool_name: "memory_consolidate".to_string(),
+                    reason: format!("consolidation failed: {e}"),
+                },
+            })?;
+
+        info!(merged = report.merged, "memories consolidated");
+
+        Ok(json!({
+            "examined": report.examined,
+            "merged": report.merged,
+            "clusters": report.clusters,
+        }))
+    }
 }
 
 #[async_trait]
@@ -252,23 +371,32 @@ impl Adapter for MemoryToolsAdapter {
                     },
                     "required": ["content", "category"]
                 }),
+                destructive: true,
             },
             ToolDefinition {
                 name: "memory_search".into(),
-                description: "Search memories by keyword".into(),
+                description: "Search memories by relevance, returning scored matches".into(),
                 parameters: json!({
                     "type": "object",
                     "properties": {
                         "query": {
                             "type": "string",
-                            "description": "Keyword query to search for"
+                            "description": "Words to search for; results matching more of them rank higher"
                         },
                         "category": {
                             "type": "string",
                             "enum": ["preference", "knowledge", "pattern", "skill"],
                             "description": "Optional category filter"
                         },
-                        "limit": {
+                        "created_after": {
+                            "type": "string",
+                            "description": "Only memories created at or after this time (RFC 3339 or YYYY-MM-DD)"
+                        },
+                        "created_before": {
+                            "type": "string",
+                            "description": "Only memories created before this time (RFC 3339 or YYYY-MM-DD)"
+                        },
+                        "top_k": {
                             "type": "integer",
                             "description": "Maximum number of results (default: 5)",
                             "minimum": 1,
@@ -277,6 +405,7 @@ impl Adapter for MemoryToolsAdapter {
                     },
                     "required": ["query"]
                 }),
+                destructive: false,
             },
             ToolDefinition {
                 name: "memory_list".into(),
@@ -297,6 +426,7 @@ impl Adapter for MemoryToolsAdapter {
                         }
                     }
                 }),
+                destructive: false,
             },
             ToolDefinition {
                 name: "memory_delete".into(),
@@ -311,6 +441,28 @@ impl Adapter for MemoryToolsAdapter {
                     },
                     "required": ["id"]
                 }),
+                destructive: true,
+            },
+            ToolDefinition {
+                name: "memory_consolidate".into(),
+                description: "Merge near-duplicate memories into one entry, keeping the originals as provenance".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "similarity_threshold": {
+                            "type": "number",
+                            "description": "Similarity from 0.0 to 1.0 at which memories are merged (default: 0.85)",
+                            "minimum": 0.0,
+                            "maximum": 1.0
+                        },
+                        "category": {
+                            "type": "string",
+                            "enum": ["preference", "knowledge", "pattern", "skill"],
+                            "description": "Only consolidate memories of this category"
+                        }
+                    }
+                }),
+                destructive: true,
             },
         ]
     }
@@ -327,6 +479,7 @@ impl Adapter for MemoryToolsAdapter {
             "memory_search" => self.tool_memory_search(params).await,
             "memory_list" => self.tool_memory_list(params).await,
             "memory_delete" => self.tool_memory_delete(params).await,
+            "memory_consolidate" => self.tool_memory_consolidate(params).await,
             _ => Err(AdapterError::ToolNotFound {
                 adapter_id: self.id.clone(),
                 tool_name: name.to_string(),
@@ -364,9 +517,40 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn memory_tools_adapter_has_four_tools() {
+    async fn failed_embedding_still_saves_memory() {
+        let llm = openintent_agent::LlmClientConfig::openai_compatible(
+            "test-key",
+            "chat-model",
+            "http://127.0.0.1:9",
+        );
+        let llm = Arc::new(LlmClient::new(llm).unwrap_or_else(|e| {
+            panic!("failed to build client: {e}");
+        }));
+        let adapter = setup().await.with_embedder(llm);
+
+        let saved = adapter
+            .execute_tool(
+                "memory_save",
+                json!({"content": "prefers dark mode", "category": "preference"}),
+            )
+            .await
+            .unwrap_or_else(|e| panic!("save failed: {e}"));
+        let id = saved["id"]
+            .as_i64()
+            .unwrap_or_else(|| panic!("no id: {saved}"));
+        let memory = adapter
+            .memory
+            .get(id)
+            .await
+            .unwrap_or_else(|e| panic!("get failed: {e}"));
+        assert_eq!(memory.content, "prefers dark mode");
+        assert!(memory.embedding.is_none());
+    }
+
+    #[tokio::test]
+    async fn memory_tools_adapter_has_five_tools() {
         let adapter = setup().await;
-        assert_eq!(adapter.tools().len(), 4);
+        assert_eq!(adapter.tools().len(), 5);
     }
 
     #[tokio::test]
@@ -543,4 +727,113 @@ mod tests {
         let result = adapter.execute_tool("memory_nonexistent", json!({})).await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn memory_search_filters_by_category_and_scores() {
+        let adapter = setup().await;
+        for (content, category) in [
+            ("prefers rust for backend services", "preference"),
+            ("rust borrow checker rules", "knowledge"),
+            ("rust release cadence is six weeks", "knowledge"),
+        ] {
+            adapter
+                .execute_tool(
+                    "memory_save",
+                    json!({"content": content, "category": category}),
+                )
+                .await
+                .unwrap_or_else(|e| panic!("save failed: {e}"));
+        }
+
+        let search_result = adapter
+            .execute_tool(
+                "memory_search",
+                json!({"query": "rust", "category": "knowledge", "top_k": 10}),
+            )
+            .await
+            .unwrap_or_else(|e| panic!("search failed: {e}"));
+
+        let results = search_result["results"]
+            .as_array()
+            .unwrap_or_else(|| panic!("results should be an array"));
+        assert_eq!(results.len(), 2);
+        for result in results {
+            assert_eq!(result["category"], "knowledge");
+            assert!(result["score"].as_f64().is_some_and(|s| s > 0.0));
+        }
+    }
+
+    #[tokio::test]
+    async fn memory_search_applies_date_range() {
+        let adapter = setup().await;
+        adapter
+            .execute_tool(
+                "memory_save",
+                json!({"content": "standup moved to 10am", "category": "pattern"}),
+            )
+            .await
+            .unwrap_or_else(|e| panic!("save failed: {e}"));
+
+        let past = adapter
+            .execute_tool(
+                "memory_search",
+                json!({"query": "standup", "created_before": "2000-01-01"}),
+            )
+            .await
+            .unwrap_or_else(|e| panic!("search failed: {e}"));
+        assert_eq!(past["results"].as_array().map(Vec::len), Some(0));
+
+        let recent = adapter
+            .execute_tool(
+                "memory_search",
+                json!({"query": "standup", "created_after": "2000-01-01T00:00:00Z"}),
+            )
+            .await
+            .unwrap_or_else(|e| panic!("search failed: {e}"));
+        assert_eq!(recent["results"].as_array().map(Vec::len), Some(1));
+
+        let invalid = adapter
+            .execute_tool(
+                "memory_search",
+                json!({"query": "standup", "created_after": "last tuesday"}),
+            )
+            .await;
+        assert!(matches!(invalid, Err(AdapterError::InvalidParams { .. })));
+    }
+
+    #[tokio::test]
+    async fn memory_consolidate_collapses_near_duplicates() {
+        let adapter = setup().await;
+        for content in [
+            "User prefers dark mode in the editor",
+            "user prefers dark mode in editor",
+            "The user prefers dark mode in the editor.",
+        ] {
+            adapter
+                .execute_tool(
+                    "memory_save",
+                    json!({"content": content, "category": "preference"}),
+                )
+                .await
+                .unwrap_or_else(|e| panic!("save failed: {e}"));
+        }
+
+        let report = adapter
+            .execute_tool("memory_consolidate", json!({"similarity_threshold": 0.8}))
+            .await
+            .unwrap_or_else(|e| panic!("consolidate failed: {e}"));
+        assert_eq!(report["merged"], 2);
+        assert_eq!(report["clusters"].as_array().map(Vec::len), Some(1));
+
+        let listed = adapter
+            .execute_tool("memory_list", json!({}))
+            .await
+            .unwrap_or_else(|e| panic!("list failed: {e}"));
+        assert_eq!(listed["results"].as_array().map(Vec::len), Some(1));
+
+        let invalid = adapter
+            .execute_tool("memory_consolidate", json!({"similarity_threshold": 2.0}))
+            .await;
+        assert!(matches!(invalid, Err(AdapterError::InvalidParams { .. })));
+    }
 }
@@ -7,6 +7,7 @@ use uuid::Uuid;
 
 use crate::traits::{Adapter, AdapterType, ToolDefinition, AuthRequirement, HealthStatus};
 use crate::error::{AdapterError, Result};
+use crate::http_client;
 
 /// MQTT Quality of Service levels
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -285,6 +286,7 @@ impl Adapter for MqttAdapter {
                     },
                     "required": ["topic", "payload"]
                 }),
+                destructive: true,
             },
             ToolDefinition {
                 name: "mqtt_subscribe".to_string(),
@@ -304,6 +306,7 @@ impl Adapter for MqttAdapter {
                     },
                     "required": ["topic"]
                 }),
+                destructive: true,
             },
             ToolDefinition {
                 name: "mqtt_unsubscribe".to_string(),
@@ -318,6 +321,7 @@ impl Adapter for MqttAdapter {
                     },
                     "required": ["topic"]
                 }),
+                destructive: true,
             },
             ToolDefinition {
                 name: "mqtt_list_subscriptions".to_string(),
@@ -326,6 +330,7 @@ impl Adapter for MqttAdapter {
                     "type": "object",
                     "properties": {}
                 }),
+                destructive: false,
             },
         ]
     }
@@ -335,6 +340,7 @@ impl Adapter for MqttAdapter {
         name: &str,
         params: serde_json::Value,
     ) -> Result<serde_json::Value> {
+        http_client::ensure_online()?;
         match name {
             "mqtt_publish" => {
                 let topic = params["topic"].as_str()
@@ -0,0 +1,380 @@
+//! Minimal DNS stub resolver.
+//!
+//! Sends A and AAAA queries over UDP to a configured name server and parses
+//! the answers, keeping TTLs and the CNAME chain.  When no server is
+//! configured the operating system resolver is used instead, which does
+//! not report TTLs.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use ring::rand::{SecureRandom, SystemRandom};
+use tokio::net::UdpSocket;
+
+/// Record type for IPv4 addresses.
+const TYPE_A: u16 = 1;
+/// Record type for canonical names.
+const TYPE_CNAME: u16 = 5;
+/// Record type for IPv6 addresses.
+const TYPE_AAAA: u16 = 28;
+/// The Internet class.
+const CLASS_IN: u16 = 1;
+
+/// Receive buffer size for UDP responses.
+const MAX_UDP_RESPONSE: usize = 4096;
+
+/// Where host names are resolved.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Resolver {
+    /// The operating system resolver.
+    #[default]
+    System,
+    /// A DNS server queried directly over UDP.
+    Server(SocketAddr),
+}
+
+/// One resolved address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedAddr {
+    /// The address.
+    pub ip: IpAddr,
+    /// Time-to-live in seconds, when the resolver reports it.
+    pub ttl: Option<u32>,
+}
+
+/// The result of resolving a host name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Lookup {
+    /// Addresses, IPv4 first.
+    pub addrs: Vec<ResolvedAddr>,
+    /// Canonical names followed while resolving, in order.
+    pub cnames: Vec<String>,
+}
+
+/// Errors from a DNS lookup.
+#[derive(Debug, thiserror::Error)]
+pub enum DnsError {
+    /// The name does not exist.
+    #[error("`{0}` does not exist (NXDOMAIN)")]
+    NotFound(String),
+    /// The server answered with an error code.
+    #[error("name server returned {0}")]
+    Server(&'static str),
+    /// The response could not be parsed.
+    #[error("malformed DNS response: {0}")]
+    Malformed(&'static str),
+    /// The host name cannot be encoded as a query.
+    #[error("invalid host name `{0}`")]
+    InvalidName(String),
+    /// Sending or receiving failed.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+impl Resolver {
+    /// Resolve `host` to its IPv4 and IPv6 addresses.
+    ///
+    /// IP literals are returned as-is without a query.  The caller is
+    /// responsible for bounding the time spent here.
+    pub async fn lookup(&self, host: &str) -> Result<Lookup, DnsError> {
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            return Ok(Lookup {
+                addrs: vec![ResolvedAddr { ip, ttl: None }],
+                cnames: Vec::new(),
+            });
+        }
+        match self {
+            Self::System => lookup_system(host).await,
+            Self::Server(server) => lookup_server(*server, host).await,
+        }
+    }
+}
+
+async fn lookup_system(host: &str) -> Result<Lookup, DnsError> {
+    let mut addrs: Vec<ResolvedAddr> = Vec::new();
+    for addr in tokio::net::lookup_host((host, 0)).await? {
+        if !addrs.iter().any(|a| a.ip == addr.ip()) {
+            addrs.push(ResolvedAddr {
+                ip: addr.ip(),
+                ttl: None,
+            });
+        }
+    }
+    if addrs.is_empty() {
+        return Err(DnsError::NotFound(host.to_string()));
+    }
+    addrs.sort_by_key(|a| a.ip.is_ipv6());
+    Ok(Lookup {
+        addrs,
+        cnames: Vec::new(),
+    })
+}
+
+async fn lookup_server(server: SocketAddr, host: &str) -> Result<Lookup, DnsError> {
+    let (v4, v6) = tokio::join!(query(server, host, TYPE_A), query(server, host, TYPE_AAAA));
+    let (v4, v6) = (v4?, v6?);
+    if v4.is_none() && v6.is_none() {
+        return Err(DnsError::NotFound(host.to_string()));
+    }
+
+    let mut lookup = Lookup::default();
+    for answer in [v4, v6].into_iter().flatten() {
+        lookup.addrs.extend(answer.addrs);
+        for cname in answer.cnames {
+            if !lookup.cnames.contains(&cname) {
+                lookup.cnames.push(cname);
+            }
+        }
+    }
+    Ok(lookup)
+}
+
+/// Send one query and wait for the matching response.  Returns `None` for
+/// NXDOMAIN.
+async fn query(server: SocketAddr, host: &str, qtype: u16) -> Result<Option<Lookup>, DnsError> {
+    let mut id = [0u8; 2];
+    SystemRandom::new()
+        .fill(&mut id)
+        .map_err(|_| std::io::Error::other("random number generator failed"))?;
+    let id = u16::from_be_bytes(id);
+    let request = encode_query(id, host, qtype)?;
+
+    let bind: SocketAddr = if server.is_ipv4() {
+        (Ipv4Addr::UNSPECIFIED, 0).into()
+    } else {
+        (Ipv6Addr::UNSPECIFIED, 0).into()
+    };
+    let socket = UdpSocket::bind(bind).await?;
+    socket.connect(server).await?;
+    socket.send(&request).await?;
+
+    let mut buf = vec![0u8; MAX_UDP_RESPONSE];
+    loop {
+        let len = socket.recv(&mut buf).await?;
+        // Ignore stray datagrams that do not answer this query.
+        if len >= 2 && u16::from_be_bytes([buf[0], buf[1]]) == id {
+            return parse_response(&buf[..len], qtype);
+        }
+    }
+}
+
+/// Encode a recursive query for `host`.
+fn encode_query(id: u16, host: &str, qtype: u16) -> Result<Vec<u8>, DnsError> {
+    let invalid = || DnsError::InvalidName(host.to_string());
+    let name = host.strip_suffix('.').unwrap_or(host);
+    if name.is_empty() || name.len() > 253 {
+        return Err(invalid());
+    }
+
+    let mut out = Vec::with_capacity(18 + name.len());
+    out.extend_from_slice(&id.to_be_bytes());
+    // Flags: standard query, recursion desired.
+    out.extend_from_slice(&0x0100u16.to_be_bytes());
+    // One question, no answer/authority/additional records.
+    out.extend_from_slice(&[0, 1, 0, 0, 0, 0, 0, 0]);
+    for label in name.split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(invalid());
+        }
+        out.push(label.len() as u8);
+        out.extend_from_slice(label.as_bytes());
+    }
+    out.push(0);
+    out.extend_from_slice(&qtype.to_be_bytes());
+    out.extend_from_slice(&CLASS_IN.to_be_bytes());
+    Ok(out)
+}
+
+/// Parse a response to a query of type `qtype`.
+fn parse_response(msg: &[u8], qtype: u16) -> Result<Option<Lookup>, DnsError> {
+    let mut r = Reader { msg, pos: 0 };
+    r.take(2)?; // id, already matched
+    let flags = r.u16()?;
+    if flags & 0x8000 == 0 {
+        return Err(DnsError::Malformed("message is not a response"));
+    }
+    match flags & 0x000f {
+        0 => {}
+        3 => return Ok(None),
+        1 => return Err(DnsError::Server("FORMERR")),
+        2 => return Err(DnsError::Server("SERVFAIL")),
+        4 => return Err(DnsError::Server("NOTIMP")),
+        5 => return Err(DnsError::Server("REFUSED")),
+        _ => return Err(DnsError::Server("an unknown error")),
+    }
+    let questions = r.u16()?;
+    let answers = r.u16()?;
+    r.take(4)?; // authority and additional counts
+
+    for _ in 0..questions {
+        r.name()?;
+        r.take(4)?;
+    }
+
+    let mut lookup = Lookup::default();
+    for _ in 0..answers {
+        r.name()?;
+        let rtype = r.u16()?;
+        let class = r.u16()?;
+        let ttl = r.u32()?;
+        let rdlength = r.u16()? as usize;
+        let rdata_start = r.pos;
+        let rdata = r.take(rdlength)?;
+        if class != CLASS_IN {
+            continue;
+        }
+        let ip = match rtype {
+            TYPE_A if qtype == TYPE_A => IpAddr::from(
+                <[u8; 4]>::try_from(rdata)
+                    .map_err(|_| DnsError::Malformed("A record is not 4 bytes"))?,
+            ),
+            TYPE_AAAA if qtype == TYPE_AAAA => IpAddr::from(
+                <[u8; 16]>::try_from(rdata)
+                    .map_err(|_| DnsError::Malformed("AAAA record is not 16 bytes"))?,
+            ),
+            TYPE_CNAME => {
+                let mut target = Reader {
+                    msg,
+                    pos: rdata_start,
+                };
+                lookup.cnames.push(target.name()?);
+                continue;
+            }
+            _ => continue,
+        };
+        lookup.addrs.push(ResolvedAddr { ip, ttl: Some(ttl) });
+    }
+    Ok(Some(lookup))
+}
+
+/// Cursor over a DNS message.
+struct Reader<'a> {
+    msg: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], DnsError> {
+        let bytes = self
+            .msg
+            .get(self.pos..self.pos + n)
+            .ok_or(DnsError::Malformed("message is truncated"))?;
+        self.pos += n;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16, DnsError> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, DnsError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Read a possibly compressed domain name, leaving the cursor after it.
+    fn name(&mut self) -> Result<String, DnsError> {
+        let mut labels: Vec<String> = Vec::new();
+        let mut pos = self.pos;
+        let mut resume = None;
+        // Each pointer must go backwards, which rules out loops.
+        let mut limit = pos;
+        loop {
+            let len = *self
+                .msg
+                .get(pos)
+                .ok_or(DnsError::Malformed("name is truncated"))? as usize;
+            match len & 0xc0 {
+                0x00 if len == 0 => {
+                    pos += 1;
+                    break;
+                }
+                0x00 => {
+                    let label = self
+                        .msg
+                        .get(pos + 1..pos + 1 + len)
+                        .ok_or(DnsError::Malformed("name is truncated"))?;
+                    labels.push(String::from_utf8_lossy(label).into_owned());
+                    pos += 1 + len;
+                }
+                0xc0 => {
+                    let low = *self
+                        .msg
+                        .get(pos + 1)
+                        .ok_or(DnsError::Malformed("name is truncated"))?
+                        as usize;
+                    let target = ((len & 0x3f) << 8) | low;
+                    if target >= limit {
+                        return Err(DnsError::Malformed("name pointer does not go backwards"));
+                    }
+                    resume.get_or_insert(pos + 2);
+                    limit = target;
+                    pos = target;
+                }
+                _ => return Err(DnsError::Malformed("unsupported label type")),
+            }
+        }
+        self.pos = resume.unwrap_or(pos);
+        Ok(labels.join("."))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_compressed_cname_chain() {
+        // Response for www.example.com A: CNAME to example.com, then an A
+        // record, both using name compression.
+        let mut msg = encode_query(7, "www.example.com", TYPE_A).unwrap();
+        msg[2] = 0x81;
+        msg[3] = 0x80;
+        msg[7] = 2; // two answers
+        // www.example.com CNAME example.com (pointer to offset 16)
+        msg.extend_from_slice(&[0xc0, 12, 0, 5, 0, 1, 0, 0, 0, 60, 0, 2, 0xc0, 16]);
+        // example.com A 93.184.216.34
+        msg.extend_from_slice(&[0xc0, 16, 0, 1, 0, 1, 0, 0, 1, 0, 0, 4, 93, 184, 216, 34]);
+
+        let lookup = parse_response(&msg, TYPE_A).unwrap().unwrap();
+        assert_eq!(lookup.cnames, vec!["example.com".to_string()]);
+        assert_eq!(
+            lookup.addrs,
+            vec![ResolvedAddr {
+                ip: "93.184.216.34".parse().unwrap(),
+                ttl: Some(256),
+            }]
+        );
+    }
+
+    #[test]
+    fn rejects_pointer_loops() {
+        let mut msg = encode_query(7, "a", TYPE_A).unwrap();
+        msg[2] = 0x81;
+        msg[7] = 1;
+        // Answer name points at itself.
+        let at = msg.len() as u8;
+        msg.extend_from_slice(&[0xc0, at, 0, 1, 0, 1, 0, 0, 0, 0, 0, 4, 1, 2, 3, 4]);
+        assert!(matches!(
+            parse_response(&msg, TYPE_A),
+            Err(DnsError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_short_address_record() {
+        let mut msg = encode_query(7, "a", TYPE_A).unwrap();
+        msg[2] = 0x81;
+        msg[7] = 1;
+        // A record whose rdata is 3 bytes long.
+        msg.extend_from_slice(&[0xc0, 12, 0, 1, 0, 1, 0, 0, 0, 60, 0, 3, 1, 2, 3]);
+        assert!(matches!(
+            parse_response(&msg, TYPE_A),
+            Err(DnsError::Malformed(_))
+        ));
+    }
+}
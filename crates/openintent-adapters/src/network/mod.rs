@@ -0,0 +1,678 @@
+//! Network diagnostics adapter -- DNS, reachability, and port checks.
+//!
+//! Four tools for infrastructure agents, each returning structured results
+//! and bounded by a timeout:
+//!
+//! - `dns_lookup` -- resolve a host to its addresses (with TTLs when a DNS
+//!   server is configured) and CNAME chain.
+//! - `ping` -- measure round-trip latency.  ICMP needs raw-socket
+//!   privileges, so this times TCP handshakes instead; a refused connection
+//!   still counts as a reply because the host answered.
+//! - `port_check` -- report whether a TCP port is open, closed, or filtered.
+//! - `http_head` -- send a HEAD request and report status, headers, and
+//!   latency without following redirects.
+//!
+//! Probes refuse targets in private, loopback, and link-local ranges unless
+//! [`NetworkConfig::allow_private`] is set, so the agent cannot be used to
+//! scan the local network.
+
+pub mod dns;
+
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use tokio::net::TcpStream;
+use tracing::{debug, info, warn};
+
+use crate::error::{AdapterError, Result};
+use crate::http_client::{self, HttpClientFactory};
+use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
+use crate::web_fetch::is_private_ip;
+
+pub use dns::{DnsError, Lookup, ResolvedAddr, Resolver};
+
+/// Default per-operation timeout in milliseconds.
+const DEFAULT_TIMEOUT_MS: u64 = 3_000;
+
+/// Longest timeout a tool call may ask for, in milliseconds.
+const MAX_TIMEOUT_MS: u64 = 30_000;
+
+/// Default number of `ping` probes.
+const DEFAULT_PING_COUNT: u64 = 4;
+
+/// Most `ping` probes a single call may send.
+const MAX_PING_COUNT: u64 = 10;
+
+/// Port `ping` connects to when none is given.
+const DEFAULT_PING_PORT: u16 = 443;
+
+/// Pause between `ping` probes.
+const PING_INTERVAL: Duration = Duration::from_millis(250);
+
+// ---------------------------------------------------------------------------
+// Configuration
+// ---------------------------------------------------------------------------
+
+/// Network adapter settings.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkConfig {
+    /// Where host names are resolved.
+    pub resolver: Resolver,
+    /// Whether probes may target private, loopback, and link-local
+    /// addresses.
+    pub allow_private: bool,
+}
+
+impl NetworkConfig {
+    /// Read the configuration from `NETWORK_DNS_SERVER` (an `ip` or
+    /// `ip:port` to query instead of the system resolver) and
+    /// `NETWORK_ALLOW_PRIVATE` (`true` or `1`), using the defaults for unset
+    /// or invalid variables.
+    pub fn from_env() -> Self {
+        let resolver = std::env::var("NETWORK_DNS_SERVER")
+            .ok()
+            .and_then(|s| {
+                s.parse::<SocketAddr>()
+                    .or_else(|_| s.parse::<IpAddr>().map(|ip| SocketAddr::new(ip, 53)))
+                    .ok()
+            })
+            .map(Resolver::Server)
+            .unwrap_or_default();
+        let allow_private = std::env::var("NETWORK_ALLOW_PRIVATE")
+            .is_ok_and(|v| v.eq_ignore_ascii_case("true") || v == "1");
+        Self {
+            resolver,
+            allow_private,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Adapter
+// ---------------------------------------------------------------------------
+
+/// Network diagnostics adapter.
+pub struct NetworkAdapter {
+    /// Unique identifier for this adapter instance.
+    id: String,
+    /// Whether the adapter has been connected.
+    connected: bool,
+    /// Resolver and target policy.
+    config: NetworkConfig,
+}
+
+impl NetworkAdapter {
+    /// Create a new network adapter.
+    pub fn new(id: impl Into<String>, config: NetworkConfig) -> Self {
+        Self {
+            id: id.into(),
+            connected: false,
+            config,
+        }
+    }
+
+    /// Extract a required string field from JSON params.
+    fn require_str<'a>(params: &'a Value, field: &str, tool_name: &str) -> Result<&'a str> {
+        params
+            .get(field)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| AdapterError::InvalidParams {
+                tool_name: tool_name.to_string(),
+                reason: format!("missing required string field `{field}`"),
+            })
+    }
+
+    /// Extract a required port number from JSON params.
+    fn require_port(params: &Value, tool_name: &str) -> Result<u16> {
+        params
+            .get("port")
+            .and_then(|v| v.as_u64())
+            .and_then(|p| u16::try_from(p).ok())
+            .filter(|p| *p != 0)
+            .ok_or_else(|| AdapterError::InvalidParams {
+                tool_name: tool_name.to_string(),
+                reason: "`port` must be an integer between 1 and 65535".into(),
+            })
+    }
+
+    /// The `timeout_ms` param, defaulted and capped.
+    fn timeout(params: &Value) -> Duration {
+        let ms = params
+            .get("timeout_ms")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_TIMEOUT_MS)
+            .clamp(1, MAX_TIMEOUT_MS);
+        Duration::from_millis(ms)
+    }
+
+    /// Resolve `host` within `timeout`.
+    async fn resolve(&self, tool_name: &str, host: &str, timeout: Duration) -> Result<Lookup> {
+        match tokio::time::timeout(timeout, self.config.resolver.lookup(host)).await {
+            Ok(Ok(lookup)) => Ok(lookup),
+            Ok(Err(e)) => Err(AdapterError::ExecutionFailed {
+                tool_name: tool_name.to_string(),
+                reason: format!("DNS lookup for `{host}` failed: {e}"),
+            }),
+            Err(_) => Err(AdapterError::Timeout {
+                seconds: timeout.as_secs().max(1),
+                reason: format!("DNS lookup for `{host}` timed out"),
+            }),
+        }
+    }
+
+    /// Resolve `host` and pick the address to probe, refusing private
+    /// targets unless they are allowed.
+    async fn resolve_target(
+        &self,
+        tool_name: &str,
+        host: &str,
+        timeout: Duration,
+    ) -> Result<IpAddr> {
+        let lookup = self.resolve(tool_name, host, timeout).await?;
+        if !self.config.allow_private
+            && let Some(private) = lookup.addrs.iter().find(|a| is_private_ip(a.ip))
+        {
+            warn!(tool = tool_name, host, ip = %private.ip, "refusing private network target");
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: tool_name.to_string(),
+                reason: format!(
+                    "`{host}` resolves to private address {}; probing private networks is disabled",
+                    private.ip
+                ),
+            });
+        }
+        lookup
+            .addrs
+            .first()
+            .map(|a| a.ip)
+            .ok_or_else(|| AdapterError::ExecutionFailed {
+                tool_name: tool_name.to_string(),
+                reason: format!("`{host}` has no addresses"),
+            })
+    }
+
+    // -- Tool implementations ------------------------------------------------
+
+    /// Resolve a host name.
+    async fn tool_dns_lookup(&self, params: Value) -> Result<Value> {
+        let host = Self::require_str(&params, "host", "dns_lookup")?;
+        let timeout = Self::timeout(&params);
+
+        let start = Instant::now();
+        let lookup = self.resolve("dns_lookup", host, timeout).await?;
+        let elapsed_ms = start.elapsed().as_millis() as u64;
+
+        let addresses: Vec<Value> = lookup
+            .addrs
+            .iter()
+            .map(|a| {
+                json!({
+                    "ip": a.ip.to_string(),
+                    "family": if a.ip.is_ipv4() { "ipv4" } else { "ipv6" },
+                    "ttl": a.ttl,
+                    "private": is_private_ip(a.ip),
+                })
+            })
+            .collect();
+        let resolver = match self.config.resolver {
+            Resolver::System => "system".to_string(),
+            Resolver::Server(addr) => addr.to_string(),
+        };
+        Ok(json!({
+            "host": host,
+            "addresses": addresses,
+            "cnames": lookup.cnames,
+            "resolver": resolver,
+            "elapsed_ms": elapsed_ms,
+        }))
+    }
+
+    /// Measure TCP handshake round-trips to a host.
+    async fn tool_ping(&self, params: Value) -> Result<Value> {
+        let host = Self::require_str(&params, "host", "ping")?;
+        let count = params
+            .get("count")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_PING_COUNT)
+            .clamp(1, MAX_PING_COUNT);
+        let port = match params.get("port") {
+            Some(_) => Self::require_port(&params, "ping")?,
+            None => DEFAULT_PING_PORT,
+        };
+        let timeout = Self::timeout(&params);
+        let ip = self.resolve_target("ping", host, timeout).await?;
+        let addr = SocketAddr::new(ip, port);
+
+        debug!(%addr, count, "pinging");
+        let mut rtts: Vec<f64> = Vec::new();
+        for seq in 0..count {
+            if seq > 0 {
+                tokio::time::sleep(PING_INTERVAL).await;
+            }
+            let (outcome, elapsed) = probe(addr, timeout).await;
+            if outcome != PortState::Filtered {
+                rtts.push(elapsed.as_secs_f64() * 1000.0);
+            }
+        }
+
+        let received = rtts.len() as u64;
+        let stats = (!rtts.is_empty()).then(|| {
+            let min = rtts.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = rtts.iter().copied().fold(0.0, f64::max);
+            let avg = rtts.iter().sum::<f64>() / rtts.len() as f64;
+            json!({ "min": round_ms(min), "avg": round_ms(avg), "max": round_ms(max) })
+        });
+        Ok(json!({
+            "host": host,
+            "ip": ip.to_string(),
+            "port": port,
+            "method": "tcp",
+            "sent": count,
+            "received": received,
+            "loss_percent": (count - received) * 100 / count,
+            "rtt_ms": rtts.iter().copied().map(round_ms).collect::<Vec<_>>(),
+            "stats_ms": stats,
+        }))
+    }
+
+    /// Check whether a TCP port accepts connections.
+    async fn tool_port_check(&self, params: Value) -> Result<Value> {
+        let host = Self::require_str(&params, "host", "port_check")?;
+        let port = Self::require_port(&params, "port_check")?;
+        let timeout = Self::timeout(&params);
+        let ip = self.resolve_target("port_check", host, timeout).await?;
+
+        let (state, elapsed) = probe(SocketAddr::new(ip, port), timeout).await;
+        Ok(json!({
+            "host": host,
+            "ip": ip.to_string(),
+            "port": port,
+            "state": state.as_str(),
+            "open": state == PortState::Open,
+            "elapsed_ms": round_ms(elapsed.as_secs_f64() * 1000.0),
+        }))
+    }
+
+    /// Send a HEAD request without following redirects.
+    async fn tool_http_head(&self, params: Value) -> Result<Value> {
+        let url_str = Self::require_str(&params, "url", "http_head")?;
+        let timeout = Self::timeout(&params);
+        let url = url::Url::parse(url_str).map_err(|e| AdapterError::InvalidParams {
+            tool_name: "http_head".into(),
+            reason: format!("invalid URL `{url_str}`: {e}"),
+        })?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(AdapterError::InvalidParams {
+                tool_name: "http_head".into(),
+                reason: format!("unsupported scheme `{}`", url.scheme()),
+            });
+        }
+        let host = url
+            .host_str()
+            .ok_or_else(|| AdapterError::InvalidParams {
+                tool_name: "http_head".into(),
+                reason: "URL has no host".into(),
+            })?
+            .trim_start_matches('[')
+            .trim_end_matches(']');
+        let port = url.port_or_known_default().unwrap_or(443);
+        let ip = self.resolve_target("http_head", host, timeout).await?;
+
+        // Connect to the address that passed the private-range check rather
+        // than letting the client resolve the name again.
+        let client = HttpClientFactory::default()
+            .with_timeout(timeout)
+            .builder()?
+            .redirect(reqwest::redirect::Policy::none())
+            .resolve(host, SocketAddr::new(ip, port))
+            .build()
+            .map_err(|e| AdapterError::ExecutionFailed {
+                tool_name: "http_head".into(),
+                reason: format!("failed to build HTTP client: {e}"),
+            })?;
+
+        let start = Instant::now();
+        let response = client.head(url.clone()).send().await.map_err(|e| {
+            if e.is_timeout() {
+                AdapterError::Timeout {
+                    seconds: timeout.as_secs().max(1),
+                    reason: format!("HEAD `{url_str}` timed out"),
+                }
+            } else {
+                AdapterError::ExecutionFailed {
+                    tool_name: "http_head".into(),
+                    reason: format!("HEAD `{url_str}` failed: {e}"),
+                }
+            }
+        })?;
+        let elapsed_ms = start.elapsed().as_millis() as u64;
+
+        let status = response.status();
+        let headers: serde_json::Map<String, Value> = response
+            .headers()
+            .iter()
+            .map(|(k, v)| {
+                (
+                    k.as_str().to_string(),
+                    Value::String(String::from_utf8_lossy(v.as_bytes()).into_owned()),
+                )
+            })
+            .collect();
+        Ok(json!({
+            "url": url_str,
+            "ip": ip.to_string(),
+            "status": status.as_u16(),
+            "ok": status.is_success(),
+            "redirect": status.is_redirection(),
+            "location": response
+                .headers()
+                .get(reqwest::header::LOCATION)
+                .and_then(|v| v.to_str().ok()),
+            "headers": headers,
+            "elapsed_ms": elapsed_ms,
+        }))
+    }
+}
+
+/// Outcome of a TCP connection attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PortState {
+    /// The connection was accepted.
+    Open,
+    /// The host actively refused the connection.
+    Closed,
+    /// No answer before the timeout, or the host is unreachable.
+    Filtered,
+}
+
+impl PortState {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Closed => "closed",
+            Self::Filtered => "filtered",
+        }
+    }
+}
+
+/// Attempt a TCP connection to `addr` and time it.
+async fn probe(addr: SocketAddr, timeout: Duration) -> (PortState, Duration) {
+    let start = Instant::now();
+    let state = match tokio::time::timeout(timeout, TcpStream::connect(addr)).await {
+        Ok(Ok(_stream)) => PortState::Open,
+        Ok(Err(e)) if e.kind() == std::io::ErrorKind::ConnectionRefused => PortState::Closed,
+        Ok(Err(_)) | Err(_) => PortState::Filtered,
+    };
+    (state, start.elapsed())
+}
+
+/// Round milliseconds to two decimal places.
+fn round_ms(ms: f64) -> f64 {
+    (ms * 100.0).round() / 100.0
+}
+
+#[async_trait]
+impl Adapter for NetworkAdapter {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        AdapterType::DevTools
+    }
+
+    async fn connect(&mut self) -> Result<()> {
+        info!(
+            id = %self.id,
+            resolver = ?self.config.resolver,
+            allow_private = self.config.allow_private,
+            "network adapter connected"
+        );
+        self.connected = true;
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        info!(id = %self.id, "network adapter disconnected");
+        self.connected = false;
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        if self.connected {
+            Ok(HealthStatus::Healthy)
+        } else {
+            Ok(HealthStatus::Unhealthy)
+        }
+    }
+
+    fn tools(&self) -> Vec<ToolDefinition> {
+        let timeout = json!({
+            "type": "integer",
+            "description": "Timeout in milliseconds (default: 3000, max: 30000)"
+        });
+        vec![
+            ToolDefinition {
+                name: "dns_lookup".into(),
+                description: "Resolve a host name to its IPv4 and IPv6 addresses and CNAME chain"
+                    .into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "host": {
+                            "type": "string",
+                            "description": "Host name to resolve"
+                        },
+                        "timeout_ms": timeout
+                    },
+                    "required": ["host"]
+                }),
+                destructive: false,
+            },
+            ToolDefinition {
+                name: "ping".into(),
+                description: "Measure round-trip latency to a host by timing TCP handshakes".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "host": {
+                            "type": "string",
+                            "description": "Host name or IP address"
+                        },
+                        "count": {
+                            "type": "integer",
+                            "description": "Number of probes (default: 4, max: 10)"
+                        },
+                        "port": {
+                            "type": "integer",
+                            "description": "TCP port to connect to (default: 443)"
+                        },
+                        "timeout_ms": timeout
+                    },
+                    "required": ["host"]
+                }),
+                destructive: false,
+            },
+            ToolDefinition {
+                name: "port_check".into(),
+                description: "Check whether a TCP port on a host is open, closed, or filtered"
+                    .into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "host": {
+                            "type": "string",
+                            "description": "Host name or IP address"
+                        },
+                        "port": {
+                            "type": "integer",
+                            "description": "TCP port number"
+                        },
+                        "timeout_ms": timeout
+                    },
+                    "required": ["host", "port"]
+                }),
+                destructive: false,
+            },
+            ToolDefinition {
+                name: "http_head".into(),
+                description: "Send an HTTP HEAD request and return the status, headers, and \
+                              latency without following redirects"
+                    .into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "url": {
+                            "type": "string",
+                            "description": "http:// or https:// URL"
+                        },
+                        "timeout_ms": timeout
+                    },
+                    "required": ["url"]
+                }),
+                destructive: false,
+            },
+        ]
+    }
+
+    async fn execute_tool(&self, name: &str, params: Value) -> Result<Value> {
+        if !self.connected {
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: name.to_string(),
+                reason: format!("adapter `{}` is not connected", self.id),
+            });
+        }
+        http_client::ensure_online()?;
+        match name {
+            "dns_lookup" => self.tool_dns_lookup(params).await,
+            "ping" => self.tool_ping(params).await,
+            "port_check" => self.tool_port_check(params).await,
+            "http_head" => self.tool_http_head(params).await,
+            _ => Err(AdapterError::ToolNotFound {
+                adapter_id: self.id.clone(),
+                tool_name: name.to_string(),
+            }),
+        }
+    }
+
+    fn required_auth(&self) -> Option<AuthRequirement> {
+        None
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::UdpSocket;
+
+    /// Serve one fixed answer per query: `203.0.113.7` for A and
+    /// `2001:db8::7` for AAAA, both with a TTL of 300.
+    async fn fixture_resolver() -> SocketAddr {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = socket.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = [0u8; 512];
+            loop {
+                let Ok((len, peer)) = socket.recv_from(&mut buf).await else {
+                    return;
+                };
+                let query = &buf[..len];
+                let qtype = u16::from_be_bytes([query[len - 4], query[len - 3]]);
+                let rdata: &[u8] = match qtype {
+                    1 => &[203, 0, 113, 7],
+                    28 => &[0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 7],
+                    _ => &[],
+                };
+                let mut reply = query.to_vec();
+                reply[2] = 0x81;
+                reply[3] = 0x80;
+                reply[7] = u8::from(!rdata.is_empty());
+                if !rdata.is_empty() {
+                    reply.extend_from_slice(&[0xc0, 12]);
+                    reply.extend_from_slice(&qtype.to_be_bytes());
+                    reply.extend_from_slice(&[0, 1, 0, 0, 1, 0x2c, 0, rdata.len() as u8]);
+                    reply.extend_from_slice(rdata);
+                }
+                let _ = socket.send_to(&reply, peer).await;
+            }
+        });
+        addr
+    }
+
+    async fn connected(config: NetworkConfig) -> NetworkAdapter {
+        let mut adapter = NetworkAdapter::new("network", config);
+        adapter.connect().await.unwrap();
+        adapter
+    }
+
+    #[tokio::test]
+    async fn dns_lookup_against_fixture_resolver() {
+        let adapter = connected(NetworkConfig {
+            resolver: Resolver::Server(fixture_resolver().await),
+            allow_private: false,
+        })
+        .await;
+
+        let result = adapter
+            .execute_tool("dns_lookup", json!({ "host": "service.example.test" }))
+            .await
+            .unwrap();
+        assert_eq!(result["host"], "service.example.test");
+        assert_eq!(result["addresses"][0]["ip"], "203.0.113.7");
+        assert_eq!(result["addresses"][0]["family"], "ipv4");
+        assert_eq!(result["addresses"][0]["ttl"], 300);
+        assert_eq!(result["addresses"][1]["ip"], "2001:db8::7");
+        assert_eq!(result["addresses"][1]["family"], "ipv6");
+        assert_eq!(result["addresses"].as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn port_check_reports_closed_port() {
+        let port = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap().port()
+        };
+        let adapter = connected(NetworkConfig {
+            allow_private: true,
+            ..Default::default()
+        })
+        .await;
+
+        let result = adapter
+            .execute_tool(
+                "port_check",
+                json!({ "host": "127.0.0.1", "port": port, "timeout_ms": 2000 }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["state"], "closed");
+        assert_eq!(result["open"], false);
+    }
+
+    #[tokio::test]
+    async fn private_targets_are_refused_by_default() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let adapter = connected(NetworkConfig::default()).await;
+
+        let err = adapter
+            .execute_tool("port_check", json!({ "host": "127.0.0.1", "port": port }))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("private"), "{err}");
+        let err = adapter
+            .execute_tool(
+                "http_head",
+                json!({ "url": format!("http://127.0.0.1:{port}/") }),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("private"), "{err}");
+    }
+}
@@ -0,0 +1,397 @@
+//! Conversion between Notion blocks and Markdown.
+//!
+//! Notion stores page content as a tree of typed blocks whose text is a list
+//! of rich-text runs.  The agent reads and writes Markdown instead, so page
+//! content is rendered with [`blocks_to_markdown`] and new content is parsed
+//! with [`markdown_to_blocks`].
+//!
+//! Only the common block types round-trip: paragraphs, headings, bulleted,
+//! numbered, and to-do list items, quotes, code, and dividers.  Other blocks
+//! are rendered from their text where they have any and skipped otherwise.
+//! Inline `**bold**`, `*italic*`, `~~strike~~`, `` `code` `` and `[links](url)` map to
+//! rich-text annotations in both directions.
+
+use serde_json::{Value, json};
+
+/// Notion rejects rich-text runs longer than this many characters.
+const MAX_TEXT_LEN: usize = 2000;
+
+// ---------------------------------------------------------------------------
+// Blocks → Markdown
+// ---------------------------------------------------------------------------
+
+/// Render a list of Notion blocks as Markdown.
+pub fn blocks_to_markdown(blocks: &[Value]) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut number = 0;
+    let mut previous = "";
+    for block in blocks {
+        let kind = block.get("type").and_then(Value::as_str).unwrap_or("");
+        let data = block.get(kind).unwrap_or(&Value::Null);
+        let text = data
+            .get("rich_text")
+            .map(rich_text_to_markdown)
+            .unwrap_or_default();
+
+        number = if kind == "numbered_list_item" {
+            number + 1
+        } else {
+            0
+        };
+        let line = match kind {
+            "paragraph" => text,
+            "heading_1" => format!("# {text}"),
+            "heading_2" => format!("## {text}"),
+            "heading_3" => format!("### {text}"),
+            "bulleted_list_item" => format!("- {text}"),
+            "numbered_list_item" => format!("{number}. {text}"),
+            "to_do" => {
+                let checked = data.get("checked").and_then(Value::as_bool) == Some(true);
+                format!("- [{}] {text}", if checked { "x" } else { " " })
+            }
+            "quote" | "callout" => format!("> {text}"),
+            "code" => {
+                let language = data
+                    .get("language")
+                    .and_then(Value::as_str)
+                    .filter(|l| *l != "plain text")
+                    .unwrap_or("");
+                // Code is rendered verbatim, without inline markup.
+                let code = data
+                    .get("rich_text")
+                    .map(rich_text_to_plain)
+                    .unwrap_or_default();
+                format!("```{language}\n{code}\n```")
+            }
+            "divider" => "---".to_string(),
+            "child_page" => {
+                let title = data.get("title").and_then(Value::as_str).unwrap_or("");
+                format!("[{title}](notion://{})", block_id(block))
+            }
+            _ if !text.is_empty() => text,
+            _ => continue,
+        };
+
+        // Items of one list sit on consecutive lines; everything else is its
+        // own paragraph.
+        let continues_list = kind == previous
+            && matches!(kind, "bulleted_list_item" | "numbered_list_item" | "to_do");
+        if !lines.is_empty() && !continues_list {
+            lines.push(String::new());
+        }
+        previous = kind;
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+/// Render rich-text runs as Markdown, keeping annotations and links.
+pub fn rich_text_to_markdown(rich_text: &Value) -> String {
+    let Some(runs) = rich_text.as_array() else {
+        return String::new();
+    };
+    runs.iter()
+        .map(|run| {
+            let mut text = run
+                .get("plain_text")
+                .or_else(|| run.pointer("/text/content"))
+                .and_then(Value::as_str)
+                .unwrap_or("")
+                .to_string();
+            if text.is_empty() {
+                return text;
+            }
+            let annotated = |flag: &str| {
+                run.pointer(&format!("/annotations/{flag}")) == Some(&Value::Bool(true))
+            };
+            if annotated("code") {
+                text = format!("`{text}`");
+            }
+            if annotated("bold") {
+                text = format!("**{text}**");
+            }
+            if annotated("italic") {
+                text = format!("*{text}*");
+            }
+            if annotated("strikethrough") {
+                text = format!("~~{text}~~");
+            }
+            let href = run
+                .get("href")
+                .or_else(|| run.pointer("/text/link/url"))
+                .and_then(Value::as_str);
+            match href {
+                Some(url) => format!("[{text}]({url})"),
+                None => text,
+            }
+        })
+        .collect()
+}
+
+/// Concatenate the plain text of rich-text runs.
+pub fn rich_text_to_plain(rich_text: &Value) -> String {
+    rich_text
+        .as_array()
+        .map(|runs| {
+            runs.iter()
+                .filter_map(|run| {
+                    run.get("plain_text")
+                        .or_else(|| run.pointer("/text/content"))
+                        .and_then(Value::as_str)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn block_id(block: &Value) -> &str {
+    block.get("id").and_then(Value::as_str).unwrap_or("")
+}
+
+// ---------------------------------------------------------------------------
+// Markdown → Blocks
+// ---------------------------------------------------------------------------
+
+/// Parse Markdown into Notion block objects suitable for `children`.
+///
+/// Consecutive plain lines are joined into one paragraph; blank lines end it.
+pub fn markdown_to_blocks(markdown: &str) -> Vec<Value> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut lines = markdown.lines();
+
+    let flush = |paragraph: &mut Vec<&str>, blocks: &mut Vec<Value>| {
+        if !paragraph.is_empty() {
+            blocks.push(text_block("paragraph", &paragraph.join("\n")));
+            paragraph.clear();
+        }
+    };
+
+    while let Some(raw) = lines.next() {
+        let line = raw.trim_end();
+        let trimmed = line.trim_start();
+
+        if let Some(language) = trimmed.strip_prefix("```") {
+            flush(&mut paragraph, &mut blocks);
+            let mut code = Vec::new();
+            for inner in lines.by_ref() {
+                if inner.trim_start().starts_with("```") {
+                    break;
+                }
+                code.push(inner);
+            }
+            blocks.push(code_block(language.trim(), &code.join("\n")));
+            continue;
+        }
+        if trimmed.is_empty() {
+            flush(&mut paragraph, &mut blocks);
+            continue;
+        }
+
+        let block = if let Some(rest) = trimmed.strip_prefix("### ") {
+            text_block("heading_3", rest)
+        } else if let Some(rest) = trimmed.strip_prefix("## ") {
+            text_block("heading_2", rest)
+        } else if let Some(rest) = trimmed.strip_prefix("# ") {
+            text_block("heading_1", rest)
+        } else if let Some((checked, rest)) = to_do_item(trimmed) {
+            let mut block = text_block("to_do", rest);
+            block["to_do"]["checked"] = json!(checked);
+            block
+        } else if let Some(rest) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            text_block("bulleted_list_item", rest)
+        } else if let Some(rest) = numbered_item(trimmed) {
+            text_block("numbered_list_item", rest)
+        } else if let Some(rest) = trimmed.strip_prefix('>') {
+            text_block("quote", rest.trim_start())
+        } else if trimmed == "---" || trimmed == "***" {
+            json!({ "object": "block", "type": "divider", "divider": {} })
+        } else {
+            paragraph.push(trimmed);
+            continue;
+        };
+        flush(&mut paragraph, &mut blocks);
+        blocks.push(block);
+    }
+    flush(&mut paragraph, &mut blocks);
+    blocks
+}
+
+/// Parse inline Markdown into Notion rich-text runs.
+pub fn markdown_to_rich_text(text: &str) -> Vec<Value> {
+    let mut runs = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let styled = match c {
+            '`' => delimited(rest, "`").map(|(inner, len)| (inner, len, "code", None)),
+            '*' if rest.starts_with("**") => {
+                delimited(rest, "**").map(|(inner, len)| (inner, len, "bold", None))
+            }
+            '*' => delimited(rest, "*").map(|(inner, len)| (inner, len, "italic", None)),
+            '~' if rest.starts_with("~~") => {
+                delimited(rest, "~~").map(|(inner, len)| (inner, len, "strikethrough", None))
+            }
+            '[' => link(rest).map(|(inner, url, len)| (inner, len, "", Some(url))),
+            _ => None,
+        };
+        match styled {
+            // `2 * 3 * 4` is not emphasis: markup must hug its text.
+            Some((inner, len, annotation, url)) if !inner.is_empty() && inner.trim() == inner => {
+                push_runs(&mut runs, &plain, None, None);
+                plain.clear();
+                let annotation = (!annotation.is_empty()).then_some(annotation);
+                push_runs(&mut runs, inner, annotation, url);
+                rest = &rest[len..];
+            }
+            _ => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    push_runs(&mut runs, &plain, None, None);
+    runs
+}
+
+/// A block of `kind` whose text is parsed from inline Markdown.
+fn text_block(kind: &str, text: &str) -> Value {
+    json!({
+        "object": "block",
+        "type": kind,
+        kind: { "rich_text": markdown_to_rich_text(text) },
+    })
+}
+
+fn code_block(language: &str, code: &str) -> Value {
+    let mut runs = Vec::new();
+    push_runs(&mut runs, code, None, None);
+    json!({
+        "object": "block",
+        "type": "code",
+        "code": {
+            "rich_text": runs,
+            "language": if language.is_empty() { "plain text" } else { language },
+        },
+    })
+}
+
+/// Append `text` as runs of at most [`MAX_TEXT_LEN`] characters.
+fn push_runs(runs: &mut Vec<Value>, text: &str, annotation: Option<&str>, url: Option<&str>) {
+    let chars: Vec<char> = text.chars().collect();
+    for chunk in chars.chunks(MAX_TEXT_LEN) {
+        let content: String = chunk.iter().collect();
+        let mut run = json!({ "type": "text", "text": { "content": content } });
+        if let Some(url) = url {
+            run["text"]["link"] = json!({ "url": url });
+        }
+        if let Some(annotation) = annotation {
+            run["annotations"] = json!({ annotation: true });
+        }
+        runs.push(run);
+    }
+}
+
+/// Match `delim…delim` at the start of `text`, returning the inner text and
+/// the total length consumed.
+fn delimited<'a>(text: &'a str, delim: &str) -> Option<(&'a str, usize)> {
+    let body = text.strip_prefix(delim)?;
+    let end = body.find(delim)?;
+    Some((&body[..end], delim.len() * 2 + end))
+}
+
+/// Match `[label](url)` at the start of `text`.
+fn link(text: &str) -> Option<(&str, &str, usize)> {
+    let body = text.strip_prefix('[')?;
+    let label_end = body.find("](")?;
+    let after = &body[label_end + 2..];
+    let url_end = after.find(')')?;
+    Some((
+        &body[..label_end],
+        &after[..url_end],
+        1 + label_end + 2 + url_end + 1,
+    ))
+}
+
+/// `- [ ] item` or `- [x] item`.
+fn to_do_item(line: &str) -> Option<(bool, &str)> {
+    let rest = line
+        .strip_prefix("- [")
+        .or_else(|| line.strip_prefix("* ["))?;
+    let (mark, text) = rest.split_once("] ")?;
+    match mark {
+        " " => Some((false, text)),
+        "x" | "X" => Some((true, text)),
+        _ => None,
+    }
+}
+
+/// `1. item`.
+fn numbered_item(line: &str) -> Option<&str> {
+    let (number, text) = line.split_once(". ")?;
+    (!number.is_empty() && number.bytes().all(|b| b.is_ascii_digit())).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_round_trips_through_blocks() {
+        let markdown = "# Plan\n\nShip the **beta** by *Friday*.\n\n\
+                        - draft notes\n- review\n\n\
+                        1. build\n2. deploy\n\n\
+                        - [x] tests pass\n- [ ] changelog\n\n\
+                        > See [the doc](https://example.com)\n\n\
+                        ```rust\nfn main() {}\n```\n\n---";
+        let blocks = markdown_to_blocks(markdown);
+        let kinds: Vec<&str> = blocks.iter().map(|b| b["type"].as_str().unwrap()).collect();
+        assert_eq!(
+            kinds,
+            [
+                "heading_1",
+                "paragraph",
+                "bulleted_list_item",
+                "bulleted_list_item",
+                "numbered_list_item",
+                "numbered_list_item",
+                "to_do",
+                "to_do",
+                "quote",
+                "code",
+                "divider",
+            ]
+        );
+        assert_eq!(blocks[6]["to_do"]["checked"], true);
+        assert_eq!(blocks[9]["code"]["language"], "rust");
+
+        // Blocks written by the API carry `plain_text`; requests only carry
+        // `text.content`, which is read as a fallback.
+        assert_eq!(blocks_to_markdown(&blocks), markdown);
+    }
+
+    #[test]
+    fn inline_markup_becomes_annotations() {
+        let runs = markdown_to_rich_text("run `cargo test` then **merge**");
+        assert_eq!(runs.len(), 4);
+        assert_eq!(runs[1]["text"]["content"], "cargo test");
+        assert_eq!(runs[1]["annotations"]["code"], true);
+        assert_eq!(runs[3]["annotations"]["bold"], true);
+
+        let plain = markdown_to_rich_text("2 * 3 * 4 = 24");
+        assert_eq!(plain.len(), 1);
+        assert_eq!(plain[0]["text"]["content"], "2 * 3 * 4 = 24");
+    }
+
+    #[test]
+    fn long_text_is_split_into_runs() {
+        let text = "a".repeat(MAX_TEXT_LEN + 10);
+        let runs = markdown_to_rich_text(&text);
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[1]["text"]["content"].as_str().unwrap().len(), 10);
+    }
+}
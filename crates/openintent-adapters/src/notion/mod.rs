@@ -0,0 +1,962 @@
+//! Notion API adapter for OpenIntentOS.
+//!
+//! Provides tools for searching a workspace, reading and creating pages,
+//! querying databases, and appending content to pages.  The integration
+//! token comes from the adapter's configuration, the `NOTION_TOKEN`
+//! environment variable, or the `notion` provider in the auth engine (backed
+//! by the vault), in that order.
+//!
+//! Page content is exchanged as Markdown; see [`markdown`] for how it maps
+//! onto Notion's block model.  Database rows are returned with their
+//! properties flattened to plain JSON values.
+
+pub mod markdown;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use openintent_auth_engine::AuthManager;
+use reqwest::Method;
+use serde_json::{Map, Value, json};
+use tracing::{debug, info, warn};
+
+use crate::credentials;
+use crate::error::{AdapterError, Result};
+use crate::http_client::{self, HttpClientFactory};
+use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
+
+/// Notion API base URL.
+const DEFAULT_BASE_URL: &str = "https://api.notion.com/v1";
+
+/// Notion API version sent with every request.
+const NOTION_VERSION: &str = "2022-06-28";
+
+/// Provider name used for the integration token in the auth engine.
+const AUTH_PROVIDER: &str = "notion";
+
+/// Default page size for search and database queries.
+const DEFAULT_PAGE_SIZE: u64 = 25;
+
+/// Notion accepts at most this many blocks per append request.
+const MAX_BLOCKS_PER_REQUEST: usize = 100;
+
+/// Upper bound on blocks read when rendering a page's content.
+const MAX_PAGE_BLOCKS: usize = 1000;
+
+/// Notion API adapter.
+pub struct NotionAdapter {
+    /// Unique identifier for this adapter instance.
+    id: String,
+    /// Whether the adapter has been connected.
+    connected: bool,
+    /// Internal integration token (`secret_...` / `ntn_...`).
+    token: Option<String>,
+    /// Auth engine consulted for a token when none is configured.
+    auth: Option<Arc<AuthManager>>,
+    /// Base URL for the Notion API.
+    base_url: String,
+    /// HTTP client for making requests.
+    client: reqwest::Client,
+}
+
+impl NotionAdapter {
+    /// Create a new Notion adapter with no token configured.
+    pub fn new(id: impl Into<String>) -> Self {
+        let client = HttpClientFactory::default().build().unwrap_or_default();
+
+        Self {
+            id: id.into(),
+            connected: false,
+            token: None,
+            auth: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            client,
+        }
+    }
+
+    /// Create a new Notion adapter with a pre-configured integration token.
+    pub fn with_token(id: impl Into<String>, token: impl Into<String>) -> Self {
+        let mut adapter = Self::new(id);
+        adapter.token = Some(token.into());
+        adapter
+    }
+
+    /// Look up the integration token in the auth engine on connect if none
+    /// is set.
+    pub fn with_auth_manager(mut self, auth: Arc<AuthManager>) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Point the adapter at a different API base URL (e.g. a test server).
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Look for a token in the environment, then in the auth engine.
+    async fn find_token(&self) -> Option<String> {
+        if let Ok(token) = std::env::var("NOTION_TOKEN")
+            && !token.is_empty()
+        {
+            info!(id = %self.id, "Notion adapter loaded token from environment");
+            return Some(token);
+        }
+        let auth = self.auth.as_ref()?;
+        match auth.get_valid_token(AUTH_PROVIDER, None).await {
+            Ok(token) => {
+                info!(id = %self.id, "Notion adapter loaded token from auth engine");
+                Some(token)
+            }
+            Err(e) => {
+                debug!(id = %self.id, error = %e, "no Notion token in auth engine");
+                None
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // HTTP helpers
+    // -----------------------------------------------------------------------
+
+    /// Build a full API URL from a path like `/pages/{id}`.
+    fn api_url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// Resolve the integration token, returning an error if none is available.
+    fn resolve_token(&self) -> Result<&str> {
+        self.token
+            .as_deref()
+            .ok_or_else(|| AdapterError::AuthRequired {
+                adapter_id: self.id.clone(),
+                provider: AUTH_PROVIDER.to_string(),
+            })
+    }
+
+    /// Send a request to the Notion API and return its JSON body, mapping
+    /// throttling, auth failures, and error objects to adapter errors.
+    async fn call(
+        &self,
+        tool_name: &str,
+        method: Method,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<Value> {
+        let token = self.resolve_token()?;
+        let url = self.api_url(path);
+        debug!(url = %url, method = %method, tool = tool_name, "Notion request");
+
+        let mut request = self
+            .client
+            .request(method, &url)
+            .bearer_auth(token)
+            .header("Notion-Version", NOTION_VERSION);
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AdapterError::ExecutionFailed {
+                tool_name: tool_name.to_string(),
+                reason: format!("Notion API request failed: {e}"),
+            })?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let retry_after_secs = response
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u64>().ok());
+            warn!(
+                tool = tool_name,
+                ?retry_after_secs,
+                "Notion API rate limited"
+            );
+            return Err(AdapterError::RateLimited {
+                adapter_id: self.id.clone(),
+                retry_after_secs,
+            });
+        }
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(AdapterError::AuthRequired {
+                adapter_id: self.id.clone(),
+                provider: AUTH_PROVIDER.to_string(),
+            });
+        }
+
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| AdapterError::ExecutionFailed {
+                tool_name: tool_name.to_string(),
+                reason: format!("failed to parse Notion API response (HTTP {status}): {e}"),
+            })?;
+        if !status.is_success() {
+            let code = body
+                .get("code")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown_error");
+            let message = body.get("message").and_then(Value::as_str).unwrap_or("");
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: tool_name.to_string(),
+                reason: format!("Notion API returned {status} {code}: {message}"),
+            });
+        }
+        Ok(body)
+    }
+
+    /// Read all child blocks of `block_id`, following pagination.
+    async fn fetch_children(&self, tool_name: &str, block_id: &str) -> Result<Vec<Value>> {
+        let mut blocks = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut path = format!("/blocks/{block_id}/children?page_size=100");
+            if let Some(cursor) = &cursor {
+                path.push_str(&format!("&start_cursor={cursor}"));
+            }
+            let page = self.call(tool_name, Method::GET, &path, None).await?;
+            if let Some(results) = page.get("results").and_then(Value::as_array) {
+                blocks.extend(results.iter().cloned());
+            }
+            cursor = page
+                .get("next_cursor")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            if cursor.is_none() || blocks.len() >= MAX_PAGE_BLOCKS {
+                break;
+            }
+        }
+        blocks.truncate(MAX_PAGE_BLOCKS);
+        Ok(blocks)
+    }
+
+    // -----------------------------------------------------------------------
+    // Tool implementations
+    // -----------------------------------------------------------------------
+
+    /// Search pages and databases shared with the integration.
+    async fn tool_search(&self, params: Value) -> Result<Value> {
+        let tool = "notion_search";
+        let mut body = json!({
+            "page_size": page_size(&params),
+        });
+        if let Some(query) = params.get("query").and_then(Value::as_str) {
+            body["query"] = json!(query);
+        }
+        match params.get("filter").and_then(Value::as_str) {
+            Some(object @ ("page" | "database")) => {
+                body["filter"] = json!({ "property": "object", "value": object });
+            }
+            Some(other) => {
+                return Err(AdapterError::InvalidParams {
+                    tool_name: tool.into(),
+                    reason: format!("`filter` must be \"page\" or \"database\", got {other:?}"),
+                });
+            }
+            None => {}
+        }
+        if let Some(cursor) = params.get("start_cursor").and_then(Value::as_str) {
+            body["start_cursor"] = json!(cursor);
+        }
+
+        let resp = self
+            .call(tool, Method::POST, "/search", Some(&body))
+            .await?;
+        let results: Vec<Value> = resp
+            .get("results")
+            .and_then(Value::as_array)
+            .map(|list| list.iter().map(summarize_object).collect())
+            .unwrap_or_default();
+        Ok(json!({
+            "results": results,
+            "next_cursor": resp.get("next_cursor"),
+        }))
+    }
+
+    /// Read a page's properties and, optionally, its content as Markdown.
+    async fn tool_get_page(&self, params: Value) -> Result<Value> {
+        let tool = "notion_get_page";
+        let page_id = require_str(tool, &params, "page_id")?;
+        let include_content = params
+            .get("include_content")
+            .and_then(Value::as_bool)
+            .unwrap_or(true);
+
+        let page = self
+            .call(tool, Method::GET, &format!("/pages/{page_id}"), None)
+            .await?;
+        let mut result = summarize_object(&page);
+        if include_content {
+            let blocks = self.fetch_children(tool, page_id).await?;
+            result["content"] = json!(markdown::blocks_to_markdown(&blocks));
+        }
+        Ok(result)
+    }
+
+    /// Create a page under a page or database, with Markdown content.
+    async fn tool_create_page(&self, params: Value) -> Result<Value> {
+        let tool = "notion_create_page";
+        let parent = match params.get("parent") {
+            Some(Value::String(id)) => json!({ "page_id": id }),
+            Some(Value::Object(obj))
+                if obj.contains_key("page_id") || obj.contains_key("database_id") =>
+            {
+                Value::Object(obj.clone())
+            }
+            _ => {
+                return Err(AdapterError::InvalidParams {
+                    tool_name: tool.into(),
+                    reason: "`parent` must be a page ID or an object with `page_id` or \
+                             `database_id`"
+                        .into(),
+                });
+            }
+        };
+
+        let mut properties = match params.get("properties") {
+            Some(Value::Object(props)) => props.clone(),
+            Some(_) => {
+                return Err(AdapterError::InvalidParams {
+                    tool_name: tool.into(),
+                    reason: "`properties` must be an object".into(),
+                });
+            }
+            None => Map::new(),
+        };
+        // Pages under a page only have a title; accept it as a plain string.
+        if let Some(title) = params.get("title").and_then(Value::as_str) {
+            properties.insert(
+                "title".into(),
+                json!({ "title": markdown::markdown_to_rich_text(title) }),
+            );
+        }
+        if properties.is_empty() {
+            return Err(AdapterError::InvalidParams {
+                tool_name: tool.into(),
+                reason: "either `title` or `properties` is required".into(),
+            });
+        }
+
+        let mut body = json!({ "parent": parent, "properties": properties });
+        let children = params
+            .get("content")
+            .and_then(Value::as_str)
+            .map(markdown::markdown_to_blocks)
+            .unwrap_or_default();
+        let (first, rest) = children.split_at(children.len().min(MAX_BLOCKS_PER_REQUEST));
+        if !first.is_empty() {
+            body["children"] = json!(first);
+        }
+
+        let page = self.call(tool, Method::POST, "/pages", Some(&body)).await?;
+        let page_id = page.get("id").and_then(Value::as_str).unwrap_or_default();
+        if !rest.is_empty() {
+            self.append_children(tool, page_id, rest).await?;
+        }
+        info!(page_id = page_id, "Notion page created");
+        Ok(json!({
+            "success": true,
+            "id": page_id,
+            "url": page.get("url"),
+        }))
+    }
+
+    /// Query a database with optional filter and sorts.
+    async fn tool_query_database(&self, params: Value) -> Result<Value> {
+        let tool = "notion_query_database";
+        let database_id = require_str(tool, &params, "database_id")?;
+
+        let mut body = json!({ "page_size": page_size(&params) });
+        if let Some(filter) = params.get("filter").filter(|f| !f.is_null()) {
+            body["filter"] = filter.clone();
+        }
+        if let Some(sorts) = params.get("sorts").filter(|s| !s.is_null()) {
+            if !sorts.is_array() {
+                return Err(AdapterError::InvalidParams {
+                    tool_name: tool.into(),
+                    reason: "`sorts` must be an array".into(),
+                });
+            }
+            body["sorts"] = sorts.clone();
+        }
+        if let Some(cursor) = params.get("start_cursor").and_then(Value::as_str) {
+            body["start_cursor"] = json!(cursor);
+        }
+
+        let resp = self
+            .call(
+                tool,
+                Method::POST,
+                &format!("/databases/{database_id}/query"),
+                Some(&body),
+            )
+            .await?;
+        let rows: Vec<Value> = resp
+            .get("results")
+            .and_then(Value::as_array)
+            .map(|list| {
+                list.iter()
+                    .map(|row| {
+                        json!({
+                            "id": row.get("id"),
+                            "url": row.get("url"),
+                            "properties": flatten_properties(row.get("properties")),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(json!({
+            "rows": rows,
+            "next_cursor": resp.get("next_cursor"),
+        }))
+    }
+
+    /// Append Markdown content (or raw blocks) to a page or block.
+    async fn tool_append_blocks(&self, params: Value) -> Result<Value> {
+        let tool = "notion_append_blocks";
+        let block_id = require_str(tool, &params, "block_id")?;
+        let blocks = match (params.get("content"), params.get("blocks")) {
+            (Some(Value::String(content)), _) => markdown::markdown_to_blocks(content),
+            (_, Some(Value::Array(blocks))) => blocks.clone(),
+            _ => {
+                return Err(AdapterError::InvalidParams {
+                    tool_name: tool.into(),
+                    reason: "either `content` (Markdown) or `blocks` (array) is required".into(),
+                });
+            }
+        };
+        if blocks.is_empty() {
+            return Err(AdapterError::InvalidParams {
+                tool_name: tool.into(),
+                reason: "nothing to append".into(),
+            });
+        }
+
+        let appended = self.append_children(tool, block_id, &blocks).await?;
+        info!(
+            block_id = block_id,
+            count = appended,
+            "Notion blocks appended"
+        );
+        Ok(json!({
+            "success": true,
+            "appended": appended,
+        }))
+    }
+
+    /// Append `blocks` under `block_id` in batches Notion accepts.
+    async fn append_children(
+        &self,
+        tool_name: &str,
+        block_id: &str,
+        blocks: &[Value],
+    ) -> Result<usize> {
+        for batch in blocks.chunks(MAX_BLOCKS_PER_REQUEST) {
+            let body = json!({ "children": batch });
+            self.call(
+                tool_name,
+                Method::PATCH,
+                &format!("/blocks/{block_id}/children"),
+                Some(&body),
+            )
+            .await?;
+        }
+        Ok(blocks.len())
+    }
+}
+
+#[async_trait]
+impl Adapter for NotionAdapter {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        AdapterType::Productivity
+    }
+
+    async fn connect(&mut self) -> Result<()> {
+        if self.token.is_none() {
+            self.token = self.find_token().await;
+        }
+        if self.token.is_none() {
+            warn!(
+                id = %self.id,
+                "no Notion token configured; Notion adapter connecting without auth"
+            );
+        }
+        self.connected = true;
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        info!(id = %self.id, "Notion adapter disconnected");
+        self.token = None;
+        self.connected = false;
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        if !self.connected {
+            return Ok(HealthStatus::Unhealthy);
+        }
+        if self.token.is_some() {
+            Ok(HealthStatus::Healthy)
+        } else {
+            Ok(HealthStatus::Degraded)
+        }
+    }
+
+    fn tools(&self) -> Vec<ToolDefinition> {
+        vec![
+            ToolDefinition {
+                name: "notion_search".into(),
+                description: "Search Notion pages and databases shared with the integration".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "query": {
+                            "type": "string",
+                            "description": "Text to match against titles (omit to list everything)"
+                        },
+                        "filter": {
+                            "type": "string",
+                            "enum": ["page", "database"],
+                            "description": "Only return pages or only databases"
+                        },
+                        "page_size": {
+                            "type": "integer",
+                            "description": "Maximum results to return (default 25, max 100)"
+                        },
+                        "start_cursor": {
+                            "type": "string",
+                            "description": "Pagination cursor from a previous call's next_cursor"
+                        }
+                    }
+                }),
+                destructive: false,
+            },
+            ToolDefinition {
+                name: "notion_get_page".into(),
+                description: "Get a Notion page's properties and its content as Markdown".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "page_id": {
+                            "type": "string",
+                            "description": "Page ID"
+                        },
+                        "include_content": {
+                            "type": "boolean",
+                            "description": "Whether to read the page body (default true)"
+                        }
+                    },
+                    "required": ["page_id"]
+                }),
+                destructive: false,
+            },
+            ToolDefinition {
+                name: "notion_create_page".into(),
+                description: "Create a Notion page under a page or database, with Markdown content"
+                    .into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "parent": {
+                            "description": "Parent page ID, or {\"page_id\": ...} / {\"database_id\": ...}"
+                        },
+                        "title": {
+                            "type": "string",
+                            "description": "Page title (for pages under a page)"
+                        },
+                        "properties": {
+                            "type": "object",
+                            "description": "Notion property values (required for database rows)"
+                        },
+                        "content": {
+                            "type": "string",
+                            "description": "Page body in Markdown"
+                        }
+                    },
+                    "required": ["parent"]
+                }),
+                destructive: true,
+            },
+            ToolDefinition {
+                name: "notion_query_database".into(),
+                description: "Query rows of a Notion database with an optional filter and sorts"
+                    .into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "database_id": {
+                            "type": "string",
+                            "description": "Database ID"
+                        },
+                        "filter": {
+                            "type": "object",
+                            "description": "Notion filter object, e.g. {\"property\": \"Status\", \"status\": {\"equals\": \"Done\"}}"
+                        },
+                        "sorts": {
+                            "type": "array",
+                            "description": "Notion sort objects, e.g. [{\"property\": \"Due\", \"direction\": \"ascending\"}]"
+                        },
+                        "page_size": {
+                            "type": "integer",
+                            "description": "Maximum rows to return (default 25, max 100)"
+                        },
+                        "start_cursor": {
+                            "type": "string",
+                            "description": "Pagination cursor from a previous call's next_cursor"
+                        }
+                    },
+                    "required": ["database_id"]
+                }),
+                destructive: false,
+            },
+            ToolDefinition {
+                name: "notion_append_blocks".into(),
+                description: "Append Markdown content to a Notion page or block".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "block_id": {
+                            "type": "string",
+                            "description": "Page or block ID to append to"
+                        },
+                        "content": {
+                            "type": "string",
+                            "description": "Content in Markdown"
+                        },
+                        "blocks": {
+                            "type": "array",
+                            "description": "Raw Notion block objects, used when `content` is absent"
+                        }
+                    },
+                    "required": ["block_id"]
+                }),
+                destructive: true,
+            },
+        ]
+    }
+
+    async fn execute_tool(&self, name: &str, params: Value) -> Result<Value> {
+        if !self.connected {
+            return Err(AdapterError::NotConnected {
+                adapter_id: self.id.clone(),
+                reason: "adapter not connected".into(),
+            });
+        }
+        http_client::ensure_online()?;
+
+        match name {
+            "notion_search" => self.tool_search(params).await,
+            "notion_get_page" => self.tool_get_page(params).await,
+            "notion_create_page" => self.tool_create_page(params).await,
+            "notion_query_database" => self.tool_query_database(params).await,
+            "notion_append_blocks" => self.tool_append_blocks(params).await,
+            _ => Err(AdapterError::ToolNotFound {
+                adapter_id: self.id.clone(),
+                tool_name: name.to_string(),
+            }),
+        }
+    }
+
+    fn required_auth(&self) -> Option<AuthRequirement> {
+        Some(AuthRequirement {
+            provider: AUTH_PROVIDER.into(),
+            scopes: vec![
+                "read_content".into(),
+                "update_content".into(),
+                "insert_content".into(),
+            ],
+        })
+    }
+
+    fn has_credentials(&self) -> bool {
+        credentials::is_set(self.token.as_deref())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Extract a required string parameter.
+fn require_str<'a>(tool_name: &str, params: &'a Value, field: &str) -> Result<&'a str> {
+    params
+        .get(field)
+        .and_then(Value::as_str)
+        .ok_or_else(|| AdapterError::InvalidParams {
+            tool_name: tool_name.into(),
+            reason: format!("missing required string field: {field}"),
+        })
+}
+
+/// The `page_size` parameter, clamped to what Notion accepts.
+fn page_size(params: &Value) -> u64 {
+    params
+        .get("page_size")
+        .and_then(Value::as_u64)
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, 100)
+}
+
+/// Summarize a page or database object as ID, kind, title, and URL.
+fn summarize_object(object: &Value) -> Value {
+    let title = match object.get("object").and_then(Value::as_str) {
+        // Databases carry their title at the top level.
+        Some("database") => object
+            .get("title")
+            .map(markdown::rich_text_to_plain)
+            .unwrap_or_default(),
+        // A page's title lives in whichever property has type `title`.
+        _ => object
+            .get("properties")
+            .and_then(Value::as_object)
+            .and_then(|props| {
+                props
+                    .values()
+                    .find(|p| p.get("type").and_then(Value::as_str) == Some("title"))
+            })
+            .and_then(|p| p.get("title"))
+            .map(markdown::rich_text_to_plain)
+            .unwrap_or_default(),
+    };
+    let mut summary = json!({
+        "id": object.get("id"),
+        "object": object.get("object"),
+        "title": title,
+        "url": object.get("url"),
+        "last_edited_time": object.get("last_edited_time"),
+    });
+    if object.get("object").and_then(Value::as_str) == Some("page") {
+        summary["properties"] = flatten_properties(object.get("properties"));
+    }
+    summary
+}
+
+/// Flatten Notion property values to plain JSON keyed by property name.
+fn flatten_properties(properties: Option<&Value>) -> Value {
+    let Some(properties) = properties.and_then(Value::as_object) else {
+        return json!({});
+    };
+    let flat: Map<String, Value> = properties
+        .iter()
+        .map(|(name, prop)| (name.clone(), flatten_property(prop)))
+        .collect();
+    Value::Object(flat)
+}
+
+/// Reduce one property value to its plain content.
+fn flatten_property(prop: &Value) -> Value {
+    let kind = prop.get("type").and_then(Value::as_str).unwrap_or("");
+    let value = prop.get(kind).unwrap_or(&Value::Null);
+    match kind {
+        "title" | "rich_text" => json!(markdown::rich_text_to_plain(value)),
+        "select" | "status" => value.get("name").cloned().unwrap_or(Value::Null),
+        "multi_select" => json!(names(value)),
+        "people" => json!(
+            value
+                .as_array()
+                .map(|people| {
+                    people
+                        .iter()
+                        .filter_map(|p| p.get("name").or_else(|| p.get("id")).cloned())
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        ),
+        "relation" => json!(
+            value
+                .as_array()
+                .map(|items| items
+                    .iter()
+                    .filter_map(|r| r.get("id").cloned())
+                    .collect::<Vec<_>>())
+                .unwrap_or_default()
+        ),
+        "date" => match value.get("end").filter(|e| !e.is_null()) {
+            Some(end) => json!({ "start": value.get("start"), "end": end }),
+            None => value.get("start").cloned().unwrap_or(Value::Null),
+        },
+        "formula" => {
+            let inner = value.get("type").and_then(Value::as_str).unwrap_or("");
+            value.get(inner).cloned().unwrap_or(Value::Null)
+        }
+        "files" => json!(names(value)),
+        _ => value.clone(),
+    }
+}
+
+/// The `name` of each entry in an array value.
+fn names(value: &Value) -> Vec<Value> {
+    value
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|i| i.get("name").cloned())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::webhook::{read_request, write_response};
+
+    /// A request the mock Notion API received.
+    #[derive(Debug)]
+    struct Recorded {
+        method: String,
+        path: String,
+        notion_version: Option<String>,
+        body: String,
+    }
+
+    /// Serve a mock Notion API that answers each call with `reply`.
+    async fn mock_notion(reply: Value) -> (String, Arc<Mutex<Vec<Recorded>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&recorded);
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let Ok(request) = read_request(&mut stream).await else {
+                    continue;
+                };
+                log.lock().unwrap().push(Recorded {
+                    notion_version: request.header("notion-version").map(str::to_string),
+                    method: request.method,
+                    path: request.path,
+                    body: String::from_utf8_lossy(&request.body).into_owned(),
+                });
+                let _ = write_response(
+                    &mut stream,
+                    "200 OK",
+                    "application/json",
+                    &reply.to_string(),
+                )
+                .await;
+            }
+        });
+        (base, recorded)
+    }
+
+    async fn connected(base: &str) -> NotionAdapter {
+        let mut adapter = NotionAdapter::with_token("notion", "secret_test").with_base_url(base);
+        adapter.connect().await.unwrap();
+        adapter
+    }
+
+    #[tokio::test]
+    async fn query_database_flattens_rows() {
+        let (base, recorded) = mock_notion(json!({
+            "object": "list",
+            "results": [{
+                "object": "page",
+                "id": "row-1",
+                "url": "https://www.notion.so/row-1",
+                "properties": {
+                    "Name": { "type": "title", "title": [{ "plain_text": "Write launch post" }] },
+                    "Status": { "type": "status", "status": { "name": "In progress" } },
+                    "Tags": { "type": "multi_select",
+                              "multi_select": [{ "name": "blog" }, { "name": "launch" }] },
+                    "Due": { "type": "date", "date": { "start": "2026-11-01", "end": null } },
+                    "Points": { "type": "number", "number": 3 }
+                }
+            }],
+            "next_cursor": null,
+            "has_more": false
+        }))
+        .await;
+        let adapter = connected(&base).await;
+
+        let filter = json!({ "property": "Status", "status": { "equals": "In progress" } });
+        let sorts = json!([{ "property": "Due", "direction": "ascending" }]);
+        let result = adapter
+            .execute_tool(
+                "notion_query_database",
+                json!({ "database_id": "db-1", "filter": filter, "sorts": sorts }),
+            )
+            .await
+            .unwrap();
+
+        let row = &result["rows"][0];
+        assert_eq!(row["id"], "row-1");
+        assert_eq!(row["properties"]["Name"], "Write launch post");
+        assert_eq!(row["properties"]["Status"], "In progress");
+        assert_eq!(row["properties"]["Tags"], json!(["blog", "launch"]));
+        assert_eq!(row["properties"]["Due"], "2026-11-01");
+        assert_eq!(row["properties"]["Points"], 3);
+
+        let recorded = recorded.lock().unwrap();
+        assert_eq!(recorded[0].method, "POST");
+        assert_eq!(recorded[0].path, "/databases/db-1/query");
+        assert_eq!(recorded[0].notion_version.as_deref(), Some(NOTION_VERSION));
+        let body: Value = serde_json::from_str(&recorded[0].body).unwrap();
+        assert_eq!(body["filter"], filter);
+        assert_eq!(body["sorts"], sorts);
+    }
+
+    #[tokio::test]
+    async fn append_blocks_sends_paragraph() {
+        let (base, recorded) = mock_notion(json!({ "object": "list", "results": [] })).await;
+        let adapter = connected(&base).await;
+
+        let result = adapter
+            .execute_tool(
+                "notion_append_blocks",
+                json!({ "block_id": "page-1", "content": "Meeting moved to **3pm**." }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["appended"], 1);
+
+        let recorded = recorded.lock().unwrap();
+        assert_eq!(recorded[0].method, "PATCH");
+        assert_eq!(recorded[0].path, "/blocks/page-1/children");
+        let body: Value = serde_json::from_str(&recorded[0].body).unwrap();
+        let block = &body["children"][0];
+        assert_eq!(block["type"], "paragraph");
+        let runs = &block["paragraph"]["rich_text"];
+        assert_eq!(runs[0]["text"]["content"], "Meeting moved to ");
+        assert_eq!(runs[1]["text"]["content"], "3pm");
+        assert_eq!(runs[1]["annotations"]["bold"], true);
+    }
+
+    #[tokio::test]
+    async fn unauthorized_requires_auth() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            if let Ok((mut stream, _)) = listener.accept().await {
+                let _ = read_request(&mut stream).await;
+                let body = r#"{"object":"error","status":401,"code":"unauthorized"}"#;
+                let _ =
+                    write_response(&mut stream, "401 Unauthorized", "application/json", body).await;
+            }
+        });
+        let adapter = connected(&base).await;
+
+        let err = adapter
+            .execute_tool("notion_search", json!({ "query": "roadmap" }))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AdapterError::AuthRequired { .. }));
+    }
+}
@@ -0,0 +1,620 @@
+//! PostgreSQL database adapter for OpenIntentOS.
+//!
+//! Provides structured data access to a PostgreSQL server.  Statements always
+//! go through the extended query protocol with values sent separately from
+//! the SQL text, so the server itself rejects text that holds more than one
+//! statement.
+
+use std::future::Future;
+
+use async_trait::async_trait;
+use openintent_vault::store::Vault;
+use serde_json::{Map, Value};
+use sqlx::pool::PoolConnection;
+use sqlx::postgres::{PgArguments, PgRow};
+use sqlx::{Column, Connection, Executor, PgPool, Postgres, Row, TypeInfo};
+
+use crate::error::{AdapterError, Result};
+use crate::postgres::connection::connect_pool;
+use crate::postgres::{PostgresError, PostgresPoolConfig, connection_url_from_vault, schema};
+use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
+
+/// Schema used when a tool call does not name one.
+const DEFAULT_SCHEMA: &str = "public";
+
+/// PostgreSQL database adapter for OpenIntentOS.
+pub struct PostgresAdapter {
+    /// Connection string; never logged because it may carry a password.
+    url: String,
+    config: PostgresPoolConfig,
+    pool: Option<PgPool>,
+}
+
+impl PostgresAdapter {
+    /// Create a new PostgreSQL adapter for the given connection string.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            config: PostgresPoolConfig::default(),
+            pool: None,
+        }
+    }
+
+    /// Create a new PostgreSQL adapter whose connection string is stored in
+    /// the vault under `provider`.
+    pub fn from_vault(vault: &Vault, provider: &str) -> Result<Self> {
+        let url = connection_url_from_vault(vault, provider)
+            .map_err(|e| AdapterError::ConfigError(e.to_string()))?;
+        Ok(Self::new(url))
+    }
+
+    /// Use explicit pool size and timeouts.
+    pub fn with_pool_config(mut self, config: PostgresPoolConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Execute a read-only SQL query and return results as JSON.
+    ///
+    /// The query runs in a `READ ONLY` transaction that is always rolled
+    /// back, so PostgreSQL itself rejects any statement that would write.
+    async fn execute_query(&self, sql: &str, params: Vec<Value>) -> Result<Value> {
+        const TOOL: &str = "postgres_query";
+
+        let mut query = sqlx::query(sql);
+        for param in params {
+            query = Self::bind_json_param(TOOL, query, param)?;
+        }
+
+        let mut conn = self.acquire(TOOL).await?;
+        let rows = self
+            .timed(TOOL, async {
+                let mut tx = conn.begin().await?;
+                tx.execute("SET TRANSACTION READ ONLY").await?;
+                let rows = query.fetch_all(&mut *tx).await?;
+                tx.rollback().await?;
+                Ok(rows)
+            })
+            .await?;
+
+        Ok(Value::Array(rows.iter().map(row_to_json).collect()))
+    }
+
+    /// Execute a SQL command (INSERT, UPDATE, DELETE, DDL) and return
+    /// affected rows.
+    async fn execute_command(&self, sql: &str, params: Vec<Value>) -> Result<Value> {
+        const TOOL: &str = "postgres_execute";
+
+        let mut query = sqlx::query(sql);
+        for param in params {
+            query = Self::bind_json_param(TOOL, query, param)?;
+        }
+
+        let mut conn = self.acquire(TOOL).await?;
+        let result = self.timed(TOOL, query.execute(&mut *conn)).await?;
+
+        Ok(serde_json::json!({ "rows_affected": result.rows_affected() }))
+    }
+
+    /// Check out a pooled connection.
+    async fn acquire(&self, tool_name: &str) -> Result<PoolConnection<Postgres>> {
+        self.resolve_pool(tool_name)?
+            .acquire()
+            .await
+            .map_err(|e| Self::tool_error(tool_name, e.into()))
+    }
+
+    /// Await `work`, bounded by the statement timeout.
+    ///
+    /// The server cancels overrunning statements on its own; the client-side
+    /// limit covers a server that stops responding.  sqlx drains a connection
+    /// abandoned mid-statement before its next use.
+    async fn timed<T>(
+        &self,
+        tool_name: &str,
+        work: impl Future<Output = std::result::Result<T, sqlx::Error>>,
+    ) -> Result<T> {
+        let limit = self.config.statement_timeout;
+        match tokio::time::timeout(limit, work).await {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(e)) if PostgresError::is_statement_timeout(&e) => {
+                Err(Self::tool_error(tool_name, PostgresError::Timeout(limit)))
+            }
+            Ok(Err(e)) => Err(Self::tool_error(tool_name, e.into())),
+            Err(_) => Err(Self::tool_error(tool_name, PostgresError::Timeout(limit))),
+        }
+    }
+
+    /// Convert a [`PostgresError`] into the adapter error surfaced to the
+    /// agent.
+    fn tool_error(tool_name: &str, err: PostgresError) -> AdapterError {
+        match err {
+            PostgresError::Timeout(limit) => AdapterError::Timeout {
+                seconds: limit.as_secs(),
+                reason: format!("`{tool_name}`: {err}"),
+            },
+            other => AdapterError::ExecutionFailed {
+                tool_name: tool_name.into(),
+                reason: other.to_string(),
+            },
+        }
+    }
+
+    /// The connection pool, or an error if the adapter is not connected.
+    fn resolve_pool(&self, tool_name: &str) -> Result<&PgPool> {
+        self.pool
+            .as_ref()
+            .ok_or_else(|| AdapterError::ExecutionFailed {
+                tool_name: tool_name.into(),
+                reason: "postgres adapter is not connected".into(),
+            })
+    }
+
+    /// Extract and validate the `sql` text and positional `params` of a
+    /// statement tool call.
+    fn statement_args<'a>(tool_name: &str, params: &'a Value) -> Result<(&'a str, Vec<Value>)> {
+        let sql = params
+            .get("sql")
+            .and_then(|v| v.as_str())
+            .filter(|sql| !sql.trim().is_empty())
+            .ok_or_else(|| AdapterError::InvalidParams {
+                tool_name: tool_name.into(),
+                reason: "missing required field `sql`".into(),
+            })?;
+
+        let sql_params = match params.get("params") {
+            None | Some(Value::Null) => Vec::new(),
+            Some(Value::Array(values)) => values.clone(),
+            Some(other) => {
+                return Err(AdapterError::InvalidParams {
+                    tool_name: tool_name.into(),
+                    reason: format!("`params` must be an array, got {other}"),
+                });
+            }
+        };
+
+        Ok((sql, sql_params))
+    }
+
+    /// Bind a JSON value to an sqlx query.
+    ///
+    /// Objects and arrays are bound as `jsonb`.
+    fn bind_json_param<'q>(
+        tool_name: &str,
+        query: sqlx::query::Query<'q, Postgres, PgArguments>,
+        param: Value,
+    ) -> Result<sqlx::query::Query<'q, Postgres, PgArguments>> {
+        match param {
+            Value::String(s) => Ok(query.bind(s)),
+            Value::Number(n) => {
+                if let Some(i) = n.as_i64() {
+                    Ok(query.bind(i))
+                } else if let Some(f) = n.as_f64() {
+                    Ok(query.bind(f))
+                } else {
+                    Err(AdapterError::InvalidParams {
+                        tool_name: tool_name.into(),
+                        reason: format!("unsupported number: {n}"),
+                    })
+                }
+            }
+            Value::Bool(b) => Ok(query.bind(b)),
+            Value::Null => Ok(query.bind(Option::<String>::None)),
+            json @ (Value::Array(_) | Value::Object(_)) => Ok(query.bind(json)),
+        }
+    }
+}
+
+/// Convert a result row into a JSON object keyed by column name.
+fn row_to_json(row: &PgRow) -> Value {
+    let mut object = Map::new();
+    for (i, column) in row.columns().iter().enumerate() {
+        object.insert(
+            column.name().to_string(),
+            column_to_json(row, i, column.type_info().name()),
+        );
+    }
+    Value::Object(object)
+}
+
+/// Decode one column by its PostgreSQL type.  Types without a JSON mapping
+/// (e.g. `numeric`) decode as text where sqlx allows it and as `null`
+/// otherwise; cast them to `text` in the query to read them.
+fn column_to_json(row: &PgRow, i: usize, type_name: &str) -> Value {
+    fn get<'r, T>(row: &'r PgRow, i: usize, to_json: impl FnOnce(T) -> Value) -> Value
+    where
+        T: sqlx::Decode<'r, Postgres> + sqlx::Type<Postgres>,
+    {
+        match row.try_get::<Option<T>, _>(i) {
+            Ok(Some(value)) => to_json(value),
+            _ => Value::Null,
+        }
+    }
+
+    match type_name {
+        "BOOL" => get(row, i, Value::Bool),
+        "INT2" => get(row, i, |v: i16| Value::from(v)),
+        "INT4" => get(row, i, |v: i32| Value::from(v)),
+        "INT8" => get(row, i, |v: i64| Value::from(v)),
+        "FLOAT4" => get(row, i, |v: f32| Value::from(v)),
+        "FLOAT8" => get(row, i, |v: f64| Value::from(v)),
+        "JSON" | "JSONB" => get(row, i, |v: Value| v),
+        "UUID" => get(row, i, |v: uuid::Uuid| Value::String(v.to_string())),
+        "TIMESTAMPTZ" => get(row, i, |v: chrono::DateTime<chrono::Utc>| {
+            Value::String(v.to_rfc3339())
+        }),
+        "TIMESTAMP" => get(row, i, |v: chrono::NaiveDateTime| {
+            Value::String(v.format("%Y-%m-%dT%H:%M:%S%.f").to_string())
+        }),
+        "DATE" => get(row, i, |v: chrono::NaiveDate| Value::String(v.to_string())),
+        "TIME" => get(row, i, |v: chrono::NaiveTime| Value::String(v.to_string())),
+        _ => get(row, i, Value::String),
+    }
+}
+
+#[async_trait]
+impl Adapter for PostgresAdapter {
+    fn id(&self) -> &str {
+        "postgres"
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        AdapterType::System
+    }
+
+    async fn connect(&mut self) -> Result<()> {
+        let pool = connect_pool(&self.url, &self.config)
+            .await
+            .map_err(|e| match e {
+                PostgresError::Configuration(_) => AdapterError::ConfigError(e.to_string()),
+                other => AdapterError::NotConnected {
+                    adapter_id: "postgres".into(),
+                    reason: other.to_string(),
+                },
+            })?;
+        self.pool = Some(pool);
+        tracing::info!("postgres adapter connected");
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        if let Some(pool) = self.pool.take() {
+            pool.close().await;
+        }
+        tracing::info!("postgres adapter disconnected");
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        let Some(pool) = &self.pool else {
+            return Ok(HealthStatus::Unhealthy);
+        };
+        match sqlx::query("SELECT 1").execute(pool).await {
+            Ok(_) => Ok(HealthStatus::Healthy),
+            Err(_) => Ok(HealthStatus::Degraded),
+        }
+    }
+
+    fn tools(&self) -> Vec<ToolDefinition> {
+        let statement_properties = serde_json::json!({
+            "sql": {
+                "type": "string",
+                "description": "A single SQL statement; use `$1`, `$2`, ... placeholders for values"
+            },
+            "params": {
+                "type": "array",
+                "description": "Values bound to the placeholders, in order. Strings bind as \
+                                text, so cast where another type is needed (e.g. `$1::date`); \
+                                objects and arrays bind as jsonb",
+                "items": {},
+                "default": []
+            }
+        });
+        let schema_property = serde_json::json!({
+            "type": "string",
+            "description": "Schema name (default: 'public')",
+            "default": DEFAULT_SCHEMA
+        });
+
+        vec![
+            ToolDefinition {
+                name: "postgres_query".into(),
+                description: "Run a read-only query on the PostgreSQL database (writes are \
+                              rejected). Cast numeric and other uncommon column types to text"
+                    .into(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": statement_properties,
+                    "required": ["sql"]
+                }),
+                destructive: false,
+            },
+            ToolDefinition {
+                name: "postgres_execute".into(),
+                description:
+                    "Execute a write statement (INSERT, UPDATE, DELETE, DDL) on the PostgreSQL \
+                     database"
+                        .into(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": statement_properties,
+                    "required": ["sql"]
+                }),
+                destructive: true,
+            },
+            ToolDefinition {
+                name: "postgres_list_tables".into(),
+                description: "List the tables and views in the PostgreSQL database".into(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "schema": {
+                            "type": "string",
+                            "description": "Only list tables in this schema (default: all user schemas)"
+                        }
+                    }
+                }),
+                destructive: false,
+            },
+            ToolDefinition {
+                name: "postgres_describe_table".into(),
+                description: "Describe a table's columns, types, primary key, and foreign keys"
+                    .into(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "schema": schema_property,
+                        "table": {
+                            "type": "string",
+                            "description": "Table name"
+                        }
+                    },
+                    "required": ["table"]
+                }),
+                destructive: false,
+            },
+        ]
+    }
+
+    async fn execute_tool(&self, tool_name: &str, params: Value) -> Result<Value> {
+        if self.pool.is_none() {
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: tool_name.into(),
+                reason: "postgres adapter is not connected".into(),
+            });
+        }
+
+        let schema_name = params.get("schema").and_then(|v| v.as_str());
+
+        match tool_name {
+            "postgres_query" => {
+                let (sql, sql_params) = Self::statement_args(tool_name, &params)?;
+                self.execute_query(sql, sql_params).await
+            }
+            "postgres_execute" => {
+                let (sql, sql_params) = Self::statement_args(tool_name, &params)?;
+                self.execute_command(sql, sql_params).await
+            }
+            "postgres_list_tables" => {
+                let pool = self.resolve_pool(tool_name)?;
+                schema::list_tables(pool, schema_name)
+                    .await
+                    .map_err(|e| Self::tool_error(tool_name, e))
+            }
+            "postgres_describe_table" => {
+                let table = params
+                    .get("table")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| AdapterError::InvalidParams {
+                        tool_name: tool_name.into(),
+                        reason: "missing required field `table`".into(),
+                    })?;
+                let pool = self.resolve_pool(tool_name)?;
+                schema::describe_table(pool, schema_name.unwrap_or(DEFAULT_SCHEMA), table)
+                    .await
+                    .map_err(|e| Self::tool_error(tool_name, e))
+            }
+            _ => Err(AdapterError::ToolNotFound {
+                adapter_id: "postgres".into(),
+                tool_name: tool_name.into(),
+            }),
+        }
+    }
+
+    fn required_auth(&self) -> Option<AuthRequirement> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::postgres::mock::{self, BOOL, INT8, MockPostgres, Reply, TEXT};
+
+    async fn connected_adapter(server: &MockPostgres) -> PostgresAdapter {
+        let mut adapter = PostgresAdapter::new(server.url());
+        adapter.connect().await.unwrap();
+        adapter
+    }
+
+    #[tokio::test]
+    async fn query_binds_params_in_a_read_only_transaction() {
+        let server = MockPostgres::start(|sql| {
+            if sql.starts_with("SELECT id, name, active FROM users") {
+                Reply::rows(
+                    vec![("id", INT8), ("name", TEXT), ("active", BOOL)],
+                    vec![vec![mock::int8(2), mock::text("ada"), mock::boolean(true)]],
+                )
+            } else {
+                Reply::command("SELECT 1")
+            }
+        })
+        .await;
+        let adapter = connected_adapter(&server).await;
+
+        let sql = "SELECT id, name, active FROM users WHERE id > $1 AND name <> $2";
+        let rows = adapter
+            .execute_tool(
+                "postgres_query",
+                json!({"sql": sql, "params": [1, "x'; DROP TABLE users; --"]}),
+            )
+            .await
+            .unwrap();
+        assert_eq!(rows, json!([{"id": 2, "name": "ada", "active": true}]));
+
+        let executed = server.executed();
+        let statements: Vec<&str> = executed.iter().map(|e| e.sql.as_str()).collect();
+        assert_eq!(
+            statements,
+            ["BEGIN", "SET TRANSACTION READ ONLY", sql, "ROLLBACK"]
+        );
+        assert_eq!(
+            executed[2].params,
+            vec![mock::int8(1), mock::text("x'; DROP TABLE users; --")]
+        );
+    }
+
+    #[tokio::test]
+    async fn execute_reports_rows_affected() {
+        let server = MockPostgres::start(|_| Reply::command("INSERT 0 1")).await;
+        let adapter = connected_adapter(&server).await;
+
+        let result = adapter
+            .execute_tool(
+                "postgres_execute",
+                json!({
+                    "sql": "INSERT INTO notes (body, meta) VALUES ($1, $2)",
+                    "params": ["hello", null]
+                }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result, json!({"rows_affected": 1}));
+
+        let executed = server.executed();
+        assert_eq!(executed.len(), 1);
+        assert_eq!(executed[0].params, vec![mock::text("hello"), None]);
+    }
+
+    #[tokio::test]
+    async fn schema_introspection_reads_catalogs() {
+        let server = MockPostgres::start(|sql| {
+            if sql.contains("FROM information_schema.tables") {
+                Reply::rows(
+                    vec![("schema", TEXT), ("name", TEXT), ("type", TEXT)],
+                    vec![vec![
+                        mock::text("public"),
+                        mock::text("orders"),
+                        mock::text("table"),
+                    ]],
+                )
+            } else if sql.contains("FROM information_schema.columns") {
+                Reply::rows(
+                    vec![
+                        ("name", TEXT),
+                        ("type", TEXT),
+                        ("not_null", BOOL),
+                        ("default", TEXT),
+                        ("primary_key", BOOL),
+                    ],
+                    vec![
+                        vec![
+                            mock::text("id"),
+                            mock::text("bigint"),
+                            mock::boolean(true),
+                            mock::text("nextval('orders_id_seq'::regclass)"),
+                            mock::boolean(true),
+                        ],
+                        vec![
+                            mock::text("user_id"),
+                            mock::text("bigint"),
+                            mock::boolean(false),
+                            None,
+                            mock::boolean(false),
+                        ],
+                    ],
+                )
+            } else if sql.contains("FROM pg_constraint") {
+                Reply::rows(
+                    vec![
+                        ("name", TEXT),
+                        ("column", TEXT),
+                        ("references_schema", TEXT),
+                        ("references_table", TEXT),
+                        ("references_column", TEXT),
+                    ],
+                    vec![vec![
+                        mock::text("orders_user_id_fkey"),
+                        mock::text("user_id"),
+                        mock::text("public"),
+                        mock::text("users"),
+                        mock::text("id"),
+                    ]],
+                )
+            } else {
+                Reply::command("SELECT 1")
+            }
+        })
+        .await;
+        let adapter = connected_adapter(&server).await;
+
+        let tables = adapter
+            .execute_tool("postgres_list_tables", json!({}))
+            .await
+            .unwrap();
+        assert_eq!(
+            tables,
+            json!({"tables": [{"schema": "public", "name": "orders", "type": "table"}]})
+        );
+
+        let description = adapter
+            .execute_tool("postgres_describe_table", json!({"table": "orders"}))
+            .await
+            .unwrap();
+        assert_eq!(description["primary_key"], json!(["id"]));
+        assert_eq!(description["columns"][1]["default"], Value::Null);
+        assert_eq!(description["columns"][1]["not_null"], false);
+        assert_eq!(
+            description["foreign_keys"],
+            json!([{
+                "name": "orders_user_id_fkey",
+                "column": "user_id",
+                "references_schema": "public",
+                "references_table": "users",
+                "references_column": "id"
+            }])
+        );
+
+        // Names are bound, never spliced into the catalog queries.
+        let executed = server.executed();
+        assert_eq!(executed[0].params, vec![None]);
+        for statement in &executed[1..] {
+            assert!(!statement.sql.contains("orders"));
+            assert_eq!(
+                statement.params,
+                vec![mock::text("public"), mock::text("orders")]
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn cancelled_statements_surface_as_timeouts() {
+        let server = MockPostgres::start(|_| Reply::Error {
+            code: "57014",
+            message: "canceling statement due to statement timeout".into(),
+        })
+        .await;
+        let adapter = connected_adapter(&server).await;
+
+        let err = adapter
+            .execute_tool(
+                "postgres_execute",
+                json!({"sql": "UPDATE jobs SET done = true WHERE id = $1", "params": [7]}),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AdapterError::Timeout { seconds: 30, .. }));
+    }
+}
@@ -0,0 +1,72 @@
+//! Error types for PostgreSQL adapter.
+
+use std::time::Duration;
+
+use thiserror::Error;
+
+/// SQLSTATE reported when a statement is cancelled by `statement_timeout`.
+const QUERY_CANCELED: &str = "57014";
+
+/// Errors that can occur when using the PostgreSQL adapter.
+#[derive(Error, Debug)]
+pub enum PostgresError {
+    /// Connection pool error.
+    #[error("connection pool error: {0}")]
+    ConnectionPool(String),
+
+    /// Database connection error.
+    #[error("database connection failed: {0}")]
+    Connection(String),
+
+    /// Query execution error.
+    #[error("query execution failed: {0}")]
+    QueryExecution(String),
+
+    /// Configuration error.
+    #[error("configuration error: {0}")]
+    Configuration(String),
+
+    /// Invalid parameter error.
+    #[error("invalid parameter: {0}")]
+    InvalidParameter(String),
+
+    /// Schema error.
+    #[error("schema error: {0}")]
+    Schema(String),
+
+    /// A statement exceeded the configured statement timeout and was
+    /// cancelled.
+    #[error("statement timed out after {}ms", .0.as_millis())]
+    Timeout(Duration),
+}
+
+impl PostgresError {
+    /// Whether `err` is the server cancelling a statement that ran past
+    /// `statement_timeout`.
+    pub fn is_statement_timeout(err: &sqlx::Error) -> bool {
+        matches!(err, sqlx::Error::Database(db_err) if db_err.code().as_deref() == Some(QUERY_CANCELED))
+    }
+}
+
+impl From<sqlx::Error> for PostgresError {
+    fn from(err: sqlx::Error) -> Self {
+        match err {
+            sqlx::Error::Database(db_err) => PostgresError::QueryExecution(db_err.to_string()),
+            sqlx::Error::Io(io_err) => PostgresError::Connection(io_err.to_string()),
+            sqlx::Error::Tls(tls_err) => PostgresError::Connection(tls_err.to_string()),
+            sqlx::Error::Configuration(cfg_err) => {
+                PostgresError::Configuration(cfg_err.to_string())
+            }
+            sqlx::Error::PoolClosed => {
+                PostgresError::ConnectionPool("connection pool is closed".to_string())
+            }
+            sqlx::Error::PoolTimedOut => {
+                PostgresError::ConnectionPool("connection pool timed out".to_string())
+            }
+            _ => PostgresError::QueryExecution(err.to_string()),
+        }
+    }
+}
+
+/// Result type for PostgreSQL operations.
+pub type PostgresResult<T> = Result<T, PostgresError>;
@@ -0,0 +1,399 @@
+//! A minimal PostgreSQL server for adapter tests.
+//!
+//! Speaks just enough of the v3 wire protocol for sqlx: trust
+//! authentication, simple queries, and the extended query protocol with
+//! binary results.  Replies come from a handler keyed on the SQL text, and
+//! every statement the client runs is recorded together with its bound
+//! parameters so tests can check that values never reach the SQL text.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Type OIDs used by the tests.
+pub const BOOL: u32 = 16;
+pub const INT8: u32 = 20;
+pub const TEXT: u32 = 25;
+
+/// Protocol version 3.0 startup code.
+const PROTOCOL_V3: i32 = 196_608;
+/// `SSLRequest` code.
+const SSL_REQUEST: i32 = 80_877_103;
+
+/// How the mock answers one statement.
+pub enum Reply {
+    /// Return `rows` described by `columns`, then complete with `tag`.
+    Rows {
+        columns: Vec<(&'static str, u32)>,
+        rows: Vec<Vec<Option<Vec<u8>>>>,
+        tag: String,
+    },
+    /// Fail with the given SQLSTATE and message.
+    Error { code: &'static str, message: String },
+}
+
+impl Reply {
+    /// A command that returns no rows.
+    pub fn command(tag: &str) -> Self {
+        Self::Rows {
+            columns: Vec::new(),
+            rows: Vec::new(),
+            tag: tag.to_string(),
+        }
+    }
+
+    /// A `SELECT` returning `rows`.
+    pub fn rows(columns: Vec<(&'static str, u32)>, rows: Vec<Vec<Option<Vec<u8>>>>) -> Self {
+        let tag = format!("SELECT {}", rows.len());
+        Self::Rows { columns, rows, tag }
+    }
+
+    fn columns(&self) -> &[(&'static str, u32)] {
+        match self {
+            Self::Rows { columns, .. } => columns,
+            Self::Error { .. } => &[],
+        }
+    }
+}
+
+/// Binary encoding of a `text` value.
+pub fn text(value: &str) -> Option<Vec<u8>> {
+    Some(value.as_bytes().to_vec())
+}
+
+/// Binary encoding of an `int8` value.
+pub fn int8(value: i64) -> Option<Vec<u8>> {
+    Some(value.to_be_bytes().to_vec())
+}
+
+/// Binary encoding of a `bool` value.
+pub fn boolean(value: bool) -> Option<Vec<u8>> {
+    Some(vec![u8::from(value)])
+}
+
+/// A statement the client ran.
+#[derive(Debug, Clone)]
+pub struct Executed {
+    /// SQL text exactly as sent.
+    pub sql: String,
+    /// Bound parameter values; `None` is SQL `NULL`.
+    pub params: Vec<Option<Vec<u8>>>,
+}
+
+type Handler = Arc<dyn Fn(&str) -> Reply + Send + Sync>;
+
+/// A running mock server.
+pub struct MockPostgres {
+    port: u16,
+    executed: Arc<Mutex<Vec<Executed>>>,
+}
+
+impl MockPostgres {
+    /// Start a server answering statements with `handler`.
+    pub async fn start(handler: impl Fn(&str) -> Reply + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let executed = Arc::new(Mutex::new(Vec::new()));
+        let handler: Handler = Arc::new(handler);
+
+        let log = Arc::clone(&executed);
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let handler = Arc::clone(&handler);
+                let log = Arc::clone(&log);
+                tokio::spawn(async move {
+                    let _ = Session::new(stream, handler, log).run().await;
+                });
+            }
+        });
+
+        Self { port, executed }
+    }
+
+    /// Connection string for the server.
+    pub fn url(&self) -> String {
+        format!(
+            "postgres://mock@127.0.0.1:{}/mock?sslmode=disable",
+            self.port
+        )
+    }
+
+    /// Every statement run so far, in order.
+    pub fn executed(&self) -> Vec<Executed> {
+        self.executed.lock().unwrap().clone()
+    }
+}
+
+/// One client connection.
+struct Session {
+    stream: TcpStream,
+    handler: Handler,
+    log: Arc<Mutex<Vec<Executed>>>,
+    /// Prepared statements: name -> (SQL text, declared parameter types).
+    statements: HashMap<String, (String, Vec<u32>)>,
+    /// SQL of the statement bound to the unnamed portal.
+    portal: Option<String>,
+    /// Transaction status reported in `ReadyForQuery`.
+    status: u8,
+    /// After an error, messages are ignored until the next `Sync`.
+    failed: bool,
+    out: Vec<u8>,
+}
+
+impl Session {
+    fn new(stream: TcpStream, handler: Handler, log: Arc<Mutex<Vec<Executed>>>) -> Self {
+        Self {
+            stream,
+            handler,
+            log,
+            statements: HashMap::new(),
+            portal: None,
+            status: b'I',
+            failed: false,
+            out: Vec::new(),
+        }
+    }
+
+    async fn run(mut self) -> std::io::Result<()> {
+        loop {
+            let len = self.stream.read_i32().await?;
+            let mut body = vec![0u8; len as usize - 4];
+            self.stream.read_exact(&mut body).await?;
+            match i32::from_be_bytes(body[..4].try_into().unwrap()) {
+                SSL_REQUEST => self.stream.write_all(b"N").await?,
+                PROTOCOL_V3 => break,
+                other => panic!("unexpected startup code {other}"),
+            }
+        }
+
+        self.message(b'R', &0i32.to_be_bytes());
+        for (name, value) in [("server_version", "15.0"), ("integer_datetimes", "on")] {
+            let mut body = cstring(name);
+            body.extend(cstring(value));
+            self.message(b'S', &body);
+        }
+        let mut key = 1i32.to_be_bytes().to_vec();
+        key.extend(2i32.to_be_bytes());
+        self.message(b'K', &key);
+        self.ready_for_query();
+        self.flush().await?;
+
+        loop {
+            let tag = self.stream.read_u8().await?;
+            let len = self.stream.read_i32().await?;
+            let mut body = vec![0u8; len as usize - 4];
+            self.stream.read_exact(&mut body).await?;
+            let mut reader = Reader(&body);
+
+            if tag == b'S' {
+                self.failed = false;
+                self.ready_for_query();
+                self.flush().await?;
+                continue;
+            }
+            if self.failed {
+                continue;
+            }
+            match tag {
+                b'Q' => {
+                    let sql = reader.cstring();
+                    self.record(&sql, Vec::new());
+                    self.simple_query(&sql);
+                    self.flush().await?;
+                }
+                b'P' => {
+                    let name = reader.cstring();
+                    let sql = reader.cstring();
+                    let types = (0..reader.i16()).map(|_| reader.i32() as u32).collect();
+                    self.statements.insert(name, (sql, types));
+                    self.message(b'1', &[]);
+                }
+                b'D' => {
+                    let kind = reader.u8();
+                    let name = reader.cstring();
+                    let sql = if kind == b'S' {
+                        // Parameter types are whatever the client declared.
+                        let (sql, types) = self.statements.get(&name).cloned().unwrap_or_default();
+                        let mut body = (types.len() as i16).to_be_bytes().to_vec();
+                        for oid in types {
+                            body.extend(oid.to_be_bytes());
+                        }
+                        self.message(b't', &body);
+                        sql
+                    } else {
+                        self.portal.clone().unwrap_or_default()
+                    };
+                    self.describe_rows(&sql);
+                }
+                b'B' => {
+                    let _portal = reader.cstring();
+                    let statement = reader.cstring();
+                    let formats = reader.i16();
+                    for _ in 0..formats {
+                        reader.i16();
+                    }
+                    let count = reader.i16();
+                    let params = (0..count)
+                        .map(|_| {
+                            let len = reader.i32();
+                            (len >= 0).then(|| reader.bytes(len as usize).to_vec())
+                        })
+                        .collect();
+                    let sql = self
+                        .statements
+                        .get(&statement)
+                        .map(|(sql, _)| sql.clone())
+                        .unwrap_or_default();
+                    self.record(&sql, params);
+                    self.portal = Some(sql);
+                    self.message(b'2', &[]);
+                }
+                b'E' => {
+                    let sql = self.portal.clone().unwrap_or_default();
+                    self.execute(&sql);
+                }
+                b'C' => self.message(b'3', &[]),
+                b'H' => self.flush().await?,
+                b'X' => return Ok(()),
+                other => panic!("unexpected message {:?}", other as char),
+            }
+        }
+    }
+
+    fn record(&self, sql: &str, params: Vec<Option<Vec<u8>>>) {
+        self.log.lock().unwrap().push(Executed {
+            sql: sql.to_string(),
+            params,
+        });
+    }
+
+    /// Answer a simple query.  Only transaction control and `SET` are
+    /// expected here, so no rows are returned.
+    fn simple_query(&mut self, sql: &str) {
+        let upper = sql.trim().to_ascii_uppercase();
+        let verb = upper.split_whitespace().next().unwrap_or("").to_string();
+        match verb.as_str() {
+            "BEGIN" | "START" => self.status = b'T',
+            "COMMIT" | "ROLLBACK" => self.status = b'I',
+            _ => {}
+        }
+        let tag = if verb == "START" {
+            "START TRANSACTION"
+        } else {
+            verb.as_str()
+        };
+        self.message(b'C', &cstring(tag));
+        self.ready_for_query();
+    }
+
+    fn describe_rows(&mut self, sql: &str) {
+        let reply = (self.handler)(sql);
+        let columns = reply.columns();
+        if columns.is_empty() {
+            self.message(b'n', &[]);
+            return;
+        }
+        let mut body = (columns.len() as i16).to_be_bytes().to_vec();
+        for (name, oid) in columns {
+            body.extend(cstring(name));
+            body.extend(0i32.to_be_bytes());
+            body.extend(0i16.to_be_bytes());
+            body.extend(oid.to_be_bytes());
+            body.extend((-1i16).to_be_bytes());
+            body.extend((-1i32).to_be_bytes());
+            body.extend(0i16.to_be_bytes());
+        }
+        self.message(b'T', &body);
+    }
+
+    fn execute(&mut self, sql: &str) {
+        match (self.handler)(sql) {
+            Reply::Rows { rows, tag, .. } => {
+                for row in rows {
+                    let mut body = (row.len() as i16).to_be_bytes().to_vec();
+                    for value in row {
+                        match value {
+                            Some(bytes) => {
+                                body.extend((bytes.len() as i32).to_be_bytes());
+                                body.extend(bytes);
+                            }
+                            None => body.extend((-1i32).to_be_bytes()),
+                        }
+                    }
+                    self.message(b'D', &body);
+                }
+                self.message(b'C', &cstring(&tag));
+            }
+            Reply::Error { code, message } => {
+                let mut body = Vec::new();
+                for (field, value) in [(b'S', "ERROR"), (b'V', "ERROR"), (b'C', code)] {
+                    body.push(field);
+                    body.extend(cstring(value));
+                }
+                body.push(b'M');
+                body.extend(cstring(&message));
+                body.push(0);
+                self.message(b'E', &body);
+                if self.status == b'T' {
+                    self.status = b'E';
+                }
+                self.failed = true;
+            }
+        }
+    }
+
+    fn ready_for_query(&mut self) {
+        let status = self.status;
+        self.message(b'Z', &[status]);
+    }
+
+    fn message(&mut self, tag: u8, body: &[u8]) {
+        self.out.push(tag);
+        self.out.extend((body.len() as i32 + 4).to_be_bytes());
+        self.out.extend(body);
+    }
+
+    async fn flush(&mut self) -> std::io::Result<()> {
+        self.stream.write_all(&self.out).await?;
+        self.out.clear();
+        self.stream.flush().await
+    }
+}
+
+fn cstring(value: &str) -> Vec<u8> {
+    let mut bytes = value.as_bytes().to_vec();
+    bytes.push(0);
+    bytes
+}
+
+/// Cursor over a message body.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, n: usize) -> &'a [u8] {
+        let (head, tail) = self.0.split_at(n);
+        self.0 = tail;
+        head
+    }
+
+    fn u8(&mut self) -> u8 {
+        self.bytes(1)[0]
+    }
+
+    fn i16(&mut self) -> i16 {
+        i16::from_be_bytes(self.bytes(2).try_into().unwrap())
+    }
+
+    fn i32(&mut self) -> i32 {
+        i32::from_be_bytes(self.bytes(4).try_into().unwrap())
+    }
+
+    fn cstring(&mut self) -> String {
+        let end = self.0.iter().position(|&b| b == 0).unwrap_or(self.0.len());
+        let value = String::from_utf8_lossy(&self.0[..end]).into_owned();
+        self.bytes((end + 1).min(self.0.len()));
+        value
+    }
+}
@@ -0,0 +1,17 @@
+//! PostgreSQL adapter for OpenIntentOS
+//!
+//! Provides the same query, execute, and schema tools as the SQLite adapter
+//! against a PostgreSQL server.  The connection string is stored in the vault
+//! (see [`connection_url_from_vault`]).
+
+pub mod adapter;
+pub mod connection;
+pub mod error;
+pub mod schema;
+
+#[cfg(test)]
+mod mock;
+
+pub use adapter::PostgresAdapter;
+pub use connection::{DEFAULT_VAULT_PROVIDER, PostgresPoolConfig, connection_url_from_vault};
+pub use error::{PostgresError, PostgresResult};
@@ -0,0 +1,134 @@
+//! Schema introspection for PostgreSQL databases.
+//!
+//! Reads `information_schema` and `pg_catalog` so the agent can discover
+//! tables, columns, and keys before writing SQL.  Schema and table names are
+//! bound as parameters rather than spliced into the text, and catalog columns
+//! are cast to `text` so they decode as plain strings.
+
+use serde_json::{Value, json};
+use sqlx::{PgPool, Row};
+
+use crate::postgres::{PostgresError, PostgresResult};
+
+/// List user tables and views, excluding the system schemas.
+///
+/// When `schema` is given only that schema is listed.
+pub async fn list_tables(pool: &PgPool, schema: Option<&str>) -> PostgresResult<Value> {
+    let rows = sqlx::query(
+        "SELECT table_schema::text AS schema, table_name::text AS name, \
+                CASE table_type WHEN 'VIEW' THEN 'view' ELSE 'table' END AS type \
+         FROM information_schema.tables \
+         WHERE table_schema NOT IN ('pg_catalog', 'information_schema') \
+           AND table_schema NOT LIKE 'pg_toast%' \
+           AND ($1::text IS NULL OR table_schema = $1) \
+         ORDER BY table_schema, table_name",
+    )
+    .bind(schema)
+    .fetch_all(pool)
+    .await?;
+
+    let tables: Vec<Value> = rows
+        .iter()
+        .map(|row| {
+            json!({
+                "schema": row.get::<String, _>("schema"),
+                "name": row.get::<String, _>("name"),
+                "type": row.get::<String, _>("type"),
+            })
+        })
+        .collect();
+
+    Ok(json!({ "tables": tables }))
+}
+
+/// Describe a table's columns, primary key, and foreign keys.
+pub async fn describe_table(pool: &PgPool, schema: &str, table: &str) -> PostgresResult<Value> {
+    let column_rows = sqlx::query(
+        "SELECT c.column_name::text AS name, c.data_type::text AS type, \
+                c.is_nullable = 'NO' AS not_null, c.column_default::text AS \"default\", \
+                EXISTS ( \
+                    SELECT 1 FROM information_schema.table_constraints tc \
+                    JOIN information_schema.key_column_usage k \
+                      ON k.constraint_schema = tc.constraint_schema \
+                     AND k.constraint_name = tc.constraint_name \
+                    WHERE tc.constraint_type = 'PRIMARY KEY' \
+                      AND tc.table_schema = c.table_schema \
+                      AND tc.table_name = c.table_name \
+                      AND k.column_name = c.column_name \
+                ) AS primary_key \
+         FROM information_schema.columns c \
+         WHERE c.table_schema = $1 AND c.table_name = $2 \
+         ORDER BY c.ordinal_position",
+    )
+    .bind(schema)
+    .bind(table)
+    .fetch_all(pool)
+    .await?;
+
+    if column_rows.is_empty() {
+        return Err(PostgresError::Schema(format!(
+            "table '{schema}.{table}' not found"
+        )));
+    }
+
+    let mut primary_key = Vec::new();
+    let columns: Vec<Value> = column_rows
+        .iter()
+        .map(|row| {
+            let name: String = row.get("name");
+            let pk: bool = row.get("primary_key");
+            if pk {
+                primary_key.push(name.clone());
+            }
+            json!({
+                "name": name,
+                "type": row.get::<String, _>("type"),
+                "not_null": row.get::<bool, _>("not_null"),
+                "default": row.get::<Option<String>, _>("default"),
+                "primary_key": pk,
+            })
+        })
+        .collect();
+
+    // One row per column pair, so composite keys keep their column order.
+    let fk_rows = sqlx::query(
+        "SELECT con.conname::text AS name, a.attname::text AS \"column\", \
+                rn.nspname::text AS references_schema, rt.relname::text AS references_table, \
+                ra.attname::text AS references_column \
+         FROM pg_constraint con \
+         JOIN pg_class t ON t.oid = con.conrelid \
+         JOIN pg_namespace n ON n.oid = t.relnamespace \
+         JOIN pg_class rt ON rt.oid = con.confrelid \
+         JOIN pg_namespace rn ON rn.oid = rt.relnamespace \
+         CROSS JOIN LATERAL unnest(con.conkey, con.confkey) WITH ORDINALITY AS k(attnum, refnum, ord) \
+         JOIN pg_attribute a ON a.attrelid = con.conrelid AND a.attnum = k.attnum \
+         JOIN pg_attribute ra ON ra.attrelid = con.confrelid AND ra.attnum = k.refnum \
+         WHERE con.contype = 'f' AND n.nspname = $1 AND t.relname = $2 \
+         ORDER BY con.conname, k.ord",
+    )
+    .bind(schema)
+    .bind(table)
+    .fetch_all(pool)
+    .await?;
+
+    let foreign_keys: Vec<Value> = fk_rows
+        .iter()
+        .map(|row| {
+            json!({
+                "name": row.get::<String, _>("name"),
+                "column": row.get::<String, _>("column"),
+                "references_schema": row.get::<String, _>("references_schema"),
+                "references_table": row.get::<String, _>("references_table"),
+                "references_column": row.get::<String, _>("references_column"),
+            })
+        })
+        .collect();
+
+    Ok(json!({
+        "schema": schema,
+        "name": table,
+        "columns": columns,
+        "primary_key": primary_key,
+        "foreign_keys": foreign_keys,
+    }))
+}
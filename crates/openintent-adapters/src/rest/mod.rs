@@ -0,0 +1,477 @@
+//! Generic REST adapter generated from an OpenAPI 3 document.
+//!
+//! [`RestAdapter::from_openapi`] reads a spec and exposes one tool per
+//! operation.  Each tool's input schema is built from the operation's path,
+//! query, and header parameters plus a `body` property for JSON request
+//! bodies (see [`openapi`]); executing the tool performs the HTTP call and
+//! returns the status and decoded response body.
+//!
+//! Tool names are the adapter ID followed by the `operationId` in
+//! `snake_case`, e.g. `tasks_api_list_tasks`.
+
+pub mod openapi;
+
+use std::path::Path;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use tracing::{debug, info, warn};
+
+use crate::error::{AdapterError, Result};
+use crate::http_client::{self, HttpClientFactory};
+use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
+
+use self::openapi::{Operation, ParamLocation};
+
+/// Request timeout for generated operations.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Response bodies longer than this are truncated.
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// How requests made by a [`RestAdapter`] authenticate.
+#[derive(Debug, Clone, Default)]
+pub enum RestAuth {
+    /// No authentication.
+    #[default]
+    None,
+    /// `Authorization: Bearer <token>`.
+    Bearer(String),
+    /// HTTP basic authentication.
+    Basic {
+        username: String,
+        password: Option<String>,
+    },
+    /// An API key sent in a request header.
+    Header { name: String, value: String },
+    /// An API key sent as a query parameter.
+    Query { name: String, value: String },
+}
+
+/// Adapter exposing the operations of an OpenAPI-described API as tools.
+pub struct RestAdapter {
+    /// Unique identifier for this adapter instance, also the tool prefix.
+    id: String,
+    /// Whether the adapter has been connected.
+    connected: bool,
+    /// Base URL operations' paths are appended to.
+    base_url: Option<String>,
+    /// Credentials applied to every request.
+    auth: RestAuth,
+    /// Operations from the spec.
+    operations: Vec<Operation>,
+    /// HTTP client for making requests.
+    client: reqwest::Client,
+}
+
+impl RestAdapter {
+    /// Load an OpenAPI 3 document (JSON or YAML) and build an adapter with
+    /// one tool per operation.
+    ///
+    /// The adapter ID is derived from the spec's `info.title` and the base
+    /// URL from its first `servers` entry; both can be overridden.
+    pub fn from_openapi(spec_path: impl AsRef<Path>, auth: RestAuth) -> Result<Self> {
+        let spec_path = spec_path.as_ref();
+        let text = std::fs::read_to_string(spec_path).map_err(|e| {
+            AdapterError::ConfigError(format!(
+                "failed to read OpenAPI spec {}: {e}",
+                spec_path.display()
+            ))
+        })?;
+        let spec = openapi::parse(&text)?;
+
+        let mut id = openapi::tool_name(&spec.title);
+        if id.is_empty() {
+            id = "rest".into();
+        }
+        info!(
+            id = %id,
+            spec = %spec_path.display(),
+            operations = spec.operations.len(),
+            "loaded OpenAPI spec"
+        );
+
+        let client = HttpClientFactory::default()
+            .with_timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+
+        Ok(Self {
+            id,
+            connected: false,
+            base_url: spec.server_url,
+            auth,
+            operations: spec.operations,
+            client,
+        })
+    }
+
+    /// Use `id` as the adapter ID and tool-name prefix.
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    /// Send requests to `base_url` instead of the spec's server.
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = Some(base_url.trim_end_matches('/').to_string());
+        self
+    }
+
+    /// The tool name for `op`.
+    fn tool_name(&self, op: &Operation) -> String {
+        format!("{}_{}", self.id, op.name)
+    }
+
+    /// Perform `op` with the tool input `params`.
+    async fn call(&self, tool_name: &str, op: &Operation, params: &Value) -> Result<Value> {
+        let base_url = self
+            .base_url
+            .as_deref()
+            .filter(|url| url.starts_with("http://") || url.starts_with("https://"))
+            .ok_or_else(|| {
+                AdapterError::ConfigError(format!(
+                    "adapter `{}` has no absolute server URL; set one with `with_base_url`",
+                    self.id
+                ))
+            })?;
+
+        let mut path = op.path.clone();
+        let mut query: Vec<(String, String)> = Vec::new();
+        let mut headers: Vec<(String, String)> = Vec::new();
+        for param in &op.params {
+            let Some(value) = params.get(&param.name).filter(|v| !v.is_null()) else {
+                if param.location == ParamLocation::Path {
+                    return Err(AdapterError::InvalidParams {
+                        tool_name: tool_name.into(),
+                        reason: format!("missing required path parameter: {}", param.name),
+                    });
+                }
+                continue;
+            };
+            match param.location {
+                ParamLocation::Path => {
+                    let encoded = percent_encode(&scalar_string(value));
+                    path = path.replace(&format!("{{{}}}", param.name), &encoded);
+                }
+                ParamLocation::Query => match value {
+                    Value::Array(items) => query.extend(
+                        items
+                            .iter()
+                            .map(|item| (param.name.clone(), scalar_string(item))),
+                    ),
+                    _ => query.push((param.name.clone(), scalar_string(value))),
+                },
+                ParamLocation::Header => headers.push((param.name.clone(), scalar_string(value))),
+            }
+        }
+
+        let method = reqwest::Method::from_bytes(op.method.as_bytes())
+            .map_err(|_| AdapterError::ConfigError(format!("invalid HTTP method {}", op.method)))?;
+        let url = format!("{base_url}{path}");
+        debug!(url = %url, method = %method, tool = tool_name, "REST request");
+
+        let mut request = self.client.request(method, &url);
+        match &self.auth {
+            RestAuth::None => {}
+            RestAuth::Bearer(token) => request = request.bearer_auth(token),
+            RestAuth::Basic { username, password } => {
+                request = request.basic_auth(username, password.as_ref());
+            }
+            RestAuth::Header { name, value } => request = request.header(name, value),
+            RestAuth::Query { name, value } => query.push((name.clone(), value.clone())),
+        }
+        if !query.is_empty() {
+            request = request.query(&query);
+        }
+        for (name, value) in &headers {
+            request = request.header(name, value);
+        }
+        if op.has_body
+            && let Some(body) = params.get("body").filter(|b| !b.is_null())
+        {
+            request = request.json(body);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            if e.is_timeout() {
+                AdapterError::Timeout {
+                    seconds: REQUEST_TIMEOUT.as_secs(),
+                    reason: format!("{} {url} timed out", op.method),
+                }
+            } else {
+                AdapterError::ExecutionFailed {
+                    tool_name: tool_name.into(),
+                    reason: format!("request failed: {e}"),
+                }
+            }
+        })?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let retry_after_secs = response
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u64>().ok());
+            warn!(tool = tool_name, ?retry_after_secs, "REST API rate limited");
+            return Err(AdapterError::RateLimited {
+                adapter_id: self.id.clone(),
+                retry_after_secs,
+            });
+        }
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(AdapterError::AuthRequired {
+                adapter_id: self.id.clone(),
+                provider: self.id.clone(),
+            });
+        }
+
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| AdapterError::ExecutionFailed {
+                tool_name: tool_name.into(),
+                reason: format!("failed to read response body: {e}"),
+            })?;
+        let body = match serde_json::from_slice::<Value>(&bytes) {
+            Ok(json) => json,
+            Err(_) => {
+                let end = bytes.len().min(MAX_BODY_BYTES);
+                Value::String(String::from_utf8_lossy(&bytes[..end]).into_owned())
+            }
+        };
+
+        if !status.is_success() {
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: tool_name.into(),
+                reason: format!("{} {} returned {status}: {body}", op.method, op.path),
+            });
+        }
+        Ok(json!({
+            "status": status.as_u16(),
+            "body": body,
+        }))
+    }
+}
+
+#[async_trait]
+impl Adapter for RestAdapter {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        AdapterType::DevTools
+    }
+
+    async fn connect(&mut self) -> Result<()> {
+        info!(
+            id = %self.id,
+            base_url = ?self.base_url,
+            tools = self.operations.len(),
+            "REST adapter connected"
+        );
+        self.connected = true;
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        info!(id = %self.id, "REST adapter disconnected");
+        self.connected = false;
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        if self.connected {
+            Ok(HealthStatus::Healthy)
+        } else {
+            Ok(HealthStatus::Unhealthy)
+        }
+    }
+
+    fn tools(&self) -> Vec<ToolDefinition> {
+        self.operations
+            .iter()
+            .map(|op| ToolDefinition {
+                name: self.tool_name(op),
+                description: format!("{} ({} {})", op.description, op.method, op.path),
+                parameters: op.input_schema.clone(),
+                destructive: !matches!(op.method.as_str(), "GET" | "HEAD"),
+            })
+            .collect()
+    }
+
+    async fn execute_tool(&self, name: &str, params: Value) -> Result<Value> {
+        if !self.connected {
+            return Err(AdapterError::NotConnected {
+                adapter_id: self.id.clone(),
+                reason: "adapter not connected".into(),
+            });
+        }
+        http_client::ensure_online()?;
+
+        let op = self
+            .operations
+            .iter()
+            .find(|op| self.tool_name(op) == name)
+            .ok_or_else(|| AdapterError::ToolNotFound {
+                adapter_id: self.id.clone(),
+                tool_name: name.to_string(),
+            })?;
+        self.call(name, op, &params).await
+    }
+
+    fn required_auth(&self) -> Option<AuthRequirement> {
+        None
+    }
+}
+
+/// Render a parameter value the way it appears in a URL or header.
+fn scalar_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Percent-encode a path segment.
+fn percent_encode(input: &str) -> String {
+    let mut encoded = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char);
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::webhook::{read_request, write_response};
+
+    fn fixture() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("src/rest/fixtures/tasks.yaml")
+    }
+
+    #[test]
+    fn fixture_spec_yields_one_tool_per_operation() {
+        let adapter = RestAdapter::from_openapi(fixture(), RestAuth::None).unwrap();
+        assert_eq!(adapter.id(), "tasks_api");
+
+        let tools = adapter.tools();
+        assert_eq!(tools.len(), 2);
+
+        let list = tools
+            .iter()
+            .find(|t| t.name == "tasks_api_list_tasks")
+            .unwrap();
+        assert!(list.description.starts_with("List tasks in a project"));
+        assert_eq!(
+            list.parameters,
+            json!({
+                "type": "object",
+                "properties": {
+                    "projectId": { "type": "string", "description": "Project ID" },
+                    "status": { "type": "string", "enum": ["open", "done"] },
+                    "limit": { "type": "integer", "minimum": 1, "maximum": 100 }
+                },
+                "required": ["projectId"]
+            })
+        );
+
+        let create = tools
+            .iter()
+            .find(|t| t.name == "tasks_api_create_task")
+            .unwrap();
+        assert_eq!(
+            create.parameters,
+            json!({
+                "type": "object",
+                "properties": {
+                    "projectId": { "type": "string", "description": "Project ID" },
+                    "body": {
+                        "type": "object",
+                        "properties": {
+                            "title": { "type": "string" },
+                            "due": { "type": "string", "format": "date" }
+                        },
+                        "required": ["title"]
+                    }
+                },
+                "required": ["projectId", "body"]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn execute_tool_performs_the_operation() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let seen = Arc::new(Mutex::new(None));
+        let log = Arc::clone(&seen);
+        tokio::spawn(async move {
+            if let Ok((mut stream, _)) = listener.accept().await {
+                let request = read_request(&mut stream).await.unwrap();
+                *log.lock().unwrap() = Some((
+                    request.method.clone(),
+                    request.path.clone(),
+                    request.header("authorization").map(str::to_string),
+                    String::from_utf8_lossy(&request.body).into_owned(),
+                ));
+                let _ = write_response(
+                    &mut stream,
+                    "201 Created",
+                    "application/json",
+                    r#"{"id":"t-9","title":"Ship it"}"#,
+                )
+                .await;
+            }
+        });
+
+        let mut adapter = RestAdapter::from_openapi(fixture(), RestAuth::Bearer("tok".into()))
+            .unwrap()
+            .with_base_url(&base);
+        adapter.connect().await.unwrap();
+
+        let result = adapter
+            .execute_tool(
+                "tasks_api_create_task",
+                json!({ "projectId": "p 1", "body": { "title": "Ship it" } }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["status"], 201);
+        assert_eq!(result["body"]["id"], "t-9");
+
+        let (method, path, authorization, body) = seen.lock().unwrap().take().unwrap();
+        assert_eq!(method, "POST");
+        assert_eq!(path, "/projects/p%201/tasks");
+        assert_eq!(authorization.as_deref(), Some("Bearer tok"));
+        assert_eq!(body, r#"{"title":"Ship it"}"#);
+    }
+
+    #[test]
+    fn tool_names_are_snake_case() {
+        assert_eq!(openapi::tool_name("listTasks"), "list_tasks");
+        assert_eq!(openapi::tool_name("Tasks API"), "tasks_api");
+        assert_eq!(
+            openapi::tool_name("get_/projects/{projectId}"),
+            "get_projects_project_id"
+        );
+    }
+}
@@ -0,0 +1,307 @@
+//! OpenAPI 3 document parsing.
+//!
+//! Turns the `paths` of an OpenAPI 3.x document into a flat list of
+//! [`Operation`]s, each carrying a JSON Schema for its inputs.  Path, query,
+//! and header parameters become top-level properties of that schema; a JSON
+//! request body becomes a `body` property.  Local `$ref`s
+//! (`#/components/...`) are inlined so the schema is self-contained.
+
+use serde_json::{Map, Value, json};
+
+use crate::error::{AdapterError, Result};
+
+/// HTTP methods an OpenAPI path item may define operations for.
+const METHODS: &[&str] = &["get", "put", "post", "delete", "options", "head", "patch"];
+
+/// Depth at which nested `$ref`s stop being inlined, guarding against
+/// recursive schemas.
+const MAX_REF_DEPTH: usize = 8;
+
+/// Where an operation parameter is sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamLocation {
+    Path,
+    Query,
+    Header,
+}
+
+/// A parameter of an operation.
+#[derive(Debug, Clone)]
+pub struct Param {
+    /// Name as sent on the wire, and as the tool input property.
+    pub name: String,
+    /// Where the parameter goes in the request.
+    pub location: ParamLocation,
+}
+
+/// One HTTP operation from the spec.
+#[derive(Debug, Clone)]
+pub struct Operation {
+    /// Tool-safe name derived from `operationId` (or method and path).
+    pub name: String,
+    /// Upper-case HTTP method.
+    pub method: String,
+    /// Path template, e.g. `/tasks/{taskId}`.
+    pub path: String,
+    /// Summary or description shown to the model.
+    pub description: String,
+    /// Parameters, in declaration order.
+    pub params: Vec<Param>,
+    /// Whether the operation takes a JSON request body.
+    pub has_body: bool,
+    /// JSON Schema for the tool input.
+    pub input_schema: Value,
+}
+
+/// A parsed OpenAPI document.
+#[derive(Debug, Clone)]
+pub struct Spec {
+    /// `info.title`.
+    pub title: String,
+    /// First server URL with variables substituted, if any.
+    pub server_url: Option<String>,
+    /// Every operation in the document.
+    pub operations: Vec<Operation>,
+}
+
+/// Parse an OpenAPI 3 document from JSON or YAML text.
+pub fn parse(text: &str) -> Result<Spec> {
+    let doc: Value = match serde_json::from_str(text) {
+        Ok(doc) => doc,
+        Err(_) => serde_yaml::from_str(text)
+            .map_err(|e| AdapterError::ConfigError(format!("invalid OpenAPI document: {e}")))?,
+    };
+
+    let version = doc.get("openapi").and_then(Value::as_str).unwrap_or("");
+    if !version.starts_with("3.") {
+        return Err(AdapterError::ConfigError(format!(
+            "unsupported OpenAPI version {version:?}; only 3.x documents are supported"
+        )));
+    }
+
+    let title = doc
+        .pointer("/info/title")
+        .and_then(Value::as_str)
+        .unwrap_or("api")
+        .to_string();
+    let server_url = doc.pointer("/servers/0").and_then(server_url);
+
+    let mut operations = Vec::new();
+    if let Some(paths) = doc.get("paths").and_then(Value::as_object) {
+        for (path, item) in paths {
+            let item = resolve(&doc, item);
+            let shared_params = item
+                .get("parameters")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default();
+            for method in METHODS {
+                if let Some(op) = item.get(*method) {
+                    operations.push(operation(&doc, method, path, op, &shared_params)?);
+                }
+            }
+        }
+    }
+
+    Ok(Spec {
+        title,
+        server_url,
+        operations,
+    })
+}
+
+/// Build one [`Operation`] from its spec object.
+fn operation(
+    doc: &Value,
+    method: &str,
+    path: &str,
+    op: &Value,
+    shared_params: &[Value],
+) -> Result<Operation> {
+    let name = match op.get("operationId").and_then(Value::as_str) {
+        Some(id) => tool_name(id),
+        None => tool_name(&format!("{method}_{path}")),
+    };
+    let description = op
+        .get("summary")
+        .or_else(|| op.get("description"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{} {path}", method.to_uppercase()));
+
+    // Operation-level parameters override path-level ones with the same
+    // name and location.
+    let mut declared: Vec<Value> = shared_params.iter().map(|p| resolve(doc, p)).collect();
+    for param in op
+        .get("parameters")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let param = resolve(doc, param);
+        declared.retain(|p| p.get("name") != param.get("name") || p.get("in") != param.get("in"));
+        declared.push(param);
+    }
+
+    let mut params = Vec::new();
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for param in &declared {
+        let Some(name) = param.get("name").and_then(Value::as_str) else {
+            continue;
+        };
+        let location = match param.get("in").and_then(Value::as_str) {
+            Some("path") => ParamLocation::Path,
+            Some("query") => ParamLocation::Query,
+            Some("header") => ParamLocation::Header,
+            // Cookie parameters are not supported.
+            _ => continue,
+        };
+
+        let mut schema = param
+            .get("schema")
+            .map(|s| inline_refs(doc, s, 0))
+            .unwrap_or_else(|| json!({ "type": "string" }));
+        if let Some(description) = param.get("description")
+            && let Some(obj) = schema.as_object_mut()
+        {
+            obj.insert("description".into(), description.clone());
+        }
+        properties.insert(name.to_string(), schema);
+        if location == ParamLocation::Path
+            || param.get("required").and_then(Value::as_bool) == Some(true)
+        {
+            required.push(json!(name));
+        }
+        params.push(Param {
+            name: name.to_string(),
+            location,
+        });
+    }
+
+    let mut has_body = false;
+    if let Some(body) = op.get("requestBody").map(|b| resolve(doc, b)) {
+        let schema = body
+            .get("content")
+            .and_then(Value::as_object)
+            .and_then(|content| {
+                content
+                    .iter()
+                    .find(|(media, _)| media.starts_with("application/json"))
+            })
+            .and_then(|(_, media)| media.get("schema"));
+        if let Some(schema) = schema {
+            if params.iter().any(|p| p.name == "body") {
+                return Err(AdapterError::ConfigError(format!(
+                    "operation `{name}` has both a parameter and a request body named `body`"
+                )));
+            }
+            let mut schema = inline_refs(doc, schema, 0);
+            if let Some(description) = body.get("description")
+                && let Some(obj) = schema.as_object_mut()
+            {
+                obj.entry("description").or_insert(description.clone());
+            }
+            properties.insert("body".into(), schema);
+            if body.get("required").and_then(Value::as_bool) == Some(true) {
+                required.push(json!("body"));
+            }
+            has_body = true;
+        }
+    }
+
+    let mut input_schema = json!({ "type": "object", "properties": properties });
+    if !required.is_empty() {
+        input_schema["required"] = Value::Array(required);
+    }
+
+    Ok(Operation {
+        name,
+        method: method.to_uppercase(),
+        path: path.to_string(),
+        description,
+        params,
+        has_body,
+        input_schema,
+    })
+}
+
+/// Follow `value`'s `$ref` once, if it has one.
+fn resolve(doc: &Value, value: &Value) -> Value {
+    match value.get("$ref").and_then(Value::as_str) {
+        Some(reference) => lookup(doc, reference)
+            .cloned()
+            .unwrap_or_else(|| value.clone()),
+        None => value.clone(),
+    }
+}
+
+/// Replace every local `$ref` in `schema` with the schema it points to.
+fn inline_refs(doc: &Value, schema: &Value, depth: usize) -> Value {
+    match schema {
+        Value::Object(obj) => {
+            if let Some(reference) = obj.get("$ref").and_then(Value::as_str) {
+                return match lookup(doc, reference) {
+                    Some(target) if depth < MAX_REF_DEPTH => inline_refs(doc, target, depth + 1),
+                    // Too deep or unresolvable: leave an open schema.
+                    _ => json!({}),
+                };
+            }
+            Value::Object(
+                obj.iter()
+                    .map(|(k, v)| (k.clone(), inline_refs(doc, v, depth)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => {
+            Value::Array(items.iter().map(|v| inline_refs(doc, v, depth)).collect())
+        }
+        other => other.clone(),
+    }
+}
+
+/// Look up a local reference such as `#/components/schemas/Task`.
+fn lookup<'a>(doc: &'a Value, reference: &str) -> Option<&'a Value> {
+    let pointer = reference.strip_prefix('#')?;
+    // JSON Pointer escapes `~` and `/` inside segments; the pointer lookup
+    // handles them itself.
+    doc.pointer(pointer)
+}
+
+/// The URL of a server object, with variables replaced by their defaults.
+fn server_url(server: &Value) -> Option<String> {
+    let mut url = server.get("url").and_then(Value::as_str)?.to_string();
+    if let Some(vars) = server.get("variables").and_then(Value::as_object) {
+        for (name, var) in vars {
+            if let Some(default) = var.get("default").and_then(Value::as_str) {
+                url = url.replace(&format!("{{{name}}}"), default);
+            }
+        }
+    }
+    Some(url.trim_end_matches('/').to_string())
+}
+
+/// Convert an identifier into a tool name: `snake_case`, limited to
+/// `[a-z0-9_]`.
+pub fn tool_name(raw: &str) -> String {
+    let mut name = String::with_capacity(raw.len());
+    let mut prev_lower = false;
+    for c in raw.chars() {
+        if c.is_ascii_uppercase() {
+            if prev_lower {
+                name.push('_');
+            }
+            name.push(c.to_ascii_lowercase());
+            prev_lower = false;
+        } else if c.is_ascii_alphanumeric() {
+            name.push(c);
+            prev_lower = true;
+        } else {
+            if !name.ends_with('_') {
+                name.push('_');
+            }
+            prev_lower = false;
+        }
+    }
+    name.trim_matches('_').to_string()
+}
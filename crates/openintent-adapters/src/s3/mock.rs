@@ -0,0 +1,123 @@
+//! A minimal S3-compatible server for adapter tests.
+//!
+//! Path-style only.  Supports object put and get, `ListObjectsV2` with a
+//! prefix, and multipart upload, and refuses requests that are not signed
+//! with the test access key.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+
+use crate::webhook::read_request;
+
+/// Objects held by the mock S3 service, keyed by `/bucket/key`.
+pub type Store = Arc<Mutex<BTreeMap<String, Vec<u8>>>>;
+
+/// Serve a path-style mock S3 supporting put, get, list, and multipart
+/// upload.  Requests without a SigV4 `authorization` header are refused.
+pub async fn mock_s3() -> (String, Store) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let store: Store = Arc::default();
+    let objects = Arc::clone(&store);
+    tokio::spawn(async move {
+        let parts: Arc<Mutex<BTreeMap<usize, Vec<u8>>>> = Arc::default();
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let Ok(request) = read_request(&mut stream).await else {
+                continue;
+            };
+            let query: BTreeMap<String, String> =
+                url::form_urlencoded::parse(request.query.as_bytes())
+                    .into_owned()
+                    .collect();
+            let signed = request
+                .header("authorization")
+                .is_some_and(|a| a.starts_with("AWS4-HMAC-SHA256 Credential=minio/"));
+            let path = percent_decode(&request.path);
+
+            let (status, headers, body) = if !signed {
+                ("403 Forbidden", String::new(), error_xml("AccessDenied"))
+            } else if request.method == "PUT" && query.contains_key("partNumber") {
+                let n: usize = query["partNumber"].parse().unwrap();
+                parts.lock().unwrap().insert(n, request.body);
+                ("200 OK", format!("ETag: \"part-{n}\"\r\n"), String::new())
+            } else if request.method == "PUT" {
+                objects.lock().unwrap().insert(path, request.body);
+                ("200 OK", "ETag: \"abc\"\r\n".into(), String::new())
+            } else if request.method == "POST" && query.contains_key("uploads") {
+                parts.lock().unwrap().clear();
+                let xml = "<InitiateMultipartUploadResult><UploadId>up-1</UploadId>\
+                           </InitiateMultipartUploadResult>";
+                ("200 OK", String::new(), xml.to_string())
+            } else if request.method == "POST" && query.contains_key("uploadId") {
+                let joined: Vec<u8> = parts.lock().unwrap().values().flatten().copied().collect();
+                objects.lock().unwrap().insert(path, joined);
+                let xml = "<CompleteMultipartUploadResult><ETag>&quot;mp-2&quot;</ETag>\
+                           </CompleteMultipartUploadResult>";
+                ("200 OK", String::new(), xml.to_string())
+            } else if request.method == "GET" && query.contains_key("list-type") {
+                let bucket = format!("{path}/");
+                let prefix = query.get("prefix").cloned().unwrap_or_default();
+                let contents: String = objects
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .filter_map(|(k, v)| Some((k.strip_prefix(&bucket)?, v)))
+                    .filter(|(k, _)| k.starts_with(&prefix))
+                    .map(|(k, v)| {
+                        format!(
+                            "<Contents><Key>{k}</Key><Size>{}</Size></Contents>",
+                            v.len()
+                        )
+                    })
+                    .collect();
+                let xml = format!(
+                    "<ListBucketResult><IsTruncated>false</IsTruncated>{contents}\
+                     </ListBucketResult>"
+                );
+                ("200 OK", String::new(), xml)
+            } else {
+                match objects.lock().unwrap().get(&path) {
+                    Some(data) => (
+                        "200 OK",
+                        String::new(),
+                        String::from_utf8(data.clone()).unwrap(),
+                    ),
+                    None => ("404 Not Found", String::new(), error_xml("NoSuchKey")),
+                }
+            };
+            let response = format!(
+                "HTTP/1.1 {status}\r\n{headers}Content-Length: {}\r\n\
+                 Connection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        }
+    });
+    (base, store)
+}
+
+fn percent_decode(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match (bytes[i], path.get(i + 1..i + 3)) {
+            (b'%', Some(hex)) if u8::from_str_radix(hex, 16).is_ok() => {
+                decoded.push(u8::from_str_radix(hex, 16).unwrap());
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).unwrap()
+}
+
+fn error_xml(code: &str) -> String {
+    format!("<Error><Code>{code}</Code><Message>mock error</Message></Error>")
+}
@@ -0,0 +1,263 @@
+//! S3-compatible object storage adapter for OpenIntentOS.
+//!
+//! Provides tools for uploading, downloading, and listing objects and for
+//! creating presigned URLs.  Works with AWS S3 and S3-compatible services
+//! such as MinIO: set [`S3Config::endpoint`] and enable
+//! [`S3Config::path_style`] for the latter.
+//!
+//! Requests are signed with AWS Signature Version 4 (see [`sigv4`]).  Access
+//! keys come from [`S3Adapter::with_credentials`] (typically loaded from the
+//! vault with [`S3Credentials::from_vault`]) or the standard
+//! `AWS_ACCESS_KEY_ID` / `AWS_SECRET_ACCESS_KEY` / `AWS_SESSION_TOKEN`
+//! environment variables.
+//!
+//! Uploads larger than [`S3Config::multipart_threshold`] use multipart
+//! upload, reading files one part at a time.  Local files named by the
+//! `path` parameter must lie inside [`S3Config::root_dir`].
+
+mod request;
+pub mod sigv4;
+mod tools;
+
+#[cfg(test)]
+mod mock;
+
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use openintent_vault::store::Vault;
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::error::{AdapterError, Result};
+use crate::http_client::{self, HttpClientFactory};
+use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
+
+pub use sigv4::S3Credentials;
+
+/// Default vault provider name for S3 credentials.
+pub const DEFAULT_VAULT_PROVIDER: &str = "s3";
+
+/// Upper bound on a single object transfer.
+const TRANSFER_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
+/// Connection settings for an S3-compatible service.
+#[derive(Debug, Clone)]
+pub struct S3Config {
+    /// Service endpoint, e.g. `https://s3.eu-west-1.amazonaws.com` or
+    /// `http://localhost:9000`.  Defaults to the AWS endpoint for `region`.
+    pub endpoint: Option<String>,
+    /// Region used for signing.  MinIO accepts any value (`us-east-1`).
+    pub region: String,
+    /// Bucket used when a tool call does not name one.
+    pub bucket: String,
+    /// Address buckets as `endpoint/bucket/key` instead of
+    /// `bucket.endpoint/key`.  Required by most self-hosted services.
+    pub path_style: bool,
+    /// Uploads larger than this many bytes use multipart upload.
+    pub multipart_threshold: usize,
+    /// Size of each multipart part.  S3 requires at least 5 MiB for every
+    /// part but the last.
+    pub part_size: usize,
+    /// Directory that `path` parameters are resolved against.  Uploads and
+    /// downloads of files outside it are rejected.
+    pub root_dir: PathBuf,
+}
+
+impl Default for S3Config {
+    fn default() -> Self {
+        Self {
+            endpoint: None,
+            region: "us-east-1".into(),
+            bucket: String::new(),
+            path_style: false,
+            multipart_threshold: 16 * 1024 * 1024,
+            part_size: 8 * 1024 * 1024,
+            root_dir: PathBuf::from("."),
+        }
+    }
+}
+
+impl S3Credentials {
+    /// Load access keys stored in the vault under `provider`.
+    ///
+    /// The credential data must hold `access_key_id` and
+    /// `secret_access_key`, and may hold `session_token`.
+    pub fn from_vault(vault: &Vault, provider: &str) -> Result<Self> {
+        let credential = vault.get_credential(provider).map_err(|e| {
+            AdapterError::ConfigError(format!("failed to load S3 credentials `{provider}`: {e}"))
+        })?;
+        let field = |name: &str| {
+            credential
+                .data
+                .get(name)
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        };
+        match (field("access_key_id"), field("secret_access_key")) {
+            (Some(access_key_id), Some(secret_access_key)) => Ok(Self {
+                access_key_id,
+                secret_access_key,
+                session_token: field("session_token"),
+            }),
+            _ => Err(AdapterError::ConfigError(format!(
+                "vault credential `{provider}` must contain access_key_id and secret_access_key"
+            ))),
+        }
+    }
+
+    /// Read access keys from the standard AWS environment variables.
+    fn from_env() -> Option<Self> {
+        let access_key_id = std::env::var("AWS_ACCESS_KEY_ID").ok()?;
+        let secret_access_key = std::env::var("AWS_SECRET_ACCESS_KEY").ok()?;
+        if access_key_id.is_empty() || secret_access_key.is_empty() {
+            return None;
+        }
+        Some(Self {
+            access_key_id,
+            secret_access_key,
+            session_token: std::env::var("AWS_SESSION_TOKEN")
+                .ok()
+                .filter(|t| !t.is_empty()),
+        })
+    }
+}
+
+/// S3-compatible object storage adapter.
+pub struct S3Adapter {
+    /// Unique identifier for this adapter instance.
+    id: String,
+    /// Whether the adapter has been connected.
+    connected: bool,
+    /// Endpoint, region, and bucket settings.
+    config: S3Config,
+    /// Access keys used to sign requests.
+    credentials: Option<S3Credentials>,
+    /// HTTP client for making requests.
+    client: reqwest::Client,
+}
+
+impl S3Adapter {
+    /// Create a new S3 adapter with no credentials configured.
+    pub fn new(id: impl Into<String>, config: S3Config) -> Self {
+        let client = HttpClientFactory::default()
+            .with_timeout(TRANSFER_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            id: id.into(),
+            connected: false,
+            config,
+            credentials: None,
+            client,
+        }
+    }
+
+    /// Sign requests with `credentials`.
+    pub fn with_credentials(mut self, credentials: S3Credentials) -> Self {
+        self.credentials = Some(credentials);
+        self
+    }
+}
+
+#[async_trait]
+impl Adapter for S3Adapter {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        AdapterType::Productivity
+    }
+
+    async fn connect(&mut self) -> Result<()> {
+        self.endpoint()?;
+        if self.credentials.is_none() {
+            self.credentials = S3Credentials::from_env();
+            if self.credentials.is_some() {
+                info!(id = %self.id, "S3 adapter loaded credentials from environment");
+            }
+        }
+        if self.credentials.is_none() {
+            warn!(
+                id = %self.id,
+                "no S3 credentials configured; S3 adapter connecting without auth"
+            );
+        }
+        self.connected = true;
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        info!(id = %self.id, "S3 adapter disconnected");
+        self.connected = false;
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        if !self.connected {
+            return Ok(HealthStatus::Unhealthy);
+        }
+        if self.credentials.is_some() {
+            Ok(HealthStatus::Healthy)
+        } else {
+            Ok(HealthStatus::Degraded)
+        }
+    }
+
+    fn tools(&self) -> Vec<ToolDefinition> {
+        tools::definitions()
+    }
+
+    async fn execute_tool(&self, name: &str, params: Value) -> Result<Value> {
+        if !self.connected {
+            return Err(AdapterError::NotConnected {
+                adapter_id: self.id.clone(),
+                reason: "adapter not connected".into(),
+            });
+        }
+        http_client::ensure_online()?;
+
+        match name {
+            "s3_put_object" => self.tool_put_object(params).await,
+            "s3_get_object" => self.tool_get_object(params).await,
+            "s3_list_objects" => self.tool_list_objects(params).await,
+            "s3_presign_url" => self.tool_presign_url(params).await,
+            _ => Err(AdapterError::ToolNotFound {
+                adapter_id: self.id.clone(),
+                tool_name: name.to_string(),
+            }),
+        }
+    }
+
+    fn required_auth(&self) -> Option<AuthRequirement> {
+        Some(AuthRequirement {
+            provider: DEFAULT_VAULT_PROVIDER.into(),
+            scopes: vec![
+                "s3:GetObject".into(),
+                "s3:PutObject".into(),
+                "s3:ListBucket".into(),
+            ],
+        })
+    }
+
+    fn has_credentials(&self) -> bool {
+        self.credentials.is_some()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Extract a required string parameter.
+fn require_str<'a>(tool_name: &str, params: &'a Value, field: &str) -> Result<&'a str> {
+    params
+        .get(field)
+        .and_then(Value::as_str)
+        .ok_or_else(|| AdapterError::InvalidParams {
+            tool_name: tool_name.into(),
+            reason: format!("missing required string field: {field}"),
+        })
+}
@@ -0,0 +1,204 @@
+//! Signed requests to the S3 API.
+//!
+//! Resolves bucket and object URLs for virtual-hosted or path-style
+//! addressing, signs each request with [`sigv4`](super::sigv4), and maps S3
+//! error responses to adapter errors.  S3 answers in XML; the few elements
+//! the tools need are extracted with the string helpers at the end.
+
+use chrono::Utc;
+use reqwest::Method;
+use serde_json::Value;
+use tracing::{debug, warn};
+
+use super::sigv4::{self, SignableRequest};
+use super::{DEFAULT_VAULT_PROVIDER, S3Adapter};
+use crate::error::{AdapterError, Result};
+
+/// Where a request goes and how it is signed.
+pub(super) struct Target {
+    /// Full request URL.
+    pub(super) url: String,
+    /// `Host` header value.
+    pub(super) host: String,
+    /// Encoded path, as signed.
+    pub(super) path: String,
+}
+
+impl S3Adapter {
+    /// The endpoint URL, defaulting to AWS for the configured region.
+    pub(super) fn endpoint(&self) -> Result<url::Url> {
+        let endpoint = match &self.config.endpoint {
+            Some(endpoint) => endpoint.clone(),
+            None => format!("https://s3.{}.amazonaws.com", self.config.region),
+        };
+        url::Url::parse(&endpoint)
+            .map_err(|e| AdapterError::ConfigError(format!("invalid S3 endpoint {endpoint}: {e}")))
+    }
+
+    /// The bucket named in `params`, or the configured default.
+    pub(super) fn bucket<'a>(&'a self, tool_name: &str, params: &'a Value) -> Result<&'a str> {
+        let bucket = params
+            .get("bucket")
+            .and_then(Value::as_str)
+            .unwrap_or(&self.config.bucket);
+        if bucket.is_empty() {
+            return Err(AdapterError::InvalidParams {
+                tool_name: tool_name.into(),
+                reason: "no `bucket` given and no default bucket configured".into(),
+            });
+        }
+        Ok(bucket)
+    }
+
+    /// Resolve the URL, host, and signed path for `key` in `bucket` (or the
+    /// bucket itself when `key` is `None`).
+    pub(super) fn target(
+        &self,
+        bucket: &str,
+        key: Option<&str>,
+        query: &[(String, String)],
+    ) -> Result<Target> {
+        let endpoint = self.endpoint()?;
+        let endpoint_host = endpoint
+            .host_str()
+            .ok_or_else(|| AdapterError::ConfigError("S3 endpoint has no host".into()))?;
+        let port = endpoint.port().map(|p| format!(":{p}")).unwrap_or_default();
+        let key = key.map(|k| sigv4::uri_encode(k, true)).unwrap_or_default();
+
+        let (host, path) = if self.config.path_style {
+            let path = if key.is_empty() {
+                format!("/{bucket}")
+            } else {
+                format!("/{bucket}/{key}")
+            };
+            (format!("{endpoint_host}{port}"), path)
+        } else {
+            (format!("{bucket}.{endpoint_host}{port}"), format!("/{key}"))
+        };
+
+        let mut url = format!("{}://{host}{path}", endpoint.scheme());
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&sigv4::canonical_query(query));
+        }
+        Ok(Target { url, host, path })
+    }
+
+    /// Sign and send a request, mapping S3 errors to adapter errors.
+    pub(super) async fn send(
+        &self,
+        tool_name: &str,
+        method: Method,
+        target: &Target,
+        query: &[(String, String)],
+        body: Vec<u8>,
+        content_type: Option<&str>,
+    ) -> Result<reqwest::Response> {
+        let credentials = self
+            .credentials
+            .as_ref()
+            .ok_or_else(|| AdapterError::AuthRequired {
+                adapter_id: self.id.clone(),
+                provider: DEFAULT_VAULT_PROVIDER.to_string(),
+            })?;
+        let payload_hash = sigv4::sha256_hex(&body);
+        let request = SignableRequest {
+            method: method.as_str(),
+            host: &target.host,
+            path: &target.path,
+            query,
+            payload_hash: &payload_hash,
+        };
+        let headers = sigv4::sign_headers(&request, credentials, &self.config.region, Utc::now());
+        debug!(url = %target.url, method = %method, tool = tool_name, "S3 request");
+
+        let mut builder = self.client.request(method, &target.url).body(body);
+        for (name, value) in headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(content_type) = content_type {
+            builder = builder.header("content-type", content_type);
+        }
+        let response = builder
+            .send()
+            .await
+            .map_err(|e| AdapterError::ExecutionFailed {
+                tool_name: tool_name.to_string(),
+                reason: format!("S3 request failed: {e}"),
+            })?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let text = response.text().await.unwrap_or_default();
+        let code = xml_text(&text, "Code").unwrap_or_else(|| status.to_string());
+        let message = xml_text(&text, "Message").unwrap_or_default();
+        match code.as_str() {
+            "SlowDown" | "ServiceUnavailable" => {
+                warn!(tool = tool_name, "S3 throttled request");
+                Err(AdapterError::RateLimited {
+                    adapter_id: self.id.clone(),
+                    retry_after_secs: None,
+                })
+            }
+            "InvalidAccessKeyId" | "SignatureDoesNotMatch" | "ExpiredToken" => {
+                Err(AdapterError::AuthRequired {
+                    adapter_id: self.id.clone(),
+                    provider: DEFAULT_VAULT_PROVIDER.to_string(),
+                })
+            }
+            _ => Err(AdapterError::ExecutionFailed {
+                tool_name: tool_name.to_string(),
+                reason: format!("S3 returned {code}: {message}"),
+            }),
+        }
+    }
+}
+
+/// The `ETag` header of a response.
+pub(super) fn etag(response: &reqwest::Response) -> Option<String> {
+    response
+        .headers()
+        .get("etag")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
+
+/// The raw inner XML of every `<tag>…</tag>` element in `xml`.
+pub(super) fn xml_elements<'a>(xml: &'a str, tag: &str) -> Vec<&'a str> {
+    let (open, close) = (format!("<{tag}>"), format!("</{tag}>"));
+    let mut elements = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        let after = &rest[start + open.len()..];
+        let Some(end) = after.find(&close) else {
+            break;
+        };
+        elements.push(&after[..end]);
+        rest = &after[end + close.len()..];
+    }
+    elements
+}
+
+/// The unescaped text of the first `<tag>` element in `xml`.
+pub(super) fn xml_text(xml: &str, tag: &str) -> Option<String> {
+    xml_elements(xml, tag)
+        .first()
+        .map(|text| xml_unescape(text))
+}
+
+fn xml_unescape(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+pub(super) fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,751 @@
+//! The S3 tools: object upload and download, listing, and presigned URLs.
+
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use chrono::Utc;
+use reqwest::Method;
+use serde_json::{Value, json};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tracing::{debug, info, warn};
+
+use super::request::{etag, xml_elements, xml_escape, xml_text};
+use super::sigv4::{self, SignableRequest};
+use super::{DEFAULT_VAULT_PROVIDER, S3Adapter, require_str};
+use crate::error::{AdapterError, Result};
+use crate::filesystem::resolve_within;
+use crate::traits::ToolDefinition;
+
+/// Objects larger than this are not returned inline by `s3_get_object`.
+const MAX_INLINE_BYTES: usize = 1024 * 1024;
+
+/// Default and maximum presigned URL lifetime, in seconds.
+const DEFAULT_PRESIGN_SECS: u64 = 3600;
+const MAX_PRESIGN_SECS: u64 = 7 * 24 * 3600;
+
+/// An upload body, either in memory or read from a file part by part.
+enum UploadBody {
+    Bytes(Vec<u8>),
+    File(tokio::fs::File),
+}
+
+impl UploadBody {
+    /// Take up to `max` bytes from the front of the body.
+    async fn next_chunk(&mut self, max: usize) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::Bytes(bytes) => {
+                let n = bytes.len().min(max);
+                Ok(bytes.drain(..n).collect())
+            }
+            Self::File(file) => {
+                let mut chunk = Vec::with_capacity(max);
+                file.take(max as u64).read_to_end(&mut chunk).await?;
+                Ok(chunk)
+            }
+        }
+    }
+}
+
+impl S3Adapter {
+    /// Upload an object from inline content or a local file.
+    pub(super) async fn tool_put_object(&self, params: Value) -> Result<Value> {
+        let tool = "s3_put_object";
+        let bucket = self.bucket(tool, &params)?;
+        let key = require_str(tool, &params, "key")?;
+        let content_type = params.get("content_type").and_then(Value::as_str);
+
+        let (mut body, size) = match (
+            params.get("content").and_then(Value::as_str),
+            params.get("path").and_then(Value::as_str),
+        ) {
+            (Some(content), _) => (
+                UploadBody::Bytes(content.as_bytes().to_vec()),
+                content.len(),
+            ),
+            (None, Some(path)) => {
+                let file = tokio::fs::File::open(self.local_path(tool, path)?).await?;
+                let size = file.metadata().await?.len() as usize;
+                (UploadBody::File(file), size)
+            }
+            (None, None) => {
+                return Err(AdapterError::InvalidParams {
+                    tool_name: tool.into(),
+                    reason: "either `content` or `path` is required".into(),
+                });
+            }
+        };
+
+        let etag = if size > self.config.multipart_threshold {
+            self.multipart_upload(tool, bucket, key, &mut body, content_type)
+                .await?
+        } else {
+            let bytes = body.next_chunk(size).await?;
+            let target = self.target(bucket, Some(key), &[])?;
+            let response = self
+                .send(tool, Method::PUT, &target, &[], bytes, content_type)
+                .await?;
+            etag(&response)
+        };
+
+        info!(bucket = bucket, key = key, size, "S3 object uploaded");
+        Ok(json!({
+            "success": true,
+            "bucket": bucket,
+            "key": key,
+            "size": size,
+            "etag": etag,
+            "multipart": size > self.config.multipart_threshold,
+        }))
+    }
+
+    /// Upload `body` in parts, aborting the upload if any step fails.
+    async fn multipart_upload(
+        &self,
+        tool_name: &str,
+        bucket: &str,
+        key: &str,
+        body: &mut UploadBody,
+        content_type: Option<&str>,
+    ) -> Result<Option<String>> {
+        let query = vec![("uploads".to_string(), String::new())];
+        let target = self.target(bucket, Some(key), &query)?;
+        let response = self
+            .send(
+                tool_name,
+                Method::POST,
+                &target,
+                &query,
+                Vec::new(),
+                content_type,
+            )
+            .await?;
+        let text = response.text().await.unwrap_or_default();
+        let upload_id =
+            xml_text(&text, "UploadId").ok_or_else(|| AdapterError::ExecutionFailed {
+                tool_name: tool_name.into(),
+                reason: "S3 did not return an upload ID".into(),
+            })?;
+        debug!(key = key, upload_id = %upload_id, "S3 multipart upload started");
+
+        let result = self
+            .upload_parts(tool_name, bucket, key, &upload_id, body)
+            .await;
+        if result.is_err() {
+            let query = vec![("uploadId".to_string(), upload_id.clone())];
+            let aborted = match self.target(bucket, Some(key), &query) {
+                Ok(target) => self
+                    .send(tool_name, Method::DELETE, &target, &query, Vec::new(), None)
+                    .await
+                    .map(|_| ()),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = aborted {
+                warn!(key = key, upload_id = %upload_id, error = %e, "failed to abort S3 multipart upload");
+            }
+        }
+        result
+    }
+
+    /// Send every part of `body` and complete the upload.
+    async fn upload_parts(
+        &self,
+        tool_name: &str,
+        bucket: &str,
+        key: &str,
+        upload_id: &str,
+        body: &mut UploadBody,
+    ) -> Result<Option<String>> {
+        let mut parts = Vec::new();
+        loop {
+            let chunk = body.next_chunk(self.config.part_size).await?;
+            if chunk.is_empty() {
+                break;
+            }
+            let part_number = parts.len() + 1;
+            let query = vec![
+                ("partNumber".to_string(), part_number.to_string()),
+                ("uploadId".to_string(), upload_id.to_string()),
+            ];
+            let target = self.target(bucket, Some(key), &query)?;
+            let response = self
+                .send(tool_name, Method::PUT, &target, &query, chunk, None)
+                .await?;
+            let etag = etag(&response).ok_or_else(|| AdapterError::ExecutionFailed {
+                tool_name: tool_name.into(),
+                reason: format!("S3 returned no ETag for part {part_number}"),
+            })?;
+            parts.push(etag);
+        }
+
+        let manifest: String = parts
+            .iter()
+            .enumerate()
+            .map(|(i, etag)| {
+                format!(
+                    "<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>",
+                    i + 1,
+                    xml_escape(etag)
+                )
+            })
+            .collect();
+        let manifest = format!("<CompleteMultipartUpload>{manifest}</CompleteMultipartUpload>");
+        let query = vec![("uploadId".to_string(), upload_id.to_string())];
+        let target = self.target(bucket, Some(key), &query)?;
+        let response = self
+            .send(
+                tool_name,
+                Method::POST,
+                &target,
+                &query,
+                manifest.into_bytes(),
+                Some("application/xml"),
+            )
+            .await?;
+
+        // CompleteMultipartUpload can fail after a 200 response.
+        let text = response.text().await.unwrap_or_default();
+        if text.contains("<Error>") {
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: tool_name.into(),
+                reason: format!(
+                    "S3 failed to complete the upload: {}",
+                    xml_text(&text, "Message").unwrap_or_default()
+                ),
+            });
+        }
+        info!(
+            key = key,
+            parts = parts.len(),
+            "S3 multipart upload completed"
+        );
+        Ok(xml_text(&text, "ETag"))
+    }
+
+    /// Download an object inline or to a local file.
+    pub(super) async fn tool_get_object(&self, params: Value) -> Result<Value> {
+        let tool = "s3_get_object";
+        let bucket = self.bucket(tool, &params)?;
+        let key = require_str(tool, &params, "key")?;
+
+        let target = self.target(bucket, Some(key), &[])?;
+        let mut response = self
+            .send(tool, Method::GET, &target, &[], Vec::new(), None)
+            .await?;
+        let content_type = response
+            .headers()
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+
+        if let Some(path) = params.get("path").and_then(Value::as_str) {
+            let local = self.local_path(tool, path)?;
+            if let Some(parent) = local.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            let mut file = tokio::fs::File::create(&local).await?;
+            let mut size = 0;
+            while let Some(chunk) =
+                response
+                    .chunk()
+                    .await
+                    .map_err(|e| AdapterError::ExecutionFailed {
+                        tool_name: tool.into(),
+                        reason: format!("failed to read S3 object: {e}"),
+                    })?
+            {
+                size += chunk.len();
+                file.write_all(&chunk).await?;
+            }
+            file.flush().await?;
+            info!(
+                bucket = bucket,
+                key = key,
+                size,
+                path = path,
+                "S3 object downloaded"
+            );
+            return Ok(json!({
+                "bucket": bucket,
+                "key": key,
+                "path": path,
+                "size": size,
+                "content_type": content_type,
+            }));
+        }
+
+        // Refuse oversized objects before buffering them: by the advertised
+        // length when there is one, otherwise as soon as the limit is passed.
+        if let Some(len) = response.content_length()
+            && len > MAX_INLINE_BYTES as u64
+        {
+            return Err(too_large_inline(tool));
+        }
+        let mut bytes = Vec::new();
+        while let Some(chunk) =
+            response
+                .chunk()
+                .await
+                .map_err(|e| AdapterError::ExecutionFailed {
+                    tool_name: tool.into(),
+                    reason: format!("failed to read S3 object: {e}"),
+                })?
+        {
+            if bytes.len() + chunk.len() > MAX_INLINE_BYTES {
+                return Err(too_large_inline(tool));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        let mut result = json!({
+            "bucket": bucket,
+            "key": key,
+            "size": bytes.len(),
+            "content_type": content_type,
+        });
+        match std::str::from_utf8(&bytes) {
+            Ok(text) => result["content"] = json!(text),
+            Err(_) => {
+                result["content_base64"] =
+                    json!(base64::engine::general_purpose::STANDARD.encode(&bytes));
+            }
+        }
+        Ok(result)
+    }
+
+    /// List objects, optionally under a prefix.
+    pub(super) async fn tool_list_objects(&self, params: Value) -> Result<Value> {
+        let tool = "s3_list_objects";
+        let bucket = self.bucket(tool, &params)?;
+
+        let max_keys = params
+            .get("max_keys")
+            .and_then(Value::as_u64)
+            .unwrap_or(1000)
+            .clamp(1, 1000);
+        let mut query = vec![
+            ("list-type".to_string(), "2".to_string()),
+            ("max-keys".to_string(), max_keys.to_string()),
+        ];
+        for (param, name) in [
+            ("prefix", "prefix"),
+            ("delimiter", "delimiter"),
+            ("continuation_token", "continuation-token"),
+        ] {
+            if let Some(value) = params.get(param).and_then(Value::as_str) {
+                query.push((name.to_string(), value.to_string()));
+            }
+        }
+
+        let target = self.target(bucket, None, &query)?;
+        let response = self
+            .send(tool, Method::GET, &target, &query, Vec::new(), None)
+            .await?;
+        let text = response
+            .text()
+            .await
+            .map_err(|e| AdapterError::ExecutionFailed {
+                tool_name: tool.into(),
+                reason: format!("failed to read S3 listing: {e}"),
+            })?;
+
+        let objects: Vec<Value> = xml_elements(&text, "Contents")
+            .into_iter()
+            .map(|entry| {
+                json!({
+                    "key": xml_text(entry, "Key"),
+                    "size": xml_text(entry, "Size").and_then(|s| s.parse::<u64>().ok()),
+                    "last_modified": xml_text(entry, "LastModified"),
+                    "etag": xml_text(entry, "ETag"),
+                })
+            })
+            .collect();
+        let prefixes: Vec<String> = xml_elements(&text, "CommonPrefixes")
+            .into_iter()
+            .filter_map(|entry| xml_text(entry, "Prefix"))
+            .collect();
+        let truncated = xml_text(&text, "IsTruncated").as_deref() == Some("true");
+
+        Ok(json!({
+            "bucket": bucket,
+            "objects": objects,
+            "common_prefixes": prefixes,
+            "next_continuation_token": if truncated {
+                xml_text(&text, "NextContinuationToken")
+            } else {
+                None
+            },
+        }))
+    }
+
+    /// Create a presigned URL for downloading or uploading an object.
+    pub(super) async fn tool_presign_url(&self, params: Value) -> Result<Value> {
+        let tool = "s3_presign_url";
+        let bucket = self.bucket(tool, &params)?;
+        let key = require_str(tool, &params, "key")?;
+        let expires_secs = params
+            .get("expiry_secs")
+            .and_then(Value::as_u64)
+            .unwrap_or(DEFAULT_PRESIGN_SECS)
+            .clamp(1, MAX_PRESIGN_SECS);
+        let method = match params.get("method").and_then(Value::as_str) {
+            None => "GET",
+            Some(m) if m.eq_ignore_ascii_case("get") => "GET",
+            Some(m) if m.eq_ignore_ascii_case("put") => "PUT",
+            Some(other) => {
+                return Err(AdapterError::InvalidParams {
+                    tool_name: tool.into(),
+                    reason: format!("`method` must be GET or PUT, got {other:?}"),
+                });
+            }
+        };
+        let credentials = self
+            .credentials
+            .as_ref()
+            .ok_or_else(|| AdapterError::AuthRequired {
+                adapter_id: self.id.clone(),
+                provider: DEFAULT_VAULT_PROVIDER.to_string(),
+            })?;
+
+        let target = self.target(bucket, Some(key), &[])?;
+        let request = SignableRequest {
+            method,
+            host: &target.host,
+            path: &target.path,
+            query: &[],
+            payload_hash: sigv4::UNSIGNED_PAYLOAD,
+        };
+        let query = sigv4::presign_query(
+            &request,
+            credentials,
+            &self.config.region,
+            Utc::now(),
+            expires_secs,
+        );
+        Ok(json!({
+            "url": format!("{}?{query}", target.url),
+            "method": method,
+            "expires_in_secs": expires_secs,
+        }))
+    }
+
+    /// Resolve a `path` parameter inside [`S3Config::root_dir`].
+    ///
+    /// Only relative paths are accepted, and they may not climb out of the
+    /// root with `..`.
+    ///
+    /// [`S3Config::root_dir`]: super::S3Config::root_dir
+    fn local_path(&self, tool_name: &str, path: &str) -> Result<PathBuf> {
+        if Path::new(path).is_absolute() {
+            return Err(AdapterError::InvalidParams {
+                tool_name: tool_name.into(),
+                reason: format!("`path` must be relative to the S3 root directory, got `{path}`"),
+            });
+        }
+        resolve_within(&self.config.root_dir, path, tool_name)
+    }
+}
+
+fn too_large_inline(tool_name: &str) -> AdapterError {
+    AdapterError::InvalidParams {
+        tool_name: tool_name.into(),
+        reason: format!(
+            "object is larger than {MAX_INLINE_BYTES} bytes, too large to return inline; \
+             pass `path` to save it"
+        ),
+    }
+}
+
+/// The tools exposed by the S3 adapter.
+pub(super) fn definitions() -> Vec<ToolDefinition> {
+    let bucket = json!({
+        "type": "string",
+        "description": "Bucket name (defaults to the configured bucket)"
+    });
+    vec![
+        ToolDefinition {
+            name: "s3_put_object".into(),
+            description: "Upload an object to S3 from text content or a local file".into(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "bucket": bucket,
+                    "key": { "type": "string", "description": "Object key" },
+                    "content": {
+                        "type": "string",
+                        "description": "Text content to upload"
+                    },
+                    "path": {
+                        "type": "string",
+                        "description": "Local file to upload, relative to the S3 root directory; used when `content` is absent"
+                    },
+                    "content_type": {
+                        "type": "string",
+                        "description": "MIME type stored with the object"
+                    }
+                },
+                "required": ["key"]
+            }),
+            destructive: true,
+        },
+        ToolDefinition {
+            name: "s3_get_object".into(),
+            description: "Download an S3 object, returning its content or saving it to a file"
+                .into(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "bucket": bucket,
+                    "key": { "type": "string", "description": "Object key" },
+                    "path": {
+                        "type": "string",
+                        "description": "Local file, relative to the S3 root directory, to save the object to instead of returning it"
+                    }
+                },
+                "required": ["key"]
+            }),
+            destructive: false,
+        },
+        ToolDefinition {
+            name: "s3_list_objects".into(),
+            description: "List objects in an S3 bucket, optionally under a prefix".into(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "bucket": bucket,
+                    "prefix": {
+                        "type": "string",
+                        "description": "Only list keys starting with this prefix"
+                    },
+                    "delimiter": {
+                        "type": "string",
+                        "description": "Group keys by this delimiter (e.g. \"/\") into common prefixes"
+                    },
+                    "max_keys": {
+                        "type": "integer",
+                        "description": "Maximum keys to return (default and max 1000)"
+                    },
+                    "continuation_token": {
+                        "type": "string",
+                        "description": "Token from a previous call's next_continuation_token"
+                    }
+                }
+            }),
+            destructive: false,
+        },
+        ToolDefinition {
+            name: "s3_presign_url".into(),
+            description: "Create a time-limited URL to download or upload an S3 object".into(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "bucket": bucket,
+                    "key": { "type": "string", "description": "Object key" },
+                    "expiry_secs": {
+                        "type": "integer",
+                        "description": "Lifetime in seconds (default 3600, max 604800)"
+                    },
+                    "method": {
+                        "type": "string",
+                        "enum": ["GET", "PUT"],
+                        "description": "GET to download (default) or PUT to upload"
+                    }
+                },
+                "required": ["key"]
+            }),
+            destructive: false,
+        },
+    ]
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::s3::mock::mock_s3;
+    use crate::s3::{S3Adapter, S3Config, S3Credentials};
+    use crate::traits::Adapter;
+
+    async fn connected(base: &str, config: S3Config) -> S3Adapter {
+        let config = S3Config {
+            endpoint: Some(base.to_string()),
+            bucket: "docs".into(),
+            path_style: true,
+            ..config
+        };
+        let mut adapter = S3Adapter::new("s3", config).with_credentials(S3Credentials {
+            access_key_id: "minio".into(),
+            secret_access_key: "minio-secret".into(),
+            session_token: None,
+        });
+        adapter.connect().await.unwrap();
+        adapter
+    }
+
+    #[tokio::test]
+    async fn put_then_get_round_trips() {
+        let (base, store) = mock_s3().await;
+        let adapter = connected(&base, S3Config::default()).await;
+
+        let put = adapter
+            .execute_tool(
+                "s3_put_object",
+                json!({ "key": "reports/q3 summary.txt", "content": "Revenue up 12%" }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(put["etag"], "\"abc\"");
+        assert_eq!(put["multipart"], false);
+        assert!(
+            store
+                .lock()
+                .unwrap()
+                .contains_key("/docs/reports/q3 summary.txt")
+        );
+
+        let get = adapter
+            .execute_tool("s3_get_object", json!({ "key": "reports/q3 summary.txt" }))
+            .await
+            .unwrap();
+        assert_eq!(get["content"], "Revenue up 12%");
+        assert_eq!(get["size"], 14);
+
+        let missing = adapter
+            .execute_tool("s3_get_object", json!({ "key": "nope" }))
+            .await
+            .unwrap_err();
+        assert!(missing.to_string().contains("NoSuchKey"));
+    }
+
+    #[tokio::test]
+    async fn list_objects_filters_by_prefix() {
+        let (base, _) = mock_s3().await;
+        let adapter = connected(&base, S3Config::default()).await;
+        for key in ["reports/a.txt", "reports/b.txt", "images/logo.png"] {
+            adapter
+                .execute_tool("s3_put_object", json!({ "key": key, "content": "x" }))
+                .await
+                .unwrap();
+        }
+
+        let listed = adapter
+            .execute_tool("s3_list_objects", json!({ "prefix": "reports/" }))
+            .await
+            .unwrap();
+        let keys: Vec<&str> = listed["objects"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|o| o["key"].as_str().unwrap())
+            .collect();
+        assert_eq!(keys, ["reports/a.txt", "reports/b.txt"]);
+        assert_eq!(listed["objects"][0]["size"], 1);
+        assert!(listed["next_continuation_token"].is_null());
+    }
+
+    #[tokio::test]
+    async fn large_upload_uses_multipart() {
+        let (base, store) = mock_s3().await;
+        let config = S3Config {
+            multipart_threshold: 10,
+            part_size: 8,
+            ..S3Config::default()
+        };
+        let adapter = connected(&base, config).await;
+
+        let content = "0123456789abcdefghij";
+        let put = adapter
+            .execute_tool(
+                "s3_put_object",
+                json!({ "key": "big.txt", "content": content }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(put["multipart"], true);
+        assert_eq!(put["etag"], "\"mp-2\"");
+        assert_eq!(
+            store.lock().unwrap().get("/docs/big.txt").unwrap(),
+            content.as_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn presign_url_targets_the_object() {
+        let adapter = connected("http://localhost:9000", S3Config::default()).await;
+        let result = adapter
+            .execute_tool(
+                "s3_presign_url",
+                json!({ "key": "a b.txt", "expiry_secs": 600 }),
+            )
+            .await
+            .unwrap();
+        let url = result["url"].as_str().unwrap();
+        assert!(url.starts_with("http://localhost:9000/docs/a%20b.txt?X-Amz-Algorithm="));
+        assert!(url.contains("X-Amz-Expires=600"));
+        assert!(url.contains("X-Amz-Signature="));
+    }
+
+    #[tokio::test]
+    async fn local_paths_are_confined_to_the_root() {
+        let (base, _) = mock_s3().await;
+        let dir = tempfile::tempdir().unwrap();
+        let config = S3Config {
+            root_dir: dir.path().to_path_buf(),
+            ..S3Config::default()
+        };
+        let adapter = connected(&base, config).await;
+        adapter
+            .execute_tool("s3_put_object", json!({ "key": "a.txt", "content": "hi" }))
+            .await
+            .unwrap();
+
+        for path in ["../escape.txt", "out/../../escape.txt", "/etc/passwd"] {
+            let err = adapter
+                .execute_tool("s3_get_object", json!({ "key": "a.txt", "path": path }))
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(err, AdapterError::InvalidParams { .. }),
+                "{path}: {err}"
+            );
+            let err = adapter
+                .execute_tool("s3_put_object", json!({ "key": "b.txt", "path": path }))
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(err, AdapterError::InvalidParams { .. }),
+                "{path}: {err}"
+            );
+        }
+
+        let get = adapter
+            .execute_tool(
+                "s3_get_object",
+                json!({ "key": "a.txt", "path": "downloads/a.txt" }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(get["size"], 2);
+        let saved = std::fs::read_to_string(dir.path().join("downloads/a.txt")).unwrap();
+        assert_eq!(saved, "hi");
+    }
+
+    #[tokio::test]
+    async fn oversized_object_is_not_returned_inline() {
+        let (base, store) = mock_s3().await;
+        let adapter = connected(&base, S3Config::default()).await;
+        store
+            .lock()
+            .unwrap()
+            .insert("/docs/big.txt".into(), vec![b'a'; MAX_INLINE_BYTES + 1]);
+
+        let err = adapter
+            .execute_tool("s3_get_object", json!({ "key": "big.txt" }))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("pass `path`"), "{err}");
+    }
+}
@@ -0,0 +1,517 @@
+//! Scratchpad adapter -- a small persistent key/value store for the agent.
+//!
+//! Lets the agent deliberately remember small facts across sessions ("the
+//! user's staging server is `stage-2`") without going through semantic
+//! memory.  Entries live in [`openintent_store::BotStateStore`] under a
+//! per-adapter namespace, usually one per user or session, and may carry a
+//! time-to-live.  Four tools are exposed:
+//!
+//! - `kv_set` -- store a JSON value under a key, optionally expiring.
+//! - `kv_get` -- read a value back.
+//! - `kv_delete` -- remove a key.
+//! - `kv_list` -- list keys (and values) by prefix.
+//!
+//! Expired entries are treated as absent and removed when next read.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::{Value, json};
+use tracing::{debug, info};
+
+use openintent_store::BotStateStore;
+
+use crate::error::{AdapterError, Result};
+use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
+
+/// Prefix of every scratchpad key in the bot state table.
+const KEY_PREFIX: &str = "scratchpad/";
+
+/// Longest accepted key, in bytes.
+const MAX_KEY_LEN: usize = 256;
+
+/// Largest accepted value, in bytes of serialized JSON.
+const MAX_VALUE_BYTES: usize = 16 * 1024;
+
+/// Default and maximum number of entries returned by `kv_list`.
+const DEFAULT_LIST_LIMIT: usize = 50;
+const MAX_LIST_LIMIT: usize = 500;
+
+/// Adapter that exposes a namespaced key/value scratchpad as agent tools.
+pub struct ScratchpadAdapter {
+    /// Unique adapter instance identifier.
+    id: String,
+    /// Whether the adapter has been connected (initialised).
+    connected: bool,
+    /// Backing key/value store.
+    store: BotStateStore,
+    /// Storage prefix for this namespace, e.g. `scratchpad/user:42/`.
+    prefix: String,
+}
+
+impl ScratchpadAdapter {
+    /// Create a scratchpad whose keys live in `namespace`.  Adapters with
+    /// the same namespace and store see the same entries.
+    pub fn new(id: impl Into<String>, store: BotStateStore, namespace: &str) -> Self {
+        // Escape `/` so one namespace can never be a prefix of another.
+        let namespace = namespace.replace('%', "%25").replace('/', "%2F");
+        Self {
+            id: id.into(),
+            connected: false,
+            store,
+            prefix: format!("{KEY_PREFIX}{namespace}/"),
+        }
+    }
+
+    /// Create a scratchpad private to the user with the given ID.
+    pub fn for_user(id: impl Into<String>, store: BotStateStore, user_id: i64) -> Self {
+        Self::new(id, store, &format!("user:{user_id}"))
+    }
+
+    /// Create a scratchpad private to the named session.
+    pub fn for_session(id: impl Into<String>, store: BotStateStore, session: &str) -> Self {
+        Self::new(id, store, &format!("session:{session}"))
+    }
+
+    /// Extract and validate the `key` field.
+    fn require_key<'a>(params: &'a Value, tool_name: &str) -> Result<&'a str> {
+        let key = params.get("key").and_then(|v| v.as_str()).ok_or_else(|| {
+            AdapterError::InvalidParams {
+                tool_name: tool_name.to_string(),
+                reason: "missing required string field `key`".to_string(),
+            }
+        })?;
+        if key.is_empty() || key.len() > MAX_KEY_LEN {
+            return Err(AdapterError::InvalidParams {
+                tool_name: tool_name.to_string(),
+                reason: format!("`key` must be 1 to {MAX_KEY_LEN} bytes long"),
+            });
+        }
+        Ok(key)
+    }
+
+    /// Map a store failure to an adapter error.
+    fn store_error(tool_name: &str, e: impl std::fmt::Display) -> AdapterError {
+        AdapterError::ExecutionFailed {
+            tool_name: tool_name.to_string(),
+            reason: format!("scratchpad store error: {e}"),
+        }
+    }
+
+    /// Decode a stored entry, returning `None` if it has expired or is not a
+    /// scratchpad entry.
+    fn decode(raw: &str, now: i64) -> Option<Entry> {
+        let stored: Value = serde_json::from_str(raw).ok()?;
+        let expires_at = stored.get("expires_at").and_then(Value::as_i64);
+        if expires_at.is_some_and(|at| at <= now) {
+            return None;
+        }
+        Some(Entry {
+            value: stored.get("value").cloned()?,
+            expires_at,
+        })
+    }
+
+    // -- Tool implementations ------------------------------------------------
+
+    /// Store a value under a key.
+    async fn tool_kv_set(&self, params: Value) -> Result<Value> {
+        let key = Self::require_key(&params, "kv_set")?;
+        let value = params
+            .get("value")
+            .cloned()
+            .ok_or_else(|| AdapterError::InvalidParams {
+                tool_name: "kv_set".to_string(),
+                reason: "missing required field `value`".to_string(),
+            })?;
+        let ttl_secs = match params.get("ttl_secs") {
+            None | Some(Value::Null) => None,
+            Some(v) => Some(v.as_i64().filter(|secs| *secs > 0).ok_or_else(|| {
+                AdapterError::InvalidParams {
+                    tool_name: "kv_set".to_string(),
+                    reason: "`ttl_secs` must be a positive integer".to_string(),
+                }
+            })?),
+        };
+
+        let expires_at = ttl_secs.map(|secs| Utc::now().timestamp().saturating_add(secs));
+        let stored = json!({ "value": value, "expires_at": expires_at }).to_string();
+        if stored.len() > MAX_VALUE_BYTES {
+            return Err(AdapterError::InvalidParams {
+                tool_name: "kv_set".to_string(),
+                reason: format!(
+                    "value is too large ({} bytes, limit {MAX_VALUE_BYTES}); store long content in a file or semantic memory",
+                    stored.len()
+                ),
+            });
+        }
+
+        debug!(key, ?ttl_secs, "scratchpad set");
+        self.store
+            .set(&format!("{}{key}", self.prefix), &stored)
+            .await
+            .map_err(|e| Self::store_error("kv_set", e))?;
+
+        Ok(json!({ "key": key, "stored": true, "expires_at": expires_at }))
+    }
+
+    /// Read a value back.
+    async fn tool_kv_get(&self, params: Value) -> Result<Value> {
+        let key = Self::require_key(&params, "kv_get")?;
+        let storage_key = format!("{}{key}", self.prefix);
+        let raw = self
+            .store
+            .get(&storage_key)
+            .await
+            .map_err(|e| Self::store_error("kv_get", e))?;
+
+        let entry = match raw {
+            Some(raw) => match Self::decode(&raw, Utc::now().timestamp()) {
+                Some(entry) => Some(entry),
+                None => {
+                    self.store
+                        .delete(&storage_key)
+                        .await
+                        .map_err(|e| Self::store_error("kv_get", e))?;
+                    None
+                }
+            },
+            None => None,
+        };
+
+        Ok(match entry {
+            Some(entry) => json!({
+                "key": key,
+                "found": true,
+                "value": entry.value,
+                "expires_at": entry.expires_at,
+            }),
+            None => json!({ "key": key, "found": false }),
+        })
+    }
+
+    /// Remove a key.
+    async fn tool_kv_delete(&self, params: Value) -> Result<Value> {
+        let key = Self::require_key(&params, "kv_delete")?;
+        let deleted = self
+            .store
+            .delete(&format!("{}{key}", self.prefix))
+            .await
+            .map_err(|e| Self::store_error("kv_delete", e))?;
+        Ok(json!({ "key": key, "deleted": deleted }))
+    }
+
+    /// List live entries whose key starts with `prefix`.
+    async fn tool_kv_list(&self, params: Value) -> Result<Value> {
+        let prefix = params.get("prefix").and_then(|v| v.as_str()).unwrap_or("");
+        let limit = params
+            .get("limit")
+            .and_then(|v| v.as_u64())
+            .map_or(DEFAULT_LIST_LIMIT, |n| {
+                (n as usize).clamp(1, MAX_LIST_LIMIT)
+            });
+
+        let rows = self
+            .store
+            .list_prefix(&format!("{}{prefix}", self.prefix))
+            .await
+            .map_err(|e| Self::store_error("kv_list", e))?;
+
+        let now = Utc::now().timestamp();
+        let mut entries = Vec::new();
+        let mut total = 0;
+        for (storage_key, raw) in rows {
+            let key = &storage_key[self.prefix.len()..];
+            match Self::decode(&raw, now) {
+                Some(entry) => {
+                    total += 1;
+                    if entries.len() < limit {
+                        entries.push(json!({
+                            "key": key,
+                            "value": entry.value,
+                            "expires_at": entry.expires_at,
+                        }));
+                    }
+                }
+                None => {
+                    self.store
+                        .delete(&storage_key)
+                        .await
+                        .map_err(|e| Self::store_error("kv_list", e))?;
+                }
+            }
+        }
+
+        Ok(json!({ "entries": entries, "total": total }))
+    }
+}
+
+/// A live scratchpad entry.
+struct Entry {
+    value: Value,
+    expires_at: Option<i64>,
+}
+
+#[async_trait]
+impl Adapter for ScratchpadAdapter {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        AdapterType::System
+    }
+
+    async fn connect(&mut self) -> Result<()> {
+        info!(id = %self.id, "scratchpad adapter connected");
+        self.connected = true;
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        info!(id = %self.id, "scratchpad adapter disconnected");
+        self.connected = false;
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        if !self.connected {
+            return Ok(HealthStatus::Unhealthy);
+        }
+        match self.store.get(&self.prefix).await {
+            Ok(_) => Ok(HealthStatus::Healthy),
+            Err(_) => Ok(HealthStatus::Degraded),
+        }
+    }
+
+    fn tools(&self) -> Vec<ToolDefinition> {
+        vec![
+            ToolDefinition {
+                name: "kv_set".into(),
+                description: "Remember a small fact under a key, across sessions. Use for exact values you will look up by name; use memory_save for knowledge to search later".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "key": {
+                            "type": "string",
+                            "description": "Key to store under; use `/` to group related keys (e.g. `deploy/host`)"
+                        },
+                        "value": {
+                            "description": "Any JSON value to store"
+                        },
+                        "ttl_secs": {
+                            "type": "integer",
+                            "description": "Forget the value after this many seconds (default: keep forever)",
+                            "minimum": 1
+                        }
+                    },
+                    "required": ["key", "value"]
+                }),
+                destructive: true,
+            },
+            ToolDefinition {
+                name: "kv_get".into(),
+                description: "Read a value previously stored with kv_set".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "key": {
+                            "type": "string",
+                            "description": "Key to read"
+                        }
+                    },
+                    "required": ["key"]
+                }),
+                destructive: false,
+            },
+            ToolDefinition {
+                name: "kv_delete".into(),
+                description: "Forget a value stored with kv_set".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "key": {
+                            "type": "string",
+                            "description": "Key to delete"
+                        }
+                    },
+                    "required": ["key"]
+                }),
+                destructive: true,
+            },
+            ToolDefinition {
+                name: "kv_list".into(),
+                description: "List stored keys and values, optionally only those starting with a prefix".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "prefix": {
+                            "type": "string",
+                            "description": "Only list keys starting with this prefix"
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "description": "Maximum number of entries (default: 50)",
+                            "minimum": 1,
+                            "maximum": 500
+                        }
+                    }
+                }),
+                destructive: false,
+            },
+        ]
+    }
+
+    async fn execute_tool(&self, name: &str, params: Value) -> Result<Value> {
+        if !self.connected {
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: name.to_string(),
+                reason: format!("adapter `{}` is not connected", self.id),
+            });
+        }
+        match name {
+            "kv_set" => self.tool_kv_set(params).await,
+            "kv_get" => self.tool_kv_get(params).await,
+            "kv_delete" => self.tool_kv_delete(params).await,
+            "kv_list" => self.tool_kv_list(params).await,
+            _ => Err(AdapterError::ToolNotFound {
+                adapter_id: self.id.clone(),
+                tool_name: name.to_string(),
+            }),
+        }
+    }
+
+    fn required_auth(&self) -> Option<AuthRequirement> {
+        None
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openintent_store::Database;
+
+    async fn setup_store() -> BotStateStore {
+        let db = Database::open_in_memory().unwrap();
+        db.run_migrations().await.unwrap();
+        BotStateStore::new(db)
+    }
+
+    async fn connected(store: &BotStateStore, namespace: &str) -> ScratchpadAdapter {
+        let mut adapter = ScratchpadAdapter::new("scratchpad", store.clone(), namespace);
+        adapter.connect().await.unwrap();
+        adapter
+    }
+
+    #[tokio::test]
+    async fn values_persist_across_adapter_instances() {
+        let store = setup_store().await;
+
+        let first = connected(&store, "user:1").await;
+        first
+            .execute_tool(
+                "kv_set",
+                json!({"key": "deploy/host", "value": {"name": "stage-2", "port": 8443}}),
+            )
+            .await
+            .unwrap();
+        drop(first);
+
+        let second = connected(&store, "user:1").await;
+        let result = second
+            .execute_tool("kv_get", json!({"key": "deploy/host"}))
+            .await
+            .unwrap();
+        assert_eq!(result["found"], true);
+        assert_eq!(result["value"], json!({"name": "stage-2", "port": 8443}));
+
+        // Another namespace does not see the entry.
+        let other = connected(&store, "user:2").await;
+        let result = other
+            .execute_tool("kv_get", json!({"key": "deploy/host"}))
+            .await
+            .unwrap();
+        assert_eq!(result["found"], false);
+    }
+
+    #[tokio::test]
+    async fn list_filters_by_prefix_and_delete_removes() {
+        let store = setup_store().await;
+        let adapter = connected(&store, "session:a").await;
+        for (key, value) in [
+            ("deploy/host", "stage-2"),
+            ("deploy/user", "ci"),
+            ("tz", "UTC"),
+        ] {
+            adapter
+                .execute_tool("kv_set", json!({"key": key, "value": value}))
+                .await
+                .unwrap();
+        }
+        // A namespace that only shares a string prefix stays separate.
+        connected(&store, "session:a/deploy")
+            .await
+            .execute_tool("kv_set", json!({"key": "x", "value": 1}))
+            .await
+            .unwrap();
+
+        let listed = adapter
+            .execute_tool("kv_list", json!({"prefix": "deploy/"}))
+            .await
+            .unwrap();
+        assert_eq!(listed["total"], 2);
+        assert_eq!(listed["entries"][0]["key"], "deploy/host");
+        assert_eq!(listed["entries"][1]["value"], "ci");
+
+        let deleted = adapter
+            .execute_tool("kv_delete", json!({"key": "tz"}))
+            .await
+            .unwrap();
+        assert_eq!(deleted["deleted"], true);
+        let listed = adapter.execute_tool("kv_list", json!({})).await.unwrap();
+        assert_eq!(listed["total"], 2);
+    }
+
+    #[tokio::test]
+    async fn expired_entries_are_forgotten() {
+        let store = setup_store().await;
+        let adapter = connected(&store, "user:1").await;
+
+        let result = adapter
+            .execute_tool(
+                "kv_set",
+                json!({"key": "otp", "value": "1234", "ttl_secs": 60}),
+            )
+            .await
+            .unwrap();
+        assert!(result["expires_at"].as_i64().unwrap() > Utc::now().timestamp());
+
+        // Backdate the entry past its expiry.
+        let expired = json!({"value": "1234", "expires_at": Utc::now().timestamp() - 1});
+        store
+            .set("scratchpad/user:1/otp", &expired.to_string())
+            .await
+            .unwrap();
+
+        let result = adapter
+            .execute_tool("kv_get", json!({"key": "otp"}))
+            .await
+            .unwrap();
+        assert_eq!(result["found"], false);
+        assert!(store.get("scratchpad/user:1/otp").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn invalid_ttl_is_rejected() {
+        let store = setup_store().await;
+        let adapter = connected(&store, "user:1").await;
+        let err = adapter
+            .execute_tool("kv_set", json!({"key": "k", "value": 1, "ttl_secs": 0}))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AdapterError::InvalidParams { .. }));
+    }
+}
@@ -218,6 +218,7 @@ impl Adapter for ShellAdapter {
                 },
                 "required": ["command"]
             }),
+            destructive: true,
         }]
     }
 
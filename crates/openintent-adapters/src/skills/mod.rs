@@ -101,6 +101,7 @@ impl ToolAdapter for SkillsAdapter {
                     },
                     "required": ["email"]
                 }),
+                destructive: true,
             },
             ToolDefinition {
                 name: "skill_ip_lookup_lookup".into(),
@@ -114,6 +115,7 @@ impl ToolAdapter for SkillsAdapter {
                         }
                     }
                 }),
+                destructive: false,
             },
         ]
     }
@@ -0,0 +1,280 @@
+//! Slack Events API receiver.
+//!
+//! Slack delivers subscribed events as signed `POST` requests.  Each request
+//! carries `X-Slack-Request-Timestamp` and `X-Slack-Signature: v0=<hex>`,
+//! the HMAC-SHA256 of `v0:<timestamp>:<body>` under the app's signing
+//! secret.  Verified `event_callback` payloads are published onto the
+//! [`IpcBus`] as [`Event::AdapterEvent`]s whose `kind` is the Slack event
+//! type (e.g. `message`, `app_mention`) and whose payload is the inner
+//! event.  The one-time `url_verification` handshake is answered directly.
+
+use chrono::Utc;
+use openintent_kernel::ipc::{Event, IpcBus};
+use ring::hmac;
+use serde_json::{Value, json};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::error::{AdapterError, Result};
+use crate::webhook::{Request, decode_hex, read_request, write_response};
+
+/// Requests older than this (in seconds) are rejected to prevent replays.
+const MAX_CLOCK_SKEW_SECS: i64 = 5 * 60;
+
+/// Where the Events API listener binds and how requests are verified.
+#[derive(Debug, Clone)]
+pub struct SlackEventsConfig {
+    /// Address to bind, e.g. `127.0.0.1` or `0.0.0.0`.
+    pub host: String,
+    /// Port to listen on.  `0` picks a free port.
+    pub port: u16,
+    /// Request path configured as the app's Request URL.
+    pub path: String,
+    /// The app's signing secret.
+    pub signing_secret: String,
+}
+
+impl Default for SlackEventsConfig {
+    fn default() -> Self {
+        Self {
+            host: "127.0.0.1".into(),
+            port: 8788,
+            path: "/slack/events".into(),
+            signing_secret: String::new(),
+        }
+    }
+}
+
+/// Bind the listener and serve Events API requests until the returned task
+/// is aborted.
+pub(crate) async fn start(
+    adapter_id: &str,
+    config: &SlackEventsConfig,
+    bus: IpcBus,
+) -> Result<JoinHandle<()>> {
+    if config.signing_secret.is_empty() {
+        return Err(AdapterError::ConfigError(
+            "Slack signing secret is not set; refusing to accept unsigned events".into(),
+        ));
+    }
+    let listener = TcpListener::bind(format!("{}:{}", config.host, config.port)).await?;
+    info!(
+        id = adapter_id,
+        addr = %listener.local_addr()?,
+        path = %config.path,
+        "Slack Events API listener started"
+    );
+
+    let receiver = EventsReceiver {
+        adapter_id: adapter_id.to_string(),
+        path: config.path.clone(),
+        key: hmac::Key::new(hmac::HMAC_SHA256, config.signing_secret.as_bytes()),
+        bus,
+    };
+    Ok(tokio::spawn(receiver.serve(listener)))
+}
+
+/// State shared by every connection the listener accepts.
+#[derive(Clone)]
+struct EventsReceiver {
+    adapter_id: String,
+    path: String,
+    key: hmac::Key,
+    bus: IpcBus,
+}
+
+impl EventsReceiver {
+    /// Accept connections until the task is aborted.
+    async fn serve(self, listener: TcpListener) {
+        loop {
+            match listener.accept().await {
+                Ok((stream, peer)) => {
+                    let receiver = self.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = receiver.handle(stream).await {
+                            debug!(peer = %peer, error = %e, "Slack events connection failed");
+                        }
+                    });
+                }
+                Err(e) => warn!(error = %e, "Slack events listener failed to accept"),
+            }
+        }
+    }
+
+    /// Read one request, act on it, and write the response.
+    async fn handle(&self, mut stream: TcpStream) -> std::io::Result<()> {
+        let (status, body) = match read_request(&mut stream).await {
+            Ok(request) => self.accept(request, Utc::now().timestamp()),
+            Err(status) => (status, String::new()),
+        };
+        write_response(&mut stream, status, "application/json", &body).await
+    }
+
+    /// Verify and dispatch `request`, returning the response status and body.
+    fn accept(&self, request: Request, now: i64) -> (&'static str, String) {
+        if request.path != self.path {
+            return ("404 Not Found", String::new());
+        }
+        if request.method != "POST" {
+            return ("405 Method Not Allowed", String::new());
+        }
+        if !self.verify(&request, now) {
+            warn!(id = %self.adapter_id, "rejected Slack event with missing or invalid signature");
+            return ("401 Unauthorized", String::new());
+        }
+        let Ok(payload) = serde_json::from_slice::<Value>(&request.body) else {
+            return ("400 Bad Request", String::new());
+        };
+
+        match payload.get("type").and_then(Value::as_str) {
+            Some("url_verification") => {
+                let challenge = payload.get("challenge").cloned().unwrap_or_default();
+                ("200 OK", json!({ "challenge": challenge }).to_string())
+            }
+            Some("event_callback") => {
+                let event = payload.get("event").cloned().unwrap_or_default();
+                let kind = event
+                    .get("type")
+                    .and_then(Value::as_str)
+                    .unwrap_or("unknown")
+                    .to_string();
+                if let Some(retry) = request.header("x-slack-retry-num") {
+                    debug!(kind = %kind, retry, "Slack redelivered an event");
+                }
+                info!(id = %self.adapter_id, kind = %kind, "Slack event received");
+                let published = self.bus.publish(Event::AdapterEvent {
+                    adapter_id: self.adapter_id.clone(),
+                    kind,
+                    payload: event.to_string(),
+                    timestamp: Utc::now(),
+                });
+                match published {
+                    Ok(_) => ("200 OK", String::new()),
+                    Err(e) => {
+                        warn!(error = %e, "failed to publish Slack event");
+                        ("500 Internal Server Error", String::new())
+                    }
+                }
+            }
+            other => {
+                debug!(kind = ?other, "ignoring Slack request of unhandled type");
+                ("200 OK", String::new())
+            }
+        }
+    }
+
+    /// Check the request signature and that its timestamp is recent.
+    fn verify(&self, request: &Request, now: i64) -> bool {
+        let Some(timestamp) = request.header("x-slack-request-timestamp") else {
+            return false;
+        };
+        let Ok(sent_at) = timestamp.parse::<i64>() else {
+            return false;
+        };
+        if (now - sent_at).abs() > MAX_CLOCK_SKEW_SECS {
+            return false;
+        }
+        let Some(signature) = request
+            .header("x-slack-signature")
+            .and_then(|sig| sig.strip_prefix("v0="))
+            .and_then(decode_hex)
+        else {
+            return false;
+        };
+
+        let mut base = format!("v0:{timestamp}:").into_bytes();
+        base.extend_from_slice(&request.body);
+        hmac::verify(&self.key, &base, &signature).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "8f742231b10e8888abcd99yyyzzz85a5";
+
+    fn receiver(bus: &IpcBus) -> EventsReceiver {
+        EventsReceiver {
+            adapter_id: "slack".into(),
+            path: "/slack/events".into(),
+            key: hmac::Key::new(hmac::HMAC_SHA256, SECRET.as_bytes()),
+            bus: bus.clone(),
+        }
+    }
+
+    fn signed_request(body: &str, timestamp: i64) -> Request {
+        let key = hmac::Key::new(hmac::HMAC_SHA256, SECRET.as_bytes());
+        let tag = hmac::sign(&key, format!("v0:{timestamp}:{body}").as_bytes());
+        let hex: String = tag.as_ref().iter().map(|b| format!("{b:02x}")).collect();
+        Request {
+            method: "POST".into(),
+            path: "/slack/events".into(),
+            query: String::new(),
+            headers: vec![
+                ("x-slack-request-timestamp".into(), timestamp.to_string()),
+                ("x-slack-signature".into(), format!("v0={hex}")),
+            ],
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn signed_event_callback_is_published() {
+        let bus = IpcBus::new(16);
+        let mut rx = bus.subscribe();
+        let now = 1_700_000_000;
+
+        let body = r#"{"type":"event_callback","event":{"type":"app_mention","text":"<@U1> status?","channel":"C1"}}"#;
+        let (status, _) = receiver(&bus).accept(signed_request(body, now), now);
+        assert_eq!(status, "200 OK");
+
+        let event = rx.try_recv().expect("event must be published");
+        match event.as_ref() {
+            Event::AdapterEvent {
+                adapter_id,
+                kind,
+                payload,
+                ..
+            } => {
+                assert_eq!(adapter_id, "slack");
+                assert_eq!(kind, "app_mention");
+                let payload: Value = serde_json::from_str(payload).unwrap();
+                assert_eq!(payload["channel"], "C1");
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn url_verification_echoes_challenge() {
+        let bus = IpcBus::new(16);
+        let now = 1_700_000_000;
+        let body = r#"{"type":"url_verification","challenge":"3eZbrw1aBm2rZgRNFdxV2595E9CY3gmdALWMmHkvFXO7tYXAYM8P"}"#;
+
+        let (status, response) = receiver(&bus).accept(signed_request(body, now), now);
+        assert_eq!(status, "200 OK");
+        assert_eq!(
+            response,
+            r#"{"challenge":"3eZbrw1aBm2rZgRNFdxV2595E9CY3gmdALWMmHkvFXO7tYXAYM8P"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn stale_or_unsigned_requests_are_rejected() {
+        let bus = IpcBus::new(16);
+        let mut rx = bus.subscribe();
+        let now = 1_700_000_000;
+        let body = r#"{"type":"event_callback","event":{"type":"message"}}"#;
+
+        let stale = signed_request(body, now - MAX_CLOCK_SKEW_SECS - 1);
+        assert_eq!(receiver(&bus).accept(stale, now).0, "401 Unauthorized");
+
+        let mut unsigned = signed_request(body, now);
+        unsigned.headers.retain(|(k, _)| k != "x-slack-signature");
+        assert_eq!(receiver(&bus).accept(unsigned, now).0, "401 Unauthorized");
+
+        assert!(rx.try_recv().is_err(), "no event may be published");
+    }
+}
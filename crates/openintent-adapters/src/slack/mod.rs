@@ -0,0 +1,838 @@
+//! Slack Web API adapter for OpenIntentOS.
+//!
+//! Provides tools for posting plain-text and Block Kit messages, listing
+//! channels, and uploading files to Slack workspaces.  The bot token comes
+//! from the adapter's configuration, the `SLACK_BOT_TOKEN` environment
+//! variable, or the `slack` provider in the auth engine, in that order.
+//!
+//! Slack throttles per method and workspace.  Throttled calls surface as
+//! [`AdapterError::RateLimited`] carrying Slack's `Retry-After`, so the agent
+//! runtime can back off and retry.
+//!
+//! Incoming Events API callbacks are handled by [`events`]; when enabled with
+//! [`SlackAdapter::with_events`] they are published onto the IPC bus like
+//! webhook payloads.
+
+pub mod events;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use openintent_auth_engine::AuthManager;
+use openintent_kernel::ipc::IpcBus;
+use serde_json::{Value, json};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::credentials;
+use crate::error::{AdapterError, Result};
+use crate::http_client::{self, HttpClientFactory};
+use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
+
+pub use events::SlackEventsConfig;
+
+/// Slack Web API base URL.
+const DEFAULT_BASE_URL: &str = "https://slack.com/api";
+
+/// Provider name used for the bot token in the auth engine.
+const AUTH_PROVIDER: &str = "slack";
+
+/// Default page size for `conversations.list`.
+const DEFAULT_CHANNEL_LIMIT: u64 = 100;
+
+/// Slack error codes meaning the token is missing, invalid, or revoked.
+const AUTH_ERRORS: &[&str] = &[
+    "not_authed",
+    "invalid_auth",
+    "token_revoked",
+    "token_expired",
+    "account_inactive",
+];
+
+/// Slack Web API adapter.
+pub struct SlackAdapter {
+    /// Unique identifier for this adapter instance.
+    id: String,
+    /// Whether the adapter has been connected.
+    connected: bool,
+    /// Bot token (`xoxb-...`) used for API calls.
+    bot_token: Option<String>,
+    /// Auth engine consulted for a token when none is configured.
+    auth: Option<Arc<AuthManager>>,
+    /// Base URL for the Slack Web API.
+    base_url: String,
+    /// HTTP client for making requests.
+    client: reqwest::Client,
+    /// Events API listener configuration and the bus events go to.
+    events: Option<(SlackEventsConfig, IpcBus)>,
+    /// The Events API listener, while connected.
+    events_server: Option<JoinHandle<()>>,
+}
+
+impl SlackAdapter {
+    /// Create a new Slack adapter with no token configured.
+    pub fn new(id: impl Into<String>) -> Self {
+        let client = HttpClientFactory::default().build().unwrap_or_default();
+
+        Self {
+            id: id.into(),
+            connected: false,
+            bot_token: None,
+            auth: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            client,
+            events: None,
+            events_server: None,
+        }
+    }
+
+    /// Create a new Slack adapter with a pre-configured bot token.
+    pub fn with_token(id: impl Into<String>, bot_token: impl Into<String>) -> Self {
+        let mut adapter = Self::new(id);
+        adapter.bot_token = Some(bot_token.into());
+        adapter
+    }
+
+    /// Look up the bot token in the auth engine on connect if none is set.
+    pub fn with_auth_manager(mut self, auth: Arc<AuthManager>) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Point the adapter at a different API base URL (e.g. a test server).
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Receive Events API callbacks on connect and publish them to `bus`.
+    pub fn with_events(mut self, config: SlackEventsConfig, bus: IpcBus) -> Self {
+        self.events = Some((config, bus));
+        self
+    }
+
+    /// Look for a bot token in the environment, then in the auth engine.
+    async fn find_token(&self) -> Option<String> {
+        if let Ok(token) = std::env::var("SLACK_BOT_TOKEN")
+            && !token.is_empty()
+        {
+            info!(id = %self.id, "Slack adapter loaded bot token from environment");
+            return Some(token);
+        }
+        let auth = self.auth.as_ref()?;
+        match auth.get_valid_token(AUTH_PROVIDER, None).await {
+            Ok(token) => {
+                info!(id = %self.id, "Slack adapter loaded bot token from auth engine");
+                Some(token)
+            }
+            Err(e) => {
+                debug!(id = %self.id, error = %e, "no Slack token in auth engine");
+                None
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // HTTP helpers
+    // -----------------------------------------------------------------------
+
+    /// Build a full API URL for a Web API method.
+    fn api_url(&self, method: &str) -> String {
+        format!("{}/{}", self.base_url, method)
+    }
+
+    /// Resolve the bot token, returning an error if none is available.
+    fn resolve_token(&self) -> Result<&str> {
+        self.bot_token
+            .as_deref()
+            .ok_or_else(|| AdapterError::AuthRequired {
+                adapter_id: self.id.clone(),
+                provider: AUTH_PROVIDER.to_string(),
+            })
+    }
+
+    /// Call a Web API method with a JSON body.
+    async fn post_json(&self, tool_name: &str, method: &str, body: &Value) -> Result<Value> {
+        let token = self.resolve_token()?;
+        let url = self.api_url(method);
+        debug!(url = %url, tool = tool_name, "Slack POST");
+
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(token)
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| request_failed(tool_name, e))?;
+        self.read_response(tool_name, response).await
+    }
+
+    /// Call a Web API method with form-encoded arguments.
+    async fn post_form(
+        &self,
+        tool_name: &str,
+        method: &str,
+        form: &[(&str, String)],
+    ) -> Result<Value> {
+        let token = self.resolve_token()?;
+        let url = self.api_url(method);
+        debug!(url = %url, tool = tool_name, "Slack POST (form)");
+
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(token)
+            .form(form)
+            .send()
+            .await
+            .map_err(|e| request_failed(tool_name, e))?;
+        self.read_response(tool_name, response).await
+    }
+
+    /// Turn a Web API response into its JSON body, mapping throttling, auth
+    /// failures, and `"ok": false` to adapter errors.
+    async fn read_response(&self, tool_name: &str, response: reqwest::Response) -> Result<Value> {
+        let status = response.status();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let retry_after_secs = response
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u64>().ok());
+            warn!(
+                tool = tool_name,
+                ?retry_after_secs,
+                "Slack API rate limited"
+            );
+            return Err(AdapterError::RateLimited {
+                adapter_id: self.id.clone(),
+                retry_after_secs,
+            });
+        }
+
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| AdapterError::ExecutionFailed {
+                tool_name: tool_name.to_string(),
+                reason: format!("failed to parse Slack API response (HTTP {status}): {e}"),
+            })?;
+
+        if body.get("ok").and_then(Value::as_bool) == Some(true) {
+            return Ok(body);
+        }
+        let error = body
+            .get("error")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown_error");
+        if error == "ratelimited" {
+            return Err(AdapterError::RateLimited {
+                adapter_id: self.id.clone(),
+                retry_after_secs: None,
+            });
+        }
+        if AUTH_ERRORS.contains(&error) {
+            return Err(AdapterError::AuthRequired {
+                adapter_id: self.id.clone(),
+                provider: AUTH_PROVIDER.to_string(),
+            });
+        }
+        Err(AdapterError::ExecutionFailed {
+            tool_name: tool_name.to_string(),
+            reason: format!("Slack API returned error: {error}"),
+        })
+    }
+
+    // -----------------------------------------------------------------------
+    // Tool implementations
+    // -----------------------------------------------------------------------
+
+    /// Post a plain-text message, optionally in a thread.
+    async fn tool_send_message(&self, params: Value) -> Result<Value> {
+        let tool = "slack_send_message";
+        let channel = require_str(tool, &params, "channel")?;
+        let text = require_str(tool, &params, "text")?;
+
+        let mut body = json!({ "channel": channel, "text": text });
+        if let Some(thread_ts) = params.get("thread_ts").and_then(Value::as_str) {
+            body["thread_ts"] = json!(thread_ts);
+        }
+
+        let resp = self.post_json(tool, "chat.postMessage", &body).await?;
+        info!(channel = channel, "Slack message sent");
+        Ok(json!({
+            "success": true,
+            "channel": resp.get("channel"),
+            "ts": resp.get("ts"),
+        }))
+    }
+
+    /// Post a Block Kit message.
+    async fn tool_send_blocks(&self, params: Value) -> Result<Value> {
+        let tool = "slack_send_blocks";
+        let channel = require_str(tool, &params, "channel")?;
+
+        // Accept the blocks either as a JSON array or as a string holding one.
+        let blocks = match params.get("blocks") {
+            Some(Value::String(raw)) => {
+                serde_json::from_str(raw).map_err(|e| AdapterError::InvalidParams {
+                    tool_name: tool.into(),
+                    reason: format!("`blocks` is not valid JSON: {e}"),
+                })?
+            }
+            Some(value) => value.clone(),
+            None => {
+                return Err(AdapterError::InvalidParams {
+                    tool_name: tool.into(),
+                    reason: "missing required field: blocks".into(),
+                });
+            }
+        };
+        if !blocks.is_array() {
+            return Err(AdapterError::InvalidParams {
+                tool_name: tool.into(),
+                reason: "`blocks` must be a JSON array of Block Kit blocks".into(),
+            });
+        }
+
+        // Slack uses `text` for notifications and clients without block support.
+        let text = params
+            .get("text")
+            .and_then(Value::as_str)
+            .unwrap_or("New message");
+        let body = json!({ "channel": channel, "blocks": blocks, "text": text });
+
+        let resp = self.post_json(tool, "chat.postMessage", &body).await?;
+        info!(channel = channel, "Slack blocks message sent");
+        Ok(json!({
+            "success": true,
+            "channel": resp.get("channel"),
+            "ts": resp.get("ts"),
+        }))
+    }
+
+    /// List channels the bot can see.
+    async fn tool_list_channels(&self, params: Value) -> Result<Value> {
+        let tool = "slack_list_channels";
+        let limit = params
+            .get("limit")
+            .and_then(Value::as_u64)
+            .unwrap_or(DEFAULT_CHANNEL_LIMIT)
+            .clamp(1, 1000);
+
+        let mut form = vec![
+            ("limit", limit.to_string()),
+            ("types", "public_channel,private_channel".to_string()),
+            ("exclude_archived", "true".to_string()),
+        ];
+        if let Some(cursor) = params.get("cursor").and_then(Value::as_str) {
+            form.push(("cursor", cursor.to_string()));
+        }
+
+        let resp = self.post_form(tool, "conversations.list", &form).await?;
+        let channels: Vec<Value> = resp
+            .get("channels")
+            .and_then(Value::as_array)
+            .map(|list| {
+                list.iter()
+                    .map(|c| {
+                        json!({
+                            "id": c.get("id"),
+                            "name": c.get("name"),
+                            "is_private": c.get("is_private"),
+                            "num_members": c.get("num_members"),
+                            "topic": c.pointer("/topic/value"),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let next_cursor = resp
+            .pointer("/response_metadata/next_cursor")
+            .and_then(Value::as_str)
+            .filter(|c| !c.is_empty());
+
+        Ok(json!({
+            "channels": channels,
+            "next_cursor": next_cursor,
+        }))
+    }
+
+    /// Upload a file to a channel using Slack's external upload flow.
+    async fn tool_upload_file(&self, params: Value) -> Result<Value> {
+        let tool = "slack_upload_file";
+        let channel = require_str(tool, &params, "channel")?;
+
+        let (filename, bytes) = match params.get("path").and_then(Value::as_str) {
+            Some(path) => {
+                let bytes = tokio::fs::read(path).await?;
+                let name = std::path::Path::new(path)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| "upload".to_string());
+                let name = params
+                    .get("filename")
+                    .and_then(Value::as_str)
+                    .map_or(name, str::to_string);
+                (name, bytes)
+            }
+            None => {
+                let content = require_str(tool, &params, "content").map_err(|_| {
+                    AdapterError::InvalidParams {
+                        tool_name: tool.into(),
+                        reason: "either `path` or `content` is required".into(),
+                    }
+                })?;
+                let name = require_str(tool, &params, "filename")?;
+                (name.to_string(), content.as_bytes().to_vec())
+            }
+        };
+        let title = params
+            .get("title")
+            .and_then(Value::as_str)
+            .unwrap_or(&filename)
+            .to_string();
+
+        // 1. Reserve an upload URL.
+        let form = [
+            ("filename", filename.clone()),
+            ("length", bytes.len().to_string()),
+        ];
+        let reserved = self
+            .post_form(tool, "files.getUploadURLExternal", &form)
+            .await?;
+        let (Some(upload_url), Some(file_id)) = (
+            reserved.get("upload_url").and_then(Value::as_str),
+            reserved.get("file_id").and_then(Value::as_str),
+        ) else {
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: tool.into(),
+                reason: "Slack did not return an upload URL".into(),
+            });
+        };
+
+        // 2. Send the bytes.
+        let size = bytes.len();
+        let response = self
+            .client
+            .post(upload_url)
+            .body(bytes)
+            .send()
+            .await
+            .map_err(|e| request_failed(tool, e))?;
+        if !response.status().is_success() {
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: tool.into(),
+                reason: format!("file upload returned HTTP {}", response.status()),
+            });
+        }
+
+        // 3. Share it in the channel.
+        let mut body = json!({
+            "files": [{ "id": file_id, "title": title }],
+            "channel_id": channel,
+        });
+        if let Some(comment) = params.get("initial_comment").and_then(Value::as_str) {
+            body["initial_comment"] = json!(comment);
+        }
+        self.post_json(tool, "files.completeUploadExternal", &body)
+            .await?;
+
+        info!(
+            channel = channel,
+            file_id = file_id,
+            size,
+            "Slack file uploaded"
+        );
+        Ok(json!({
+            "success": true,
+            "file_id": file_id,
+            "filename": filename,
+            "size": size,
+        }))
+    }
+}
+
+#[async_trait]
+impl Adapter for SlackAdapter {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        AdapterType::Messaging
+    }
+
+    async fn connect(&mut self) -> Result<()> {
+        if self.bot_token.is_none() {
+            self.bot_token = self.find_token().await;
+        }
+        if self.bot_token.is_none() {
+            warn!(
+                id = %self.id,
+                "no Slack bot token configured; Slack adapter connecting without auth"
+            );
+        }
+
+        if let Some((config, bus)) = &self.events
+            && self.events_server.is_none()
+        {
+            self.events_server = Some(events::start(&self.id, config, bus.clone()).await?);
+        }
+
+        self.connected = true;
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        if let Some(server) = self.events_server.take() {
+            server.abort();
+        }
+        info!(id = %self.id, "Slack adapter disconnected");
+        self.bot_token = None;
+        self.connected = false;
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        if !self.connected {
+            return Ok(HealthStatus::Unhealthy);
+        }
+        if self.bot_token.is_some() {
+            Ok(HealthStatus::Healthy)
+        } else {
+            Ok(HealthStatus::Degraded)
+        }
+    }
+
+    fn tools(&self) -> Vec<ToolDefinition> {
+        vec![
+            ToolDefinition {
+                name: "slack_send_message".into(),
+                description: "Send a plain-text message to a Slack channel".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "channel": {
+                            "type": "string",
+                            "description": "Channel ID (e.g. C0123456789) or name"
+                        },
+                        "text": {
+                            "type": "string",
+                            "description": "Message text (Slack mrkdwn supported)"
+                        },
+                        "thread_ts": {
+                            "type": "string",
+                            "description": "Timestamp of a parent message to reply in its thread"
+                        }
+                    },
+                    "required": ["channel", "text"]
+                }),
+                destructive: true,
+            },
+            ToolDefinition {
+                name: "slack_send_blocks".into(),
+                description: "Send a rich Block Kit message to a Slack channel".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "channel": {
+                            "type": "string",
+                            "description": "Channel ID or name"
+                        },
+                        "blocks": {
+                            "description": "Block Kit blocks, as a JSON array or a string containing one"
+                        },
+                        "text": {
+                            "type": "string",
+                            "description": "Fallback text for notifications"
+                        }
+                    },
+                    "required": ["channel", "blocks"]
+                }),
+                destructive: true,
+            },
+            ToolDefinition {
+                name: "slack_list_channels".into(),
+                description: "List Slack channels the bot can see".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "limit": {
+                            "type": "integer",
+                            "description": "Maximum channels to return (default 100, max 1000)"
+                        },
+                        "cursor": {
+                            "type": "string",
+                            "description": "Pagination cursor from a previous call's next_cursor"
+                        }
+                    }
+                }),
+                destructive: false,
+            },
+            ToolDefinition {
+                name: "slack_upload_file".into(),
+                description:
+                    "Upload a file to a Slack channel, from a local path or inline content".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "channel": {
+                            "type": "string",
+                            "description": "Channel ID to share the file in"
+                        },
+                        "path": {
+                            "type": "string",
+                            "description": "Local file to upload"
+                        },
+                        "content": {
+                            "type": "string",
+                            "description": "Text content to upload instead of a file (requires filename)"
+                        },
+                        "filename": {
+                            "type": "string",
+                            "description": "File name shown in Slack"
+                        },
+                        "title": {
+                            "type": "string",
+                            "description": "Title of the file"
+                        },
+                        "initial_comment": {
+                            "type": "string",
+                            "description": "Message posted with the file"
+                        }
+                    },
+                    "required": ["channel"]
+                }),
+                destructive: true,
+            },
+        ]
+    }
+
+    async fn execute_tool(&self, name: &str, params: Value) -> Result<Value> {
+        if !self.connected {
+            return Err(AdapterError::NotConnected {
+                adapter_id: self.id.clone(),
+                reason: "adapter not connected".into(),
+            });
+        }
+        http_client::ensure_online()?;
+
+        match name {
+            "slack_send_message" => self.tool_send_message(params).await,
+            "slack_send_blocks" => self.tool_send_blocks(params).await,
+            "slack_list_channels" => self.tool_list_channels(params).await,
+            "slack_upload_file" => self.tool_upload_file(params).await,
+            _ => Err(AdapterError::ToolNotFound {
+                adapter_id: self.id.clone(),
+                tool_name: name.to_string(),
+            }),
+        }
+    }
+
+    fn required_auth(&self) -> Option<AuthRequirement> {
+        Some(AuthRequirement {
+            provider: AUTH_PROVIDER.into(),
+            scopes: vec![
+                "chat:write".into(),
+                "channels:read".into(),
+                "groups:read".into(),
+                "files:write".into(),
+            ],
+        })
+    }
+
+    fn has_credentials(&self) -> bool {
+        credentials::is_set(self.bot_token.as_deref())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Extract a required string parameter.
+fn require_str<'a>(tool_name: &str, params: &'a Value, field: &str) -> Result<&'a str> {
+    params
+        .get(field)
+        .and_then(Value::as_str)
+        .ok_or_else(|| AdapterError::InvalidParams {
+            tool_name: tool_name.into(),
+            reason: format!("missing required string field: {field}"),
+        })
+}
+
+/// Map a transport failure to an adapter error.
+fn request_failed(tool_name: &str, e: reqwest::Error) -> AdapterError {
+    AdapterError::ExecutionFailed {
+        tool_name: tool_name.to_string(),
+        reason: format!("Slack API request failed: {e}"),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::webhook::{read_request, write_response};
+
+    /// A request the mock Slack API received.
+    #[derive(Debug)]
+    struct Recorded {
+        path: String,
+        authorization: Option<String>,
+        body: String,
+    }
+
+    /// Serve a mock Slack API that answers each call with `reply(path)`.
+    async fn mock_slack<F>(reply: F) -> (String, Arc<Mutex<Vec<Recorded>>>)
+    where
+        F: Fn(&str) -> (&'static str, Value) + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&recorded);
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let Ok(request) = read_request(&mut stream).await else {
+                    continue;
+                };
+                let (status, body) = reply(&request.path);
+                log.lock().unwrap().push(Recorded {
+                    authorization: request.header("authorization").map(str::to_string),
+                    path: request.path,
+                    body: String::from_utf8_lossy(&request.body).into_owned(),
+                });
+                let _ = write_response(&mut stream, status, "application/json", &body.to_string())
+                    .await;
+            }
+        });
+        (base, recorded)
+    }
+
+    async fn connected(base: &str) -> SlackAdapter {
+        let mut adapter = SlackAdapter::with_token("slack", "xoxb-test").with_base_url(base);
+        adapter.connect().await.unwrap();
+        adapter
+    }
+
+    #[tokio::test]
+    async fn send_message_posts_to_chat_post_message() {
+        let (base, recorded) = mock_slack(|_| {
+            (
+                "200 OK",
+                json!({ "ok": true, "channel": "C123", "ts": "1700000000.000100" }),
+            )
+        })
+        .await;
+        let adapter = connected(&base).await;
+
+        let result = adapter
+            .execute_tool(
+                "slack_send_message",
+                json!({ "channel": "C123", "text": "Deploy finished" }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["ts"], "1700000000.000100");
+
+        let recorded = recorded.lock().unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].path, "/chat.postMessage");
+        assert_eq!(
+            recorded[0].authorization.as_deref(),
+            Some("Bearer xoxb-test")
+        );
+        let body: Value = serde_json::from_str(&recorded[0].body).unwrap();
+        assert_eq!(
+            body,
+            json!({ "channel": "C123", "text": "Deploy finished" })
+        );
+    }
+
+    #[tokio::test]
+    async fn list_channels_returns_summaries_and_cursor() {
+        let (base, recorded) = mock_slack(|_| {
+            (
+                "200 OK",
+                json!({
+                    "ok": true,
+                    "channels": [
+                        { "id": "C1", "name": "general", "is_private": false,
+                          "num_members": 42, "topic": { "value": "Company news" } },
+                        { "id": "C2", "name": "ops", "is_private": true,
+                          "num_members": 5, "topic": { "value": "" } }
+                    ],
+                    "response_metadata": { "next_cursor": "dGVhbTpDMg==" }
+                }),
+            )
+        })
+        .await;
+        let adapter = connected(&base).await;
+
+        let result = adapter
+            .execute_tool("slack_list_channels", json!({ "limit": 2 }))
+            .await
+            .unwrap();
+        assert_eq!(result["channels"][0]["name"], "general");
+        assert_eq!(result["channels"][0]["topic"], "Company news");
+        assert_eq!(result["channels"][1]["is_private"], true);
+        assert_eq!(result["next_cursor"], "dGVhbTpDMg==");
+
+        let recorded = recorded.lock().unwrap();
+        assert_eq!(recorded[0].path, "/conversations.list");
+        assert!(recorded[0].body.contains("limit=2"));
+    }
+
+    #[tokio::test]
+    async fn throttled_call_reports_retry_after() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            if let Ok((mut stream, _)) = listener.accept().await {
+                let _ = read_request(&mut stream).await;
+                let response = "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 7\r\n\
+                                Content-Length: 0\r\nConnection: close\r\n\r\n";
+                let _ = tokio::io::AsyncWriteExt::write_all(&mut stream, response.as_bytes()).await;
+            }
+        });
+        let adapter = connected(&base).await;
+
+        let err = adapter
+            .execute_tool(
+                "slack_send_message",
+                json!({ "channel": "C1", "text": "hi" }),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AdapterError::RateLimited {
+                retry_after_secs: Some(7),
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn invalid_token_requires_auth() {
+        let (base, _) =
+            mock_slack(|_| ("200 OK", json!({ "ok": false, "error": "invalid_auth" }))).await;
+        let adapter = connected(&base).await;
+
+        let err = adapter
+            .execute_tool("slack_list_channels", json!({}))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AdapterError::AuthRequired { .. }));
+    }
+}
@@ -4,13 +4,18 @@
 
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::Instant;
 
 use async_trait::async_trait;
 use serde_json::Value;
-use sqlx::{Column, Row, SqlitePool};
+use sqlx::pool::PoolConnection;
+use sqlx::{Column, Row, Sqlite, SqlitePool};
 
 use crate::error::{AdapterError, Result};
-use crate::sqlite::{MigrationManager, SqliteConnectionPool};
+use crate::sqlite::connection::{arm_statement_deadline, disarm_statement_deadline};
+use crate::sqlite::schema;
+use crate::sqlite::statement::ensure_single_statement;
+use crate::sqlite::{MigrationManager, SqliteConnectionPool, SqliteError, SqlitePoolConfig};
 use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
 
 /// SQLite database adapter for OpenIntentOS.
@@ -24,7 +29,15 @@ pub struct SqliteAdapter {
 impl SqliteAdapter {
     /// Create a new SQLite adapter.
     pub async fn new(default_db_path: Option<PathBuf>) -> Result<Self> {
-        let pool = SqliteConnectionPool::new(default_db_path)
+        Self::with_config(default_db_path, SqlitePoolConfig::default()).await
+    }
+
+    /// Create a new SQLite adapter with explicit pool size and timeouts.
+    pub async fn with_config(
+        default_db_path: Option<PathBuf>,
+        config: SqlitePoolConfig,
+    ) -> Result<Self> {
+        let pool = SqliteConnectionPool::with_config(default_db_path, config)
             .await
             .map_err(|e| AdapterError::ConfigError(e.to_string()))?;
         let migrations = MigrationManager::new();
@@ -37,26 +50,43 @@ impl SqliteAdapter {
         })
     }
 
-    /// Execute a SQL SELECT query and return results as JSON.
-    async fn execute_query(
-        &self,
-        db_name: &str,
-        query: &str,
-        params: Vec<Value>,
-    ) -> Result<Value> {
-        let pool = self.resolve_pool(db_name)?;
+    /// Execute a read-only SQL query and return results as JSON.
+    ///
+    /// The connection is switched to `query_only` for the duration of the
+    /// call, so SQLite itself rejects any statement that would write.
+    async fn execute_query(&self, db_name: &str, query: &str, params: Vec<Value>) -> Result<Value> {
+        const TOOL: &str = "sqlite_query";
 
         let mut sql_query = sqlx::query(query);
         for param in params {
             sql_query = Self::bind_json_param(sql_query, param)?;
         }
 
-        let rows = sql_query.fetch_all(pool).await.map_err(|e| {
-            AdapterError::ExecutionFailed {
-                tool_name: "sqlite_query".into(),
-                reason: e.to_string(),
-            }
-        })?;
+        let mut conn = self.acquire(db_name, TOOL).await?;
+        sqlx::query("PRAGMA query_only = ON")
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| Self::tool_error(TOOL, e.into()))?;
+
+        let outcome = async {
+            let deadline = self.arm_deadline(&mut conn, TOOL).await?;
+            let rows = sql_query.fetch_all(&mut *conn).await;
+            self.disarm_deadline(&mut conn, TOOL).await?;
+            rows.map_err(|e| self.statement_error(TOOL, e, deadline))
+        }
+        .await;
+
+        // Never hand a read-only connection back to the pool, whichever way
+        // the query ended.
+        if sqlx::query("PRAGMA query_only = OFF")
+            .execute(&mut *conn)
+            .await
+            .is_err()
+        {
+            conn.close_on_drop();
+        }
+
+        let rows = outcome?;
 
         let mut results = Vec::new();
         for row in rows {
@@ -84,26 +114,27 @@ impl SqliteAdapter {
         Ok(Value::Array(results))
     }
 
-    /// Execute a SQL command (INSERT, UPDATE, DELETE) and return affected rows.
+    /// Execute a SQL command (INSERT, UPDATE, DELETE, DDL) and return
+    /// affected rows.
     async fn execute_command(
         &self,
         db_name: &str,
         query: &str,
         params: Vec<Value>,
     ) -> Result<Value> {
-        let pool = self.resolve_pool(db_name)?;
+        const TOOL: &str = "sqlite_execute";
 
         let mut sql_query = sqlx::query(query);
         for param in params {
             sql_query = Self::bind_json_param(sql_query, param)?;
         }
 
-        let result = sql_query.execute(pool).await.map_err(|e| {
-            AdapterError::ExecutionFailed {
-                tool_name: "sqlite_execute".into(),
-                reason: e.to_string(),
-            }
-        })?;
+        let mut conn = self.acquire(db_name, TOOL).await?;
+        let deadline = self.arm_deadline(&mut conn, TOOL).await?;
+        let outcome = sql_query.execute(&mut *conn).await;
+        self.disarm_deadline(&mut conn, TOOL).await?;
+
+        let result = outcome.map_err(|e| self.statement_error(TOOL, e, deadline))?;
 
         Ok(serde_json::json!({
             "rows_affected": result.rows_affected(),
@@ -111,6 +142,66 @@ impl SqliteAdapter {
         }))
     }
 
+    /// Check out a pooled connection for the given database name.
+    async fn acquire(&self, db_name: &str, tool_name: &str) -> Result<PoolConnection<Sqlite>> {
+        let pool = self.resolve_pool(db_name)?;
+        pool.acquire()
+            .await
+            .map_err(|e| Self::tool_error(tool_name, e.into()))
+    }
+
+    /// Arm the statement timeout on a checked-out connection.
+    async fn arm_deadline(
+        &self,
+        conn: &mut PoolConnection<Sqlite>,
+        tool_name: &str,
+    ) -> Result<Instant> {
+        arm_statement_deadline(conn, self.pool.config().statement_timeout)
+            .await
+            .map_err(|e| Self::tool_error(tool_name, e))
+    }
+
+    /// Disarm the statement timeout before the connection returns to the pool.
+    async fn disarm_deadline(
+        &self,
+        conn: &mut PoolConnection<Sqlite>,
+        tool_name: &str,
+    ) -> Result<()> {
+        disarm_statement_deadline(conn)
+            .await
+            .map_err(|e| Self::tool_error(tool_name, e))
+    }
+
+    /// Classify a statement failure, reporting interrupted statements as
+    /// timeouts.
+    fn statement_error(
+        &self,
+        tool_name: &str,
+        err: sqlx::Error,
+        deadline: Instant,
+    ) -> AdapterError {
+        let err = if Instant::now() >= deadline {
+            SqliteError::Timeout(self.pool.config().statement_timeout)
+        } else {
+            SqliteError::from(err)
+        };
+        Self::tool_error(tool_name, err)
+    }
+
+    /// Convert a [`SqliteError`] into the adapter error surfaced to the agent.
+    fn tool_error(tool_name: &str, err: SqliteError) -> AdapterError {
+        match err {
+            SqliteError::Timeout(limit) => AdapterError::Timeout {
+                seconds: limit.as_secs(),
+                reason: format!("`{tool_name}`: {err}"),
+            },
+            other => AdapterError::ExecutionFailed {
+                tool_name: tool_name.into(),
+                reason: other.to_string(),
+            },
+        }
+    }
+
     /// Resolve a pool reference for the given database name.
     fn resolve_pool(&self, db_name: &str) -> Result<&SqlitePool> {
         if db_name == "main" {
@@ -123,15 +214,48 @@ impl SqliteAdapter {
                 })
             }
         } else {
-            self.databases.get(db_name).ok_or_else(|| {
-                AdapterError::ExecutionFailed {
+            self.databases
+                .get(db_name)
+                .ok_or_else(|| AdapterError::ExecutionFailed {
                     tool_name: "sqlite".into(),
                     reason: format!("database '{db_name}' not found"),
-                }
-            })
+                })
         }
     }
 
+    /// Extract and validate the `sql` text and positional `params` of a
+    /// statement tool call.
+    ///
+    /// `query` is accepted as a legacy alias for `sql`.
+    fn statement_args<'a>(tool_name: &str, params: &'a Value) -> Result<(&'a str, Vec<Value>)> {
+        let sql = params
+            .get("sql")
+            .or_else(|| params.get("query"))
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| AdapterError::InvalidParams {
+                tool_name: tool_name.into(),
+                reason: "missing required field `sql`".into(),
+            })?;
+
+        ensure_single_statement(sql).map_err(|e| AdapterError::InvalidParams {
+            tool_name: tool_name.into(),
+            reason: e.to_string(),
+        })?;
+
+        let sql_params = match params.get("params") {
+            None | Some(Value::Null) => Vec::new(),
+            Some(Value::Array(values)) => values.clone(),
+            Some(other) => {
+                return Err(AdapterError::InvalidParams {
+                    tool_name: tool_name.into(),
+                    reason: format!("`params` must be an array, got {other}"),
+                });
+            }
+        };
+
+        Ok((sql, sql_params))
+    }
+
     /// Bind a JSON value to an sqlx query.
     fn bind_json_param<'q>(
         query: sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
@@ -210,7 +334,8 @@ impl Adapter for SqliteAdapter {
         vec![
             ToolDefinition {
                 name: "sqlite_query".into(),
-                description: "Execute a SELECT query on a SQLite database".into(),
+                description: "Run a read-only query on a SQLite database (writes are rejected)"
+                    .into(),
                 parameters: serde_json::json!({
                     "type": "object",
                     "properties": {
@@ -219,24 +344,26 @@ impl Adapter for SqliteAdapter {
                             "description": "Database name (default: 'main')",
                             "default": "main"
                         },
-                        "query": {
+                        "sql": {
                             "type": "string",
-                            "description": "SQL SELECT query to execute"
+                            "description": "A single SQL statement; use `?` placeholders for values"
                         },
                         "params": {
                             "type": "array",
-                            "description": "Query parameters",
+                            "description": "Values bound to the `?` placeholders, in order",
                             "items": {},
                             "default": []
                         }
                     },
-                    "required": ["query"]
+                    "required": ["sql"]
                 }),
+                destructive: false,
             },
             ToolDefinition {
                 name: "sqlite_execute".into(),
-                description: "Execute an INSERT, UPDATE, or DELETE command on a SQLite database"
-                    .into(),
+                description:
+                    "Execute a write statement (INSERT, UPDATE, DELETE, DDL) on a SQLite database"
+                        .into(),
                 parameters: serde_json::json!({
                     "type": "object",
                     "properties": {
@@ -245,19 +372,76 @@ impl Adapter for SqliteAdapter {
                             "description": "Database name (default: 'main')",
                             "default": "main"
                         },
-                        "query": {
+                        "sql": {
                             "type": "string",
-                            "description": "SQL command to execute"
+                            "description": "A single SQL statement; use `?` placeholders for values"
                         },
                         "params": {
                             "type": "array",
-                            "description": "Query parameters",
+                            "description": "Values bound to the `?` placeholders, in order",
                             "items": {},
                             "default": []
                         }
                     },
-                    "required": ["query"]
+                    "required": ["sql"]
+                }),
+                destructive: true,
+            },
+            ToolDefinition {
+                name: "sqlite_list_tables".into(),
+                description: "List the tables and views in a SQLite database".into(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "database": {
+                            "type": "string",
+                            "description": "Database name (default: 'main')",
+                            "default": "main"
+                        }
+                    }
+                }),
+                destructive: false,
+            },
+            ToolDefinition {
+                name: "sqlite_describe_table".into(),
+                description: "Describe a table's columns, types, primary key, and foreign keys"
+                    .into(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "database": {
+                            "type": "string",
+                            "description": "Database name (default: 'main')",
+                            "default": "main"
+                        },
+                        "table": {
+                            "type": "string",
+                            "description": "Table name"
+                        }
+                    },
+                    "required": ["table"]
                 }),
+                destructive: false,
+            },
+            ToolDefinition {
+                name: "sqlite_list_indexes".into(),
+                description: "List the indexes on a table and the columns they cover".into(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "database": {
+                            "type": "string",
+                            "description": "Database name (default: 'main')",
+                            "default": "main"
+                        },
+                        "table": {
+                            "type": "string",
+                            "description": "Table name"
+                        }
+                    },
+                    "required": ["table"]
+                }),
+                destructive: false,
             },
             ToolDefinition {
                 name: "sqlite_migrate".into(),
@@ -276,6 +460,7 @@ impl Adapter for SqliteAdapter {
                         }
                     }
                 }),
+                destructive: true,
             },
             ToolDefinition {
                 name: "sqlite_backup".into(),
@@ -295,6 +480,7 @@ impl Adapter for SqliteAdapter {
                     },
                     "required": ["backup_path"]
                 }),
+                destructive: true,
             },
         ]
     }
@@ -314,34 +500,34 @@ impl Adapter for SqliteAdapter {
 
         match tool_name {
             "sqlite_query" => {
-                let query = params
-                    .get("query")
-                    .and_then(|v| v.as_str())
-                    .ok_or_else(|| AdapterError::InvalidParams {
-                        tool_name: "sqlite_query".into(),
-                        reason: "missing required field `query`".into(),
-                    })?;
-                let query_params = params
-                    .get("params")
-                    .and_then(|v| v.as_array())
-                    .cloned()
-                    .unwrap_or_default();
-                self.execute_query(database, query, query_params).await
+                let (sql, sql_params) = Self::statement_args("sqlite_query", &params)?;
+                self.execute_query(database, sql, sql_params).await
             }
             "sqlite_execute" => {
-                let query = params
-                    .get("query")
+                let (sql, sql_params) = Self::statement_args("sqlite_execute", &params)?;
+                self.execute_command(database, sql, sql_params).await
+            }
+            "sqlite_list_tables" => {
+                let pool = self.resolve_pool(database)?;
+                schema::list_tables(pool)
+                    .await
+                    .map_err(|e| Self::tool_error(tool_name, e))
+            }
+            "sqlite_describe_table" | "sqlite_list_indexes" => {
+                let table = params
+                    .get("table")
                     .and_then(|v| v.as_str())
                     .ok_or_else(|| AdapterError::InvalidParams {
-                        tool_name: "sqlite_execute".into(),
-                        reason: "missing required field `query`".into(),
+                        tool_name: tool_name.into(),
+                        reason: "missing required field `table`".into(),
                     })?;
-                let query_params = params
-                    .get("params")
-                    .and_then(|v| v.as_array())
-                    .cloned()
-                    .unwrap_or_default();
-                self.execute_command(database, query, query_params).await
+                let pool = self.resolve_pool(database)?;
+                let result = if tool_name == "sqlite_describe_table" {
+                    schema::describe_table(pool, table).await
+                } else {
+                    schema::list_indexes(pool, table).await
+                };
+                result.map_err(|e| Self::tool_error(tool_name, e))
             }
             "sqlite_migrate" => {
                 let migration_dir = params.get("migration_dir").and_then(|v| v.as_str());
@@ -382,3 +568,296 @@ impl Adapter for SqliteAdapter {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::time::Duration;
+
+    use serde_json::json;
+    use sqlx::Connection;
+    use sqlx::sqlite::{SqliteConnectOptions, SqliteConnection};
+
+    use super::*;
+
+    async fn connected_adapter(path: &Path, config: SqlitePoolConfig) -> SqliteAdapter {
+        let mut adapter = SqliteAdapter::with_config(Some(path.to_path_buf()), config)
+            .await
+            .unwrap();
+        adapter.connect().await.unwrap();
+        adapter
+    }
+
+    #[tokio::test]
+    async fn query_reads_and_execute_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let adapter =
+            connected_adapter(&dir.path().join("test.db"), SqlitePoolConfig::default()).await;
+
+        adapter
+            .execute_tool(
+                "sqlite_execute",
+                json!({"sql": "CREATE TABLE notes (body TEXT)"}),
+            )
+            .await
+            .unwrap();
+        let result = adapter
+            .execute_tool(
+                "sqlite_execute",
+                json!({"sql": "INSERT INTO notes (body) VALUES (?)", "params": ["hello"]}),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["rows_affected"], 1);
+
+        let rows = adapter
+            .execute_tool("sqlite_query", json!({"sql": "SELECT body FROM notes"}))
+            .await
+            .unwrap();
+        assert_eq!(rows, json!([{"body": "hello"}]));
+    }
+
+    #[tokio::test]
+    async fn params_are_bound_positionally() {
+        let dir = tempfile::tempdir().unwrap();
+        let adapter =
+            connected_adapter(&dir.path().join("test.db"), SqlitePoolConfig::default()).await;
+        adapter
+            .execute_tool(
+                "sqlite_execute",
+                json!({"sql": "CREATE TABLE users (name TEXT, age INTEGER, active BOOLEAN)"}),
+            )
+            .await
+            .unwrap();
+
+        let hostile = "x'); DROP TABLE users; --";
+        adapter
+            .execute_tool(
+                "sqlite_execute",
+                json!({"sql": "INSERT INTO users (name, age, active) VALUES (?, ?, ?)", "params": [hostile, 42, true]}),
+            )
+            .await
+            .unwrap();
+
+        let rows = adapter
+            .execute_tool(
+                "sqlite_query",
+                json!({"sql": "SELECT name, age FROM users WHERE age > ? AND name = ?", "params": [40, hostile]}),
+            )
+            .await
+            .unwrap();
+        assert_eq!(rows, json!([{"name": hostile, "age": 42}]));
+    }
+
+    #[tokio::test]
+    async fn multi_statement_payload_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let adapter =
+            connected_adapter(&dir.path().join("test.db"), SqlitePoolConfig::default()).await;
+        adapter
+            .execute_tool(
+                "sqlite_execute",
+                json!({"sql": "CREATE TABLE t (id INTEGER)"}),
+            )
+            .await
+            .unwrap();
+
+        let result = adapter
+            .execute_tool(
+                "sqlite_execute",
+                json!({"sql": "INSERT INTO t VALUES (1); DROP TABLE t"}),
+            )
+            .await;
+        assert!(matches!(result, Err(AdapterError::InvalidParams { .. })));
+
+        let rows = adapter
+            .execute_tool(
+                "sqlite_query",
+                json!({"sql": "SELECT count(*) AS n FROM t"}),
+            )
+            .await
+            .unwrap();
+        assert_eq!(rows, json!([{"n": 0}]));
+    }
+
+    #[tokio::test]
+    async fn legacy_query_field_is_accepted() {
+        let dir = tempfile::tempdir().unwrap();
+        let adapter =
+            connected_adapter(&dir.path().join("test.db"), SqlitePoolConfig::default()).await;
+
+        let rows = adapter
+            .execute_tool(
+                "sqlite_query",
+                json!({"query": "SELECT ? AS v", "params": ["ok"]}),
+            )
+            .await
+            .unwrap();
+        assert_eq!(rows, json!([{"v": "ok"}]));
+    }
+
+    #[tokio::test]
+    async fn describe_table_reports_columns_and_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let adapter =
+            connected_adapter(&dir.path().join("test.db"), SqlitePoolConfig::default()).await;
+        adapter
+            .execute_tool(
+                "sqlite_execute",
+                json!({"sql": "CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL UNIQUE)"}),
+            )
+            .await
+            .unwrap();
+        adapter
+            .execute_tool(
+                "sqlite_execute",
+                json!({"sql": "CREATE TABLE memberships (user_id INTEGER NOT NULL REFERENCES users(id) \
+                               ON DELETE CASCADE, team TEXT NOT NULL DEFAULT 'core', \
+                               PRIMARY KEY (team, user_id))"}),
+            )
+            .await
+            .unwrap();
+
+        let desc = adapter
+            .execute_tool("sqlite_describe_table", json!({"table": "memberships"}))
+            .await
+            .unwrap();
+        assert_eq!(desc["name"], "memberships");
+        assert_eq!(desc["primary_key"], json!(["team", "user_id"]));
+
+        let columns = desc["columns"].as_array().unwrap();
+        assert_eq!(columns.len(), 2);
+        assert_eq!(columns[0]["name"], "user_id");
+        assert_eq!(columns[0]["type"], "INTEGER");
+        assert_eq!(columns[0]["not_null"], true);
+        assert_eq!(columns[1]["default"], "'core'");
+
+        let fks = desc["foreign_keys"].as_array().unwrap();
+        assert_eq!(fks.len(), 1);
+        assert_eq!(fks[0]["column"], "user_id");
+        assert_eq!(fks[0]["references_table"], "users");
+        assert_eq!(fks[0]["references_column"], "id");
+        assert_eq!(fks[0]["on_delete"], "CASCADE");
+
+        let tables = adapter
+            .execute_tool("sqlite_list_tables", json!({}))
+            .await
+            .unwrap();
+        assert_eq!(
+            tables["tables"],
+            json!([{"name": "memberships", "type": "table"}, {"name": "users", "type": "table"}])
+        );
+
+        let indexes = adapter
+            .execute_tool("sqlite_list_indexes", json!({"table": "users"}))
+            .await
+            .unwrap();
+        let indexes = indexes["indexes"].as_array().unwrap();
+        assert_eq!(indexes.len(), 1);
+        assert_eq!(indexes[0]["unique"], true);
+        assert_eq!(indexes[0]["columns"], json!(["email"]));
+    }
+
+    #[tokio::test]
+    async fn describe_missing_table_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let adapter =
+            connected_adapter(&dir.path().join("test.db"), SqlitePoolConfig::default()).await;
+
+        let result = adapter
+            .execute_tool("sqlite_describe_table", json!({"table": "nope"}))
+            .await;
+        assert!(matches!(result, Err(AdapterError::ExecutionFailed { .. })));
+    }
+
+    #[tokio::test]
+    async fn query_tool_rejects_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let adapter =
+            connected_adapter(&dir.path().join("test.db"), SqlitePoolConfig::default()).await;
+
+        let result = adapter
+            .execute_tool(
+                "sqlite_query",
+                json!({"sql": "CREATE TABLE sneaky (id INTEGER)"}),
+            )
+            .await;
+        assert!(matches!(result, Err(AdapterError::ExecutionFailed { .. })));
+
+        // The connection is usable for writes again afterwards.
+        adapter
+            .execute_tool(
+                "sqlite_execute",
+                json!({"sql": "CREATE TABLE fine (id INTEGER)"}),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn long_running_statement_is_interrupted() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SqlitePoolConfig {
+            statement_timeout: Duration::from_millis(100),
+            ..SqlitePoolConfig::default()
+        };
+        let adapter = connected_adapter(&dir.path().join("test.db"), config).await;
+
+        let started = std::time::Instant::now();
+        let result = adapter
+            .execute_tool(
+                "sqlite_query",
+                json!({"sql": "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n) \
+                                 SELECT count(*) FROM n"}),
+            )
+            .await;
+        assert!(matches!(result, Err(AdapterError::Timeout { .. })));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn write_honors_busy_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let config = SqlitePoolConfig {
+            busy_timeout: Duration::from_millis(200),
+            ..SqlitePoolConfig::default()
+        };
+        let adapter = connected_adapter(&path, config).await;
+        adapter
+            .execute_tool(
+                "sqlite_execute",
+                json!({"sql": "CREATE TABLE t (id INTEGER)"}),
+            )
+            .await
+            .unwrap();
+
+        // Hold the write lock from an independent connection.
+        let mut locker =
+            SqliteConnection::connect_with(&SqliteConnectOptions::new().filename(&path))
+                .await
+                .unwrap();
+        sqlx::query("BEGIN EXCLUSIVE")
+            .execute(&mut locker)
+            .await
+            .unwrap();
+
+        let started = std::time::Instant::now();
+        let result = adapter
+            .execute_tool("sqlite_execute", json!({"sql": "INSERT INTO t VALUES (1)"}))
+            .await;
+        let waited = started.elapsed();
+
+        assert!(matches!(result, Err(AdapterError::ExecutionFailed { .. })));
+        assert!(
+            waited >= Duration::from_millis(150),
+            "gave up too early: {waited:?}"
+        );
+        assert!(
+            waited < Duration::from_secs(5),
+            "ignored busy_timeout: {waited:?}"
+        );
+
+        sqlx::query("ROLLBACK").execute(&mut locker).await.unwrap();
+    }
+}
@@ -1,19 +1,66 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
-use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
-use tracing::debug;
+use std::time::{Duration, Instant};
+
+use sqlx::SqliteConnection;
+use sqlx::pool::PoolConnection;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+use sqlx::{Sqlite, SqlitePool};
 
 use crate::sqlite::SqliteError;
 
+/// Number of SQLite VM instructions between statement-deadline checks.
+const PROGRESS_HANDLER_OPS: i32 = 1000;
+
+/// Tuning knobs for the per-database connection pools.
+#[derive(Debug, Clone)]
+pub struct SqlitePoolConfig {
+    /// Maximum number of open connections per database (default: 10).
+    pub max_connections: u32,
+    /// How long a checkout waits for a free connection (default: 30s).
+    pub acquire_timeout: Duration,
+    /// How long SQLite retries on a locked database before failing (default: 5s).
+    pub busy_timeout: Duration,
+    /// Maximum time a single statement may run before it is interrupted
+    /// (default: 30s).
+    pub statement_timeout: Duration,
+}
+
+impl Default for SqlitePoolConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: 10,
+            acquire_timeout: Duration::from_secs(30),
+            busy_timeout: Duration::from_secs(5),
+            statement_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
 /// SQLite connection pool manager
 pub struct SqliteConnectionPool {
     default_db_path: PathBuf,
+    config: SqlitePoolConfig,
     pools: HashMap<String, SqlitePool>,
 }
 
 impl SqliteConnectionPool {
     /// Create a new connection pool manager
     pub async fn new(default_db_path: Option<PathBuf>) -> Result<Self, SqliteError> {
+        Self::with_config(default_db_path, SqlitePoolConfig::default()).await
+    }
+
+    /// Create a new connection pool manager with explicit pool settings.
+    pub async fn with_config(
+        default_db_path: Option<PathBuf>,
+        config: SqlitePoolConfig,
+    ) -> Result<Self, SqliteError> {
+        if config.max_connections == 0 {
+            return Err(SqliteError::Configuration(
+                "max_connections must be at least 1".to_string(),
+            ));
+        }
+
         let default_path = default_db_path.unwrap_or_else(|| {
             PathBuf::from("data/openintent.db")
         });
@@ -26,10 +73,16 @@ impl SqliteConnectionPool {
 
         Ok(Self {
             default_db_path: default_path,
+            config,
             pools: HashMap::new(),
         })
     }
 
+    /// Return the pool settings applied to every database.
+    pub fn config(&self) -> &SqlitePoolConfig {
+        &self.config
+    }
+
     /// Get or create a database connection pool
     pub async fn get_or_create_database(&mut self, db_name: &str) -> Result<SqlitePool, SqliteError> {
         if let Some(pool) = self.pools.get(db_name) {
@@ -50,29 +103,39 @@ impl SqliteConnectionPool {
                 .map_err(|e| SqliteError::Connection(format!("Failed to create database directory: {}", e)))?;
         }
 
-        let database_url = format!("sqlite:{}", db_path.display());
-        
-        let pool = SqlitePoolOptions::new()
-            .max_connections(10)
-            .connect(&database_url)
-            .await
-            .map_err(|e| SqliteError::Connection(format!("Failed to connect to database {}: {}", db_name, e)))?;
-
-        // Run basic setup
-        sqlx::query("PRAGMA foreign_keys = ON")
-            .execute(&pool)
-            .await
-            .map_err(|e| SqliteError::QueryExecution(format!("Failed to enable foreign keys: {}", e)))?;
+        // Connection-level settings are applied to every connection the pool
+        // opens, not just the first one.
+        let options = SqliteConnectOptions::new()
+            .filename(&db_path)
+            .create_if_missing(true)
+            .foreign_keys(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .busy_timeout(self.config.busy_timeout);
 
-        sqlx::query("PRAGMA journal_mode = WAL")
-            .execute(&pool)
+        let pool = SqlitePoolOptions::new()
+            .max_connections(self.config.max_connections)
+            .acquire_timeout(self.config.acquire_timeout)
+            .connect_with(options)
             .await
-            .map_err(|e| SqliteError::QueryExecution(format!("Failed to set WAL mode: {}", e)))?;
+            .map_err(|e| {
+                SqliteError::Connection(format!("Failed to connect to database {}: {}", db_name, e))
+            })?;
 
         self.pools.insert(db_name.to_string(), pool.clone());
         Ok(pool)
     }
 
+    /// Check out a connection from a database pool.
+    ///
+    /// Waits up to `acquire_timeout` for a free connection when the pool is
+    /// exhausted.
+    pub async fn acquire(&self, db_name: &str) -> Result<PoolConnection<Sqlite>, SqliteError> {
+        let pool = self.pools.get(db_name).ok_or_else(|| {
+            SqliteError::Configuration(format!("Database '{}' not found", db_name))
+        })?;
+        Ok(pool.acquire().await?)
+    }
+
     /// Get an existing database pool
     pub fn get_database(&self, db_name: &str) -> Option<&SqlitePool> {
         self.pools.get(db_name)
@@ -146,4 +209,70 @@ impl SqliteConnectionPool {
     pub fn get_main_pool(&self) -> &SqlitePool {
         self.pools.get("main").expect("Main database should always be available")
     }
-}
\ No newline at end of file
+}
+
+/// Install a progress handler that interrupts any statement still running
+/// once `timeout` has elapsed.
+///
+/// Returns the deadline so callers can classify a subsequent failure as a
+/// timeout.  Pair with [`disarm_statement_deadline`].
+pub(crate) async fn arm_statement_deadline(
+    conn: &mut SqliteConnection,
+    timeout: Duration,
+) -> Result<Instant, SqliteError> {
+    let deadline = Instant::now() + timeout;
+    conn.lock_handle()
+        .await?
+        .set_progress_handler(PROGRESS_HANDLER_OPS, move || Instant::now() < deadline);
+    Ok(deadline)
+}
+
+/// Remove the progress handler installed by [`arm_statement_deadline`].
+pub(crate) async fn disarm_statement_deadline(
+    conn: &mut SqliteConnection,
+) -> Result<(), SqliteError> {
+    conn.lock_handle().await?.remove_progress_handler();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn bounded_pool_blocks_extra_checkout() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SqlitePoolConfig {
+            max_connections: 2,
+            acquire_timeout: Duration::from_millis(200),
+            ..SqlitePoolConfig::default()
+        };
+        let mut pool = SqliteConnectionPool::with_config(Some(dir.path().join("test.db")), config)
+            .await
+            .unwrap();
+        pool.get_or_create_database("main").await.unwrap();
+
+        let first = pool.acquire("main").await.unwrap();
+        let _second = pool.acquire("main").await.unwrap();
+
+        // The third checkout waits for a free slot and then gives up.
+        let third = pool.acquire("main").await;
+        assert!(matches!(third, Err(SqliteError::ConnectionPool(_))));
+
+        // Returning a connection frees a slot.
+        drop(first);
+        assert!(pool.acquire("main").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn zero_max_connections_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SqlitePoolConfig {
+            max_connections: 0,
+            ..SqlitePoolConfig::default()
+        };
+        let result =
+            SqliteConnectionPool::with_config(Some(dir.path().join("test.db")), config).await;
+        assert!(matches!(result, Err(SqliteError::Configuration(_))));
+    }
+}
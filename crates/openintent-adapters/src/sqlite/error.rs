@@ -1,5 +1,7 @@
 //! Error types for SQLite adapter.
 
+use std::time::Duration;
+
 use thiserror::Error;
 
 /// Errors that can occur when using the SQLite adapter.
@@ -40,17 +42,18 @@ pub enum SqliteError {
     /// Schema error.
     #[error("schema error: {0}")]
     Schema(String),
+
+    /// A statement exceeded the configured statement timeout and was
+    /// interrupted.
+    #[error("statement timed out after {}ms", .0.as_millis())]
+    Timeout(Duration),
 }
 
 impl From<sqlx::Error> for SqliteError {
     fn from(err: sqlx::Error) -> Self {
         match err {
-            sqlx::Error::Database(db_err) => {
-                SqliteError::QueryExecution(db_err.to_string())
-            }
-            sqlx::Error::Io(io_err) => {
-                SqliteError::Connection(io_err.to_string())
-            }
+            sqlx::Error::Database(db_err) => SqliteError::QueryExecution(db_err.to_string()),
+            sqlx::Error::Io(io_err) => SqliteError::Connection(io_err.to_string()),
             sqlx::Error::PoolClosed => {
                 SqliteError::ConnectionPool("connection pool is closed".to_string())
             }
@@ -69,4 +72,4 @@ impl From<serde_json::Error> for SqliteError {
 }
 
 /// Result type for SQLite operations.
-pub type SqliteResult<T> = Result<T, SqliteError>;
\ No newline at end of file
+pub type SqliteResult<T> = Result<T, SqliteError>;
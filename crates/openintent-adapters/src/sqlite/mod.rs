@@ -1,13 +1,15 @@
 //! SQLite adapter for OpenIntentOS
-//! 
+//!
 //! Provides structured data storage capabilities using SQLite databases.
 
 pub mod adapter;
 pub mod connection;
 pub mod error;
 pub mod migration;
+pub mod schema;
+pub mod statement;
 
 pub use adapter::SqliteAdapter;
-pub use connection::SqliteConnectionPool;
+pub use connection::{SqliteConnectionPool, SqlitePoolConfig};
 pub use error::{SqliteError, SqliteResult};
-pub use migration::MigrationManager;
\ No newline at end of file
+pub use migration::MigrationManager;
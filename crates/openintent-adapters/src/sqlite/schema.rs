@@ -0,0 +1,136 @@
+//! Schema introspection for SQLite databases.
+//!
+//! Reads `sqlite_master` and the `pragma_*` table-valued functions so the
+//! agent can discover tables, columns, keys, and indexes before writing SQL.
+//! Table names are bound as parameters rather than spliced into the text.
+
+use serde_json::{Value, json};
+use sqlx::{Row, SqlitePool};
+
+use crate::sqlite::{SqliteError, SqliteResult};
+
+/// List user tables and views, excluding SQLite's internal tables.
+pub async fn list_tables(pool: &SqlitePool) -> SqliteResult<Value> {
+    let rows = sqlx::query(
+        "SELECT name, type FROM sqlite_master \
+         WHERE type IN ('table', 'view') AND name NOT LIKE 'sqlite_%' \
+         ORDER BY name",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let tables: Vec<Value> = rows
+        .iter()
+        .map(|row| {
+            json!({
+                "name": row.get::<String, _>("name"),
+                "type": row.get::<String, _>("type"),
+            })
+        })
+        .collect();
+
+    Ok(json!({ "tables": tables }))
+}
+
+/// Describe a table's columns, primary key, and foreign keys.
+pub async fn describe_table(pool: &SqlitePool, table: &str) -> SqliteResult<Value> {
+    let column_rows = sqlx::query(
+        "SELECT name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?) ORDER BY cid",
+    )
+    .bind(table)
+    .fetch_all(pool)
+    .await?;
+
+    if column_rows.is_empty() {
+        return Err(SqliteError::Schema(format!("table '{table}' not found")));
+    }
+
+    let mut primary_key: Vec<(i64, String)> = Vec::new();
+    let columns: Vec<Value> = column_rows
+        .iter()
+        .map(|row| {
+            let name: String = row.get("name");
+            let pk: i64 = row.get("pk");
+            if pk > 0 {
+                primary_key.push((pk, name.clone()));
+            }
+            json!({
+                "name": name,
+                "type": row.get::<String, _>("type"),
+                "not_null": row.get::<i64, _>("notnull") != 0,
+                "default": row.get::<Option<String>, _>("dflt_value"),
+                "primary_key": pk > 0,
+            })
+        })
+        .collect();
+    primary_key.sort_by_key(|(position, _)| *position);
+
+    let fk_rows = sqlx::query(
+        "SELECT id, seq, \"table\", \"from\", \"to\", on_update, on_delete \
+         FROM pragma_foreign_key_list(?) ORDER BY id, seq",
+    )
+    .bind(table)
+    .fetch_all(pool)
+    .await?;
+
+    let foreign_keys: Vec<Value> = fk_rows
+        .iter()
+        .map(|row| {
+            json!({
+                "id": row.get::<i64, _>("id"),
+                "column": row.get::<String, _>("from"),
+                "references_table": row.get::<String, _>("table"),
+                "references_column": row.get::<Option<String>, _>("to"),
+                "on_update": row.get::<String, _>("on_update"),
+                "on_delete": row.get::<String, _>("on_delete"),
+            })
+        })
+        .collect();
+
+    Ok(json!({
+        "name": table,
+        "columns": columns,
+        "primary_key": primary_key.into_iter().map(|(_, name)| name).collect::<Vec<_>>(),
+        "foreign_keys": foreign_keys,
+    }))
+}
+
+/// List the indexes on a table together with their indexed columns.
+pub async fn list_indexes(pool: &SqlitePool, table: &str) -> SqliteResult<Value> {
+    let exists = sqlx::query("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?")
+        .bind(table)
+        .fetch_optional(pool)
+        .await?
+        .is_some();
+    if !exists {
+        return Err(SqliteError::Schema(format!("table '{table}' not found")));
+    }
+
+    let index_rows = sqlx::query(
+        "SELECT name, \"unique\", origin, partial FROM pragma_index_list(?) ORDER BY seq",
+    )
+    .bind(table)
+    .fetch_all(pool)
+    .await?;
+
+    let mut indexes = Vec::with_capacity(index_rows.len());
+    for row in &index_rows {
+        let name: String = row.get("name");
+        let columns: Vec<Option<String>> =
+            sqlx::query_scalar("SELECT name FROM pragma_index_info(?) ORDER BY seqno")
+                .bind(&name)
+                .fetch_all(pool)
+                .await?;
+
+        indexes.push(json!({
+            "name": name,
+            "unique": row.get::<i64, _>("unique") != 0,
+            // "c" = CREATE INDEX, "u" = UNIQUE constraint, "pk" = PRIMARY KEY.
+            "origin": row.get::<String, _>("origin"),
+            "partial": row.get::<i64, _>("partial") != 0,
+            "columns": columns,
+        }));
+    }
+
+    Ok(json!({ "table": table, "indexes": indexes }))
+}
@@ -0,0 +1,170 @@
+//! Static checks applied to SQL text before it reaches SQLite.
+//!
+//! The agent passes SQL and its values separately; these checks make sure a
+//! tool call carries exactly one statement so that a value smuggled into the
+//! SQL text cannot append a second one.
+
+use crate::sqlite::SqliteError;
+
+/// Lexer state while scanning SQL text.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    Normal,
+    SingleQuoted,
+    DoubleQuoted,
+    Backticked,
+    Bracketed,
+    LineComment,
+    BlockComment,
+}
+
+/// Reject SQL text that is empty or contains more than one statement.
+///
+/// String literals, quoted identifiers, and comments are skipped, so a `;`
+/// inside them does not count.  A single trailing `;` is allowed.  Semicolons
+/// inside the `BEGIN ... END` body of a `CREATE TRIGGER` statement belong to
+/// that statement and are allowed as well.
+pub fn ensure_single_statement(sql: &str) -> Result<(), SqliteError> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut state = State::Normal;
+    let mut words: Vec<String> = Vec::new();
+    let mut word = String::new();
+    let mut depth: usize = 0;
+    let mut terminated = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        match state {
+            State::Normal => {
+                if c.is_alphanumeric() || c == '_' {
+                    if terminated {
+                        return Err(multiple_statements());
+                    }
+                    word.push(c);
+                    i += 1;
+                    continue;
+                }
+                flush_word(&mut word, &mut words, &mut depth);
+
+                match (c, next) {
+                    ('-', Some('-')) => {
+                        state = State::LineComment;
+                        i += 1;
+                    }
+                    ('/', Some('*')) => {
+                        state = State::BlockComment;
+                        i += 1;
+                    }
+                    (';', _) if depth > 0 && is_trigger(&words) => {}
+                    (';', _) => terminated = true,
+                    _ if c.is_whitespace() => {}
+                    _ if terminated => return Err(multiple_statements()),
+                    ('\'', _) => state = State::SingleQuoted,
+                    ('"', _) => state = State::DoubleQuoted,
+                    ('`', _) => state = State::Backticked,
+                    ('[', _) => state = State::Bracketed,
+                    _ => {}
+                }
+            }
+            // Doubled quotes are escapes and simply re-enter the literal.
+            State::SingleQuoted if c == '\'' => state = State::Normal,
+            State::DoubleQuoted if c == '"' => state = State::Normal,
+            State::Backticked if c == '`' => state = State::Normal,
+            State::Bracketed if c == ']' => state = State::Normal,
+            State::LineComment if c == '\n' => state = State::Normal,
+            State::BlockComment if c == '*' && next == Some('/') => {
+                state = State::Normal;
+                i += 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    flush_word(&mut word, &mut words, &mut depth);
+
+    if words.is_empty() {
+        return Err(SqliteError::InvalidParameter(
+            "SQL text contains no statement".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Record a completed keyword or identifier and track trigger-body nesting.
+fn flush_word(word: &mut String, words: &mut Vec<String>, depth: &mut usize) {
+    if word.is_empty() {
+        return;
+    }
+    let upper = word.to_ascii_uppercase();
+    word.clear();
+
+    if is_trigger(words) {
+        match upper.as_str() {
+            "BEGIN" | "CASE" => *depth += 1,
+            "END" => *depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    words.push(upper);
+}
+
+/// Whether the statement scanned so far is a `CREATE [TEMP] TRIGGER`.
+fn is_trigger(words: &[String]) -> bool {
+    match words {
+        [create, trigger, ..] if create == "CREATE" && trigger == "TRIGGER" => true,
+        [create, temp, trigger, ..] => {
+            create == "CREATE" && (temp == "TEMP" || temp == "TEMPORARY") && trigger == "TRIGGER"
+        }
+        _ => false,
+    }
+}
+
+fn multiple_statements() -> SqliteError {
+    SqliteError::InvalidParameter(
+        "SQL text contains multiple statements; send one statement per call and pass \
+         values through `params`"
+            .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_statements_pass() {
+        assert!(ensure_single_statement("SELECT 1").is_ok());
+        assert!(ensure_single_statement("SELECT 1;").is_ok());
+        assert!(ensure_single_statement("SELECT 1; -- trailing comment").is_ok());
+        assert!(ensure_single_statement("INSERT INTO t VALUES ('a;b')").is_ok());
+        assert!(ensure_single_statement("SELECT \"odd;name\" FROM [x;y]").is_ok());
+        assert!(ensure_single_statement("SELECT 'it''s; fine'").is_ok());
+        assert!(ensure_single_statement("SELECT 1 /* ; */").is_ok());
+    }
+
+    #[test]
+    fn multiple_statements_are_rejected() {
+        assert!(ensure_single_statement("SELECT 1; SELECT 2").is_err());
+        assert!(ensure_single_statement("DELETE FROM t;DROP TABLE t;").is_err());
+        assert!(ensure_single_statement("SELECT 'x'; -- hi\n DROP TABLE t").is_err());
+    }
+
+    #[test]
+    fn empty_text_is_rejected() {
+        assert!(ensure_single_statement("").is_err());
+        assert!(ensure_single_statement("  ;  -- nothing").is_err());
+    }
+
+    #[test]
+    fn trigger_bodies_are_one_statement() {
+        let sql = "CREATE TRIGGER trg AFTER INSERT ON t BEGIN \
+                   UPDATE t SET n = CASE WHEN n > 1 THEN 1 ELSE 0 END; \
+                   DELETE FROM u; \
+                   END;";
+        assert!(ensure_single_statement(sql).is_ok());
+        assert!(ensure_single_statement(&format!("{sql} SELECT 1")).is_err());
+    }
+}
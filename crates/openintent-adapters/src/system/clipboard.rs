@@ -0,0 +1,158 @@
+//! Text clipboard access through the platform's clipboard utilities.
+//!
+//! | Platform | Read | Write |
+//! |----------|------|-------|
+//! | macOS    | `pbpaste` | `pbcopy` |
+//! | Windows  | `Get-Clipboard` | `Set-Clipboard` (PowerShell) |
+//! | Wayland  | `wl-paste` | `wl-copy` |
+//! | X11      | `xclip` or `xsel` | `xclip` or `xsel` |
+//!
+//! Text is passed on stdin/stdout, never on a command line.
+
+use std::process::Stdio;
+use std::time::Duration;
+
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// How long a clipboard utility may run.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A program and its arguments.
+pub type CommandLine = Vec<String>;
+
+/// The commands used to read and write the clipboard.
+#[derive(Debug, Clone)]
+pub struct Clipboard {
+    /// Prints the clipboard text on stdout.
+    read: CommandLine,
+    /// Replaces the clipboard with the text on stdin.
+    write: CommandLine,
+}
+
+impl Clipboard {
+    /// Use custom read and write commands.
+    pub fn new(read: CommandLine, write: CommandLine) -> Self {
+        Self { read, write }
+    }
+
+    /// Find the clipboard utilities for the current desktop session, or
+    /// `None` when there is no session (e.g. on a server).
+    pub fn detect() -> Option<Self> {
+        let argv = |args: &[&str]| -> CommandLine { args.iter().map(|a| a.to_string()).collect() };
+
+        if cfg!(target_os = "macos") {
+            return Some(Self::new(argv(&["pbpaste"]), argv(&["pbcopy"])));
+        }
+        if cfg!(windows) {
+            return Some(Self::new(
+                argv(&[
+                    "powershell",
+                    "-NoProfile",
+                    "-Command",
+                    "[Console]::OutputEncoding = [Text.Encoding]::UTF8; Get-Clipboard -Raw",
+                ]),
+                argv(&[
+                    "powershell",
+                    "-NoProfile",
+                    "-Command",
+                    "[Console]::InputEncoding = [Text.Encoding]::UTF8; \
+                     Set-Clipboard -Value ([Console]::In.ReadToEnd())",
+                ]),
+            ));
+        }
+
+        let has_env = |name: &str| std::env::var_os(name).is_some_and(|v| !v.is_empty());
+        if has_env("WAYLAND_DISPLAY") && super::on_path("wl-copy") && super::on_path("wl-paste") {
+            return Some(Self::new(
+                argv(&["wl-paste", "--no-newline"]),
+                argv(&["wl-copy"]),
+            ));
+        }
+        if has_env("DISPLAY") {
+            if super::on_path("xclip") {
+                return Some(Self::new(
+                    argv(&["xclip", "-selection", "clipboard", "-out"]),
+                    argv(&["xclip", "-selection", "clipboard", "-in"]),
+                ));
+            }
+            if super::on_path("xsel") {
+                return Some(Self::new(
+                    argv(&["xsel", "--clipboard", "--output"]),
+                    argv(&["xsel", "--clipboard", "--input"]),
+                ));
+            }
+        }
+        None
+    }
+
+    /// Read the clipboard as text.
+    pub async fn read_text(&self) -> std::io::Result<String> {
+        let mut command = command(&self.read)?;
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        let output = tokio::time::timeout(COMMAND_TIMEOUT, command.output())
+            .await
+            .map_err(|_| timed_out(&self.read))??;
+        if !output.status.success() {
+            return Err(failed(&self.read, &output.stderr));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Replace the clipboard contents with `text`.
+    pub async fn write_text(&self, text: &str) -> std::io::Result<()> {
+        let mut command = command(&self.write)?;
+        // X11 and Wayland utilities fork a child that keeps serving the
+        // selection; it must not hold our pipes open.
+        command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .kill_on_drop(true);
+        let mut child = command.spawn()?;
+        let mut stdin = child.stdin.take().ok_or_else(|| {
+            std::io::Error::other(format!("`{}` has no stdin", self.write.join(" ")))
+        })?;
+        let write = async {
+            stdin.write_all(text.as_bytes()).await?;
+            drop(stdin);
+            child.wait().await
+        };
+        let status = tokio::time::timeout(COMMAND_TIMEOUT, write)
+            .await
+            .map_err(|_| timed_out(&self.write))??;
+        if !status.success() {
+            return Err(failed(&self.write, b""));
+        }
+        Ok(())
+    }
+}
+
+/// Build a [`Command`] from a non-empty command line.
+fn command(argv: &[String]) -> std::io::Result<Command> {
+    let (program, args) = argv.split_first().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, "empty clipboard command")
+    })?;
+    let mut command = Command::new(program);
+    command.args(args);
+    Ok(command)
+}
+
+fn timed_out(argv: &[String]) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::TimedOut,
+        format!("`{}` did not finish", argv.join(" ")),
+    )
+}
+
+fn failed(argv: &[String], stderr: &[u8]) -> std::io::Error {
+    std::io::Error::other(format!(
+        "`{}` failed: {}",
+        argv.join(" "),
+        String::from_utf8_lossy(stderr).trim()
+    ))
+}
@@ -0,0 +1,381 @@
+//! Desktop system adapter -- clipboard and native notifications.
+//!
+//! Lets the agent hand results to other applications by placing text on
+//! the clipboard, read what the user copied, and raise a desktop
+//! notification when a long task finishes.  Three tools are exposed:
+//!
+//! - `clipboard_read` -- return the clipboard text.
+//! - `clipboard_write` -- replace the clipboard text.
+//! - `notify` -- show a notification with a title and body.
+//!
+//! The adapter is only compiled with the `desktop` feature, so server builds
+//! never expose it.  At connect time it looks for the platform's clipboard
+//! and notification utilities (see [`Clipboard::detect`] and
+//! [`Notifier::detect`]) and fails when there is no desktop session.
+
+pub mod clipboard;
+pub mod notification;
+
+use std::path::Path;
+
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use tracing::{debug, info, warn};
+
+use crate::error::{AdapterError, Result};
+use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
+
+pub use clipboard::Clipboard;
+pub use notification::Notifier;
+
+/// Longest clipboard text returned by `clipboard_read`, in bytes.
+const MAX_READ_BYTES: usize = 64 * 1024;
+
+/// Adapter exposing the desktop clipboard and notifications as tools.
+pub struct SystemAdapter {
+    /// Unique adapter instance identifier.
+    id: String,
+    /// Whether the adapter has been connected.
+    connected: bool,
+    /// Clipboard commands; detected on connect when not set.
+    clipboard: Option<Clipboard>,
+    /// Notification backend; detected on connect when not set.
+    notifier: Option<Notifier>,
+}
+
+impl SystemAdapter {
+    /// Create a new system adapter that detects the platform's clipboard
+    /// and notification utilities on connect.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            connected: false,
+            clipboard: None,
+            notifier: None,
+        }
+    }
+
+    /// Use `clipboard` instead of the detected clipboard utilities.
+    pub fn with_clipboard(mut self, clipboard: Clipboard) -> Self {
+        self.clipboard = Some(clipboard);
+        self
+    }
+
+    /// Use `notifier` instead of the detected notification backend.
+    pub fn with_notifier(mut self, notifier: Notifier) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
+    /// Extract a required string field from JSON params.
+    fn require_str<'a>(params: &'a Value, field: &str, tool_name: &str) -> Result<&'a str> {
+        params
+            .get(field)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| AdapterError::InvalidParams {
+                tool_name: tool_name.to_string(),
+                reason: format!("missing required string field `{field}`"),
+            })
+    }
+
+    fn unavailable(tool_name: &str, what: &str) -> AdapterError {
+        AdapterError::ExecutionFailed {
+            tool_name: tool_name.to_string(),
+            reason: format!("no {what} is available in this desktop session"),
+        }
+    }
+
+    // -- Tool implementations ------------------------------------------------
+
+    /// Return the clipboard text.
+    async fn tool_clipboard_read(&self) -> Result<Value> {
+        let clipboard = self
+            .clipboard
+            .as_ref()
+            .ok_or_else(|| Self::unavailable("clipboard_read", "clipboard"))?;
+        let mut text = clipboard
+            .read_text()
+            .await
+            .map_err(|e| AdapterError::ExecutionFailed {
+                tool_name: "clipboard_read".to_string(),
+                reason: format!("failed to read the clipboard: {e}"),
+            })?;
+
+        let truncated = text.len() > MAX_READ_BYTES;
+        if truncated {
+            let mut end = MAX_READ_BYTES;
+            while !text.is_char_boundary(end) {
+                end -= 1;
+            }
+            text.truncate(end);
+        }
+        Ok(json!({ "text": text, "truncated": truncated }))
+    }
+
+    /// Replace the clipboard text.
+    async fn tool_clipboard_write(&self, params: Value) -> Result<Value> {
+        let text = Self::require_str(&params, "text", "clipboard_write")?;
+        let clipboard = self
+            .clipboard
+            .as_ref()
+            .ok_or_else(|| Self::unavailable("clipboard_write", "clipboard"))?;
+
+        debug!(bytes = text.len(), "writing clipboard");
+        clipboard
+            .write_text(text)
+            .await
+            .map_err(|e| AdapterError::ExecutionFailed {
+                tool_name: "clipboard_write".to_string(),
+                reason: format!("failed to write the clipboard: {e}"),
+            })?;
+        Ok(json!({ "written": true, "chars": text.chars().count() }))
+    }
+
+    /// Show a desktop notification.
+    async fn tool_notify(&self, params: Value) -> Result<Value> {
+        let title = Self::require_str(&params, "title", "notify")?;
+        let body = params.get("body").and_then(|v| v.as_str()).unwrap_or("");
+        let notifier = self
+            .notifier
+            .as_ref()
+            .ok_or_else(|| Self::unavailable("notify", "notification service"))?;
+
+        notifier
+            .send(title, body)
+            .await
+            .map_err(|e| AdapterError::ExecutionFailed {
+                tool_name: "notify".to_string(),
+                reason: format!("failed to show notification: {e}"),
+            })?;
+        Ok(json!({ "sent": true }))
+    }
+}
+
+/// Whether an executable named `name` is on `PATH`.
+fn on_path(name: &str) -> bool {
+    std::env::var_os("PATH").is_some_and(|paths| {
+        std::env::split_paths(&paths).any(|dir| is_executable(&dir.join(name)))
+    })
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[async_trait]
+impl Adapter for SystemAdapter {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        AdapterType::System
+    }
+
+    async fn connect(&mut self) -> Result<()> {
+        if self.clipboard.is_none() {
+            self.clipboard = Clipboard::detect();
+        }
+        if self.notifier.is_none() {
+            self.notifier = Notifier::detect();
+        }
+        match (&self.clipboard, &self.notifier) {
+            (None, None) => {
+                return Err(AdapterError::NotConnected {
+                    adapter_id: self.id.clone(),
+                    reason: "no desktop session: clipboard and notification utilities not found"
+                        .to_string(),
+                });
+            }
+            (None, _) => {
+                warn!(id = %self.id, "no clipboard utility found; clipboard tools disabled")
+            }
+            (_, None) => warn!(id = %self.id, "no notification service found; notify disabled"),
+            _ => {}
+        }
+        info!(id = %self.id, "system adapter connected");
+        self.connected = true;
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        info!(id = %self.id, "system adapter disconnected");
+        self.connected = false;
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        if !self.connected {
+            return Ok(HealthStatus::Unhealthy);
+        }
+        if self.clipboard.is_some() && self.notifier.is_some() {
+            Ok(HealthStatus::Healthy)
+        } else {
+            Ok(HealthStatus::Degraded)
+        }
+    }
+
+    fn tools(&self) -> Vec<ToolDefinition> {
+        vec![
+            ToolDefinition {
+                name: "clipboard_read".into(),
+                description: "Read the text currently on the user's clipboard".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {}
+                }),
+                destructive: false,
+            },
+            ToolDefinition {
+                name: "clipboard_write".into(),
+                description:
+                    "Put text on the user's clipboard so they can paste it into another app".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "text": {
+                            "type": "string",
+                            "description": "Text to place on the clipboard"
+                        }
+                    },
+                    "required": ["text"]
+                }),
+                destructive: true,
+            },
+            ToolDefinition {
+                name: "notify".into(),
+                description: "Show a desktop notification to the user".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "title": {
+                            "type": "string",
+                            "description": "Notification title"
+                        },
+                        "body": {
+                            "type": "string",
+                            "description": "Notification text"
+                        }
+                    },
+                    "required": ["title"]
+                }),
+                destructive: false,
+            },
+        ]
+    }
+
+    async fn execute_tool(&self, name: &str, params: Value) -> Result<Value> {
+        if !self.connected {
+            return Err(AdapterError::ExecutionFailed {
+                tool_name: name.to_string(),
+                reason: format!("adapter `{}` is not connected", self.id),
+            });
+        }
+        match name {
+            "clipboard_read" => self.tool_clipboard_read().await,
+            "clipboard_write" => self.tool_clipboard_write(params).await,
+            "notify" => self.tool_notify(params).await,
+            _ => Err(AdapterError::ToolNotFound {
+                adapter_id: self.id.clone(),
+                tool_name: name.to_string(),
+            }),
+        }
+    }
+
+    fn required_auth(&self) -> Option<AuthRequirement> {
+        None
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The OS clipboard round-trips a string.  Skipped when the test runs
+    /// outside a desktop session.
+    #[tokio::test]
+    async fn os_clipboard_round_trips() {
+        let Some(clipboard) = Clipboard::detect() else {
+            eprintln!("skipping: no desktop clipboard available");
+            return;
+        };
+        let text = format!("OpenIntentOS clipboard test {}", std::process::id());
+        clipboard.write_text(&text).await.unwrap();
+        assert_eq!(clipboard.read_text().await.unwrap(), text);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn clipboard_tools_round_trip_through_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("clipboard").display().to_string();
+        let sh = |script: &str| -> Vec<String> {
+            vec![
+                "sh".into(),
+                "-c".into(),
+                script.into(),
+                "sh".into(),
+                path.clone(),
+            ]
+        };
+        let mut adapter = SystemAdapter::new("system")
+            .with_clipboard(Clipboard::new(sh(r#"cat "$1""#), sh(r#"cat > "$1""#)))
+            .with_notifier(Notifier::Command(vec!["true".into()]));
+        adapter.connect().await.unwrap();
+
+        let text = "héllo \"world\"; $(rm -rf /)\n";
+        let written = adapter
+            .execute_tool("clipboard_write", json!({ "text": text }))
+            .await
+            .unwrap();
+        assert_eq!(written["written"], true);
+
+        let read = adapter
+            .execute_tool("clipboard_read", json!({}))
+            .await
+            .unwrap();
+        assert_eq!(read["text"], text);
+        assert_eq!(read["truncated"], false);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn notify_passes_title_and_body_as_arguments() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notification");
+        let script = format!(r#"printf '%s|%s' "$1" "$2" > '{}'"#, path.display());
+        let mut adapter = SystemAdapter::new("system")
+            .with_clipboard(Clipboard::new(vec!["true".into()], vec!["true".into()]))
+            .with_notifier(Notifier::Command(vec![
+                "sh".into(),
+                "-c".into(),
+                script,
+                "sh".into(),
+            ]));
+        adapter.connect().await.unwrap();
+
+        adapter
+            .execute_tool(
+                "notify",
+                json!({ "title": "Build done", "body": "All 42 tests passed; `ok`" }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "Build done|All 42 tests passed; `ok`"
+        );
+    }
+}
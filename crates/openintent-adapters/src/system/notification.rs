@@ -0,0 +1,135 @@
+//! Native desktop notifications.
+//!
+//! Uses `notify-send` on Linux and BSD desktops, AppleScript on macOS, and a
+//! PowerShell toast on Windows.  Title and body travel as separate
+//! arguments or environment variables, so their contents are never parsed
+//! as script.
+
+use std::process::Stdio;
+use std::time::Duration;
+
+use tokio::process::Command;
+
+/// How long the notification command may run.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Application name shown with notifications.
+const APP_NAME: &str = "OpenIntentOS";
+
+/// AppleScript taking the title and body as `argv`.
+const APPLESCRIPT: &[&str] = &[
+    "on run argv",
+    "display notification (item 2 of argv) with title (item 1 of argv)",
+    "end run",
+];
+
+/// PowerShell toast reading the title and body from the environment.
+const POWERSHELL_TOAST: &str = "\
+[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] > $null; \
+$xml = [Windows.UI.Notifications.ToastNotificationManager]::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02); \
+$text = $xml.GetElementsByTagName('text'); \
+$text.Item(0).AppendChild($xml.CreateTextNode($env:OPENINTENT_NOTIFY_TITLE)) > $null; \
+$text.Item(1).AppendChild($xml.CreateTextNode($env:OPENINTENT_NOTIFY_BODY)) > $null; \
+[Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('OpenIntentOS').Show([Windows.UI.Notifications.ToastNotification]::new($xml))";
+
+/// How notifications are delivered.
+#[derive(Debug, Clone)]
+pub enum Notifier {
+    /// freedesktop.org notifications via `notify-send`.
+    NotifySend,
+    /// macOS Notification Center via `osascript`.
+    AppleScript,
+    /// Windows toast via PowerShell.
+    PowerShell,
+    /// A custom program, called with the title and body appended to `args`.
+    Command(Vec<String>),
+}
+
+impl Notifier {
+    /// Pick the notifier for the current desktop session, or `None` when
+    /// there is no session (e.g. on a server).
+    pub fn detect() -> Option<Self> {
+        if cfg!(target_os = "macos") {
+            return Some(Self::AppleScript);
+        }
+        if cfg!(windows) {
+            return Some(Self::PowerShell);
+        }
+        let session = ["WAYLAND_DISPLAY", "DISPLAY", "DBUS_SESSION_BUS_ADDRESS"]
+            .iter()
+            .any(|name| std::env::var_os(name).is_some_and(|v| !v.is_empty()));
+        (session && super::on_path("notify-send")).then_some(Self::NotifySend)
+    }
+
+    /// Show a notification.
+    pub async fn send(&self, title: &str, body: &str) -> std::io::Result<()> {
+        let mut command = self.command(title, body)?;
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        let output = tokio::time::timeout(COMMAND_TIMEOUT, command.output())
+            .await
+            .map_err(|_| {
+                std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "notification command did not finish",
+                )
+            })??;
+        if !output.status.success() {
+            return Err(std::io::Error::other(format!(
+                "notification command failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(())
+    }
+
+    fn command(&self, title: &str, body: &str) -> std::io::Result<Command> {
+        let command = match self {
+            Self::NotifySend => {
+                let mut command = Command::new("notify-send");
+                command
+                    .arg(format!("--app-name={APP_NAME}"))
+                    .arg("--")
+                    .arg(title)
+                    .arg(body);
+                command
+            }
+            Self::AppleScript => {
+                let mut command = Command::new("osascript");
+                for line in APPLESCRIPT {
+                    command.arg("-e").arg(line);
+                }
+                command.arg(title).arg(body);
+                command
+            }
+            Self::PowerShell => {
+                let mut command = Command::new("powershell");
+                command
+                    .args([
+                        "-NoProfile",
+                        "-NonInteractive",
+                        "-Command",
+                        POWERSHELL_TOAST,
+                    ])
+                    .env("OPENINTENT_NOTIFY_TITLE", title)
+                    .env("OPENINTENT_NOTIFY_BODY", body);
+                command
+            }
+            Self::Command(argv) => {
+                let (program, args) = argv.split_first().ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "empty notification command",
+                    )
+                })?;
+                let mut command = Command::new(program);
+                command.args(args).arg(title).arg(body);
+                command
+            }
+        };
+        Ok(command)
+    }
+}
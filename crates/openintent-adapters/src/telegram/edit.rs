@@ -0,0 +1,144 @@
+//! Editing sent messages, and pointing the adapter at another Bot API
+//! server.
+//!
+//! The OAuth flow in [`crate::telegram_oauth`] uses these to replace a
+//! device code it showed in chat once the code has lapsed.
+
+use serde_json::{Value, json};
+use tracing::debug;
+
+use super::TelegramAdapter;
+use crate::error::{AdapterError, Result};
+
+impl TelegramAdapter {
+    /// Builder method to use a self-hosted Bot API server instead of
+    /// `api.telegram.org`.  `base` is the URL prefix up to and including
+    /// `/bot`, e.g. `http://localhost:8081/bot`.
+    pub fn with_api_base(mut self, base: impl Into<String>) -> Self {
+        self.api_base = base.into();
+        self
+    }
+
+    /// Replace the text of a message previously sent by the bot.
+    pub async fn edit_message_text(
+        &self,
+        chat_id: &str,
+        message_id: i64,
+        text: &str,
+        parse_mode: Option<&str>,
+    ) -> Result<Value> {
+        let url = self.api_url("editMessageText")?;
+        let mut body = json!({
+            "chat_id": chat_id,
+            "message_id": message_id,
+            "text": text,
+        });
+        if let Some(mode) = parse_mode {
+            body["parse_mode"] = json!(mode);
+        }
+
+        debug!(chat_id = %chat_id, message_id, "editing Telegram message");
+
+        let response = self.http.post(&url).json(&body).send().await.map_err(|e| {
+            AdapterError::ExecutionFailed {
+                tool_name: "telegram_edit_message".into(),
+                reason: format!("failed to edit message: {e}"),
+            }
+        })?;
+        let json_resp: Value =
+            response
+                .json()
+                .await
+                .map_err(|e| AdapterError::ExecutionFailed {
+                    tool_name: "telegram_edit_message".into(),
+                    reason: format!("failed to parse response: {e}"),
+                })?;
+
+        Self::parse_telegram_response(&json_resp, "telegram_edit_message")?;
+        Ok(json_resp.get("result").cloned().unwrap_or(json!({})))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+
+    /// Answer one Bot API call with `reply`; returns the request line and
+    /// JSON body it received.
+    async fn serve_once(listener: tokio::net::TcpListener, reply: &'static str) -> (String, Value) {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 4096];
+        let (head, body) = loop {
+            let n = stream.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&request).to_string();
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let len = head
+                    .lines()
+                    .find_map(|l| {
+                        l.to_lowercase()
+                            .strip_prefix("content-length:")
+                            .map(|v| v.trim().parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                if body.len() >= len {
+                    break (head.to_string(), body.to_string());
+                }
+            }
+        };
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            reply.len(),
+            reply
+        );
+        stream.write_all(response.as_bytes()).await.unwrap();
+        let request_line = head.lines().next().unwrap_or_default().to_string();
+        (request_line, serde_json::from_str(&body).unwrap())
+    }
+
+    async fn adapter_with_mock(
+        reply: &'static str,
+    ) -> (TelegramAdapter, tokio::task::JoinHandle<(String, Value)>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}/bot", listener.local_addr().unwrap());
+        let server = tokio::spawn(serve_once(listener, reply));
+        (
+            TelegramAdapter::with_token("telegram", "TOKEN").with_api_base(base),
+            server,
+        )
+    }
+
+    #[tokio::test]
+    async fn edit_message_text_posts_to_api_base() {
+        let (adapter, server) = adapter_with_mock(r#"{"ok":true,"result":{"message_id":7}}"#).await;
+
+        let result = adapter
+            .edit_message_text("chat-1", 7, "*Expired*", Some("Markdown"))
+            .await
+            .unwrap();
+        assert_eq!(result["message_id"], 7);
+
+        let (request_line, body) = server.await.unwrap();
+        assert!(request_line.starts_with("POST /botTOKEN/editMessageText "));
+        assert_eq!(body["chat_id"], "chat-1");
+        assert_eq!(body["message_id"], 7);
+        assert_eq!(body["parse_mode"], "Markdown");
+    }
+
+    #[tokio::test]
+    async fn edit_message_text_reports_api_errors() {
+        let (adapter, _server) = adapter_with_mock(
+            r#"{"ok":false,"error_code":400,"description":"message is not modified"}"#,
+        )
+        .await;
+
+        let err = adapter
+            .edit_message_text("chat-1", 7, "same text", None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("message is not modified"));
+    }
+}
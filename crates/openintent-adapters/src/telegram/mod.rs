@@ -12,11 +12,17 @@
 //! - `telegram_get_chat` — Get information about a chat
 //! - `telegram_set_webhook` — Set a webhook URL for push-based updates
 
+mod edit;
+#[cfg(test)]
+mod tests;
+
 use async_trait::async_trait;
 use serde_json::{Value, json};
 use tracing::{debug, info, warn};
 
+use crate::credentials;
 use crate::error::{AdapterError, Result};
+use crate::http_client::{self, HttpClientFactory};
 use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
 
 /// Telegram Bot API base URL.  All method calls are POSTed to
@@ -37,21 +43,21 @@ pub struct TelegramAdapter {
     bot_token: Option<String>,
     /// HTTP client for making requests.
     http: reqwest::Client,
+    /// Bot API base URL; the bot token and method name are appended.
+    api_base: String,
 }
 
 impl TelegramAdapter {
     /// Create a new Telegram adapter with default configuration and no token.
     pub fn new(id: impl Into<String>) -> Self {
-        let http = reqwest::Client::builder()
-            .user_agent("OpenIntentOS/0.1")
-            .build()
-            .unwrap_or_default();
+        let http = HttpClientFactory::default().build().unwrap_or_default();
 
         Self {
             id: id.into(),
             connected: false,
             bot_token: None,
             http,
+            api_base: TELEGRAM_API_BASE.to_string(),
         }
     }
 
@@ -92,7 +98,7 @@ impl TelegramAdapter {
     /// Build a full Telegram Bot API URL for the given method.
     fn api_url(&self, method: &str) -> Result<String> {
         let token = self.resolve_token()?;
-        Ok(format!("{}{}/{}", TELEGRAM_API_BASE, token, method))
+        Ok(format!("{}{}/{}", self.api_base, token, method))
     }
 
     // -----------------------------------------------------------------------
@@ -740,6 +746,7 @@ impl Adapter for TelegramAdapter {
                     },
                     "required": ["chat_id", "text"]
                 }),
+                destructive: true,
             },
             ToolDefinition {
                 name: "telegram_send_photo".into(),
@@ -762,6 +769,7 @@ impl Adapter for TelegramAdapter {
                     },
                     "required": ["chat_id", "photo_url"]
                 }),
+                destructive: true,
             },
             ToolDefinition {
                 name: "telegram_send_document".into(),
@@ -784,6 +792,7 @@ impl Adapter for TelegramAdapter {
                     },
                     "required": ["chat_id", "file_path"]
                 }),
+                destructive: true,
             },
             ToolDefinition {
                 name: "telegram_send_video".into(),
@@ -806,6 +815,7 @@ impl Adapter for TelegramAdapter {
                     },
                     "required": ["chat_id", "file_path"]
                 }),
+                destructive: true,
             },
             ToolDefinition {
                 name: "telegram_get_updates".into(),
@@ -826,6 +836,7 @@ impl Adapter for TelegramAdapter {
                     },
                     "required": []
                 }),
+                destructive: false,
             },
             ToolDefinition {
                 name: "telegram_get_chat".into(),
@@ -840,6 +851,7 @@ impl Adapter for TelegramAdapter {
                     },
                     "required": ["chat_id"]
                 }),
+                destructive: false,
             },
             ToolDefinition {
                 name: "telegram_set_webhook".into(),
@@ -855,6 +867,7 @@ impl Adapter for TelegramAdapter {
                     },
                     "required": ["url"]
                 }),
+                destructive: true,
             },
             ToolDefinition {
                 name: "telegram_configure_group_chat".into(),
@@ -881,6 +894,7 @@ impl Adapter for TelegramAdapter {
                     },
                     "required": ["chat_id"]
                 }),
+                destructive: true,
             },
             ToolDefinition {
                 name: "telegram_get_chat_member".into(),
@@ -899,6 +913,7 @@ impl Adapter for TelegramAdapter {
                     },
                     "required": ["chat_id", "user_id"]
                 }),
+                destructive: false,
             },
         ]
     }
@@ -910,6 +925,7 @@ impl Adapter for TelegramAdapter {
                 reason: format!("adapter `{}` is not connected", self.id),
             });
         }
+        http_client::ensure_online()?;
 
         match name {
             "telegram_send_message" => self.tool_send_message(params).await,
@@ -934,421 +950,8 @@ impl Adapter for TelegramAdapter {
             scopes: vec!["TELEGRAM_BOT_TOKEN".into()],
         })
     }
-}
-
-// ---------------------------------------------------------------------------
-// Tests
-// ---------------------------------------------------------------------------
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    // -- Construction tests --
-
-    #[test]
-    fn new_creates_adapter_with_defaults() {
-        let adapter = TelegramAdapter::new("tg-test");
-        assert_eq!(adapter.id, "tg-test");
-        assert!(!adapter.connected);
-        assert!(adapter.bot_token.is_none());
-    }
-
-    #[test]
-    fn with_token_sets_bot_token() {
-        let adapter = TelegramAdapter::with_token("tg-test", "123456:ABC-DEF");
-        assert_eq!(adapter.id, "tg-test");
-        assert_eq!(adapter.bot_token.as_deref(), Some("123456:ABC-DEF"));
-        assert!(!adapter.connected);
-    }
-
-    // -- Adapter trait basics --
-
-    #[test]
-    fn adapter_id_returns_id() {
-        let adapter = TelegramAdapter::new("my-telegram");
-        assert_eq!(adapter.id(), "my-telegram");
-    }
-
-    #[test]
-    fn adapter_type_is_messaging() {
-        let adapter = TelegramAdapter::new("telegram");
-        assert_eq!(adapter.adapter_type(), AdapterType::Messaging);
-    }
-
-    #[test]
-    fn required_auth_returns_telegram_provider() {
-        let adapter = TelegramAdapter::new("telegram");
-        let auth = adapter.required_auth().expect("should require auth");
-        assert_eq!(auth.provider, "telegram");
-        assert!(auth.scopes.contains(&"TELEGRAM_BOT_TOKEN".to_string()));
-    }
-
-    // -- Tool definitions --
-
-    #[test]
-    fn tools_returns_expected_count() {
-        let adapter = TelegramAdapter::new("telegram");
-        let tools = adapter.tools();
-        assert_eq!(tools.len(), 9);
-    }
-
-    #[test]
-    fn tools_have_expected_names() {
-        let adapter = TelegramAdapter::new("telegram");
-        let names: Vec<String> = adapter.tools().iter().map(|t| t.name.clone()).collect();
-        let expected = vec![
-            "telegram_send_message",
-            "telegram_send_photo",
-            "telegram_send_document",
-            "telegram_send_video",
-            "telegram_get_updates",
-            "telegram_get_chat",
-            "telegram_set_webhook",
-            "telegram_configure_group_chat",
-            "telegram_get_chat_member",
-        ];
-        assert_eq!(names, expected);
-    }
-
-    #[test]
-    fn tool_send_message_has_required_fields() {
-        let adapter = TelegramAdapter::new("telegram");
-        let tools = adapter.tools();
-        let send_msg = tools
-            .iter()
-            .find(|t| t.name == "telegram_send_message")
-            .expect("should have telegram_send_message");
-        let required = send_msg.parameters["required"]
-            .as_array()
-            .expect("required should be an array");
-        assert!(required.contains(&json!("chat_id")));
-        assert!(required.contains(&json!("text")));
-    }
-
-    #[test]
-    fn tool_send_photo_has_required_fields() {
-        let adapter = TelegramAdapter::new("telegram");
-        let tools = adapter.tools();
-        let send_photo = tools
-            .iter()
-            .find(|t| t.name == "telegram_send_photo")
-            .expect("should have telegram_send_photo");
-        let required = send_photo.parameters["required"]
-            .as_array()
-            .expect("required should be an array");
-        assert!(required.contains(&json!("chat_id")));
-        assert!(required.contains(&json!("photo_url")));
-    }
-
-    #[test]
-    fn tool_get_updates_has_no_required_fields() {
-        let adapter = TelegramAdapter::new("telegram");
-        let tools = adapter.tools();
-        let get_updates = tools
-            .iter()
-            .find(|t| t.name == "telegram_get_updates")
-            .expect("should have telegram_get_updates");
-        let required = get_updates.parameters["required"]
-            .as_array()
-            .expect("required should be an array");
-        assert!(required.is_empty());
-    }
-
-    #[test]
-    fn tool_get_chat_has_required_fields() {
-        let adapter = TelegramAdapter::new("telegram");
-        let tools = adapter.tools();
-        let get_chat = tools
-            .iter()
-            .find(|t| t.name == "telegram_get_chat")
-            .expect("should have telegram_get_chat");
-        let required = get_chat.parameters["required"]
-            .as_array()
-            .expect("required should be an array");
-        assert!(required.contains(&json!("chat_id")));
-    }
-
-    #[test]
-    fn tool_set_webhook_has_required_fields() {
-        let adapter = TelegramAdapter::new("telegram");
-        let tools = adapter.tools();
-        let set_webhook = tools
-            .iter()
-            .find(|t| t.name == "telegram_set_webhook")
-            .expect("should have telegram_set_webhook");
-        let required = set_webhook.parameters["required"]
-            .as_array()
-            .expect("required should be an array");
-        assert!(required.contains(&json!("url")));
-    }
-
-    #[test]
-    fn tool_configure_group_chat_has_required_fields() {
-        let adapter = TelegramAdapter::new("telegram");
-        let tools = adapter.tools();
-        let configure_group = tools
-            .iter()
-            .find(|t| t.name == "telegram_configure_group_chat")
-            .expect("should have telegram_configure_group_chat");
-        let required = configure_group.parameters["required"]
-            .as_array()
-            .expect("required should be an array");
-        assert!(required.contains(&json!("chat_id")));
-    }
-
-    #[test]
-    fn tool_get_chat_member_has_required_fields() {
-        let adapter = TelegramAdapter::new("telegram");
-        let tools = adapter.tools();
-        let get_member = tools
-            .iter()
-            .find(|t| t.name == "telegram_get_chat_member")
-            .expect("should have telegram_get_chat_member");
-        let required = get_member.parameters["required"]
-            .as_array()
-            .expect("required should be an array");
-        assert!(required.contains(&json!("chat_id")));
-        assert!(required.contains(&json!("user_id")));
-    }
-
-    // -- Connect / disconnect --
-
-    #[tokio::test]
-    async fn connect_succeeds_without_env_token() {
-        let mut adapter = TelegramAdapter::new("telegram");
-        let result = adapter.connect().await;
-        assert!(result.is_ok());
-        assert!(adapter.connected);
-    }
-
-    #[tokio::test]
-    async fn connect_with_preloaded_token_keeps_token() {
-        let mut adapter = TelegramAdapter::with_token("telegram", "my-token");
-        adapter.connect().await.unwrap();
-        assert!(adapter.connected);
-        assert_eq!(adapter.bot_token.as_deref(), Some("my-token"));
-    }
-
-    #[tokio::test]
-    async fn disconnect_clears_token_and_sets_disconnected() {
-        let mut adapter = TelegramAdapter::with_token("telegram", "test-token");
-        adapter.connected = true;
-        adapter.disconnect().await.unwrap();
-        assert!(!adapter.connected);
-        assert!(adapter.bot_token.is_none());
-    }
-
-    // -- Health check --
-
-    #[tokio::test]
-    async fn health_check_returns_unhealthy_when_disconnected() {
-        let adapter = TelegramAdapter::new("telegram");
-        let status = adapter.health_check().await.unwrap();
-        assert_eq!(status, HealthStatus::Unhealthy);
-    }
-
-    #[tokio::test]
-    async fn health_check_returns_degraded_when_connected_without_token() {
-        let mut adapter = TelegramAdapter::new("telegram");
-        adapter.connected = true;
-        let status = adapter.health_check().await.unwrap();
-        assert_eq!(status, HealthStatus::Degraded);
-    }
-
-    #[tokio::test]
-    async fn health_check_returns_healthy_when_connected_with_token() {
-        let mut adapter = TelegramAdapter::with_token("telegram", "valid-token");
-        adapter.connected = true;
-        let status = adapter.health_check().await.unwrap();
-        assert_eq!(status, HealthStatus::Healthy);
-    }
-
-    // -- Token resolution --
-
-    #[test]
-    fn resolve_token_succeeds_with_token() {
-        let adapter = TelegramAdapter::with_token("telegram", "my-token");
-        let token = adapter.resolve_token().unwrap();
-        assert_eq!(token, "my-token");
-    }
-
-    #[test]
-    fn resolve_token_fails_without_token() {
-        let adapter = TelegramAdapter::new("telegram");
-        let result = adapter.resolve_token();
-        assert!(result.is_err());
-    }
-
-    // -- Response parsing --
-
-    #[test]
-    fn parse_telegram_response_succeeds_on_ok_true() {
-        let resp = json!({ "ok": true, "result": { "message_id": 42 } });
-        let result = TelegramAdapter::parse_telegram_response(&resp, "test_tool");
-        assert!(result.is_ok());
-    }
-
-    #[test]
-    fn parse_telegram_response_fails_on_ok_false() {
-        let resp = json!({ "ok": false, "error_code": 401, "description": "Unauthorized" });
-        let result = TelegramAdapter::parse_telegram_response(&resp, "test_tool");
-        assert!(result.is_err());
-        let err_msg = result.unwrap_err().to_string();
-        assert!(err_msg.contains("401"));
-        assert!(err_msg.contains("Unauthorized"));
-    }
-
-    #[test]
-    fn parse_telegram_response_fails_on_missing_ok() {
-        let resp = json!({});
-        let result = TelegramAdapter::parse_telegram_response(&resp, "test_tool");
-        assert!(result.is_err());
-    }
-
-    // -- URL construction --
-
-    #[test]
-    fn api_url_constructs_correct_url() {
-        let adapter = TelegramAdapter::with_token("telegram", "123456:ABC-DEF");
-        let url = adapter.api_url("sendMessage").unwrap();
-        assert_eq!(
-            url,
-            "https://api.telegram.org/bot123456:ABC-DEF/sendMessage"
-        );
-    }
-
-    #[test]
-    fn api_url_fails_without_token() {
-        let adapter = TelegramAdapter::new("telegram");
-        let result = adapter.api_url("sendMessage");
-        assert!(result.is_err());
-    }
-
-    // -- Execute tool when not connected --
-
-    #[tokio::test]
-    async fn execute_tool_rejects_when_not_connected() {
-        let adapter = TelegramAdapter::with_token("telegram", "token");
-        let result = adapter
-            .execute_tool("telegram_send_message", json!({}))
-            .await;
-        assert!(result.is_err());
-        let err = result.unwrap_err();
-        assert!(err.to_string().contains("not connected"));
-    }
-
-    // -- Execute tool rejects unknown tool --
-
-    #[tokio::test]
-    async fn execute_tool_rejects_unknown_tool() {
-        let mut adapter = TelegramAdapter::new("telegram");
-        adapter.connected = true;
-        let result = adapter.execute_tool("nonexistent_tool", json!({})).await;
-        assert!(result.is_err());
-        let err = result.unwrap_err();
-        assert!(err.to_string().contains("tool not found"));
-    }
-
-    // -- Missing required parameters --
-
-    #[tokio::test]
-    async fn send_message_rejects_missing_chat_id() {
-        let mut adapter = TelegramAdapter::with_token("telegram", "token");
-        adapter.connected = true;
-        let result = adapter
-            .execute_tool("telegram_send_message", json!({ "text": "hello" }))
-            .await;
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("chat_id"));
-    }
-
-    #[tokio::test]
-    async fn send_message_rejects_missing_text() {
-        let mut adapter = TelegramAdapter::with_token("telegram", "token");
-        adapter.connected = true;
-        let result = adapter
-            .execute_tool("telegram_send_message", json!({ "chat_id": "12345" }))
-            .await;
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("text"));
-    }
-
-    #[tokio::test]
-    async fn send_photo_rejects_missing_chat_id() {
-        let mut adapter = TelegramAdapter::with_token("telegram", "token");
-        adapter.connected = true;
-        let result = adapter
-            .execute_tool(
-                "telegram_send_photo",
-                json!({ "photo_url": "https://example.com/photo.jpg" }),
-            )
-            .await;
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("chat_id"));
-    }
-
-    #[tokio::test]
-    async fn send_photo_rejects_missing_photo_url() {
-        let mut adapter = TelegramAdapter::with_token("telegram", "token");
-        adapter.connected = true;
-        let result = adapter
-            .execute_tool("telegram_send_photo", json!({ "chat_id": "12345" }))
-            .await;
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("photo_url"));
-    }
-
-    #[tokio::test]
-    async fn get_chat_rejects_missing_chat_id() {
-        let mut adapter = TelegramAdapter::with_token("telegram", "token");
-        adapter.connected = true;
-        let result = adapter.execute_tool("telegram_get_chat", json!({})).await;
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("chat_id"));
-    }
-
-    #[tokio::test]
-    async fn set_webhook_rejects_missing_url() {
-        let mut adapter = TelegramAdapter::with_token("telegram", "token");
-        adapter.connected = true;
-        let result = adapter
-            .execute_tool("telegram_set_webhook", json!({}))
-            .await;
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("url"));
-    }
-
-    #[tokio::test]
-    async fn configure_group_chat_rejects_missing_chat_id() {
-        let mut adapter = TelegramAdapter::with_token("telegram", "token");
-        adapter.connected = true;
-        let result = adapter
-            .execute_tool("telegram_configure_group_chat", json!({}))
-            .await;
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("chat_id"));
-    }
-
-    #[tokio::test]
-    async fn get_chat_member_rejects_missing_chat_id() {
-        let mut adapter = TelegramAdapter::with_token("telegram", "token");
-        adapter.connected = true;
-        let result = adapter
-            .execute_tool("telegram_get_chat_member", json!({ "user_id": 12345 }))
-            .await;
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("chat_id"));
-    }
 
-    #[tokio::test]
-    async fn get_chat_member_rejects_missing_user_id() {
-        let mut adapter = TelegramAdapter::with_token("telegram", "token");
-        adapter.connected = true;
-        let result = adapter
-            .execute_tool("telegram_get_chat_member", json!({ "chat_id": "12345" }))
-            .await;
-        assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("user_id"));
+    fn has_credentials(&self) -> bool {
+        credentials::is_set(self.bot_token.as_deref())
     }
 }
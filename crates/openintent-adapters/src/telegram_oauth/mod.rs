@@ -8,9 +8,14 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
-use openintent_auth_engine::{AuthManager, OAuthConfig, DeviceCodeConfig};
+use openintent_auth_engine::oauth_providers::DEFAULT_REDIRECT_URI;
+use openintent_auth_engine::{
+    AuthManager, DeviceCodeConfig, DeviceCodeResponse, OAuthConfig, OAuthProviderRegistry,
+    ProviderOverride,
+};
 use serde::{Deserialize, Serialize};
-use tokio::time::timeout;
+use tokio::task::JoinHandle;
+use tokio::time::{MissedTickBehavior, timeout};
 use uuid::Uuid;
 
 use crate::telegram::TelegramAdapter;
@@ -36,7 +41,7 @@ impl Default for TelegramOAuthConfig {
                 client_secret: None,
                 auth_url: String::new(),
                 token_url: String::new(),
-                redirect_uri: "http://127.0.0.1:8400/callback".to_string(),
+                redirect_uri: DEFAULT_REDIRECT_URI.to_string(),
                 scopes: vec![],
             },
             device_code_config: None,
@@ -59,6 +64,27 @@ struct OAuthSession {
     config: TelegramOAuthConfig,
 }
 
+/// Handle to a background task that expires abandoned OAuth sessions.
+///
+/// The task stops when [`stop`](Self::stop) is called or the handle is
+/// dropped.
+pub struct SessionSweeper {
+    task: JoinHandle<()>,
+}
+
+impl SessionSweeper {
+    /// Stop sweeping.
+    pub fn stop(self) {
+        self.task.abort();
+    }
+}
+
+impl Drop for SessionSweeper {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
 /// Manages OAuth flows within Telegram conversations.
 pub struct TelegramOAuth {
     /// The underlying authentication manager.
@@ -158,11 +184,36 @@ impl TelegramOAuth {
             "🔄 **Requesting device authorization...**"
         ).await?;
 
+        // Message id of the displayed code and the instant the code lapses.
+        let shown: Mutex<Option<(i64, Instant)>> = Mutex::new(None);
+        let shown_ref = &shown;
+        let flow = self.auth_manager.authenticate_device_code_with(
+            &session.provider,
+            device_config.clone(),
+            |response| async move {
+                let expires_at = Instant::now() + Duration::from_secs(response.expires_in);
+                match self.send_device_code(&session.chat_id, &session.provider, &response).await {
+                    Ok(message_id) => {
+                        *shown_ref.lock().unwrap_or_else(|e| e.into_inner()) =
+                            Some((message_id, expires_at));
+                    }
+                    Err(e) => tracing::warn!(error = %e, "failed to show device code"),
+                }
+            },
+        );
+
         // Use timeout for the entire flow
-        let result = timeout(
-            Duration::from_secs(session.config.timeout_secs),
-            self.auth_manager.authenticate_device_code(&session.provider, device_config.clone())
-        ).await;
+        let result = timeout(Duration::from_secs(session.config.timeout_secs), flow).await;
+
+        if !matches!(result, Ok(Ok(_))) {
+            let shown = *shown.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some((message_id, expires_at)) = shown
+                && (result.is_err() || Instant::now() >= expires_at)
+            {
+                self.mark_device_code_expired(&session.chat_id, &session.provider, message_id)
+                    .await;
+            }
+        }
 
         match result {
             Ok(Ok(_tokens)) => {
@@ -187,6 +238,38 @@ impl TelegramOAuth {
         Ok(())
     }
 
+    /// Send the device code prompt to `chat_id` and return its message id.
+    async fn send_device_code(
+        &self,
+        chat_id: &str,
+        provider: &str,
+        response: &DeviceCodeResponse,
+    ) -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+        let sent = self
+            .telegram
+            .send_message(chat_id, &device_code_message(provider, response), Some("Markdown"))
+            .await?;
+        sent.pointer("/data/message_id")
+            .and_then(|v| v.as_i64())
+            .ok_or_else(|| "Telegram did not return a message id".into())
+    }
+
+    /// Replace a displayed device code with a notice that it has lapsed.
+    async fn mark_device_code_expired(&self, chat_id: &str, provider: &str, message_id: i64) {
+        if let Err(e) = self
+            .telegram
+            .edit_message_text(
+                chat_id,
+                message_id,
+                &device_code_expired_message(provider),
+                Some("Markdown"),
+            )
+            .await
+        {
+            tracing::warn!(error = %e, "failed to mark device code as expired");
+        }
+    }
+
     /// Handle authorization code OAuth flow.
     async fn handle_authorization_code_flow(
         &self,
@@ -268,9 +351,54 @@ impl TelegramOAuth {
 
     /// Clean up expired sessions.
     pub fn cleanup_expired_sessions(&self, max_age: Duration) {
-        let mut sessions = self.sessions.lock().unwrap();
+        self.take_expired_sessions(max_age);
+    }
+
+    /// Remove and return the sessions older than `max_age`.
+    fn take_expired_sessions(&self, max_age: Duration) -> Vec<OAuthSession> {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
         let now = Instant::now();
-        sessions.retain(|_, session| now.duration_since(session.created_at) < max_age);
+        let expired: Vec<String> = sessions
+            .iter()
+            .filter(|(_, session)| now.duration_since(session.created_at) >= max_age)
+            .map(|(id, _)| id.clone())
+            .collect();
+        expired
+            .iter()
+            .filter_map(|id| sessions.remove(id))
+            .collect()
+    }
+
+    /// Spawn a task that removes sessions older than `max_age` every
+    /// `interval`, telling each affected chat that its flow timed out.
+    pub fn start_sweeper(&self, interval: Duration, max_age: Duration) -> SessionSweeper {
+        let this = self.clone();
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                let expired = this.take_expired_sessions(max_age);
+                if expired.is_empty() {
+                    continue;
+                }
+                tracing::info!(count = expired.len(), "expired abandoned OAuth sessions");
+                for session in expired {
+                    let _ = this
+                        .send_message(
+                            &session.chat_id,
+                            &format!(
+                                "⏰ **Authentication Timed Out**\n\n\
+                                 The {} sign-in was not completed in time.\n\
+                                 Start the authentication again when you are ready.",
+                                session.provider
+                            ),
+                        )
+                        .await;
+                }
+            }
+        });
+        SessionSweeper { task }
     }
 
     /// Get the number of active sessions.
@@ -290,73 +418,100 @@ impl Clone for TelegramOAuth {
     }
 }
 
-/// Predefined OAuth configurations for common email providers.
+/// Render the chat message asking the user to enter a device code.
+fn device_code_message(provider: &str, response: &DeviceCodeResponse) -> String {
+    let link = response
+        .verification_uri_complete
+        .as_deref()
+        .unwrap_or(&response.verification_uri);
+    format!(
+        "🔑 **Authorize {}**\n\n\
+         1. Open {}\n\
+         2. Enter the code: `{}`\n\n\
+         Or [open the sign-in page directly]({}).\n\
+         The code expires in {} minutes.",
+        provider,
+        response.verification_uri,
+        response.user_code,
+        link,
+        response.expires_in.div_ceil(60)
+    )
+}
+
+/// Render the replacement for a device code message once the code lapses.
+fn device_code_expired_message(provider: &str) -> String {
+    format!(
+        "⌛ **Code Expired**\n\n\
+         The {} device code has expired.\n\
+         Start the authentication again to get a new code.",
+        provider
+    )
+}
+
+/// Telegram flow configurations for the providers in an
+/// [`OAuthProviderRegistry`].
 pub mod providers {
     use super::*;
 
+    /// Timeout for device code flows, which wait on the user switching
+    /// devices.
+    const DEVICE_CODE_TIMEOUT_SECS: u64 = 900;
+
+    /// Build a Telegram configuration for the registry entry `name`.
+    ///
+    /// Providers that support the device code grant prefer it, since the
+    /// user may be chatting from a device without a local browser.
+    pub fn from_registry(
+        registry: &OAuthProviderRegistry,
+        name: &str,
+    ) -> Option<TelegramOAuthConfig> {
+        let oauth_config = registry.provider_config(name)?;
+        let device_code_config = registry.device_code_config(name);
+        let prefer_device_code = device_code_config.is_some();
+        let defaults = TelegramOAuthConfig::default();
+        Some(TelegramOAuthConfig {
+            oauth_config,
+            device_code_config,
+            timeout_secs: if prefer_device_code {
+                DEVICE_CODE_TIMEOUT_SECS
+            } else {
+                defaults.timeout_secs
+            },
+            prefer_device_code,
+        })
+    }
+
+    /// Build a configuration for a built-in provider with the given client
+    /// credentials.
+    fn builtin_with_credentials(
+        name: &str,
+        client_id: String,
+        client_secret: String,
+    ) -> TelegramOAuthConfig {
+        let mut registry = OAuthProviderRegistry::builtin();
+        let overrides = ProviderOverride {
+            client_id: Some(client_id),
+            client_secret: Some(client_secret),
+            ..ProviderOverride::default()
+        };
+        // Overriding a registered provider cannot fail.
+        let _ = registry.apply_override(name, overrides);
+        from_registry(&registry, name).unwrap_or_default()
+    }
+
     /// Create a Gmail OAuth configuration for Telegram.
     pub fn gmail_config(client_id: String, client_secret: String) -> TelegramOAuthConfig {
-        TelegramOAuthConfig {
-            oauth_config: OAuthConfig {
-                client_id,
-                client_secret: Some(client_secret),
-                auth_url: "https://accounts.google.com/o/oauth2/v2/auth".to_string(),
-                token_url: "https://oauth2.googleapis.com/token".to_string(),
-                redirect_uri: "http://127.0.0.1:8400/callback".to_string(),
-                scopes: vec![
-                    "https://www.googleapis.com/auth/gmail.readonly".to_string(),
-                    "https://www.googleapis.com/auth/gmail.send".to_string(),
-                ],
-            },
-            device_code_config: None,
-            timeout_secs: 300,
-            prefer_device_code: false,
-        }
+        builtin_with_credentials("gmail", client_id, client_secret)
     }
 
     /// Create an Outlook/Office 365 OAuth configuration for Telegram.
     pub fn outlook_config(client_id: String, client_secret: String) -> TelegramOAuthConfig {
-        TelegramOAuthConfig {
-            oauth_config: OAuthConfig {
-                client_id: client_id.clone(),
-                client_secret: Some(client_secret),
-                auth_url: "https://login.microsoftonline.com/common/oauth2/v2.0/authorize".to_string(),
-                token_url: "https://login.microsoftonline.com/common/oauth2/v2.0/token".to_string(),
-                redirect_uri: "http://127.0.0.1:8400/callback".to_string(),
-                scopes: vec![
-                    "https://graph.microsoft.com/Mail.Read".to_string(),
-                    "https://graph.microsoft.com/Mail.Send".to_string(),
-                ],
-            },
-            device_code_config: Some(DeviceCodeConfig {
-                client_id: client_id.clone(),
-                device_auth_url: "https://login.microsoftonline.com/common/oauth2/v2.0/devicecode".to_string(),
-                token_url: "https://login.microsoftonline.com/common/oauth2/v2.0/token".to_string(),
-                scopes: vec![
-                    "https://graph.microsoft.com/Mail.Read".to_string(),
-                    "https://graph.microsoft.com/Mail.Send".to_string(),
-                ],
-            }),
-            timeout_secs: 900, // 15 minutes for device code flow
-            prefer_device_code: true,
-        }
+        builtin_with_credentials("outlook", client_id, client_secret)
     }
 
     /// Create a Yahoo OAuth configuration for Telegram.
     pub fn yahoo_config(client_id: String, client_secret: String) -> TelegramOAuthConfig {
-        TelegramOAuthConfig {
-            oauth_config: OAuthConfig {
-                client_id,
-                client_secret: Some(client_secret),
-                auth_url: "https://api.login.yahoo.com/oauth2/request_auth".to_string(),
-                token_url: "https://api.login.yahoo.com/oauth2/get_token".to_string(),
-                redirect_uri: "http://127.0.0.1:8400/callback".to_string(),
-                scopes: vec!["mail-r".to_string(), "mail-w".to_string()],
-            },
-            device_code_config: None,
-            timeout_secs: 300,
-            prefer_device_code: false,
-        }
+        builtin_with_credentials("yahoo", client_id, client_secret)
     }
 }
 
@@ -424,4 +579,107 @@ mod tests {
         // Verify AuthManager can be created and used without panics.
         let _auth_manager = test_auth_manager();
     }
+
+    fn insert_session(oauth: &TelegramOAuth, chat_id: &str, age: Duration) {
+        let created_at = Instant::now().checked_sub(age).unwrap();
+        oauth.sessions.lock().unwrap().insert(
+            Uuid::new_v4().to_string(),
+            OAuthSession {
+                chat_id: chat_id.to_string(),
+                provider: "gmail".to_string(),
+                created_at,
+                config: TelegramOAuthConfig::default(),
+            },
+        );
+    }
+
+    /// Serve Telegram Bot API calls, recording each JSON request body.
+    async fn mock_bot_api(
+        listener: tokio::net::TcpListener,
+        bodies: Arc<Mutex<Vec<serde_json::Value>>>,
+    ) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        loop {
+            let Ok((mut stream, _)) = listener.accept().await else {
+                return;
+            };
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            let body = loop {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request).to_string();
+                if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                    let len = head
+                        .lines()
+                        .find_map(|l| {
+                            l.to_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap())
+                        })
+                        .unwrap_or(0);
+                    if body.len() >= len {
+                        break body.to_string();
+                    }
+                }
+            };
+            bodies.lock().unwrap().push(serde_json::from_str(&body).unwrap());
+            let reply = r#"{"ok":true,"result":{"message_id":42}}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                reply.len(),
+                reply
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn device_code_is_shown_in_chat() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}/bot", listener.local_addr().unwrap());
+        let bodies = Arc::new(Mutex::new(Vec::new()));
+        tokio::spawn(mock_bot_api(listener, Arc::clone(&bodies)));
+
+        let telegram = TelegramAdapter::with_token("telegram", "TOKEN").with_api_base(base);
+        let oauth = TelegramOAuth::new(test_auth_manager(), Arc::new(telegram));
+        let response = DeviceCodeResponse {
+            device_code: "device-123".to_string(),
+            user_code: "WDJB-MJHT".to_string(),
+            verification_uri: "https://microsoft.com/devicelogin".to_string(),
+            verification_uri_complete: None,
+            expires_in: 900,
+            interval: 5,
+        };
+
+        let message_id = oauth.send_device_code("chat-1", "outlook", &response).await.unwrap();
+        assert_eq!(message_id, 42);
+        oauth.mark_device_code_expired("chat-1", "outlook", message_id).await;
+
+        let bodies = bodies.lock().unwrap();
+        let text = bodies[0]["text"].as_str().unwrap();
+        assert!(text.contains("WDJB-MJHT"));
+        assert!(text.contains("[open the sign-in page directly](https://microsoft.com/devicelogin)"));
+        assert_eq!(bodies[1]["message_id"], 42);
+        assert!(bodies[1]["text"].as_str().unwrap().contains("Expired"));
+    }
+
+    #[tokio::test]
+    async fn sweeper_removes_expired_sessions() {
+        let oauth = TelegramOAuth::new(
+            test_auth_manager(),
+            Arc::new(TelegramAdapter::new("telegram")),
+        );
+        insert_session(&oauth, "abandoned", Duration::from_secs(600));
+        insert_session(&oauth, "in-progress", Duration::ZERO);
+
+        let sweeper = oauth.start_sweeper(Duration::from_millis(10), Duration::from_secs(300));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        sweeper.stop();
+
+        assert_eq!(oauth.active_sessions_count(), 1);
+        let sessions = oauth.sessions.lock().unwrap();
+        assert!(sessions.values().all(|s| s.chat_id == "in-progress"));
+    }
 }
\ No newline at end of file
@@ -5,6 +5,7 @@
 //! and intent engine to discover and invoke tools.
 
 use async_trait::async_trait;
+use openintent_agent::PromptFn;
 use serde::{Deserialize, Serialize};
 
 use crate::error::Result;
@@ -72,6 +73,11 @@ pub struct ToolDefinition {
     pub description: String,
     /// JSON Schema describing the tool's input parameters.
     pub parameters: serde_json::Value,
+    /// Whether the tool changes state or acts outside the system: writing
+    /// or deleting data, sending messages, running commands.  Viewer
+    /// accounts may not call destructive tools.
+    #[serde(default)]
+    pub destructive: bool,
 }
 
 /// Authentication requirements for an adapter.
@@ -121,6 +127,51 @@ pub trait Adapter: Send + Sync {
         params: serde_json::Value,
     ) -> Result<serde_json::Value>;
 
+    /// Execute a tool that may ask the user to confirm a destructive step
+    /// through `prompt` (see [`openintent_agent::confirm`]).
+    ///
+    /// The default ignores the prompt and calls
+    /// [`execute_tool`](Self::execute_tool); wrappers forward it to the
+    /// adapter they wrap.
+    async fn execute_tool_with_prompt(
+        &self,
+        name: &str,
+        params: serde_json::Value,
+        _prompt: Option<PromptFn>,
+    ) -> Result<serde_json::Value> {
+        self.execute_tool(name, params).await
+    }
+
     /// Return the authentication requirements for this adapter, if any.
     fn required_auth(&self) -> Option<AuthRequirement>;
+
+    /// Whether the credentials described by [`required_auth`](Self::required_auth)
+    /// are already configured on this instance.
+    ///
+    /// Adapters that cannot do anything useful without credentials override
+    /// this; those that degrade gracefully (e.g. GitHub on public data) keep
+    /// the default.
+    fn has_credentials(&self) -> bool {
+        true
+    }
+
+    /// Whether `tool_name` has a side effect that must not be repeated when
+    /// a call is retried (sending a message, creating an issue, ...).
+    ///
+    /// Calls to mutating tools accept an optional `idempotency_key` when the
+    /// adapter is wrapped in an
+    /// [`IdempotentAdapter`](crate::idempotency::IdempotentAdapter).  The
+    /// default looks the tool up in
+    /// [`MUTATING_TOOLS`](crate::idempotency::MUTATING_TOOLS).
+    fn is_mutating(&self, tool_name: &str) -> bool {
+        crate::idempotency::is_mutating_tool(tool_name)
+    }
+
+    /// Copy of `params` that is safe to record in the tool-call audit trail.
+    ///
+    /// The default masks values under secret-looking keys; adapters whose
+    /// tools take other sensitive input override it.
+    fn redact_params(&self, _tool_name: &str, params: &serde_json::Value) -> serde_json::Value {
+        openintent_agent::redact_sensitive(params)
+    }
 }
@@ -21,6 +21,7 @@ use serde_json::{Value, json};
 use tracing::{debug, info, warn};
 
 use crate::error::{AdapterError, Result};
+use crate::http_client::HttpClientFactory;
 use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
 
 // ═══════════════════════════════════════════════════════════════════════
@@ -30,9 +31,6 @@ use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDef
 /// Default maximum content length in characters.
 const DEFAULT_MAX_LENGTH: usize = 80_000;
 
-/// Default request timeout in seconds.
-const DEFAULT_TIMEOUT_SECS: u64 = 30;
-
 /// Maximum number of retries for transient failures.
 const MAX_RETRIES: u32 = 2;
 
@@ -58,18 +56,16 @@ pub struct WebFetchAdapter {
     id: String,
     connected: bool,
     client: reqwest::Client,
+    /// Factory the client was built from.
+    http: HttpClientFactory,
     cache: Cache<String, Value>,
 }
 
 impl WebFetchAdapter {
     /// Create a new web fetch adapter.
     pub fn new(id: impl Into<String>) -> Self {
-        let client = reqwest::Client::builder()
-            .user_agent(BROWSER_USER_AGENT)
-            .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
-            .redirect(reqwest::redirect::Policy::limited(10))
-            .build()
-            .unwrap_or_default();
+        let http = HttpClientFactory::default();
+        let client = Self::build_client(&http).unwrap_or_default();
 
         let cache = Cache::builder()
             .max_capacity(CACHE_MAX_ENTRIES)
@@ -80,10 +76,27 @@ impl WebFetchAdapter {
             id: id.into(),
             connected: false,
             client,
+            http,
             cache,
         }
     }
 
+    /// Build the HTTP client from `http` instead of the default factory.
+    pub fn with_http_client_factory(mut self, http: HttpClientFactory) -> Result<Self> {
+        self.client = Self::build_client(&http)?;
+        self.http = http;
+        Ok(self)
+    }
+
+    /// Build a client that identifies as a browser to avoid being blocked.
+    fn build_client(http: &HttpClientFactory) -> Result<reqwest::Client> {
+        http.builder()?
+            .user_agent(BROWSER_USER_AGENT)
+            .redirect(reqwest::redirect::Policy::limited(10))
+            .build()
+            .map_err(|e| AdapterError::ConfigError(format!("failed to build HTTP client: {e}")))
+    }
+
     /// Fetch a URL and return its content with cache-first, retry, and SSRF guard.
     async fn tool_web_fetch(&self, params: Value) -> Result<Value> {
         let url_str = params.get("url").and_then(|v| v.as_str()).ok_or_else(|| {
@@ -282,7 +295,7 @@ async fn check_ssrf(url: &url::Url) -> Result<()> {
 }
 
 /// Check if an IP address is private, loopback, link-local, or otherwise internal.
-fn is_private_ip(ip: IpAddr) -> bool {
+pub(crate) fn is_private_ip(ip: IpAddr) -> bool {
     match ip {
         IpAddr::V4(v4) => {
             v4.is_loopback()
@@ -545,6 +558,7 @@ impl Adapter for WebFetchAdapter {
                 },
                 "required": ["url"]
             }),
+            destructive: false,
         }]
     }
 
@@ -555,6 +569,7 @@ impl Adapter for WebFetchAdapter {
                 reason: format!("adapter `{}` is not connected", self.id),
             });
         }
+        self.http.ensure_online()?;
         match name {
             "web_fetch" => self.tool_web_fetch(params).await,
             _ => Err(AdapterError::ToolNotFound {
@@ -840,4 +855,23 @@ mod tests {
         adapter.cache.insert(key.clone(), val.clone()).await;
         assert_eq!(adapter.cache.get(&key).await, Some(val));
     }
+
+    #[tokio::test]
+    async fn offline_mode_fails_fast() {
+        use crate::http_client::NetworkMode;
+
+        let mut adapter = WebFetchAdapter::new("wf-test")
+            .with_http_client_factory(
+                HttpClientFactory::new().with_network_mode(NetworkMode::Offline),
+            )
+            .unwrap();
+        adapter.connect().await.unwrap();
+
+        let start = std::time::Instant::now();
+        let result = adapter
+            .execute_tool("web_fetch", json!({"url": "https://example.com"}))
+            .await;
+        assert!(matches!(result, Err(AdapterError::NetworkDisabled)));
+        assert!(start.elapsed() < Duration::from_millis(100));
+    }
 }
@@ -26,6 +26,7 @@ use tracing::{debug, info, warn};
 use crate::web_fetch;
 
 use crate::error::{AdapterError, Result};
+use crate::http_client::HttpClientFactory;
 use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
 
 // ═══════════════════════════════════════════════════════════════════════
@@ -55,6 +56,8 @@ pub struct WebSearchAdapter {
     id: String,
     connected: bool,
     client: reqwest::Client,
+    /// Factory the client was built from.
+    http: HttpClientFactory,
     brave_api_key: Option<String>,
     perplexity_api_key: Option<String>,
     /// In-memory LRU cache for search results.
@@ -64,11 +67,8 @@ pub struct WebSearchAdapter {
 impl WebSearchAdapter {
     /// Create a new web search adapter.
     pub fn new(id: impl Into<String>) -> Self {
-        let client = reqwest::Client::builder()
-            .user_agent(BROWSER_USER_AGENT)
-            .timeout(Duration::from_secs(30))
-            .build()
-            .unwrap_or_default();
+        let http = HttpClientFactory::default();
+        let client = Self::build_client(&http).unwrap_or_default();
 
         let brave_api_key = env_non_empty("BRAVE_API_KEY");
         let perplexity_api_key =
@@ -83,12 +83,28 @@ impl WebSearchAdapter {
             id: id.into(),
             connected: false,
             client,
+            http,
             brave_api_key,
             perplexity_api_key,
             cache,
         }
     }
 
+    /// Build the HTTP client from `http` instead of the default factory.
+    pub fn with_http_client_factory(mut self, http: HttpClientFactory) -> Result<Self> {
+        self.client = Self::build_client(&http)?;
+        self.http = http;
+        Ok(self)
+    }
+
+    /// Build a client that identifies as a browser to avoid being blocked.
+    fn build_client(http: &HttpClientFactory) -> Result<reqwest::Client> {
+        http.builder()?
+            .user_agent(BROWSER_USER_AGENT)
+            .build()
+            .map_err(|e| AdapterError::ConfigError(format!("failed to build HTTP client: {e}")))
+    }
+
     /// Execute a web search with cache-first strategy.
     async fn tool_web_search(&self, params: Value) -> Result<Value> {
         let query = params
@@ -742,6 +758,7 @@ impl Adapter for WebSearchAdapter {
                     },
                     "required": ["query"]
                 }),
+                destructive: false,
             },
             ToolDefinition {
                 name: "web_research".into(),
@@ -772,6 +789,7 @@ impl Adapter for WebSearchAdapter {
                     },
                     "required": ["query"]
                 }),
+                destructive: false,
             },
         ]
     }
@@ -783,6 +801,7 @@ impl Adapter for WebSearchAdapter {
                 reason: format!("adapter `{}` is not connected", self.id),
             });
         }
+        self.http.ensure_online()?;
         match name {
             "web_search" => self.tool_web_search(params).await,
             "web_research" => self.tool_web_research(params).await,
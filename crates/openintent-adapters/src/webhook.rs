@@ -0,0 +1,529 @@
+//! Inbound webhook adapter.
+//!
+//! On [`connect`](Adapter::connect) the adapter starts a small HTTP listener
+//! that accepts signed `POST` requests on a configured path and publishes
+//! each payload onto the kernel [`IpcBus`] as an [`Event::AdapterEvent`].
+//! Event triggers registered with the intent engine can then react to it.
+//!
+//! Requests are authenticated with an HMAC-SHA256 of the raw body, sent as
+//! `X-Signature-256: sha256=<hex>` (the scheme GitHub and many other services
+//! use).  Unsigned or mis-signed requests are rejected with `401` and never
+//! reach the bus.
+//!
+//! Like the OAuth callback server, the listener uses a raw
+//! [`tokio::net::TcpListener`] rather than a full HTTP framework.
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use openintent_kernel::ipc::{Event, IpcBus};
+use ring::hmac;
+use serde_json::{Value, json};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::error::{AdapterError, Result};
+use crate::traits::{Adapter, AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
+
+/// Header carrying the request signature.
+const SIGNATURE_HEADER: &str = "x-signature-256";
+
+/// Optional header naming the event; becomes the published event's `kind`.
+const EVENT_HEADER: &str = "x-webhook-event";
+
+/// Event kind used when the sender does not name one.
+const DEFAULT_EVENT_KIND: &str = "webhook";
+
+/// Largest request body accepted.
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Largest request head (request line plus headers) accepted.
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+
+/// How long a client may take to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+// ---------------------------------------------------------------------------
+// Configuration
+// ---------------------------------------------------------------------------
+
+/// Where the webhook listener binds and how requests are authenticated.
+#[derive(Debug, Clone)]
+pub struct WebhookConfig {
+    /// Address to bind, e.g. `127.0.0.1` or `0.0.0.0`.
+    pub host: String,
+    /// Port to listen on.  `0` picks a free port.
+    pub port: u16,
+    /// Request path webhooks are posted to.
+    pub path: String,
+    /// Shared secret used to verify request signatures.
+    pub secret: String,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            host: "127.0.0.1".into(),
+            port: 8787,
+            path: "/webhook".into(),
+            secret: String::new(),
+        }
+    }
+}
+
+impl WebhookConfig {
+    /// Read the configuration from `WEBHOOK_HOST`, `WEBHOOK_PORT`,
+    /// `WEBHOOK_PATH` and `WEBHOOK_SECRET`, using the defaults for unset
+    /// variables.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            host: std::env::var("WEBHOOK_HOST").unwrap_or(defaults.host),
+            port: std::env::var("WEBHOOK_PORT")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(defaults.port),
+            path: std::env::var("WEBHOOK_PATH").unwrap_or(defaults.path),
+            secret: std::env::var("WEBHOOK_SECRET").unwrap_or_default(),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Adapter
+// ---------------------------------------------------------------------------
+
+/// Receives signed webhooks and publishes them onto the IPC bus.
+pub struct WebhookAdapter {
+    /// Unique identifier for this adapter instance.
+    id: String,
+    /// Listener configuration.
+    config: WebhookConfig,
+    /// Bus that received events are published to.
+    bus: IpcBus,
+    /// Address the listener is bound to while connected.
+    local_addr: Option<SocketAddr>,
+    /// The accept loop, while connected.
+    server: Option<JoinHandle<()>>,
+}
+
+impl WebhookAdapter {
+    /// Create a new webhook adapter publishing to `bus`.
+    pub fn new(id: impl Into<String>, config: WebhookConfig, bus: IpcBus) -> Self {
+        Self {
+            id: id.into(),
+            config,
+            bus,
+            local_addr: None,
+            server: None,
+        }
+    }
+
+    /// The address the listener is bound to, once connected.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addr
+    }
+
+    /// Full URL webhooks should be posted to, once connected.
+    fn url(&self) -> Option<String> {
+        self.local_addr
+            .map(|addr| format!("http://{addr}{}", self.config.path))
+    }
+}
+
+#[async_trait]
+impl Adapter for WebhookAdapter {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        AdapterType::System
+    }
+
+    async fn connect(&mut self) -> Result<()> {
+        if self.server.is_some() {
+            return Ok(());
+        }
+        if self.config.secret.is_empty() {
+            return Err(AdapterError::ConfigError(
+                "webhook secret is not set; refusing to accept unsigned requests".into(),
+            ));
+        }
+
+        let addr = format!("{}:{}", self.config.host, self.config.port);
+        let listener = TcpListener::bind(&addr).await?;
+        let local_addr = listener.local_addr()?;
+
+        let receiver = Receiver {
+            adapter_id: self.id.clone(),
+            path: self.config.path.clone(),
+            key: hmac::Key::new(hmac::HMAC_SHA256, self.config.secret.as_bytes()),
+            bus: self.bus.clone(),
+        };
+        self.server = Some(tokio::spawn(receiver.serve(listener)));
+        self.local_addr = Some(local_addr);
+
+        info!(
+            id = %self.id,
+            addr = %local_addr,
+            path = %self.config.path,
+            "webhook listener started"
+        );
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        if let Some(server) = self.server.take() {
+            server.abort();
+        }
+        self.local_addr = None;
+        info!(id = %self.id, "webhook listener stopped");
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus> {
+        match &self.server {
+            Some(server) if !server.is_finished() => Ok(HealthStatus::Healthy),
+            _ => Ok(HealthStatus::Unhealthy),
+        }
+    }
+
+    fn tools(&self) -> Vec<ToolDefinition> {
+        vec![ToolDefinition {
+            name: "webhook_info".into(),
+            description: "Show the URL external services should post webhooks to and how \
+                          to sign them"
+                .into(),
+            parameters: json!({ "type": "object", "properties": {} }),
+            destructive: false,
+        }]
+    }
+
+    async fn execute_tool(&self, name: &str, _params: Value) -> Result<Value> {
+        match name {
+            "webhook_info" => Ok(json!({
+                "listening": self.server.is_some(),
+                "url": self.url(),
+                "signature_header": "X-Signature-256",
+                "signature_format": "sha256=<hex HMAC-SHA256 of the body>",
+                "event_header": "X-Webhook-Event",
+            })),
+            _ => Err(AdapterError::ToolNotFound {
+                adapter_id: self.id.clone(),
+                tool_name: name.to_string(),
+            }),
+        }
+    }
+
+    fn required_auth(&self) -> Option<AuthRequirement> {
+        None
+    }
+
+    fn has_credentials(&self) -> bool {
+        !self.config.secret.is_empty()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Request handling
+// ---------------------------------------------------------------------------
+
+/// State shared by every connection the listener accepts.
+#[derive(Clone)]
+struct Receiver {
+    adapter_id: String,
+    path: String,
+    key: hmac::Key,
+    bus: IpcBus,
+}
+
+/// A parsed HTTP request.
+pub(crate) struct Request {
+    pub(crate) method: String,
+    pub(crate) path: String,
+    /// The raw query string, without the leading `?`.  Only the mock
+    /// servers in adapter tests look at it.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) query: String,
+    /// Headers with lower-cased names.
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) body: Vec<u8>,
+}
+
+impl Request {
+    /// The value of header `name` (lower-case), if present.
+    pub(crate) fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+impl Receiver {
+    /// Accept connections until the task is aborted.
+    async fn serve(self, listener: TcpListener) {
+        loop {
+            match listener.accept().await {
+                Ok((stream, peer)) => {
+                    let receiver = self.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = receiver.handle(stream).await {
+                            debug!(peer = %peer, error = %e, "webhook connection failed");
+                        }
+                    });
+                }
+                Err(e) => warn!(error = %e, "webhook listener failed to accept"),
+            }
+        }
+    }
+
+    /// Read one request, publish it if valid, and write the response.
+    async fn handle(&self, mut stream: TcpStream) -> std::io::Result<()> {
+        let status = match read_request(&mut stream).await {
+            Ok(request) => self.accept(request),
+            Err(status) => status,
+        };
+        write_response(&mut stream, status, "text/plain", "").await
+    }
+
+    /// Validate `request` and publish it, returning the response status.
+    fn accept(&self, request: Request) -> &'static str {
+        if request.path != self.path {
+            return "404 Not Found";
+        }
+        if request.method != "POST" {
+            return "405 Method Not Allowed";
+        }
+        let verified = request
+            .header(SIGNATURE_HEADER)
+            .and_then(|sig| sig.strip_prefix("sha256="))
+            .and_then(decode_hex)
+            .is_some_and(|sig| hmac::verify(&self.key, &request.body, &sig).is_ok());
+        if !verified {
+            warn!(id = %self.adapter_id, "rejected webhook with missing or invalid signature");
+            return "401 Unauthorized";
+        }
+        let kind = request
+            .header(EVENT_HEADER)
+            .unwrap_or(DEFAULT_EVENT_KIND)
+            .to_string();
+        let Ok(payload) = String::from_utf8(request.body) else {
+            return "400 Bad Request";
+        };
+        info!(id = %self.adapter_id, kind = %kind, bytes = payload.len(), "webhook received");
+        let event = Event::AdapterEvent {
+            adapter_id: self.adapter_id.clone(),
+            kind,
+            payload,
+            timestamp: Utc::now(),
+        };
+        match self.bus.publish(event) {
+            Ok(_) => "202 Accepted",
+            Err(e) => {
+                warn!(error = %e, "failed to publish webhook event");
+                "500 Internal Server Error"
+            }
+        }
+    }
+}
+
+/// Read an HTTP/1.1 request with a `Content-Length` body.  On failure the
+/// error is the response status to send.
+pub(crate) async fn read_request(
+    stream: &mut TcpStream,
+) -> std::result::Result<Request, &'static str> {
+    tokio::time::timeout(READ_TIMEOUT, read_request_untimed(stream))
+        .await
+        .unwrap_or(Err("408 Request Timeout"))
+}
+
+/// Write a complete response and close the exchange.
+pub(crate) async fn write_response(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.flush().await
+}
+
+async fn read_request_untimed(
+    stream: &mut TcpStream,
+) -> std::result::Result<Request, &'static str> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let head_end = loop {
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos;
+        }
+        if buf.len() > MAX_HEAD_BYTES {
+            return Err("431 Request Header Fields Too Large");
+        }
+        let n = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|_| "400 Bad Request")?;
+        if n == 0 {
+            return Err("400 Bad Request");
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = std::str::from_utf8(&buf[..head_end]).map_err(|_| "400 Bad Request")?;
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let (Some(method), Some(target)) = (request_line.next(), request_line.next()) else {
+        return Err("400 Bad Request");
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let (path, query) = (path.to_string(), query.to_string());
+    let method = method.to_string();
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(k, v)| (k.trim().to_ascii_lowercase(), v.trim().to_string()))
+        .collect();
+
+    let content_length = match headers.iter().find(|(k, _)| k == "content-length") {
+        Some((_, v)) => v.parse::<usize>().map_err(|_| "400 Bad Request")?,
+        None => 0,
+    };
+    if content_length > MAX_BODY_BYTES {
+        return Err("413 Payload Too Large");
+    }
+
+    let mut body = buf.split_off(head_end + 4);
+    while body.len() < content_length {
+        let n = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|_| "400 Bad Request")?;
+        if n == 0 {
+            return Err("400 Bad Request");
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+    body.truncate(content_length);
+
+    Ok(Request {
+        method,
+        path,
+        query,
+        headers,
+        body,
+    })
+}
+
+/// Decode a hex string, returning `None` if it is not valid hex.
+pub(crate) fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "s3cret";
+
+    async fn connected_adapter(bus: &IpcBus) -> WebhookAdapter {
+        let config = WebhookConfig {
+            port: 0,
+            secret: SECRET.into(),
+            ..WebhookConfig::default()
+        };
+        let mut adapter = WebhookAdapter::new("webhook", config, bus.clone());
+        adapter.connect().await.expect("listener must start");
+        adapter
+    }
+
+    fn sign(body: &str) -> String {
+        let key = hmac::Key::new(hmac::HMAC_SHA256, SECRET.as_bytes());
+        let tag = hmac::sign(&key, body.as_bytes());
+        let hex: String = tag.as_ref().iter().map(|b| format!("{b:02x}")).collect();
+        format!("sha256={hex}")
+    }
+
+    /// Post `body` to the adapter and return the response status line.
+    async fn post(adapter: &WebhookAdapter, body: &str, headers: &[(&str, String)]) -> String {
+        let addr = adapter.local_addr().expect("adapter must be connected");
+        let mut stream = TcpStream::connect(addr)
+            .await
+            .expect("connect must succeed");
+        let mut request = format!("POST /webhook HTTP/1.1\r\nHost: {addr}\r\n");
+        for (name, value) in headers {
+            request.push_str(&format!("{name}: {value}\r\n"));
+        }
+        request.push_str(&format!("Content-Length: {}\r\n\r\n{body}", body.len()));
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response.lines().next().unwrap_or_default().to_string()
+    }
+
+    #[tokio::test]
+    async fn signed_payload_is_published() {
+        let bus = IpcBus::new(16);
+        let mut rx = bus.subscribe();
+        let adapter = connected_adapter(&bus).await;
+
+        let body = r#"{"ref":"refs/heads/main"}"#;
+        let headers = [
+            ("X-Signature-256", sign(body)),
+            ("X-Webhook-Event", "push".to_string()),
+        ];
+        assert_eq!(
+            post(&adapter, body, &headers).await,
+            "HTTP/1.1 202 Accepted"
+        );
+
+        let event = rx.recv().await.expect("event must be published");
+        match event.as_ref() {
+            Event::AdapterEvent {
+                adapter_id,
+                kind,
+                payload,
+                ..
+            } => {
+                assert_eq!(adapter_id, "webhook");
+                assert_eq!(kind, "push");
+                assert_eq!(payload, body);
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn unsigned_or_forged_payload_is_rejected() {
+        let bus = IpcBus::new(16);
+        let mut rx = bus.subscribe();
+        let adapter = connected_adapter(&bus).await;
+
+        let body = r#"{"ref":"refs/heads/main"}"#;
+        assert_eq!(post(&adapter, body, &[]).await, "HTTP/1.1 401 Unauthorized");
+        let forged = [("X-Signature-256", sign("something else"))];
+        assert_eq!(
+            post(&adapter, body, &forged).await,
+            "HTTP/1.1 401 Unauthorized"
+        );
+
+        assert!(rx.try_recv().is_err(), "no event may be published");
+    }
+}
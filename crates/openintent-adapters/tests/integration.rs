@@ -391,7 +391,7 @@ async fn adapter_tools_are_well_defined() {
     let mut cron = CronAdapter::new("cron");
     cron.connect().await.unwrap();
     let tools = cron.tools();
-    assert_eq!(tools.len(), 4);
+    assert_eq!(tools.len(), 6);
 }
 
 #[tokio::test]
@@ -0,0 +1,141 @@
+//! Tool-call audit trail.
+//!
+//! When an [`AgentContext`] has a [`ToolAuditStore`] attached, the runtime
+//! records every tool call it handles, including denied ones, in the
+//! store's `tool_audit` table.  Arguments go through the owning adapter's
+//! [`ToolAdapter::redact_arguments`] first, so secrets never reach the
+//! trail; results are kept only as a short preview.
+//!
+//! [`AgentContext`]: crate::runtime::AgentContext
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use openintent_store::{NewToolAudit, ToolAuditStore};
+use serde_json::Value;
+
+use crate::llm::types::{ToolCall, ToolResult};
+use crate::runtime::ToolAdapter;
+
+/// Replacement for redacted argument values.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Key words that mark an argument as secret (`client_secret`, `bot_token`).
+const SENSITIVE_WORDS: &[&str] = &[
+    "authorization",
+    "cookie",
+    "credentials",
+    "passphrase",
+    "password",
+    "secret",
+    "token",
+];
+
+/// Key suffixes that mark an argument as secret (`x-api-key`).
+const SENSITIVE_SUFFIXES: &[&str] = &["access_key", "api_key", "apikey", "private_key"];
+
+/// Longest tool result kept in the audit trail, in characters.
+const RESULT_PREVIEW_CHARS: usize = 2_000;
+
+/// Copy `arguments`, replacing the value of every secret-looking key, at any
+/// depth, with [`REDACTED`].
+pub fn redact_sensitive(arguments: &Value) -> Value {
+    match arguments {
+        Value::Object(map) => map
+            .iter()
+            .map(|(key, value)| {
+                let value = if is_sensitive_key(key) {
+                    Value::String(REDACTED.into())
+                } else {
+                    redact_sensitive(value)
+                };
+                (key.clone(), value)
+            })
+            .collect(),
+        Value::Array(items) => items.iter().map(redact_sensitive).collect(),
+        other => other.clone(),
+    }
+}
+
+fn is_sensitive_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase().replace('-', "_");
+    key.split('_').any(|word| SENSITIVE_WORDS.contains(&word))
+        || SENSITIVE_SUFFIXES
+            .iter()
+            .any(|suffix| key.ends_with(suffix))
+}
+
+/// A tool call waiting for its outcome before it is recorded.
+pub(crate) struct PendingAudit {
+    store: ToolAuditStore,
+    entry: NewToolAudit,
+}
+
+impl PendingAudit {
+    /// Start an audit entry for `call`, owned by `adapter` if one was found.
+    pub(crate) fn new(
+        store: ToolAuditStore,
+        session_id: Option<String>,
+        task_id: String,
+        call: &ToolCall,
+        adapter: Option<&Arc<dyn ToolAdapter>>,
+    ) -> Self {
+        let arguments = match adapter {
+            Some(adapter) => adapter.redact_arguments(&call.name, &call.arguments),
+            None => redact_sensitive(&call.arguments),
+        };
+        Self {
+            store,
+            entry: NewToolAudit {
+                session_id,
+                task_id,
+                tool_name: call.name.clone(),
+                adapter_id: adapter.map(|a| a.adapter_id().to_string()),
+                arguments,
+                success: false,
+                result: String::new(),
+                duration_ms: 0,
+            },
+        }
+    }
+
+    /// Record the call's outcome.  Failures are logged, never propagated:
+    /// a broken audit store must not break the agent.
+    pub(crate) async fn finish(mut self, result: &ToolResult, duration: Duration) {
+        self.entry.success = !result.is_error;
+        self.entry.result = result.content.chars().take(RESULT_PREVIEW_CHARS).collect();
+        self.entry.duration_ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+        if let Err(e) = self.store.record(self.entry).await {
+            tracing::warn!(error = %e, "failed to record tool call in audit trail");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn redacts_secret_keys_at_any_depth() {
+        let redacted = redact_sensitive(&json!({
+            "url": "https://example.com",
+            "headers": {"Authorization": "Bearer abc", "X-Api-Key": "k"},
+            "accounts": [{"user": "ana", "password": "hunter2"}],
+            "client_secret": "s",
+            "max_tokens": 100,
+            "key": "reports/2026.csv",
+        }));
+        assert_eq!(
+            redacted,
+            json!({
+                "url": "https://example.com",
+                "headers": {"Authorization": REDACTED, "X-Api-Key": REDACTED},
+                "accounts": [{"user": "ana", "password": REDACTED}],
+                "client_secret": REDACTED,
+                "max_tokens": 100,
+                "key": "reports/2026.csv",
+            })
+        );
+    }
+}
@@ -2,7 +2,8 @@
 //! usage manageable during long-running agent sessions.
 //!
 //! When the conversation history exceeds [`CompactionConfig::max_messages`],
-//! the compaction logic:
+//! or its token count leaves less than [`CompactionConfig::max_output_tokens`]
+//! of the model's context window for the reply, the compaction logic:
 //!
 //! 1. Extracts the system prompt (if any).
 //! 2. Takes all messages *except* the most recent `keep_recent` messages.
@@ -18,6 +19,7 @@ use tracing::{debug, info};
 
 use crate::error::{AgentError, Result};
 use crate::llm::client::LlmClient;
+use crate::llm::tokens::{context_window, count_tokens, token_upper_bound};
 use crate::llm::types::{ChatRequest, LlmResponse, Message, Role};
 
 // ---------------------------------------------------------------------------
@@ -33,6 +35,12 @@ pub struct CompactionConfig {
     pub keep_recent: usize,
     /// Model to use for the summarization request.
     pub model: String,
+    /// Context window in tokens; `None` uses the known limit of the model
+    /// being compacted for.
+    pub context_window: Option<usize>,
+    /// Tokens reserved for the model's reply, normally the request's
+    /// `max_tokens`.
+    pub max_output_tokens: usize,
 }
 
 impl Default for CompactionConfig {
@@ -43,6 +51,8 @@ impl Default for CompactionConfig {
             // Empty string defers to the LLM client's currently active model,
             // which respects provider overrides (OpenAI, DeepSeek, Anthropic, etc.).
             model: String::new(),
+            context_window: None,
+            max_output_tokens: 4096,
         }
     }
 }
@@ -51,9 +61,21 @@ impl Default for CompactionConfig {
 // Public API
 // ---------------------------------------------------------------------------
 
-/// Check whether compaction is needed based on the current message count.
-pub fn needs_compaction(messages: &[Message], config: &CompactionConfig) -> bool {
-    messages.len() > config.max_messages
+/// Check whether compaction is needed before sending `messages` to `model`.
+///
+/// Compaction is needed when there are more than `max_messages` messages, or
+/// when their token count plus `max_output_tokens` exceeds the context
+/// window, which the provider would reject.
+pub fn needs_compaction(messages: &[Message], model: &str, config: &CompactionConfig) -> bool {
+    if messages.len() > config.max_messages {
+        return true;
+    }
+    let limit = config
+        .context_window
+        .unwrap_or_else(|| context_window(model))
+        .saturating_sub(config.max_output_tokens);
+    // Short conversations cannot reach the limit; skip the tokenizer.
+    token_upper_bound(messages) > limit && count_tokens(model, messages) > limit
 }
 
 /// Compact the conversation by summarizing older messages.
@@ -232,9 +254,10 @@ mod tests {
             max_messages: 50,
             keep_recent: 10,
             model: "test".into(),
+            ..CompactionConfig::default()
         };
         let messages = make_messages(10);
-        assert!(!needs_compaction(&messages, &config));
+        assert!(!needs_compaction(&messages, "test", &config));
     }
 
     #[test]
@@ -243,10 +266,11 @@ mod tests {
             max_messages: 10,
             keep_recent: 5,
             model: "test".into(),
+            ..CompactionConfig::default()
         };
         // 1 system + 10 conversation = 11 messages
         let messages = make_messages(10);
-        assert!(needs_compaction(&messages, &config));
+        assert!(needs_compaction(&messages, "test", &config));
     }
 
     #[test]
@@ -255,9 +279,35 @@ mod tests {
             max_messages: 5,
             keep_recent: 3,
             model: "test".into(),
+            ..CompactionConfig::default()
         };
         let messages = make_messages(20);
-        assert!(needs_compaction(&messages, &config));
+        assert!(needs_compaction(&messages, "test", &config));
+    }
+
+    #[test]
+    fn needs_compaction_at_token_limit() {
+        let model = "gpt-4o";
+        let config = CompactionConfig {
+            context_window: Some(1_000),
+            max_output_tokens: 200,
+            ..CompactionConfig::default()
+        };
+        let limit = 800;
+
+        // Grow a single message word by word until it crosses the limit.
+        let mut words = 0;
+        let messages = |words: usize| vec![Message::user("token ".repeat(words))];
+        while count_tokens(model, &messages(words + 1)) <= limit {
+            words += 1;
+        }
+        let under = messages(words);
+        let over = messages(words + 1);
+        assert!(count_tokens(model, &under) <= limit);
+        assert!(count_tokens(model, &over) > limit);
+
+        assert!(!needs_compaction(&under, model, &config));
+        assert!(needs_compaction(&over, model, &config));
     }
 
     #[test]
@@ -327,6 +377,7 @@ mod tests {
             max_messages: 50,
             keep_recent: 20,
             model: "test".into(),
+            ..CompactionConfig::default()
         };
 
         // 1 system + 5 conversation = 6 messages, well below keep_recent=20.
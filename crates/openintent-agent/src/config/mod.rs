@@ -8,7 +8,7 @@
 
 pub mod auth;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, SystemTime};
@@ -35,10 +35,13 @@ pub enum ConfigChange {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GatewayConfig {
     /// API keys for various providers.
+    #[serde(default)]
     pub api_keys: HashMap<String, String>,
     /// Service endpoints.
+    #[serde(default)]
     pub endpoints: HashMap<String, String>,
     /// Feature flags.
+    #[serde(default)]
     pub features: HashMap<String, bool>,
     /// Last update timestamp.
     #[serde(skip, default = "SystemTime::now")]
@@ -56,6 +59,28 @@ impl Default for GatewayConfig {
     }
 }
 
+impl GatewayConfig {
+    /// Whether the adapter `adapter_id` is enabled by its `adapter.<id>`
+    /// feature flag.  Adapters without a flag are enabled, so the flag
+    /// serves as a kill-switch for a misbehaving integration.
+    pub fn adapter_enabled(&self, adapter_id: &str) -> bool {
+        self.features
+            .get(&format!("adapter.{adapter_id}"))
+            .copied()
+            .unwrap_or(true)
+    }
+
+    /// Ids of the adapters switched off by `adapter.<id> = false` flags.
+    pub fn disabled_adapters(&self) -> HashSet<String> {
+        self.features
+            .iter()
+            .filter(|(_, enabled)| !**enabled)
+            .filter_map(|(flag, _)| flag.strip_prefix("adapter."))
+            .map(str::to_owned)
+            .collect()
+    }
+}
+
 /// Configuration manager with hot-reloading capabilities.
 #[derive(Debug)]
 pub struct ConfigManager {
@@ -120,6 +145,19 @@ impl ConfigManager {
         Ok(manager)
     }
 
+    /// Create a configuration manager for `config_path` without watching it.
+    ///
+    /// The file is loaded if it exists; [`save_to_file`](Self::save_to_file)
+    /// creates it otherwise.
+    pub fn from_file(config_path: PathBuf) -> Result<Self> {
+        let mut manager = Self {
+            config_path: Some(config_path),
+            ..Self::new()?
+        };
+        manager.load_from_file()?;
+        Ok(manager)
+    }
+
     /// Get a snapshot of the current configuration.
     pub fn get_config(&self) -> GatewayConfig {
         self.config.read().unwrap().clone()
@@ -269,6 +307,22 @@ impl ConfigManager {
         Ok(())
     }
 
+    /// Read a configuration value as a string, or `None` if it is unset.
+    pub fn get_value(&self, section: &str, key: &str) -> Result<Option<String>> {
+        let config = self.get_config();
+        let value = match section {
+            "api_keys" => config.api_keys.get(key).cloned(),
+            "endpoints" => config.endpoints.get(key).cloned(),
+            "features" => config.features.get(key).map(bool::to_string),
+            _ => {
+                return Err(AgentError::ConfigError {
+                    reason: format!("Unknown configuration section: {}", section),
+                });
+            }
+        };
+        Ok(value)
+    }
+
     /// Start the configuration monitoring loop.
     pub async fn start_monitoring(&self) -> Result<()> {
         let mut rx = self.subscribe();
@@ -343,6 +397,26 @@ mod tests {
         assert_eq!(config.features.get("test_feature"), Some(&true));
     }
 
+    #[test]
+    fn adapter_flags_default_to_enabled() {
+        let manager = ConfigManager::new().unwrap();
+        manager
+            .update_value("features", "adapter.browser", "false".to_string())
+            .unwrap();
+        manager
+            .update_value("features", "adapter.shell", "true".to_string())
+            .unwrap();
+
+        let config = manager.get_config();
+        assert!(!config.adapter_enabled("browser"));
+        assert!(config.adapter_enabled("shell"));
+        assert!(config.adapter_enabled("github"));
+        assert_eq!(
+            config.disabled_adapters(),
+            HashSet::from(["browser".to_string()])
+        );
+    }
+
     #[test]
     fn invalid_feature_value_returns_error() {
         let manager = ConfigManager::new().unwrap();
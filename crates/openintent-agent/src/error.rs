@@ -3,6 +3,8 @@
 //! All agent subsystems surface errors through [`AgentError`].  Each variant
 //! carries enough context for callers to decide how to handle the failure.
 
+use std::fmt;
+
 use uuid::Uuid;
 
 /// Unified error type for the agent runtime.
@@ -21,6 +23,18 @@ pub enum AgentError {
     #[error("llm stream error: {reason}")]
     LlmStreamError { reason: String },
 
+    /// The streaming connection dropped before the response completed and
+    /// could not be resumed.  `partial_text` holds the text received so far.
+    #[error("llm stream interrupted: {reason}")]
+    LlmStreamInterrupted {
+        reason: String,
+        partial_text: String,
+    },
+
+    /// A tool definition's `input_schema` is not a valid object schema.
+    #[error("invalid input schema for tool `{tool}`: {reason}")]
+    InvalidToolSchema { tool: String, reason: String },
+
     /// No suitable model configuration found for the requested provider.
     #[error("no model configured for provider: {provider}")]
     NoModelConfigured { provider: String },
@@ -38,10 +52,27 @@ pub enum AgentError {
     #[error("unknown tool: {tool_name}")]
     UnknownTool { tool_name: String },
 
+    /// The arguments of a tool call do not match the tool's input schema.
+    #[error("invalid arguments for tool `{tool_name}`: {reason}")]
+    InvalidToolArguments { tool_name: String, reason: String },
+
+    /// A tool's adapter needs credentials that are not configured.  `how`
+    /// tells the user how to supply them.
+    #[error("authentication required for `{provider}`: {how}")]
+    AuthRequired { provider: String, how: String },
+
     /// A tool invocation failed.
     #[error("tool execution failed for `{tool_name}`: {reason}")]
     ToolExecutionFailed { tool_name: String, reason: String },
 
+    /// An adapter failed.  `category` tells the runtime whether to retry,
+    /// re-authenticate, or surface the failure.
+    #[error("adapter error ({category}): {reason}")]
+    AdapterFailed {
+        category: ErrorCategory,
+        reason: String,
+    },
+
     // -- Planner errors ------------------------------------------------------
     /// The planner could not decompose the given intent into actionable steps.
     #[error("planning failed for intent: {reason}")]
@@ -89,6 +120,59 @@ pub enum AgentError {
 /// Convenience alias used throughout the agent crate.
 pub type Result<T> = std::result::Result<T, AgentError>;
 
+impl AgentError {
+    /// The failure category, for errors that originate in an adapter.
+    pub fn category(&self) -> Option<ErrorCategory> {
+        match self {
+            Self::AdapterFailed { category, .. } => Some(*category),
+            _ => None,
+        }
+    }
+
+    /// Whether retrying the same operation later may succeed.
+    pub fn is_retryable(&self) -> bool {
+        self.category().is_some_and(ErrorCategory::is_retryable)
+    }
+}
+
+/// Broad classification of adapter failures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCategory {
+    /// The adapter or its backing service is unavailable.
+    NotConnected,
+    /// Credentials are missing or were rejected.
+    AuthRequired,
+    /// The service is throttling requests; retry after a delay.
+    RateLimited,
+    /// The caller supplied bad input and should correct it.
+    InvalidInput,
+    /// The backing service or the adapter itself failed.
+    Upstream,
+}
+
+impl ErrorCategory {
+    /// Whether failures in this category are transient.
+    pub fn is_retryable(self) -> bool {
+        matches!(self, Self::RateLimited)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::NotConnected => "not_connected",
+            Self::AuthRequired => "auth_required",
+            Self::RateLimited => "rate_limited",
+            Self::InvalidInput => "invalid_input",
+            Self::Upstream => "upstream",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl From<reqwest::Error> for AgentError {
     fn from(err: reqwest::Error) -> Self {
         Self::LlmRequestFailed {
@@ -568,6 +568,7 @@ mod tests {
                 name: "echo".into(),
                 description: "Echoes input".into(),
                 input_schema: serde_json::json!({"type": "object"}),
+                destructive: false,
             }]
         }
 
@@ -592,6 +593,7 @@ mod tests {
                 name: "flaky_tool".into(),
                 description: "Fails then succeeds".into(),
                 input_schema: serde_json::json!({"type": "object"}),
+                destructive: false,
             }]
         }
 
@@ -622,6 +624,7 @@ mod tests {
                 name: "always_fail".into(),
                 description: "Always fails".into(),
                 input_schema: serde_json::json!({"type": "object"}),
+                destructive: false,
             }]
         }
 
@@ -649,6 +652,7 @@ mod tests {
                 name: "track".into(),
                 description: "Tracks execution order".into(),
                 input_schema: serde_json::json!({"type": "object"}),
+                destructive: false,
             }]
         }
 
@@ -27,8 +27,13 @@
 //! - [`planner`] -- Intent decomposition into executable plans.
 //! - [`executor`] -- Step-by-step plan execution with retries.
 //! - [`compaction`] -- Context window compaction via conversation summarization.
+//! - [`truncation`] -- Structure-preserving truncation of oversized tool results.
+//! - [`permissions`] -- Role-based gating of destructive and admin-only tools.
+//! - [`audit`] -- Persistent audit trail of tool calls, with argument redaction.
+//! - [`replay`] -- Dry-run or live replay of a session's recorded tool calls.
 //! - [`error`] -- Agent error types.
 
+pub mod audit;
 pub mod compaction;
 pub mod config;
 pub mod error;
@@ -37,25 +42,35 @@ pub mod executor;
 pub mod llm;
 pub mod memory;
 pub mod orchestrator;
+pub mod permissions;
 pub mod planner;
+pub mod rate_limit;
+pub mod replay;
 pub mod runtime;
+pub mod truncation;
 
 // Re-export the most commonly used types at the crate root.
+pub use audit::redact_sensitive;
 pub use compaction::{CompactionConfig, compact_messages, needs_compaction};
-pub use error::{AgentError, Result};
+pub use error::{AgentError, ErrorCategory, Result};
 pub use evolution::{EvolutionConfig, EvolutionEngine, PatternMemory, UnhandledIntent};
 pub use executor::{Executor, ExecutorConfig, StepResult};
 pub use llm::{
-    ChatRequest, LlmClient, LlmClientConfig, LlmProvider, LlmResponse, Message, ModelConfig,
-    ModelRouter, Role, ToolCall, ToolDefinition, ToolResult,
+    ChatRequest, EmbeddingConfig, LlmBackend, LlmClient, LlmClientConfig, LlmProvider,
+    LlmResponse, Message, ModelConfig, ModelRouter, ResponseCache, ResponseCacheConfig, Role,
+    ScriptedBackend, ToolCall, ToolDefinition, ToolResult,
 };
 pub use memory::{AutoMemoryConfig, AutoMemoryManager, MemoryEntry, MemoryStore, MemoryType};
 pub use orchestrator::{
     OrchestratedTask, Orchestrator, OrchestratorStatus, TaskResult, WorkerSpecialization,
     WorkerStatus,
 };
+pub use permissions::{ToolAccess, check_role, classify_tool};
 pub use planner::{Plan, Planner, PlannerConfig, Step, StepStatus};
+pub use rate_limit::{RateLimit, RateLimiter};
+pub use replay::{ReplayMode, ReplayOutcome, ReplayStep, replay};
 pub use runtime::{
-    AgentConfig, AgentContext, AgentResponse, PolicyCheckerFn, TextDeltaCallback, ToolAdapter,
-    ToolPermission, ToolStartCallback, react_loop,
+    AgentConfig, AgentContext, AgentResponse, PolicyCheckerFn, PromptFn, TextDeltaCallback,
+    ToolAdapter, ToolPermission, ToolStartCallback, ToolTiming, TurnTiming, confirm, react_loop,
 };
+pub use truncation::{TruncationConfig, TruncationStrategy, truncate_tool_result};
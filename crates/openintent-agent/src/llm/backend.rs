@@ -0,0 +1,74 @@
+//! Pluggable transport for [`LlmClient`](super::LlmClient).
+//!
+//! By default the client talks HTTP to Anthropic or an OpenAI-compatible
+//! endpoint.  Installing an [`LlmBackend`] with
+//! [`LlmClient::with_backend`](super::LlmClient::with_backend) replaces that
+//! transport, which lets tests drive the agent runtime offline with a
+//! [`ScriptedBackend`] that replays canned tool calls and answers.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use crate::error::{AgentError, Result};
+use crate::llm::types::{ChatRequest, LlmResponse};
+
+/// A source of LLM responses.
+#[async_trait]
+pub trait LlmBackend: fmt::Debug + Send + Sync {
+    /// Answer one chat request.
+    async fn chat(&self, request: &ChatRequest) -> Result<LlmResponse>;
+}
+
+/// A backend that returns a fixed sequence of responses, one per request,
+/// and records every request it receives.
+#[derive(Debug, Default)]
+pub struct ScriptedBackend {
+    responses: Mutex<VecDeque<LlmResponse>>,
+    requests: Mutex<Vec<ChatRequest>>,
+}
+
+impl ScriptedBackend {
+    /// Create a backend that answers with `responses` in order.
+    pub fn new(responses: impl IntoIterator<Item = LlmResponse>) -> Self {
+        Self {
+            responses: Mutex::new(responses.into_iter().collect()),
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The requests received so far, oldest first.
+    pub fn requests(&self) -> Vec<ChatRequest> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Number of scripted responses not yet consumed.
+    pub fn remaining(&self) -> usize {
+        self.responses
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .len()
+    }
+}
+
+#[async_trait]
+impl LlmBackend for ScriptedBackend {
+    async fn chat(&self, request: &ChatRequest) -> Result<LlmResponse> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(request.clone());
+        self.responses
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .pop_front()
+            .ok_or_else(|| AgentError::LlmRequestFailed {
+                reason: "scripted backend has no responses left".into(),
+            })
+    }
+}
@@ -4,19 +4,20 @@
 //! API** (including OpenAI-compatible endpoints such as Ollama, Together, and
 //! vLLM) with both streaming SSE and non-streaming modes.
 
+use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
+use std::time::Instant;
 
-use futures::StreamExt;
 use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue};
 use serde_json::{Value, json};
 
 use crate::error::{AgentError, Result};
-use crate::llm::streaming::SseParser;
-use crate::llm::streaming_openai::OpenAiStreamAccumulator;
-use crate::llm::types::{
-    ChatRequest, LlmResponse, Message, Role, StreamDelta, StreamEvent, ToolCall, ToolDefinition,
-    Usage,
-};
+use crate::llm::backend::LlmBackend;
+use crate::llm::response_cache::ResponseCache;
+use crate::llm::embeddings::EmbeddingConfig;
+use crate::llm::prompt_cache::add_cache_breakpoints;
+use crate::llm::transcript::{DEFAULT_TRANSCRIPT_DIR, TranscriptLog};
+use crate::llm::types::{ChatRequest, LlmResponse, Message, Role, ToolCall, ToolDefinition, Usage};
 
 // ---------------------------------------------------------------------------
 // Constants
@@ -64,6 +65,14 @@ pub struct LlmClientConfig {
     pub default_model: String,
     /// Default maximum tokens per response.
     pub max_tokens: u32,
+    /// Write each request/response pair, with credentials redacted, to a
+    /// rotating JSONL file in [`transcript_dir`](Self::transcript_dir).
+    pub log_transcripts: bool,
+    /// Directory for transcript files (default `data/transcripts`).
+    pub transcript_dir: PathBuf,
+    /// Mark long system prompts and tool lists as cacheable with Anthropic
+    /// `cache_control` breakpoints (default on; ignored by OpenAI).
+    pub prompt_caching: bool,
 }
 
 impl LlmClientConfig {
@@ -75,6 +84,9 @@ impl LlmClientConfig {
             base_url: ANTHROPIC_BASE_URL.to_owned(),
             default_model: model.into(),
             max_tokens: 4096,
+            log_transcripts: false,
+            transcript_dir: PathBuf::from(DEFAULT_TRANSCRIPT_DIR),
+            prompt_caching: true,
         }
     }
 
@@ -86,6 +98,9 @@ impl LlmClientConfig {
             base_url: OPENAI_BASE_URL.to_owned(),
             default_model: model.into(),
             max_tokens: 4096,
+            log_transcripts: false,
+            transcript_dir: PathBuf::from(DEFAULT_TRANSCRIPT_DIR),
+            prompt_caching: true,
         }
     }
 
@@ -102,6 +117,9 @@ impl LlmClientConfig {
             base_url: base_url.into(),
             default_model: model.into(),
             max_tokens: 4096,
+            log_transcripts: false,
+            transcript_dir: PathBuf::from(DEFAULT_TRANSCRIPT_DIR),
+            prompt_caching: true,
         }
     }
 }
@@ -124,6 +142,14 @@ pub struct LlmClient {
     /// without re-creating the client.
     overrides: Arc<RwLock<RuntimeOverrides>>,
     http: reqwest::Client,
+    /// Cache of temperature-0 responses; `None` unless enabled.
+    pub(super) cache: Option<ResponseCache>,
+    /// Transcript writer; `None` unless `log_transcripts` is set.
+    transcripts: Option<Arc<TranscriptLog>>,
+    /// Replaces the HTTP transport when set (e.g. a scripted test backend).
+    backend: Option<Arc<dyn LlmBackend>>,
+    /// Embedding settings; `None` uses the provider defaults.
+    embeddings: Option<Arc<EmbeddingConfig>>,
 }
 
 /// Mutable runtime overrides for the LLM client.
@@ -162,13 +188,56 @@ impl LlmClient {
             default_model: None,
         }));
 
+        let transcripts = config
+            .log_transcripts
+            .then(|| Arc::new(TranscriptLog::new(&config.transcript_dir)));
+
         Ok(Self {
             config: Arc::new(config),
             overrides,
             http,
+            cache: None,
+            transcripts,
+            backend: None,
+            embeddings: None,
         })
     }
 
+    /// Send every request to `backend` instead of the configured provider.
+    ///
+    /// The backend replaces the whole transport: the response cache and
+    /// transcripts are bypassed, and streaming calls deliver a text answer
+    /// as a single delta with zero usage.
+    pub fn with_backend(mut self, backend: Arc<dyn LlmBackend>) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
+    /// Use `config` for [`embed`](Self::embed) instead of the provider
+    /// defaults.
+    pub fn with_embeddings(mut self, config: EmbeddingConfig) -> Self {
+        self.embeddings = Some(Arc::new(config));
+        self
+    }
+
+    /// The embedding settings in effect for the current provider.
+    pub fn embedding_config(&self) -> EmbeddingConfig {
+        self.embeddings
+            .as_deref()
+            .cloned()
+            .unwrap_or_else(|| EmbeddingConfig::for_provider(&self.provider()))
+    }
+
+    /// The HTTP client shared by chat and embedding requests.
+    pub(super) fn http(&self) -> &reqwest::Client {
+        &self.http
+    }
+
+    /// The transcript writer, if transcripts are enabled.
+    pub(super) fn transcripts(&self) -> Option<&TranscriptLog> {
+        self.transcripts.as_deref()
+    }
+
     /// Returns the current provider (respects runtime overrides).
     pub fn provider(&self) -> LlmProvider {
         self.overrides
@@ -339,7 +408,7 @@ impl LlmClient {
     }
 
     /// Read the current API key (snapshot).
-    fn current_api_key(&self) -> String {
+    pub(super) fn current_api_key(&self) -> String {
         self.overrides
             .read()
             .map(|o| o.api_key.clone())
@@ -347,7 +416,7 @@ impl LlmClient {
     }
 
     /// Read the current base URL (snapshot, respects overrides).
-    fn current_base_url(&self) -> String {
+    pub(super) fn current_base_url(&self) -> String {
         self.overrides
             .read()
             .ok()
@@ -356,7 +425,7 @@ impl LlmClient {
     }
 
     /// Read the current default model (snapshot, respects overrides).
-    fn current_default_model(&self) -> String {
+    pub fn current_default_model(&self) -> String {
         self.overrides
             .read()
             .ok()
@@ -371,12 +440,26 @@ impl LlmClient {
     /// Send a chat request and return the full response (non-streaming).
     ///
     /// This blocks until the entire response is received and then parses it
-    /// into an [`LlmResponse`].
+    /// into an [`LlmResponse`].  Tool schemas are validated before anything
+    /// is sent; see [`ChatRequest::validate_tools`].
     pub async fn chat(&self, request: &ChatRequest) -> Result<LlmResponse> {
-        match self.provider() {
+        request.validate_tools()?;
+        if let Some(ref backend) = self.backend {
+            return backend.chat(request).await;
+        }
+        let key = self.response_cache_key(request);
+        if let Some(cached) = self.cached_response(key.as_deref()).await {
+            return Ok(cached);
+        }
+        let started = Instant::now();
+        let result = match self.provider() {
             LlmProvider::Anthropic => self.chat_anthropic(request).await,
             LlmProvider::OpenAI => self.chat_openai(request).await,
-        }
+        };
+        self.record_transcript(request, started, result.as_ref().map(|r| (r, None)));
+        let response = result?;
+        self.cache_response(key.as_deref(), &response).await;
+        Ok(response)
     }
 
     /// Send a chat request using streaming SSE and return the aggregated
@@ -385,10 +468,23 @@ impl LlmClient {
     /// Internally consumes the SSE stream, accumulating text and tool-call
     /// fragments until the message is complete.
     pub async fn stream_chat(&self, request: &ChatRequest) -> Result<(LlmResponse, Usage)> {
-        match self.provider() {
+        request.validate_tools()?;
+        if let Some(ref backend) = self.backend {
+            return Ok((backend.chat(request).await?, Usage::default()));
+        }
+        let key = self.response_cache_key(request);
+        if let Some(cached) = self.cached_response(key.as_deref()).await {
+            return Ok((cached, Usage::default()));
+        }
+        let started = Instant::now();
+        let result = match self.provider() {
             LlmProvider::Anthropic => self.stream_chat_anthropic(request).await,
             LlmProvider::OpenAI => self.stream_chat_openai(request, &mut |_| {}).await,
-        }
+        };
+        self.record_transcript(request, started, result.as_ref().map(|(r, u)| (r, Some(u))));
+        let (response, usage) = result?;
+        self.cache_response(key.as_deref(), &response).await;
+        Ok((response, usage))
     }
 
     /// Send a chat request using streaming SSE, invoking a callback for each
@@ -403,13 +499,34 @@ impl LlmClient {
     where
         F: FnMut(&str) + Send,
     {
-        match self.provider() {
+        request.validate_tools()?;
+        if let Some(ref backend) = self.backend {
+            let response = backend.chat(request).await?;
+            if let LlmResponse::Text(ref text) = response {
+                on_text(text);
+            }
+            return Ok((response, Usage::default()));
+        }
+        let key = self.response_cache_key(request);
+        if let Some(cached) = self.cached_response(key.as_deref()).await {
+            // Replay the cached text so streaming UIs still render it.
+            if let LlmResponse::Text(ref text) = cached {
+                on_text(text);
+            }
+            return Ok((cached, Usage::default()));
+        }
+        let started = Instant::now();
+        let result = match self.provider() {
             LlmProvider::Anthropic => {
                 self.stream_chat_anthropic_with_callback(request, &mut on_text)
                     .await
             }
             LlmProvider::OpenAI => self.stream_chat_openai(request, &mut on_text).await,
-        }
+        };
+        self.record_transcript(request, started, result.as_ref().map(|(r, u)| (r, Some(u))));
+        let (response, usage) = result?;
+        self.cache_response(key.as_deref(), &response).await;
+        Ok((response, usage))
     }
 
     // =======================================================================
@@ -451,33 +568,10 @@ impl LlmClient {
             .await
     }
 
-    /// Streaming Anthropic chat with a text callback.
-    async fn stream_chat_anthropic_with_callback<F>(
-        &self,
-        request: &ChatRequest,
-        on_text: &mut F,
-    ) -> Result<(LlmResponse, Usage)>
-    where
-        F: FnMut(&str),
-    {
-        let body = self.build_anthropic_request_body(request, true);
-        let resp = self.send_anthropic_request(&body).await?;
-
-        let status = resp.status();
-        if !status.is_success() {
-            let text = resp.text().await.unwrap_or_default();
-            return Err(AgentError::LlmRequestFailed {
-                reason: format!("API returned {status}: {text}"),
-            });
-        }
-
-        self.consume_anthropic_stream(resp, on_text).await
-    }
-
     // -- Anthropic request building ------------------------------------------
 
     /// Build the JSON body for the Anthropic Messages API.
-    fn build_anthropic_request_body(&self, request: &ChatRequest, stream: bool) -> Value {
+    pub(super) fn build_anthropic_request_body(&self, request: &ChatRequest, stream: bool) -> Value {
         let (system_text, messages) = messages_to_anthropic(&request.messages);
         let default_model = self.current_default_model();
 
@@ -503,6 +597,10 @@ impl LlmClient {
             body["tools"] = tools_to_anthropic(&request.tools);
         }
 
+        if self.config.prompt_caching {
+            add_cache_breakpoints(&mut body);
+        }
+
         if stream {
             body["stream"] = json!(true);
         }
@@ -515,9 +613,30 @@ impl LlmClient {
     /// Supports both standard API keys (`x-api-key` header) and OAuth tokens
     /// (`Authorization: Bearer` header).  OAuth tokens are detected by their
     /// `sk-ant-oat` prefix.
-    async fn send_anthropic_request(&self, body: &Value) -> Result<reqwest::Response> {
-        let url = format!("{}/v1/messages", self.current_base_url());
+    pub(super) async fn send_anthropic_request(&self, body: &Value) -> Result<reqwest::Response> {
+        let url = self.anthropic_url();
+        let headers = self.anthropic_headers()?;
+        let is_oauth = headers.contains_key(AUTHORIZATION);
+
+        tracing::debug!(url = %url, model = %body["model"], provider = "anthropic", is_oauth = is_oauth, "sending LLM request");
 
+        self.http
+            .post(&url)
+            .headers(headers)
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| AgentError::LlmRequestFailed {
+                reason: e.to_string(),
+            })
+    }
+
+    pub(super) fn anthropic_url(&self) -> String {
+        format!("{}/v1/messages", self.current_base_url())
+    }
+
+    /// Build the Anthropic request headers for the current API key.
+    pub(super) fn anthropic_headers(&self) -> Result<HeaderMap> {
         let mut headers = HeaderMap::new();
 
         // Snapshot the current API key (may have been refreshed at runtime).
@@ -555,63 +674,7 @@ impl LlmClient {
             HeaderValue::from_static(ANTHROPIC_VERSION),
         );
         headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-
-        tracing::debug!(url = %url, model = %body["model"], provider = "anthropic", is_oauth = is_oauth, "sending LLM request");
-
-        self.http
-            .post(&url)
-            .headers(headers)
-            .json(body)
-            .send()
-            .await
-            .map_err(|e| AgentError::LlmRequestFailed {
-                reason: e.to_string(),
-            })
-    }
-
-    // -- Anthropic streaming -------------------------------------------------
-
-    /// Consume an Anthropic SSE stream and aggregate into a final response with usage.
-    async fn consume_anthropic_stream<F>(
-        &self,
-        resp: reqwest::Response,
-        on_text: &mut F,
-    ) -> Result<(LlmResponse, Usage)>
-    where
-        F: FnMut(&str),
-    {
-        let mut parser = SseParser::new();
-        let mut accumulator = StreamAccumulator::new();
-
-        let mut byte_stream = resp.bytes_stream();
-        let mut line_buffer = String::new();
-
-        while let Some(chunk_result) = byte_stream.next().await {
-            let chunk = chunk_result.map_err(|e| AgentError::LlmStreamError {
-                reason: format!("stream read error: {e}"),
-            })?;
-
-            let text = std::str::from_utf8(&chunk).map_err(|e| AgentError::LlmStreamError {
-                reason: format!("invalid UTF-8 in stream: {e}"),
-            })?;
-
-            line_buffer.push_str(text);
-
-            while let Some(newline_pos) = line_buffer.find('\n') {
-                let line = line_buffer[..newline_pos].to_owned();
-                line_buffer = line_buffer[newline_pos + 1..].to_owned();
-
-                if let Some(event) = parser.parse_line(&line)? {
-                    accumulator.apply(&event, on_text);
-
-                    if matches!(event, StreamEvent::MessageStop) {
-                        return accumulator.into_response();
-                    }
-                }
-            }
-        }
-
-        accumulator.into_response()
+        Ok(headers)
     }
 
     // =======================================================================
@@ -644,33 +707,10 @@ impl LlmClient {
         parse_openai_response(&v)
     }
 
-    /// Streaming OpenAI chat with a text callback.
-    async fn stream_chat_openai<F>(
-        &self,
-        request: &ChatRequest,
-        on_text: &mut F,
-    ) -> Result<(LlmResponse, Usage)>
-    where
-        F: FnMut(&str),
-    {
-        let body = self.build_openai_request_body(request, true);
-        let resp = self.send_openai_request(&body).await?;
-
-        let status = resp.status();
-        if !status.is_success() {
-            let text = resp.text().await.unwrap_or_default();
-            return Err(AgentError::LlmRequestFailed {
-                reason: format!("API returned {status}: {text}"),
-            });
-        }
-
-        self.consume_openai_stream(resp, on_text).await
-    }
-
     // -- OpenAI request building ---------------------------------------------
 
     /// Build the JSON body for the OpenAI Chat Completions API.
-    fn build_openai_request_body(&self, request: &ChatRequest, stream: bool) -> Value {
+    pub(super) fn build_openai_request_body(&self, request: &ChatRequest, stream: bool) -> Value {
         let messages = messages_to_openai(&request.messages);
         let default_model = self.current_default_model();
 
@@ -700,18 +740,9 @@ impl LlmClient {
     }
 
     /// Send the HTTP request to the OpenAI Chat Completions API endpoint.
-    async fn send_openai_request(&self, body: &Value) -> Result<reqwest::Response> {
-        let url = format!("{}/chat/completions", self.current_base_url());
-
-        let mut headers = HeaderMap::new();
-        let auth_value = format!("Bearer {}", self.current_api_key());
-        headers.insert(
-            AUTHORIZATION,
-            HeaderValue::from_str(&auth_value).map_err(|e| AgentError::LlmRequestFailed {
-                reason: format!("invalid authorization header: {e}"),
-            })?,
-        );
-        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    pub(super) async fn send_openai_request(&self, body: &Value) -> Result<reqwest::Response> {
+        let url = self.openai_url();
+        let headers = self.openai_headers()?;
 
         tracing::debug!(url = %url, model = %body["model"], provider = "openai", "sending LLM request");
 
@@ -726,49 +757,24 @@ impl LlmClient {
             })
     }
 
-    // -- OpenAI streaming ----------------------------------------------------
-
-    /// Consume an OpenAI SSE stream and aggregate into a final response with usage.
-    async fn consume_openai_stream<F>(
-        &self,
-        resp: reqwest::Response,
-        on_text: &mut F,
-    ) -> Result<(LlmResponse, Usage)>
-    where
-        F: FnMut(&str),
-    {
-        let mut accumulator = OpenAiStreamAccumulator::new();
-
-        let mut byte_stream = resp.bytes_stream();
-        let mut line_buffer = String::new();
-
-        while let Some(chunk_result) = byte_stream.next().await {
-            let chunk = chunk_result.map_err(|e| AgentError::LlmStreamError {
-                reason: format!("stream read error: {e}"),
-            })?;
-
-            let text = std::str::from_utf8(&chunk).map_err(|e| AgentError::LlmStreamError {
-                reason: format!("invalid UTF-8 in stream: {e}"),
-            })?;
-
-            line_buffer.push_str(text);
-
-            while let Some(newline_pos) = line_buffer.find('\n') {
-                let line = line_buffer[..newline_pos].to_owned();
-                line_buffer = line_buffer[newline_pos + 1..].to_owned();
-
-                if let Some(delta_text) = accumulator.feed_line(&line)? {
-                    on_text(&delta_text);
-                }
-
-                if accumulator.is_done() {
-                    return accumulator.into_response();
-                }
-            }
-        }
+    pub(super) fn openai_url(&self) -> String {
+        format!("{}/chat/completions", self.current_base_url())
+    }
 
-        accumulator.into_response()
+    /// Build the OpenAI request headers for the current API key.
+    pub(super) fn openai_headers(&self) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        let auth_value = format!("Bearer {}", self.current_api_key());
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&auth_value).map_err(|e| AgentError::LlmRequestFailed {
+                reason: format!("invalid authorization header: {e}"),
+            })?,
+        );
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        Ok(headers)
     }
+
 }
 
 // ===========================================================================
@@ -1029,126 +1035,6 @@ pub fn parse_openai_response(v: &Value) -> Result<LlmResponse> {
     Ok(LlmResponse::Text(content.to_owned()))
 }
 
-// ---------------------------------------------------------------------------
-// Anthropic stream accumulator (unchanged from original)
-// ---------------------------------------------------------------------------
-
-/// Accumulates fragments from Anthropic streaming events into a complete
-/// response.
-#[derive(Debug, Default)]
-struct StreamAccumulator {
-    /// Accumulated text output.
-    text: String,
-
-    /// Tool calls being built up from streaming fragments.
-    tool_calls: Vec<ToolCallBuilder>,
-
-    /// The stop reason, if received.
-    stop_reason: Option<String>,
-
-    /// Usage tracking (populated from message_start and message_delta events).
-    usage: Usage,
-}
-
-/// In-progress tool call being assembled from streaming deltas.
-#[derive(Debug)]
-struct ToolCallBuilder {
-    id: String,
-    name: String,
-    /// Accumulated JSON input string.
-    input_json: String,
-}
-
-impl StreamAccumulator {
-    fn new() -> Self {
-        Self::default()
-    }
-
-    /// Apply a single stream event to the accumulator.
-    fn apply<F>(&mut self, event: &StreamEvent, on_text: &mut F)
-    where
-        F: FnMut(&str),
-    {
-        match event {
-            StreamEvent::MessageStart { input_tokens, .. } => {
-                self.usage.input_tokens = *input_tokens;
-            }
-
-            StreamEvent::ContentBlockStart {
-                content_type,
-                id,
-                name,
-                ..
-            } => {
-                if content_type == "tool_use" {
-                    self.tool_calls.push(ToolCallBuilder {
-                        id: id.clone().unwrap_or_default(),
-                        name: name.clone().unwrap_or_default(),
-                        input_json: String::new(),
-                    });
-                }
-            }
-
-            StreamEvent::ContentBlockDelta { delta, .. } => match delta {
-                StreamDelta::TextDelta(t) => {
-                    self.text.push_str(t);
-                    on_text(t);
-                }
-                StreamDelta::InputJsonDelta(j) => {
-                    if let Some(builder) = self.tool_calls.last_mut() {
-                        builder.input_json.push_str(j);
-                    }
-                }
-            },
-
-            StreamEvent::MessageDelta {
-                stop_reason,
-                output_tokens,
-            } => {
-                self.stop_reason = stop_reason.clone();
-                self.usage.output_tokens = *output_tokens;
-            }
-
-            _ => {}
-        }
-    }
-
-    /// Convert the accumulated state into a final [`LlmResponse`] and [`Usage`].
-    fn into_response(self) -> Result<(LlmResponse, Usage)> {
-        let usage = self.usage;
-        if self.tool_calls.is_empty() {
-            Ok((LlmResponse::Text(self.text), usage))
-        } else {
-            let calls: Result<Vec<ToolCall>> = self
-                .tool_calls
-                .into_iter()
-                .map(|b| {
-                    let arguments: Value = if b.input_json.is_empty() {
-                        Value::Object(Default::default())
-                    } else {
-                        serde_json::from_str(&b.input_json).map_err(|e| {
-                            AgentError::LlmParseFailed {
-                                reason: format!(
-                                    "invalid JSON in tool call `{}` input: {e}",
-                                    b.name
-                                ),
-                            }
-                        })?
-                    };
-
-                    Ok(ToolCall {
-                        id: b.id,
-                        name: b.name,
-                        arguments,
-                    })
-                })
-                .collect();
-
-            Ok((LlmResponse::ToolCalls(calls?), usage))
-        }
-    }
-}
-
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -1207,6 +1093,7 @@ mod tests {
                     },
                     "required": ["path"]
                 }),
+                destructive: false,
             }],
             temperature: None,
             max_tokens: None,
@@ -1219,6 +1106,41 @@ mod tests {
         assert_eq!(body["tools"][0]["name"], "read_file");
     }
 
+    #[test]
+    fn build_anthropic_request_body_prompt_caching() {
+        let long_system = "Follow the house style. ".repeat(200);
+        let request = ChatRequest {
+            model: String::new(),
+            messages: vec![Message::system(long_system.clone()), Message::user("Hello")],
+            tools: vec![ToolDefinition {
+                name: "read_file".into(),
+                description: "Read a file".into(),
+                input_schema: serde_json::json!({"type": "object"}),
+                destructive: false,
+            }],
+            temperature: None,
+            max_tokens: None,
+            stream: false,
+        };
+
+        let config = LlmClientConfig::anthropic("test-key", "claude-sonnet-4-20250514");
+        let body = LlmClient::new(config)
+            .unwrap()
+            .build_anthropic_request_body(&request, false);
+        assert_eq!(body["system"][0]["type"], "text");
+        assert_eq!(body["system"][0]["text"], long_system.as_str());
+        assert_eq!(body["system"][0]["cache_control"]["type"], "ephemeral");
+        // The tool block alone is too short to be worth its own breakpoint.
+        assert!(body["tools"][0].get("cache_control").is_none());
+
+        let mut config = LlmClientConfig::anthropic("test-key", "claude-sonnet-4-20250514");
+        config.prompt_caching = false;
+        let body = LlmClient::new(config)
+            .unwrap()
+            .build_anthropic_request_body(&request, false);
+        assert_eq!(body["system"], long_system.as_str());
+    }
+
     #[test]
     fn build_anthropic_request_body_tool_results() {
         let config = LlmClientConfig::anthropic("test-key", "claude-sonnet-4-20250514");
@@ -1410,6 +1332,7 @@ mod tests {
                 },
                 "required": ["path"]
             }),
+            destructive: false,
         }];
 
         let wire = tools_to_openai(&tools);
@@ -1554,6 +1477,7 @@ mod tests {
                         "city": {"type": "string"}
                     }
                 }),
+                destructive: false,
             }],
             temperature: None,
             max_tokens: None,
@@ -1570,4 +1494,43 @@ mod tests {
         assert_eq!(tools[0]["type"], "function");
         assert_eq!(tools[0]["function"]["name"], "get_weather");
     }
+
+    fn stream_request() -> ChatRequest {
+        ChatRequest {
+            model: String::new(),
+            messages: vec![Message::user("hi")],
+            tools: vec![],
+            temperature: None,
+            max_tokens: None,
+            stream: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_tool_schema_is_rejected_before_sending() {
+        let (base, calls) =
+            crate::llm::test_support::mock_server("application/json", |_| "{}".to_owned()).await;
+        let client = LlmClient::new(LlmClientConfig::openai_compatible("k", "mock", base)).unwrap();
+        let mut request = stream_request();
+        request.tools.push(ToolDefinition {
+            name: "fs_read_file".into(),
+            description: "Read a file".into(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": { "path": { "type": "string" } },
+                "required": ["encoding"]
+            }),
+            destructive: false,
+        });
+
+        let err = client
+            .chat(&request)
+            .await
+            .expect_err("schema must be rejected");
+
+        assert!(
+            matches!(err, AgentError::InvalidToolSchema { ref tool, .. } if tool == "fs_read_file")
+        );
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 0);
+    }
 }
@@ -0,0 +1,230 @@
+//! Text embeddings for semantic memory.
+//!
+//! [`LlmClient::embed`] turns texts into vectors via an OpenAI-style
+//! `POST {base_url}/embeddings` endpoint.  OpenAI and compatible servers
+//! (Ollama, vLLM, Together) serve embeddings next to chat completions, so by
+//! default the client's own base URL and key are reused.  Anthropic has no
+//! embeddings API and recommends Voyage AI, which speaks the same protocol
+//! but needs its own key; set one with [`LlmClient::with_embeddings`].
+//! Inputs are sent in batches of [`EmbeddingConfig::batch_size`].
+
+use reqwest::header::{AUTHORIZATION, HeaderValue};
+use serde_json::{Value, json};
+
+use crate::error::{AgentError, Result};
+use crate::llm::client::{LlmClient, LlmProvider};
+
+/// Default embedding model for OpenAI-compatible providers.
+const OPENAI_EMBEDDING_MODEL: &str = "text-embedding-3-small";
+
+/// Voyage AI base URL, used for embeddings when the provider is Anthropic.
+const VOYAGE_BASE_URL: &str = "https://api.voyageai.com/v1";
+
+/// Default Voyage AI embedding model.
+const VOYAGE_EMBEDDING_MODEL: &str = "voyage-3";
+
+/// Default number of texts sent per request.
+const DEFAULT_BATCH_SIZE: usize = 96;
+
+/// Configuration for [`LlmClient::embed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbeddingConfig {
+    /// Embedding model identifier.
+    pub model: String,
+    /// Endpoint base URL; `None` uses the chat endpoint for OpenAI-compatible
+    /// providers and Voyage AI for Anthropic.
+    pub base_url: Option<String>,
+    /// API key; `None` reuses the chat API key.  Required for Anthropic,
+    /// whose key is not accepted by Voyage AI.
+    pub api_key: Option<String>,
+    /// Maximum number of texts per request.
+    pub batch_size: usize,
+}
+
+impl EmbeddingConfig {
+    /// Defaults for `provider`.
+    pub fn for_provider(provider: &LlmProvider) -> Self {
+        let (model, base_url) = match provider {
+            LlmProvider::OpenAI => (OPENAI_EMBEDDING_MODEL, None),
+            LlmProvider::Anthropic => (VOYAGE_EMBEDDING_MODEL, Some(VOYAGE_BASE_URL.to_owned())),
+        };
+        Self {
+            model: model.to_owned(),
+            base_url,
+            api_key: None,
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    /// Set the embedding model.
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Set the endpoint base URL.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Set a dedicated API key for the embedding endpoint.
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Set the maximum number of texts per request.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+}
+
+impl LlmClient {
+    /// Embed `texts`, returning one vector per text in input order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AgentError::MissingApiKey`] if the provider is Anthropic and
+    /// no Voyage AI key is configured, and [`AgentError::LlmRequestFailed`] or
+    /// [`AgentError::LlmParseFailed`] if a batch fails.
+    pub async fn embed(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        let config = self.embedding_config();
+        let provider = self.provider();
+        let api_key = match (&config.api_key, &provider) {
+            (Some(key), _) => key.clone(),
+            (None, LlmProvider::OpenAI) => self.current_api_key(),
+            (None, LlmProvider::Anthropic) => {
+                return Err(AgentError::MissingApiKey {
+                    provider: "voyage".into(),
+                });
+            }
+        };
+        let base_url = config
+            .base_url
+            .clone()
+            .unwrap_or_else(|| self.current_base_url());
+        let url = format!("{}/embeddings", base_url.trim_end_matches('/'));
+        let auth = HeaderValue::from_str(&format!("Bearer {api_key}")).map_err(|e| {
+            AgentError::LlmRequestFailed {
+                reason: format!("invalid authorization header: {e}"),
+            }
+        })?;
+
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(config.batch_size.max(1)) {
+            tracing::debug!(url = %url, model = %config.model, count = batch.len(), "requesting embeddings");
+            let resp = self
+                .http()
+                .post(&url)
+                .header(AUTHORIZATION, auth.clone())
+                .json(&json!({ "model": config.model, "input": batch }))
+                .send()
+                .await?;
+            let status = resp.status();
+            let text = resp.text().await?;
+            if !status.is_success() {
+                return Err(AgentError::LlmRequestFailed {
+                    reason: format!("embeddings API returned {status}: {text}"),
+                });
+            }
+            let v: Value = serde_json::from_str(&text).map_err(|e| AgentError::LlmParseFailed {
+                reason: format!("invalid JSON response: {e}"),
+            })?;
+            embeddings.extend(parse_embeddings(&v, batch.len())?);
+        }
+        Ok(embeddings)
+    }
+}
+
+/// Extract `expected` vectors from an embeddings response, ordered by their
+/// `index` field.
+fn parse_embeddings(v: &Value, expected: usize) -> Result<Vec<Vec<f32>>> {
+    let parse_failed = |reason: String| AgentError::LlmParseFailed { reason };
+    let data = v["data"]
+        .as_array()
+        .ok_or_else(|| parse_failed("embeddings response has no `data` array".into()))?;
+    if data.len() != expected {
+        return Err(parse_failed(format!(
+            "expected {expected} embeddings, got {}",
+            data.len()
+        )));
+    }
+
+    let mut indexed = data
+        .iter()
+        .enumerate()
+        .map(|(position, item)| {
+            let index = item["index"].as_u64().map_or(position, |i| i as usize);
+            let vector = item["embedding"]
+                .as_array()
+                .ok_or_else(|| parse_failed(format!("embedding {index} is not an array")))?
+                .iter()
+                .map(|x| x.as_f64().map(|x| x as f32))
+                .collect::<Option<Vec<f32>>>()
+                .ok_or_else(|| parse_failed(format!("embedding {index} has non-numeric values")))?;
+            Ok((index, vector))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    indexed.sort_by_key(|(index, _)| *index);
+    Ok(indexed.into_iter().map(|(_, vector)| vector).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use super::*;
+    use crate::llm::client::LlmClientConfig;
+    use crate::llm::test_support::mock_server;
+
+    const DIMENSION: usize = 8;
+
+    /// An embeddings response with `count` vectors, listed in reverse index
+    /// order, whose first component identifies the request and position.
+    fn response(call: usize, count: usize) -> String {
+        let data: Vec<Value> = (0..count)
+            .rev()
+            .map(|i| {
+                let mut vector = vec![0.5_f32; DIMENSION];
+                vector[0] = (call * 10 + i) as f32;
+                json!({ "object": "embedding", "index": i, "embedding": vector })
+            })
+            .collect();
+        json!({ "object": "list", "data": data, "model": "mock-embed" }).to_string()
+    }
+
+    #[tokio::test]
+    async fn embeds_in_batches_preserving_order() {
+        // Five texts in batches of two: requests carry 2, 2, and 1 inputs.
+        let (base, calls) = mock_server("application/json", |n| {
+            response(n, if n < 3 { 2 } else { 1 })
+        })
+        .await;
+        let client = LlmClient::new(LlmClientConfig::openai_compatible("k", "chat", base))
+            .expect("client must build")
+            .with_embeddings(
+                EmbeddingConfig::for_provider(&LlmProvider::OpenAI)
+                    .with_model("mock-embed")
+                    .with_batch_size(2),
+            );
+
+        let texts = ["a", "b", "c", "d", "e"].map(String::from).to_vec();
+        let vectors = client.embed(texts).await.expect("embed must succeed");
+
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert_eq!(vectors.len(), 5);
+        assert!(vectors.iter().all(|v| v.len() == DIMENSION));
+        let firsts: Vec<f32> = vectors.iter().map(|v| v[0]).collect();
+        assert_eq!(firsts, [10.0, 11.0, 20.0, 21.0, 30.0]);
+    }
+
+    #[tokio::test]
+    async fn anthropic_requires_a_dedicated_key() {
+        let client = LlmClient::new(LlmClientConfig::anthropic("sk-ant", "claude"))
+            .expect("client must build");
+        let err = client.embed(vec!["hello".into()]).await.unwrap_err();
+        assert!(matches!(err, AgentError::MissingApiKey { provider } if provider == "voyage"));
+    }
+}
@@ -5,19 +5,40 @@
 //!
 //! - [`types`] -- Core data types (messages, tool calls, streaming events).
 //! - [`client`] -- HTTP client for Anthropic and OpenAI APIs.
+//! - [`backend`] -- Pluggable transport, including a scripted test backend.
+//! - [`embeddings`] -- Text embeddings for semantic memory.
+//! - `prompt_cache` -- Anthropic prompt-cache breakpoints.
+//! - [`response_cache`] -- Response cache for deterministic (temperature 0)
+//!   requests.
 //! - [`router`] -- Complexity-based model routing.
+//! - `stream_recovery` -- Resuming streams that drop mid-response.
 //! - [`streaming`] -- SSE stream parser for Anthropic incremental responses.
 //! - [`streaming_openai`] -- SSE stream parser for OpenAI incremental responses.
+//! - [`tokens`] -- Token counting and model context windows.
+//! - `transcript` -- Redacted request/response logging.
 
+pub mod backend;
 pub mod client;
+pub mod embeddings;
+mod prompt_cache;
+pub mod response_cache;
 pub mod router;
+mod stream_recovery;
 pub mod streaming;
 pub mod streaming_openai;
+#[cfg(test)]
+mod test_support;
+pub mod tokens;
+mod transcript;
 pub mod types;
 
 // Re-export the most commonly used types for convenience.
+pub use backend::{LlmBackend, ScriptedBackend};
+pub use response_cache::{ResponseCache, ResponseCacheConfig};
 pub use client::{LlmClient, LlmClientConfig, LlmProvider};
+pub use embeddings::EmbeddingConfig;
 pub use router::{Complexity, ModelConfig, ModelRouter};
+pub use tokens::{context_window, count_tokens, token_upper_bound};
 pub use types::{
     ChatRequest, LlmResponse, Message, Role, StreamEvent, ToolCall, ToolDefinition, ToolResult,
     Usage,
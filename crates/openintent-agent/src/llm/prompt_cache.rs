@@ -0,0 +1,81 @@
+//! Anthropic prompt caching.
+//!
+//! The Messages API caches a request prefix (tools, then system prompt) up
+//! to each `cache_control` breakpoint, so an unchanged prefix is billed at
+//! the cache-read rate on later turns.  Breakpoints are only worth adding
+//! to prefixes long enough for the API to cache.
+
+use serde_json::{Value, json};
+
+/// Shortest tools/system prefix, in characters, worth a prompt-cache
+/// breakpoint.  Anthropic does not cache prefixes under 1024 tokens, which is
+/// roughly 4096 characters of English text.
+const PROMPT_CACHE_MIN_CHARS: usize = 4096;
+
+/// Put cache breakpoints on the last tool and on the system prompt of an
+/// Anthropic request `body` when the prefix up to them is long enough.
+pub(super) fn add_cache_breakpoints(body: &mut Value) {
+    let tools_len = body.get("tools").map_or(0, |tools| tools.to_string().len());
+
+    if tools_len >= PROMPT_CACHE_MIN_CHARS
+        && let Some(last) = body["tools"].as_array_mut().and_then(|t| t.last_mut())
+    {
+        last["cache_control"] = json!({"type": "ephemeral"});
+    }
+
+    if let Some(system) = body.get("system").and_then(Value::as_str)
+        && tools_len + system.len() >= PROMPT_CACHE_MIN_CHARS
+    {
+        body["system"] = json!([{
+            "type": "text",
+            "text": system,
+            "cache_control": {"type": "ephemeral"},
+        }]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::client::{LlmClient, LlmClientConfig};
+    use crate::llm::types::{ChatRequest, Message, ToolDefinition};
+
+    #[test]
+    fn build_anthropic_request_body_caches_long_tool_block() {
+        let config = LlmClientConfig::anthropic("test-key", "claude-sonnet-4-20250514");
+        let client = LlmClient::new(config).unwrap();
+
+        let tools = (0..40)
+            .map(|i| ToolDefinition {
+                name: format!("tool_{i}"),
+                description: "Does something useful with the workspace.".repeat(3),
+                input_schema: serde_json::json!({"type": "object"}),
+                destructive: false,
+            })
+            .collect();
+        let request = ChatRequest {
+            model: String::new(),
+            messages: vec![Message::user("Hello")],
+            tools,
+            temperature: None,
+            max_tokens: None,
+            stream: false,
+        };
+
+        let body = client.build_anthropic_request_body(&request, false);
+        let tools = body["tools"].as_array().unwrap();
+        assert_eq!(tools[39]["cache_control"]["type"], "ephemeral");
+        assert!(tools[..39].iter().all(|t| t.get("cache_control").is_none()));
+    }
+
+    #[test]
+    fn short_prefix_gets_no_breakpoint() {
+        let mut body = json!({
+            "system": "Be brief.",
+            "tools": [{"name": "read_file", "input_schema": {"type": "object"}}],
+        });
+        add_cache_breakpoints(&mut body);
+        assert_eq!(body["system"], "Be brief.");
+        assert!(body["tools"][0].get("cache_control").is_none());
+    }
+}
@@ -0,0 +1,237 @@
+//! Response cache for deterministic LLM requests.
+//!
+//! Requests sent with a temperature of exactly `0.0` are expected to produce
+//! the same answer every time, so [`LlmClient`](super::LlmClient) can serve
+//! repeats from an in-memory [`CacheLayer`] instead of paying for another API
+//! call.  Entries are keyed by a hash of the endpoint, model, messages, tools,
+//! temperature, and token limit; any other temperature bypasses the cache.
+
+use std::fmt;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use openintent_store::{CacheLayer, CacheStats};
+use serde_json::json;
+
+use crate::llm::client::LlmClient;
+use crate::llm::types::{ChatRequest, LlmResponse};
+
+/// Configuration for the LLM response cache.
+#[derive(Debug, Clone)]
+pub struct ResponseCacheConfig {
+    /// How long a cached response stays valid, in seconds.
+    pub ttl_seconds: u64,
+    /// Maximum number of cached responses.
+    pub max_entries: u64,
+    /// Disable caching entirely.
+    pub no_cache: bool,
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl_seconds: 3600,
+            max_entries: 1000,
+            no_cache: false,
+        }
+    }
+}
+
+/// Cache of temperature-0 responses, shared by clones of a client.
+#[derive(Clone)]
+pub struct ResponseCache {
+    layer: CacheLayer<LlmResponse>,
+}
+
+impl ResponseCache {
+    /// Build a cache from `config`, or `None` if caching is disabled.
+    pub fn new(config: &ResponseCacheConfig) -> Option<Self> {
+        if config.no_cache {
+            return None;
+        }
+        let layer = CacheLayer::builder("llm_responses")
+            .max_capacity(config.max_entries)
+            .ttl_seconds(config.ttl_seconds)
+            .build();
+        Some(Self { layer })
+    }
+
+    /// Hit/miss statistics for cacheable requests.
+    pub fn stats(&self) -> &CacheStats {
+        self.layer.stats()
+    }
+
+    /// Look up a cached response.
+    pub(crate) async fn get(&self, key: &str) -> Option<LlmResponse> {
+        self.layer.get(key).await
+    }
+
+    /// Cache `response` under `key`.  Failures only cost a future cache hit.
+    pub(crate) async fn insert(&self, key: &str, response: &LlmResponse) {
+        if let Err(e) = self.layer.insert(key, response).await {
+            tracing::warn!(error = %e, "failed to cache LLM response");
+        }
+    }
+}
+
+impl fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("entries", &self.layer.entry_count())
+            .field("stats", self.layer.stats())
+            .finish()
+    }
+}
+
+impl LlmClient {
+    /// Serve repeated temperature-0 requests from an in-memory cache.
+    ///
+    /// Clones of the returned client share the cache.  With
+    /// [`ResponseCacheConfig::no_cache`] set, caching stays disabled.
+    pub fn with_response_cache(mut self, config: ResponseCacheConfig) -> Self {
+        self.cache = ResponseCache::new(&config);
+        self
+    }
+
+    /// The response cache, if enabled.
+    pub fn response_cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_ref()
+    }
+
+    /// Cache key for `request`, or `None` if caching is off or the request
+    /// is not deterministic.
+    pub(super) fn response_cache_key(&self, request: &ChatRequest) -> Option<String> {
+        self.cache.as_ref()?;
+        let model = if request.model.is_empty() {
+            self.current_default_model()
+        } else {
+            request.model.clone()
+        };
+        cache_key(&self.current_base_url(), &model, request)
+    }
+
+    pub(super) async fn cached_response(&self, key: Option<&str>) -> Option<LlmResponse> {
+        match (&self.cache, key) {
+            (Some(cache), Some(key)) => cache.get(key).await,
+            _ => None,
+        }
+    }
+
+    pub(super) async fn cache_response(&self, key: Option<&str>, response: &LlmResponse) {
+        if let (Some(cache), Some(key)) = (&self.cache, key) {
+            cache.insert(key, response).await;
+        }
+    }
+}
+
+/// Compute the cache key for `request`, or `None` if it must not be cached.
+///
+/// `endpoint` and `model` are the resolved values the request will actually
+/// be sent with, so a provider failover never serves another model's answer.
+fn cache_key(endpoint: &str, model: &str, request: &ChatRequest) -> Option<String> {
+    if request.temperature != Some(0.0) {
+        return None;
+    }
+    let material = json!({
+        "endpoint": endpoint,
+        "model": model,
+        "messages": request.messages,
+        "tools": request.tools,
+        "temperature": request.temperature,
+        "max_tokens": request.max_tokens,
+    });
+    let mut hasher = DefaultHasher::new();
+    material.to_string().hash(&mut hasher);
+    Some(format!("{:016x}", hasher.finish()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::llm::client::{LlmClient, LlmClientConfig};
+    use crate::llm::test_support::mock_server;
+    use crate::llm::types::Message;
+
+    /// Serve OpenAI-style completions answering `answer N` to the Nth call.
+    async fn counting_server() -> (String, Arc<AtomicUsize>) {
+        mock_server("application/json", |n| {
+            json!({
+                "choices": [{ "message": { "role": "assistant", "content": format!("answer {n}") } }]
+            })
+            .to_string()
+        })
+        .await
+    }
+
+    fn request(temperature: Option<f32>) -> ChatRequest {
+        ChatRequest {
+            model: String::new(),
+            messages: vec![Message::user("What is 2+2?")],
+            tools: vec![],
+            temperature,
+            max_tokens: Some(64),
+            stream: false,
+        }
+    }
+
+    fn text(response: LlmResponse) -> String {
+        match response {
+            LlmResponse::Text(text) => text,
+            LlmResponse::ToolCalls(calls) => panic!("unexpected tool calls: {calls:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn identical_deterministic_requests_hit_cache() {
+        let (base, calls) = counting_server().await;
+        let client = LlmClient::new(LlmClientConfig::openai_compatible("k", "mock", base))
+            .expect("client must build")
+            .with_response_cache(ResponseCacheConfig::default());
+
+        let first = text(client.chat(&request(Some(0.0))).await.unwrap());
+        let second = text(client.chat(&request(Some(0.0))).await.unwrap());
+
+        assert_eq!(first, "answer 1");
+        assert_eq!(second, first);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        let stats = client.response_cache().expect("cache enabled").stats();
+        assert_eq!((stats.hits(), stats.misses()), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn sampled_and_uncached_requests_always_call_api() {
+        let (base, calls) = counting_server().await;
+        let sampled = LlmClient::new(LlmClientConfig::openai_compatible("k", "mock", &base))
+            .expect("client must build")
+            .with_response_cache(ResponseCacheConfig::default());
+        sampled.chat(&request(Some(0.7))).await.unwrap();
+        sampled.chat(&request(Some(0.7))).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let disabled = LlmClient::new(LlmClientConfig::openai_compatible("k", "mock", &base))
+            .expect("client must build")
+            .with_response_cache(ResponseCacheConfig {
+                no_cache: true,
+                ..ResponseCacheConfig::default()
+            });
+        assert!(disabled.response_cache().is_none());
+        disabled.chat(&request(Some(0.0))).await.unwrap();
+        disabled.chat(&request(Some(0.0))).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn key_depends_on_model_and_messages() {
+        let req = request(Some(0.0));
+        let key = cache_key("https://api", "a", &req).expect("temperature 0 is cacheable");
+        assert_eq!(cache_key("https://api", "a", &req), Some(key.clone()));
+        assert_ne!(cache_key("https://api", "b", &req), Some(key.clone()));
+
+        let mut other = req.clone();
+        other.messages.push(Message::user("and 3+3?"));
+        assert_ne!(cache_key("https://api", "a", &other), Some(key));
+        assert_eq!(cache_key("https://api", "a", &request(None)), None);
+    }
+}
@@ -0,0 +1,322 @@
+//! Recovery from LLM streams that drop mid-response.
+//!
+//! A streaming request whose connection closes before the provider's end
+//! marker is re-sent up to [`MAX_STREAM_RECONNECTS`] times.  Anthropic
+//! requests resume from the text received so far via an assistant prefill;
+//! OpenAI requests are only re-sent while nothing has been rendered.  When
+//! recovery is impossible the partial text is surfaced in
+//! [`AgentError::LlmStreamInterrupted`].
+
+use futures::StreamExt;
+use serde_json::json;
+
+use crate::error::{AgentError, Result};
+use crate::llm::client::LlmClient;
+use crate::llm::streaming::{SseParser, StreamAccumulator};
+use crate::llm::streaming_openai::OpenAiStreamAccumulator;
+use crate::llm::types::{ChatRequest, LlmResponse, StreamEvent, Usage};
+
+/// How many times a stream that drops mid-response is re-sent before the
+/// partial response is surfaced as an error.
+const MAX_STREAM_RECONNECTS: u32 = 2;
+
+/// How an SSE stream ended.
+#[derive(Debug)]
+enum StreamEnd {
+    /// The provider signalled the end of the response.
+    Complete,
+    /// The connection closed or failed before the response was complete.
+    Dropped(String),
+}
+
+impl LlmClient {
+    /// Streaming Anthropic chat with a text callback.
+    ///
+    /// If the connection drops mid-stream, the request is re-sent with the
+    /// text received so far as an assistant prefill so the model continues
+    /// where it stopped.  Once [`MAX_STREAM_RECONNECTS`] is exhausted the
+    /// partial text is returned in [`AgentError::LlmStreamInterrupted`].
+    pub(super) async fn stream_chat_anthropic_with_callback<F>(
+        &self,
+        request: &ChatRequest,
+        on_text: &mut F,
+    ) -> Result<(LlmResponse, Usage)>
+    where
+        F: FnMut(&str),
+    {
+        let mut accumulator = StreamAccumulator::new();
+        let mut attempt = 0;
+
+        loop {
+            let mut body = self.build_anthropic_request_body(request, true);
+            if !accumulator.text().is_empty()
+                && let Some(messages) = body["messages"].as_array_mut()
+            {
+                messages.push(json!({ "role": "assistant", "content": accumulator.text() }));
+            }
+            let resp = self.send_anthropic_request(&body).await?;
+
+            let status = resp.status();
+            if !status.is_success() {
+                let text = resp.text().await.unwrap_or_default();
+                return Err(AgentError::LlmRequestFailed {
+                    reason: format!("API returned {status}: {text}"),
+                });
+            }
+
+            match self
+                .consume_anthropic_stream(resp, &mut accumulator, on_text)
+                .await?
+            {
+                StreamEnd::Complete => return accumulator.into_response(),
+                StreamEnd::Dropped(reason) if attempt < MAX_STREAM_RECONNECTS => {
+                    attempt += 1;
+                    tracing::warn!(
+                        attempt,
+                        reason = %reason,
+                        partial_len = accumulator.text().len(),
+                        "LLM stream dropped, reconnecting"
+                    );
+                    accumulator.apply(&StreamEvent::Reconnecting { attempt }, on_text);
+                }
+                StreamEnd::Dropped(reason) => {
+                    return Err(AgentError::LlmStreamInterrupted {
+                        reason,
+                        partial_text: accumulator.text().to_owned(),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Streaming OpenAI chat with a text callback.
+    ///
+    /// Chat Completions cannot continue from a prefill, so a dropped stream
+    /// is only re-sent while no text has been rendered yet.  Otherwise the
+    /// partial text is returned in [`AgentError::LlmStreamInterrupted`].
+    pub(super) async fn stream_chat_openai<F>(
+        &self,
+        request: &ChatRequest,
+        on_text: &mut F,
+    ) -> Result<(LlmResponse, Usage)>
+    where
+        F: FnMut(&str),
+    {
+        let body = self.build_openai_request_body(request, true);
+        let mut attempt = 0;
+
+        loop {
+            let resp = self.send_openai_request(&body).await?;
+
+            let status = resp.status();
+            if !status.is_success() {
+                let text = resp.text().await.unwrap_or_default();
+                return Err(AgentError::LlmRequestFailed {
+                    reason: format!("API returned {status}: {text}"),
+                });
+            }
+
+            let mut accumulator = OpenAiStreamAccumulator::new();
+            match self
+                .consume_openai_stream(resp, &mut accumulator, on_text)
+                .await?
+            {
+                StreamEnd::Complete => return accumulator.into_response(),
+                StreamEnd::Dropped(reason)
+                    if attempt < MAX_STREAM_RECONNECTS && accumulator.text().is_empty() =>
+                {
+                    attempt += 1;
+                    tracing::warn!(attempt, reason = %reason, "LLM stream dropped, reconnecting");
+                }
+                StreamEnd::Dropped(reason) => {
+                    return Err(AgentError::LlmStreamInterrupted {
+                        reason,
+                        partial_text: accumulator.text().to_owned(),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Consume an Anthropic SSE stream into `accumulator`.
+    async fn consume_anthropic_stream<F>(
+        &self,
+        resp: reqwest::Response,
+        accumulator: &mut StreamAccumulator,
+        on_text: &mut F,
+    ) -> Result<StreamEnd>
+    where
+        F: FnMut(&str),
+    {
+        let mut parser = SseParser::new();
+
+        let mut byte_stream = resp.bytes_stream();
+        let mut line_buffer = String::new();
+
+        while let Some(chunk_result) = byte_stream.next().await {
+            let chunk = match chunk_result {
+                Ok(chunk) => chunk,
+                Err(e) => return Ok(StreamEnd::Dropped(format!("stream read error: {e}"))),
+            };
+
+            let text = std::str::from_utf8(&chunk).map_err(|e| AgentError::LlmStreamError {
+                reason: format!("invalid UTF-8 in stream: {e}"),
+            })?;
+
+            line_buffer.push_str(text);
+
+            while let Some(newline_pos) = line_buffer.find('\n') {
+                let line = line_buffer[..newline_pos].to_owned();
+                line_buffer = line_buffer[newline_pos + 1..].to_owned();
+
+                if let Some(event) = parser.parse_line(&line)? {
+                    accumulator.apply(&event, on_text);
+
+                    if matches!(event, StreamEvent::MessageStop) {
+                        return Ok(StreamEnd::Complete);
+                    }
+                }
+            }
+        }
+
+        Ok(StreamEnd::Dropped(
+            "stream ended before message_stop".into(),
+        ))
+    }
+
+    /// Consume an OpenAI SSE stream into `accumulator`.
+    async fn consume_openai_stream<F>(
+        &self,
+        resp: reqwest::Response,
+        accumulator: &mut OpenAiStreamAccumulator,
+        on_text: &mut F,
+    ) -> Result<StreamEnd>
+    where
+        F: FnMut(&str),
+    {
+        let mut byte_stream = resp.bytes_stream();
+        let mut line_buffer = String::new();
+
+        while let Some(chunk_result) = byte_stream.next().await {
+            let chunk = match chunk_result {
+                Ok(chunk) => chunk,
+                Err(e) => return Ok(StreamEnd::Dropped(format!("stream read error: {e}"))),
+            };
+
+            let text = std::str::from_utf8(&chunk).map_err(|e| AgentError::LlmStreamError {
+                reason: format!("invalid UTF-8 in stream: {e}"),
+            })?;
+
+            line_buffer.push_str(text);
+
+            while let Some(newline_pos) = line_buffer.find('\n') {
+                let line = line_buffer[..newline_pos].to_owned();
+                line_buffer = line_buffer[newline_pos + 1..].to_owned();
+
+                if let Some(delta_text) = accumulator.feed_line(&line)? {
+                    on_text(&delta_text);
+                }
+
+                if accumulator.is_done() {
+                    return Ok(StreamEnd::Complete);
+                }
+            }
+        }
+
+        if accumulator.is_complete() {
+            Ok(StreamEnd::Complete)
+        } else {
+            Ok(StreamEnd::Dropped("stream ended before [DONE]".into()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::client::LlmClientConfig;
+    use crate::llm::types::Message;
+
+    fn stream_request() -> ChatRequest {
+        ChatRequest {
+            model: String::new(),
+            messages: vec![Message::user("hi")],
+            tools: vec![],
+            temperature: None,
+            max_tokens: None,
+            stream: true,
+        }
+    }
+
+    fn anthropic_event(event: &str, data: serde_json::Value) -> String {
+        format!("event: {event}\ndata: {data}\n\n")
+    }
+
+    fn anthropic_text_delta(text: &str) -> String {
+        anthropic_event(
+            "content_block_delta",
+            serde_json::json!({ "index": 0, "delta": { "type": "text_delta", "text": text } }),
+        )
+    }
+
+    #[tokio::test]
+    async fn dropped_anthropic_stream_resumes_from_partial_text() {
+        let (base, calls) = crate::llm::test_support::mock_server("text/event-stream", |n| {
+            let start = anthropic_event(
+                "message_start",
+                serde_json::json!({ "message": { "id": "msg", "model": "m", "usage": { "input_tokens": 5 } } }),
+            );
+            if n == 1 {
+                // The connection closes before `message_stop`.
+                return start + &anthropic_text_delta("Hello ");
+            }
+            start
+                + &anthropic_text_delta(" world")
+                + &anthropic_event("message_stop", serde_json::json!({}))
+        })
+        .await;
+        let mut config = LlmClientConfig::anthropic("sk-ant-test", "m");
+        config.base_url = base;
+        let client = LlmClient::new(config).unwrap();
+
+        let mut streamed = String::new();
+        let (response, _) = client
+            .stream_chat_with_callback(&stream_request(), |t| streamed.push_str(t))
+            .await
+            .expect("stream must be resumed");
+
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(streamed, "Hello  world");
+        match response {
+            LlmResponse::Text(text) => assert_eq!(text, "Hello world"),
+            other => panic!("expected text, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn truncated_openai_stream_surfaces_partial_text() {
+        let (base, calls) = crate::llm::test_support::mock_server("text/event-stream", |_| {
+            concat!(
+                "data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hello\"}}]}\n\n",
+                "data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\" wor\"}}]}\n\n",
+            )
+            .to_owned()
+        })
+        .await;
+        let client = LlmClient::new(LlmClientConfig::openai_compatible("k", "mock", base)).unwrap();
+
+        let err = client
+            .stream_chat(&stream_request())
+            .await
+            .expect_err("truncated stream must fail");
+
+        // Text was already rendered, so the request is not re-sent.
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+        match err {
+            AgentError::LlmStreamInterrupted { partial_text, .. } => {
+                assert_eq!(partial_text, "Hello wor")
+            }
+            other => panic!("expected LlmStreamInterrupted, got {other:?}"),
+        }
+    }
+}
@@ -2,12 +2,13 @@
 //!
 //! The Anthropic streaming format sends `event:` and `data:` lines in
 //! standard SSE format.  This module parses those lines into typed
-//! [`StreamEvent`] values that the rest of the agent runtime can consume.
+//! [`StreamEvent`] values that the rest of the agent runtime can consume,
+//! and [`StreamAccumulator`] folds those events into a complete response.
 
 use serde_json::Value;
 
 use crate::error::{AgentError, Result};
-use crate::llm::types::{StreamDelta, StreamEvent};
+use crate::llm::types::{LlmResponse, StreamDelta, StreamEvent, ToolCall, Usage};
 
 /// Parses raw SSE lines from the Anthropic Messages API stream.
 ///
@@ -65,13 +66,14 @@ impl SseParser {
             "message_start" => {
                 let v: Value = parse_json(data)?;
                 let message = &v["message"];
-                let input_tokens = message["usage"]["input_tokens"]
-                    .as_u64()
-                    .unwrap_or(0) as u32;
+                let usage = &message["usage"];
+                let tokens = |key: &str| usage[key].as_u64().unwrap_or(0) as u32;
                 Ok(Some(StreamEvent::MessageStart {
                     message_id: json_string(message, "id"),
                     model: json_string(message, "model"),
-                    input_tokens,
+                    input_tokens: tokens("input_tokens"),
+                    cache_creation_input_tokens: tokens("cache_creation_input_tokens"),
+                    cache_read_input_tokens: tokens("cache_read_input_tokens"),
                 }))
             }
 
@@ -160,6 +162,146 @@ fn json_string(v: &Value, field: &str) -> String {
     v[field].as_str().unwrap_or_default().to_owned()
 }
 
+// ---------------------------------------------------------------------------
+// Response accumulator
+// ---------------------------------------------------------------------------
+
+/// Accumulates fragments from Anthropic streaming events into a complete
+/// response.
+#[derive(Debug, Default)]
+pub(crate) struct StreamAccumulator {
+    /// Accumulated text output.
+    text: String,
+
+    /// Tool calls being built up from streaming fragments.
+    tool_calls: Vec<ToolCallBuilder>,
+
+    /// The stop reason, if received.
+    stop_reason: Option<String>,
+
+    /// Usage tracking (populated from message_start and message_delta events).
+    usage: Usage,
+}
+
+/// In-progress tool call being assembled from streaming deltas.
+#[derive(Debug)]
+struct ToolCallBuilder {
+    id: String,
+    name: String,
+    /// Accumulated JSON input string.
+    input_json: String,
+}
+
+impl StreamAccumulator {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// The text received so far.
+    pub(crate) fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Apply a single stream event to the accumulator.
+    pub(crate) fn apply<F>(&mut self, event: &StreamEvent, on_text: &mut F)
+    where
+        F: FnMut(&str),
+    {
+        match event {
+            StreamEvent::MessageStart {
+                input_tokens,
+                cache_creation_input_tokens,
+                cache_read_input_tokens,
+                ..
+            } => {
+                self.usage.input_tokens = *input_tokens;
+                self.usage.cache_creation_input_tokens = *cache_creation_input_tokens;
+                self.usage.cache_read_input_tokens = *cache_read_input_tokens;
+            }
+
+            StreamEvent::ContentBlockStart {
+                content_type,
+                id,
+                name,
+                ..
+            } => {
+                if content_type == "tool_use" {
+                    self.tool_calls.push(ToolCallBuilder {
+                        id: id.clone().unwrap_or_default(),
+                        name: name.clone().unwrap_or_default(),
+                        input_json: String::new(),
+                    });
+                }
+            }
+
+            StreamEvent::ContentBlockDelta { delta, .. } => match delta {
+                StreamDelta::TextDelta(t) => {
+                    self.text.push_str(t);
+                    on_text(t);
+                }
+                StreamDelta::InputJsonDelta(j) => {
+                    if let Some(builder) = self.tool_calls.last_mut() {
+                        builder.input_json.push_str(j);
+                    }
+                }
+            },
+
+            StreamEvent::MessageDelta {
+                stop_reason,
+                output_tokens,
+            } => {
+                self.stop_reason = stop_reason.clone();
+                self.usage.output_tokens = *output_tokens;
+            }
+
+            StreamEvent::Reconnecting { .. } => {
+                // Half-streamed tool calls cannot be resumed; the continuation
+                // re-issues them.  The API rejects prefills ending in whitespace.
+                self.tool_calls.clear();
+                self.stop_reason = None;
+                self.text.truncate(self.text.trim_end().len());
+            }
+
+            _ => {}
+        }
+    }
+
+    /// Convert the accumulated state into a final [`LlmResponse`] and [`Usage`].
+    pub(crate) fn into_response(self) -> Result<(LlmResponse, Usage)> {
+        let usage = self.usage;
+        if self.tool_calls.is_empty() {
+            Ok((LlmResponse::Text(self.text), usage))
+        } else {
+            let calls: Result<Vec<ToolCall>> = self
+                .tool_calls
+                .into_iter()
+                .map(|b| {
+                    let arguments: Value = if b.input_json.is_empty() {
+                        Value::Object(Default::default())
+                    } else {
+                        serde_json::from_str(&b.input_json).map_err(|e| {
+                            AgentError::LlmParseFailed {
+                                reason: format!(
+                                    "invalid JSON in tool call `{}` input: {e}",
+                                    b.name
+                                ),
+                            }
+                        })?
+                    };
+
+                    Ok(ToolCall {
+                        id: b.id,
+                        name: b.name,
+                        arguments,
+                    })
+                })
+                .collect();
+
+            Ok((LlmResponse::ToolCalls(calls?), usage))
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -178,7 +320,12 @@ mod tests {
             .unwrap();
 
         match event {
-            StreamEvent::MessageStart { message_id, model, input_tokens } => {
+            StreamEvent::MessageStart {
+                message_id,
+                model,
+                input_tokens,
+                ..
+            } => {
                 assert_eq!(message_id, "msg_01");
                 assert_eq!(model, "claude-sonnet-4-20250514");
                 assert_eq!(input_tokens, 10);
@@ -187,6 +334,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_message_start_cache_usage() {
+        let mut parser = SseParser::new();
+        parser.parse_line("event: message_start").unwrap();
+        let event = parser
+            .parse_line(r#"data: {"type":"message_start","message":{"id":"msg_02","model":"m","usage":{"input_tokens":4,"cache_creation_input_tokens":2048,"cache_read_input_tokens":1024,"output_tokens":0}}}"#)
+            .unwrap()
+            .unwrap();
+
+        match event {
+            StreamEvent::MessageStart {
+                cache_creation_input_tokens,
+                cache_read_input_tokens,
+                ..
+            } => {
+                assert_eq!(cache_creation_input_tokens, 2048);
+                assert_eq!(cache_read_input_tokens, 1024);
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
     #[test]
     fn parse_text_delta() {
         let mut parser = SseParser::new();
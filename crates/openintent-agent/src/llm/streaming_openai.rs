@@ -33,6 +33,10 @@ pub struct OpenAiStreamAccumulator {
     /// Whether the `[DONE]` sentinel has been received.
     done: bool,
 
+    /// Whether a chunk carried a `finish_reason`.  Some compatible providers
+    /// close the stream after it without sending `[DONE]`.
+    finished: bool,
+
     /// Token usage collected from stream chunks that include a `usage` field
     /// (OpenAI sends this in the final chunk before `[DONE]`).
     usage: Usage,
@@ -60,6 +64,17 @@ impl OpenAiStreamAccumulator {
         self.done
     }
 
+    /// Returns `true` if the response is complete: either `[DONE]` or a
+    /// `finish_reason` has been received.
+    pub fn is_complete(&self) -> bool {
+        self.done || self.finished
+    }
+
+    /// The text content accumulated so far.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
     /// Feed a single SSE line from the stream.
     ///
     /// Returns `Ok(Some(text_delta))` when a text content delta is present
@@ -95,6 +110,10 @@ impl OpenAiStreamAccumulator {
             reason: format!("invalid JSON in OpenAI SSE data: {e}"),
         })?;
 
+        if !v["choices"][0]["finish_reason"].is_null() {
+            self.finished = true;
+        }
+
         // Navigate to choices[0].delta.
         let delta = &v["choices"][0]["delta"];
         if delta.is_null() {
@@ -230,6 +249,20 @@ mod tests {
         assert!(acc.is_done());
     }
 
+    #[test]
+    fn finish_reason_marks_complete() {
+        let mut acc = OpenAiStreamAccumulator::new();
+        acc.feed_line(r#"data: {"choices":[{"index":0,"delta":{"content":"Hi"}}]}"#)
+            .unwrap();
+        assert!(!acc.is_complete());
+        assert_eq!(acc.text(), "Hi");
+
+        acc.feed_line(r#"data: {"choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#)
+            .unwrap();
+        assert!(acc.is_complete());
+        assert!(!acc.is_done());
+    }
+
     #[test]
     fn blank_and_comment_lines_ignored() {
         let mut acc = OpenAiStreamAccumulator::new();
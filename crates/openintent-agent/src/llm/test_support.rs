@@ -0,0 +1,56 @@
+//! Helpers shared by the LLM module's tests.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Serve HTTP requests on a local port, answering the Nth request (starting
+/// at 1) with `body(N)`.  Returns the base URL and the request counter.
+pub(crate) async fn mock_server<F>(
+    content_type: &'static str,
+    body: F,
+) -> (String, Arc<AtomicUsize>)
+where
+    F: Fn(usize) -> String + Send + 'static,
+{
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind must succeed");
+    let base = format!("http://{}", listener.local_addr().expect("local addr"));
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&calls);
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            let mut request = Vec::new();
+            let mut buf = [0u8; 8192];
+            loop {
+                let read = stream.read(&mut buf).await.unwrap_or(0);
+                request.extend_from_slice(&buf[..read]);
+                let text = String::from_utf8_lossy(&request);
+                let complete = text.split_once("\r\n\r\n").is_some_and(|(head, rest)| {
+                    let len = head
+                        .lines()
+                        .find_map(|l| {
+                            l.to_lowercase()
+                                .strip_prefix("content-length:")
+                                .and_then(|v| v.trim().parse::<usize>().ok())
+                        })
+                        .unwrap_or(0);
+                    rest.len() >= len
+                });
+                if read == 0 || complete {
+                    break;
+                }
+            }
+            let body = body(n);
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        }
+    });
+    (base, calls)
+}
@@ -0,0 +1,126 @@
+//! Token counting and model context windows.
+//!
+//! [`count_tokens`] measures a conversation the way the provider will, so
+//! compaction triggers before a request exceeds the model's context window
+//! rather than after the provider rejects it.  OpenAI models are counted with
+//! their real BPE tokenizer (`o200k_base` for GPT-4o and the o-series,
+//! `cl100k_base` otherwise).  Anthropic does not publish its tokenizer, so
+//! Claude models use a conservative character-based estimate.
+
+use tiktoken_rs::CoreBPE;
+use tiktoken_rs::tokenizer::{Tokenizer, get_tokenizer};
+
+use crate::llm::types::Message;
+
+/// Tokens added per message for role and framing, as documented by OpenAI.
+const TOKENS_PER_MESSAGE: usize = 3;
+
+/// Tokens that prime the assistant's reply.
+const REPLY_PRIMING_TOKENS: usize = 3;
+
+/// Characters per token assumed for models without a public tokenizer.
+/// Deliberately low so the estimate errs towards compacting early.
+const CHARS_PER_TOKEN: usize = 3;
+
+/// Context window assumed for models not listed in [`context_window`].
+const DEFAULT_CONTEXT_WINDOW: usize = 32_768;
+
+/// Count the tokens `messages` occupy in a request to `model`, including
+/// tool-call names and arguments.
+pub fn count_tokens(model: &str, messages: &[Message]) -> usize {
+    let count: Box<dyn Fn(&str) -> usize> = match bpe_for_model(model) {
+        Some(bpe) => Box::new(|text| bpe.encode_with_special_tokens(text).len()),
+        None => Box::new(|text| text.chars().count().div_ceil(CHARS_PER_TOKEN)),
+    };
+
+    let body: usize = messages
+        .iter()
+        .map(|msg| {
+            let tool_calls: usize = msg
+                .tool_calls
+                .iter()
+                .map(|tc| count(&tc.name) + count(&tc.arguments.to_string()))
+                .sum();
+            TOKENS_PER_MESSAGE + count(&msg.content) + tool_calls
+        })
+        .sum();
+    body + REPLY_PRIMING_TOKENS
+}
+
+/// An upper bound on [`count_tokens`] for any model, computed without
+/// loading a tokenizer: no token is shorter than one byte.
+pub fn token_upper_bound(messages: &[Message]) -> usize {
+    let body: usize = messages
+        .iter()
+        .map(|msg| {
+            let tool_calls: usize = msg
+                .tool_calls
+                .iter()
+                .map(|tc| tc.name.len() + tc.arguments.to_string().len())
+                .sum();
+            TOKENS_PER_MESSAGE + msg.content.len() + tool_calls
+        })
+        .sum();
+    body + REPLY_PRIMING_TOKENS
+}
+
+/// The context window of `model`, in tokens.
+pub fn context_window(model: &str) -> usize {
+    let model = model.to_ascii_lowercase();
+    let model = model.rsplit('/').next().unwrap_or(&model);
+    if model.starts_with("claude") {
+        200_000
+    } else if model.starts_with("gpt-4.1") {
+        1_047_576
+    } else if model.starts_with("gpt-4o") || model.starts_with("gpt-4-turbo") {
+        128_000
+    } else if model.starts_with("gpt-4-32k") {
+        32_768
+    } else if model.starts_with("gpt-4") {
+        8_192
+    } else if model.starts_with("gpt-3.5") {
+        16_385
+    } else if model.starts_with("o1") || model.starts_with("o3") || model.starts_with("o4") {
+        200_000
+    } else if model.starts_with("deepseek") {
+        65_536
+    } else {
+        DEFAULT_CONTEXT_WINDOW
+    }
+}
+
+/// The BPE tokenizer for `model`, or `None` if its tokenizer is not public.
+fn bpe_for_model(model: &str) -> Option<&'static CoreBPE> {
+    let model = model.rsplit('/').next().unwrap_or(model);
+    if model.starts_with("claude") {
+        return None;
+    }
+    let bpe = match get_tokenizer(model) {
+        Some(Tokenizer::O200kBase) => tiktoken_rs::o200k_base_singleton(),
+        // Unknown and open-weight models are closest to cl100k on average.
+        _ => tiktoken_rs::cl100k_base_singleton(),
+    };
+    Some(bpe)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_openai_tokens_with_real_tokenizer() {
+        let messages = [Message::user("hello world")];
+        // "hello world" is two tokens in o200k_base, plus framing.
+        assert_eq!(
+            count_tokens("gpt-4o", &messages),
+            TOKENS_PER_MESSAGE + 2 + REPLY_PRIMING_TOKENS
+        );
+    }
+
+    #[test]
+    fn context_window_ignores_provider_prefix() {
+        assert_eq!(context_window("anthropic/claude-sonnet-4"), 200_000);
+        assert_eq!(context_window("gpt-4o-mini"), 128_000);
+        assert_eq!(context_window("llama3"), DEFAULT_CONTEXT_WINDOW);
+    }
+}
@@ -0,0 +1,305 @@
+//! Request/response transcripts for debugging LLM interactions.
+//!
+//! When [`LlmClientConfig::log_transcripts`](super::LlmClientConfig) is on,
+//! every request sent to the provider is appended to
+//! `<transcript_dir>/transcripts.jsonl` together with its response (text or
+//! tool calls), token usage, or error.  Credentials never reach the file:
+//! sensitive headers are masked and the API key is scrubbed from every string
+//! before writing.  The file rotates once it exceeds [`MAX_FILE_BYTES`],
+//! keeping up to [`MAX_ROTATED_FILES`] older files.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use reqwest::header::HeaderMap;
+use serde_json::{Map, Value, json};
+
+use crate::error::AgentError;
+use crate::llm::client::{LlmClient, LlmProvider};
+use crate::llm::types::{ChatRequest, LlmResponse, Usage};
+
+/// Default directory for transcript files.
+pub(crate) const DEFAULT_TRANSCRIPT_DIR: &str = "data/transcripts";
+
+/// Name of the active transcript file.
+const FILE_NAME: &str = "transcripts.jsonl";
+
+/// Size at which the active file is rotated.
+const MAX_FILE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Number of rotated files kept (`transcripts.1.jsonl` is the newest).
+const MAX_ROTATED_FILES: usize = 5;
+
+/// Replacement for redacted values.
+const REDACTED: &str = "[REDACTED]";
+
+/// Header and field names whose values are always redacted.
+const SENSITIVE_KEYS: [&str; 4] = ["authorization", "x-api-key", "api_key", "api-key"];
+
+/// One request/response exchange.
+pub(crate) struct Exchange<'a> {
+    pub provider: &'a str,
+    pub url: &'a str,
+    pub headers: &'a HeaderMap,
+    pub request: &'a ChatRequest,
+    pub outcome: Result<(&'a LlmResponse, Option<&'a Usage>), String>,
+    pub duration_ms: u128,
+}
+
+/// Appends redacted exchanges to a rotating JSONL file.
+#[derive(Debug)]
+pub(crate) struct TranscriptLog {
+    dir: PathBuf,
+    /// Serializes writes and rotation across clones of the client.
+    lock: Mutex<()>,
+}
+
+impl TranscriptLog {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Record one exchange, scrubbing `api_key` from it.  Errors are logged
+    /// and otherwise ignored so transcripts can never break a request.
+    pub fn record(&self, exchange: &Exchange<'_>, api_key: &str) {
+        let mut entry = entry_json(exchange);
+        redact(&mut entry, api_key);
+        if let Err(e) = self.append(&entry) {
+            tracing::warn!(error = %e, dir = %self.dir.display(), "failed to write LLM transcript");
+        }
+    }
+
+    fn append(&self, entry: &Value) -> std::io::Result<()> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(FILE_NAME);
+        if fs::metadata(&path).is_ok_and(|m| m.len() >= MAX_FILE_BYTES) {
+            rotate(&self.dir)?;
+        }
+
+        let mut line = entry.to_string();
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?
+            .write_all(line.as_bytes())
+    }
+}
+
+impl LlmClient {
+    /// Append one exchange to the transcript log, if enabled.
+    pub(super) fn record_transcript(
+        &self,
+        request: &ChatRequest,
+        started: Instant,
+        result: std::result::Result<(&LlmResponse, Option<&Usage>), &AgentError>,
+    ) {
+        let Some(log) = self.transcripts() else {
+            return;
+        };
+        let (provider, url, headers) = match self.provider() {
+            LlmProvider::Anthropic => ("anthropic", self.anthropic_url(), self.anthropic_headers()),
+            LlmProvider::OpenAI => ("openai", self.openai_url(), self.openai_headers()),
+        };
+        let exchange = Exchange {
+            provider,
+            url: &url,
+            headers: &headers.unwrap_or_default(),
+            request,
+            outcome: result.map_err(ToString::to_string),
+            duration_ms: started.elapsed().as_millis(),
+        };
+        log.record(&exchange, &self.current_api_key());
+    }
+}
+
+/// Shift `transcripts.N.jsonl` to `N+1`, dropping the oldest, and move the
+/// active file to `transcripts.1.jsonl`.
+fn rotate(dir: &Path) -> std::io::Result<()> {
+    let rotated = |n: usize| dir.join(format!("transcripts.{n}.jsonl"));
+    let oldest = rotated(MAX_ROTATED_FILES);
+    if oldest.exists() {
+        fs::remove_file(oldest)?;
+    }
+    for n in (1..MAX_ROTATED_FILES).rev() {
+        let from = rotated(n);
+        if from.exists() {
+            fs::rename(from, rotated(n + 1))?;
+        }
+    }
+    fs::rename(dir.join(FILE_NAME), rotated(1))
+}
+
+fn entry_json(exchange: &Exchange<'_>) -> Value {
+    let headers: Map<String, Value> = exchange
+        .headers
+        .iter()
+        .map(|(name, value)| {
+            let value = value.to_str().unwrap_or("<binary>");
+            (name.as_str().to_owned(), Value::String(value.to_owned()))
+        })
+        .collect();
+
+    let (response, usage, error) = match &exchange.outcome {
+        Ok((response, usage)) => (json!(response), json!(usage), Value::Null),
+        Err(e) => (Value::Null, Value::Null, json!(e)),
+    };
+
+    json!({
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "provider": exchange.provider,
+        "url": exchange.url,
+        "headers": headers,
+        "request": exchange.request,
+        "stream": exchange.request.stream,
+        "response": response,
+        "usage": usage,
+        "error": error,
+        "duration_ms": exchange.duration_ms,
+    })
+}
+
+/// Mask sensitive fields and scrub `secret` from every string in `value`.
+fn redact(value: &mut Value, secret: &str) {
+    match value {
+        Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if SENSITIVE_KEYS.contains(&key.to_ascii_lowercase().as_str()) {
+                    *field = Value::String(REDACTED.to_owned());
+                } else {
+                    redact(field, secret);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| redact(item, secret)),
+        Value::String(s) if !secret.is_empty() && s.contains(secret) => {
+            *s = s.replace(secret, REDACTED);
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::client::{LlmClient, LlmClientConfig};
+    use crate::llm::test_support::mock_server;
+    use crate::llm::types::Message;
+
+    const API_KEY: &str = "sk-test-do-not-log-1234";
+
+    const TOOL_CALL_STREAM: &str = concat!(
+        "data: {\"choices\":[{\"index\":0,\"delta\":{\"tool_calls\":[{\"index\":0,\"id\":\"call_1\",",
+        "\"function\":{\"name\":\"fs_read_file\",\"arguments\":\"{\\\"path\\\":\\\"README.md\\\"}\"}}]}}]}\n\n",
+        "data: {\"choices\":[{\"index\":0,\"delta\":{},\"finish_reason\":\"tool_calls\"}],",
+        "\"usage\":{\"prompt_tokens\":21,\"completion_tokens\":7}}\n\n",
+        "data: [DONE]\n\n",
+    );
+
+    fn request() -> ChatRequest {
+        ChatRequest {
+            model: String::new(),
+            messages: vec![Message::user("read the readme")],
+            tools: vec![],
+            temperature: None,
+            max_tokens: None,
+            stream: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn logged_transcript_contains_exchange_but_no_api_key() {
+        let dir = tempfile::tempdir().expect("tempdir must be created");
+        let (base, _) = mock_server("text/event-stream", |_| TOOL_CALL_STREAM.to_owned()).await;
+        let mut config = LlmClientConfig::openai_compatible(API_KEY, "mock-model", base);
+        config.log_transcripts = true;
+        config.transcript_dir = dir.path().join("transcripts");
+        let client = LlmClient::new(config).expect("client must build");
+
+        client
+            .stream_chat(&request())
+            .await
+            .expect("request must succeed");
+
+        let path = dir.path().join("transcripts").join(FILE_NAME);
+        let contents = fs::read_to_string(&path).expect("transcript file must exist");
+        assert!(!contents.contains(API_KEY), "API key leaked: {contents}");
+
+        let entry: Value = serde_json::from_str(contents.trim()).expect("entry must be JSON");
+        assert_eq!(entry["headers"]["authorization"], REDACTED);
+        assert_eq!(
+            entry["request"]["messages"][0]["content"],
+            "read the readme"
+        );
+        assert_eq!(entry["response"]["ToolCalls"][0]["name"], "fs_read_file");
+        assert_eq!(entry["usage"]["input_tokens"], 21);
+    }
+
+    #[tokio::test]
+    async fn transcripts_are_off_by_default() {
+        let dir = tempfile::tempdir().expect("tempdir must be created");
+        let (base, _) = mock_server("text/event-stream", |_| TOOL_CALL_STREAM.to_owned()).await;
+        let mut config = LlmClientConfig::openai_compatible(API_KEY, "mock-model", base);
+        config.transcript_dir = dir.path().to_path_buf();
+        let client = LlmClient::new(config).expect("client must build");
+
+        client
+            .stream_chat(&request())
+            .await
+            .expect("request must succeed");
+        assert!(!dir.path().join(FILE_NAME).exists());
+    }
+
+    #[test]
+    fn redacts_headers_and_embedded_keys() {
+        let mut value = json!({
+            "headers": { "Authorization": "Bearer sk-secret", "content-type": "application/json" },
+            "error": "API returned 401: invalid key sk-secret",
+            "request": { "messages": [{ "content": "my key is sk-secret" }] },
+        });
+        redact(&mut value, "sk-secret");
+
+        assert_eq!(value["headers"]["Authorization"], REDACTED);
+        assert_eq!(value["headers"]["content-type"], "application/json");
+        assert_eq!(value["error"], "API returned 401: invalid key [REDACTED]");
+        assert!(!value.to_string().contains("sk-secret"));
+    }
+
+    #[test]
+    fn rotates_when_file_is_full() {
+        let dir = tempfile::tempdir().expect("tempdir must be created");
+        let active = dir.path().join(FILE_NAME);
+        fs::write(&active, vec![b'x'; MAX_FILE_BYTES as usize]).unwrap();
+
+        let log = TranscriptLog::new(dir.path());
+        let request = ChatRequest {
+            model: "m".into(),
+            messages: vec![Message::user("hi")],
+            tools: vec![],
+            temperature: None,
+            max_tokens: None,
+            stream: false,
+        };
+        let exchange = Exchange {
+            provider: "openai",
+            url: "http://localhost/chat/completions",
+            headers: &HeaderMap::new(),
+            request: &request,
+            outcome: Err("boom".into()),
+            duration_ms: 1,
+        };
+        log.record(&exchange, "k");
+
+        assert!(dir.path().join("transcripts.1.jsonl").exists());
+        let line = fs::read_to_string(&active).unwrap();
+        assert_eq!(line.lines().count(), 1);
+        assert!(line.contains("\"error\":\"boom\""));
+    }
+}
@@ -7,6 +7,8 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::error::{AgentError, Result};
+
 // ---------------------------------------------------------------------------
 // Messages
 // ---------------------------------------------------------------------------
@@ -145,7 +147,7 @@ pub struct ToolResult {
 // ---------------------------------------------------------------------------
 
 /// The high-level response from an LLM after processing a turn.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum LlmResponse {
     /// The model produced a final text answer.
     Text(String),
@@ -184,6 +186,16 @@ pub struct ChatRequest {
     pub stream: bool,
 }
 
+impl ChatRequest {
+    /// Check every tool's `input_schema` so a malformed definition fails
+    /// locally instead of the provider rejecting the whole request.
+    pub fn validate_tools(&self) -> Result<()> {
+        self.tools
+            .iter()
+            .try_for_each(ToolDefinition::validate_schema)
+    }
+}
+
 /// A tool definition exposed to the LLM so it knows what tools are available.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolDefinition {
@@ -195,6 +207,142 @@ pub struct ToolDefinition {
 
     /// JSON Schema describing the tool's input parameters.
     pub input_schema: Value,
+
+    /// Whether the tool changes state or acts outside the system; viewers
+    /// may not call it (see [`crate::permissions`]).
+    #[serde(default)]
+    pub destructive: bool,
+}
+
+impl ToolDefinition {
+    /// Validate that `input_schema` is an object schema whose `properties`
+    /// are schemas and whose `required` entries name existing properties.
+    pub fn validate_schema(&self) -> Result<()> {
+        let invalid = |reason: String| AgentError::InvalidToolSchema {
+            tool: self.name.clone(),
+            reason,
+        };
+
+        let schema = self
+            .input_schema
+            .as_object()
+            .ok_or_else(|| invalid("schema must be a JSON object".into()))?;
+        if schema.get("type").and_then(Value::as_str) != Some("object") {
+            return Err(invalid("top-level `type` must be \"object\"".into()));
+        }
+
+        let properties = match schema.get("properties") {
+            None => None,
+            Some(Value::Object(props)) => Some(props),
+            Some(_) => return Err(invalid("`properties` must be an object".into())),
+        };
+        if let Some((name, _)) = properties
+            .into_iter()
+            .flatten()
+            .find(|(_, prop)| !prop.is_object() && !prop.is_boolean())
+        {
+            return Err(invalid(format!(
+                "property `{name}` must be a schema object"
+            )));
+        }
+
+        let Some(required) = schema.get("required") else {
+            return Ok(());
+        };
+        let required = required
+            .as_array()
+            .ok_or_else(|| invalid("`required` must be an array".into()))?;
+        for entry in required {
+            let name = entry
+                .as_str()
+                .ok_or_else(|| invalid("`required` entries must be strings".into()))?;
+            if !properties.is_some_and(|props| props.contains_key(name)) {
+                return Err(invalid(format!(
+                    "`required` lists `{name}`, which is not in `properties`"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check call `arguments` against `input_schema`: every required
+    /// property must be present and top-level values must have the declared
+    /// `type`.  All problems are reported together so the model can fix
+    /// them in one retry.
+    pub fn validate_arguments(&self, arguments: &Value) -> Result<()> {
+        let invalid = |reason: String| AgentError::InvalidToolArguments {
+            tool_name: self.name.clone(),
+            reason,
+        };
+        let empty = serde_json::Map::new();
+        let args = match arguments {
+            Value::Object(args) => args,
+            Value::Null => &empty,
+            _ => return Err(invalid("arguments must be a JSON object".into())),
+        };
+
+        let mut problems = Vec::new();
+        if let Some(required) = self.input_schema["required"].as_array() {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !args.contains_key(name) {
+                    problems.push(format!("missing required argument `{name}`"));
+                }
+            }
+        }
+        if let Some(properties) = self.input_schema["properties"].as_object() {
+            for (name, value) in args {
+                let Some(expected) = properties.get(name).map(|p| &p["type"]) else {
+                    continue;
+                };
+                let allowed: Vec<&str> = match expected {
+                    Value::String(t) => vec![t.as_str()],
+                    Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+                    _ => continue,
+                };
+                if !allowed.iter().any(|t| json_type_matches(t, value)) {
+                    problems.push(format!(
+                        "argument `{name}` must be of type {}, got {}",
+                        allowed.join(" or "),
+                        json_type_name(value)
+                    ));
+                }
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(invalid(problems.join("; ")))
+        }
+    }
+}
+
+/// Whether `value` satisfies the JSON Schema primitive type `expected`.
+/// Unknown type names are accepted.
+fn json_type_matches(expected: &str, value: &Value) -> bool {
+    match expected {
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+/// The JSON Schema type name of `value`.
+fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -215,6 +363,10 @@ pub enum StreamEvent {
         model: String,
         /// Number of input (prompt) tokens billed for this request.
         input_tokens: u32,
+        /// Input tokens written to the prompt cache.
+        cache_creation_input_tokens: u32,
+        /// Input tokens served from the prompt cache.
+        cache_read_input_tokens: u32,
     },
 
     /// A new content block has started.  For text blocks, `content_type` will
@@ -257,6 +409,13 @@ pub enum StreamEvent {
 
     /// A ping / keepalive event (no payload).
     Ping,
+
+    /// Emitted by the client (not the API) when a dropped stream is resumed
+    /// by re-sending the request with the partial answer as a prefill.
+    Reconnecting {
+        /// One-based reconnection attempt number.
+        attempt: u32,
+    },
 }
 
 /// Incremental delta within a streaming content block.
@@ -280,4 +439,101 @@ pub struct Usage {
     pub input_tokens: u32,
     /// Number of tokens generated by the model.
     pub output_tokens: u32,
+    /// Input tokens written to the provider's prompt cache (Anthropic).
+    #[serde(default)]
+    pub cache_creation_input_tokens: u32,
+    /// Input tokens read from the provider's prompt cache (Anthropic).
+    #[serde(default)]
+    pub cache_read_input_tokens: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tool(input_schema: Value) -> ToolDefinition {
+        ToolDefinition {
+            name: "fs_read_file".into(),
+            description: "Read a file".into(),
+            input_schema,
+            destructive: false,
+        }
+    }
+
+    #[test]
+    fn valid_schemas_pass() {
+        tool(json!({"type": "object"})).validate_schema().unwrap();
+        tool(json!({
+            "type": "object",
+            "properties": { "path": { "type": "string" } },
+            "required": ["path"]
+        }))
+        .validate_schema()
+        .unwrap();
+    }
+
+    #[test]
+    fn required_must_reference_existing_property() {
+        let err = tool(json!({
+            "type": "object",
+            "properties": { "path": { "type": "string" } },
+            "required": ["path", "encoding"]
+        }))
+        .validate_schema()
+        .unwrap_err();
+
+        match err {
+            AgentError::InvalidToolSchema { tool, reason } => {
+                assert_eq!(tool, "fs_read_file");
+                assert!(reason.contains("`encoding`"), "unexpected reason: {reason}");
+            }
+            other => panic!("expected InvalidToolSchema, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn arguments_are_checked_against_schema() {
+        let def = tool(json!({
+            "type": "object",
+            "properties": {
+                "path": { "type": "string" },
+                "limit": { "type": "integer" },
+                "encoding": { "type": ["string", "null"] }
+            },
+            "required": ["path"]
+        }));
+
+        def.validate_arguments(&json!({"path": "a.txt", "limit": 10, "encoding": null}))
+            .unwrap();
+
+        let err = def
+            .validate_arguments(&json!({"limit": "ten"}))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("missing required argument `path`"), "{err}");
+        assert!(
+            err.contains("argument `limit` must be of type integer, got string"),
+            "{err}"
+        );
+        assert!(def.validate_arguments(&json!([1, 2])).is_err());
+    }
+
+    #[test]
+    fn non_object_schemas_are_rejected() {
+        for schema in [
+            json!("object"),
+            json!({"type": "string"}),
+            json!({"type": "object", "properties": []}),
+            json!({"type": "object", "properties": { "path": "string" }}),
+        ] {
+            assert!(
+                matches!(
+                    tool(schema.clone()).validate_schema(),
+                    Err(AgentError::InvalidToolSchema { .. })
+                ),
+                "schema should be rejected: {schema}"
+            );
+        }
+    }
 }
@@ -0,0 +1,126 @@
+//! Role-based tool permissions.
+//!
+//! When an [`AgentContext`] carries the acting user's [`UserRole`], every
+//! tool call is checked against it before the context's own policy checker
+//! runs:
+//!
+//! - **Viewer** accounts are read-only and may not call destructive tools
+//!   (writing or deleting files, running shell commands, sending messages).
+//! - **Admin-only** tools (self-update, schema migrations, webhook and
+//!   account setup) require an **Admin**.
+//! - Everything else is open to every role.
+//!
+//! A tool is destructive when its [`ToolDefinition::destructive`] flag is
+//! set by the adapter that defines it; admin-only tools are listed by name.
+//!
+//! [`AgentContext`]: crate::runtime::AgentContext
+
+use openintent_store::UserRole;
+
+use crate::llm::types::ToolDefinition;
+use crate::runtime::ToolPermission;
+
+/// How much authority a tool call needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolAccess {
+    /// Only reads data; open to every role.
+    ReadOnly,
+    /// Changes state or has side effects outside the system; denied to viewers.
+    Destructive,
+    /// Changes how the system itself runs; admins only.
+    AdminOnly,
+}
+
+/// Tools that only admins may call, besides every `system_*` tool.
+const ADMIN_TOOLS: &[&str] = &[
+    "skill_email_oauth_setup",
+    "sqlite_migrate",
+    "telegram_configure_group_chat",
+    "telegram_set_webhook",
+];
+
+/// Classify a tool by its definition.
+pub fn classify_tool(tool: &ToolDefinition) -> ToolAccess {
+    if tool.name.starts_with("system_") || ADMIN_TOOLS.contains(&tool.name.as_str()) {
+        ToolAccess::AdminOnly
+    } else if tool.destructive {
+        ToolAccess::Destructive
+    } else {
+        ToolAccess::ReadOnly
+    }
+}
+
+/// Decide whether a user with `role` may call `tool`.
+///
+/// Denial reasons are written to be relayed to the user by the LLM.
+pub fn check_role(role: UserRole, tool: &ToolDefinition) -> ToolPermission {
+    let tool_name = &tool.name;
+    match (classify_tool(tool), role) {
+        (ToolAccess::AdminOnly, UserRole::Admin) => ToolPermission::Allow,
+        (ToolAccess::AdminOnly, _) => ToolPermission::Deny(format!(
+            "`{tool_name}` requires an admin account, but the current user is a {role}"
+        )),
+        (ToolAccess::Destructive, UserRole::Viewer) => ToolPermission::Deny(format!(
+            "`{tool_name}` makes changes, and viewer accounts have read-only access"
+        )),
+        _ => ToolPermission::Allow,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn tool(name: &str, destructive: bool) -> ToolDefinition {
+        ToolDefinition {
+            name: name.into(),
+            description: String::new(),
+            input_schema: json!({"type": "object"}),
+            destructive,
+        }
+    }
+
+    #[test]
+    fn classifies_by_definition() {
+        // Names alone no longer decide: the adapter's flag does.
+        assert_eq!(
+            classify_tool(&tool("memory_consolidate", true)),
+            ToolAccess::Destructive
+        );
+        assert_eq!(
+            classify_tool(&tool("clipboard_write", true)),
+            ToolAccess::Destructive
+        );
+        assert_eq!(
+            classify_tool(&tool("fs_read_file", false)),
+            ToolAccess::ReadOnly
+        );
+        for name in ["system_self_update", "sqlite_migrate"] {
+            assert_eq!(
+                classify_tool(&tool(name, true)),
+                ToolAccess::AdminOnly,
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn roles_gate_tools() {
+        let delete = tool("fs_delete", true);
+        let read = tool("fs_read_file", false);
+        let update = tool("system_self_update", true);
+        assert!(matches!(
+            check_role(UserRole::Viewer, &delete),
+            ToolPermission::Deny(_)
+        ));
+        assert_eq!(check_role(UserRole::Viewer, &read), ToolPermission::Allow);
+        assert_eq!(check_role(UserRole::User, &delete), ToolPermission::Allow);
+        assert!(matches!(
+            check_role(UserRole::User, &update),
+            ToolPermission::Deny(_)
+        ));
+        assert_eq!(check_role(UserRole::Admin, &update), ToolPermission::Allow);
+    }
+}
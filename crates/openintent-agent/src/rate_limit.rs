@@ -0,0 +1,157 @@
+//! Per-adapter rate limiting for tool calls.
+//!
+//! Some adapters wrap services with upstream quotas (GitHub, web search), and
+//! a tool loop can easily exhaust them.  [`AgentConfig::rate_limits`] maps an
+//! adapter id to a token-bucket [`RateLimit`]; before each tool call the
+//! runtime takes a token from that adapter's bucket, waiting for one to
+//! refill when the bucket is empty.  If the wait would exceed
+//! [`RateLimit::max_wait`] the call is refused with a
+//! [`ErrorCategory::RateLimited`] error instead, which the runtime reports to
+//! the LLM as a tool result.
+//!
+//! [`AgentConfig::rate_limits`]: crate::runtime::AgentConfig::rate_limits
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::error::{AgentError, ErrorCategory, Result};
+
+/// Token-bucket limit for one adapter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    /// Tokens added to the bucket per second.
+    pub per_second: f64,
+    /// Bucket capacity: how many calls may be made back to back.
+    pub burst: u32,
+    /// Longest a call may wait for a token before it is refused.
+    pub max_wait: Duration,
+}
+
+impl RateLimit {
+    /// Allow `n` calls per second, with a burst of `n`.
+    pub fn per_second(n: u32) -> Self {
+        Self {
+            per_second: f64::from(n),
+            burst: n,
+            max_wait: Duration::from_secs(10),
+        }
+    }
+
+    /// Allow `n` calls per minute, with a burst of `n`.
+    pub fn per_minute(n: u32) -> Self {
+        Self {
+            per_second: f64::from(n) / 60.0,
+            ..Self::per_second(n)
+        }
+    }
+
+    /// Set the longest a call may wait for a token.
+    pub fn with_max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = max_wait;
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// Token buckets for every rate-limited adapter, shared across tool calls.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    limits: HashMap<String, RateLimit>,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl RateLimiter {
+    /// Create a limiter enforcing `limits`, keyed by adapter id.
+    pub fn new(limits: HashMap<String, RateLimit>) -> Self {
+        Self {
+            limits,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Take a token for `adapter_id`, waiting for one if necessary.
+    ///
+    /// Adapters without a configured limit are never delayed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AgentError::AdapterFailed`] with
+    /// [`ErrorCategory::RateLimited`] if no token would be available within
+    /// the limit's `max_wait`.
+    pub async fn acquire(&self, adapter_id: &str) -> Result<()> {
+        let Some(limit) = self.limits.get(adapter_id) else {
+            return Ok(());
+        };
+        let wait = self.reserve(adapter_id, limit)?;
+        if !wait.is_zero() {
+            tracing::debug!(
+                adapter = adapter_id,
+                wait_ms = wait.as_millis() as u64,
+                "rate limit reached, delaying tool call"
+            );
+            tokio::time::sleep(wait).await;
+        }
+        Ok(())
+    }
+
+    /// Reserve a token and return how long to wait before using it.
+    fn reserve(&self, adapter_id: &str, limit: &RateLimit) -> Result<Duration> {
+        let now = Instant::now();
+        let capacity = f64::from(limit.burst.max(1));
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let bucket = buckets.entry(adapter_id.to_owned()).or_insert(Bucket {
+            tokens: capacity,
+            refilled_at: now,
+        });
+
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * limit.per_second).min(capacity);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(Duration::ZERO);
+        }
+
+        // Tokens may go negative: each waiting call reserves the next token
+        // to refill, so concurrent callers queue up instead of racing.
+        let wait = Duration::try_from_secs_f64((1.0 - bucket.tokens) / limit.per_second)
+            .unwrap_or(Duration::MAX);
+        if wait > limit.max_wait {
+            return Err(AgentError::AdapterFailed {
+                category: ErrorCategory::RateLimited,
+                reason: format!(
+                    "rate limit for `{adapter_id}` exceeded; next call allowed in {:.1}s",
+                    wait.as_secs_f64()
+                ),
+            });
+        }
+        bucket.tokens -= 1.0;
+        Ok(wait)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn refuses_when_wait_exceeds_max() {
+        let limit = RateLimit::per_minute(1).with_max_wait(Duration::from_secs(1));
+        let limiter = RateLimiter::new(HashMap::from([("web_search".to_owned(), limit)]));
+
+        limiter.acquire("web_search").await.unwrap();
+        let err = limiter.acquire("web_search").await.unwrap_err();
+        assert_eq!(err.category(), Some(ErrorCategory::RateLimited));
+        // Unlimited adapters are unaffected.
+        limiter.acquire("filesystem").await.unwrap();
+    }
+}
@@ -0,0 +1,257 @@
+//! Replay of a session's recorded tool calls.
+//!
+//! [`replay`] walks the [tool-call audit trail](crate::audit) of one session
+//! in the order the calls were made.  In [`ReplayMode::DryRun`], the
+//! default, nothing is executed: the report simply lists the recorded
+//! calls.  [`ReplayMode::Execute`] runs every call again against the live
+//! adapters — **this has real side effects** (files are written, messages
+//! are sent again) and must only be chosen explicitly.
+//!
+//! The audit trail stores redacted arguments, so calls whose arguments were
+//! redacted are never re-executed; neither are calls that were denied when
+//! they were first made.  Replay bypasses the role and policy checks of a
+//! live agent run, so it is meant for operators diagnosing their own
+//! sessions.
+
+use std::sync::Arc;
+
+use openintent_store::{ToolAuditEntry, ToolAuditStore};
+use serde_json::Value;
+
+use crate::audit::REDACTED;
+use crate::error::{AgentError, Result};
+use crate::runtime::ToolAdapter;
+
+/// Whether a replay executes the recorded calls.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReplayMode {
+    /// Report the recorded calls without executing anything.
+    #[default]
+    DryRun,
+    /// Execute every replayable call again.  Has side effects.
+    Execute,
+}
+
+/// What happened to one recorded call during a replay.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayOutcome {
+    /// Dry run: the call was only reported.
+    NotRun,
+    /// The call could not be replayed safely; the reason says why.
+    Skipped(String),
+    /// The call was executed again.
+    Executed {
+        /// Whether the call succeeded this time.
+        success: bool,
+        /// The result or error message.
+        result: String,
+    },
+}
+
+/// One recorded call and its replay outcome.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayStep {
+    /// The call as recorded in the audit trail.
+    pub entry: ToolAuditEntry,
+    /// What the replay did with it.
+    pub outcome: ReplayOutcome,
+}
+
+impl ReplayStep {
+    /// Whether an executed call now behaves differently from the recording.
+    pub fn diverged(&self) -> bool {
+        match &self.outcome {
+            ReplayOutcome::Executed { success, .. } => *success != self.entry.success,
+            _ => false,
+        }
+    }
+}
+
+/// Replay the tool calls recorded for `session_id`, in order.
+///
+/// In [`ReplayMode::Execute`], calls run one at a time against the first
+/// adapter in `adapters` that provides the tool, preferring the adapter
+/// that handled the original call.
+pub async fn replay(
+    store: &ToolAuditStore,
+    session_id: &str,
+    adapters: &[Arc<dyn ToolAdapter>],
+    mode: ReplayMode,
+) -> Result<Vec<ReplayStep>> {
+    let entries = store
+        .list_for_session(session_id)
+        .await
+        .map_err(|e| AgentError::Internal(format!("failed to load tool audit trail: {e}")))?;
+
+    let mut steps = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let outcome = match mode {
+            ReplayMode::DryRun => ReplayOutcome::NotRun,
+            ReplayMode::Execute => execute_entry(&entry, adapters).await,
+        };
+        steps.push(ReplayStep { entry, outcome });
+    }
+    Ok(steps)
+}
+
+async fn execute_entry(
+    entry: &ToolAuditEntry,
+    adapters: &[Arc<dyn ToolAdapter>],
+) -> ReplayOutcome {
+    if was_denied(entry) {
+        return ReplayOutcome::Skipped("the call was denied when it was recorded".into());
+    }
+    if contains_redacted(&entry.arguments) {
+        return ReplayOutcome::Skipped("the recorded arguments were redacted".into());
+    }
+    let Some(adapter) = find_adapter(entry, adapters) else {
+        return ReplayOutcome::Skipped(format!("no adapter provides `{}`", entry.tool_name));
+    };
+
+    tracing::warn!(
+        tool = %entry.tool_name,
+        audit_id = entry.id,
+        "replaying recorded tool call against live adapter"
+    );
+    match adapter
+        .execute(&entry.tool_name, entry.arguments.clone())
+        .await
+    {
+        Ok(result) => ReplayOutcome::Executed {
+            success: true,
+            result,
+        },
+        Err(e) => ReplayOutcome::Executed {
+            success: false,
+            result: format!("Error: {e}"),
+        },
+    }
+}
+
+fn find_adapter<'a>(
+    entry: &ToolAuditEntry,
+    adapters: &'a [Arc<dyn ToolAdapter>],
+) -> Option<&'a Arc<dyn ToolAdapter>> {
+    let provides = |a: &&Arc<dyn ToolAdapter>| {
+        a.tool_definitions()
+            .iter()
+            .any(|td| td.name == entry.tool_name)
+    };
+    adapters
+        .iter()
+        .filter(provides)
+        .find(|a| entry.adapter_id.as_deref() == Some(a.adapter_id()))
+        .or_else(|| adapters.iter().find(provides))
+}
+
+/// Denied calls are recorded with the runtime's policy-denial message.
+fn was_denied(entry: &ToolAuditEntry) -> bool {
+    !entry.success
+        && entry.result.starts_with(&format!(
+            "Error: tool `{}` denied by policy",
+            entry.tool_name
+        ))
+}
+
+fn contains_redacted(value: &Value) -> bool {
+    match value {
+        Value::String(s) => s == REDACTED,
+        Value::Array(items) => items.iter().any(contains_redacted),
+        Value::Object(map) => map.values().any(contains_redacted),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use async_trait::async_trait;
+    use openintent_store::{Database, NewToolAudit};
+    use serde_json::json;
+
+    use super::*;
+    use crate::llm::types::ToolDefinition;
+
+    /// Adapter that counts how often its tools run.
+    struct CountingAdapter {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ToolAdapter for CountingAdapter {
+        fn adapter_id(&self) -> &str {
+            "filesystem"
+        }
+
+        fn tool_definitions(&self) -> Vec<ToolDefinition> {
+            ["fs_read_file", "fs_write_file"]
+                .into_iter()
+                .map(|name| ToolDefinition {
+                    name: name.into(),
+                    description: String::new(),
+                    input_schema: json!({"type": "object"}),
+                    destructive: false,
+                })
+                .collect()
+        }
+
+        async fn execute(&self, _tool_name: &str, _arguments: Value) -> Result<String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok("ok".into())
+        }
+    }
+
+    #[tokio::test]
+    async fn dry_run_reports_calls_in_order_without_executing() {
+        let db = Database::open_in_memory().unwrap();
+        db.run_migrations().await.unwrap();
+        let store = ToolAuditStore::new(db);
+        for (session_id, tool_name, path) in [
+            ("s1", "fs_read_file", "a.txt"),
+            ("s2", "fs_read_file", "other.txt"),
+            ("s1", "fs_write_file", "b.txt"),
+        ] {
+            store
+                .record(NewToolAudit {
+                    session_id: Some(session_id.into()),
+                    task_id: "task-1".into(),
+                    tool_name: tool_name.into(),
+                    adapter_id: Some("filesystem".into()),
+                    arguments: json!({"path": path}),
+                    success: true,
+                    result: "ok".into(),
+                    duration_ms: 1,
+                })
+                .await
+                .unwrap();
+        }
+        let adapter = Arc::new(CountingAdapter {
+            calls: AtomicUsize::new(0),
+        });
+        let adapters: Vec<Arc<dyn ToolAdapter>> = vec![adapter.clone()];
+
+        let steps = replay(&store, "s1", &adapters, ReplayMode::DryRun)
+            .await
+            .unwrap();
+
+        let calls: Vec<_> = steps
+            .iter()
+            .map(|s| {
+                (
+                    s.entry.tool_name.as_str(),
+                    s.entry.arguments["path"].clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            calls,
+            [
+                ("fs_read_file", json!("a.txt")),
+                ("fs_write_file", json!("b.txt"))
+            ]
+        );
+        assert!(steps.iter().all(|s| s.outcome == ReplayOutcome::NotRun));
+        assert_eq!(adapter.calls.load(Ordering::SeqCst), 0);
+    }
+}
@@ -5,18 +5,26 @@
 //! runtime executes them and feeds the results back.  This continues until the
 //! LLM produces a final text response or the turn limit is exceeded.
 
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
+use openintent_store::{ToolAuditStore, UserRole};
 use serde_json::Value;
+use tracing::Instrument;
 use uuid::Uuid;
 
+use crate::audit::{PendingAudit, redact_sensitive};
 use crate::compaction::{CompactionConfig, compact_messages, needs_compaction};
 use crate::error::{AgentError, Result};
 use crate::llm::LlmClient;
 use crate::llm::router::ModelRouter;
 use crate::llm::types::{ChatRequest, LlmResponse, Message, ToolCall, ToolDefinition, ToolResult};
 use crate::memory::{AutoMemoryManager, MemoryType};
+use crate::permissions::check_role;
+use crate::rate_limit::{RateLimit, RateLimiter};
+use crate::truncation::{TruncationConfig, truncate_tool_result};
 
 // ---------------------------------------------------------------------------
 // Tool adapter trait
@@ -38,6 +46,39 @@ pub trait ToolAdapter: Send + Sync {
     ///
     /// Returns the result as a string suitable for feeding back to the LLM.
     async fn execute(&self, tool_name: &str, arguments: Value) -> Result<String>;
+
+    /// Check that the credentials this adapter needs are available.
+    ///
+    /// Called before any tool in a batch runs; an
+    /// [`AgentError::AuthRequired`] aborts the loop so the caller can prompt
+    /// the user to authenticate instead of letting the call fail remotely.
+    fn check_auth(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Execute a tool that may ask the user a question mid-execution.
+    ///
+    /// The runtime always calls this method, passing the context's
+    /// [`PromptFn`] when one is installed.  Interactive tools override it
+    /// and use [`confirm`] before destructive steps; the default ignores the
+    /// prompt and calls [`execute`](Self::execute).
+    async fn execute_with_prompt(
+        &self,
+        tool_name: &str,
+        arguments: Value,
+        _prompt: Option<PromptFn>,
+    ) -> Result<String> {
+        self.execute(tool_name, arguments).await
+    }
+
+    /// Copy of `arguments` that is safe to persist in the audit trail.
+    ///
+    /// The default masks values under secret-looking keys (see
+    /// [`redact_sensitive`]); adapters whose tools take other sensitive
+    /// input override it.
+    fn redact_arguments(&self, _tool_name: &str, arguments: &Value) -> Value {
+        redact_sensitive(arguments)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -56,6 +97,32 @@ pub type PolicyCheckerFn = Arc<dyn Fn(&str, &Value) -> ToolPermission + Send + S
 /// Receives `(tool_name, arguments)`.
 pub type ToolStartCallback = Arc<dyn Fn(&str, &Value) + Send + Sync>;
 
+/// Callback that asks the user a question and returns the answer, or `None`
+/// if nobody can answer (e.g. a non-interactive session).
+///
+/// It may block, e.g. on a terminal read; [`confirm`] calls it on the
+/// blocking thread pool.
+pub type PromptFn = Arc<dyn Fn(&str) -> Option<String> + Send + Sync>;
+
+/// Ask `question` through `prompt` and report whether the user said yes.
+///
+/// Anything but `y` or `yes` (case-insensitive), including having no way to
+/// ask, counts as no, so destructive steps default to not running.
+pub async fn confirm(prompt: Option<&PromptFn>, question: &str) -> bool {
+    let Some(ask) = prompt.cloned() else {
+        return false;
+    };
+    let question = question.to_owned();
+    let answer = match tokio::task::spawn_blocking(move || ask(&question)).await {
+        Ok(answer) => answer,
+        Err(e) => {
+            tracing::warn!(error = %e, "prompt callback failed");
+            None
+        }
+    };
+    answer.is_some_and(|answer| matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
 /// The outcome of a pre-tool policy check.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ToolPermission {
@@ -87,6 +154,23 @@ pub struct AgentConfig {
     /// Optional model router for dynamic per-turn model selection based on
     /// input complexity.  When set, the router overrides `model` each turn.
     pub router: Option<ModelRouter>,
+
+    /// Token-bucket limits on tool calls, keyed by adapter id.  Adapters
+    /// without an entry are not limited.
+    pub rate_limits: HashMap<String, RateLimit>,
+
+    /// Maximum number of tool calls across the whole run.  Once spent, the
+    /// LLM is told to answer and further requests are sent without tools.
+    /// `None` means unlimited.
+    pub max_tool_calls_total: Option<u32>,
+
+    /// How oversized tool results are shortened before they are added to
+    /// the conversation.
+    pub tool_result_truncation: TruncationConfig,
+
+    /// Ids of adapters whose tools are hidden from the LLM, typically from
+    /// `adapter.<id> = false` feature flags.
+    pub disabled_adapters: HashSet<String>,
 }
 
 impl Default for AgentConfig {
@@ -98,6 +182,10 @@ impl Default for AgentConfig {
             max_tokens: Some(4096),
             compaction: CompactionConfig::default(),
             router: None,
+            rate_limits: HashMap::new(),
+            max_tool_calls_total: None,
+            tool_result_truncation: TruncationConfig::default(),
+            disabled_adapters: HashSet::new(),
         }
     }
 }
@@ -130,12 +218,30 @@ pub struct AgentContext {
     /// Returns [`ToolPermission::Allow`] or [`ToolPermission::Deny`].
     pub policy_checker: Option<PolicyCheckerFn>,
 
+    /// Role of the user the agent acts for.  When set, tool calls the role
+    /// may not make are denied before the policy checker runs (see
+    /// [`crate::permissions`]).  `None` applies no role restrictions.
+    pub user_role: Option<UserRole>,
+
     /// Optional callback invoked when a tool execution starts.
     /// Useful for sending progress indicators (e.g., "Searching...").
     pub on_tool_start: Option<ToolStartCallback>,
 
+    /// Optional callback that lets tools ask the user for input, such as
+    /// confirming a destructive action.
+    pub on_prompt: Option<PromptFn>,
+
     /// Optional auto-memory manager for intelligent conversation tracking.
     pub memory_manager: Option<Arc<AutoMemoryManager>>,
+
+    /// Session this run belongs to, recorded with each audited tool call.
+    pub session_id: Option<String>,
+
+    /// Optional store every tool call is recorded in (see [`crate::audit`]).
+    pub tool_audit: Option<ToolAuditStore>,
+
+    /// Enforces `config.rate_limits` across all turns of this context.
+    rate_limiter: Arc<RateLimiter>,
 }
 
 impl AgentContext {
@@ -145,6 +251,7 @@ impl AgentContext {
         adapters: Vec<Arc<dyn ToolAdapter>>,
         config: AgentConfig,
     ) -> Self {
+        let rate_limiter = Arc::new(RateLimiter::new(config.rate_limits.clone()));
         Self {
             task_id: Uuid::now_v7(),
             messages: Vec::new(),
@@ -153,8 +260,13 @@ impl AgentContext {
             config,
             on_text_delta: None,
             policy_checker: None,
+            user_role: None,
             on_tool_start: None,
+            on_prompt: None,
             memory_manager: None,
+            session_id: None,
+            tool_audit: None,
+            rate_limiter,
         }
     }
 
@@ -170,24 +282,82 @@ impl AgentContext {
         self
     }
 
+    /// Act on behalf of a user with `role`.
+    pub fn with_user_role(mut self, role: UserRole) -> Self {
+        self.user_role = Some(role);
+        self
+    }
+
+    /// Decide whether a tool call may run: the user's role first, then the
+    /// policy checker.
+    fn check_permission(&self, call: &ToolCall) -> ToolPermission {
+        if let Some(role) = self.user_role {
+            // A tool no adapter defines fails when it runs, so only known
+            // tools need a role check.
+            let tool = self
+                .all_tool_definitions()
+                .into_iter()
+                .find(|td| td.name == call.name);
+            if let Some(tool) = tool {
+                let permission = check_role(role, &tool);
+                if permission != ToolPermission::Allow {
+                    return permission;
+                }
+            }
+        }
+        match self.policy_checker {
+            Some(ref checker) => checker(&call.name, &call.arguments),
+            None => ToolPermission::Allow,
+        }
+    }
+
+    /// Tag this run with the session it belongs to.
+    pub fn with_session_id(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+
+    /// Record every tool call in `store`.
+    pub fn with_tool_audit(mut self, store: ToolAuditStore) -> Self {
+        self.tool_audit = Some(store);
+        self
+    }
+
+    /// Start an audit entry for `call` if an audit store is attached.
+    fn start_audit(&self, call: &ToolCall) -> Option<PendingAudit> {
+        let store = self.tool_audit.clone()?;
+        Some(PendingAudit::new(
+            store,
+            self.session_id.clone(),
+            self.task_id.to_string(),
+            call,
+            self.find_adapter_for_tool(&call.name),
+        ))
+    }
+
     /// Set the memory manager for this context.
     pub fn with_memory_manager(mut self, memory_manager: Arc<AutoMemoryManager>) -> Self {
         self.memory_manager = Some(memory_manager);
         self
     }
 
-    /// Collect all tool definitions from registered adapters.
-    fn all_tool_definitions(&self) -> Vec<ToolDefinition> {
+    /// Registered adapters that are not disabled.
+    fn enabled_adapters(&self) -> impl Iterator<Item = &Arc<dyn ToolAdapter>> {
         self.adapters
             .iter()
+            .filter(|a| !self.config.disabled_adapters.contains(a.adapter_id()))
+    }
+
+    /// Collect all tool definitions from enabled adapters.
+    fn all_tool_definitions(&self) -> Vec<ToolDefinition> {
+        self.enabled_adapters()
             .flat_map(|a| a.tool_definitions())
             .collect()
     }
 
-    /// Find the adapter that owns a given tool name.
+    /// Find the enabled adapter that owns a given tool name.
     fn find_adapter_for_tool(&self, tool_name: &str) -> Option<&Arc<dyn ToolAdapter>> {
-        self.adapters
-            .iter()
+        self.enabled_adapters()
             .find(|a| a.tool_definitions().iter().any(|td| td.name == tool_name))
     }
 }
@@ -197,7 +367,7 @@ impl AgentContext {
 // ---------------------------------------------------------------------------
 
 /// The final response from an agent invocation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct AgentResponse {
     /// The final text output from the agent.
     pub text: String,
@@ -217,6 +387,27 @@ pub struct AgentResponse {
     /// Whether the response was a forced summary because max turns was hit.
     /// When true, the task is likely incomplete and may benefit from a retry.
     pub hit_turn_limit: bool,
+
+    /// Per-turn LLM and tool latencies, in turn order.
+    pub timings: Vec<TurnTiming>,
+}
+
+/// How long one ReAct turn spent waiting on the LLM and on each tool.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct TurnTiming {
+    /// Zero-based turn index.
+    pub turn: u32,
+    /// Latency of the LLM call.
+    pub llm: Duration,
+    /// Latency of each tool executed this turn, in request order.
+    pub tools: Vec<ToolTiming>,
+}
+
+/// Latency of a single tool execution.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ToolTiming {
+    pub tool_name: String,
+    pub duration: Duration,
 }
 
 impl AgentResponse {
@@ -229,6 +420,7 @@ impl AgentResponse {
             input_tokens: 0,
             output_tokens: 0,
             hit_turn_limit: false,
+            timings: Vec::new(),
         }
     }
 
@@ -238,6 +430,32 @@ impl AgentResponse {
         self.output_tokens = output;
         self
     }
+
+    /// Set the per-turn timings for this response.
+    pub fn with_timings(mut self, timings: Vec<TurnTiming>) -> Self {
+        self.timings = timings;
+        self
+    }
+
+    /// Summarize total time per component, e.g. `LLM 1.2s, shell 0.3s`.
+    ///
+    /// Tools are listed in the order they first ran, each with the sum of
+    /// its latencies across all turns.
+    pub fn timing_summary(&self) -> String {
+        let llm: Duration = self.timings.iter().map(|t| t.llm).sum();
+        let mut tools: Vec<(&str, Duration)> = Vec::new();
+        for tool in self.timings.iter().flat_map(|t| &t.tools) {
+            match tools.iter_mut().find(|(name, _)| *name == tool.tool_name) {
+                Some((_, total)) => *total += tool.duration,
+                None => tools.push((&tool.tool_name, tool.duration)),
+            }
+        }
+        std::iter::once(("LLM", llm))
+            .chain(tools)
+            .map(|(name, total)| format!("{name} {:.1}s", total.as_secs_f64()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -251,6 +469,10 @@ impl AgentResponse {
 /// 3. Appends tool results to the conversation.
 /// 4. Repeats until the LLM returns a text response or `max_turns` is hit.
 ///
+/// Once `max_tool_calls_total` calls have run, any further requested calls
+/// are refused, the LLM is told to answer with what it has, and later turns
+/// offer it no tools.
+///
 /// # Errors
 ///
 /// Returns [`AgentError::MaxTurnsExceeded`] if the loop hits the turn limit.
@@ -269,6 +491,12 @@ pub async fn react_loop(ctx: &mut AgentContext) -> Result<AgentResponse> {
     let mut consecutive_fail_count: u32 = 0;
     const MAX_CONSECUTIVE_FAILURES: u32 = 3;
 
+    // Tool calls run so far, checked against `max_tool_calls_total`.
+    let tool_budget = ctx.config.max_tool_calls_total;
+    let mut tool_calls_used: u32 = 0;
+
+    let mut timings: Vec<TurnTiming> = Vec::new();
+
     tracing::info!(
         task_id = %task_id,
         max_turns,
@@ -280,7 +508,7 @@ pub async fn react_loop(ctx: &mut AgentContext) -> Result<AgentResponse> {
         tracing::debug!(turn, "ReAct turn start");
 
         // Check if context compaction is needed before the LLM call.
-        if needs_compaction(&ctx.messages, &ctx.config.compaction) {
+        if needs_compaction(&ctx.messages, &ctx.config.model, &ctx.config.compaction) {
             tracing::info!(
                 task_id = %task_id,
                 message_count = ctx.messages.len(),
@@ -330,17 +558,25 @@ pub async fn react_loop(ctx: &mut AgentContext) -> Result<AgentResponse> {
             ctx.config.model.clone()
         };
 
-        // Build the chat request for this turn.
+        // Build the chat request for this turn.  With the tool budget spent,
+        // offer no tools so the LLM has to answer.
+        let budget_spent = tool_budget.is_some_and(|max| tool_calls_used >= max);
         let request = ChatRequest {
             model: model_for_turn,
             messages: ctx.messages.clone(),
-            tools: tools.clone(),
+            tools: if budget_spent {
+                Vec::new()
+            } else {
+                tools.clone()
+            },
             temperature: ctx.config.temperature,
             max_tokens: ctx.config.max_tokens,
             stream: true,
         };
 
         // Call the LLM — use streaming callback if one is provided.
+        let llm_span = tracing::info_span!("llm_call", turn, model = %request.model);
+        let llm_started = Instant::now();
         let (response, turn_usage) = if let Some(ref cb) = ctx.on_text_delta {
             let cb = Arc::clone(cb);
             ctx.llm
@@ -349,10 +585,16 @@ pub async fn react_loop(ctx: &mut AgentContext) -> Result<AgentResponse> {
                         f(delta);
                     }
                 })
+                .instrument(llm_span)
                 .await?
         } else {
-            ctx.llm.stream_chat(&request).await?
+            ctx.llm.stream_chat(&request).instrument(llm_span).await?
         };
+        timings.push(TurnTiming {
+            turn,
+            llm: llm_started.elapsed(),
+            tools: Vec::new(),
+        });
 
         // Accumulate token usage for this turn.
         total_input = total_input.saturating_add(turn_usage.input_tokens);
@@ -378,7 +620,8 @@ pub async fn react_loop(ctx: &mut AgentContext) -> Result<AgentResponse> {
                 }
 
                 return Ok(AgentResponse::new(text, turn + 1, task_id)
-                    .with_usage(total_input, total_output));
+                    .with_usage(total_input, total_output)
+                    .with_timings(timings));
             }
 
             LlmResponse::ToolCalls(calls) => {
@@ -394,8 +637,17 @@ pub async fn react_loop(ctx: &mut AgentContext) -> Result<AgentResponse> {
                 ctx.messages
                     .push(Message::assistant_tool_calls(calls.clone()));
 
-                // Execute all tool calls and collect results (with policy check).
-                let results = execute_tool_calls(&calls, ctx).await?;
+                // Execute the calls that fit in the tool budget and collect
+                // results (with policy check).
+                let allowed = tool_budget.map_or(calls.len(), |max| {
+                    max.saturating_sub(tool_calls_used) as usize
+                });
+                let (run, refused) = calls.split_at(allowed.min(calls.len()));
+                let (results, tool_timings) = execute_tool_calls(run, ctx).await?;
+                if let Some(timing) = timings.last_mut() {
+                    timing.tools = tool_timings;
+                }
+                tool_calls_used = tool_calls_used.saturating_add(run.len() as u32);
 
                 // Track consecutive failures of the same tool.
                 let failed_tools: Vec<&str> = results
@@ -417,10 +669,38 @@ pub async fn react_loop(ctx: &mut AgentContext) -> Result<AgentResponse> {
                     consecutive_fail_count = 0;
                 }
 
-                // Append each tool result to the conversation.
+                // Append each tool result to the conversation, truncating
+                // any that would crowd out the rest of the context.
                 for result in results {
+                    let content =
+                        truncate_tool_result(&result.content, &ctx.config.tool_result_truncation);
                     ctx.messages
-                        .push(Message::tool_result(&result.tool_call_id, &result.content));
+                        .push(Message::tool_result(&result.tool_call_id, content));
+                }
+
+                // Every call needs a result, including those over budget.
+                for call in refused {
+                    ctx.messages.push(Message::tool_result(
+                        &call.id,
+                        format!(
+                            "Error: tool-call budget exhausted; `{}` was not run",
+                            call.name
+                        ),
+                    ));
+                }
+                if let Some(max) = tool_budget
+                    && tool_calls_used >= max
+                {
+                    tracing::warn!(
+                        task_id = %task_id,
+                        max_tool_calls = max,
+                        "tool-call budget exhausted, asking for a final answer"
+                    );
+                    ctx.messages.push(Message::user(format!(
+                        "SYSTEM: You have used all {max} tool calls allowed for this task. \
+                         You cannot call any more tools. Answer now using the information \
+                         you have gathered so far."
+                    )));
                 }
 
                 // If the same tool has failed too many times, inject a guidance
@@ -474,8 +754,21 @@ pub async fn react_loop(ctx: &mut AgentContext) -> Result<AgentResponse> {
         stream: true,
     };
 
-    match ctx.llm.stream_chat(&summary_request).await {
+    let llm_span =
+        tracing::info_span!("llm_call", turn = max_turns, model = %summary_request.model);
+    let llm_started = Instant::now();
+    match ctx
+        .llm
+        .stream_chat(&summary_request)
+        .instrument(llm_span)
+        .await
+    {
         Ok((LlmResponse::Text(text), usage)) => {
+            timings.push(TurnTiming {
+                turn: max_turns,
+                llm: llm_started.elapsed(),
+                tools: Vec::new(),
+            });
             total_input = total_input.saturating_add(usage.input_tokens);
             total_output = total_output.saturating_add(usage.output_tokens);
             tracing::info!(
@@ -484,7 +777,8 @@ pub async fn react_loop(ctx: &mut AgentContext) -> Result<AgentResponse> {
                 "forced summary after max turns"
             );
             let mut resp = AgentResponse::new(text, max_turns + 1, task_id)
-                .with_usage(total_input, total_output);
+                .with_usage(total_input, total_output)
+                .with_timings(timings);
             resp.hit_turn_limit = true;
             Ok(resp)
         }
@@ -495,41 +789,55 @@ pub async fn react_loop(ctx: &mut AgentContext) -> Result<AgentResponse> {
     }
 }
 
-/// Execute a batch of tool calls, returning their results.
+/// Execute a batch of tool calls, returning their results and the latency
+/// of each call that actually ran.
 ///
-/// If a `policy_checker` is set on the context, each tool call is checked
-/// before execution.  Denied tools return an error result to the LLM instead
-/// of being executed.
+/// Each tool call is checked against the context's user role and
+/// `policy_checker` before execution.  Denied tools return an error result to
+/// the LLM instead of being executed.
 ///
 /// Calls are executed concurrently using `tokio::spawn` for parallelism.
-async fn execute_tool_calls(calls: &[ToolCall], ctx: &AgentContext) -> Result<Vec<ToolResult>> {
+/// Each call first takes a token from its adapter's rate limit, if any; a
+/// call that would wait too long gets a `rate_limited` error result.
+async fn execute_tool_calls(
+    calls: &[ToolCall],
+    ctx: &AgentContext,
+) -> Result<(Vec<ToolResult>, Vec<ToolTiming>)> {
+    // Refuse the whole batch before anything runs if credentials are missing.
+    for call in calls {
+        if let Some(adapter) = ctx.find_adapter_for_tool(&call.name) {
+            adapter.check_auth()?;
+        }
+    }
+
     let mut handles = Vec::with_capacity(calls.len());
 
     for call in calls {
-        // Policy check: if a policy checker is set, evaluate before executing.
-        if let Some(ref checker) = ctx.policy_checker {
-            let permission = checker(&call.name, &call.arguments);
-            if let ToolPermission::Deny(reason) = permission {
-                tracing::warn!(
-                    tool = %call.name,
-                    reason = %reason,
-                    "tool execution denied by policy"
-                );
-                handles.push(tokio::spawn({
-                    let tool_id = call.id.clone();
-                    let tool_name = call.name.clone();
-                    async move {
-                        ToolResult {
-                            tool_call_id: tool_id,
-                            content: format!(
-                                "Error: tool `{tool_name}` denied by policy: {reason}"
-                            ),
-                            is_error: true,
-                        }
+        let audit = ctx.start_audit(call);
+
+        // Role and policy check before executing.
+        if let ToolPermission::Deny(reason) = ctx.check_permission(call) {
+            tracing::warn!(
+                tool = %call.name,
+                reason = %reason,
+                "tool execution denied by policy"
+            );
+            handles.push(tokio::spawn({
+                let tool_id = call.id.clone();
+                let tool_name = call.name.clone();
+                async move {
+                    let result = ToolResult {
+                        tool_call_id: tool_id,
+                        content: format!("Error: tool `{tool_name}` denied by policy: {reason}"),
+                        is_error: true,
+                    };
+                    if let Some(audit) = audit {
+                        audit.finish(&result, Duration::ZERO).await;
                     }
-                }));
-                continue;
-            }
+                    (result, None)
+                }
+            }));
+            continue;
         }
 
         // Notify tool-start callback if set.
@@ -547,39 +855,67 @@ async fn execute_tool_calls(calls: &[ToolCall], ctx: &AgentContext) -> Result<Ve
         let tool_name = call.name.clone();
         let tool_id = call.id.clone();
         let arguments = call.arguments.clone();
+        let rate_limiter = Arc::clone(&ctx.rate_limiter);
+        let prompt = ctx.on_prompt.clone();
+
+        let span = tracing::info_span!("tool_call", tool = %tool_name, id = %tool_id);
+
+        handles.push(tokio::spawn(
+            async move {
+                let mut duration = Duration::ZERO;
+                let result = async {
+                    rate_limiter.acquire(adapter.adapter_id()).await?;
+                    tracing::debug!("executing tool");
+                    let started = Instant::now();
+                    let result = adapter
+                        .execute_with_prompt(&tool_name, arguments, prompt)
+                        .await;
+                    duration = started.elapsed();
+                    result
+                }
+                .await;
 
-        handles.push(tokio::spawn(async move {
-            tracing::debug!(tool = %tool_name, id = %tool_id, "executing tool");
-
-            let result = adapter.execute(&tool_name, arguments).await;
-
-            match result {
-                Ok(content) => ToolResult {
-                    tool_call_id: tool_id,
-                    content,
-                    is_error: false,
-                },
-                Err(e) => {
-                    tracing::warn!(tool = %tool_name, error = %e, "tool execution failed");
-                    ToolResult {
+                let result = match result {
+                    Ok(content) => ToolResult {
                         tool_call_id: tool_id,
-                        content: format!("Error: {e}"),
-                        is_error: true,
+                        content,
+                        is_error: false,
+                    },
+                    Err(e) => {
+                        tracing::warn!(error = %e, "tool execution failed");
+                        ToolResult {
+                            tool_call_id: tool_id,
+                            content: format!("Error: {e}"),
+                            is_error: true,
+                        }
                     }
+                };
+                if let Some(audit) = audit {
+                    audit.finish(&result, duration).await;
                 }
+                (
+                    result,
+                    Some(ToolTiming {
+                        tool_name,
+                        duration,
+                    }),
+                )
             }
-        }));
+            .instrument(span),
+        ));
     }
 
     let mut results = Vec::with_capacity(handles.len());
+    let mut timings = Vec::with_capacity(handles.len());
     for handle in handles {
-        let result = handle
+        let (result, timing) = handle
             .await
             .map_err(|e| AgentError::Internal(format!("tool execution task panicked: {e}")))?;
         results.push(result);
+        timings.extend(timing);
     }
 
-    Ok(results)
+    Ok((results, timings))
 }
 
 // ---------------------------------------------------------------------------
@@ -587,94 +923,4 @@ async fn execute_tool_calls(calls: &[ToolCall], ctx: &AgentContext) -> Result<Ve
 // ---------------------------------------------------------------------------
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::llm::types::ToolDefinition;
-
-    struct MockAdapter {
-        id: String,
-        tools: Vec<ToolDefinition>,
-    }
-
-    #[async_trait]
-    impl ToolAdapter for MockAdapter {
-        fn adapter_id(&self) -> &str {
-            &self.id
-        }
-
-        fn tool_definitions(&self) -> Vec<ToolDefinition> {
-            self.tools.clone()
-        }
-
-        async fn execute(&self, tool_name: &str, _arguments: Value) -> Result<String> {
-            Ok(format!("mock result for {tool_name}"))
-        }
-    }
-
-    #[test]
-    fn agent_context_collects_tools() {
-        let config = AgentConfig::default();
-        let llm_config =
-            crate::llm::LlmClientConfig::anthropic("test-key", "claude-sonnet-4-20250514");
-        let llm = Arc::new(LlmClient::new(llm_config).unwrap());
-
-        let adapter: Arc<dyn ToolAdapter> = Arc::new(MockAdapter {
-            id: "test".into(),
-            tools: vec![
-                ToolDefinition {
-                    name: "tool_a".into(),
-                    description: "Tool A".into(),
-                    input_schema: serde_json::json!({"type": "object"}),
-                },
-                ToolDefinition {
-                    name: "tool_b".into(),
-                    description: "Tool B".into(),
-                    input_schema: serde_json::json!({"type": "object"}),
-                },
-            ],
-        });
-
-        let ctx = AgentContext::new(llm, vec![adapter], config);
-        let tools = ctx.all_tool_definitions();
-        assert_eq!(tools.len(), 2);
-        assert_eq!(tools[0].name, "tool_a");
-        assert_eq!(tools[1].name, "tool_b");
-    }
-
-    #[test]
-    fn agent_context_finds_adapter_for_tool() {
-        let config = AgentConfig::default();
-        let llm_config =
-            crate::llm::LlmClientConfig::anthropic("test-key", "claude-sonnet-4-20250514");
-        let llm = Arc::new(LlmClient::new(llm_config).unwrap());
-
-        let adapter: Arc<dyn ToolAdapter> = Arc::new(MockAdapter {
-            id: "fs".into(),
-            tools: vec![ToolDefinition {
-                name: "read_file".into(),
-                description: "Read a file".into(),
-                input_schema: serde_json::json!({"type": "object"}),
-            }],
-        });
-
-        let ctx = AgentContext::new(llm, vec![adapter], config);
-        assert!(ctx.find_adapter_for_tool("read_file").is_some());
-        assert!(ctx.find_adapter_for_tool("nonexistent").is_none());
-    }
-
-    #[test]
-    fn agent_context_builder_pattern() {
-        let config = AgentConfig::default();
-        let llm_config =
-            crate::llm::LlmClientConfig::anthropic("test-key", "claude-sonnet-4-20250514");
-        let llm = Arc::new(LlmClient::new(llm_config).unwrap());
-
-        let ctx = AgentContext::new(llm, vec![], config)
-            .with_system_prompt("You are helpful.")
-            .with_user_message("Hello");
-
-        assert_eq!(ctx.messages.len(), 2);
-        assert_eq!(ctx.messages[0].role, crate::llm::Role::System);
-        assert_eq!(ctx.messages[1].role, crate::llm::Role::User);
-    }
-}
+mod tests;
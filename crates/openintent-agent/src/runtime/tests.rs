@@ -0,0 +1,492 @@
+//! Tests for the agent runtime.
+
+use super::*;
+use crate::llm::types::ToolDefinition;
+
+struct MockAdapter {
+    id: String,
+    tools: Vec<ToolDefinition>,
+}
+
+#[async_trait]
+impl ToolAdapter for MockAdapter {
+    fn adapter_id(&self) -> &str {
+        &self.id
+    }
+
+    fn tool_definitions(&self) -> Vec<ToolDefinition> {
+        self.tools.clone()
+    }
+
+    async fn execute(&self, tool_name: &str, _arguments: Value) -> Result<String> {
+        Ok(format!("mock result for {tool_name}"))
+    }
+}
+
+#[test]
+fn agent_context_collects_tools() {
+    let config = AgentConfig::default();
+    let llm_config = crate::llm::LlmClientConfig::anthropic("test-key", "claude-sonnet-4-20250514");
+    let llm = Arc::new(LlmClient::new(llm_config).unwrap());
+
+    let adapter: Arc<dyn ToolAdapter> = Arc::new(MockAdapter {
+        id: "test".into(),
+        tools: vec![
+            ToolDefinition {
+                name: "tool_a".into(),
+                description: "Tool A".into(),
+                input_schema: serde_json::json!({"type": "object"}),
+                destructive: false,
+            },
+            ToolDefinition {
+                name: "tool_b".into(),
+                description: "Tool B".into(),
+                input_schema: serde_json::json!({"type": "object"}),
+                destructive: false,
+            },
+        ],
+    });
+
+    let ctx = AgentContext::new(llm, vec![adapter], config);
+    let tools = ctx.all_tool_definitions();
+    assert_eq!(tools.len(), 2);
+    assert_eq!(tools[0].name, "tool_a");
+    assert_eq!(tools[1].name, "tool_b");
+}
+
+#[test]
+fn disabled_feature_flag_hides_adapter_tools() {
+    let gateway = crate::config::ConfigManager::new().unwrap();
+    gateway
+        .update_value("features", "adapter.browser", "false".into())
+        .unwrap();
+    let config = AgentConfig {
+        disabled_adapters: gateway.get_config().disabled_adapters(),
+        ..AgentConfig::default()
+    };
+    let llm_config = crate::llm::LlmClientConfig::anthropic("test-key", "claude-sonnet-4-20250514");
+    let llm = Arc::new(LlmClient::new(llm_config).unwrap());
+
+    let tool = |name: &str| ToolDefinition {
+        name: name.into(),
+        description: String::new(),
+        input_schema: serde_json::json!({"type": "object"}),
+        destructive: false,
+    };
+    let adapters: Vec<Arc<dyn ToolAdapter>> = vec![
+        Arc::new(MockAdapter {
+            id: "filesystem".into(),
+            tools: vec![tool("fs_read_file")],
+        }),
+        Arc::new(MockAdapter {
+            id: "browser".into(),
+            tools: vec![tool("browser_navigate"), tool("browser_click")],
+        }),
+    ];
+
+    let ctx = AgentContext::new(llm, adapters, config);
+    let names: Vec<String> = ctx
+        .all_tool_definitions()
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    assert_eq!(names, ["fs_read_file"]);
+    assert!(ctx.find_adapter_for_tool("browser_navigate").is_none());
+}
+
+#[test]
+fn agent_context_finds_adapter_for_tool() {
+    let config = AgentConfig::default();
+    let llm_config = crate::llm::LlmClientConfig::anthropic("test-key", "claude-sonnet-4-20250514");
+    let llm = Arc::new(LlmClient::new(llm_config).unwrap());
+
+    let adapter: Arc<dyn ToolAdapter> = Arc::new(MockAdapter {
+        id: "fs".into(),
+        tools: vec![ToolDefinition {
+            name: "read_file".into(),
+            description: "Read a file".into(),
+            input_schema: serde_json::json!({"type": "object"}),
+            destructive: false,
+        }],
+    });
+
+    let ctx = AgentContext::new(llm, vec![adapter], config);
+    assert!(ctx.find_adapter_for_tool("read_file").is_some());
+    assert!(ctx.find_adapter_for_tool("nonexistent").is_none());
+}
+
+#[test]
+fn agent_context_builder_pattern() {
+    let config = AgentConfig::default();
+    let llm_config = crate::llm::LlmClientConfig::anthropic("test-key", "claude-sonnet-4-20250514");
+    let llm = Arc::new(LlmClient::new(llm_config).unwrap());
+
+    let ctx = AgentContext::new(llm, vec![], config)
+        .with_system_prompt("You are helpful.")
+        .with_user_message("Hello");
+
+    assert_eq!(ctx.messages.len(), 2);
+    assert_eq!(ctx.messages[0].role, crate::llm::Role::System);
+    assert_eq!(ctx.messages[1].role, crate::llm::Role::User);
+}
+
+#[tokio::test]
+async fn scripted_backend_drives_two_turn_conversation() {
+    use crate::llm::{LlmResponse, ScriptedBackend, ToolCall};
+
+    let backend = Arc::new(ScriptedBackend::new([
+        LlmResponse::ToolCalls(vec![ToolCall {
+            id: "call_1".into(),
+            name: "read_file".into(),
+            arguments: serde_json::json!({"path": "notes.txt"}),
+        }]),
+        LlmResponse::Text("The notes are about testing.".into()),
+    ]));
+    let llm_config = crate::llm::LlmClientConfig::anthropic("test-key", "test-model");
+    let llm = Arc::new(
+        LlmClient::new(llm_config)
+            .unwrap()
+            .with_backend(backend.clone()),
+    );
+    let adapter: Arc<dyn ToolAdapter> = Arc::new(MockAdapter {
+        id: "fs".into(),
+        tools: vec![ToolDefinition {
+            name: "read_file".into(),
+            description: "Read a file".into(),
+            input_schema: serde_json::json!({"type": "object"}),
+            destructive: false,
+        }],
+    });
+
+    let mut ctx = AgentContext::new(llm, vec![adapter], AgentConfig::default())
+        .with_user_message("Summarize notes.txt");
+    let response = react_loop(&mut ctx).await.unwrap();
+
+    assert_eq!(response.text, "The notes are about testing.");
+    assert_eq!(response.turns_used, 2);
+    assert_eq!(backend.remaining(), 0);
+
+    // One timing per turn; only the tool-calling turn ran a tool.
+    assert_eq!(response.timings.len(), 2);
+    let tools: Vec<&str> = response.timings[0]
+        .tools
+        .iter()
+        .map(|t| t.tool_name.as_str())
+        .collect();
+    assert_eq!(tools, ["read_file"]);
+    assert!(response.timings[1].tools.is_empty());
+    assert!(response.timing_summary().starts_with("LLM "));
+    assert!(response.timing_summary().contains(", read_file "));
+
+    let requests = backend.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].tools.len(), 1);
+    assert!(
+        requests[1]
+            .messages
+            .iter()
+            .any(|m| m.content.contains("mock result for read_file")),
+        "tool result must be fed back to the model"
+    );
+}
+
+/// Adapter that lacks credentials and must never be executed.
+struct UnauthenticatedAdapter;
+
+#[async_trait]
+impl ToolAdapter for UnauthenticatedAdapter {
+    fn adapter_id(&self) -> &str {
+        "github"
+    }
+
+    fn tool_definitions(&self) -> Vec<ToolDefinition> {
+        vec![ToolDefinition {
+            name: "github_list_issues".into(),
+            description: "List issues".into(),
+            input_schema: serde_json::json!({"type": "object"}),
+            destructive: false,
+        }]
+    }
+
+    async fn execute(&self, _tool_name: &str, _arguments: Value) -> Result<String> {
+        panic!("an unauthenticated adapter must not be executed");
+    }
+
+    fn check_auth(&self) -> Result<()> {
+        Err(AgentError::AuthRequired {
+            provider: "github".into(),
+            how: "run `openintent vault set github token`".into(),
+        })
+    }
+}
+
+#[tokio::test]
+async fn unauthenticated_adapter_short_circuits_with_auth_required() {
+    use crate::llm::{LlmResponse, ScriptedBackend, ToolCall};
+
+    let backend = Arc::new(ScriptedBackend::new([LlmResponse::ToolCalls(vec![
+        ToolCall {
+            id: "call_1".into(),
+            name: "github_list_issues".into(),
+            arguments: serde_json::json!({}),
+        },
+    ])]));
+    let llm_config = crate::llm::LlmClientConfig::anthropic("test-key", "test-model");
+    let llm = Arc::new(LlmClient::new(llm_config).unwrap().with_backend(backend));
+    let adapter: Arc<dyn ToolAdapter> = Arc::new(UnauthenticatedAdapter);
+
+    let mut ctx = AgentContext::new(llm, vec![adapter], AgentConfig::default())
+        .with_user_message("List my open issues");
+    let err = react_loop(&mut ctx).await.unwrap_err();
+
+    match err {
+        AgentError::AuthRequired { provider, .. } => assert_eq!(provider, "github"),
+        other => panic!("expected AuthRequired, got {other:?}"),
+    }
+}
+
+/// Adapter that records when each of its calls started.
+struct TimedAdapter {
+    started: std::sync::Mutex<Vec<tokio::time::Instant>>,
+}
+
+#[async_trait]
+impl ToolAdapter for TimedAdapter {
+    fn adapter_id(&self) -> &str {
+        "web_search"
+    }
+
+    fn tool_definitions(&self) -> Vec<ToolDefinition> {
+        vec![ToolDefinition {
+            name: "web_search".into(),
+            description: "Search the web".into(),
+            input_schema: serde_json::json!({"type": "object"}),
+            destructive: false,
+        }]
+    }
+
+    async fn execute(&self, _tool_name: &str, _arguments: Value) -> Result<String> {
+        self.started
+            .lock()
+            .unwrap()
+            .push(tokio::time::Instant::now());
+        Ok("results".into())
+    }
+}
+
+#[tokio::test]
+async fn rate_limit_delays_third_rapid_call() {
+    use crate::llm::{LlmResponse, ScriptedBackend, ToolCall};
+
+    let calls = (1..=3)
+        .map(|i| ToolCall {
+            id: format!("call_{i}"),
+            name: "web_search".into(),
+            arguments: serde_json::json!({}),
+        })
+        .collect();
+    let backend = Arc::new(ScriptedBackend::new([
+        LlmResponse::ToolCalls(calls),
+        LlmResponse::Text("done".into()),
+    ]));
+    let llm_config = crate::llm::LlmClientConfig::anthropic("test-key", "test-model");
+    let llm = Arc::new(LlmClient::new(llm_config).unwrap().with_backend(backend));
+    let adapter = Arc::new(TimedAdapter {
+        started: std::sync::Mutex::new(Vec::new()),
+    });
+    let config = AgentConfig {
+        rate_limits: HashMap::from([("web_search".to_owned(), RateLimit::per_second(2))]),
+        ..AgentConfig::default()
+    };
+
+    let begin = tokio::time::Instant::now();
+    let mut ctx = AgentContext::new(llm, vec![adapter.clone()], config)
+        .with_user_message("Search three things");
+    react_loop(&mut ctx).await.unwrap();
+
+    let mut started: Vec<_> = adapter
+        .started
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|t| t.duration_since(begin))
+        .collect();
+    started.sort();
+    assert_eq!(started.len(), 3);
+    assert!(started[1] < std::time::Duration::from_millis(200));
+    assert!(
+        started[2] >= std::time::Duration::from_millis(450),
+        "third call must wait for a token, started after {:?}",
+        started[2]
+    );
+}
+
+#[tokio::test]
+async fn tool_budget_stops_runaway_tool_loop() {
+    use crate::llm::{LlmResponse, ScriptedBackend, ToolCall};
+
+    let search = |i: u32| ToolCall {
+        id: format!("call_{i}"),
+        name: "web_search".into(),
+        arguments: serde_json::json!({}),
+    };
+    let backend = Arc::new(ScriptedBackend::new([
+        LlmResponse::ToolCalls(vec![search(1), search(2)]),
+        LlmResponse::ToolCalls(vec![search(3), search(4)]),
+        LlmResponse::Text("Here is what I found.".into()),
+    ]));
+    let llm_config = crate::llm::LlmClientConfig::anthropic("test-key", "test-model");
+    let llm = Arc::new(
+        LlmClient::new(llm_config)
+            .unwrap()
+            .with_backend(backend.clone()),
+    );
+    let adapter = Arc::new(TimedAdapter {
+        started: std::sync::Mutex::new(Vec::new()),
+    });
+    let config = AgentConfig {
+        max_tool_calls_total: Some(3),
+        ..AgentConfig::default()
+    };
+
+    let mut ctx = AgentContext::new(llm, vec![adapter.clone()], config)
+        .with_user_message("Research everything");
+    let response = react_loop(&mut ctx).await.unwrap();
+
+    assert_eq!(response.text, "Here is what I found.");
+    assert_eq!(adapter.started.lock().unwrap().len(), 3);
+    let requests = backend.requests();
+    assert_eq!(requests.len(), 3);
+    assert!(!requests[1].tools.is_empty());
+    assert!(
+        requests[2].tools.is_empty(),
+        "no tools once budget is spent"
+    );
+    let messages = &requests[2].messages;
+    assert!(
+        messages
+            .iter()
+            .any(|m| m.content.contains("`web_search` was not run"))
+    );
+    assert!(
+        messages
+            .last()
+            .is_some_and(|m| m.content.contains("used all 3 tool calls"))
+    );
+}
+
+/// Adapter exposing a destructive tool that records whether it ran.
+struct DeleteAdapter {
+    ran: std::sync::atomic::AtomicBool,
+}
+
+#[async_trait]
+impl ToolAdapter for DeleteAdapter {
+    fn adapter_id(&self) -> &str {
+        "filesystem"
+    }
+
+    fn tool_definitions(&self) -> Vec<ToolDefinition> {
+        vec![ToolDefinition {
+            name: "fs_delete".into(),
+            description: "Delete a file".into(),
+            input_schema: serde_json::json!({"type": "object"}),
+            destructive: true,
+        }]
+    }
+
+    async fn execute(&self, _tool_name: &str, _arguments: Value) -> Result<String> {
+        self.ran.store(true, std::sync::atomic::Ordering::SeqCst);
+        Ok("deleted".into())
+    }
+}
+
+/// Run one `fs_delete` call as `role`; returns whether the tool ran and the
+/// second request sent to the model.
+async fn delete_as(role: UserRole) -> (bool, ChatRequest) {
+    use crate::llm::{LlmResponse, ScriptedBackend, ToolCall};
+
+    let backend = Arc::new(ScriptedBackend::new([
+        LlmResponse::ToolCalls(vec![ToolCall {
+            id: "call_1".into(),
+            name: "fs_delete".into(),
+            arguments: serde_json::json!({"path": "notes.txt"}),
+        }]),
+        LlmResponse::Text("done".into()),
+    ]));
+    let llm_config = crate::llm::LlmClientConfig::anthropic("test-key", "test-model");
+    let llm = Arc::new(
+        LlmClient::new(llm_config)
+            .unwrap()
+            .with_backend(backend.clone()),
+    );
+    let adapter = Arc::new(DeleteAdapter {
+        ran: std::sync::atomic::AtomicBool::new(false),
+    });
+
+    let mut ctx = AgentContext::new(llm, vec![adapter.clone()], AgentConfig::default())
+        .with_user_role(role)
+        .with_user_message("Delete notes.txt");
+    react_loop(&mut ctx).await.unwrap();
+
+    let ran = adapter.ran.load(std::sync::atomic::Ordering::SeqCst);
+    (ran, backend.requests().remove(1))
+}
+
+#[tokio::test]
+async fn viewer_is_denied_destructive_tool_but_admin_is_allowed() {
+    let (ran, request) = delete_as(UserRole::Viewer).await;
+    assert!(!ran, "a viewer must not be able to delete files");
+    assert!(
+        request
+            .messages
+            .iter()
+            .any(|m| m.content.contains("denied by policy") && m.content.contains("read-only")),
+        "the denial must be relayed to the model"
+    );
+
+    let (ran, _) = delete_as(UserRole::Admin).await;
+    assert!(ran, "an admin may delete files");
+}
+
+#[tokio::test]
+async fn tool_call_is_recorded_in_audit_trail() {
+    use crate::llm::{LlmResponse, ScriptedBackend, ToolCall};
+
+    let db = openintent_store::Database::open_in_memory().unwrap();
+    db.run_migrations().await.unwrap();
+    let audit = ToolAuditStore::new(db);
+
+    let backend = Arc::new(ScriptedBackend::new([
+        LlmResponse::ToolCalls(vec![ToolCall {
+            id: "call_1".into(),
+            name: "fs_delete".into(),
+            arguments: serde_json::json!({"path": "notes.txt", "password": "hunter2"}),
+        }]),
+        LlmResponse::Text("done".into()),
+    ]));
+    let llm_config = crate::llm::LlmClientConfig::anthropic("test-key", "test-model");
+    let llm = Arc::new(LlmClient::new(llm_config).unwrap().with_backend(backend));
+    let adapter = Arc::new(DeleteAdapter {
+        ran: std::sync::atomic::AtomicBool::new(false),
+    });
+
+    let mut ctx = AgentContext::new(llm, vec![adapter], AgentConfig::default())
+        .with_session_id("session-1")
+        .with_tool_audit(audit.clone())
+        .with_user_message("Delete notes.txt");
+    react_loop(&mut ctx).await.unwrap();
+
+    let entries = audit.list_for_session("session-1").await.unwrap();
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert_eq!(entry.tool_name, "fs_delete");
+    assert_eq!(entry.adapter_id.as_deref(), Some("filesystem"));
+    assert_eq!(entry.task_id, ctx.task_id.to_string());
+    assert!(entry.success);
+    assert_eq!(entry.result, "deleted");
+    assert_eq!(
+        entry.arguments,
+        serde_json::json!({"path": "notes.txt", "password": crate::audit::REDACTED})
+    );
+}
@@ -0,0 +1,263 @@
+//! Truncation of oversized tool results.
+//!
+//! A single large tool result (a 50 KB file read, a long search listing) fed
+//! verbatim to the LLM can fill most of its context window.  Before a result
+//! is appended to the conversation the runtime shortens anything longer than
+//! [`TruncationConfig::max_chars`] with the configured
+//! [`TruncationStrategy`], noting what was cut so the LLM can ask for the
+//! rest with a narrower call.
+//!
+//! JSON results are pruned structurally rather than cut mid-token: long
+//! arrays and objects keep their first entries, deep nesting is collapsed,
+//! and long strings are shortened, so the result stays valid JSON.
+
+use serde_json::{Map, Value};
+
+/// How an oversized tool result is shortened.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TruncationStrategy {
+    /// Keep whole lines from the start and end, dropping the middle.
+    HeadTail,
+    /// Keep the first and last characters, with an ellipsis marker between.
+    MiddleEllipsis,
+    /// Prune JSON results structurally so they stay valid JSON; other
+    /// results fall back to [`HeadTail`](Self::HeadTail).
+    #[default]
+    JsonShallow,
+}
+
+/// Configuration for tool-result truncation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TruncationConfig {
+    /// Results longer than this many bytes are truncated to fit.
+    pub max_chars: usize,
+    /// How oversized results are shortened.
+    pub strategy: TruncationStrategy,
+}
+
+impl Default for TruncationConfig {
+    fn default() -> Self {
+        Self {
+            // Roughly 5k tokens.
+            max_chars: 20_000,
+            strategy: TruncationStrategy::default(),
+        }
+    }
+}
+
+/// Pruning limits tried in order by [`TruncationStrategy::JsonShallow`] until
+/// the result fits: `(items per array or object, depth, string length)`.
+const JSON_LEVELS: &[(usize, usize, usize)] = &[
+    (100, 8, 2_000),
+    (50, 6, 1_000),
+    (20, 5, 500),
+    (10, 4, 200),
+    (5, 3, 100),
+    (2, 2, 80),
+    (1, 1, 80),
+];
+
+/// Room kept for the truncation note appended to text results.
+const NOTE_RESERVE: usize = 200;
+
+/// Shorten `content` to fit `config.max_chars`, returning it unchanged if it
+/// already fits.
+pub fn truncate_tool_result(content: &str, config: &TruncationConfig) -> String {
+    if content.len() <= config.max_chars {
+        return content.to_owned();
+    }
+    tracing::debug!(
+        len = content.len(),
+        max_chars = config.max_chars,
+        strategy = ?config.strategy,
+        "truncating oversized tool result"
+    );
+
+    let budget = config.max_chars.saturating_sub(NOTE_RESERVE);
+    let truncated = match config.strategy {
+        TruncationStrategy::JsonShallow => {
+            if let Some(pruned) = serde_json::from_str::<Value>(content)
+                .ok()
+                .and_then(|value| prune_to_fit(&value, config.max_chars))
+            {
+                // The omission markers inside the JSON are the note.
+                return pruned;
+            }
+            head_tail(content, budget)
+        }
+        TruncationStrategy::HeadTail => head_tail(content, budget),
+        TruncationStrategy::MiddleEllipsis => middle_ellipsis(content, budget),
+    };
+    format!(
+        "{truncated}\n\n[Output truncated from {} to about {} characters. \
+         To see more, call the tool again with a narrower request \
+         (a line range, offset, limit, or filter).]",
+        content.len(),
+        truncated.len()
+    )
+}
+
+/// Prune `value` with increasingly strict limits until its serialization
+/// fits in `max_chars`.
+fn prune_to_fit(value: &Value, max_chars: usize) -> Option<String> {
+    JSON_LEVELS.iter().find_map(|&(items, depth, string)| {
+        let pruned = prune(value, items, depth, string);
+        serde_json::to_string(&pruned)
+            .ok()
+            .filter(|s| s.len() <= max_chars)
+    })
+}
+
+/// Copy `value`, keeping at most `items` entries per array or object,
+/// collapsing containers nested `depth` levels down, and shortening strings
+/// longer than `string` characters.  Omissions are marked in place.
+fn prune(value: &Value, items: usize, depth: usize, string: usize) -> Value {
+    match value {
+        Value::Array(array) if depth == 0 => {
+            Value::String(format!("[... {} items omitted]", array.len()))
+        }
+        Value::Object(object) if depth == 0 => {
+            Value::String(format!("{{... {} keys omitted}}", object.len()))
+        }
+        Value::Array(array) => {
+            let mut pruned: Vec<Value> = array
+                .iter()
+                .take(items)
+                .map(|v| prune(v, items, depth - 1, string))
+                .collect();
+            if array.len() > items {
+                pruned.push(Value::String(format!(
+                    "... {} more items omitted; request a narrower range to see them",
+                    array.len() - items
+                )));
+            }
+            Value::Array(pruned)
+        }
+        Value::Object(object) => {
+            let mut pruned: Map<String, Value> = object
+                .iter()
+                .take(items)
+                .map(|(k, v)| (k.clone(), prune(v, items, depth - 1, string)))
+                .collect();
+            if object.len() > items {
+                pruned.insert(
+                    "...".into(),
+                    Value::String(format!("{} more keys omitted", object.len() - items)),
+                );
+            }
+            Value::Object(pruned)
+        }
+        Value::String(s) if s.chars().count() > string => {
+            let kept: String = s.chars().take(string).collect();
+            Value::String(format!(
+                "{kept}... [{} characters omitted]",
+                s.chars().count() - string
+            ))
+        }
+        other => other.clone(),
+    }
+}
+
+/// Keep whole lines from the start (three quarters of `budget`) and the end
+/// (the rest), replacing the lines between with a marker.
+fn head_tail(content: &str, budget: usize) -> String {
+    let head_budget = budget * 3 / 4;
+    let tail_budget = budget - head_budget;
+
+    let mut head_len = 0;
+    for line in content.split_inclusive('\n') {
+        if head_len + line.len() > head_budget {
+            break;
+        }
+        head_len += line.len();
+    }
+    // A single huge first line: cut it rather than keep nothing.
+    if head_len == 0 {
+        head_len = content.floor_char_boundary(head_budget);
+    }
+
+    let rest = &content[head_len..];
+    let mut tail_start = rest.len();
+    for line in rest.split_inclusive('\n').rev() {
+        if rest.len() - tail_start + line.len() > tail_budget {
+            break;
+        }
+        tail_start -= line.len();
+    }
+
+    let (head, omitted, tail) = (
+        &content[..head_len],
+        &rest[..tail_start],
+        &rest[tail_start..],
+    );
+    let separator = if head.ends_with('\n') { "" } else { "\n" };
+    format!(
+        "{head}{separator}[... {} lines omitted ...]\n{tail}",
+        omitted.lines().count()
+    )
+}
+
+/// Keep the first and last halves of `budget` characters, with a marker in
+/// place of the middle.
+fn middle_ellipsis(content: &str, budget: usize) -> String {
+    let head_end = content.floor_char_boundary(budget / 2);
+    let tail_start = content.ceil_char_boundary(content.len() - budget / 2);
+    format!(
+        "{}...[{} characters omitted]...{}",
+        &content[..head_end],
+        tail_start - head_end,
+        &content[tail_start..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(strategy: TruncationStrategy) -> TruncationConfig {
+        TruncationConfig {
+            max_chars: 1_000,
+            strategy,
+        }
+    }
+
+    #[test]
+    fn oversized_json_array_is_pruned_to_valid_json() {
+        let rows: Vec<Value> = (0..500)
+            .map(|i| serde_json::json!({ "id": i, "name": format!("file-{i}.txt"), "tags": ["a", "b"] }))
+            .collect();
+        let content = serde_json::to_string(&rows).unwrap();
+        assert!(content.len() > 1_000);
+
+        let truncated = truncate_tool_result(&content, &config(TruncationStrategy::JsonShallow));
+
+        assert!(truncated.len() <= 1_000);
+        let parsed: Vec<Value> =
+            serde_json::from_str(&truncated).expect("pruned result must stay valid JSON");
+        assert_eq!(parsed[0]["name"], "file-0.txt");
+        let marker = parsed.last().and_then(Value::as_str).unwrap();
+        assert!(marker.contains("more items omitted"), "{marker}");
+    }
+
+    #[test]
+    fn text_strategies_keep_both_ends_and_add_a_note() {
+        let content: String = (0..200).map(|i| format!("line {i}\n")).collect();
+
+        for strategy in [
+            TruncationStrategy::HeadTail,
+            TruncationStrategy::MiddleEllipsis,
+            TruncationStrategy::JsonShallow,
+        ] {
+            let truncated = truncate_tool_result(&content, &config(strategy));
+            assert!(truncated.len() <= 1_000, "{strategy:?}");
+            assert!(truncated.starts_with("line 0\n"), "{strategy:?}");
+            assert!(truncated.contains("line 199"), "{strategy:?}");
+            assert!(truncated.contains("omitted"), "{strategy:?}");
+            assert!(truncated.contains("[Output truncated"), "{strategy:?}");
+        }
+        assert_eq!(
+            truncate_tool_result("short", &config(TruncationStrategy::HeadTail)),
+            "short"
+        );
+    }
+}
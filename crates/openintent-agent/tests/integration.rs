@@ -28,10 +28,11 @@ fn needs_compaction_below_threshold() {
         max_messages: 50,
         keep_recent: 10,
         model: "test".into(),
+        ..CompactionConfig::default()
     };
 
     let few_messages: Vec<Message> = (0..10).map(|i| Message::user(format!("msg {i}"))).collect();
-    assert!(!needs_compaction(&few_messages, &config));
+    assert!(!needs_compaction(&few_messages, "test", &config));
 }
 
 #[test]
@@ -40,10 +41,11 @@ fn needs_compaction_above_threshold() {
         max_messages: 50,
         keep_recent: 10,
         model: "test".into(),
+        ..CompactionConfig::default()
     };
 
     let many_messages: Vec<Message> = (0..60).map(|i| Message::user(format!("msg {i}"))).collect();
-    assert!(needs_compaction(&many_messages, &config));
+    assert!(needs_compaction(&many_messages, "test", &config));
 }
 
 #[test]
@@ -52,15 +54,16 @@ fn needs_compaction_exactly_at_threshold() {
         max_messages: 10,
         keep_recent: 5,
         model: "test".into(),
+        ..CompactionConfig::default()
     };
 
     // Exactly at max_messages should not trigger (needs to exceed).
     let exact: Vec<Message> = (0..10).map(|i| Message::user(format!("msg {i}"))).collect();
-    assert!(!needs_compaction(&exact, &config));
+    assert!(!needs_compaction(&exact, "test", &config));
 
     // One over should trigger.
     let over: Vec<Message> = (0..11).map(|i| Message::user(format!("msg {i}"))).collect();
-    assert!(needs_compaction(&over, &config));
+    assert!(needs_compaction(&over, "test", &config));
 }
 
 // ═══════════════════════════════════════════════════════════════════════
@@ -209,6 +212,7 @@ async fn compact_messages_below_keep_recent_returns_as_is() {
         max_messages: 50,
         keep_recent: 20,
         model: "test".into(),
+        ..CompactionConfig::default()
     };
 
     // 1 system + 5 conversation = 6 messages, well below keep_recent=20.
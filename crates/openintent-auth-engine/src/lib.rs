@@ -7,6 +7,7 @@
 //! - **Device Authorization Grant** (RFC 8628)
 //! - **Local callback server** for OAuth browser redirects
 //! - **Token lifecycle management**: storage, refresh, and revocation
+//! - **Provider registry** with built-in email providers and config overrides
 //!
 //! All tokens are stored encrypted in the [`openintent_vault`] credential
 //! vault. The [`AuthManager`] orchestrates complete authentication flows
@@ -54,12 +55,14 @@ pub mod device_code;
 pub mod error;
 pub mod manager;
 pub mod oauth;
+pub mod oauth_providers;
 
 // Re-export key types at the crate root for convenience.
 pub use callback::CallbackServer;
 pub use device_code::{DeviceCodeConfig, DeviceCodeFlow, DeviceCodeResponse};
 pub use manager::AuthManager;
 pub use oauth::{OAuthConfig, OAuthFlow, OAuthTokens};
+pub use oauth_providers::{OAuthProviderRegistry, ProviderDefinition, ProviderOverride};
 
 use serde::{Deserialize, Serialize};
 
@@ -5,12 +5,13 @@
 //! primary entry point for consuming code that needs to authenticate with
 //! third-party services.
 
+use std::future::Future;
 use std::sync::Mutex;
 
 use openintent_vault::store::{CredentialType, Vault};
 
 use crate::callback::CallbackServer;
-use crate::device_code::{DeviceCodeConfig, DeviceCodeFlow};
+use crate::device_code::{DeviceCodeConfig, DeviceCodeFlow, DeviceCodeResponse};
 use crate::error::{AuthEngineError, Result};
 use crate::oauth::{OAuthConfig, OAuthFlow, OAuthTokens, generate_pkce_verifier, pkce_challenge};
 
@@ -138,6 +139,30 @@ impl AuthManager {
         provider: &str,
         config: DeviceCodeConfig,
     ) -> Result<OAuthTokens> {
+        self.authenticate_device_code_with(provider, config, |_| async {})
+            .await
+    }
+
+    /// Perform a device authorization grant flow, handing the device code
+    /// response to `on_code` before polling starts.
+    ///
+    /// Use this to show the user code and verification URI somewhere other
+    /// than the log, e.g. in a chat.  Polling begins once `on_code`
+    /// completes.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`authenticate_device_code`](Self::authenticate_device_code).
+    pub async fn authenticate_device_code_with<F, Fut>(
+        &self,
+        provider: &str,
+        config: DeviceCodeConfig,
+        on_code: F,
+    ) -> Result<OAuthTokens>
+    where
+        F: FnOnce(DeviceCodeResponse) -> Fut,
+        Fut: Future<Output = ()>,
+    {
         tracing::info!(provider = provider, "starting device code flow");
 
         let flow = DeviceCodeFlow::new(config);
@@ -158,6 +183,7 @@ impl AuthManager {
                 "or open this URL directly"
             );
         }
+        on_code(device_response.clone()).await;
 
         // Step 3: Poll for token.
         let tokens = flow
@@ -0,0 +1,368 @@
+//! Registry of known OAuth providers.
+//!
+//! Chat adapters and skills look providers up by name instead of carrying
+//! their own copies of endpoint URLs and scopes.  The registry starts from
+//! the built-in definitions below and can be extended or overridden from
+//! the `[oauth_providers]` section of a TOML config file:
+//!
+//! ```toml
+//! [oauth_providers.gmail]
+//! client_id = "1234.apps.googleusercontent.com"
+//! client_secret = "..."
+//!
+//! [oauth_providers.gitea]
+//! auth_url = "https://git.example.com/login/oauth/authorize"
+//! token_url = "https://git.example.com/login/oauth/access_token"
+//! scopes = ["read:user"]
+//! ```
+//!
+//! Fields left out of an override keep their built-in values; a provider
+//! that is not built in must at least set `auth_url` and `token_url`.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::device_code::DeviceCodeConfig;
+use crate::error::{AuthEngineError, Result};
+use crate::oauth::OAuthConfig;
+
+/// Redirect URI served by the local [`CallbackServer`](crate::CallbackServer).
+pub const DEFAULT_REDIRECT_URI: &str = "http://127.0.0.1:8400/callback";
+
+/// Name of the config file section holding provider overrides.
+const CONFIG_SECTION: &str = "oauth_providers";
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// Everything needed to run an OAuth flow against one provider.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderDefinition {
+    /// The OAuth client ID registered with the provider.
+    pub client_id: String,
+
+    /// The OAuth client secret (confidential clients only).
+    pub client_secret: Option<String>,
+
+    /// The authorization endpoint URL.
+    pub auth_url: String,
+
+    /// The token endpoint URL.
+    pub token_url: String,
+
+    /// The device authorization endpoint, for providers supporting RFC 8628.
+    pub device_auth_url: Option<String>,
+
+    /// The redirect URI registered with the provider.
+    pub redirect_uri: String,
+
+    /// The scopes to request.
+    pub scopes: Vec<String>,
+}
+
+/// A partial [`ProviderDefinition`] read from a config file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProviderOverride {
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    pub auth_url: Option<String>,
+    pub token_url: Option<String>,
+    pub device_auth_url: Option<String>,
+    pub redirect_uri: Option<String>,
+    pub scopes: Option<Vec<String>>,
+}
+
+/// Named OAuth provider definitions.
+#[derive(Debug, Clone, Default)]
+pub struct OAuthProviderRegistry {
+    providers: HashMap<String, ProviderDefinition>,
+}
+
+// ---------------------------------------------------------------------------
+// Implementation
+// ---------------------------------------------------------------------------
+
+impl ProviderDefinition {
+    fn builtin(auth_url: &str, token_url: &str, scopes: &[&str]) -> Self {
+        Self {
+            client_id: String::new(),
+            client_secret: None,
+            auth_url: auth_url.to_string(),
+            token_url: token_url.to_string(),
+            device_auth_url: None,
+            redirect_uri: DEFAULT_REDIRECT_URI.to_string(),
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    /// Build the authorization code flow configuration.
+    pub fn oauth_config(&self) -> OAuthConfig {
+        OAuthConfig {
+            client_id: self.client_id.clone(),
+            client_secret: self.client_secret.clone(),
+            auth_url: self.auth_url.clone(),
+            token_url: self.token_url.clone(),
+            redirect_uri: self.redirect_uri.clone(),
+            scopes: self.scopes.clone(),
+        }
+    }
+
+    /// Build the device code flow configuration, if the provider has one.
+    pub fn device_code_config(&self) -> Option<DeviceCodeConfig> {
+        self.device_auth_url
+            .as_ref()
+            .map(|device_auth_url| DeviceCodeConfig {
+                client_id: self.client_id.clone(),
+                device_auth_url: device_auth_url.clone(),
+                token_url: self.token_url.clone(),
+                scopes: self.scopes.clone(),
+            })
+    }
+
+    fn apply(&mut self, overrides: ProviderOverride) {
+        let ProviderOverride {
+            client_id,
+            client_secret,
+            auth_url,
+            token_url,
+            device_auth_url,
+            redirect_uri,
+            scopes,
+        } = overrides;
+        if let Some(v) = client_id {
+            self.client_id = v;
+        }
+        if client_secret.is_some() {
+            self.client_secret = client_secret;
+        }
+        if let Some(v) = auth_url {
+            self.auth_url = v;
+        }
+        if let Some(v) = token_url {
+            self.token_url = v;
+        }
+        if device_auth_url.is_some() {
+            self.device_auth_url = device_auth_url;
+        }
+        if let Some(v) = redirect_uri {
+            self.redirect_uri = v;
+        }
+        if let Some(v) = scopes {
+            self.scopes = v;
+        }
+    }
+}
+
+impl OAuthProviderRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry holding the built-in email providers (`gmail`,
+    /// `outlook`, `yahoo`).  Client IDs are empty until overridden.
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+        registry.register(
+            "gmail",
+            ProviderDefinition::builtin(
+                "https://accounts.google.com/o/oauth2/v2/auth",
+                "https://oauth2.googleapis.com/token",
+                &["https://mail.google.com/"],
+            ),
+        );
+        registry.register(
+            "outlook",
+            ProviderDefinition {
+                device_auth_url: Some(
+                    "https://login.microsoftonline.com/common/oauth2/v2.0/devicecode".to_string(),
+                ),
+                ..ProviderDefinition::builtin(
+                    "https://login.microsoftonline.com/common/oauth2/v2.0/authorize",
+                    "https://login.microsoftonline.com/common/oauth2/v2.0/token",
+                    &[
+                        "https://outlook.office.com/IMAP.AccessAsUser.All",
+                        "https://outlook.office.com/SMTP.Send",
+                        "offline_access",
+                    ],
+                )
+            },
+        );
+        registry.register(
+            "yahoo",
+            ProviderDefinition::builtin(
+                "https://api.login.yahoo.com/oauth2/request_auth",
+                "https://api.login.yahoo.com/oauth2/get_token",
+                &["mail-r", "mail-w"],
+            ),
+        );
+        registry
+    }
+
+    /// Load the built-in providers with the overrides from `path` applied.
+    ///
+    /// A missing file or a file without an `[oauth_providers]` section
+    /// yields the built-in providers unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AuthEngineError::Io`] if the file exists but cannot be
+    /// read, or [`AuthEngineError::InvalidConfig`] if it is malformed.
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut registry = Self::builtin();
+        match std::fs::read_to_string(path) {
+            Ok(content) => registry.apply_toml(&content)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        Ok(registry)
+    }
+
+    /// Apply the `[oauth_providers]` section of a TOML document.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AuthEngineError::InvalidConfig`] if the document does not
+    /// parse or an override is invalid.
+    pub fn apply_toml(&mut self, content: &str) -> Result<()> {
+        #[derive(Deserialize)]
+        struct ConfigFile {
+            #[serde(default, rename = "oauth_providers")]
+            providers: HashMap<String, ProviderOverride>,
+        }
+
+        let file: ConfigFile =
+            toml::from_str(content).map_err(|e| AuthEngineError::InvalidConfig {
+                reason: format!("[{CONFIG_SECTION}]: {e}"),
+            })?;
+        let mut overrides: Vec<_> = file.providers.into_iter().collect();
+        overrides.sort_by(|a, b| a.0.cmp(&b.0));
+        for (name, overrides) in overrides {
+            self.apply_override(&name, overrides)?;
+        }
+        Ok(())
+    }
+
+    /// Merge `overrides` into the provider called `name`, adding it if it
+    /// is not registered yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AuthEngineError::InvalidConfig`] if `name` is new and the
+    /// override lacks `auth_url` or `token_url`.
+    pub fn apply_override(&mut self, name: &str, overrides: ProviderOverride) -> Result<()> {
+        if let Some(existing) = self.providers.get_mut(name) {
+            existing.apply(overrides);
+            return Ok(());
+        }
+
+        let (Some(auth_url), Some(token_url)) = (&overrides.auth_url, &overrides.token_url) else {
+            return Err(AuthEngineError::InvalidConfig {
+                reason: format!(
+                    "{CONFIG_SECTION}.{name}: a new provider needs auth_url and token_url"
+                ),
+            });
+        };
+        let mut definition = ProviderDefinition::builtin(auth_url, token_url, &[]);
+        definition.apply(overrides);
+        self.register(name, definition);
+        Ok(())
+    }
+
+    /// Add or replace a provider.
+    pub fn register(&mut self, name: impl Into<String>, definition: ProviderDefinition) {
+        self.providers.insert(name.into(), definition);
+    }
+
+    /// Look up a provider definition.
+    pub fn get(&self, name: &str) -> Option<&ProviderDefinition> {
+        self.providers.get(name)
+    }
+
+    /// The authorization code flow configuration for `name`.
+    pub fn provider_config(&self, name: &str) -> Option<OAuthConfig> {
+        self.get(name).map(ProviderDefinition::oauth_config)
+    }
+
+    /// The device code flow configuration for `name`, if it supports one.
+    pub fn device_code_config(&self, name: &str) -> Option<DeviceCodeConfig> {
+        self.get(name)
+            .and_then(ProviderDefinition::device_code_config)
+    }
+
+    /// Registered provider names, sorted.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.providers.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_gmail_config() {
+        let registry = OAuthProviderRegistry::builtin();
+        let config = registry.provider_config("gmail").unwrap();
+        assert_eq!(
+            config.auth_url,
+            "https://accounts.google.com/o/oauth2/v2/auth"
+        );
+        assert_eq!(config.token_url, "https://oauth2.googleapis.com/token");
+        assert_eq!(config.redirect_uri, DEFAULT_REDIRECT_URI);
+        assert_eq!(config.scopes, vec!["https://mail.google.com/"]);
+        assert!(registry.device_code_config("gmail").is_none());
+        assert!(registry.device_code_config("outlook").is_some());
+        assert!(registry.provider_config("myspace").is_none());
+    }
+
+    #[test]
+    fn override_replaces_client_id_and_adds_providers() {
+        let mut registry = OAuthProviderRegistry::builtin();
+        registry
+            .apply_toml(
+                r#"
+                [general]
+                name = "ignored"
+
+                [oauth_providers.gmail]
+                client_id = "1234.apps.googleusercontent.com"
+
+                [oauth_providers.gitea]
+                auth_url = "https://git.example.com/login/oauth/authorize"
+                token_url = "https://git.example.com/login/oauth/access_token"
+                scopes = ["read:user"]
+                "#,
+            )
+            .unwrap();
+
+        let gmail = registry.provider_config("gmail").unwrap();
+        assert_eq!(gmail.client_id, "1234.apps.googleusercontent.com");
+        assert_eq!(gmail.token_url, "https://oauth2.googleapis.com/token");
+        assert_eq!(
+            registry.provider_config("gitea").unwrap().scopes,
+            vec!["read:user"]
+        );
+
+        let err = registry
+            .apply_toml("[oauth_providers.bare]\nclient_id = \"x\"\n")
+            .unwrap_err();
+        assert!(matches!(err, AuthEngineError::InvalidConfig { .. }));
+    }
+
+    #[test]
+    fn load_missing_file_yields_builtins() {
+        let registry = OAuthProviderRegistry::load(Path::new("/nonexistent/oauth.toml")).unwrap();
+        assert_eq!(registry.names(), vec!["gmail", "outlook", "yahoo"]);
+    }
+}
@@ -4,16 +4,21 @@
 //! This module provides a single function to initialize them all, eliminating
 //! code duplication.
 
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
 use openintent_adapters::Adapter;
+use openintent_agent::config::ConfigManager;
 use openintent_agent::runtime::ToolAdapter;
+use openintent_kernel::IpcBus;
 use openintent_sandbox::{PluginLoader, SandboxConfig};
 use openintent_store::Database;
 
 use crate::bridge::AdapterBridge;
+use crate::config::GATEWAY_CONFIG_FILE;
+use crate::vault::open_existing_vault;
 
 /// The result of initializing all adapters.
 pub struct InitializedAdapters {
@@ -31,93 +36,328 @@ pub struct InitializedAdapters {
 
     /// Number of WASM plugins loaded.
     pub wasm_plugin_count: usize,
+
+    /// Bus the adapters publish events on, such as file changes seen by
+    /// `fs_watch_path`.
+    pub bus: IpcBus,
 }
 
-/// Initialize and connect all adapters.
+/// Events buffered per bus subscriber before it starts missing them.
+const EVENT_BUS_CAPACITY: usize = 256;
+
+/// Built-in adapters, in initialization order.
 ///
-/// This is the single source of truth for adapter setup. All subcommands
-/// that need adapters should call this function.
-pub async fn init_adapters(
-    cwd: PathBuf,
-    db: Database,
-    include_telegram_discord: bool,
-) -> Result<InitializedAdapters> {
-    let mut fs_adapter = openintent_adapters::FilesystemAdapter::new("filesystem", cwd.clone());
-    fs_adapter.connect().await?;
+/// `telegram` and `discord` are only initialized by subcommands that ask for
+/// messaging adapters.  `system` is only built with the `desktop` feature.
+pub const ADAPTER_NAMES: &[&str] = &[
+    "filesystem",
+    "shell",
+    "web_search",
+    "web_fetch",
+    "http_request",
+    "network",
+    "cron",
+    "memory",
+    "scratchpad",
+    "github",
+    "email",
+    "browser",
+    "feishu",
+    "slack",
+    "notion",
+    "calendar",
+    #[cfg(feature = "desktop")]
+    "system",
+    "telegram",
+    "discord",
+];
+
+/// Short names accepted in place of full adapter names.
+const ADAPTER_ALIASES: &[(&str, &str)] = &[("fs", "filesystem"), ("http", "http_request")];
+
+/// Which built-in adapters to initialize.
+///
+/// Read from `--adapters` or the `enabled` list in the `[adapters]` section
+/// of `config/default.toml`; when neither is set every adapter is used.
+/// Adapters switched off by an `adapter.<id> = false` feature flag in
+/// `config/gateway.toml` are skipped either way.
+#[derive(Debug, Clone, Default)]
+pub struct AdapterSelection {
+    /// Explicitly requested adapters; `None` selects all of them.
+    requested: Option<Vec<&'static str>>,
+    /// Adapters disabled by feature flags, including skills and plugins.
+    disabled: HashSet<String>,
+    /// Whether the cron adapter fires runs missed while offline.
+    cron_catch_up: bool,
+}
+
+impl AdapterSelection {
+    /// Select every adapter.
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Select the adapters named in a comma-separated list.
+    pub fn parse(list: &str) -> Result<Self> {
+        Self::from_names(list.split(',').map(str::trim).filter(|n| !n.is_empty()))
+    }
 
-    let mut shell_adapter = openintent_adapters::ShellAdapter::new("shell", cwd.clone());
-    shell_adapter.connect().await?;
+    /// Select the adapters in `names`, rejecting unknown ones.
+    fn from_names<'a>(names: impl IntoIterator<Item = &'a str>) -> Result<Self> {
+        let mut requested = Vec::new();
+        for name in names {
+            let name = ADAPTER_ALIASES
+                .iter()
+                .find(|(alias, _)| *alias == name)
+                .map_or(name, |(_, full)| full);
+            let Some(&known) = ADAPTER_NAMES.iter().find(|n| **n == name) else {
+                bail!(
+                    "unknown adapter '{name}' (available: {})",
+                    ADAPTER_NAMES.join(", ")
+                );
+            };
+            if !requested.contains(&known) {
+                requested.push(known);
+            }
+        }
+        Ok(Self {
+            requested: Some(requested),
+            ..Self::default()
+        })
+    }
 
-    let mut web_search_adapter = openintent_adapters::WebSearchAdapter::new("web_search");
-    web_search_adapter.connect().await?;
+    /// Use `flag` if given, otherwise the `[adapters]` config section, then
+    /// drop adapters disabled by feature flags.  Adapter settings such as
+    /// `[cron] catch_up_missed` are read from the same file.
+    pub fn resolve(flag: Option<&str>) -> Result<Self> {
+        let config = Path::new("config/default.toml");
+        let mut selection = match flag {
+            Some(list) => Self::parse(list)?,
+            None => Self::from_config(config)?,
+        };
+        selection.cron_catch_up = cron_catch_up_from_config(config)?;
+        selection.with_feature_flags(Path::new(GATEWAY_CONFIG_FILE))
+    }
 
-    let mut web_fetch_adapter = openintent_adapters::WebFetchAdapter::new("web_fetch");
-    web_fetch_adapter.connect().await?;
+    /// Make the cron adapter fire runs missed while offline once on
+    /// startup, as `--cron-catch-up` does.  `false` keeps the configured
+    /// setting.
+    pub fn with_cron_catch_up(mut self, catch_up: bool) -> Self {
+        self.cron_catch_up |= catch_up;
+        self
+    }
 
-    let mut http_adapter = openintent_adapters::HttpRequestAdapter::new("http_request");
-    http_adapter.connect().await?;
+    /// Disable adapters whose `adapter.<id>` flag is false in the gateway
+    /// config at `path`.  A missing file disables nothing.
+    fn with_feature_flags(mut self, path: &Path) -> Result<Self> {
+        let gateway = ConfigManager::from_file(path.to_path_buf())
+            .with_context(|| format!("failed to load {}", path.display()))?;
+        self.disabled = gateway.get_config().disabled_adapters();
+        if !self.disabled.is_empty() {
+            tracing::info!(adapters = ?self.disabled, "adapters disabled by feature flags");
+        }
+        Ok(self)
+    }
 
-    let mut cron_adapter = openintent_adapters::CronAdapter::new("cron");
-    cron_adapter.connect().await?;
+    /// Read `[adapters] enabled = [...]` from the config file at `path`.
+    ///
+    /// A missing file, section, or key selects every adapter.
+    fn from_config(path: &Path) -> Result<Self> {
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return Ok(Self::all());
+        };
+        let table: toml::Table = content
+            .parse()
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        let Some(enabled) = table.get("adapters").and_then(|a| a.get("enabled")) else {
+            return Ok(Self::all());
+        };
+        let Some(list) = enabled.as_array() else {
+            bail!("[adapters] enabled must be a list of adapter names");
+        };
+        let names = list
+            .iter()
+            .map(|v| {
+                v.as_str()
+                    .context("[adapters] enabled must be a list of adapter names")
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Self::from_names(names)
+    }
 
-    let memory = Arc::new(openintent_store::SemanticMemory::new(db));
-    let mut memory_adapter =
-        openintent_adapters::MemoryToolsAdapter::new("memory", Arc::clone(&memory));
-    memory_adapter.connect().await?;
+    /// Whether the adapter `name` should be initialized.
+    pub fn includes(&self, name: &str) -> bool {
+        !self.is_disabled(name)
+            && self
+                .requested
+                .as_ref()
+                .is_none_or(|names| names.contains(&name))
+    }
 
-    let mut github_adapter = openintent_adapters::GitHubAdapter::new("github");
-    github_adapter.connect().await?;
+    /// Whether the adapter `id` is switched off by a feature flag.
+    pub fn is_disabled(&self, id: &str) -> bool {
+        self.disabled.contains(id)
+    }
 
-    let mut email_adapter = openintent_adapters::EmailAdapter::new("email");
-    email_adapter.connect().await?;
+    /// Whether adapters were picked explicitly rather than defaulting to all.
+    pub fn is_explicit(&self) -> bool {
+        self.requested.is_some()
+    }
+}
 
-    let mut browser_adapter = openintent_adapters::BrowserAdapter::new("browser");
-    if let Err(e) = browser_adapter.connect().await {
-        tracing::warn!(error = %e, "browser adapter failed to connect (Chrome may not be running)");
+/// Read `[cron] catch_up_missed` from the config file at `path`.  A missing
+/// file, section, or key means no.
+fn cron_catch_up_from_config(path: &Path) -> Result<bool> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Ok(false);
+    };
+    let table: toml::Table = content
+        .parse()
+        .with_context(|| format!("failed to parse {}", path.display()))?;
+    match table.get("cron").and_then(|c| c.get("catch_up_missed")) {
+        None => Ok(false),
+        Some(value) => value
+            .as_bool()
+            .context("[cron] catch_up_missed must be true or false"),
     }
+}
 
-    let mut feishu_adapter = openintent_adapters::FeishuAdapter::new("feishu");
-    feishu_adapter.connect().await?;
+/// Create the built-in adapter `name`, wrapped so its mutating tools accept
+/// an idempotency key.  Adapters that emit events publish them on `bus`.
+fn build_adapter(
+    name: &str,
+    cwd: &Path,
+    db: &Database,
+    bus: &IpcBus,
+    selection: &AdapterSelection,
+) -> Option<Box<dyn Adapter>> {
+    use openintent_adapters as a;
+
+    let adapter: Box<dyn Adapter> = match name {
+        "filesystem" => {
+            Box::new(a::FilesystemAdapter::new(name, cwd.to_path_buf()).with_bus(bus.clone()))
+        }
+        "shell" => Box::new(a::ShellAdapter::new(name, cwd.to_path_buf())),
+        "web_search" => Box::new(a::WebSearchAdapter::new(name)),
+        "web_fetch" => Box::new(a::WebFetchAdapter::new(name)),
+        "http_request" => Box::new(a::HttpRequestAdapter::new(name)),
+        "network" => Box::new(a::NetworkAdapter::new(name, a::NetworkConfig::from_env())),
+        "cron" => Box::new(
+            a::CronAdapter::new(name)
+                .with_store(openintent_store::CronJobStore::new(db.clone()))
+                .with_catch_up_missed(selection.cron_catch_up),
+        ),
+        "memory" => Box::new(a::MemoryToolsAdapter::new(
+            name,
+            Arc::new(openintent_store::SemanticMemory::new(db.clone())),
+        )),
+        "scratchpad" => Box::new(a::ScratchpadAdapter::new(
+            name,
+            openintent_store::BotStateStore::new(db.clone()),
+            "default",
+        )),
+        "github" => Box::new(a::GitHubAdapter::new(name)),
+        "email" => Box::new(a::EmailAdapter::new(name)),
+        "browser" => Box::new(a::BrowserAdapter::new(name)),
+        "feishu" => Box::new(a::FeishuAdapter::new(name)),
+        "slack" => Box::new(a::SlackAdapter::new(name)),
+        "notion" => Box::new(a::NotionAdapter::new(name)),
+        "calendar" => Box::new(a::CalendarAdapter::new(name)),
+        #[cfg(feature = "desktop")]
+        "system" => Box::new(a::SystemAdapter::new(name)),
+        "telegram" => Box::new(a::TelegramAdapter::new(name)),
+        "discord" => Box::new(a::DiscordAdapter::new(name)),
+        _ => return None,
+    };
+    Some(Box::new(a::IdempotentAdapter::new(
+        adapter,
+        openintent_store::BotStateStore::new(db.clone()),
+    )))
+}
 
-    let mut calendar_adapter = openintent_adapters::CalendarAdapter::new("calendar");
-    calendar_adapter.connect().await?;
+/// Create, without connecting, every built-in adapter chosen by `selection`,
+/// including the messaging ones.
+pub fn build_adapters(
+    cwd: &Path,
+    db: &Database,
+    selection: &AdapterSelection,
+) -> Vec<Box<dyn Adapter>> {
+    let bus = IpcBus::new(EVENT_BUS_CAPACITY);
+    ADAPTER_NAMES
+        .iter()
+        .filter(|name| selection.includes(name))
+        .filter_map(|name| build_adapter(name, cwd, db, &bus, selection))
+        .collect()
+}
 
-    // Build raw adapter list (for web server).
-    let mut raw_adapters: Vec<Arc<dyn openintent_adapters::Adapter>> = vec![
-        Arc::new(fs_adapter),
-        Arc::new(shell_adapter),
-        Arc::new(web_search_adapter),
-        Arc::new(web_fetch_adapter),
-        Arc::new(http_adapter),
-        Arc::new(cron_adapter),
-        Arc::new(memory_adapter),
-        Arc::new(github_adapter),
-        Arc::new(email_adapter),
-        Arc::new(browser_adapter),
-        Arc::new(feishu_adapter),
-        Arc::new(calendar_adapter),
-    ];
-
-    // Optionally add Telegram and Discord adapters.
-    if include_telegram_discord {
-        let mut telegram_adapter = openintent_adapters::TelegramAdapter::new("telegram");
-        telegram_adapter.connect().await?;
-
-        let mut discord_adapter = openintent_adapters::DiscordAdapter::new("discord");
-        discord_adapter.connect().await?;
-
-        raw_adapters.push(Arc::new(telegram_adapter));
-        raw_adapters.push(Arc::new(discord_adapter));
+/// Create and connect the built-in adapters chosen by `selection`.
+///
+/// A requested adapter that fails to connect is an error.  When no
+/// selection was made, the browser adapter (Chrome may not be running) and
+/// the system adapter (there may be no desktop session) are allowed to fail
+/// and are left out.
+async fn connect_adapters(
+    cwd: &Path,
+    db: &Database,
+    bus: &IpcBus,
+    include_telegram_discord: bool,
+    selection: &AdapterSelection,
+) -> Result<Vec<Arc<dyn Adapter>>> {
+    let mut adapters: Vec<Arc<dyn Adapter>> = Vec::new();
+    for &name in ADAPTER_NAMES {
+        let messaging = matches!(name, "telegram" | "discord");
+        if !selection.includes(name) || (messaging && !include_telegram_discord) {
+            continue;
+        }
+        let Some(mut adapter) = build_adapter(name, cwd, db, bus, selection) else {
+            continue;
+        };
+        if let Err(e) = adapter.connect().await {
+            if name == "browser" && !selection.is_explicit() {
+                tracing::warn!(error = %e, "browser adapter failed to connect (Chrome may not be running)");
+                continue;
+            }
+            if name == "system" && !selection.is_explicit() {
+                tracing::warn!(error = %e, "system adapter failed to connect (no desktop session)");
+                continue;
+            }
+            return Err(e).with_context(|| format!("failed to connect the {name} adapter"));
+        }
+        adapters.push(Arc::from(adapter));
     }
+    Ok(adapters)
+}
+
+/// Initialize and connect all adapters.
+///
+/// This is the single source of truth for adapter setup. All subcommands
+/// that need adapters should call this function.  Only the built-in
+/// adapters chosen by `selection` are initialized; skills and WASM plugins
+/// are always loaded.
+pub async fn init_adapters(
+    cwd: PathBuf,
+    db: Database,
+    include_telegram_discord: bool,
+    selection: &AdapterSelection,
+) -> Result<InitializedAdapters> {
+    // Build raw adapter list (for web server).
+    let bus = IpcBus::new(EVENT_BUS_CAPACITY);
+    let raw_adapters =
+        connect_adapters(&cwd, &db, &bus, include_telegram_discord, selection).await?;
+    tracing::info!(
+        adapters = %raw_adapters.iter().map(|a| a.id()).collect::<Vec<_>>().join(", "),
+        "adapters initialized"
+    );
 
     // Load skills.
     let skills_dir = openintent_skills::default_skills_dir();
     let mut skill_manager = openintent_skills::SkillManager::new(skills_dir);
     let _ = skill_manager.load_all();
-    let skill_count = skill_manager.skills().len();
+    let enabled_skills = skill_manager.enabled_skills();
+    let skill_count = enabled_skills.len();
     let skill_prompt_ext = skill_manager.build_prompt_extension();
 
-    let mut skill_adapter = openintent_skills::SkillAdapter::new("skills", skill_manager.skills());
+    let mut skill_adapter = openintent_skills::SkillAdapter::new("skills", &enabled_skills);
     skill_adapter.connect().await?;
     let skill_tool_count = skill_adapter.tools().len();
 
@@ -129,11 +369,13 @@ pub async fn init_adapters(
         );
     }
 
-    // Wrap raw adapters in the bridge for the agent side.
+    // Wrap raw adapters in the bridge for the agent side.  The bridge checks
+    // the vault for credentials an adapter needs but was not configured with.
+    let vault = open_existing_vault(&cwd.join("data")).map(|v| Arc::new(Mutex::new(v)));
     let mut tool_adapters: Vec<Arc<dyn ToolAdapter>> = raw_adapters
         .iter()
         .map(|a| -> Arc<dyn ToolAdapter> {
-            Arc::new(AdapterBridge::new(RawAdapterRef(Arc::clone(a))))
+            Arc::new(AdapterBridge::new(RawAdapterRef(Arc::clone(a))).with_vault(vault.clone()))
         })
         .collect();
 
@@ -172,12 +414,16 @@ pub async fn init_adapters(
         }
     }
 
+    // Skills and plugins honour their feature flags too.
+    tool_adapters.retain(|a| !selection.is_disabled(a.adapter_id()));
+
     Ok(InitializedAdapters {
         tool_adapters,
         raw_adapters,
         skill_count,
         skill_prompt_ext,
         wasm_plugin_count,
+        bus,
     })
 }
 
@@ -225,6 +471,10 @@ impl openintent_adapters::Adapter for RawAdapterRef {
     fn required_auth(&self) -> Option<openintent_adapters::AuthRequirement> {
         self.0.required_auth()
     }
+
+    fn has_credentials(&self) -> bool {
+        self.0.has_credentials()
+    }
 }
 
 /// Wrapper that implements `Adapter` for an `Arc<PluginAdapter>`.
@@ -270,4 +520,98 @@ impl openintent_adapters::Adapter for WasmPluginRef {
     fn required_auth(&self) -> Option<openintent_adapters::AuthRequirement> {
         openintent_adapters::Adapter::required_auth(self.0.as_ref())
     }
+
+    fn has_credentials(&self) -> bool {
+        openintent_adapters::Adapter::has_credentials(self.0.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn test_db() -> Database {
+        let db = Database::open_in_memory().expect("in-memory database must open");
+        db.run_migrations().await.expect("migrations must run");
+        db
+    }
+
+    #[test]
+    fn selection_parses_aliases_and_rejects_unknown_names() {
+        let selection = AdapterSelection::parse("fs, shell,web_search,fs").unwrap();
+        assert_eq!(
+            selection.requested,
+            Some(vec!["filesystem", "shell", "web_search"])
+        );
+        assert!(selection.includes("shell"));
+        assert!(!selection.includes("browser"));
+
+        assert!(AdapterSelection::parse("fs,nope").is_err());
+        assert!(AdapterSelection::all().includes("browser"));
+    }
+
+    #[test]
+    fn selection_reads_config_section() {
+        let dir = tempfile::tempdir().expect("tempdir creation must succeed in tests");
+        let path = dir.path().join("default.toml");
+        assert!(!AdapterSelection::from_config(&path).unwrap().is_explicit());
+
+        std::fs::write(&path, "[adapters]\nenabled = [\"shell\", \"http\"]\n").unwrap();
+        let selection = AdapterSelection::from_config(&path).unwrap();
+        assert_eq!(selection.requested, Some(vec!["shell", "http_request"]));
+    }
+
+    #[test]
+    fn cron_catch_up_reads_config_section() {
+        let dir = tempfile::tempdir().expect("tempdir creation must succeed in tests");
+        let path = dir.path().join("default.toml");
+        assert!(!cron_catch_up_from_config(&path).unwrap());
+
+        std::fs::write(&path, "[cron]\ncatch_up_missed = true\n").unwrap();
+        assert!(cron_catch_up_from_config(&path).unwrap());
+
+        std::fs::write(&path, "[cron]\ncatch_up_missed = \"yes\"\n").unwrap();
+        assert!(cron_catch_up_from_config(&path).is_err());
+
+        let selection = AdapterSelection::all().with_cron_catch_up(true);
+        assert!(selection.with_cron_catch_up(false).cron_catch_up);
+    }
+
+    #[test]
+    fn feature_flags_disable_adapters() {
+        let dir = tempfile::tempdir().expect("tempdir creation must succeed in tests");
+        let path = dir.path().join("gateway.toml");
+        let selection = AdapterSelection::all().with_feature_flags(&path).unwrap();
+        assert!(selection.includes("browser"));
+
+        std::fs::write(
+            &path,
+            "[features]\n\"adapter.browser\" = false\n\"adapter.shell\" = true\n",
+        )
+        .unwrap();
+        let selection = AdapterSelection::parse("fs,shell,browser")
+            .unwrap()
+            .with_feature_flags(&path)
+            .unwrap();
+        assert!(selection.includes("shell"));
+        assert!(!selection.includes("browser"));
+        assert!(selection.is_disabled("browser"));
+    }
+
+    #[tokio::test]
+    async fn only_selected_adapters_are_connected() {
+        let dir = tempfile::tempdir().expect("tempdir creation must succeed in tests");
+        let selection = AdapterSelection::parse("fs,shell,web_search,telegram").unwrap();
+
+        let bus = IpcBus::new(EVENT_BUS_CAPACITY);
+        let adapters = connect_adapters(dir.path(), &test_db().await, &bus, false, &selection)
+            .await
+            .unwrap();
+        let ids: Vec<_> = adapters.iter().map(|a| a.id()).collect();
+        assert_eq!(ids, ["filesystem", "shell", "web_search"]);
+
+        // The filesystem adapter has the bus, so it can watch paths.
+        let tools: Vec<_> = adapters[0].tools().into_iter().map(|t| t.name).collect();
+        assert!(tools.iter().any(|t| t == "fs_watch_path"), "{tools:?}");
+    }
 }
@@ -14,9 +14,9 @@ use tracing::info;
 use openintent_agent::{
     AgentConfig, AgentContext, EvolutionEngine, LlmClient, Message, react_loop,
 };
-use openintent_store::{BotStateStore, DevTaskStore, SessionStore};
+use openintent_store::{BotStateStore, DevTaskStore};
 
-use crate::adapters::init_adapters;
+use crate::adapters::{AdapterSelection, init_adapters};
 use crate::bot_config::{load_bot_config, select_model_for_query};
 use crate::bot_helpers::{
     check_restart_signal, notify_recovered_tasks, send_pending_update_notification,
@@ -27,6 +27,7 @@ use crate::dev_worker::{DevWorker, ProgressCallback};
 use crate::failover::{self, FailoverManager};
 use crate::helpers::{env_non_empty, init_tracing, load_system_prompt, resolve_llm_config};
 use crate::messages::{self, Messages, keys};
+use crate::session_store::open_session_store;
 
 /// Run the Telegram bot gateway.
 pub async fn cmd_bot(poll_timeout: u64, allowed_users: Option<String>) -> Result<()> {
@@ -114,7 +115,7 @@ pub async fn cmd_bot(poll_timeout: u64, allowed_users: Option<String>) -> Result
     // Used to re-apply model switches after failover resets.
     let mut chat_model_alias: HashMap<i64, String> = HashMap::new();
 
-    let sessions = SessionStore::new(db.clone());
+    let sessions = open_session_store(db.clone())?;
 
     // Initialize the dev task store and bot state store.
     let dev_task_store = DevTaskStore::new(db.clone());
@@ -122,7 +123,8 @@ pub async fn cmd_bot(poll_timeout: u64, allowed_users: Option<String>) -> Result
 
     // Initialize adapters.
     let cwd = std::env::current_dir().context("failed to get current directory")?;
-    let initialized = init_adapters(cwd.clone(), db, true).await?;
+    let initialized =
+        init_adapters(cwd.clone(), db, true, &AdapterSelection::resolve(None)?).await?;
     let mut adapters = initialized.tool_adapters;
     let skill_prompt_ext = initialized.skill_prompt_ext;
 
@@ -735,7 +737,9 @@ pub async fn cmd_bot(poll_timeout: u64, allowed_users: Option<String>) -> Result
                     "web_research" => Some(keys::STATUS_RESEARCHING),
                     "web_search" => Some(keys::STATUS_SEARCHING),
                     "web_fetch" => Some(keys::STATUS_READING_PAGE),
-                    "fs_read_file" | "fs_list_directory" => Some(keys::STATUS_READING_FILES),
+                    "fs_read_file" | "fs_list_directory" | "fs_diff_files" => {
+                        Some(keys::STATUS_READING_FILES)
+                    }
                     "shell_execute" => Some(keys::STATUS_RUNNING_COMMAND),
                     "memory_search" | "memory_save" => Some(keys::STATUS_ACCESSING_MEMORY),
                     "github_create_issue" | "github_list_repos" => Some(keys::STATUS_GITHUB),
@@ -3,26 +3,55 @@
 //!
 //! The two traits have slightly different signatures (different field names,
 //! `Value` vs `String` return types), so this struct handles the conversion.
+//! Arguments are validated against the tool's schema before they reach the
+//! adapter, so the LLM gets an error it can correct instead of the adapter
+//! receiving garbage.  Adapters whose [`AuthRequirement`] is unmet — neither
+//! configured on the adapter nor stored in the vault — are refused with
+//! [`AgentError::AuthRequired`] before any call is made.
+//!
+//! [`AuthRequirement`]: openintent_adapters::AuthRequirement
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
 use serde_json::Value;
 
 use openintent_agent::runtime::ToolAdapter;
+use openintent_agent::{AgentError, PromptFn};
+use openintent_vault::Vault;
 
 /// Bridges an adapter-crate `Adapter` to the agent-crate `ToolAdapter`.
 pub struct AdapterBridge {
     adapter: Arc<dyn openintent_adapters::Adapter>,
+    /// Vault consulted for credentials the adapter itself lacks.
+    vault: Option<Arc<Mutex<Vault>>>,
 }
 
 impl AdapterBridge {
     pub fn new(adapter: impl openintent_adapters::Adapter + 'static) -> Self {
         Self {
             adapter: Arc::new(adapter),
+            vault: None,
         }
     }
 
+    /// Consult `vault` when checking the adapter's auth requirement.
+    pub fn with_vault(mut self, vault: Option<Arc<Mutex<Vault>>>) -> Self {
+        self.vault = vault;
+        self
+    }
+
+    /// Whether the vault holds a credential for `provider`.
+    fn vault_has(&self, provider: &str) -> bool {
+        self.vault.as_ref().is_some_and(|vault| {
+            vault
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .get_credential(provider)
+                .is_ok()
+        })
+    }
+
     /// Convert an adapter-side `ToolDefinition` to an agent-side `ToolDefinition`.
     fn convert_tool_def(
         td: &openintent_adapters::ToolDefinition,
@@ -31,6 +60,7 @@ impl AdapterBridge {
             name: td.name.clone(),
             description: td.description.clone(),
             input_schema: td.parameters.clone(),
+            destructive: td.destructive,
         }
     }
 }
@@ -49,17 +79,210 @@ impl ToolAdapter for AdapterBridge {
             .collect()
     }
 
+    fn check_auth(&self) -> openintent_agent::Result<()> {
+        let Some(auth) = self.adapter.required_auth() else {
+            return Ok(());
+        };
+        if self.adapter.has_credentials() || self.vault_has(&auth.provider) {
+            return Ok(());
+        }
+        let mut how = format!(
+            "store a credential with `openintent vault set {} <TYPE>`",
+            auth.provider
+        );
+        if !auth.scopes.is_empty() {
+            how.push_str(&format!(" (requires: {})", auth.scopes.join(", ")));
+        }
+        Err(AgentError::AuthRequired {
+            provider: auth.provider,
+            how,
+        })
+    }
+
     async fn execute(&self, tool_name: &str, arguments: Value) -> openintent_agent::Result<String> {
+        self.execute_with_prompt(tool_name, arguments, None).await
+    }
+
+    async fn execute_with_prompt(
+        &self,
+        tool_name: &str,
+        arguments: Value,
+        prompt: Option<PromptFn>,
+    ) -> openintent_agent::Result<String> {
+        if let Some(def) = self.adapter.tools().iter().find(|t| t.name == tool_name) {
+            Self::convert_tool_def(def).validate_arguments(&arguments)?;
+        }
+
         let result = self
             .adapter
-            .execute_tool(tool_name, arguments)
+            .execute_tool_with_prompt(tool_name, arguments, prompt)
             .await
-            .map_err(|e| openintent_agent::AgentError::ToolExecutionFailed {
-                tool_name: tool_name.to_owned(),
-                reason: e.to_string(),
-            })?;
+            .map_err(AgentError::from)?;
 
         let text = serde_json::to_string_pretty(&result).unwrap_or_else(|_| result.to_string());
         Ok(text)
     }
+
+    fn redact_arguments(&self, tool_name: &str, arguments: &Value) -> Value {
+        self.adapter.redact_params(tool_name, arguments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use openintent_adapters::{AdapterType, AuthRequirement, HealthStatus, ToolDefinition};
+    use serde_json::json;
+
+    use super::*;
+
+    /// Adapter with one `echo` tool that counts how often it is executed.
+    /// With `needs_token`, it declares an `echo` auth requirement it lacks.
+    #[derive(Clone, Default)]
+    struct EchoAdapter {
+        calls: Arc<AtomicUsize>,
+        needs_token: bool,
+    }
+
+    #[async_trait]
+    impl openintent_adapters::Adapter for EchoAdapter {
+        fn id(&self) -> &str {
+            "echo"
+        }
+
+        fn adapter_type(&self) -> AdapterType {
+            AdapterType::System
+        }
+
+        async fn connect(&mut self) -> openintent_adapters::Result<()> {
+            Ok(())
+        }
+
+        async fn disconnect(&mut self) -> openintent_adapters::Result<()> {
+            Ok(())
+        }
+
+        async fn health_check(&self) -> openintent_adapters::Result<HealthStatus> {
+            Ok(HealthStatus::Healthy)
+        }
+
+        fn tools(&self) -> Vec<ToolDefinition> {
+            vec![ToolDefinition {
+                name: "echo".into(),
+                description: "Echo the message".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": { "message": { "type": "string" } },
+                    "required": ["message"]
+                }),
+                destructive: false,
+            }]
+        }
+
+        async fn execute_tool(
+            &self,
+            _name: &str,
+            params: Value,
+        ) -> openintent_adapters::Result<Value> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(params)
+        }
+
+        /// Echoes the answer to a `Proceed?` question when given a prompt.
+        async fn execute_tool_with_prompt(
+            &self,
+            name: &str,
+            params: Value,
+            prompt: Option<PromptFn>,
+        ) -> openintent_adapters::Result<Value> {
+            let Some(ask) = prompt else {
+                return self.execute_tool(name, params).await;
+            };
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(json!({ "answer": ask("Proceed?") }))
+        }
+
+        fn required_auth(&self) -> Option<AuthRequirement> {
+            self.needs_token.then(|| AuthRequirement {
+                provider: "echo".into(),
+                scopes: vec!["messages.write".into()],
+            })
+        }
+
+        fn has_credentials(&self) -> bool {
+            !self.needs_token
+        }
+    }
+
+    #[tokio::test]
+    async fn missing_required_argument_never_reaches_adapter() {
+        let adapter = EchoAdapter::default();
+        let bridge = AdapterBridge::new(adapter.clone());
+
+        let err = bridge
+            .execute("echo", json!({}))
+            .await
+            .expect_err("missing argument must be rejected");
+
+        match err {
+            AgentError::InvalidToolArguments { tool_name, reason } => {
+                assert_eq!(tool_name, "echo");
+                assert!(reason.contains("`message`"), "unexpected reason: {reason}");
+            }
+            other => panic!("expected InvalidToolArguments, got {other:?}"),
+        }
+        assert_eq!(adapter.calls.load(Ordering::SeqCst), 0);
+
+        bridge
+            .execute("echo", json!({ "message": "hi" }))
+            .await
+            .expect("valid arguments must be forwarded");
+        assert_eq!(adapter.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn prompt_is_forwarded_to_the_adapter() {
+        let bridge = AdapterBridge::new(EchoAdapter::default());
+        let prompt: PromptFn = Arc::new(|_: &str| Some("y".to_owned()));
+
+        let output = bridge
+            .execute_with_prompt("echo", json!({ "message": "hi" }), Some(prompt))
+            .await
+            .expect("valid arguments must be forwarded");
+        assert!(output.contains(r#""answer": "y""#), "{output}");
+    }
+
+    #[test]
+    fn unmet_auth_requirement_is_reported_unless_vault_has_credential() {
+        let adapter = EchoAdapter {
+            needs_token: true,
+            ..EchoAdapter::default()
+        };
+
+        match AdapterBridge::new(adapter.clone()).check_auth() {
+            Err(AgentError::AuthRequired { provider, how }) => {
+                assert_eq!(provider, "echo");
+                assert!(how.contains("openintent vault set echo"), "{how}");
+                assert!(how.contains("messages.write"), "{how}");
+            }
+            other => panic!("expected AuthRequired, got {other:?}"),
+        }
+
+        let vault = Vault::open_in_memory(&[7u8; 32]).expect("vault must open");
+        vault
+            .store_credential(
+                "echo",
+                openintent_vault::CredentialType::ApiKey,
+                &json!({ "api_key": "secret" }),
+                None,
+                None,
+                None,
+            )
+            .expect("credential must be stored");
+        let bridge =
+            AdapterBridge::new(adapter.clone()).with_vault(Some(Arc::new(Mutex::new(vault))));
+        assert!(bridge.check_auth().is_ok());
+        assert_eq!(adapter.calls.load(Ordering::SeqCst), 0);
+    }
 }
@@ -3,6 +3,8 @@
 //! All `clap` structures live here so that `main.rs` stays focused on
 //! dispatching subcommands.
 
+use std::path::PathBuf;
+
 use clap::{Parser, Subcommand};
 
 /// OpenIntentOS -- an AI-powered operating system.
@@ -15,6 +17,18 @@ use clap::{Parser, Subcommand};
                   using available tools and adapters."
 )]
 pub struct Cli {
+    /// Write logs to this file instead of the terminal, rotating it by size.
+    #[arg(long, global = true)]
+    pub log_file: Option<PathBuf>,
+
+    /// With `--log-file`, rotate the file once it exceeds this many MiB.
+    #[arg(long, global = true, default_value_t = 10)]
+    pub log_max_size_mb: u64,
+
+    /// With `--log-file`, number of rotated files to keep.
+    #[arg(long, global = true, default_value_t = 5)]
+    pub log_max_files: usize,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -26,6 +40,35 @@ pub enum Commands {
         /// Resume or create a named session for conversation persistence.
         #[arg(long, short)]
         session: Option<String>,
+
+        /// Run a single request non-interactively, print the response, and
+        /// exit (non-zero on failure).
+        #[arg(long, short, conflicts_with = "session")]
+        prompt: Option<String>,
+
+        /// With `--prompt`, print the full agent response as JSON.
+        #[arg(long, requires = "prompt")]
+        json: bool,
+
+        /// With `--prompt`, run the prompt again whenever this file or
+        /// directory changes.
+        #[arg(long, requires = "prompt")]
+        watch: Option<PathBuf>,
+
+        /// Comma-separated adapters to initialize (e.g. `fs,shell,web_search`).
+        /// Defaults to the `[adapters]` config section, or all adapters.
+        #[arg(long)]
+        adapters: Option<String>,
+
+        /// Fire scheduled jobs missed while OpenIntentOS was not running
+        /// once on startup (`[cron] catch_up_missed`).
+        #[arg(long)]
+        cron_catch_up: bool,
+
+        /// Act as this user account, with its role's tool permissions.  The
+        /// password is read from `OPENINTENT_PASSWORD` or asked for.
+        #[arg(long)]
+        user: Option<String>,
     },
 
     /// Start the web server with embedded chat UI.
@@ -37,6 +80,30 @@ pub enum Commands {
         /// Port to listen on.
         #[arg(long, short, default_value_t = 23517)]
         port: u16,
+
+        /// Serve HTTPS.  Without --tls-cert/--tls-key, a self-signed
+        /// certificate is generated under data/tls on first use.
+        #[arg(long)]
+        tls: bool,
+
+        /// PEM certificate chain to serve HTTPS with.
+        #[arg(long, requires_all = ["tls", "tls_key"])]
+        tls_cert: Option<PathBuf>,
+
+        /// PEM private key matching --tls-cert.
+        #[arg(long, requires_all = ["tls", "tls_cert"])]
+        tls_key: Option<PathBuf>,
+
+        /// Origin allowed to call the API from a browser page served
+        /// elsewhere (repeatable), e.g. https://app.example.com.  `*`
+        /// allows any origin, without cookies or credentials.
+        #[arg(long = "allow-origin")]
+        allowed_origins: Vec<String>,
+
+        /// Fire scheduled jobs missed while OpenIntentOS was not running
+        /// once on startup (`[cron] catch_up_missed`).
+        #[arg(long)]
+        cron_catch_up: bool,
     },
 
     /// Run the interactive setup wizard.
@@ -45,6 +112,15 @@ pub enum Commands {
     /// Show current system status.
     Status,
 
+    /// Diagnose common setup problems and suggest fixes.
+    Doctor,
+
+    /// View and change gateway settings (API keys, endpoints, feature flags).
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
     /// Manage conversation sessions.
     Sessions {
         #[command(subcommand)]
@@ -73,6 +149,30 @@ pub enum Commands {
         action: SkillAction,
     },
 
+    /// Manage sandboxed Wasm plugins.
+    Plugins {
+        #[command(subcommand)]
+        action: PluginAction,
+    },
+
+    /// Manage credentials stored in the encrypted vault.
+    Vault {
+        #[command(subcommand)]
+        action: VaultAction,
+    },
+
+    /// Run and inspect stored workflows.
+    Workflows {
+        #[command(subcommand)]
+        action: WorkflowAction,
+    },
+
+    /// Inspect, search, and export long-term memories.
+    Memory {
+        #[command(subcommand)]
+        action: MemoryAction,
+    },
+
     /// Start the Telegram bot gateway (receive messages from Telegram, run the
     /// agent, send responses back).
     Bot {
@@ -98,7 +198,11 @@ pub enum Commands {
 #[derive(Subcommand)]
 pub enum SessionAction {
     /// List all saved sessions.
-    List,
+    List {
+        /// Print the sessions as a JSON array instead of a table.
+        #[arg(long)]
+        json: bool,
+    },
     /// Show messages from a session.
     Show {
         /// The session name to display.
@@ -109,13 +213,61 @@ pub enum SessionAction {
         /// The session name to delete.
         name: String,
     },
+    /// Export a session and its messages to a JSON file.
+    Export {
+        /// The session name to export.
+        name: String,
+        /// Destination file.
+        file: PathBuf,
+    },
+    /// Import a session from a file written by `sessions export`.
+    Import {
+        /// The exported session file.
+        file: PathBuf,
+    },
+    /// List a session's recorded tool calls in order, or run them again.
+    Replay {
+        /// The session name to replay.
+        name: String,
+        /// Re-execute the calls against the live adapters.  This has real
+        /// side effects: files may be changed and messages sent again.
+        #[arg(long)]
+        execute: bool,
+    },
+}
+
+/// Actions for viewing and changing gateway settings.
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Print one setting.
+    Get {
+        /// Section: api_keys, endpoints, or features.
+        section: String,
+        /// Setting name within the section.
+        key: String,
+    },
+    /// Change one setting and save it.
+    Set {
+        /// Section: api_keys, endpoints, or features.
+        section: String,
+        /// Setting name within the section.
+        key: String,
+        /// New value (`true` or `false` for features).
+        value: String,
+    },
+    /// List all settings, with API keys redacted.
+    List,
 }
 
 /// Actions for managing user accounts.
 #[derive(Subcommand)]
 pub enum UserAction {
     /// List all users.
-    List,
+    List {
+        /// Print the users as a JSON array instead of a table.
+        #[arg(long)]
+        json: bool,
+    },
     /// Create a new user.
     Create {
         /// The username for the new account.
@@ -165,4 +317,126 @@ pub enum SkillAction {
         /// Skill name.
         name: String,
     },
+    /// Re-install skills from the registry or URL they came from.
+    Update {
+        /// Skill to update; all skills when omitted.
+        name: Option<String>,
+    },
+    /// Enable a disabled skill.
+    Enable {
+        /// Skill name.
+        name: String,
+    },
+    /// Disable a skill without removing it.
+    Disable {
+        /// Skill name.
+        name: String,
+    },
+}
+
+/// Actions for managing Wasm plugins.
+#[derive(Subcommand)]
+pub enum PluginAction {
+    /// List installed plugins.
+    List,
+    /// Validate a `.wasm` module and install it.
+    Install {
+        /// Path to the `.wasm` file.
+        path: std::path::PathBuf,
+    },
+    /// Remove an installed plugin.
+    Remove {
+        /// The plugin ID to remove.
+        id: String,
+    },
+    /// Show a plugin's tools and the capabilities they require.
+    Info {
+        /// The plugin ID.
+        id: String,
+    },
+}
+
+/// Actions for managing vault credentials.
+#[derive(Subcommand)]
+pub enum VaultAction {
+    /// List stored credentials (metadata only).
+    List,
+    /// Store a credential, reading the secret from stdin.
+    Set {
+        /// Provider name (e.g. "github", "anthropic").
+        provider: String,
+        /// Credential type: api_key, oauth, cookie, or keychain.
+        #[arg(value_name = "TYPE")]
+        credential_type: String,
+        /// Optional human-readable label.
+        #[arg(long, short)]
+        label: Option<String>,
+    },
+    /// Show a stored credential; the secret is redacted unless `--reveal`.
+    Get {
+        /// Provider name.
+        provider: String,
+        /// Print the decrypted secret.
+        #[arg(long)]
+        reveal: bool,
+    },
+    /// Delete a stored credential.
+    Delete {
+        /// Provider name.
+        provider: String,
+    },
+}
+
+/// Actions for running and inspecting workflows.
+#[derive(Subcommand)]
+pub enum WorkflowAction {
+    /// List stored workflows.
+    List,
+    /// Run a stored workflow, printing each step result as it finishes.
+    Run {
+        /// Workflow name or ID.
+        name: String,
+    },
+    /// Show a workflow's trigger and steps.
+    Show {
+        /// Workflow name or ID.
+        name: String,
+    },
+    /// Report the progress of a workflow run.
+    Status {
+        /// The run ID printed by `workflows run`.
+        run_id: String,
+    },
+}
+
+/// Actions for inspecting long-term (semantic) memories.
+#[derive(Subcommand)]
+pub enum MemoryAction {
+    /// List memories, most important first.
+    List {
+        /// Maximum number of memories to show.
+        #[arg(long, short, default_value_t = 50)]
+        limit: u32,
+    },
+    /// Search memories by relevance to a query.
+    Search {
+        /// Search query.
+        query: String,
+        /// Maximum number of results.
+        #[arg(long, short, default_value_t = 10)]
+        limit: u32,
+    },
+    /// Export all memories to a JSONL file (one memory per line).
+    Export {
+        /// Output file path.
+        file: PathBuf,
+    },
+    /// Permanently delete a memory.
+    Forget {
+        /// Memory ID (as shown by `memory list`).
+        id: i64,
+        /// Skip the confirmation prompt.
+        #[arg(long, short)]
+        yes: bool,
+    },
 }
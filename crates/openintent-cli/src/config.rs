@@ -0,0 +1,139 @@
+//! `openintent config` — view and change gateway settings.
+//!
+//! Settings (API keys, service endpoints, and feature flags) live in
+//! `config/gateway.toml` and are read and written through
+//! [`ConfigManager`], so the CLI and a running gateway agree on the format.
+//! API keys are redacted when listing.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use openintent_agent::config::{ConfigManager, GatewayConfig};
+
+use crate::cli::ConfigAction;
+use crate::helpers::init_tracing;
+
+/// Gateway settings file, relative to the working directory.
+pub const GATEWAY_CONFIG_FILE: &str = "config/gateway.toml";
+
+/// Placeholder printed instead of API keys.
+const REDACTED: &str = "********";
+
+pub async fn cmd_config(action: ConfigAction) -> Result<()> {
+    init_tracing("warn");
+
+    let stdout = std::io::stdout();
+    run(Path::new(GATEWAY_CONFIG_FILE), action, &mut stdout.lock())
+}
+
+/// Execute a config action against the settings file at `path`.
+fn run(path: &Path, action: ConfigAction, out: &mut impl Write) -> Result<()> {
+    let manager = ConfigManager::from_file(PathBuf::from(path))
+        .with_context(|| format!("failed to load {}", path.display()))?;
+
+    match action {
+        ConfigAction::Get { section, key } => match manager.get_value(&section, &key)? {
+            Some(value) => writeln!(out, "{value}")?,
+            None => anyhow::bail!("{section}.{key} is not set"),
+        },
+
+        ConfigAction::Set {
+            section,
+            key,
+            value,
+        } => {
+            manager.update_value(&section, &key, value)?;
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)
+                    .with_context(|| format!("failed to create {}", dir.display()))?;
+            }
+            manager.save_to_file()?;
+            let shown = if section == "api_keys" {
+                REDACTED.to_owned()
+            } else {
+                manager.get_value(&section, &key)?.unwrap_or_default()
+            };
+            writeln!(out, "  Set {section}.{key} = {shown}")?;
+        }
+
+        ConfigAction::List => {
+            for line in render(&manager.get_config()) {
+                writeln!(out, "{line}")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Render every setting as `section.key = value`, sorted, with API keys
+/// redacted.
+fn render(config: &GatewayConfig) -> Vec<String> {
+    let mut lines: Vec<String> = config
+        .api_keys
+        .keys()
+        .map(|key| format!("api_keys.{key} = {REDACTED}"))
+        .chain(
+            config
+                .endpoints
+                .iter()
+                .map(|(key, url)| format!("endpoints.{key} = {url}")),
+        )
+        .chain(
+            config
+                .features
+                .iter()
+                .map(|(key, on)| format!("features.{key} = {on}")),
+        )
+        .collect();
+    lines.sort();
+    if lines.is_empty() {
+        lines.push("  No settings configured.".to_owned());
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_to_string(path: &Path, action: ConfigAction) -> Result<String> {
+        let mut out = Vec::new();
+        run(path, action, &mut out)?;
+        Ok(String::from_utf8(out).expect("output must be UTF-8"))
+    }
+
+    fn set(section: &str, key: &str, value: &str) -> ConfigAction {
+        ConfigAction::Set {
+            section: section.into(),
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+
+    #[test]
+    fn feature_flag_round_trips_and_keys_are_redacted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config/gateway.toml");
+
+        run_to_string(&path, set("features", "streaming", "true")).unwrap();
+        run_to_string(&path, set("api_keys", "anthropic", "sk-ant-secret")).unwrap();
+        run_to_string(&path, set("endpoints", "api_base", "http://localhost:8080")).unwrap();
+
+        let get = ConfigAction::Get {
+            section: "features".into(),
+            key: "streaming".into(),
+        };
+        assert_eq!(run_to_string(&path, get).unwrap(), "true\n");
+
+        let list = run_to_string(&path, ConfigAction::List).unwrap();
+        assert_eq!(
+            list,
+            "api_keys.anthropic = ********\n\
+             endpoints.api_base = http://localhost:8080\n\
+             features.streaming = true\n"
+        );
+        assert!(run_to_string(&path, set("features", "streaming", "maybe")).is_err());
+    }
+}
@@ -0,0 +1,428 @@
+//! `openintent doctor` — diagnose common setup problems.
+//!
+//! Runs a battery of checks (LLM credentials, config files, database, vault,
+//! and every adapter's health check) and prints each as pass, warn, or fail
+//! with a hint on how to fix it.  Warnings cover optional pieces that are not
+//! set up; any failure makes the command exit non-zero.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Result, bail};
+use futures::future::BoxFuture;
+
+use openintent_adapters::HealthStatus;
+use openintent_vault::{Vault, platform_keychain};
+
+use crate::adapters::{AdapterSelection, build_adapters};
+use crate::health::{self, Health};
+use crate::helpers::{
+    OLLAMA_BASE_URL, env_non_empty, init_tracing, read_claude_code_keychain_token,
+};
+
+/// Directory holding the database and vault.
+const DATA_DIR: &str = "data";
+
+/// Main configuration file.
+const CONFIG_FILE: &str = "config/default.toml";
+
+/// Upper bound on probing the local Ollama server.
+const OLLAMA_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Environment variables holding LLM provider keys, with provider names.
+const PROVIDER_KEYS: &[(&str, &str)] = &[
+    ("ANTHROPIC_API_KEY", "Anthropic"),
+    ("OPENAI_API_KEY", "OpenAI"),
+    ("DEEPSEEK_API_KEY", "DeepSeek"),
+    ("NVIDIA_API_KEY", "NVIDIA"),
+    ("GOOGLE_API_KEY", "Google"),
+    ("OPENROUTER_API_KEY", "OpenRouter"),
+    ("GROQ_API_KEY", "Groq"),
+    ("XAI_API_KEY", "xAI"),
+    ("MISTRAL_API_KEY", "Mistral"),
+];
+
+/// Outcome of a single check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    /// Something optional is missing or degraded.
+    Warn,
+    /// OpenIntentOS will not work until this is fixed.
+    Fail,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pass => write!(f, "PASS"),
+            Self::Warn => write!(f, "WARN"),
+            Self::Fail => write!(f, "FAIL"),
+        }
+    }
+}
+
+/// One line of the doctor report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// How to fix a warning or failure.
+    pub hint: Option<String>,
+}
+
+impl CheckResult {
+    pub fn pass(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    pub fn warn(
+        name: impl Into<String>,
+        detail: impl Into<String>,
+        hint: impl Into<String>,
+    ) -> Self {
+        Self {
+            status: CheckStatus::Warn,
+            hint: Some(hint.into()),
+            ..Self::pass(name, detail)
+        }
+    }
+
+    pub fn fail(
+        name: impl Into<String>,
+        detail: impl Into<String>,
+        hint: impl Into<String>,
+    ) -> Self {
+        Self {
+            status: CheckStatus::Fail,
+            hint: Some(hint.into()),
+            ..Self::pass(name, detail)
+        }
+    }
+}
+
+/// A check to run; it may report several results (one per adapter).
+pub type Check = BoxFuture<'static, Vec<CheckResult>>;
+
+pub async fn cmd_doctor() -> Result<()> {
+    init_tracing("error");
+
+    let data_dir = PathBuf::from(DATA_DIR);
+    let db_path = data_dir.join("openintent.db");
+    let checks: Vec<Check> = vec![
+        Box::pin(async { vec![check_llm_provider().await] }),
+        Box::pin(async { vec![check_config(Path::new(CONFIG_FILE))] }),
+        Box::pin({
+            let db_path = db_path.clone();
+            async move { vec![check_database(&db_path).await] }
+        }),
+        Box::pin({
+            let data_dir = data_dir.clone();
+            async move { vec![check_vault(&data_dir)] }
+        }),
+        Box::pin(check_adapters(db_path)),
+    ];
+
+    println!();
+    println!("  OpenIntentOS Doctor");
+    println!("  ===================");
+    println!();
+    let results = run_checks(checks).await;
+    for line in render_report(&results) {
+        println!("{line}");
+    }
+    println!();
+
+    let failed = results
+        .iter()
+        .filter(|r| r.status == CheckStatus::Fail)
+        .count();
+    if failed > 0 {
+        bail!("{failed} check(s) failed");
+    }
+    Ok(())
+}
+
+/// Run `checks` in order, collecting their results.
+pub async fn run_checks(checks: Vec<Check>) -> Vec<CheckResult> {
+    let mut results = Vec::new();
+    for check in checks {
+        results.extend(check.await);
+    }
+    results
+}
+
+/// Render one line per result (plus a hint line for warnings and
+/// failures), followed by a summary line.
+pub fn render_report(results: &[CheckResult]) -> Vec<String> {
+    let mut lines = Vec::with_capacity(results.len() + 2);
+    for result in results {
+        lines.push(format!(
+            "  [{}] {:<20} {}",
+            result.status, result.name, result.detail
+        ));
+        if let Some(hint) = &result.hint {
+            lines.push(format!("         hint: {hint}"));
+        }
+    }
+    let count = |status| results.iter().filter(|r| r.status == status).count();
+    lines.push(String::new());
+    lines.push(format!(
+        "  {} passed, {} warnings, {} failed",
+        count(CheckStatus::Pass),
+        count(CheckStatus::Warn),
+        count(CheckStatus::Fail)
+    ));
+    lines
+}
+
+/// An LLM must be reachable: either a provider key is set, or the local
+/// Ollama server answers.
+async fn check_llm_provider() -> CheckResult {
+    const NAME: &str = "LLM provider";
+    let mut providers: Vec<&str> = PROVIDER_KEYS
+        .iter()
+        .filter(|(var, _)| env_non_empty(var).is_some())
+        .map(|(_, provider)| *provider)
+        .collect();
+    if read_claude_code_keychain_token().is_some() {
+        providers.push("Claude Code OAuth");
+    }
+    if !providers.is_empty() {
+        return CheckResult::pass(NAME, providers.join(", "));
+    }
+
+    let base = env_non_empty("OPENINTENT_API_BASE_URL").unwrap_or_else(|| OLLAMA_BASE_URL.into());
+    let url = format!("{}/models", base.trim_end_matches('/'));
+    let reachable = match reqwest::Client::builder().timeout(OLLAMA_TIMEOUT).build() {
+        Ok(client) => client
+            .get(&url)
+            .send()
+            .await
+            .is_ok_and(|resp| resp.status().is_success()),
+        Err(_) => false,
+    };
+    if reachable {
+        CheckResult::warn(
+            NAME,
+            format!("no API key; using local Ollama at {base}"),
+            "set ANTHROPIC_API_KEY or OPENAI_API_KEY for hosted models",
+        )
+    } else {
+        CheckResult::fail(
+            NAME,
+            format!("no API key set and Ollama is unreachable at {base}"),
+            "set ANTHROPIC_API_KEY or OPENAI_API_KEY, or start Ollama with `ollama serve`",
+        )
+    }
+}
+
+/// The config file should exist, parse, and select known adapters.
+fn check_config(path: &Path) -> CheckResult {
+    const NAME: &str = "config";
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(_) => {
+            return CheckResult::warn(
+                NAME,
+                format!("{} not found; using defaults", path.display()),
+                "run `openintent setup` to create it",
+            );
+        }
+    };
+    if let Err(e) = toml::from_str::<toml::Table>(&text) {
+        return CheckResult::fail(
+            NAME,
+            format!("{} is not valid TOML: {}", path.display(), e.message()),
+            format!("fix the syntax error in {}", path.display()),
+        );
+    }
+    match AdapterSelection::resolve(None) {
+        Ok(_) => CheckResult::pass(NAME, path.display().to_string()),
+        Err(e) => CheckResult::fail(
+            NAME,
+            format!("{e:#}"),
+            format!("fix the [adapters] section of {}", path.display()),
+        ),
+    }
+}
+
+/// The database should exist and apply every migration.
+async fn check_database(path: &Path) -> CheckResult {
+    const NAME: &str = "database";
+    if !path.exists() {
+        return CheckResult::warn(
+            NAME,
+            format!("{} not initialized", path.display()),
+            "run `openintent setup`",
+        );
+    }
+    match openintent_store::Database::open_and_migrate(path.to_path_buf()).await {
+        Ok(_) => CheckResult::pass(NAME, format!("{} (migrated)", path.display())),
+        Err(e) => CheckResult::fail(
+            NAME,
+            format!("cannot open or migrate {}: {e}", path.display()),
+            format!(
+                "back up and remove {}, then run `openintent setup`",
+                path.display()
+            ),
+        ),
+    }
+}
+
+/// An existing vault should open with the master key from the keychain.
+fn check_vault(data_dir: &Path) -> CheckResult {
+    const NAME: &str = "vault";
+    let path = data_dir.join("vault.db");
+    if !path.exists() {
+        return CheckResult::pass(NAME, "not created yet (`openintent vault set` creates it)");
+    }
+    let master_key = match platform_keychain(data_dir).get_master_key() {
+        Ok(key) => key,
+        Err(e) => {
+            return CheckResult::fail(
+                NAME,
+                format!("master key unavailable: {e}"),
+                format!(
+                    "restore the vault master key to the keychain, or remove {} and re-add credentials",
+                    path.display()
+                ),
+            );
+        }
+    };
+    match Vault::open(&path, &master_key) {
+        Ok(_) => CheckResult::pass(NAME, path.display().to_string()),
+        Err(e) => CheckResult::fail(
+            NAME,
+            format!("cannot open {}: {e}", path.display()),
+            format!(
+                "remove {} and re-add credentials with `openintent vault set`",
+                path.display()
+            ),
+        ),
+    }
+}
+
+/// Connect and health-check every selected adapter.  Adapters are optional,
+/// so problems are warnings.
+async fn check_adapters(db_path: PathBuf) -> Vec<CheckResult> {
+    let db = match openintent_store::Database::open_and_migrate(db_path).await {
+        Ok(db) => db,
+        Err(_) => match in_memory_db().await {
+            Ok(db) => db,
+            Err(e) => {
+                return vec![CheckResult::fail(
+                    "adapters",
+                    format!("cannot create a database for adapter checks: {e}"),
+                    "check that SQLite can create in-memory databases",
+                )];
+            }
+        },
+    };
+    let selection = match AdapterSelection::resolve(None) {
+        Ok(selection) => selection,
+        // Reported by the config check.
+        Err(_) => return Vec::new(),
+    };
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let rows = health::check_all(build_adapters(&cwd, &db, &selection)).await;
+
+    rows.into_iter()
+        .map(|row| {
+            let name = format!("adapter {}", row.id);
+            match row.health {
+                Health::Status(HealthStatus::Healthy) => CheckResult::pass(name, "healthy"),
+                Health::Status(status) => CheckResult::warn(
+                    name,
+                    status.to_string(),
+                    "check the service this adapter connects to",
+                ),
+                Health::AuthRequired(auth) => CheckResult::warn(
+                    name,
+                    format!("auth required ({})", auth.provider),
+                    format!(
+                        "store a credential with `openintent vault set {}`",
+                        auth.provider
+                    ),
+                ),
+                Health::Failed(reason) => CheckResult::warn(
+                    name,
+                    reason,
+                    "make sure the service it needs (e.g. a browser or broker) is running",
+                ),
+            }
+        })
+        .collect()
+}
+
+async fn in_memory_db() -> openintent_store::StoreResult<openintent_store::Database> {
+    let db = openintent_store::Database::open_in_memory()?;
+    db.run_migrations().await?;
+    Ok(db)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn mixed_report_fails_and_renders_hints() {
+        let checks: Vec<Check> = vec![
+            Box::pin(async { vec![CheckResult::pass("LLM provider", "Anthropic")] }),
+            Box::pin(async {
+                vec![CheckResult::warn(
+                    "database",
+                    "data/openintent.db not initialized",
+                    "run `openintent setup`",
+                )]
+            }),
+            Box::pin(async {
+                vec![
+                    CheckResult::pass("adapter filesystem", "healthy"),
+                    CheckResult::fail("vault", "master key unavailable", "restore the key"),
+                ]
+            }),
+        ];
+
+        let results = run_checks(checks).await;
+        let statuses: Vec<CheckStatus> = results.iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                CheckStatus::Pass,
+                CheckStatus::Warn,
+                CheckStatus::Pass,
+                CheckStatus::Fail
+            ]
+        );
+
+        let lines = render_report(&results);
+        assert_eq!(lines[0], "  [PASS] LLM provider         Anthropic");
+        assert_eq!(lines[2], "         hint: run `openintent setup`");
+        assert!(lines[4].starts_with("  [FAIL] vault"));
+        assert_eq!(lines.last().unwrap(), "  2 passed, 1 warnings, 1 failed");
+    }
+
+    #[test]
+    fn invalid_config_is_a_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("default.toml");
+        assert_eq!(check_config(&path).status, CheckStatus::Warn);
+
+        std::fs::write(&path, "[adapters\nenabled = 1").unwrap();
+        let result = check_config(&path);
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(
+            result.detail.contains("not valid TOML"),
+            "{}",
+            result.detail
+        );
+    }
+}
@@ -0,0 +1,253 @@
+//! Adapter health checks for `openintent status`.
+//!
+//! Each adapter is connected briefly, asked for its [`HealthStatus`], and
+//! disconnected again.  An adapter whose credentials are missing is reported
+//! with its [`AuthRequirement`] instead of as a failure, so users can tell
+//! "not set up yet" apart from "broken".
+
+use std::time::{Duration, Instant};
+
+use openintent_adapters::{Adapter, AdapterError, AuthRequirement, HealthStatus};
+
+/// Upper bound on connecting to and checking a single adapter.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Upper bound on disconnecting an adapter after its check.
+const DISCONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Outcome of checking one adapter.
+#[derive(Debug)]
+pub enum Health {
+    /// The adapter connected and reported its status.
+    Status(HealthStatus),
+    /// The adapter needs credentials that are not configured.
+    AuthRequired(AuthRequirement),
+    /// Connecting or checking failed.
+    Failed(String),
+}
+
+/// One row of the adapter health table.
+#[derive(Debug)]
+pub struct AdapterHealth {
+    pub id: String,
+    pub health: Health,
+    /// Time taken to connect and check, when the check completed.
+    pub latency: Option<Duration>,
+}
+
+/// Connect, check, and disconnect every adapter concurrently.
+pub async fn check_all(adapters: Vec<Box<dyn Adapter>>) -> Vec<AdapterHealth> {
+    futures::future::join_all(adapters.into_iter().map(check_adapter)).await
+}
+
+/// Connect, check, and disconnect one adapter.
+///
+/// The adapter is disconnected whatever the outcome, so a connect that
+/// failed or timed out halfway does not leave connections open.
+pub async fn check_adapter(mut adapter: Box<dyn Adapter>) -> AdapterHealth {
+    let started = Instant::now();
+    let result = tokio::time::timeout(CHECK_TIMEOUT, async {
+        adapter.connect().await?;
+        adapter.health_check().await
+    })
+    .await;
+    let latency = started.elapsed();
+    match tokio::time::timeout(DISCONNECT_TIMEOUT, adapter.disconnect()).await {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => tracing::debug!(adapter = adapter.id(), error = %e, "disconnect failed"),
+        Err(_) => tracing::debug!(adapter = adapter.id(), "disconnect timed out"),
+    }
+
+    let (health, latency) = match result {
+        Ok(Ok(status)) => (Health::Status(status), Some(latency)),
+        Ok(Err(AdapterError::AuthRequired { provider, .. })) => {
+            let requirement = adapter.required_auth().unwrap_or(AuthRequirement {
+                provider,
+                scopes: Vec::new(),
+            });
+            (Health::AuthRequired(requirement), None)
+        }
+        Ok(Err(e)) => (Health::Failed(e.to_string()), None),
+        Err(_) => (
+            Health::Failed(format!("timed out after {}s", CHECK_TIMEOUT.as_secs())),
+            None,
+        ),
+    };
+    AdapterHealth {
+        id: adapter.id().to_owned(),
+        health,
+        latency,
+    }
+}
+
+/// Render the health table, one line per adapter, under a header line.
+pub fn render_table(rows: &[AdapterHealth]) -> Vec<String> {
+    let mut lines = vec![format!(
+        "    {:<14} {:<44} {:>8}",
+        "ADAPTER", "STATUS", "LATENCY"
+    )];
+    for row in rows {
+        let status = match &row.health {
+            Health::Status(status) => status.to_string(),
+            Health::AuthRequired(auth) if auth.scopes.is_empty() => {
+                format!("auth required ({})", auth.provider)
+            }
+            Health::AuthRequired(auth) => {
+                format!(
+                    "auth required ({}: {})",
+                    auth.provider,
+                    auth.scopes.join(", ")
+                )
+            }
+            Health::Failed(reason) => format!("error: {reason}"),
+        };
+        let latency = row
+            .latency
+            .map_or_else(|| "-".to_owned(), |d| format!("{} ms", d.as_millis()));
+        lines.push(format!("    {:<14} {status:<44} {latency:>8}", row.id));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use async_trait::async_trait;
+    use openintent_adapters::{AdapterType, ToolDefinition};
+    use serde_json::Value;
+
+    use super::*;
+
+    /// Adapter whose connect and health check results are fixed.
+    struct MockAdapter {
+        id: &'static str,
+        connect_error: Option<fn(&str) -> AdapterError>,
+        status: HealthStatus,
+        auth: Option<AuthRequirement>,
+        disconnects: Arc<AtomicUsize>,
+    }
+
+    impl MockAdapter {
+        fn healthy(id: &'static str, status: HealthStatus) -> Box<dyn Adapter> {
+            Box::new(Self {
+                id,
+                connect_error: None,
+                status,
+                auth: None,
+                disconnects: Arc::default(),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl Adapter for MockAdapter {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn adapter_type(&self) -> AdapterType {
+            AdapterType::System
+        }
+
+        async fn connect(&mut self) -> openintent_adapters::Result<()> {
+            match self.connect_error {
+                Some(error) => Err(error(self.id)),
+                None => Ok(()),
+            }
+        }
+
+        async fn disconnect(&mut self) -> openintent_adapters::Result<()> {
+            self.disconnects.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn health_check(&self) -> openintent_adapters::Result<HealthStatus> {
+            Ok(self.status)
+        }
+
+        fn tools(&self) -> Vec<ToolDefinition> {
+            Vec::new()
+        }
+
+        async fn execute_tool(
+            &self,
+            _name: &str,
+            _params: Value,
+        ) -> openintent_adapters::Result<Value> {
+            Ok(Value::Null)
+        }
+
+        fn required_auth(&self) -> Option<AuthRequirement> {
+            self.auth.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn table_reports_status_auth_and_failures() {
+        let failed_disconnects = Arc::new(AtomicUsize::new(0));
+        let adapters = vec![
+            MockAdapter::healthy("filesystem", HealthStatus::Healthy),
+            MockAdapter::healthy("web_search", HealthStatus::Degraded),
+            Box::new(MockAdapter {
+                id: "github",
+                connect_error: Some(|id| AdapterError::AuthRequired {
+                    adapter_id: id.into(),
+                    provider: "github".into(),
+                }),
+                status: HealthStatus::Healthy,
+                auth: Some(AuthRequirement {
+                    provider: "github".into(),
+                    scopes: vec!["repo".into(), "read:org".into()],
+                }),
+                disconnects: Arc::clone(&failed_disconnects),
+            }),
+            Box::new(MockAdapter {
+                id: "mqtt",
+                connect_error: Some(|id| AdapterError::NotConnected {
+                    adapter_id: id.into(),
+                    reason: "broker unreachable".into(),
+                }),
+                status: HealthStatus::Healthy,
+                auth: None,
+                disconnects: Arc::clone(&failed_disconnects),
+            }),
+        ];
+
+        let mut rows = check_all(adapters).await;
+        // Adapters whose connect failed are still disconnected.
+        assert_eq!(failed_disconnects.load(Ordering::SeqCst), 2);
+        assert!(rows[0].latency.is_some());
+        assert!(rows[2].latency.is_none());
+        // Pin latencies so the rendered table is deterministic.
+        for row in &mut rows {
+            row.latency = row.latency.map(|_| Duration::from_millis(3));
+        }
+
+        let lines = render_table(&rows);
+        let cells: Vec<Vec<&str>> = lines
+            .iter()
+            .map(|line| {
+                line.split("  ")
+                    .map(str::trim)
+                    .filter(|c| !c.is_empty())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            cells,
+            vec![
+                vec!["ADAPTER", "STATUS", "LATENCY"],
+                vec!["filesystem", "healthy", "3 ms"],
+                vec!["web_search", "degraded", "3 ms"],
+                vec!["github", "auth required (github: repo, read:org)", "-"],
+                vec![
+                    "mqtt",
+                    "error: adapter `mqtt` is not connected: broker unreachable",
+                    "-"
+                ],
+            ]
+        );
+    }
+}
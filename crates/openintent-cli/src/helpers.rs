@@ -8,6 +8,10 @@ use std::path::Path;
 use openintent_agent::LlmClientConfig;
 use tracing::info;
 use tracing_subscriber::EnvFilter;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::util::SubscriberInitExt;
+
+use crate::logging;
 
 // ---------------------------------------------------------------------------
 // Tracing
@@ -18,15 +22,28 @@ use tracing_subscriber::EnvFilter;
 /// Uses `try_init` so it is safe to call from multiple entry points
 /// (e.g., both `serve` and the bot task spawned inside it) — subsequent
 /// calls are silently ignored if a subscriber is already installed.
+///
+/// The format and destination come from [`logging::configure`].
 pub fn init_tracing(default_level: &str) {
+    install_tracing(default_level, BoxMakeWriter::new(std::io::stdout));
+}
+
+/// Like [`init_tracing`], but logs to stderr so stdout carries only the
+/// command's output (used by non-interactive modes that are piped).
+pub fn init_tracing_stderr(default_level: &str) {
+    install_tracing(default_level, BoxMakeWriter::new(std::io::stderr));
+}
+
+fn install_tracing(default_level: &str, console: BoxMakeWriter) {
     let filter =
         EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_level));
+    let options = logging::options();
+    let writer = match &options.file {
+        Some(file) => logging::file_writer(file, console),
+        None => console,
+    };
 
-    let _ = tracing_subscriber::fmt()
-        .with_env_filter(filter)
-        .with_target(false)
-        .compact()
-        .try_init();
+    let _ = logging::subscriber(options.format, filter, writer).try_init();
 }
 
 // ---------------------------------------------------------------------------
@@ -137,7 +154,7 @@ const OPENROUTER_BASE_URL: &str = "https://openrouter.ai/api/v1";
 const GROQ_BASE_URL: &str = "https://api.groq.com/openai/v1";
 const XAI_BASE_URL: &str = "https://api.x.ai/v1";
 const MISTRAL_BASE_URL: &str = "https://api.mistral.ai/v1";
-const OLLAMA_BASE_URL: &str = "http://localhost:11434/v1";
+pub const OLLAMA_BASE_URL: &str = "http://localhost:11434/v1";
 
 /// Resolve which LLM provider, API key, and model to use.
 ///
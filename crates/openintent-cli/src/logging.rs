@@ -0,0 +1,252 @@
+//! Log output configuration: format and destination.
+//!
+//! `OPENINTENT_LOG_FORMAT=json` switches from compact human-readable lines
+//! to one JSON object per line (`timestamp`, `level`, `target`, `message`
+//! and the event's fields), for log aggregation.  `--log-file` sends logs
+//! to a file instead of the terminal; the file is rotated once it exceeds
+//! a size limit, keeping a fixed number of older files (`openintent.log.1`
+//! is the most recent).
+//!
+//! [`configure`] is called once from `main`; every later
+//! [`init_tracing`](crate::helpers::init_tracing) call uses the settings.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+
+use tracing::Subscriber;
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+
+/// Settings chosen by `main` before any subcommand runs.
+static OPTIONS: OnceLock<LogOptions> = OnceLock::new();
+
+/// How log lines are formatted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Compact human-readable lines.
+    #[default]
+    Text,
+    /// One JSON object per line.
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            other => Err(format!(
+                "unknown log format `{other}` (expected `text` or `json`)"
+            )),
+        }
+    }
+}
+
+/// A size-rotated log file.
+#[derive(Debug, Clone)]
+pub struct LogFile {
+    /// The file currently written to.
+    pub path: PathBuf,
+    /// Size in bytes after which the file is rotated.
+    pub max_bytes: u64,
+    /// Rotated files kept next to the current one.
+    pub max_files: usize,
+}
+
+/// Log format and destination.
+#[derive(Debug, Clone, Default)]
+pub struct LogOptions {
+    /// Line format, from `OPENINTENT_LOG_FORMAT`.
+    pub format: LogFormat,
+    /// Log to this file instead of the terminal.
+    pub file: Option<LogFile>,
+}
+
+/// Record the log settings.  Only the first call has an effect.
+pub fn configure(options: LogOptions) {
+    let _ = OPTIONS.set(options);
+}
+
+/// The settings recorded by [`configure`], or the defaults.
+pub fn options() -> LogOptions {
+    OPTIONS.get().cloned().unwrap_or_default()
+}
+
+/// Build a subscriber writing events that pass `filter` to `writer`.
+pub fn subscriber(
+    format: LogFormat,
+    filter: EnvFilter,
+    writer: BoxMakeWriter,
+) -> Box<dyn Subscriber + Send + Sync> {
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer);
+    match format {
+        LogFormat::Text => Box::new(builder.with_target(false).compact().finish()),
+        LogFormat::Json => Box::new(
+            builder
+                .json()
+                .flatten_event(true)
+                .with_current_span(false)
+                .finish(),
+        ),
+    }
+}
+
+/// The log file as a writer, falling back to `console` if it cannot be
+/// opened.
+pub fn file_writer(file: &LogFile, console: BoxMakeWriter) -> BoxMakeWriter {
+    match RotatingFile::open(file.clone()) {
+        Ok(rotating) => BoxMakeWriter::new(Mutex::new(rotating)),
+        Err(e) => {
+            eprintln!(
+                "  Warning: cannot open log file {}: {e}",
+                file.path.display()
+            );
+            console
+        }
+    }
+}
+
+/// Writer that rotates its file once it grows past [`LogFile::max_bytes`].
+///
+/// Rotation happens between writes, and the formatter writes each log line
+/// in one call, so lines are never split across files.
+#[derive(Debug)]
+pub struct RotatingFile {
+    config: LogFile,
+    file: File,
+    written: u64,
+}
+
+impl RotatingFile {
+    /// Open (or create) the log file, appending to what it already holds.
+    pub fn open(config: LogFile) -> io::Result<Self> {
+        if let Some(parent) = config.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&config.path)?;
+        let written = file.metadata()?.len();
+        Ok(Self {
+            config,
+            file,
+            written,
+        })
+    }
+
+    /// `path.N`, the N-th most recent rotated file.
+    fn rotated_path(&self, n: usize) -> PathBuf {
+        let mut name = self.config.path.clone().into_os_string();
+        name.push(format!(".{n}"));
+        PathBuf::from(name)
+    }
+
+    /// Shift `path.1..` up by one, dropping the oldest, and start a new file.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        if self.config.max_files == 0 {
+            fs::remove_file(&self.config.path)?;
+        } else {
+            ignore_not_found(fs::remove_file(self.rotated_path(self.config.max_files)))?;
+            for n in (1..self.config.max_files).rev() {
+                ignore_not_found(fs::rename(self.rotated_path(n), self.rotated_path(n + 1)))?;
+            }
+            fs::rename(&self.config.path, self.rotated_path(1))?;
+        }
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.config.path)?;
+        self.written = 0;
+        Ok(())
+    }
+}
+
+fn ignore_not_found(result: io::Result<()>) -> io::Result<()> {
+    match result {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        other => other,
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written > 0 && self.written + buf.len() as u64 > self.config.max_bytes {
+            self.rotate()?;
+        }
+        let n = self.file.write(buf)?;
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_format_emits_parseable_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs/openintent.log");
+        let file = LogFile {
+            path: path.clone(),
+            max_bytes: 1024 * 1024,
+            max_files: 2,
+        };
+        let writer = BoxMakeWriter::new(Mutex::new(RotatingFile::open(file).unwrap()));
+        let subscriber = subscriber(LogFormat::Json, EnvFilter::new("info"), writer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(adapter = "filesystem", tools = 3, "adapter connected");
+            tracing::debug!("filtered out");
+        });
+
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 1, "{content}");
+        let line = &lines[0];
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["message"], "adapter connected");
+        assert_eq!(line["adapter"], "filesystem");
+        assert_eq!(line["tools"], 3);
+        assert!(line["target"].as_str().unwrap().starts_with("openintent"));
+        assert!(line["timestamp"].is_string());
+    }
+
+    #[test]
+    fn rotates_by_size_and_keeps_max_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("openintent.log");
+        let mut file = RotatingFile::open(LogFile {
+            path: path.clone(),
+            max_bytes: 10,
+            max_files: 2,
+        })
+        .unwrap();
+
+        for line in ["first\n", "second\n", "third\n", "fourth\n"] {
+            file.write_all(line.as_bytes()).unwrap();
+        }
+
+        let read = |name: &str| fs::read_to_string(dir.path().join(name)).ok();
+        assert_eq!(read("openintent.log").as_deref(), Some("fourth\n"));
+        assert_eq!(read("openintent.log.1").as_deref(), Some("third\n"));
+        assert_eq!(read("openintent.log.2").as_deref(), Some("second\n"));
+        assert_eq!(read("openintent.log.3"), None);
+    }
+}
@@ -0,0 +1,84 @@
+//! Signing in as a stored user: `openintent run --user <name>`.
+//!
+//! The agent then acts with that user's role, so a viewer account cannot
+//! run destructive tools.  The password comes from `OPENINTENT_PASSWORD`
+//! or is asked for on the terminal.  Without `--user` the local operator
+//! acts without role restrictions.
+
+use std::io::{BufRead, Write};
+
+use anyhow::{Context, Result, bail};
+use openintent_agent::AgentContext;
+use openintent_store::{Database, UserRole, UserStore};
+
+/// Environment variable holding the password for `--user`.
+const PASSWORD_ENV: &str = "OPENINTENT_PASSWORD";
+
+/// Sign `username` in and return their role; `None` without a username.
+pub async fn user_role(db: &Database, username: Option<&str>) -> Result<Option<UserRole>> {
+    let Some(username) = username else {
+        return Ok(None);
+    };
+    let password = match std::env::var(PASSWORD_ENV) {
+        Ok(password) => password,
+        Err(_) => {
+            let prompt = format!("  Password for {username}: ");
+            tokio::task::spawn_blocking(move || read_password(&prompt))
+                .await
+                .context("password prompt panicked")??
+        }
+    };
+    authenticate(db, username, &password).await.map(Some)
+}
+
+/// Apply the signed-in user's role to `ctx`, if any.
+pub fn with_role(ctx: AgentContext, role: Option<UserRole>) -> AgentContext {
+    match role {
+        Some(role) => ctx.with_user_role(role),
+        None => ctx,
+    }
+}
+
+/// Check `username`'s password and return their role.
+async fn authenticate(db: &Database, username: &str, password: &str) -> Result<UserRole> {
+    let user = UserStore::new(db.clone())
+        .authenticate(username, password)
+        .await
+        .context("failed to check credentials")?;
+    match user {
+        Some(user) => Ok(user.role),
+        None => bail!("invalid username or password for `{username}`"),
+    }
+}
+
+/// Ask for a password on stderr and read one line from stdin.  Blocking.
+fn read_password(prompt: &str) -> Result<String> {
+    let mut stderr = std::io::stderr();
+    write!(stderr, "{prompt}")?;
+    stderr.flush()?;
+    let mut line = String::new();
+    std::io::stdin().lock().read_line(&mut line)?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn signs_in_with_the_stored_role() {
+        let db = Database::open_in_memory().unwrap();
+        db.run_migrations().await.unwrap();
+        UserStore::new(db.clone())
+            .create("vera", None, "s3cret", UserRole::Viewer)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            authenticate(&db, "vera", "s3cret").await.unwrap(),
+            UserRole::Viewer
+        );
+        assert!(authenticate(&db, "vera", "wrong").await.is_err());
+        assert_eq!(user_role(&db, None).await.unwrap(), None);
+    }
+}
@@ -13,21 +13,36 @@ mod bot_config;
 mod bot_helpers;
 mod bridge;
 mod cli;
+mod config;
 mod dev_commands;
 mod dev_worker;
+mod doctor;
 mod failover;
+mod health;
 mod helpers;
 mod intent_classifier;
+mod logging;
+mod login;
+mod memory;
 mod messages;
 mod model_switch;
 mod onboarding;
+mod plugins;
 mod repl;
 mod self_repair;
 mod self_update_adapter;
+mod session_io;
+mod session_store;
+mod shutdown;
+mod skills;
+mod stream_printer;
 mod task_router;
 mod update;
+mod vault;
+mod watch;
+mod workflows;
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
@@ -35,12 +50,21 @@ use chrono::{TimeZone, Utc};
 use clap::Parser;
 use tracing::info;
 
-use openintent_agent::{AgentConfig, LlmClient};
-use openintent_store::SessionStore;
-
-use crate::adapters::init_adapters;
-use crate::cli::{Cli, Commands, SessionAction, SkillAction, UserAction};
+use openintent_agent::{AgentConfig, LlmClient, ReplayMode, ReplayOutcome};
+use openintent_store::ToolAuditStore;
+
+use crate::adapters::{AdapterSelection, build_adapters, init_adapters};
+use crate::cli::{Cli, Commands, SessionAction, UserAction};
+use crate::config::cmd_config;
+use crate::doctor::cmd_doctor;
+use crate::logging::{LogFile, LogFormat, LogOptions};
+use crate::memory::cmd_memory;
+use crate::plugins::cmd_plugins;
+use crate::session_store::open_session_store;
+use crate::skills::cmd_skills;
 use crate::update::cmd_update;
+use crate::vault::cmd_vault;
+use crate::workflows::cmd_workflows;
 use crate::helpers::{
     env_non_empty, init_tracing, load_system_prompt, read_claude_code_keychain_token,
     resolve_llm_config,
@@ -55,18 +79,77 @@ async fn main() -> Result<()> {
     // Load .env file if present (silently ignore if missing).
     dotenvy::dotenv().ok();
 
+    // Apply the network policy before any adapter builds an HTTP client.
+    if let Some(mode) = env_non_empty("OPENINTENT_NETWORK_MODE") {
+        let mode = mode
+            .parse::<openintent_adapters::NetworkMode>()
+            .context("invalid OPENINTENT_NETWORK_MODE")?;
+        openintent_adapters::set_network_mode(mode)?;
+    }
+
     let cli = Cli::parse();
 
+    let format = match env_non_empty("OPENINTENT_LOG_FORMAT") {
+        Some(format) => format
+            .parse::<LogFormat>()
+            .map_err(anyhow::Error::msg)
+            .context("invalid OPENINTENT_LOG_FORMAT")?,
+        None => LogFormat::Text,
+    };
+    logging::configure(LogOptions {
+        format,
+        file: cli.log_file.map(|path| LogFile {
+            path,
+            max_bytes: cli.log_max_size_mb.saturating_mul(1024 * 1024),
+            max_files: cli.log_max_files,
+        }),
+    });
+
     match cli.command {
-        Commands::Run { session } => repl::cmd_run(session).await,
-        Commands::Serve { bind, port } => cmd_serve(bind, port).await,
+        Commands::Run {
+            session,
+            prompt,
+            json,
+            watch,
+            adapters,
+            cron_catch_up,
+            user,
+        } => {
+            let selection = AdapterSelection::resolve(adapters.as_deref())?
+                .with_cron_catch_up(cron_catch_up);
+            match (prompt, watch) {
+                (Some(prompt), Some(path)) => {
+                    watch::cmd_watch(path, prompt, json, selection, user).await
+                }
+                (Some(prompt), None) => repl::cmd_prompt(prompt, json, selection, user).await,
+                (None, _) => repl::cmd_run(session, selection, user).await,
+            }
+        }
+        Commands::Serve {
+            bind,
+            port,
+            tls,
+            tls_cert,
+            tls_key,
+            allowed_origins,
+            cron_catch_up,
+        } => {
+            let selection = AdapterSelection::resolve(None)?.with_cron_catch_up(cron_catch_up);
+            cmd_serve(bind, port, tls, tls_cert, tls_key, allowed_origins, selection).await
+        }
         Commands::Setup => cmd_setup().await,
         Commands::Status => cmd_status().await,
+        Commands::Doctor => cmd_doctor().await,
+        Commands::Config { action } => cmd_config(action).await,
         Commands::Sessions { action } => cmd_sessions(action).await,
         Commands::Tui { session } => cmd_tui(session).await,
         Commands::Gui => cmd_gui().await,
         Commands::Users { action } => cmd_users(action).await,
         Commands::Skills { action } => cmd_skills(action).await,
+        Commands::Plugins { action } => cmd_plugins(action).await,
+        Commands::Vault { action } => cmd_vault(action).await,
+        Commands::Workflows { action } => cmd_workflows(action).await,
+        Commands::Memory { action } => cmd_memory(action).await,
         Commands::Bot {
             poll_timeout,
             allowed_users,
@@ -84,6 +167,21 @@ async fn cmd_tui(_session_name: Option<String>) -> Result<()> {
 
     info!("starting OpenIntentOS TUI");
 
+    let session = interactive_session().await?;
+    openintent_tui::run_tui(
+        session.llm,
+        session.adapters,
+        session.config,
+        session.system_prompt,
+    )
+    .await
+    .map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    Ok(())
+}
+
+/// Set up the store, LLM client, and adapters for an interactive UI.
+async fn interactive_session() -> Result<openintent_ui::AgentSession> {
     let data_dir = Path::new("data");
     if !data_dir.exists() {
         std::fs::create_dir_all(data_dir).context("failed to create data directory")?;
@@ -101,7 +199,7 @@ async fn cmd_tui(_session_name: Option<String>) -> Result<()> {
     info!(model = %model, "LLM client ready");
 
     let cwd = std::env::current_dir().context("failed to get current directory")?;
-    let initialized = init_adapters(cwd, db, false).await?;
+    let initialized = init_adapters(cwd, db, false, &AdapterSelection::resolve(None)?).await?;
     let adapters = initialized.tool_adapters;
 
     let system_prompt = load_system_prompt();
@@ -114,17 +212,78 @@ async fn cmd_tui(_session_name: Option<String>) -> Result<()> {
         ..AgentConfig::default()
     };
 
-    openintent_tui::run_tui(llm, adapters, config, system_prompt)
-        .await
-        .map_err(|e| anyhow::anyhow!("{e}"))?;
-
-    Ok(())
+    Ok(openintent_ui::AgentSession {
+        llm,
+        adapters,
+        config,
+        system_prompt,
+    })
 }
 
 // ---------------------------------------------------------------------------
 // Subcommand: sessions
 // ---------------------------------------------------------------------------
 
+/// A session as printed by `sessions list --json`.
+#[derive(serde::Serialize)]
+struct SessionJson<'a> {
+    id: &'a str,
+    name: &'a str,
+    model: &'a str,
+    message_count: i64,
+    token_count: i64,
+    created_at: String,
+    updated_at: String,
+}
+
+impl<'a> From<&'a openintent_store::Session> for SessionJson<'a> {
+    fn from(s: &'a openintent_store::Session) -> Self {
+        Self {
+            id: &s.id,
+            name: &s.name,
+            model: &s.model,
+            message_count: s.message_count,
+            token_count: s.token_count,
+            created_at: iso8601(s.created_at),
+            updated_at: iso8601(s.updated_at),
+        }
+    }
+}
+
+/// A user as printed by `users list --json`.
+#[derive(serde::Serialize)]
+struct UserJson<'a> {
+    id: &'a str,
+    username: &'a str,
+    display_name: Option<&'a str>,
+    role: openintent_store::UserRole,
+    active: bool,
+    created_at: String,
+    updated_at: String,
+}
+
+impl<'a> From<&'a openintent_store::User> for UserJson<'a> {
+    fn from(u: &'a openintent_store::User) -> Self {
+        Self {
+            id: &u.id,
+            username: &u.username,
+            display_name: u.display_name.as_deref(),
+            role: u.role,
+            active: u.active,
+            created_at: iso8601(u.created_at),
+            updated_at: iso8601(u.updated_at),
+        }
+    }
+}
+
+/// Format a Unix timestamp as ISO-8601 in UTC (e.g. `2025-01-31T09:30:00Z`).
+fn iso8601(timestamp: i64) -> String {
+    Utc.timestamp_opt(timestamp, 0)
+        .single()
+        .map(|dt| dt.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+        .unwrap_or_default()
+}
+
 async fn cmd_sessions(action: SessionAction) -> Result<()> {
     init_tracing("warn");
 
@@ -139,15 +298,21 @@ async fn cmd_sessions(action: SessionAction) -> Result<()> {
     let db = openintent_store::Database::open_and_migrate(db_path)
         .await
         .context("failed to open database")?;
-    let sessions = SessionStore::new(db);
+    let sessions = open_session_store(db.clone())?;
 
     match action {
-        SessionAction::List => {
+        SessionAction::List { json } => {
             let all = sessions
                 .list(100, 0)
                 .await
                 .context("failed to list sessions")?;
 
+            if json {
+                let rows: Vec<SessionJson> = all.iter().map(SessionJson::from).collect();
+                println!("{}", serde_json::to_string_pretty(&rows)?);
+                return Ok(());
+            }
+
             if all.is_empty() {
                 println!("  No sessions found.");
                 return Ok(());
@@ -243,6 +408,95 @@ async fn cmd_sessions(action: SessionAction) -> Result<()> {
 
             println!("  Deleted session: {}", name);
         }
+
+        SessionAction::Export { name, file } => {
+            let count = session_io::export_session(&sessions, &name, &file).await?;
+            println!(
+                "  Exported session '{name}' ({count} messages) to {}",
+                file.display()
+            );
+        }
+
+        SessionAction::Import { file } => {
+            let stdin = std::io::stdin();
+            let stdout = std::io::stdout();
+            let session =
+                session_io::import_session(&sessions, &file, &mut stdin.lock(), &mut stdout.lock())
+                    .await?;
+            println!(
+                "  Imported session '{}' ({} messages)",
+                session.name, session.message_count
+            );
+        }
+
+        SessionAction::Replay { name, execute } => {
+            let all = sessions
+                .list(1000, 0)
+                .await
+                .context("failed to list sessions")?;
+            let Some(session) = all.into_iter().find(|s| s.name == name) else {
+                eprintln!("  Error: Session '{}' not found.", name);
+                std::process::exit(1);
+            };
+
+            let (mode, adapters) = if execute {
+                eprintln!("  WARNING: re-executing recorded tool calls against live adapters.");
+                eprintln!("  Files may be changed and messages sent again.");
+                let cwd = std::env::current_dir().context("failed to get current directory")?;
+                let initialized =
+                    init_adapters(cwd, db.clone(), true, &AdapterSelection::resolve(None)?).await?;
+                (ReplayMode::Execute, initialized.tool_adapters)
+            } else {
+                (ReplayMode::DryRun, Vec::new())
+            };
+
+            let audit = ToolAuditStore::new(db);
+            let steps = openintent_agent::replay(&audit, &session.id, &adapters, mode)
+                .await
+                .context("failed to replay session")?;
+
+            if steps.is_empty() {
+                println!("  Session '{}' has no recorded tool calls.", name);
+                return Ok(());
+            }
+
+            println!();
+            println!(
+                "  Replay of '{}' ({} tool calls, {})",
+                session.name,
+                steps.len(),
+                if execute { "executed" } else { "dry run" }
+            );
+            println!("  {}", "-".repeat(50));
+
+            for (i, step) in steps.iter().enumerate() {
+                let entry = &step.entry;
+                let recorded = if entry.success { "ok" } else { "failed" };
+                println!(
+                    "  {}. {} {} [{recorded}]",
+                    i + 1,
+                    entry.tool_name,
+                    entry.arguments
+                );
+                match &step.outcome {
+                    ReplayOutcome::NotRun => {}
+                    ReplayOutcome::Skipped(reason) => println!("     skipped: {reason}"),
+                    ReplayOutcome::Executed { success, result } => {
+                        let diverged = if step.diverged() {
+                            " (differs from recording)"
+                        } else {
+                            ""
+                        };
+                        let status = if *success { "ok" } else { "failed" };
+                        println!("     replayed: {status}{diverged}");
+                        for line in result.lines().take(5) {
+                            println!("       {line}");
+                        }
+                    }
+                }
+            }
+            println!();
+        }
     }
 
     Ok(())
@@ -252,17 +506,43 @@ async fn cmd_sessions(action: SessionAction) -> Result<()> {
 // Subcommand: serve
 // ---------------------------------------------------------------------------
 
-async fn cmd_serve(bind: String, port: u16) -> Result<()> {
+async fn cmd_serve(
+    bind: String,
+    port: u16,
+    tls: bool,
+    tls_cert: Option<PathBuf>,
+    tls_key: Option<PathBuf>,
+    allowed_origins: Vec<String>,
+    selection: AdapterSelection,
+) -> Result<()> {
     init_tracing("info");
 
+    let data_dir = Path::new("data");
+    let tls = match (tls, tls_cert, tls_key) {
+        (false, _, _) => None,
+        (true, Some(cert), Some(key)) => Some(openintent_web::TlsConfig::new(cert, key)),
+        (true, _, _) => {
+            let mut hosts = vec!["localhost".to_owned(), "127.0.0.1".to_owned()];
+            if !matches!(bind.as_str(), "0.0.0.0" | "::" | "localhost" | "127.0.0.1") {
+                hosts.push(bind.clone());
+            }
+            let tls = openintent_web::TlsConfig::self_signed(data_dir, &hosts)
+                .map_err(|e| anyhow::anyhow!("failed to create TLS certificate: {e}"))?;
+            println!(
+                "  TLS: using self-signed certificate {}",
+                tls.cert_path.display()
+            );
+            Some(tls)
+        }
+    };
+
     // If no AI keys are configured yet, start the first-run setup wizard.
     if !openintent_web::setup::is_configured() {
-        return cmd_serve_setup(bind, port).await;
+        return cmd_serve_setup(bind, port, tls).await;
     }
 
     info!("starting OpenIntentOS web server");
 
-    let data_dir = Path::new("data");
     if !data_dir.exists() {
         std::fs::create_dir_all(data_dir).context("failed to create data directory")?;
     }
@@ -280,16 +560,20 @@ async fn cmd_serve(bind: String, port: u16) -> Result<()> {
     info!(model = %model, provider = %provider_label, "LLM client ready");
 
     let cwd = std::env::current_dir().context("failed to get current directory")?;
-    let initialized = init_adapters(cwd, db.clone(), false).await?;
+    let initialized = init_adapters(cwd, db.clone(), false, &selection).await?;
     let raw_adapters = initialized.raw_adapters;
 
     info!(
         "adapters initialized (filesystem, shell, web_search, web_fetch, http_request, cron, memory, github, email, browser, feishu, calendar)"
     );
 
+    let scheme = if tls.is_some() { "https" } else { "http" };
     let web_config = openintent_web::WebConfig {
         bind_addr: bind,
         port,
+        tls,
+        allowed_origins,
+        ..openintent_web::WebConfig::default()
     };
 
     println!();
@@ -297,11 +581,11 @@ async fn cmd_serve(bind: String, port: u16) -> Result<()> {
     println!("  Provider: {provider_label}");
     println!("  Model: {model}");
     println!(
-        "  Web UI:  http://{}:{}",
+        "  Web UI:  {scheme}://{}:{}",
         web_config.bind_addr, web_config.port
     );
     println!(
-        "  MCP:     http://{}:{}/mcp",
+        "  MCP:     {scheme}://{}:{}/mcp",
         web_config.bind_addr, web_config.port
     );
     println!("  Adapters: filesystem, shell, web_search, web_fetch, http_request, cron, memory,");
@@ -322,9 +606,20 @@ async fn cmd_serve(bind: String, port: u16) -> Result<()> {
     }
     println!();
 
-    let server = openintent_web::WebServer::new(web_config, llm, raw_adapters, db);
+    // Kernel events, such as scheduler task status changes and watched file
+    // changes, reach `/api/events`.  The first Ctrl+C shuts the kernel down,
+    // which stops the web server once open requests have finished.
+    let kernel = shutdown::start_kernel(initialized.bus, &raw_adapters);
+    let sessions = open_session_store(db.clone())?;
+    let server = openintent_web::WebServer::new(web_config, llm, raw_adapters, db)
+        .with_bus(kernel.bus().clone())
+        .with_sessions(sessions)
+        .with_shutdown(kernel.register_service("web"));
+    let ctrl_c = shutdown::shutdown_on_ctrl_c(Arc::clone(&kernel));
     server.start().await.map_err(|e| anyhow::anyhow!("{e}"))?;
 
+    // The server only stops once the kernel is shutting down.
+    let _ = ctrl_c.await;
     Ok(())
 }
 
@@ -332,8 +627,12 @@ async fn cmd_serve(bind: String, port: u16) -> Result<()> {
 // First-run setup wizard server
 // ---------------------------------------------------------------------------
 
-async fn cmd_serve_setup(bind: String, port: u16) -> Result<()> {
-    openintent_web::serve_setup(&bind, port)
+async fn cmd_serve_setup(
+    bind: String,
+    port: u16,
+    tls: Option<openintent_web::TlsConfig>,
+) -> Result<()> {
+    openintent_web::serve_setup(&bind, port, tls.as_ref())
         .await
         .map_err(|e| anyhow::anyhow!("{e}"))
 }
@@ -448,6 +747,22 @@ async fn cmd_status() -> Result<()> {
         println!("  Config:           MISSING");
     }
 
+    println!();
+    println!("  Adapters:");
+    let db = if db_path.exists() {
+        openintent_store::Database::open_and_migrate(db_path).await?
+    } else {
+        let db = openintent_store::Database::open_in_memory()?;
+        db.run_migrations().await?;
+        db
+    };
+    let cwd = std::env::current_dir()?;
+    let selection = AdapterSelection::resolve(None)?;
+    let rows = health::check_all(build_adapters(&cwd, &db, &selection)).await;
+    for line in health::render_table(&rows) {
+        println!("{line}");
+    }
+
     println!();
 
     Ok(())
@@ -467,7 +782,10 @@ async fn cmd_gui() -> Result<()> {
     println!("  Launching iced GUI...");
     println!();
 
-    openintent_ui::run_desktop_ui().map_err(|e| anyhow::anyhow!("GUI error: {e}"))?;
+    let session = interactive_session().await?;
+    // iced runs its own event loop and executor; let it block this worker.
+    tokio::task::block_in_place(|| openintent_ui::run_desktop_ui(session))
+        .map_err(|e| anyhow::anyhow!("GUI error: {e}"))?;
 
     Ok(())
 }
@@ -493,9 +811,15 @@ async fn cmd_users(action: UserAction) -> Result<()> {
     let users = openintent_store::UserStore::new(db);
 
     match action {
-        UserAction::List => {
+        UserAction::List { json } => {
             let all = users.list(1000, 0).await.context("failed to list users")?;
 
+            if json {
+                let rows: Vec<UserJson> = all.iter().map(UserJson::from).collect();
+                println!("{}", serde_json::to_string_pretty(&rows)?);
+                return Ok(());
+            }
+
             if all.is_empty() {
                 println!("  No users found.");
                 return Ok(());
@@ -567,186 +891,3 @@ async fn cmd_users(action: UserAction) -> Result<()> {
 
     Ok(())
 }
-
-// ---------------------------------------------------------------------------
-// Subcommand: skills
-// ---------------------------------------------------------------------------
-
-async fn cmd_skills(action: SkillAction) -> Result<()> {
-    init_tracing("warn");
-
-    let skills_dir = openintent_skills::default_skills_dir();
-
-    match action {
-        SkillAction::List => {
-            let mut mgr = openintent_skills::SkillManager::new(skills_dir);
-            mgr.load_all().context("failed to load skills")?;
-
-            let skills_with_status = mgr.list_with_status();
-            if skills_with_status.is_empty() {
-                println!("  No skills installed.");
-                println!();
-                println!("  Install from ClawHub:  openintent skills install <slug>");
-                println!("  Install from URL:      openintent skills install github:owner/repo");
-                println!("  Search registry:       openintent skills search <query>");
-                return Ok(());
-            }
-
-            println!();
-            println!("  Installed skills ({}):", skills_with_status.len());
-            println!();
-            for (skill, status) in &skills_with_status {
-                let status_label = match status {
-                    openintent_skills::SkillStatus::Ready => "ready",
-                    openintent_skills::SkillStatus::Degraded => "degraded",
-                    openintent_skills::SkillStatus::Unavailable => "unavailable",
-                };
-                let version = skill.version.as_deref().unwrap_or("-");
-                let scripts = skill.scripts.len();
-                println!(
-                    "  {:<24} v{:<8} scripts:{:<3} [{}]  {}",
-                    skill.name, version, scripts, status_label, skill.description
-                );
-            }
-            println!();
-        }
-
-        SkillAction::Install { source } => {
-            let mut mgr = openintent_skills::SkillManager::new(skills_dir);
-            mgr.ensure_dir()
-                .context("failed to create skills directory")?;
-            mgr.load_all().context("failed to load skills")?;
-
-            let is_url = source.starts_with("http://")
-                || source.starts_with("https://")
-                || source.starts_with("github:");
-
-            let skill = if is_url {
-                println!("  Installing skill from URL: {source}");
-                mgr.install_from_url(&source)
-                    .await
-                    .context("failed to install skill from URL")?
-            } else {
-                println!("  Installing skill from ClawHub: {source}");
-                mgr.install_from_registry(&source)
-                    .await
-                    .context("failed to install skill from registry")?
-            };
-
-            let script_count = skill.scripts.len();
-            println!();
-            println!("  Installed: {}", skill.name);
-            println!("  Description: {}", skill.description);
-            if let Some(ref v) = skill.version {
-                println!("  Version: {v}");
-            }
-            if script_count > 0 {
-                println!("  Script tools: {script_count}");
-            }
-            if !skill.metadata.requires.env.is_empty() {
-                println!(
-                    "  Required env vars: {}",
-                    skill.metadata.requires.env.join(", ")
-                );
-            }
-            println!();
-        }
-
-        SkillAction::Remove { name } => {
-            let mut mgr = openintent_skills::SkillManager::new(skills_dir);
-            mgr.load_all().context("failed to load skills")?;
-
-            mgr.remove(&name).context("failed to remove skill")?;
-            println!("  Removed skill: {name}");
-        }
-
-        SkillAction::Search { query, limit } => {
-            println!("  Searching ClawHub for: {query}");
-            println!();
-
-            let mgr = openintent_skills::SkillManager::new(skills_dir);
-            match mgr.search(&query, limit).await {
-                Ok(results) => {
-                    if results.is_empty() {
-                        println!("  No results found.");
-                    } else {
-                        println!("  Results ({}):", results.len());
-                        println!();
-                        for skill in &results {
-                            let installs = skill
-                                .installs
-                                .map(|n| format!("{n} installs"))
-                                .unwrap_or_default();
-                            println!("  {:<28} {}  {}", skill.slug, skill.description, installs);
-                        }
-                    }
-                    println!();
-                    println!("  Install with: openintent skills install <slug>");
-                }
-                Err(e) => {
-                    eprintln!("  Error: Failed to search registry: {e}");
-                    eprintln!("  (ClawHub registry may be unreachable)");
-                }
-            }
-            println!();
-        }
-
-        SkillAction::Info { name } => {
-            let mut mgr = openintent_skills::SkillManager::new(skills_dir);
-            mgr.load_all().context("failed to load skills")?;
-
-            let skill = mgr.get(&name);
-            match skill {
-                Some(skill) => {
-                    let status = openintent_skills::check_requirements(skill);
-                    let status_label = match status {
-                        openintent_skills::SkillStatus::Ready => "ready",
-                        openintent_skills::SkillStatus::Degraded => "degraded",
-                        openintent_skills::SkillStatus::Unavailable => "unavailable",
-                    };
-                    println!();
-                    println!("  Skill: {}", skill.name);
-                    println!("  Description: {}", skill.description);
-                    if let Some(ref v) = skill.version {
-                        println!("  Version: {v}");
-                    }
-                    if let Some(ref author) = skill.metadata.author {
-                        println!("  Author: {author}");
-                    }
-                    if let Some(ref homepage) = skill.metadata.homepage {
-                        println!("  Homepage: {homepage}");
-                    }
-                    println!("  Status: {status_label}");
-                    if !skill.metadata.tags.is_empty() {
-                        println!("  Tags: {}", skill.metadata.tags.join(", "));
-                    }
-                    if !skill.metadata.requires.env.is_empty() {
-                        println!("  Required env: {}", skill.metadata.requires.env.join(", "));
-                    }
-                    if !skill.metadata.requires.bins.is_empty() {
-                        println!(
-                            "  Required bins: {}",
-                            skill.metadata.requires.bins.join(", ")
-                        );
-                    }
-                    if !skill.scripts.is_empty() {
-                        println!("  Scripts:");
-                        for s in &skill.scripts {
-                            println!("    {} ({:?})", s.filename, s.interpreter);
-                        }
-                    }
-                    println!();
-                    println!("  --- Instructions ---");
-                    println!("{}", skill.instructions);
-                    println!();
-                }
-                None => {
-                    eprintln!("  Error: Skill '{name}' is not installed.");
-                    std::process::exit(1);
-                }
-            }
-        }
-    }
-
-    Ok(())
-}
@@ -0,0 +1,268 @@
+//! `openintent memory` — inspect, search, and export long-term memories.
+//!
+//! Operates on the [`SemanticMemory`] layer in `data/openintent.db`.  Exports
+//! are JSONL with one [`Memory`] per line (embeddings are not included), so
+//! they can be read back with `serde_json` line by line.
+
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{TimeZone, Utc};
+
+use openintent_store::{Database, Memory, MemoryQuery, SemanticMemory, StoreError};
+
+use crate::cli::MemoryAction;
+use crate::helpers::init_tracing;
+
+/// Maximum characters of memory content printed per line.
+const CONTENT_PREVIEW_CHARS: usize = 100;
+
+pub async fn cmd_memory(action: MemoryAction) -> Result<()> {
+    init_tracing("warn");
+
+    let db_path = Path::new("data").join("openintent.db");
+    if !db_path.exists() {
+        eprintln!("  Error: Database not found. Run `openintent setup` first.");
+        std::process::exit(1);
+    }
+
+    let db = Database::open_and_migrate(db_path)
+        .await
+        .context("failed to open database")?;
+    let memory = SemanticMemory::new(db);
+
+    let stdin = std::io::stdin();
+    let stdout = std::io::stdout();
+    run(&memory, action, &mut stdin.lock(), &mut stdout.lock()).await
+}
+
+/// Execute a memory action, reading confirmations from `input`.
+async fn run(
+    memory: &SemanticMemory,
+    action: MemoryAction,
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+) -> Result<()> {
+    match action {
+        MemoryAction::List { limit } => {
+            let memories = memory
+                .list_all(None, limit)
+                .await
+                .context("failed to list memories")?;
+            if memories.is_empty() {
+                writeln!(out, "  No memories stored.")?;
+                return Ok(());
+            }
+
+            let total = memory
+                .count(None)
+                .await
+                .context("failed to count memories")?;
+            writeln!(out)?;
+            writeln!(out, "  Memories ({} of {total}):", memories.len())?;
+            writeln!(out)?;
+            for m in &memories {
+                print_memory(out, m, None)?;
+            }
+            writeln!(out)?;
+        }
+
+        MemoryAction::Search { query, limit } => {
+            let results = memory
+                .search(&MemoryQuery {
+                    text: query.clone(),
+                    top_k: limit,
+                    ..MemoryQuery::default()
+                })
+                .await
+                .context("failed to search memories")?;
+            if results.is_empty() {
+                writeln!(out, "  No memories match: {query}")?;
+                return Ok(());
+            }
+
+            writeln!(out)?;
+            writeln!(out, "  Results ({}):", results.len())?;
+            writeln!(out)?;
+            for scored in &results {
+                print_memory(out, &scored.memory, Some(scored.score))?;
+            }
+            writeln!(out)?;
+        }
+
+        MemoryAction::Export { file } => {
+            let count = export(memory, &file).await?;
+            writeln!(out, "  Exported {count} memories to {}", file.display())?;
+        }
+
+        MemoryAction::Forget { id, yes } => {
+            let target = match memory.get(id).await {
+                Ok(m) => m,
+                Err(StoreError::NotFound { .. }) => {
+                    anyhow::bail!("memory {id} not found");
+                }
+                Err(e) => return Err(e).context("failed to read memory"),
+            };
+
+            if !yes {
+                print_memory(out, &target, None)?;
+                write!(out, "  Forget this memory? [y/N]: ")?;
+                out.flush()?;
+
+                let mut line = String::new();
+                input.read_line(&mut line)?;
+                let answer = line.trim().to_lowercase();
+                if answer != "y" && answer != "yes" {
+                    writeln!(out, "  Cancelled.")?;
+                    return Ok(());
+                }
+            }
+
+            memory
+                .delete(id)
+                .await
+                .with_context(|| format!("failed to delete memory {id}"))?;
+            writeln!(out, "  Forgot memory {id}.")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Write every memory to `path` as JSONL, returning the number written.
+async fn export(memory: &SemanticMemory, path: &Path) -> Result<usize> {
+    let total = memory
+        .count(None)
+        .await
+        .context("failed to count memories")?;
+    let limit = u32::try_from(total).unwrap_or(u32::MAX);
+    let memories = memory
+        .list_all(None, limit)
+        .await
+        .context("failed to list memories")?;
+
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("failed to create {}", path.display()))?;
+    let mut writer = std::io::BufWriter::new(file);
+    for m in &memories {
+        serde_json::to_writer(&mut writer, m).context("failed to serialize memory")?;
+        writer.write_all(b"\n")?;
+    }
+    writer
+        .flush()
+        .with_context(|| format!("failed to write {}", path.display()))?;
+
+    Ok(memories.len())
+}
+
+/// Print one memory, e.g. `#12  knowledge  0.80  2025-01-31  The user ...`.
+fn print_memory(out: &mut impl Write, m: &Memory, score: Option<f64>) -> Result<()> {
+    let mut content = m.content.replace('\n', " ");
+    if content.chars().count() > CONTENT_PREVIEW_CHARS {
+        content = content
+            .chars()
+            .take(CONTENT_PREVIEW_CHARS)
+            .collect::<String>()
+            + "...";
+    }
+    let created = Utc
+        .timestamp_opt(m.created_at, 0)
+        .single()
+        .map(|t| t.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| "-".to_string());
+
+    match score {
+        Some(score) => writeln!(
+            out,
+            "  #{:<5} {:<10} score:{score:.2}  {created}  {content}",
+            m.id,
+            m.category.as_str()
+        )?,
+        None => writeln!(
+            out,
+            "  #{:<5} {:<10} {:.2}  {created}  {content}",
+            m.id,
+            m.category.as_str(),
+            m.importance
+        )?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openintent_store::{MemoryCategory, NewMemory};
+
+    async fn semantic_memory() -> SemanticMemory {
+        let db = Database::open_in_memory().expect("in-memory database must open");
+        db.run_migrations().await.expect("migrations must run");
+        SemanticMemory::new(db)
+    }
+
+    async fn exec(memory: &SemanticMemory, action: MemoryAction, stdin: &str) -> Result<String> {
+        let mut out = Vec::new();
+        run(memory, action, &mut stdin.as_bytes(), &mut out).await?;
+        Ok(String::from_utf8(out).expect("output must be UTF-8"))
+    }
+
+    #[tokio::test]
+    async fn forget_requires_confirmation() {
+        let memory = semantic_memory().await;
+        let id = memory
+            .insert(NewMemory {
+                category: MemoryCategory::Preference,
+                content: "prefers dark mode".into(),
+                embedding: None,
+                importance: 0.5,
+                ttl: None,
+            })
+            .await
+            .unwrap();
+
+        let out = exec(&memory, MemoryAction::Forget { id, yes: false }, "n\n")
+            .await
+            .unwrap();
+        assert!(out.contains("Cancelled"), "unexpected output: {out}");
+        assert_eq!(memory.count(None).await.unwrap(), 1);
+
+        let out = exec(&memory, MemoryAction::Forget { id, yes: false }, "y\n")
+            .await
+            .unwrap();
+        assert!(out.contains("Forgot memory"), "unexpected output: {out}");
+        assert_eq!(memory.count(None).await.unwrap(), 0);
+
+        assert!(
+            exec(&memory, MemoryAction::Forget { id, yes: true }, "")
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn search_ranks_matching_memories() {
+        let memory = semantic_memory().await;
+        for content in ["rust is the team language", "lunch is at noon"] {
+            memory
+                .insert(NewMemory {
+                    category: MemoryCategory::Knowledge,
+                    content: content.into(),
+                    embedding: None,
+                    importance: 0.5,
+                    ttl: None,
+                })
+                .await
+                .unwrap();
+        }
+
+        let search = MemoryAction::Search {
+            query: "rust".into(),
+            limit: 10,
+        };
+        let out = exec(&memory, search, "").await.unwrap();
+        assert!(out.contains("Results (1)"), "unexpected output: {out}");
+        assert!(out.contains("rust is the team language"));
+        assert!(!out.contains("lunch"));
+    }
+}
@@ -0,0 +1,122 @@
+//! `openintent plugins` — manage sandboxed Wasm plugins.
+//!
+//! Plugins are installed into `./plugins`, the directory the agent scans for
+//! `.wasm` modules at startup, and recorded in its [`PluginManifest`].
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use openintent_sandbox::{Capability, InstalledPlugin, PluginManifest, SandboxConfig};
+
+use crate::cli::PluginAction;
+use crate::helpers::init_tracing;
+
+/// Directory plugins are installed into, relative to the working directory.
+const PLUGINS_DIR: &str = "plugins";
+
+pub async fn cmd_plugins(action: PluginAction) -> Result<()> {
+    init_tracing("warn");
+
+    let mut manifest =
+        PluginManifest::open(Path::new(PLUGINS_DIR)).context("failed to read plugin manifest")?;
+
+    match action {
+        PluginAction::List => {
+            let plugins = manifest.list();
+            if plugins.is_empty() {
+                println!("  No plugins installed.");
+                println!();
+                println!("  Install a plugin:  openintent plugins install <path.wasm>");
+                return Ok(());
+            }
+
+            println!();
+            println!("  Installed plugins ({}):", plugins.len());
+            println!();
+            for plugin in plugins {
+                println!(
+                    "  {:<24} v{:<8} tools:{:<3} {}",
+                    plugin.id(),
+                    plugin.info.version,
+                    plugin.info.tools.len(),
+                    plugin.info.description
+                );
+                for tool in &plugin.info.tools {
+                    println!(
+                        "    - {:<22} requires: {}",
+                        tool.name,
+                        capability_list(&tool.capabilities)
+                    );
+                }
+            }
+            println!();
+        }
+
+        PluginAction::Install { path } => {
+            println!("  Installing plugin from: {}", path.display());
+            let plugin = manifest
+                .install(&path, SandboxConfig::default())
+                .await
+                .context("failed to install plugin")?;
+            println!();
+            println!("  Installed: {}", plugin.id());
+            print_details(plugin);
+        }
+
+        PluginAction::Remove { id } => {
+            manifest.remove(&id).context("failed to remove plugin")?;
+            println!("  Removed plugin: {id}");
+        }
+
+        PluginAction::Info { id } => match manifest.get(&id) {
+            Some(plugin) => {
+                println!();
+                println!("  Plugin: {}", plugin.id());
+                print_details(plugin);
+            }
+            None => {
+                eprintln!("  Error: Plugin '{id}' is not installed.");
+                std::process::exit(1);
+            }
+        },
+    }
+
+    Ok(())
+}
+
+/// Print a plugin's metadata and tools.
+fn print_details(plugin: &InstalledPlugin) {
+    let info = &plugin.info;
+    println!("  Version: {}", info.version);
+    if !info.description.is_empty() {
+        println!("  Description: {}", info.description);
+    }
+    println!(
+        "  File: {}",
+        Path::new(PLUGINS_DIR).join(&plugin.file).display()
+    );
+    println!();
+    if info.tools.is_empty() {
+        println!("  Tools: none declared");
+    } else {
+        println!("  Tools ({}):", info.tools.len());
+        for tool in &info.tools {
+            println!("    {}  {}", tool.name, tool.description);
+            println!("      requires: {}", capability_list(&tool.capabilities));
+        }
+    }
+    println!();
+}
+
+/// Render capability requirements, e.g. `network, filesystem`.
+fn capability_list(capabilities: &[Capability]) -> String {
+    if capabilities.is_empty() {
+        return "none".to_string();
+    }
+    capabilities
+        .iter()
+        .map(|c| c.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -1,7 +1,8 @@
 //! Subcommand: `openintent run` — interactive REPL.
 //!
 //! Runs the full ReAct (Reason + Act) loop in a terminal REPL with session
-//! persistence, streaming output, and self-evolution support.
+//! persistence, streaming output, and self-evolution support.  With
+//! `--prompt`, a single request is run non-interactively instead.
 
 use std::io::{self, Write as _};
 use std::path::Path;
@@ -11,15 +12,33 @@ use anyhow::{Context, Result};
 use tracing::info;
 
 use openintent_agent::{
-    AgentConfig, AgentContext, EvolutionEngine, LlmClient, Message, react_loop,
+    AgentConfig, AgentContext, AgentResponse, CompactionConfig, EvolutionEngine, LlmClient,
+    Message, compact_messages, needs_compaction, react_loop,
 };
-use openintent_store::SessionStore;
+use openintent_store::{SessionStore, ToolAuditStore};
+use tokio::sync::mpsc;
 
-use crate::adapters::init_adapters;
-use crate::helpers::{init_tracing, load_system_prompt, resolve_llm_config};
+use crate::adapters::{AdapterSelection, init_adapters};
+use crate::helpers::{init_tracing, init_tracing_stderr, load_system_prompt, resolve_llm_config};
+use crate::login;
+use crate::session_store::open_session_store;
+use crate::shutdown::{self, SessionQueue};
+use crate::stream_printer::StreamPrinter;
+
+/// Resumed histories longer than this are compacted into a summary.
+const HISTORY_MAX_MESSAGES: usize = 40;
+
+/// Number of most recent messages kept verbatim when resuming a session.
+const HISTORY_KEEP_RECENT: usize = 20;
 
 /// Run the interactive REPL.
-pub async fn cmd_run(session_name: Option<String>) -> Result<()> {
+///
+/// `selection` comes from the `--adapters` flag; see [`AdapterSelection`].
+pub async fn cmd_run(
+    session_name: Option<String>,
+    selection: AdapterSelection,
+    user: Option<String>,
+) -> Result<()> {
     // 1. Initialize tracing.
     init_tracing("info");
 
@@ -36,6 +55,7 @@ pub async fn cmd_run(session_name: Option<String>) -> Result<()> {
         .await
         .context("failed to open database")?;
     info!(path = %db_path.display(), "store initialized");
+    let role = login::user_role(&db, user.as_deref()).await?;
 
     // 3. Resolve LLM provider, API key, and model.
     let llm_config = resolve_llm_config();
@@ -44,8 +64,9 @@ pub async fn cmd_run(session_name: Option<String>) -> Result<()> {
     let llm = Arc::new(LlmClient::new(llm_config).context("failed to create LLM client")?);
     info!(model = %model, provider = %provider_label, "LLM client ready");
 
-    // 4. Set up session persistence.
-    let sessions = SessionStore::new(db.clone());
+    // 4. Set up session persistence and the tool-call audit trail.
+    let sessions = open_session_store(db.clone())?;
+    let tool_audit = ToolAuditStore::new(db.clone());
 
     let active_session = if let Some(ref name) = session_name {
         let all = sessions
@@ -80,32 +101,21 @@ pub async fn cmd_run(session_name: Option<String>) -> Result<()> {
 
     // 5. Initialize adapters.
     let cwd = std::env::current_dir().context("failed to get current directory")?;
-    let initialized = init_adapters(cwd, db, true).await?;
+    let initialized = init_adapters(cwd, db, true, &selection).await?;
+    let adapter_names = initialized
+        .raw_adapters
+        .iter()
+        .map(|a| a.id().to_owned())
+        .collect::<Vec<_>>();
     let adapters = initialized.tool_adapters;
     let skill_prompt_ext = initialized.skill_prompt_ext;
     let skill_count = initialized.skill_count;
     let wasm_plugin_count = initialized.wasm_plugin_count;
 
-    info!(
-        "adapters initialized (filesystem, shell, web_search, web_fetch, http_request, cron, memory, github, email, browser, feishu, calendar, telegram, discord)"
-    );
-
     // 6. Load session history if resuming.
     let mut history_messages: Vec<Message> = Vec::new();
     if let Some(ref sid) = session_id {
-        let stored = sessions
-            .get_messages(sid, Some(20))
-            .await
-            .context("failed to load session messages")?;
-        for msg in &stored {
-            let message = match msg.role.as_str() {
-                "user" => Message::user(&msg.content),
-                "assistant" => Message::assistant(&msg.content),
-                "system" => Message::system(&msg.content),
-                _ => continue,
-            };
-            history_messages.push(message);
-        }
+        history_messages = load_session_history(&sessions, sid, &llm).await?;
         if !history_messages.is_empty() {
             info!(
                 count = history_messages.len(),
@@ -128,8 +138,11 @@ pub async fn cmd_run(session_name: Option<String>) -> Result<()> {
     println!("  Provider: {provider_label}");
     println!("  Model: {model}");
     println!("  Evolution: {evolution_status}");
-    println!("  Adapters: filesystem, shell, web_search, web_fetch, http_request, cron, memory,");
-    println!("            github, email, browser, feishu, calendar, telegram, discord");
+    if adapter_names.is_empty() {
+        println!("  Adapters: none");
+    } else {
+        println!("  Adapters: {}", adapter_names.join(", "));
+    }
     if skill_count > 0 {
         println!("  Skills: {skill_count}");
     }
@@ -142,41 +155,41 @@ pub async fn cmd_run(session_name: Option<String>) -> Result<()> {
     println!("  Type your request, or 'quit' to exit.");
     println!();
 
-    // 9. Set up Ctrl+C handler.
-    let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
-    {
-        let running = running.clone();
-        tokio::spawn(async move {
-            if tokio::signal::ctrl_c().await.is_ok() {
-                running.store(false, std::sync::atomic::Ordering::SeqCst);
-                eprintln!("\n  Interrupted. Goodbye!");
-                std::process::exit(0);
-            }
-        });
-    }
+    // 9. Start the kernel.  The first Ctrl+C shuts it down: the REPL lets
+    // the current request finish, flushes the session, and acknowledges.  A
+    // second press exits immediately.
+    let kernel = shutdown::start_kernel(initialized.bus, &initialized.raw_adapters);
+    let mut repl = kernel.register_service("repl");
+    let ctrl_c = shutdown::shutdown_on_ctrl_c(Arc::clone(&kernel));
+    let queue = SessionQueue::new();
+    let mut stop = None;
 
     // 10. REPL loop.
-    let stdin = io::stdin();
-    let mut line_buf = String::new();
+    let mut lines = spawn_stdin_reader();
 
     loop {
         print!("> ");
         io::stdout().flush().ok();
 
-        line_buf.clear();
-        let bytes_read = stdin.read_line(&mut line_buf);
-        match bytes_read {
-            Ok(0) => {
-                println!();
-                info!("EOF received, exiting");
+        let line = tokio::select! {
+            line = lines.recv() => line,
+            notice = repl.recv() => {
+                stop = Some(notice);
                 break;
             }
-            Ok(_) => {}
-            Err(e) => {
+        };
+        let line_buf = match line {
+            Some(Ok(line)) => line,
+            Some(Err(e)) => {
                 eprintln!("  Error reading input: {e}");
                 continue;
             }
-        }
+            None => {
+                println!();
+                info!("EOF received, exiting");
+                break;
+            }
+        };
 
         let trimmed = line_buf.trim();
 
@@ -190,40 +203,31 @@ pub async fn cmd_run(session_name: Option<String>) -> Result<()> {
         }
 
         // Persist user message to session.
-        if let Some(ref sid) = session_id
-            && let Err(e) = sessions
-                .append_message(sid, "user", trimmed, None, None)
-                .await
-        {
-            tracing::warn!(error = %e, "failed to persist user message");
+        if let Some(ref sid) = session_id {
+            queue.queue_message(sid, "user", trimmed);
+            flush_session(&queue, &sessions).await;
         }
 
         // Build agent context for this request.
-        let agent_config = AgentConfig {
-            max_turns: 20,
-            model: model.clone(),
-            temperature: Some(0.0),
-            max_tokens: Some(4096),
-            ..AgentConfig::default()
-        };
+        let agent_config = agent_config(&model);
 
         let mut system_prompt = load_system_prompt();
         if !skill_prompt_ext.is_empty() {
             system_prompt.push_str(&skill_prompt_ext);
         }
-        let mut ctx = AgentContext::new(llm.clone(), adapters.clone(), agent_config)
-            .with_system_prompt(&system_prompt);
-
-        // Enable real-time streaming.
-        let streaming_started = Arc::new(std::sync::atomic::AtomicBool::new(false));
-        let streaming_flag = streaming_started.clone();
-        ctx.on_text_delta = Some(Arc::new(std::sync::Mutex::new(move |delta: &str| {
-            if !streaming_flag.load(std::sync::atomic::Ordering::Relaxed) {
-                streaming_flag.store(true, std::sync::atomic::Ordering::Relaxed);
-            }
-            print!("{delta}");
-            io::stdout().flush().ok();
-        })));
+        let ctx = AgentContext::new(llm.clone(), adapters.clone(), agent_config)
+            .with_system_prompt(&system_prompt)
+            .with_tool_audit(tool_audit.clone());
+        let mut ctx = login::with_role(ctx, role);
+        if let Some(ref sid) = session_id {
+            ctx = ctx.with_session_id(sid);
+        }
+
+        // Stream tokens live, with a spinner until the first one arrives.
+        let printer = StreamPrinter::stdout();
+        let (on_text_delta, on_tool_start) = printer.callbacks();
+        ctx.on_text_delta = Some(on_text_delta);
+        ctx.on_tool_start = Some(on_tool_start);
 
         // Inject session history.
         for msg in &history_messages {
@@ -231,24 +235,34 @@ pub async fn cmd_run(session_name: Option<String>) -> Result<()> {
         }
         ctx = ctx.with_user_message(trimmed);
 
-        // Run the ReAct loop.
-        match react_loop(&mut ctx).await {
+        // Run the ReAct loop to completion, even if shutdown starts meanwhile.
+        printer.start();
+        let turn = react_loop(&mut ctx);
+        tokio::pin!(turn);
+        let result = tokio::select! {
+            result = &mut turn => result,
+            notice = repl.recv() => {
+                stop = Some(notice);
+                turn.await
+            }
+        };
+        let streamed = printer.finish();
+        match result {
             Ok(response) => {
-                if streaming_started.load(std::sync::atomic::Ordering::Relaxed) {
-                    println!();
-                } else {
+                if streamed.is_empty() {
                     println!("{}", response.text);
                 }
 
                 if response.turns_used > 1 {
                     println!(
-                        "  ({} tool turn{} used)",
+                        "  ({} tool turn{} used; {})",
                         response.turns_used - 1,
                         if response.turns_used - 1 == 1 {
                             ""
                         } else {
                             "s"
-                        }
+                        },
+                        response.timing_summary()
                     );
                 }
 
@@ -265,12 +279,9 @@ pub async fn cmd_run(session_name: Option<String>) -> Result<()> {
                 println!();
 
                 // Persist assistant message to session.
-                if let Some(ref sid) = session_id
-                    && let Err(e) = sessions
-                        .append_message(sid, "assistant", &response.text, None, None)
-                        .await
-                {
-                    tracing::warn!(error = %e, "failed to persist assistant message");
+                if let Some(ref sid) = session_id {
+                    queue.queue_message(sid, "assistant", &response.text);
+                    flush_session(&queue, &sessions).await;
                 }
 
                 // Update rolling history.
@@ -291,11 +302,336 @@ pub async fn cmd_run(session_name: Option<String>) -> Result<()> {
             }
         }
 
-        if !running.load(std::sync::atomic::Ordering::SeqCst) {
+        if stop.is_some() {
             break;
         }
     }
 
+    // Write anything a failed flush left behind before exiting.
+    flush_session(&queue, &sessions).await;
     info!("shutting down");
+    match stop {
+        // Ctrl+C started the shutdown; let it finish now the REPL is done.
+        Some(notice) => {
+            let _ = repl.ack(&notice);
+            let _ = ctrl_c.await;
+        }
+        None => {
+            ctrl_c.abort();
+            let ack = async {
+                let notice = repl.recv().await;
+                let _ = repl.ack(&notice);
+            };
+            tokio::join!(shutdown::shutdown(&kernel), ack);
+        }
+    }
+    Ok(())
+}
+
+/// Read stdin lines on a dedicated thread so the REPL can wait for input
+/// and a shutdown request at the same time.  The channel closes on EOF or
+/// after a read error.
+fn spawn_stdin_reader() -> mpsc::UnboundedReceiver<io::Result<String>> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    std::thread::spawn(move || {
+        let stdin = io::stdin();
+        loop {
+            let mut line = String::new();
+            match stdin.read_line(&mut line) {
+                Ok(0) => break,
+                Ok(_) => {
+                    if tx.send(Ok(line)).is_err() {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    let _ = tx.send(Err(e));
+                    break;
+                }
+            }
+        }
+    });
+    rx
+}
+
+/// Load a session's full history for resuming.
+///
+/// Histories longer than [`HISTORY_MAX_MESSAGES`], or too long for the
+/// model's context window, are compacted: everything but the last
+/// [`HISTORY_KEEP_RECENT`] messages is summarized by the LLM so long
+/// sessions keep their context within the token budget.  If
+/// summarization fails, only the recent messages are kept.
+async fn load_session_history(
+    sessions: &SessionStore,
+    session_id: &str,
+    llm: &Arc<LlmClient>,
+) -> Result<Vec<Message>> {
+    let stored = sessions
+        .get_messages(session_id, None)
+        .await
+        .context("failed to load session messages")?;
+    let messages: Vec<Message> = stored
+        .iter()
+        .filter_map(|msg| match msg.role.as_str() {
+            "user" => Some(Message::user(&msg.content)),
+            "assistant" => Some(Message::assistant(&msg.content)),
+            "system" => Some(Message::system(&msg.content)),
+            _ => None,
+        })
+        .collect();
+
+    let config = CompactionConfig {
+        max_messages: HISTORY_MAX_MESSAGES,
+        keep_recent: HISTORY_KEEP_RECENT,
+        ..CompactionConfig::default()
+    };
+    if !needs_compaction(&messages, &llm.current_default_model(), &config) {
+        return Ok(messages);
+    }
+
+    match compact_messages(&messages, llm, &config).await {
+        Ok(compacted) => Ok(compacted),
+        Err(e) => {
+            tracing::warn!(
+                error = %e,
+                total = messages.len(),
+                "failed to summarize session history, keeping recent messages only"
+            );
+            let split = messages.len().saturating_sub(HISTORY_KEEP_RECENT);
+            Ok(messages[split..].to_vec())
+        }
+    }
+}
+
+/// Write queued session messages, logging (and keeping) any that fail.
+async fn flush_session(queue: &SessionQueue, sessions: &SessionStore) {
+    if let Err(e) = queue.flush(sessions).await {
+        tracing::warn!(error = %e, "failed to persist session messages");
+    }
+}
+
+/// Run a single request non-interactively.
+///
+/// Prints the final response (or the full [`AgentResponse`] as JSON) to
+/// stdout and exits non-zero if the agent fails.  Logs go to stderr so the
+/// output can be piped.
+///
+/// [`AgentResponse`]: openintent_agent::AgentResponse
+pub async fn cmd_prompt(
+    prompt: String,
+    json: bool,
+    selection: AdapterSelection,
+    user: Option<String>,
+) -> Result<()> {
+    init_tracing_stderr("warn");
+    let mut ctx = prompt_context(&selection, user.as_deref())
+        .await?
+        .with_user_message(&prompt);
+
+    match react_loop(&mut ctx).await {
+        Ok(response) => print_response(&response, json),
+        Err(e) => {
+            eprintln!("  Error: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Build the agent context for non-interactive runs, without any user
+/// message yet, acting as `user` when given.
+pub(crate) async fn prompt_context(
+    selection: &AdapterSelection,
+    user: Option<&str>,
+) -> Result<AgentContext> {
+    let data_dir = Path::new("data");
+    std::fs::create_dir_all(data_dir).context("failed to create data directory")?;
+    let db = openintent_store::Database::open_and_migrate(data_dir.join("openintent.db"))
+        .await
+        .context("failed to open database")?;
+    let role = login::user_role(&db, user).await?;
+
+    let llm_config = resolve_llm_config();
+    let model = llm_config.default_model.clone();
+    let llm = Arc::new(LlmClient::new(llm_config).context("failed to create LLM client")?);
+
+    let cwd = std::env::current_dir().context("failed to get current directory")?;
+    let initialized = init_adapters(cwd, db, true, selection).await?;
+
+    let mut system_prompt = load_system_prompt();
+    system_prompt.push_str(&initialized.skill_prompt_ext);
+    let ctx = AgentContext::new(llm, initialized.tool_adapters, agent_config(&model))
+        .with_system_prompt(&system_prompt);
+    Ok(login::with_role(ctx, role))
+}
+
+/// Print a non-interactive run's response, as JSON if `json` is set.
+pub(crate) fn print_response(response: &AgentResponse, json: bool) -> Result<()> {
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(response).context("failed to serialize response")?
+        );
+    } else {
+        println!("{}", response.text);
+    }
     Ok(())
 }
+
+/// Agent settings shared by the REPL and one-shot mode.
+fn agent_config(model: &str) -> AgentConfig {
+    AgentConfig {
+        max_turns: 20,
+        model: model.to_owned(),
+        temperature: Some(0.0),
+        max_tokens: Some(4096),
+        ..AgentConfig::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openintent_agent::{LlmClientConfig, Role};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Answer one chat completion request with `summary`.  Returns the base URL.
+    async fn mock_summarizer(summary: &'static str) -> String {
+        let body = serde_json::json!({
+            "choices": [{ "message": { "role": "assistant", "content": summary } }]
+        });
+        mock_llm("application/json", body.to_string()).await
+    }
+
+    /// Answer one request with `body` as an OpenAI-compatible endpoint would.
+    /// Returns the base URL.
+    async fn mock_llm(content_type: &'static str, body: String) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind must succeed");
+        let base = format!("http://{}", listener.local_addr().expect("local addr"));
+        tokio::spawn(async move {
+            let Ok((mut stream, _)) = listener.accept().await else {
+                return;
+            };
+            let mut request = Vec::new();
+            let mut buf = [0u8; 8192];
+            loop {
+                let n = stream.read(&mut buf).await.unwrap_or(0);
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request);
+                let complete = text.split_once("\r\n\r\n").is_some_and(|(head, rest)| {
+                    let len = head
+                        .lines()
+                        .find_map(|l| {
+                            l.to_lowercase()
+                                .strip_prefix("content-length:")
+                                .and_then(|v| v.trim().parse::<usize>().ok())
+                        })
+                        .unwrap_or(0);
+                    rest.len() >= len
+                });
+                if n == 0 || complete {
+                    break;
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        });
+        base
+    }
+
+    /// A session store holding one session with `count` alternating messages.
+    async fn long_session(count: usize) -> (SessionStore, String) {
+        let db =
+            openintent_store::Database::open_in_memory().expect("in-memory database must open");
+        db.run_migrations().await.expect("migrations must run");
+        let sessions = SessionStore::new(db);
+        let session = sessions.create("long", "mock-model").await.unwrap();
+        for i in 0..count {
+            let role = if i % 2 == 0 { "user" } else { "assistant" };
+            sessions
+                .append_message(&session.id, role, &format!("message {i}"), None, None)
+                .await
+                .unwrap();
+        }
+        (sessions, session.id)
+    }
+
+    fn llm(base_url: &str) -> Arc<LlmClient> {
+        let config = LlmClientConfig::openai_compatible("test-key", "mock-model", base_url);
+        Arc::new(LlmClient::new(config).expect("LLM client must build"))
+    }
+
+    #[tokio::test]
+    async fn resuming_long_session_compacts_older_messages() {
+        let (sessions, sid) = long_session(100).await;
+        let base = mock_summarizer("The user asked about messages 0 to 79.").await;
+
+        let history = load_session_history(&sessions, &sid, &llm(&base))
+            .await
+            .unwrap();
+
+        assert_eq!(history.len(), HISTORY_KEEP_RECENT + 1);
+        assert_eq!(history[0].role, Role::System);
+        assert!(
+            history[0]
+                .content
+                .contains("summary of 80 earlier messages")
+        );
+        assert!(history[0].content.contains("messages 0 to 79"));
+        assert_eq!(history[1].content, "message 80");
+        assert_eq!(history[HISTORY_KEEP_RECENT].content, "message 99");
+    }
+
+    #[tokio::test]
+    async fn failed_summary_keeps_recent_messages() {
+        let (sessions, sid) = long_session(100).await;
+
+        // Nothing listens on port 1, so summarization fails.
+        let history = load_session_history(&sessions, &sid, &llm("http://127.0.0.1:1"))
+            .await
+            .unwrap();
+
+        assert_eq!(history.len(), HISTORY_KEEP_RECENT);
+        assert_eq!(history[0].content, "message 80");
+    }
+
+    #[tokio::test]
+    async fn short_session_is_loaded_verbatim() {
+        let (sessions, sid) = long_session(6).await;
+
+        let history = load_session_history(&sessions, &sid, &llm("http://127.0.0.1:1"))
+            .await
+            .unwrap();
+
+        assert_eq!(history.len(), 6);
+        assert_eq!(history[0].content, "message 0");
+    }
+
+    #[tokio::test]
+    async fn streamed_deltas_accumulate_to_final_text() {
+        let stream = concat!(
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hello\"}}]}\n\n",
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\", world\"}}]}\n\n",
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"!\"}}]}\n\n",
+            "data: [DONE]\n\n",
+        );
+        let base = mock_llm("text/event-stream", stream.to_owned()).await;
+
+        let printer = Arc::new(StreamPrinter::new(io::sink(), false));
+        let (on_text_delta, on_tool_start) = printer.callbacks();
+        let mut ctx = AgentContext::new(llm(&base), Vec::new(), agent_config("mock-model"))
+            .with_user_message("greet me");
+        ctx.on_text_delta = Some(on_text_delta);
+        ctx.on_tool_start = Some(on_tool_start);
+
+        printer.start();
+        let response = react_loop(&mut ctx).await.expect("agent must respond");
+
+        assert_eq!(response.text, "Hello, world!");
+        assert_eq!(printer.finish(), response.text);
+    }
+}
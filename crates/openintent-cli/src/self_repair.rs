@@ -404,7 +404,7 @@ async fn wait_for_release_and_update(timeout: std::time::Duration) -> Result<boo
 
 /// Determine whether an agent error looks like a code bug that we can fix.
 fn is_code_bug(error: &openintent_agent::error::AgentError) -> bool {
-    use openintent_agent::error::AgentError;
+    use openintent_agent::error::{AgentError, ErrorCategory};
 
     match error {
         // Panics and internal errors are almost always code bugs.
@@ -417,9 +417,11 @@ fn is_code_bug(error: &openintent_agent::error::AgentError) -> bool {
                 || msg.contains("expect")
         }
         // Tool execution failure CAN be a code bug if it's a panic.
-        AgentError::ToolExecutionFailed { reason, .. } => {
-            reason.contains("panicked") || reason.contains("char boundary")
-        }
+        AgentError::ToolExecutionFailed { reason, .. }
+        | AgentError::AdapterFailed {
+            category: ErrorCategory::Upstream,
+            reason,
+        } => reason.contains("panicked") || reason.contains("char boundary"),
         // Everything else is external (LLM, network, config, etc.).
         _ => false,
     }
@@ -47,6 +47,7 @@ impl ToolAdapter for SelfUpdateAdapter {
                 "properties": {},
                 "required": []
             }),
+            destructive: true,
         }]
     }
 
@@ -0,0 +1,97 @@
+//! `openintent sessions export` / `import` — move conversations between
+//! machines.
+//!
+//! An export is a single JSON document holding the session and all of its
+//! messages.  Importing recreates it through [`SessionStore::import`] with a
+//! new ID, keeping timestamps and roles.  If a session with the same name
+//! already exists the user is asked for a different one.
+
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use openintent_store::{Session, SessionMessage, SessionStore};
+
+/// Version of the export format written by this build.
+const FORMAT_VERSION: u32 = 1;
+
+/// On-disk form of an exported session.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionExport {
+    pub format_version: u32,
+    pub session: Session,
+    pub messages: Vec<SessionMessage>,
+}
+
+/// Write the session named `name` and its messages to `path`, returning the
+/// number of messages exported.
+pub async fn export_session(sessions: &SessionStore, name: &str, path: &Path) -> Result<usize> {
+    let session = sessions
+        .find_by_name(name)
+        .await
+        .context("failed to look up session")?
+        .with_context(|| format!("session '{name}' not found"))?;
+    let messages = sessions
+        .get_messages(&session.id, None)
+        .await
+        .context("failed to load session messages")?;
+
+    let export = SessionExport {
+        format_version: FORMAT_VERSION,
+        session,
+        messages,
+    };
+    let json = serde_json::to_string_pretty(&export)?;
+    std::fs::write(path, json).with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(export.messages.len())
+}
+
+/// Recreate the session exported to `path`.
+///
+/// If its name is taken, a new name is read from `input` (prompting on
+/// `output`) until a free one is given; an empty answer cancels the import.
+pub async fn import_session(
+    sessions: &SessionStore,
+    path: &Path,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<Session> {
+    let json = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let export: SessionExport = serde_json::from_str(&json)
+        .with_context(|| format!("{} is not a session export", path.display()))?;
+    if export.format_version > FORMAT_VERSION {
+        bail!(
+            "{} uses export format {}, but this version only reads up to {FORMAT_VERSION}",
+            path.display(),
+            export.format_version
+        );
+    }
+
+    let mut name = export.session.name.clone();
+    while sessions
+        .find_by_name(&name)
+        .await
+        .context("failed to look up session")?
+        .is_some()
+    {
+        write!(
+            output,
+            "  Session '{name}' already exists. New name (empty to cancel): "
+        )?;
+        output.flush()?;
+        let mut answer = String::new();
+        input.read_line(&mut answer)?;
+        name = answer.trim().to_owned();
+        if name.is_empty() {
+            bail!("import cancelled");
+        }
+    }
+
+    sessions
+        .import(&name, &export.session, &export.messages)
+        .await
+        .context("failed to import session")
+}
@@ -0,0 +1,71 @@
+//! Session store construction shared by the subcommands.
+//!
+//! With `encrypt_sessions = true` in the `[store]` section of
+//! `config/default.toml`, session message content is encrypted at rest
+//! with a key kept in the vault (see [`session_key`]).  Messages written
+//! before encryption was enabled stay readable.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use openintent_store::{Database, SessionStore};
+
+use crate::vault::session_key;
+
+/// Configuration file holding the `[store]` section.
+const CONFIG_FILE: &str = "config/default.toml";
+
+/// Directory holding the vault.
+const DATA_DIR: &str = "data";
+
+/// A session store on `db`, encrypted if the configuration asks for it.
+pub fn open_session_store(db: Database) -> Result<SessionStore> {
+    let store = SessionStore::new(db);
+    if !encryption_enabled(Path::new(CONFIG_FILE))? {
+        return Ok(store);
+    }
+    let key = session_key(Path::new(DATA_DIR))?;
+    store
+        .with_encryption(&key)
+        .context("failed to enable session encryption")
+}
+
+/// Whether `encrypt_sessions` is set in the `[store]` section of the
+/// configuration file at `path`.  A missing file means no.
+fn encryption_enabled(path: &Path) -> Result<bool> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e).with_context(|| format!("failed to read {}", path.display())),
+    };
+    let table: toml::Table = content
+        .parse()
+        .with_context(|| format!("failed to parse {}", path.display()))?;
+    Ok(table
+        .get("store")
+        .and_then(|store| store.get("encrypt_sessions"))
+        .and_then(toml::Value::as_bool)
+        .unwrap_or(false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encryption_follows_store_section() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("default.toml");
+        assert!(!encryption_enabled(&path).unwrap());
+
+        std::fs::write(&path, "[store]\ndb_path = \"data/openintent.db\"\n").unwrap();
+        assert!(!encryption_enabled(&path).unwrap());
+
+        std::fs::write(&path, "[store]\nencrypt_sessions = true\n").unwrap();
+        assert!(encryption_enabled(&path).unwrap());
+
+        std::fs::write(&path, "[store\n").unwrap();
+        assert!(encryption_enabled(&path).is_err());
+    }
+}
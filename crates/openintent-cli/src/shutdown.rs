@@ -0,0 +1,216 @@
+//! Graceful shutdown through the kernel.
+//!
+//! Long-running commands (`run`, `serve`) build a [`Kernel`] around the
+//! adapters' event bus with [`start_kernel`].  The adapters, the scheduler,
+//! and the command itself -- the REPL or the web server -- take part in its
+//! shutdown.  The first Ctrl+C calls [`Kernel::shutdown`] with
+//! [`SHUTDOWN_DEADLINE`]: the REPL lets the request in flight finish and
+//! flushes its [`SessionQueue`], the web server lets open requests finish,
+//! and both then acknowledge.  A second Ctrl+C exits immediately.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Result;
+use openintent_adapters::Adapter;
+use openintent_kernel::{
+    AdapterRegistry, AdapterStatus, IpcBus, Kernel, Scheduler, ShutdownListener, ShutdownReport,
+};
+use openintent_store::SessionStore;
+use tokio::task::JoinHandle;
+
+/// How long services and the scheduler get to finish after a shutdown
+/// request.
+pub const SHUTDOWN_DEADLINE: Duration = Duration::from_secs(10);
+
+/// Exit code used when a second Ctrl+C forces an immediate exit.
+const FORCED_EXIT_CODE: i32 = 130;
+
+/// Build the kernel for a long-running command around the adapters' `bus`.
+///
+/// Registers `adapters` with the kernel's registry and as the `adapters`
+/// service, which marks them disconnected on shutdown, and starts the
+/// scheduler worker.
+pub fn start_kernel(bus: IpcBus, adapters: &[Arc<dyn Adapter>]) -> Arc<Kernel> {
+    let kernel = Arc::new(Kernel::with_parts(
+        Scheduler::new(),
+        bus,
+        AdapterRegistry::new(),
+    ));
+
+    let registry = kernel.registry().clone();
+    for adapter in adapters {
+        registry.register(adapter.id(), adapter.adapter_type().to_string());
+        let _ = registry.set_status(adapter.id(), AdapterStatus::Connected);
+    }
+    let listener = kernel.register_service("adapters");
+    tokio::spawn(disconnect_on_shutdown(listener, registry));
+
+    kernel.scheduler().start();
+    kernel
+}
+
+/// Wait for kernel shutdown, then mark every adapter disconnected.
+async fn disconnect_on_shutdown(mut listener: ShutdownListener, registry: AdapterRegistry) {
+    let notice = listener.recv().await;
+    for id in registry.list_ids() {
+        let _ = registry.set_status(&id, AdapterStatus::Disconnected);
+    }
+    if let Err(e) = listener.ack(&notice) {
+        tracing::warn!(error = %e, "failed to acknowledge adapter shutdown");
+    }
+}
+
+/// Spawn a task that shuts `kernel` down on the first Ctrl+C and exits the
+/// process on the second.  The task ends with the shutdown report.
+pub fn shutdown_on_ctrl_c(kernel: Arc<Kernel>) -> JoinHandle<ShutdownReport> {
+    tokio::spawn(async move {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::warn!(error = %e, "cannot listen for Ctrl+C");
+            return std::future::pending().await;
+        }
+        eprintln!("\n  Shutting down (press Ctrl+C again to quit immediately)...");
+        tokio::spawn(async {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                eprintln!("\n  Interrupted. Goodbye!");
+                std::process::exit(FORCED_EXIT_CODE);
+            }
+        });
+        shutdown(&kernel).await
+    })
+}
+
+/// Run [`Kernel::shutdown`] with [`SHUTDOWN_DEADLINE`] and log whatever
+/// did not finish in time.
+pub async fn shutdown(kernel: &Kernel) -> ShutdownReport {
+    let report = kernel.shutdown(SHUTDOWN_DEADLINE).await;
+    if !report.unacknowledged.is_empty() {
+        tracing::warn!(
+            services = ?report.unacknowledged,
+            "services did not finish before the shutdown deadline"
+        );
+    }
+    if !report.unstarted_tasks.is_empty() {
+        tracing::warn!(
+            tasks = report.unstarted_tasks.len(),
+            "scheduled tasks were cancelled before they started"
+        );
+    }
+    report
+}
+
+/// A session message waiting to be written.
+#[derive(Debug, Clone)]
+struct PendingMessage {
+    session_id: String,
+    role: String,
+    content: String,
+}
+
+/// Session messages not yet written to the [`SessionStore`], in order.
+///
+/// Messages whose write fails stay queued, so a final
+/// [`flush`](Self::flush) before exiting can retry them.
+#[derive(Default)]
+pub struct SessionQueue {
+    pending: Mutex<Vec<PendingMessage>>,
+}
+
+impl SessionQueue {
+    /// Create an empty queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a session message to be written by the next [`flush`](Self::flush).
+    pub fn queue_message(&self, session_id: &str, role: &str, content: &str) {
+        self.lock_pending().push(PendingMessage {
+            session_id: session_id.to_owned(),
+            role: role.to_owned(),
+            content: content.to_owned(),
+        });
+    }
+
+    /// Write all queued messages to `sessions`, in order.
+    ///
+    /// Returns the number written.  If a write fails, it and the messages
+    /// after it stay queued.
+    pub async fn flush(&self, sessions: &SessionStore) -> Result<usize> {
+        let pending = std::mem::take(&mut *self.lock_pending());
+        for (written, msg) in pending.iter().enumerate() {
+            if let Err(e) = sessions
+                .append_message(&msg.session_id, &msg.role, &msg.content, None, None)
+                .await
+            {
+                let mut queue = self.lock_pending();
+                let newer = std::mem::take(&mut *queue);
+                queue.extend(pending[written..].iter().cloned());
+                queue.extend(newer);
+                return Err(e.into());
+            }
+        }
+        Ok(pending.len())
+    }
+
+    fn lock_pending(&self) -> std::sync::MutexGuard<'_, Vec<PendingMessage>> {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn session_store() -> SessionStore {
+        let db =
+            openintent_store::Database::open_in_memory().expect("in-memory database must open");
+        db.run_migrations().await.expect("migrations must run");
+        SessionStore::new(db)
+    }
+
+    #[tokio::test]
+    async fn pending_writes_are_flushed_before_exit() {
+        let sessions = session_store().await;
+        let session = sessions.create("work", "test-model").await.unwrap();
+        let queue = SessionQueue::new();
+
+        queue.queue_message(&session.id, "user", "hello");
+        queue.queue_message(&session.id, "assistant", "hi there");
+
+        assert_eq!(queue.flush(&sessions).await.unwrap(), 2);
+        let stored = sessions.get_messages(&session.id, None).await.unwrap();
+        let contents: Vec<_> = stored.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, ["hello", "hi there"]);
+        assert_eq!(queue.flush(&sessions).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn failed_writes_stay_queued() {
+        let sessions = session_store().await;
+        let queue = SessionQueue::new();
+        queue.queue_message("no-such-session", "user", "lost?");
+
+        assert!(queue.flush(&sessions).await.is_err());
+        assert_eq!(queue.lock_pending().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn kernel_shutdown_disconnects_adapters() {
+        let adapter: Arc<dyn Adapter> = Arc::new(openintent_adapters::ShellAdapter::new(
+            "shell",
+            std::env::temp_dir(),
+        ));
+        let kernel = start_kernel(IpcBus::new(16), &[adapter]);
+        assert_eq!(
+            kernel.registry().get("shell").unwrap().status,
+            AdapterStatus::Connected
+        );
+
+        let report = shutdown(&kernel).await;
+        assert_eq!(report.acknowledged, vec!["adapters"]);
+        assert_eq!(
+            kernel.registry().get("shell").unwrap().status,
+            AdapterStatus::Disconnected
+        );
+    }
+}
@@ -0,0 +1,266 @@
+//! `openintent skills` — manage OpenClaw-compatible skills.
+//!
+//! Skills live in [`default_skills_dir`] and are installed from the ClawHub
+//! registry or a URL through [`SkillManager`].
+
+use anyhow::{Context, Result};
+
+use openintent_skills::{
+    SkillDefinition, SkillError, SkillManager, SkillStatus, check_requirements, default_skills_dir,
+};
+
+use crate::cli::SkillAction;
+use crate::helpers::init_tracing;
+
+pub async fn cmd_skills(action: SkillAction) -> Result<()> {
+    init_tracing("warn");
+
+    let skills_dir = default_skills_dir();
+
+    match action {
+        SkillAction::List => {
+            let mut mgr = SkillManager::new(skills_dir);
+            mgr.load_all().context("failed to load skills")?;
+
+            let skills_with_status = mgr.list_with_status();
+            if skills_with_status.is_empty() {
+                println!("  No skills installed.");
+                println!();
+                println!("  Install from ClawHub:  openintent skills install <slug>");
+                println!("  Install from URL:      openintent skills install github:owner/repo");
+                println!("  Search registry:       openintent skills search <query>");
+                return Ok(());
+            }
+
+            println!();
+            println!("  Installed skills ({}):", skills_with_status.len());
+            println!();
+            for (skill, status) in &skills_with_status {
+                let version = skill.version.as_deref().unwrap_or("-");
+                let scripts = skill.scripts.len();
+                println!(
+                    "  {:<24} v{:<8} scripts:{:<3} [{}]  {}",
+                    skill.name,
+                    version,
+                    scripts,
+                    state_label(&mgr, &skill.name, *status),
+                    skill.description
+                );
+            }
+            println!();
+        }
+
+        SkillAction::Install { source } => {
+            let mut mgr = SkillManager::new(skills_dir);
+            mgr.ensure_dir()
+                .context("failed to create skills directory")?;
+            mgr.load_all().context("failed to load skills")?;
+
+            let is_url = source.starts_with("http://")
+                || source.starts_with("https://")
+                || source.starts_with("github:");
+
+            let skill = if is_url {
+                println!("  Installing skill from URL: {source}");
+                mgr.install_from_url(&source)
+                    .await
+                    .context("failed to install skill from URL")?
+            } else {
+                println!("  Installing skill from ClawHub: {source}");
+                mgr.install_from_registry(&source)
+                    .await
+                    .context("failed to install skill from registry")?
+            };
+
+            let script_count = skill.scripts.len();
+            println!();
+            println!("  Installed: {}", skill.name);
+            println!("  Description: {}", skill.description);
+            if let Some(ref v) = skill.version {
+                println!("  Version: {v}");
+            }
+            if script_count > 0 {
+                println!("  Script tools: {script_count}");
+            }
+            if !skill.metadata.requires.env.is_empty() {
+                println!(
+                    "  Required env vars: {}",
+                    skill.metadata.requires.env.join(", ")
+                );
+            }
+            print_requirements_check(&skill);
+            println!();
+        }
+
+        SkillAction::Update { name } => {
+            let mut mgr = SkillManager::new(skills_dir);
+            mgr.load_all().context("failed to load skills")?;
+
+            let names: Vec<String> = match name {
+                Some(name) => vec![name],
+                None => mgr.skills().iter().map(|s| s.name.clone()).collect(),
+            };
+            if names.is_empty() {
+                println!("  No skills installed.");
+                return Ok(());
+            }
+
+            for name in names {
+                match mgr.update(&name).await {
+                    Ok(skill) => {
+                        let version = skill.version.as_deref().unwrap_or("-");
+                        println!("  Updated: {} (v{version})", skill.name);
+                        print_requirements_check(&skill);
+                    }
+                    Err(SkillError::Registry(reason)) => {
+                        println!("  Skipped: {name} ({reason})");
+                    }
+                    Err(e) => eprintln!("  Error: Failed to update {name}: {e}"),
+                }
+            }
+        }
+
+        SkillAction::Enable { name } => {
+            let mut mgr = SkillManager::new(skills_dir);
+            mgr.set_enabled(&name, true)
+                .context("failed to enable skill")?;
+            println!("  Enabled skill: {name}");
+        }
+
+        SkillAction::Disable { name } => {
+            let mut mgr = SkillManager::new(skills_dir);
+            mgr.set_enabled(&name, false)
+                .context("failed to disable skill")?;
+            println!("  Disabled skill: {name}");
+        }
+
+        SkillAction::Remove { name } => {
+            let mut mgr = SkillManager::new(skills_dir);
+            mgr.load_all().context("failed to load skills")?;
+
+            mgr.remove(&name).context("failed to remove skill")?;
+            println!("  Removed skill: {name}");
+        }
+
+        SkillAction::Search { query, limit } => {
+            println!("  Searching ClawHub for: {query}");
+            println!();
+
+            let mgr = SkillManager::new(skills_dir);
+            match mgr.search(&query, limit).await {
+                Ok(results) => {
+                    if results.is_empty() {
+                        println!("  No results found.");
+                    } else {
+                        println!("  Results ({}):", results.len());
+                        println!();
+                        for skill in &results {
+                            let installs = skill
+                                .installs
+                                .map(|n| format!("{n} installs"))
+                                .unwrap_or_default();
+                            println!("  {:<28} {}  {}", skill.slug, skill.description, installs);
+                        }
+                    }
+                    println!();
+                    println!("  Install with: openintent skills install <slug>");
+                }
+                Err(e) => {
+                    eprintln!("  Error: Failed to search registry: {e}");
+                    eprintln!("  (ClawHub registry may be unreachable)");
+                }
+            }
+            println!();
+        }
+
+        SkillAction::Info { name } => {
+            let mut mgr = SkillManager::new(skills_dir);
+            mgr.load_all().context("failed to load skills")?;
+
+            let skill = mgr.get(&name);
+            match skill {
+                Some(skill) => {
+                    let status = check_requirements(skill);
+                    let status_label = state_label(&mgr, &skill.name, status);
+                    println!();
+                    println!("  Skill: {}", skill.name);
+                    println!("  Description: {}", skill.description);
+                    if let Some(ref v) = skill.version {
+                        println!("  Version: {v}");
+                    }
+                    if let Some(ref author) = skill.metadata.author {
+                        println!("  Author: {author}");
+                    }
+                    if let Some(ref homepage) = skill.metadata.homepage {
+                        println!("  Homepage: {homepage}");
+                    }
+                    println!("  Status: {status_label}");
+                    if !skill.metadata.tags.is_empty() {
+                        println!("  Tags: {}", skill.metadata.tags.join(", "));
+                    }
+                    if !skill.metadata.requires.env.is_empty() {
+                        println!("  Required env: {}", skill.metadata.requires.env.join(", "));
+                    }
+                    if !skill.metadata.requires.bins.is_empty() {
+                        println!(
+                            "  Required bins: {}",
+                            skill.metadata.requires.bins.join(", ")
+                        );
+                    }
+                    if !skill.scripts.is_empty() {
+                        println!("  Scripts:");
+                        for s in &skill.scripts {
+                            println!("    {} ({:?})", s.filename, s.interpreter);
+                        }
+                    }
+                    println!();
+                    println!("  --- Instructions ---");
+                    println!("{}", skill.instructions);
+                    println!();
+                }
+                None => {
+                    eprintln!("  Error: Skill '{name}' is not installed.");
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Human-readable state of an installed skill.
+fn state_label(mgr: &SkillManager, name: &str, status: SkillStatus) -> &'static str {
+    if !mgr.is_enabled(name) {
+        return "disabled";
+    }
+    match status {
+        SkillStatus::Ready => "ready",
+        SkillStatus::Degraded => "degraded",
+        SkillStatus::Unavailable => "unavailable",
+    }
+}
+
+/// Check a freshly installed skill's requirements and report what is missing.
+fn print_requirements_check(skill: &SkillDefinition) {
+    let req = &skill.metadata.requires;
+    match check_requirements(skill) {
+        SkillStatus::Ready => println!("  Status: ready"),
+        SkillStatus::Degraded => {
+            let missing: Vec<&str> = req
+                .env
+                .iter()
+                .filter(|var| std::env::var(var).is_err())
+                .map(String::as_str)
+                .collect();
+            println!("  Status: degraded (set {})", missing.join(", "));
+        }
+        SkillStatus::Unavailable => {
+            let mut needed = req.bins.clone();
+            if !req.any_bins.is_empty() {
+                needed.push(format!("one of {}", req.any_bins.join("/")));
+            }
+            println!("  Status: unavailable (requires {})", needed.join(", "));
+        }
+    }
+}
@@ -0,0 +1,237 @@
+//! Live rendering of agent output in the REPL.
+//!
+//! A spinner is shown while the agent waits on the LLM and is replaced by
+//! response tokens as soon as the first one arrives.  Tool calls are printed
+//! as dim `[calling tool X]` lines, after which the spinner returns until the
+//! next turn starts streaming.  Styling and the spinner are only used when
+//! the output is a terminal.
+
+use std::io::{self, IsTerminal, Write};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use openintent_agent::{TextDeltaCallback, ToolStartCallback};
+
+/// Spinner animation frames.
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Delay between spinner frames.
+const SPINNER_INTERVAL: Duration = Duration::from_millis(80);
+
+/// Carriage return plus "erase entire line".
+const CLEAR_LINE: &str = "\r\x1b[2K";
+
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// Renders streamed agent output for one request.
+pub struct StreamPrinter<W> {
+    state: Mutex<PrinterState<W>>,
+    /// Whether to emit ANSI styling and the spinner.
+    styled: bool,
+}
+
+struct PrinterState<W> {
+    out: W,
+    /// Current spinner frame, or `None` while the spinner is hidden.
+    spinner: Option<usize>,
+    /// Text streamed since the last tool call.
+    text: String,
+    /// Whether the cursor is in the middle of a line of streamed text.
+    mid_line: bool,
+    finished: bool,
+}
+
+impl StreamPrinter<io::Stdout> {
+    /// A printer for stdout, styled when stdout is a terminal.
+    pub fn stdout() -> Arc<Self> {
+        Arc::new(Self::new(io::stdout(), io::stdout().is_terminal()))
+    }
+}
+
+impl<W: Write + Send + 'static> StreamPrinter<W> {
+    /// Create a printer writing to `out`.
+    pub fn new(out: W, styled: bool) -> Self {
+        Self {
+            state: Mutex::new(PrinterState {
+                out,
+                spinner: None,
+                text: String::new(),
+                mid_line: false,
+                finished: false,
+            }),
+            styled,
+        }
+    }
+
+    /// Show the spinner and keep it animated until [`finish`](Self::finish).
+    pub fn start(self: &Arc<Self>) {
+        self.show_spinner(&mut self.lock());
+        if !self.styled {
+            return;
+        }
+        let this = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(SPINNER_INTERVAL).await;
+                if !this.tick() {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Callbacks to install as `on_text_delta` and `on_tool_start`.
+    pub fn callbacks(self: &Arc<Self>) -> (TextDeltaCallback, ToolStartCallback) {
+        let on_delta = Arc::clone(self);
+        let on_tool = Arc::clone(self);
+        (
+            Arc::new(Mutex::new(move |delta: &str| on_delta.delta(delta))),
+            Arc::new(move |name: &str, _args: &serde_json::Value| on_tool.tool_start(name)),
+        )
+    }
+
+    /// Print a streamed text delta, clearing the spinner first.
+    pub fn delta(&self, delta: &str) {
+        if delta.is_empty() {
+            return;
+        }
+        let mut state = self.lock();
+        self.hide_spinner(&mut state);
+        state.text.push_str(delta);
+        state.mid_line = !delta.ends_with('\n');
+        let _ = state.out.write_all(delta.as_bytes());
+        let _ = state.out.flush();
+    }
+
+    /// Print a `[calling tool X]` line and bring the spinner back.
+    pub fn tool_start(&self, name: &str) {
+        let mut state = self.lock();
+        self.hide_spinner(&mut state);
+        self.end_line(&mut state);
+        state.text.clear();
+        let _ = if self.styled {
+            writeln!(state.out, "{DIM}  [calling tool {name}]{RESET}")
+        } else {
+            writeln!(state.out, "  [calling tool {name}]")
+        };
+        self.show_spinner(&mut state);
+    }
+
+    /// Stop the spinner, end any partial line, and return the text streamed
+    /// since the last tool call (empty if nothing was streamed).
+    pub fn finish(&self) -> String {
+        let mut state = self.lock();
+        state.finished = true;
+        self.hide_spinner(&mut state);
+        self.end_line(&mut state);
+        let _ = state.out.flush();
+        std::mem::take(&mut state.text)
+    }
+
+    /// Advance the spinner one frame.  Returns `false` once finished.
+    fn tick(&self) -> bool {
+        let mut state = self.lock();
+        if state.finished {
+            return false;
+        }
+        if let Some(frame) = state.spinner {
+            let next = (frame + 1) % SPINNER_FRAMES.len();
+            state.spinner = Some(next);
+            let _ = write!(
+                state.out,
+                "{CLEAR_LINE}  {} Thinking...",
+                SPINNER_FRAMES[next]
+            );
+            let _ = state.out.flush();
+        }
+        true
+    }
+
+    fn show_spinner(&self, state: &mut PrinterState<W>) {
+        if !self.styled || state.finished || state.spinner.is_some() {
+            return;
+        }
+        state.spinner = Some(0);
+        let _ = write!(state.out, "  {} Thinking...", SPINNER_FRAMES[0]);
+        let _ = state.out.flush();
+    }
+
+    fn hide_spinner(&self, state: &mut PrinterState<W>) {
+        if state.spinner.take().is_some() {
+            let _ = write!(state.out, "{CLEAR_LINE}");
+        }
+    }
+
+    fn end_line(&self, state: &mut PrinterState<W>) {
+        if state.mid_line {
+            let _ = writeln!(state.out);
+            state.mid_line = false;
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, PrinterState<W>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A cloneable in-memory writer so tests can inspect the output.
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Buffer {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).expect("output must be UTF-8")
+        }
+    }
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn first_token_clears_spinner() {
+        let buf = Buffer::default();
+        let printer = Arc::new(StreamPrinter::new(buf.clone(), true));
+
+        printer.start();
+        assert!(buf.contents().contains("Thinking..."));
+
+        printer.delta("Hel");
+        printer.delta("lo");
+        assert_eq!(printer.finish(), "Hello");
+
+        let out = buf.contents();
+        let after_spinner = out.rsplit(CLEAR_LINE).next().unwrap();
+        assert_eq!(after_spinner, "Hello\n");
+        assert!(!printer.tick());
+    }
+
+    #[test]
+    fn tool_start_prints_line_and_resets_text() {
+        let buf = Buffer::default();
+        let printer = Arc::new(StreamPrinter::new(buf.clone(), false));
+        let (on_delta, on_tool) = printer.callbacks();
+
+        (on_delta.lock().unwrap())("Let me check.");
+        on_tool("fs_read_file", &serde_json::json!({}));
+        (on_delta.lock().unwrap())("Done.");
+
+        assert_eq!(printer.finish(), "Done.");
+        assert_eq!(
+            buf.contents(),
+            "Let me check.\n  [calling tool fs_read_file]\nDone.\n"
+        );
+    }
+}
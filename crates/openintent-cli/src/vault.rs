@@ -0,0 +1,386 @@
+//! `openintent vault` — manage credentials in the encrypted vault.
+//!
+//! The vault lives at `data/vault.db` and its master key is kept in the
+//! platform keychain (created on first use).  Secrets are only ever read
+//! from stdin so they never show up in shell history or process listings.
+//!
+//! The vault also holds the key session messages are encrypted with when
+//! `encrypt_sessions` is enabled (see [`session_key`]).
+
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+
+use openintent_store::SESSION_KEY_LEN;
+use openintent_vault::store::CredentialSummary;
+use openintent_vault::{CredentialType, Vault, VaultError, crypto, platform_keychain};
+
+use crate::cli::VaultAction;
+use crate::helpers::init_tracing;
+
+/// Directory holding the vault database and file-based master key.
+const DATA_DIR: &str = "data";
+
+/// Vault database file name inside [`DATA_DIR`].
+const VAULT_FILE: &str = "vault.db";
+
+/// Placeholder printed instead of secret values.
+const REDACTED: &str = "********";
+
+/// Vault provider holding the session encryption key.
+pub const SESSION_KEY_PROVIDER: &str = "session_encryption";
+
+pub async fn cmd_vault(action: VaultAction) -> Result<()> {
+    init_tracing("warn");
+
+    let vault = open_vault(Path::new(DATA_DIR))?;
+    let stdin = std::io::stdin();
+    let stdout = std::io::stdout();
+    run(&vault, action, &mut stdin.lock(), &mut stdout.lock())
+}
+
+/// Open the vault in `data_dir`, creating the master key on first use.
+fn open_vault(data_dir: &Path) -> Result<Vault> {
+    std::fs::create_dir_all(data_dir).context("failed to create data directory")?;
+
+    let keychain = platform_keychain(data_dir);
+    let has_key = keychain
+        .has_master_key()
+        .context("failed to query keychain")?;
+    let master_key = if has_key {
+        keychain
+            .get_master_key()
+            .context("failed to read vault master key")?
+    } else {
+        let key = crypto::random_bytes(crypto::KEY_LEN)?;
+        keychain
+            .set_master_key(&key)
+            .context("failed to store vault master key")?;
+        key
+    };
+
+    Vault::open(data_dir.join(VAULT_FILE), &master_key).context("failed to open vault")
+}
+
+/// Open the vault in `data_dir` if it and its master key already exist.
+///
+/// Used for read-only credential checks, so it never creates anything.
+pub fn open_existing_vault(data_dir: &Path) -> Option<Vault> {
+    let path = data_dir.join(VAULT_FILE);
+    if !path.exists() {
+        return None;
+    }
+    let keychain = platform_keychain(data_dir);
+    let master_key = match keychain.get_master_key() {
+        Ok(key) => key,
+        Err(e) => {
+            tracing::warn!(error = %e, "vault master key unavailable; skipping vault");
+            return None;
+        }
+    };
+    Vault::open(path, &master_key)
+        .inspect_err(|e| tracing::warn!(error = %e, "failed to open vault"))
+        .ok()
+}
+
+/// The key session message content is encrypted with, read from the vault
+/// in `data_dir`.  A random key is generated and stored on first use.
+pub fn session_key(data_dir: &Path) -> Result<Vec<u8>> {
+    let vault = open_vault(data_dir)?;
+    load_or_create_session_key(&vault)
+}
+
+fn load_or_create_session_key(vault: &Vault) -> Result<Vec<u8>> {
+    match vault.get_credential(SESSION_KEY_PROVIDER) {
+        Ok(credential) => {
+            let encoded = credential
+                .data
+                .get("key")
+                .and_then(serde_json::Value::as_str)
+                .with_context(|| format!("vault credential '{SESSION_KEY_PROVIDER}' has no key"))?;
+            BASE64
+                .decode(encoded)
+                .context("session key in the vault is not valid base64")
+        }
+        Err(VaultError::CredentialNotFound { .. }) => {
+            let key = crypto::random_bytes(SESSION_KEY_LEN)?;
+            let data = serde_json::json!({ "key": BASE64.encode(&key) });
+            vault
+                .store_credential(
+                    SESSION_KEY_PROVIDER,
+                    CredentialType::ApiKey,
+                    &data,
+                    None,
+                    Some("session encryption key"),
+                    None,
+                )
+                .context("failed to store session key")?;
+            Ok(key)
+        }
+        Err(e) => Err(e).context("failed to read session key"),
+    }
+}
+
+/// Execute a vault action against `vault`, reading secrets from `input`.
+fn run(
+    vault: &Vault,
+    action: VaultAction,
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+) -> Result<()> {
+    match action {
+        VaultAction::List => {
+            let creds = vault
+                .list_credentials()
+                .context("failed to list credentials")?;
+            if creds.is_empty() {
+                writeln!(out, "  No credentials stored.")?;
+                writeln!(out)?;
+                writeln!(
+                    out,
+                    "  Store one with:  openintent vault set <provider> <type> < secret.txt"
+                )?;
+                return Ok(());
+            }
+
+            writeln!(out)?;
+            writeln!(out, "  Stored credentials ({}):", creds.len())?;
+            writeln!(out)?;
+            for cred in &creds {
+                writeln!(
+                    out,
+                    "  {:<24} {:<10} {}",
+                    cred.provider,
+                    cred.credential_type,
+                    summary_details(cred)
+                )?;
+            }
+            writeln!(out)?;
+        }
+
+        VaultAction::Set {
+            provider,
+            credential_type,
+            label,
+        } => {
+            let Some(kind) = CredentialType::parse(&credential_type) else {
+                bail!(
+                    "unknown credential type '{credential_type}' \
+                     (expected api_key, oauth, cookie, or keychain)"
+                );
+            };
+
+            let mut secret = String::new();
+            input
+                .read_to_string(&mut secret)
+                .context("failed to read secret from stdin")?;
+            let secret = secret.trim_end_matches(['\r', '\n']);
+            if secret.is_empty() {
+                bail!("no secret provided on stdin");
+            }
+            let data = secret_data(kind, secret);
+
+            let exists = vault
+                .list_credentials()
+                .context("failed to list credentials")?
+                .iter()
+                .any(|c| c.provider == provider);
+            if exists {
+                vault
+                    .delete_credential(&provider)
+                    .context("failed to replace credential")?;
+            }
+            vault
+                .store_credential(&provider, kind, &data, None, label.as_deref(), None)
+                .context("failed to store credential")?;
+
+            let verb = if exists { "Updated" } else { "Stored" };
+            writeln!(out, "  {verb} {kind} credential for: {provider}")?;
+        }
+
+        VaultAction::Get { provider, reveal } => {
+            let cred = vault
+                .get_credential(&provider)
+                .with_context(|| format!("failed to read credential '{provider}'"))?;
+
+            writeln!(out)?;
+            writeln!(out, "  Provider: {}", cred.provider)?;
+            writeln!(out, "  Type: {}", cred.credential_type)?;
+            if let Some(ref label) = cred.user_label {
+                writeln!(out, "  Label: {label}")?;
+            }
+            if let Some(ref scopes) = cred.scopes {
+                writeln!(out, "  Scopes: {}", scopes.join(", "))?;
+            }
+            if let Some(expires) = cred.expires_at {
+                writeln!(out, "  Expires: {}", expires.format("%Y-%m-%d %H:%M UTC"))?;
+            }
+            writeln!(
+                out,
+                "  Created: {}",
+                cred.created_at.format("%Y-%m-%d %H:%M UTC")
+            )?;
+            writeln!(
+                out,
+                "  Updated: {}",
+                cred.updated_at.format("%Y-%m-%d %H:%M UTC")
+            )?;
+
+            if reveal {
+                writeln!(out, "  Data: {}", cred.data)?;
+            } else {
+                let fields = match cred.data.as_object() {
+                    Some(map) => map
+                        .keys()
+                        .map(|k| format!("{k}={REDACTED}"))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    None => REDACTED.to_string(),
+                };
+                writeln!(out, "  Data: {fields}")?;
+                writeln!(out, "  (use --reveal to print the secret)")?;
+            }
+            writeln!(out)?;
+        }
+
+        VaultAction::Delete { provider } => {
+            vault
+                .delete_credential(&provider)
+                .with_context(|| format!("failed to delete credential '{provider}'"))?;
+            writeln!(out, "  Deleted credential: {provider}")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Build the JSON payload stored for a secret read from stdin.
+///
+/// A JSON object is stored as-is (e.g. a full OAuth token set); anything else
+/// is wrapped in the field conventionally used for `kind`.
+fn secret_data(kind: CredentialType, secret: &str) -> serde_json::Value {
+    if let Ok(value @ serde_json::Value::Object(_)) = serde_json::from_str(secret) {
+        return value;
+    }
+    let field = match kind {
+        CredentialType::ApiKey => "api_key",
+        CredentialType::OAuth => "access_token",
+        CredentialType::Cookie => "cookie",
+        CredentialType::Keychain => "reference",
+    };
+    serde_json::json!({ field: secret })
+}
+
+/// Render the non-secret metadata of a credential for `vault list`.
+fn summary_details(cred: &CredentialSummary) -> String {
+    let mut parts = Vec::new();
+    if let Some(ref label) = cred.user_label {
+        parts.push(format!("label: {label}"));
+    }
+    if let Some(expires) = cred.expires_at {
+        parts.push(format!("expires: {}", expires.format("%Y-%m-%d")));
+    }
+    parts.push(format!("updated: {}", cred.updated_at.format("%Y-%m-%d")));
+    parts.join("  ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exec(vault: &Vault, action: VaultAction, stdin: &str) -> Result<String> {
+        let mut out = Vec::new();
+        run(vault, action, &mut stdin.as_bytes(), &mut out)?;
+        Ok(String::from_utf8(out).expect("output must be UTF-8"))
+    }
+
+    #[test]
+    fn set_then_list_and_get_redacts_secret() {
+        let vault = Vault::open_in_memory(&[7u8; 32]).expect("in-memory vault must open");
+
+        let set = VaultAction::Set {
+            provider: "github".into(),
+            credential_type: "api_key".into(),
+            label: Some("work".into()),
+        };
+        let out = exec(&vault, set, "ghp_secret123\n").expect("set must succeed");
+        assert!(out.contains("Stored api_key credential for: github"));
+
+        let list = exec(&vault, VaultAction::List, "").expect("list must succeed");
+        assert!(list.contains("github"), "unexpected list output: {list}");
+        assert!(
+            list.contains("label: work"),
+            "unexpected list output: {list}"
+        );
+        assert!(!list.contains("ghp_secret123"));
+
+        let get = VaultAction::Get {
+            provider: "github".into(),
+            reveal: false,
+        };
+        let out = exec(&vault, get, "").expect("get must succeed");
+        assert!(out.contains("api_key=********"), "unexpected output: {out}");
+        assert!(!out.contains("ghp_secret123"));
+
+        let reveal = VaultAction::Get {
+            provider: "github".into(),
+            reveal: true,
+        };
+        let out = exec(&vault, reveal, "").expect("get must succeed");
+        assert!(out.contains("ghp_secret123"));
+
+        exec(
+            &vault,
+            VaultAction::Delete {
+                provider: "github".into(),
+            },
+            "",
+        )
+        .expect("delete must succeed");
+        let list = exec(&vault, VaultAction::List, "").expect("list must succeed");
+        assert!(list.contains("No credentials stored"));
+    }
+
+    #[test]
+    fn session_key_is_created_once_and_reused() {
+        let vault = Vault::open_in_memory(&[7u8; 32]).expect("in-memory vault must open");
+
+        let key = load_or_create_session_key(&vault).expect("key must be created");
+        assert_eq!(key.len(), SESSION_KEY_LEN);
+        let again = load_or_create_session_key(&vault).expect("key must be read back");
+        assert_eq!(again, key);
+    }
+
+    #[test]
+    fn set_rejects_empty_secret_and_unknown_type() {
+        let vault = Vault::open_in_memory(&[7u8; 32]).expect("in-memory vault must open");
+
+        let empty = VaultAction::Set {
+            provider: "github".into(),
+            credential_type: "api_key".into(),
+            label: None,
+        };
+        assert!(exec(&vault, empty, "\n").is_err());
+
+        let bad_type = VaultAction::Set {
+            provider: "github".into(),
+            credential_type: "password".into(),
+            label: None,
+        };
+        assert!(exec(&vault, bad_type, "secret").is_err());
+        assert!(vault.list_credentials().unwrap().is_empty());
+    }
+
+    #[test]
+    fn json_secret_is_stored_verbatim() {
+        let data = secret_data(
+            CredentialType::OAuth,
+            r#"{"access_token":"a","refresh_token":"r"}"#,
+        );
+        assert_eq!(data["refresh_token"], "r");
+        assert_eq!(secret_data(CredentialType::ApiKey, "k")["api_key"], "k");
+    }
+}
@@ -0,0 +1,138 @@
+//! Subcommand: `openintent run --watch <path> --prompt ...`
+//!
+//! Runs the prompt once, then again every time the watched file or
+//! directory changes.  Bursts of file-system events (an editor saving a
+//! file usually emits several) are debounced into a single run, and all
+//! runs share one [`AgentContext`], so the agent sees its earlier answers.
+//!
+//! Changes made while the agent is running — including its own writes to
+//! the watched path — are folded into the run that just finished instead
+//! of triggering another one.
+//!
+//! [`AgentContext`]: openintent_agent::AgentContext
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use openintent_agent::{Message, react_loop};
+use tokio::sync::mpsc;
+
+use crate::adapters::AdapterSelection;
+use crate::helpers::init_tracing_stderr;
+use crate::repl::{print_response, prompt_context};
+
+/// Quiet period after the last change before the agent runs again.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Run `prompt` now and again whenever `path` changes.
+pub async fn cmd_watch(
+    path: PathBuf,
+    prompt: String,
+    json: bool,
+    selection: AdapterSelection,
+    user: Option<String>,
+) -> Result<()> {
+    init_tracing_stderr("warn");
+    let (_watcher, changes) = watch_path(&path)?;
+    let mut changes = Debouncer::new(changes, DEBOUNCE);
+    let mut ctx = prompt_context(&selection, user.as_deref()).await?;
+
+    eprintln!("  Watching {} (Ctrl+C to stop)", path.display());
+    loop {
+        ctx.messages.push(Message::user(&prompt));
+        match react_loop(&mut ctx).await {
+            Ok(response) => print_response(&response, json)?,
+            Err(e) => eprintln!("  Error: {e}"),
+        }
+        changes.discard_pending();
+
+        tokio::select! {
+            changed = changes.changed() => {
+                if !changed {
+                    anyhow::bail!("stopped watching {}", path.display());
+                }
+            }
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
+}
+
+/// Watch `path` (recursively, for a directory) and send a unit on every
+/// change.  The watcher stops when it is dropped.
+fn watch_path(path: &Path) -> Result<(RecommendedWatcher, mpsc::UnboundedReceiver<()>)> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| match res {
+        Ok(event) if !event.kind.is_access() => {
+            let _ = tx.send(());
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!(error = %e, "file watcher error"),
+    })
+    .context("failed to create file watcher")?;
+
+    let mode = if path.is_dir() {
+        RecursiveMode::Recursive
+    } else {
+        RecursiveMode::NonRecursive
+    };
+    watcher
+        .watch(path, mode)
+        .with_context(|| format!("failed to watch {}", path.display()))?;
+    Ok((watcher, rx))
+}
+
+/// Collapses bursts of change notifications into one.
+struct Debouncer {
+    rx: mpsc::UnboundedReceiver<()>,
+    quiet: Duration,
+}
+
+impl Debouncer {
+    fn new(rx: mpsc::UnboundedReceiver<()>, quiet: Duration) -> Self {
+        Self { rx, quiet }
+    }
+
+    /// Wait for a change followed by `quiet` without further changes.
+    /// Returns `false` once no more changes can arrive.
+    async fn changed(&mut self) -> bool {
+        if self.rx.recv().await.is_none() {
+            return false;
+        }
+        // Any burst still in flight ends with the sender gone or a quiet
+        // period; either way, the change already seen counts.
+        while let Ok(Some(())) = tokio::time::timeout(self.quiet, self.rx.recv()).await {}
+        true
+    }
+
+    /// Drop changes that are already queued.
+    fn discard_pending(&mut self) {
+        while self.rx.try_recv().is_ok() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn burst_of_changes_triggers_one_run() {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut debouncer = Debouncer::new(rx, Duration::from_millis(100));
+
+        // An editor save: several events in quick succession.
+        tokio::spawn(async move {
+            for _ in 0..5 {
+                tx.send(()).unwrap();
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        });
+
+        let mut runs = 0;
+        while debouncer.changed().await {
+            runs += 1;
+        }
+        assert_eq!(runs, 1);
+    }
+}
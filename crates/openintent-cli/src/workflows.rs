@@ -0,0 +1,210 @@
+//! `openintent workflows` — run and inspect stored workflows.
+//!
+//! Workflows are read from the [`WorkflowStore`] in `data/openintent.db` and
+//! executed by a [`WorkflowEngine`] that checkpoints every run to the same
+//! store, so `workflows status` can report on runs started elsewhere.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use uuid::Uuid;
+
+use openintent_intent::{StepResult, Workflow, WorkflowEngine, WorkflowStatus};
+use openintent_store::{Database, StoredWorkflow, WorkflowStore};
+
+use crate::adapters::{AdapterSelection, init_adapters};
+use crate::cli::WorkflowAction;
+use crate::helpers::init_tracing;
+
+/// Maximum characters of step output printed per step.
+const OUTPUT_PREVIEW_CHARS: usize = 200;
+
+pub async fn cmd_workflows(action: WorkflowAction) -> Result<()> {
+    init_tracing("warn");
+
+    let db_path = Path::new("data").join("openintent.db");
+    if !db_path.exists() {
+        eprintln!("  Error: Database not found. Run `openintent setup` first.");
+        std::process::exit(1);
+    }
+
+    let db = Database::open_and_migrate(db_path)
+        .await
+        .context("failed to open database")?;
+    let store = WorkflowStore::new(db.clone());
+
+    match action {
+        WorkflowAction::List => {
+            let workflows = store
+                .list(100, 0)
+                .await
+                .context("failed to list workflows")?;
+            if workflows.is_empty() {
+                println!("  No workflows stored.");
+                return Ok(());
+            }
+
+            println!();
+            println!("  Workflows ({}):", workflows.len());
+            println!();
+            for wf in &workflows {
+                let steps = wf.steps.as_array().map_or(0, Vec::len);
+                let state = if wf.enabled { "enabled" } else { "disabled" };
+                println!(
+                    "  {:<24} steps:{:<3} [{}]  {}",
+                    wf.name,
+                    steps,
+                    state,
+                    wf.description.as_deref().unwrap_or("")
+                );
+            }
+            println!();
+        }
+
+        WorkflowAction::Run { name } => {
+            let stored = find_workflow(&store, &name).await?;
+            let mut workflow =
+                Workflow::from_stored(&stored).context("failed to load workflow definition")?;
+
+            let cwd = std::env::current_dir().context("failed to get current directory")?;
+            let adapters = init_adapters(cwd, db, false, &AdapterSelection::resolve(None)?).await?;
+
+            let total = workflow.steps.len();
+            let engine = WorkflowEngine::new(adapters.tool_adapters)
+                .with_store(store)
+                .with_step_callback(Arc::new(move |result: &StepResult| {
+                    print_step(result, total);
+                }));
+
+            let run_id = Uuid::now_v7();
+            println!("  Running workflow: {} (run {run_id})", workflow.name);
+            println!();
+            let result = engine
+                .run(&mut workflow, run_id)
+                .await
+                .context("workflow execution failed")?;
+
+            println!();
+            println!("  Workflow {}.", status_label(result.status));
+            if !result.success {
+                std::process::exit(1);
+            }
+        }
+
+        WorkflowAction::Show { name } => {
+            let stored = find_workflow(&store, &name).await?;
+            let workflow =
+                Workflow::from_stored(&stored).context("failed to load workflow definition")?;
+
+            println!();
+            println!("  Workflow: {}", workflow.name);
+            println!("  ID: {}", workflow.id);
+            if let Some(ref desc) = workflow.description {
+                println!("  Description: {desc}");
+            }
+            println!("  Trigger: {:?}", workflow.trigger);
+            println!("  Enabled: {}", workflow.enabled);
+            println!();
+            println!("  Steps ({}):", workflow.steps.len());
+            for (i, step) in workflow.steps.iter().enumerate() {
+                println!(
+                    "    {}. {}  ({}.{})",
+                    i + 1,
+                    step.action,
+                    step.adapter,
+                    step.tool
+                );
+                if let Some(ref condition) = step.condition {
+                    println!("       if: {condition}");
+                }
+            }
+            println!();
+        }
+
+        WorkflowAction::Status { run_id } => {
+            let run_id = Uuid::parse_str(&run_id).context("invalid run ID")?;
+            let engine = WorkflowEngine::default().with_store(store);
+            let status = engine
+                .status(run_id)
+                .await
+                .context("failed to read run status")?;
+
+            println!();
+            println!("  Run: {}", status.run_id);
+            println!(
+                "  Workflow: {} ({})",
+                status.workflow_name, status.workflow_id
+            );
+            println!("  Status: {}", status_label(status.status));
+            println!(
+                "  Progress: {}/{} steps",
+                status.next_step.min(status.total_steps),
+                status.total_steps
+            );
+            if !status.step_results.is_empty() {
+                println!();
+                for result in &status.step_results {
+                    print_step(result, status.total_steps);
+                }
+            }
+            println!();
+        }
+    }
+
+    Ok(())
+}
+
+/// Look up a workflow by name, falling back to its ID.
+async fn find_workflow(store: &WorkflowStore, name: &str) -> Result<StoredWorkflow> {
+    if let Some(wf) = store
+        .get_by_name(name)
+        .await
+        .context("failed to look up workflow")?
+    {
+        return Ok(wf);
+    }
+    match store
+        .get(name)
+        .await
+        .context("failed to look up workflow")?
+    {
+        Some(wf) => Ok(wf),
+        None => {
+            eprintln!("  Error: Workflow '{name}' not found.");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Print one step result, e.g. `[1/2] fs_read_file  completed  {...}`.
+fn print_step(result: &StepResult, total: usize) {
+    let mut output = result.output.to_string();
+    if output.chars().count() > OUTPUT_PREVIEW_CHARS {
+        output = output
+            .chars()
+            .take(OUTPUT_PREVIEW_CHARS)
+            .collect::<String>()
+            + "...";
+    }
+    println!(
+        "  [{}/{}] {:<24} {:<10} {}",
+        result.step_index + 1,
+        total,
+        result.tool,
+        status_label(result.status),
+        output
+    );
+}
+
+fn status_label(status: WorkflowStatus) -> &'static str {
+    match status {
+        WorkflowStatus::Idle => "idle",
+        WorkflowStatus::Running => "running",
+        WorkflowStatus::Completed => "completed",
+        WorkflowStatus::Failed => "failed",
+        WorkflowStatus::Cancelled => "cancelled",
+        WorkflowStatus::Skipped => "skipped",
+        WorkflowStatus::Paused => "paused",
+    }
+}
@@ -0,0 +1,16 @@
+;; Source of echo.wasm, a minimal sandbox plugin used by the CLI tests.
+;; Rebuild with: wat2wasm echo.wat -o echo.wasm
+(module
+  (import "env" "host_set_result" (func $set_result (param i32 i32)))
+  (memory (export "memory") 1)
+  (data (i32.const 1024) "{\"name\":\"echo\",\"version\":\"0.1.0\",\"description\":\"Echoes its input back\",\"tools\":[{\"name\":\"echo\",\"description\":\"Return the parameters unchanged\",\"parameters_schema\":{\"type\":\"object\"},\"capabilities\":[\"network\"]}]}")
+
+  ;; Report plugin metadata as JSON through host_set_result.
+  (func (export "get_plugin_info") (result i32)
+    (call $set_result (i32.const 1024) (i32.const 210))
+    (i32.const 0))
+
+  ;; Echo the params JSON the host wrote at params_ptr.
+  (func (export "execute_tool") (param i32 i32 i32 i32) (result i32)
+    (call $set_result (local.get 2) (local.get 3))
+    (i32.const 0)))
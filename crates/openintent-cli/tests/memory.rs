@@ -0,0 +1,73 @@
+//! Integration tests for `openintent memory`.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+use openintent_store::{Database, Memory, MemoryCategory, NewMemory, SemanticMemory};
+
+/// Run `openintent memory <args>` inside `dir`.
+fn memory(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_openintent"))
+        .current_dir(dir)
+        .env("OPENINTENT_SKILLS_DIR", dir.join("skills"))
+        .arg("memory")
+        .args(args)
+        .output()
+        .expect("failed to run openintent")
+}
+
+#[tokio::test]
+async fn export_writes_memories_as_jsonl() {
+    let dir = tempfile::tempdir().expect("tempdir creation must succeed in tests");
+    std::fs::create_dir(dir.path().join("data")).expect("mkdir must succeed");
+    let db = Database::open_and_migrate(dir.path().join("data/openintent.db"))
+        .await
+        .expect("database must open");
+    let semantic = SemanticMemory::new(db);
+    let stored = [
+        (MemoryCategory::Preference, "prefers concise answers", 0.9),
+        (
+            MemoryCategory::Knowledge,
+            "the deploy script lives in ops/",
+            0.4,
+        ),
+    ];
+    for (category, content, importance) in stored {
+        semantic
+            .insert(NewMemory {
+                category,
+                content: content.into(),
+                embedding: None,
+                importance,
+                ttl: None,
+            })
+            .await
+            .expect("memory must be stored");
+    }
+
+    let export = memory(dir.path(), &["export", "memories.jsonl"]);
+    assert!(export.status.success(), "export failed: {export:?}");
+    assert!(String::from_utf8_lossy(&export.stdout).contains("Exported 2 memories"));
+
+    let jsonl =
+        std::fs::read_to_string(dir.path().join("memories.jsonl")).expect("export file must exist");
+    let exported: Vec<Memory> = jsonl
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("each line must be a memory"))
+        .collect();
+    assert_eq!(exported.len(), 2);
+    for (category, content, importance) in stored {
+        let m = exported
+            .iter()
+            .find(|m| m.content == content)
+            .unwrap_or_else(|| panic!("missing exported memory: {content}"));
+        assert_eq!(m.category, category);
+        assert_eq!(m.importance, importance);
+    }
+
+    let search = memory(dir.path(), &["search", "deploy"]);
+    assert!(search.status.success(), "search failed: {search:?}");
+    let out = String::from_utf8_lossy(&search.stdout);
+    assert!(out.contains("ops/"), "unexpected output: {out}");
+    assert!(!out.contains("concise"), "unexpected output: {out}");
+}
@@ -0,0 +1,62 @@
+//! Integration tests for `openintent plugins`.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+/// Run `openintent plugins <args>` inside `dir`.
+fn plugins(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_openintent"))
+        .current_dir(dir)
+        .arg("plugins")
+        .args(args)
+        .output()
+        .expect("failed to run openintent")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn install_then_list_shows_tools_and_capabilities() {
+    let dir = tempfile::tempdir().expect("tempdir creation must succeed in tests");
+    let wasm = fixture("echo.wasm");
+
+    let install = plugins(dir.path(), &["install", wasm.to_str().unwrap()]);
+    assert!(install.status.success(), "install failed: {install:?}");
+    assert!(dir.path().join("plugins/echo.wasm").exists());
+
+    let list = plugins(dir.path(), &["list"]);
+    assert!(list.status.success());
+    let out = stdout(&list);
+    assert!(out.contains("echo"), "unexpected list output: {out}");
+    assert!(out.contains("v0.1.0"), "unexpected list output: {out}");
+    assert!(
+        out.contains("requires: network"),
+        "unexpected list output: {out}"
+    );
+
+    let again = plugins(dir.path(), &["install", wasm.to_str().unwrap()]);
+    assert!(!again.status.success());
+
+    let remove = plugins(dir.path(), &["remove", "echo"]);
+    assert!(remove.status.success());
+    assert!(stdout(&plugins(dir.path(), &["list"])).contains("No plugins installed"));
+}
+
+#[test]
+fn install_rejects_invalid_module() {
+    let dir = tempfile::tempdir().expect("tempdir creation must succeed in tests");
+    let bad = dir.path().join("bad.wasm");
+    std::fs::write(&bad, b"not wasm").expect("write must succeed");
+
+    let install = plugins(dir.path(), &["install", bad.to_str().unwrap()]);
+    assert!(!install.status.success());
+    assert!(!dir.path().join("plugins").exists());
+}
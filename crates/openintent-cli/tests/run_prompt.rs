@@ -0,0 +1,112 @@
+//! Integration tests for `openintent run --prompt`.
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Serve every request with `status` and `body`, as an OpenAI-compatible
+/// endpoint would.  Returns the base URL.
+fn mock_llm(status: &'static str, body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind must succeed");
+    let base = format!("http://{}", listener.local_addr().expect("local addr"));
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { return };
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = stream.read(&mut buf).unwrap_or(0);
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request);
+                let complete = text.split_once("\r\n\r\n").is_some_and(|(head, rest)| {
+                    let len = head
+                        .lines()
+                        .find_map(|l| {
+                            l.to_lowercase()
+                                .strip_prefix("content-length:")
+                                .and_then(|v| v.trim().parse::<usize>().ok())
+                        })
+                        .unwrap_or(0);
+                    rest.len() >= len
+                });
+                if n == 0 || complete {
+                    break;
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 {status}\r\nContent-Type: text/event-stream\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+    base
+}
+
+/// Run `openintent run <args>` in `dir` against the LLM at `base_url`.
+fn run(dir: &Path, base_url: &str, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_openintent"))
+        .current_dir(dir)
+        .env("OPENINTENT_PROVIDER", "openai")
+        .env("OPENAI_API_KEY", "test-key")
+        .env("OPENINTENT_API_BASE_URL", base_url)
+        .env("OPENINTENT_MODEL", "mock-model")
+        .env("OPENINTENT_SKILLS_DIR", dir.join("skills"))
+        .env_remove("GITHUB_TOKEN")
+        .env_remove("TELEGRAM_BOT_TOKEN")
+        .env_remove("DISCORD_BOT_TOKEN")
+        .arg("run")
+        .args(args)
+        .output()
+        .expect("failed to run openintent")
+}
+
+const SUMMARY_STREAM: &str = concat!(
+    "data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"README describes OpenIntentOS.\"}}]}\n\n",
+    "data: {\"choices\":[{\"index\":0,\"delta\":{}}],\"usage\":{\"prompt_tokens\":12,\"completion_tokens\":5}}\n\n",
+    "data: [DONE]\n\n",
+);
+
+#[test]
+fn prompt_prints_final_response_and_exits_zero() {
+    let dir = tempfile::tempdir().expect("tempdir creation must succeed in tests");
+    let base = mock_llm("200 OK", SUMMARY_STREAM);
+
+    let out = run(dir.path(), &base, &["--prompt", "summarize README.md"]);
+    assert!(out.status.success(), "run failed: {out:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout).trim(),
+        "README describes OpenIntentOS."
+    );
+}
+
+#[test]
+fn prompt_json_emits_agent_response() {
+    let dir = tempfile::tempdir().expect("tempdir creation must succeed in tests");
+    let base = mock_llm("200 OK", SUMMARY_STREAM);
+
+    let out = run(
+        dir.path(),
+        &base,
+        &["--prompt", "summarize README.md", "--json"],
+    );
+    assert!(out.status.success(), "run failed: {out:?}");
+    let response: serde_json::Value =
+        serde_json::from_slice(&out.stdout).expect("stdout must be JSON");
+    assert_eq!(response["text"], "README describes OpenIntentOS.");
+    assert_eq!(response["turns_used"], 1);
+    assert_eq!(response["input_tokens"], 12);
+    assert_eq!(response["output_tokens"], 5);
+}
+
+#[test]
+fn prompt_exits_non_zero_when_the_agent_fails() {
+    let dir = tempfile::tempdir().expect("tempdir creation must succeed in tests");
+    let base = mock_llm("500 Internal Server Error", "upstream down");
+
+    let out = run(dir.path(), &base, &["--prompt", "summarize README.md"]);
+    assert_eq!(out.status.code(), Some(1));
+    assert!(out.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("Error"));
+}
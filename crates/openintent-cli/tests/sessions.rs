@@ -0,0 +1,120 @@
+//! Integration tests for `openintent sessions`.
+
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+use openintent_store::{Database, SessionStore};
+use serde_json::Value;
+
+/// Run `openintent sessions <args>` inside `dir`.
+fn sessions(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_openintent"))
+        .current_dir(dir)
+        .env("OPENINTENT_SKILLS_DIR", dir.join("skills"))
+        .arg("sessions")
+        .args(args)
+        .output()
+        .expect("failed to run openintent")
+}
+
+#[tokio::test]
+async fn list_json_emits_parseable_sessions() {
+    let dir = tempfile::tempdir().expect("tempdir creation must succeed in tests");
+    std::fs::create_dir(dir.path().join("data")).expect("mkdir must succeed");
+    let db = Database::open_and_migrate(dir.path().join("data/openintent.db"))
+        .await
+        .expect("database must open");
+    let store = SessionStore::new(db);
+    let session = store
+        .create("weekly-report", "claude-sonnet-4")
+        .await
+        .expect("session must be created");
+    store
+        .append_message(&session.id, "user", "Summarize this week", None, None)
+        .await
+        .expect("message must be stored");
+
+    let out = sessions(dir.path(), &["list", "--json"]);
+    assert!(out.status.success(), "list failed: {out:?}");
+
+    let listed: Value = serde_json::from_slice(&out.stdout).expect("stdout must be valid JSON");
+    let listed = listed.as_array().expect("output must be a JSON array");
+    assert_eq!(listed.len(), 1);
+    let row = &listed[0];
+    assert_eq!(row["id"], session.id.as_str());
+    assert_eq!(row["name"], "weekly-report");
+    assert_eq!(row["model"], "claude-sonnet-4");
+    assert_eq!(row["message_count"], 1);
+
+    let created = row["created_at"]
+        .as_str()
+        .expect("timestamp must be a string");
+    let parsed = chrono::DateTime::parse_from_rfc3339(created).expect("timestamp must be ISO-8601");
+    assert_eq!(parsed.timestamp(), session.created_at);
+}
+
+#[tokio::test]
+async fn export_then_import_round_trips_messages() {
+    let dir = tempfile::tempdir().expect("tempdir creation must succeed in tests");
+    std::fs::create_dir(dir.path().join("data")).expect("mkdir must succeed");
+    let db = Database::open_and_migrate(dir.path().join("data/openintent.db"))
+        .await
+        .expect("database must open");
+    let store = SessionStore::new(db);
+    let original = store
+        .create("trip-planning", "gpt-4o")
+        .await
+        .expect("session must be created");
+    for (role, content) in [
+        ("user", "Plan three days in Lisbon"),
+        ("assistant", "Day 1: Alfama and the castle."),
+        ("user", "Add a food tour"),
+    ] {
+        store
+            .append_message(&original.id, role, content, None, None)
+            .await
+            .expect("message must be stored");
+    }
+
+    let export = sessions(dir.path(), &["export", "trip-planning", "trip.json"]);
+    assert!(export.status.success(), "export failed: {export:?}");
+
+    // The original still exists, so import asks for a new name on stdin.
+    let mut child = Command::new(env!("CARGO_BIN_EXE_openintent"))
+        .current_dir(dir.path())
+        .env("OPENINTENT_SKILLS_DIR", dir.path().join("skills"))
+        .args(["sessions", "import", "trip.json"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to run openintent");
+    child
+        .stdin
+        .take()
+        .expect("stdin must be piped")
+        .write_all(b"trip-planning-copy\n")
+        .expect("stdin write must succeed");
+    let import = child.wait_with_output().expect("import must finish");
+    assert!(import.status.success(), "import failed: {import:?}");
+    assert!(String::from_utf8_lossy(&import.stdout).contains("already exists"));
+
+    let copy = store
+        .find_by_name("trip-planning-copy")
+        .await
+        .expect("lookup must succeed")
+        .expect("imported session must exist");
+    assert_ne!(copy.id, original.id);
+    assert_eq!(copy.created_at, original.created_at);
+
+    let expected = store.get_messages(&original.id, None).await.unwrap();
+    let imported = store.get_messages(&copy.id, None).await.unwrap();
+    assert_eq!(imported.len(), 3);
+    for (a, b) in expected.iter().zip(&imported) {
+        assert_eq!(
+            (&a.role, &a.content, a.created_at),
+            (&b.role, &b.content, b.created_at)
+        );
+    }
+}
@@ -0,0 +1,55 @@
+//! Integration tests for `openintent skills`.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Run `openintent skills <args>` against the skills in `skills_dir`.
+fn skills(skills_dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_openintent"))
+        .current_dir(skills_dir)
+        .env("OPENINTENT_SKILLS_DIR", skills_dir)
+        .arg("skills")
+        .args(args)
+        .output()
+        .expect("failed to run openintent")
+}
+
+/// Copy the fixture skill into a fresh skills directory.
+fn skills_dir_with_fixture() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().expect("tempdir creation must succeed in tests");
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/skills/greeter");
+    std::fs::create_dir(dir.path().join("greeter")).expect("mkdir must succeed");
+    std::fs::copy(
+        fixture.join("SKILL.md"),
+        dir.path().join("greeter/SKILL.md"),
+    )
+    .expect("copy must succeed");
+    dir
+}
+
+#[test]
+fn list_shows_local_skill() {
+    let dir = skills_dir_with_fixture();
+
+    let list = skills(dir.path(), &["list"]);
+    assert!(list.status.success());
+    let out = String::from_utf8_lossy(&list.stdout);
+    assert!(out.contains("greeter"), "unexpected list output: {out}");
+    assert!(out.contains("v1.0.0"), "unexpected list output: {out}");
+    assert!(out.contains("[ready]"), "unexpected list output: {out}");
+}
+
+#[test]
+fn disable_and_enable_change_listed_state() {
+    let dir = skills_dir_with_fixture();
+
+    assert!(skills(dir.path(), &["disable", "greeter"]).status.success());
+    let out = String::from_utf8_lossy(&skills(dir.path(), &["list"]).stdout).into_owned();
+    assert!(out.contains("[disabled]"), "unexpected list output: {out}");
+
+    assert!(skills(dir.path(), &["enable", "greeter"]).status.success());
+    let out = String::from_utf8_lossy(&skills(dir.path(), &["list"]).stdout).into_owned();
+    assert!(out.contains("[ready]"), "unexpected list output: {out}");
+
+    assert!(!skills(dir.path(), &["disable", "ghost"]).status.success());
+}
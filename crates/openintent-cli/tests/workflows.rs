@@ -0,0 +1,95 @@
+//! Integration tests for `openintent workflows`.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+use openintent_store::{Database, WorkflowStore};
+
+/// Run `openintent workflows <args>` inside `dir`.
+fn workflows(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_openintent"))
+        .current_dir(dir)
+        .env("OPENINTENT_SKILLS_DIR", dir.join("skills"))
+        .env_remove("GITHUB_TOKEN")
+        .env_remove("FEISHU_APP_ID")
+        .env_remove("FEISHU_APP_SECRET")
+        .arg("workflows")
+        .args(args)
+        .output()
+        .expect("failed to run openintent")
+}
+
+/// Store the two-step fixture workflow in `dir/data/openintent.db`.
+async fn store_fixture(dir: &Path) {
+    let fixture =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/workflows/two-step.json");
+    let def: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(fixture).expect("fixture must exist"))
+            .expect("fixture must be valid JSON");
+
+    std::fs::create_dir(dir.join("data")).expect("mkdir must succeed");
+    let db = Database::open_and_migrate(dir.join("data/openintent.db"))
+        .await
+        .expect("database must open");
+    WorkflowStore::new(db)
+        .create(
+            def["name"].as_str().expect("fixture has a name"),
+            def["description"].as_str(),
+            "",
+            def["steps"].clone(),
+            None,
+        )
+        .await
+        .expect("workflow must be stored");
+}
+
+#[tokio::test]
+async fn run_two_step_workflow_to_completion() {
+    let dir = tempfile::tempdir().expect("tempdir creation must succeed in tests");
+    store_fixture(dir.path()).await;
+
+    let list = workflows(dir.path(), &["list"]);
+    assert!(list.status.success());
+    assert!(String::from_utf8_lossy(&list.stdout).contains("two-step"));
+
+    let run = workflows(dir.path(), &["run", "two-step"]);
+    let out = String::from_utf8_lossy(&run.stdout).into_owned();
+    assert!(run.status.success(), "run failed: {run:?}");
+    assert!(
+        out.contains("[1/2] fs_write_file"),
+        "unexpected output: {out}"
+    );
+    assert!(
+        out.contains("[2/2] fs_read_file"),
+        "unexpected output: {out}"
+    );
+    assert!(
+        out.contains("hello from a workflow"),
+        "unexpected output: {out}"
+    );
+    assert!(
+        out.contains("Workflow completed."),
+        "unexpected output: {out}"
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("note.txt")).expect("note must be written"),
+        "hello from a workflow"
+    );
+
+    let run_id = out
+        .split("(run ")
+        .nth(1)
+        .and_then(|rest| rest.split(')').next())
+        .expect("run output must include the run ID");
+    let status = workflows(dir.path(), &["status", run_id]);
+    let out = String::from_utf8_lossy(&status.stdout);
+    assert!(status.status.success(), "status failed: {status:?}");
+    assert!(
+        out.contains("Status: completed"),
+        "unexpected output: {out}"
+    );
+    assert!(
+        out.contains("Progress: 2/2 steps"),
+        "unexpected output: {out}"
+    );
+}
@@ -0,0 +1,426 @@
+//! Step conditions — small boolean expressions over prior step results.
+//!
+//! A condition refers to earlier steps through `steps.<id>` paths, where
+//! `<id>` is a step's `id` or its zero-based index:
+//!
+//! ```text
+//! steps.fetch.success && steps.fetch.output.status == 200
+//! !(steps.0.output.items == null) || steps.check.output.count >= 3
+//! ```
+//!
+//! Supported syntax: `==`, `!=`, `<`, `<=`, `>`, `>=`, `&&`, `||`, `!`,
+//! parentheses, numbers, double- or single-quoted strings, `true`, `false`
+//! and `null`.  Each step exposes `success` (bool) and `output` (its JSON
+//! output).  Paths that do not resolve evaluate to `null`, and a value is
+//! truthy unless it is `null`, `false`, `0`, `""`, `[]` or `{}`.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::error::{IntentError, Result};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// A parsed step condition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    expr: Expr,
+}
+
+/// The values a condition is evaluated against: each prior step's result
+/// keyed by step id and by index.
+pub type ConditionContext = HashMap<String, Value>;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Literal(Value),
+    Path(Vec<String>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(Box<Expr>, CompareOp, Box<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(f64),
+    Str(String),
+    Dot,
+    LParen,
+    RParen,
+    Not,
+    And,
+    Or,
+    Op(CompareOp),
+}
+
+// ---------------------------------------------------------------------------
+// Public API
+// ---------------------------------------------------------------------------
+
+impl Condition {
+    /// Parse a condition expression.
+    pub fn parse(source: &str) -> Result<Self> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            source,
+            tokens: &tokens,
+            pos: 0,
+        };
+        let expr = parser.or()?;
+        if parser.pos != tokens.len() {
+            return Err(invalid(source, "unexpected trailing input"));
+        }
+        Ok(Self { expr })
+    }
+
+    /// Evaluate the condition against prior step results.
+    pub fn evaluate(&self, steps: &ConditionContext) -> bool {
+        truthy(&eval(&self.expr, steps))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Lexer
+// ---------------------------------------------------------------------------
+
+fn invalid(source: &str, reason: &str) -> IntentError {
+    IntentError::InvalidWorkflowState {
+        reason: format!("invalid step condition `{source}`: {reason}"),
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            _ if c.is_whitespace() => i += 1,
+            '.' => {
+                tokens.push(Token::Dot);
+                i += 1;
+            }
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '&' | '|' if next == Some(c) => {
+                tokens.push(if c == '&' { Token::And } else { Token::Or });
+                i += 2;
+            }
+            '=' if next == Some('=') => {
+                tokens.push(Token::Op(CompareOp::Eq));
+                i += 2;
+            }
+            '!' | '<' | '>' => {
+                let with_eq = next == Some('=');
+                tokens.push(match (c, with_eq) {
+                    ('!', true) => Token::Op(CompareOp::Ne),
+                    ('!', false) => Token::Not,
+                    ('<', true) => Token::Op(CompareOp::Le),
+                    ('<', false) => Token::Op(CompareOp::Lt),
+                    ('>', true) => Token::Op(CompareOp::Ge),
+                    _ => Token::Op(CompareOp::Gt),
+                });
+                i += if with_eq { 2 } else { 1 };
+            }
+            '"' | '\'' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&ch| ch == c)
+                    .ok_or_else(|| invalid(source, "unterminated string"))?;
+                tokens.push(Token::Str(chars[i + 1..i + 1 + end].iter().collect()));
+                i += end + 2;
+            }
+            _ if c.is_ascii_digit() || (c == '-' && next.is_some_and(|n| n.is_ascii_digit())) => {
+                let start = i;
+                i += 1;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    // A dot followed by a non-digit ends the number (`steps.0.output`).
+                    if chars[i] == '.' && !chars.get(i + 1).is_some_and(char::is_ascii_digit) {
+                        break;
+                    }
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let number = text
+                    .parse()
+                    .map_err(|_| invalid(source, &format!("invalid number `{text}`")))?;
+                tokens.push(Token::Number(number));
+            }
+            _ if c.is_alphanumeric() || c == '_' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '-')
+                {
+                    i += 1;
+                }
+                tokens.push(Token::Ident(chars[start..i].iter().collect()));
+            }
+            _ => return Err(invalid(source, &format!("unexpected character `{c}`"))),
+        }
+    }
+    Ok(tokens)
+}
+
+// ---------------------------------------------------------------------------
+// Parser
+// ---------------------------------------------------------------------------
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<&'a Token> {
+        let token = self.tokens.get(self.pos);
+        self.pos += 1;
+        token
+    }
+
+    fn error(&self, reason: &str) -> IntentError {
+        invalid(self.source, reason)
+    }
+
+    fn or(&mut self) -> Result<Expr> {
+        let mut lhs = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            lhs = Expr::Or(Box::new(lhs), Box::new(self.and()?));
+        }
+        Ok(lhs)
+    }
+
+    fn and(&mut self) -> Result<Expr> {
+        let mut lhs = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            lhs = Expr::And(Box::new(lhs), Box::new(self.unary()?));
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expr> {
+        if self.peek() == Some(&Token::Not) {
+            self.pos += 1;
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr> {
+        let lhs = self.primary()?;
+        if let Some(&Token::Op(op)) = self.peek() {
+            self.pos += 1;
+            let rhs = self.primary()?;
+            return Ok(Expr::Compare(Box::new(lhs), op, Box::new(rhs)));
+        }
+        Ok(lhs)
+    }
+
+    fn primary(&mut self) -> Result<Expr> {
+        match self.next().cloned() {
+            Some(Token::LParen) => {
+                let expr = self.or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(expr),
+                    _ => Err(self.error("missing `)`")),
+                }
+            }
+            Some(Token::Number(n)) => Ok(Expr::Literal(Value::from(n))),
+            Some(Token::Str(s)) => Ok(Expr::Literal(Value::String(s))),
+            Some(Token::Ident(ident)) => match ident.as_str() {
+                "true" => Ok(Expr::Literal(Value::Bool(true))),
+                "false" => Ok(Expr::Literal(Value::Bool(false))),
+                "null" => Ok(Expr::Literal(Value::Null)),
+                "steps" => self.path(),
+                other => Err(self.error(&format!(
+                    "unknown name `{other}` (paths start with `steps.`)"
+                ))),
+            },
+            Some(token) => Err(self.error(&format!("unexpected token {token:?}"))),
+            None => Err(self.error("unexpected end of expression")),
+        }
+    }
+
+    /// Parse the `.segment` parts following `steps`.
+    fn path(&mut self) -> Result<Expr> {
+        let mut segments = Vec::new();
+        while self.peek() == Some(&Token::Dot) {
+            self.pos += 1;
+            match self.next() {
+                Some(Token::Ident(s)) => segments.push(s.clone()),
+                // `steps.0` is lexed as a number; only whole indices are valid.
+                Some(Token::Number(n)) if n.fract() == 0.0 && *n >= 0.0 => {
+                    segments.push(format!("{n}"));
+                }
+                _ => return Err(self.error("expected a name after `.`")),
+            }
+        }
+        if segments.is_empty() {
+            return Err(self.error("`steps` must be followed by a step id"));
+        }
+        Ok(Expr::Path(segments))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Evaluation
+// ---------------------------------------------------------------------------
+
+fn eval(expr: &Expr, steps: &ConditionContext) -> Value {
+    match expr {
+        Expr::Literal(v) => v.clone(),
+        Expr::Path(segments) => resolve(segments, steps),
+        Expr::Not(inner) => Value::Bool(!truthy(&eval(inner, steps))),
+        Expr::And(lhs, rhs) => Value::Bool(truthy(&eval(lhs, steps)) && truthy(&eval(rhs, steps))),
+        Expr::Or(lhs, rhs) => Value::Bool(truthy(&eval(lhs, steps)) || truthy(&eval(rhs, steps))),
+        Expr::Compare(lhs, op, rhs) => {
+            Value::Bool(compare(&eval(lhs, steps), *op, &eval(rhs, steps)))
+        }
+    }
+}
+
+fn resolve(segments: &[String], steps: &ConditionContext) -> Value {
+    lookup(segments, steps).cloned().unwrap_or(Value::Null)
+}
+
+/// Follow `segments` (a step id, then object keys or array indices) through
+/// the step results, returning `None` if any segment does not resolve.
+pub(crate) fn lookup<'a, S: AsRef<str>>(
+    segments: &[S],
+    steps: &'a ConditionContext,
+) -> Option<&'a Value> {
+    let (step, rest) = segments.split_first()?;
+    let mut current = steps.get(step.as_ref())?;
+    for segment in rest {
+        let segment = segment.as_ref();
+        current = match current {
+            Value::Object(map) => map.get(segment)?,
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+    Some(current)
+}
+
+fn compare(lhs: &Value, op: CompareOp, rhs: &Value) -> bool {
+    let ordering = match (lhs, rhs) {
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .zip(b.as_f64())
+            .and_then(|(a, b)| a.partial_cmp(&b)),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        (Value::Null, Value::Null) => Some(Ordering::Equal),
+        _ => None,
+    };
+    match op {
+        CompareOp::Eq => ordering == Some(Ordering::Equal) || (ordering.is_none() && lhs == rhs),
+        CompareOp::Ne => !compare(lhs, CompareOp::Eq, rhs),
+        CompareOp::Lt => ordering == Some(Ordering::Less),
+        CompareOp::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+        CompareOp::Gt => ordering == Some(Ordering::Greater),
+        CompareOp::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+    }
+}
+
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn context() -> ConditionContext {
+        HashMap::from([
+            (
+                "fetch".to_string(),
+                json!({"success": true, "output": {"status": 200, "items": [1, 2], "tag": "v1"}}),
+            ),
+            (
+                "0".to_string(),
+                json!({"success": false, "output": {"error": "boom"}}),
+            ),
+        ])
+    }
+
+    fn eval_str(source: &str) -> bool {
+        Condition::parse(source).unwrap().evaluate(&context())
+    }
+
+    #[test]
+    fn comparisons_and_paths() {
+        assert!(eval_str("steps.fetch.output.status == 200"));
+        assert!(eval_str("steps.fetch.output.status >= 199.5"));
+        assert!(eval_str("steps.fetch.output.tag != 'v2'"));
+        assert!(eval_str("steps.fetch.output.items.1 == 2"));
+        assert!(eval_str("steps.missing.output == null"));
+        assert!(!eval_str("steps.0.success"));
+    }
+
+    #[test]
+    fn boolean_operators_and_precedence() {
+        assert!(eval_str("steps.fetch.success && !steps.0.success"));
+        assert!(eval_str("steps.0.success || steps.fetch.success && true"));
+        assert!(!eval_str("!(steps.fetch.success || steps.0.success)"));
+        assert!(eval_str("steps.0.output.error"));
+    }
+
+    #[test]
+    fn invalid_expressions_are_rejected() {
+        for source in [
+            "",
+            "steps",
+            "status == 200",
+            "steps.fetch.success &&",
+            "(steps.fetch.success",
+            "steps.fetch.output.tag == 'v1",
+            "steps.fetch.success = true",
+        ] {
+            assert!(Condition::parse(source).is_err(), "{source} should fail");
+        }
+    }
+}
@@ -17,6 +17,10 @@ pub enum IntentError {
     #[error("low confidence ({confidence:.2}) for intent: {intent}")]
     LowConfidence { intent: String, confidence: f64 },
 
+    /// The fast-path trigger configuration is malformed.
+    #[error("invalid trigger config: {reason}")]
+    InvalidTriggerConfig { reason: String },
+
     // -- Workflow errors ------------------------------------------------------
     /// The referenced workflow does not exist.
     #[error("workflow not found: {workflow_id}")]
@@ -48,6 +52,10 @@ pub enum IntentError {
     #[error("agent error: {0}")]
     Agent(#[from] openintent_agent::AgentError),
 
+    /// An error propagated from the store crate.
+    #[error("store error: {0}")]
+    Store(#[from] openintent_store::StoreError),
+
     // -- Serialization -------------------------------------------------------
     /// JSON serialization or deserialization failed.
     #[error("json error: {0}")]
@@ -0,0 +1,139 @@
+//! Input language detection for the intent parser.
+//!
+//! Text with any kana is Japanese, any Hangul is Korean, and any other Han
+//! character makes it Chinese -- CJK text routinely embeds Latin paths and
+//! product names, so a single CJK character outweighs the Latin around it.
+//! Everything else goes through [`whatlang`]'s trigram models.  A language
+//! that whatlang identifies reliably but that is not a [`Language`] variant
+//! is reported as [`Language::Unknown`], so French or Spanish input is never
+//! matched against English triggers and goes to the LLM instead.
+//!
+//! Short command lines (`ls /tmp`, `help`) carry too few trigrams for a
+//! reliable verdict; they are attributed to the script's main language
+//! listed here, e.g. English for Latin script.
+
+use serde::{Deserialize, Serialize};
+use whatlang::{Lang, Script};
+
+/// A language detected in user input.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Language {
+    English,
+    Chinese,
+    Japanese,
+    Korean,
+    Russian,
+    Arabic,
+    /// No letters, or a language not listed above.
+    #[default]
+    Unknown,
+}
+
+impl Language {
+    /// The ISO 639-1 code, or `"und"` for [`Language::Unknown`].
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::Chinese => "zh",
+            Self::Japanese => "ja",
+            Self::Korean => "ko",
+            Self::Russian => "ru",
+            Self::Arabic => "ar",
+            Self::Unknown => "und",
+        }
+    }
+
+    /// Whether the language puts spaces between words.  Chinese and
+    /// Japanese do not, so keywords there can be followed directly by text.
+    pub fn separates_words(self) -> bool {
+        !matches!(self, Self::Chinese | Self::Japanese)
+    }
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Detect the language of `text`.
+pub fn detect_language(text: &str) -> Language {
+    let (mut han, mut kana, mut hangul) = (0usize, 0usize, 0usize);
+    for c in text.chars() {
+        match c as u32 {
+            0x3040..=0x30FF | 0x31F0..=0x31FF => kana += 1,
+            0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => hangul += 1,
+            0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF => han += 1,
+            _ => {}
+        }
+    }
+    if kana > 0 {
+        return Language::Japanese;
+    }
+    if hangul > 0 {
+        return Language::Korean;
+    }
+    if han > 0 {
+        return Language::Chinese;
+    }
+
+    let Some(info) = whatlang::detect(text) else {
+        return Language::Unknown;
+    };
+    match from_whatlang(info.lang()) {
+        Some(language) => language,
+        None if info.is_reliable() => Language::Unknown,
+        None => match info.script() {
+            Script::Latin => Language::English,
+            Script::Cyrillic => Language::Russian,
+            Script::Arabic => Language::Arabic,
+            _ => Language::Unknown,
+        },
+    }
+}
+
+/// The [`Language`] for a whatlang language, if it has a variant.
+fn from_whatlang(lang: Lang) -> Option<Language> {
+    match lang {
+        Lang::Eng => Some(Language::English),
+        Lang::Cmn => Some(Language::Chinese),
+        Lang::Jpn => Some(Language::Japanese),
+        Lang::Kor => Some(Language::Korean),
+        Lang::Rus => Some(Language::Russian),
+        Lang::Ara => Some(Language::Arabic),
+        _ => None,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_language() {
+        assert_eq!(detect_language("open the file"), Language::English);
+        assert_eq!(detect_language("ls /tmp"), Language::English);
+        assert_eq!(detect_language("打开 /etc/hosts"), Language::Chinese);
+        assert_eq!(detect_language("ファイルを開く"), Language::Japanese);
+        assert_eq!(detect_language("파일 열기"), Language::Korean);
+        assert_eq!(detect_language("открой файл"), Language::Russian);
+        assert_eq!(detect_language("12 + 30 = ?"), Language::Unknown);
+    }
+
+    #[test]
+    fn other_latin_languages_are_unknown() {
+        assert_eq!(
+            detect_language("peux-tu ouvrir le fichier rapport.txt s'il te plaît"),
+            Language::Unknown
+        );
+        assert_eq!(
+            detect_language("abre el archivo de configuración por favor"),
+            Language::Unknown
+        );
+    }
+}
@@ -3,24 +3,38 @@
 //! This crate provides:
 //!
 //! - **Intent parsing**: Two-tier intent resolution (fast local matching +
-//!   LLM fallback) via [`parser::IntentParser`].
-//! - **Workflow engine**: Multi-step workflow definition and sequential
-//!   execution via [`workflow::WorkflowEngine`].
+//!   LLM fallback) via [`parser::IntentParser`], with per-language fast-path
+//!   triggers loaded from configuration by [`triggers::TriggerTables`] and
+//!   chosen by [`language::detect_language`].
+//! - **Slot filling**: Typed slot declarations per action, with missing
+//!   required slots reported for follow-up via [`slots::IntentDefinition`].
+//! - **Workflow engine**: Multi-step workflow definition and execution with
+//!   conditional branching, parallel step groups, and resumable checkpoints via
+//!   [`workflow::WorkflowEngine`].
 //! - **Trigger system**: Manual, cron, and event-based workflow triggers
 //!   via [`trigger::TriggerManager`].
 //! - **Cron scheduler**: Background scheduling daemon that fires events
 //!   on cron schedules via [`scheduler::CronScheduler`].
 
+pub mod condition;
 pub mod error;
+pub mod language;
 pub mod parser;
 pub mod scheduler;
+pub mod slots;
 pub mod trigger;
+pub mod triggers;
 pub mod workflow;
 
+pub use condition::Condition;
 pub use error::{IntentError, Result};
-pub use parser::{IntentParser, ParseSource, ParsedIntent};
+pub use language::{Language, detect_language};
+pub use parser::{IntentAlternative, IntentParser, ParseCacheConfig, ParseSource, ParsedIntent};
 pub use scheduler::{CronEvent, CronScheduler, ScheduledJob};
-pub use trigger::{TriggerManager, TriggerType};
+pub use slots::{IntentDefinition, SlotSpec, SlotType, SlotValue};
+pub use trigger::{MisfirePolicy, TriggerManager, TriggerType};
+pub use triggers::{Trigger, TriggerTables};
 pub use workflow::{
-    StepResult, Workflow, WorkflowEngine, WorkflowResult, WorkflowStatus, WorkflowStep,
+    GroupFailurePolicy, RunStatus, StepCallback, StepResult, Workflow, WorkflowEngine,
+    WorkflowResult, WorkflowStatus, WorkflowStep,
 };
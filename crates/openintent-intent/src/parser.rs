@@ -6,6 +6,19 @@
 //!    well-known commands (e.g. "open file X", "run command Y").
 //! 2. **Slow path**: Falls back to LLM-based parsing for complex or
 //!    ambiguous intents.
+//!
+//! Actions registered with an [`IntentDefinition`] additionally have their
+//! entities converted into typed slots, and missing required slots are
+//! reported on the [`ParsedIntent`] for multi-turn follow-up.
+//!
+//! The fast path picks its trigger table by the detected input language
+//! (see [`crate::language`]); the tables are loaded from configuration (see
+//! [`crate::triggers`]), and languages without one go straight to the LLM.
+//! The detected language is recorded on the [`ParsedIntent`].
+//!
+//! LLM results can be cached (see [`IntentParser::with_cache`]) under the
+//! normalized utterance -- lowercased, with whitespace collapsed -- so a
+//! recurring phrasing is only sent to the LLM once per TTL.
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -14,8 +27,12 @@ use serde::{Deserialize, Serialize};
 use tracing::{debug, info};
 
 use openintent_agent::{ChatRequest, LlmClient, LlmResponse, Message};
+use openintent_store::{CacheLayer, CacheStats};
 
 use crate::error::{IntentError, Result};
+use crate::language::{Language, detect_language};
+use crate::slots::{IntentDefinition, SlotValue};
+use crate::triggers::{TriggerMatch, TriggerTables};
 
 // ---------------------------------------------------------------------------
 // Types
@@ -38,6 +55,66 @@ pub struct ParsedIntent {
 
     /// Which parsing tier produced this result.
     pub source: ParseSource,
+
+    /// Language detected in `raw_text`.
+    #[serde(default)]
+    pub language: Language,
+
+    /// Typed slot values, for actions with an [`IntentDefinition`].
+    #[serde(default)]
+    pub slots: HashMap<String, SlotValue>,
+
+    /// Required slots that are still missing, in the order to ask for them.
+    #[serde(default)]
+    pub missing_slots: Vec<String>,
+
+    /// Other plausible actions, most confident first.  Never contains
+    /// `action` itself.
+    #[serde(default)]
+    pub alternatives: Vec<IntentAlternative>,
+}
+
+/// A runner-up interpretation of the user's text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntentAlternative {
+    /// The alternative action.
+    pub action: String,
+
+    /// Confidence score between 0.0 and 1.0.
+    pub confidence: f64,
+}
+
+impl ParsedIntent {
+    /// Whether every required slot has been filled.
+    pub fn is_complete(&self) -> bool {
+        self.missing_slots.is_empty()
+    }
+
+    /// Whether the best alternative is within `margin` of the chosen action,
+    /// in which case the caller should confirm with the user.
+    pub fn is_ambiguous(&self, margin: f64) -> bool {
+        self.alternatives
+            .first()
+            .is_some_and(|alt| self.confidence - alt.confidence <= margin)
+    }
+}
+
+/// Configuration for the parser's LLM result cache.
+#[derive(Debug, Clone)]
+pub struct ParseCacheConfig {
+    /// How long a cached intent stays valid, in seconds.
+    pub ttl_seconds: u64,
+    /// Maximum number of cached intents.
+    pub max_entries: u64,
+}
+
+impl Default for ParseCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl_seconds: 3600,
+            max_entries: 1000,
+        }
+    }
 }
 
 /// The tier that produced the parsed intent.
@@ -60,9 +137,13 @@ Respond ONLY with a JSON object:
 {
   "action": "the_action_name",
   "entities": {"key": "value", ...},
-  "confidence": 0.0-1.0
+  "confidence": 0.0-1.0,
+  "alternatives": [{"action": "other_action_name", "confidence": 0.0-1.0}, ...]
 }
 
+List in "alternatives" any other actions the text could plausibly mean
+(at most 3), or leave it empty when the intent is clear.
+
 Available actions:
 - fs_read_file (entities: path)
 - fs_write_file (entities: path, content)
@@ -97,18 +178,32 @@ pub struct IntentParser {
 
     /// Model identifier to use for LLM-based parsing requests.
     model: String,
+
+    /// Slot declarations, keyed by action.
+    definitions: HashMap<String, IntentDefinition>,
+
+    /// LLM results keyed by normalized utterance, when caching is enabled.
+    cache: Option<CacheLayer<ParsedIntent>>,
+
+    /// Fast-path trigger tables, keyed by language.
+    triggers: TriggerTables,
 }
 
 impl IntentParser {
     /// Create a new intent parser with the given confidence threshold.
     ///
     /// Without an LLM client, intents that cannot be matched via the fast
-    /// path will return low-confidence "unknown" results.
+    /// path will return low-confidence "unknown" results.  Fast-path
+    /// triggers are read from `config/default.toml`; see
+    /// [`IntentParser::with_triggers`].
     pub fn new(confidence_threshold: f64) -> Self {
         Self {
             confidence_threshold,
             llm: None,
             model: String::new(),
+            definitions: HashMap::new(),
+            cache: None,
+            triggers: TriggerTables::load_default(),
         }
     }
 
@@ -125,14 +220,129 @@ impl IntentParser {
             confidence_threshold,
             llm: Some(llm),
             model: model.into(),
+            definitions: HashMap::new(),
+            cache: None,
+            triggers: TriggerTables::load_default(),
         }
     }
 
+    /// Use `triggers` for the fast path instead of the tables in
+    /// `config/default.toml`.
+    pub fn with_triggers(mut self, triggers: TriggerTables) -> Self {
+        self.triggers = triggers;
+        self
+    }
+
+    /// Cache LLM-parsed intents by normalized utterance.
+    ///
+    /// Only the LLM tier is cached; the fast path is already cheaper than a
+    /// cache lookup.  Use [`IntentParser::parse_uncached`] to bypass it.
+    pub fn with_cache(mut self, config: ParseCacheConfig) -> Self {
+        self.cache = Some(
+            CacheLayer::builder("parsed_intents")
+                .max_capacity(config.max_entries)
+                .ttl_seconds(config.ttl_seconds)
+                .build(),
+        );
+        self
+    }
+
+    /// Hit/miss statistics of the intent cache, if enabled.
+    pub fn cache_stats(&self) -> Option<&CacheStats> {
+        self.cache.as_ref().map(CacheLayer::stats)
+    }
+
+    /// Declare the slots of an action, replacing any earlier definition.
+    pub fn with_definition(mut self, definition: IntentDefinition) -> Self {
+        self.definitions
+            .insert(definition.action.clone(), definition);
+        self
+    }
+
+    /// Provide a value for one slot of `intent`, typically the user's answer
+    /// to a follow-up question about a missing slot.
+    ///
+    /// The value is converted to the slot's declared type; on failure the
+    /// intent is left unchanged.
+    pub fn fill_slot(&self, intent: &mut ParsedIntent, name: &str, value: &str) -> Result<()> {
+        let spec = self
+            .definitions
+            .get(&intent.action)
+            .and_then(|d| d.slot(name))
+            .ok_or_else(|| IntentError::ParseFailed {
+                reason: format!("action `{}` has no slot `{name}`", intent.action),
+            })?;
+        let converted = spec
+            .slot_type
+            .convert(value)
+            .ok_or_else(|| IntentError::ParseFailed {
+                reason: format!(
+                    "`{value}` is not a valid {:?} for slot `{name}`",
+                    spec.slot_type
+                ),
+            })?;
+
+        intent
+            .entities
+            .insert(name.to_string(), value.trim().to_string());
+        intent.slots.insert(name.to_string(), converted);
+        intent.missing_slots.retain(|s| s != name);
+        debug!(action = %intent.action, slot = name, "slot filled");
+        Ok(())
+    }
+
+    /// Populate typed slots and missing required slots from the entities.
+    fn fill_slots(&self, mut intent: ParsedIntent) -> ParsedIntent {
+        if let Some(definition) = self.definitions.get(&intent.action) {
+            let (slots, missing) = definition.fill(&intent.entities);
+            intent.slots = slots;
+            intent.missing_slots = missing;
+            if !intent.is_complete() {
+                debug!(
+                    action = %intent.action,
+                    missing = ?intent.missing_slots,
+                    "intent has missing required slots"
+                );
+            }
+        }
+        intent
+    }
+
+    /// The LLM system prompt, extended with the slots of declared actions.
+    fn system_prompt(&self) -> String {
+        let mut prompt = LLM_SYSTEM_PROMPT.to_string();
+        let mut definitions: Vec<&IntentDefinition> = self.definitions.values().collect();
+        if definitions.is_empty() {
+            return prompt;
+        }
+        definitions.sort_by(|a, b| a.action.cmp(&b.action));
+        prompt.push_str("\n\nDeclared actions:");
+        for definition in definitions {
+            let slots: Vec<&str> = definition.slots.iter().map(|s| s.name.as_str()).collect();
+            prompt.push_str(&format!(
+                "\n- {} (entities: {})",
+                definition.action,
+                slots.join(", ")
+            ));
+        }
+        prompt
+    }
+
     /// Parse raw user text into a structured intent.
     ///
     /// This first tries fast local pattern matching.  If no route matches,
-    /// it falls back to LLM-based parsing.
+    /// it falls back to LLM-based parsing, served from the cache when one is
+    /// configured and holds the same normalized utterance.
     pub async fn parse(&self, text: &str) -> Result<ParsedIntent> {
+        self.parse_with(text, true).await
+    }
+
+    /// Like [`IntentParser::parse`], but neither reads nor fills the cache.
+    pub async fn parse_uncached(&self, text: &str) -> Result<ParsedIntent> {
+        self.parse_with(text, false).await
+    }
+
+    async fn parse_with(&self, text: &str, use_cache: bool) -> Result<ParsedIntent> {
         let text = text.trim();
         if text.is_empty() {
             return Err(IntentError::ParseFailed {
@@ -142,10 +352,14 @@ impl IntentParser {
 
         debug!(text = text, "parsing intent");
 
+        let language = detect_language(text);
+        debug!(language = %language, "detected input language");
+
         // Tier 1: Fast local pattern matching.
-        if let Some(intent) = self.try_fast_match(text)
+        if let Some(intent) = self.try_fast_match(text, language)
             && intent.confidence >= self.confidence_threshold
         {
+            let intent = self.fill_slots(intent);
             info!(
                 action = %intent.action,
                 confidence = intent.confidence,
@@ -157,7 +371,10 @@ impl IntentParser {
 
         // Tier 2: LLM fallback.
         if let Some(llm) = &self.llm {
-            return self.llm_parse(llm, text).await;
+            return match self.cache.as_ref().filter(|_| use_cache) {
+                Some(cache) => self.cached_llm_parse(cache, llm, text).await,
+                None => self.llm_parse(llm, text).await,
+            };
         }
 
         // No LLM available — return a low-confidence unknown intent.
@@ -167,6 +384,10 @@ impl IntentParser {
             raw_text: text.to_string(),
             confidence: 0.5,
             source: ParseSource::Llm,
+            language,
+            slots: HashMap::new(),
+            missing_slots: Vec::new(),
+            alternatives: Vec::new(),
         };
 
         if intent.confidence < self.confidence_threshold {
@@ -185,6 +406,27 @@ impl IntentParser {
         Ok(intent)
     }
 
+    /// Serve an LLM parse from `cache`, calling the LLM only on a miss.
+    async fn cached_llm_parse(
+        &self,
+        cache: &CacheLayer<ParsedIntent>,
+        llm: &LlmClient,
+        text: &str,
+    ) -> Result<ParsedIntent> {
+        let key = normalize_utterance(text);
+        if let Some(mut intent) = cache.get(&key).await {
+            intent.raw_text = text.to_string();
+            debug!(action = %intent.action, "intent served from cache");
+            return Ok(intent);
+        }
+
+        let intent = self.llm_parse(llm, text).await?;
+        if let Err(e) = cache.insert(&key, &intent).await {
+            tracing::warn!(error = %e, "failed to cache parsed intent");
+        }
+        Ok(intent)
+    }
+
     /// Parse intent text using the LLM client.
     ///
     /// Sends a structured prompt to the LLM requesting JSON output, then
@@ -192,7 +434,7 @@ impl IntentParser {
     async fn llm_parse(&self, llm: &LlmClient, text: &str) -> Result<ParsedIntent> {
         let request = ChatRequest {
             model: self.model.clone(),
-            messages: vec![Message::system(LLM_SYSTEM_PROMPT), Message::user(text)],
+            messages: vec![Message::system(self.system_prompt()), Message::user(text)],
             tools: vec![],
             temperature: Some(0.0),
             max_tokens: Some(256),
@@ -240,10 +482,17 @@ impl IntentParser {
             .as_object()
             .map(|obj| {
                 obj.iter()
-                    .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
+                    .filter_map(|(k, v)| match v {
+                        serde_json::Value::String(s) => Some((k.clone(), s.clone())),
+                        serde_json::Value::Number(_) | serde_json::Value::Bool(_) => {
+                            Some((k.clone(), v.to_string()))
+                        }
+                        _ => None,
+                    })
                     .collect()
             })
             .unwrap_or_default();
+        let alternatives = parse_llm_alternatives(&parsed["alternatives"], &action);
 
         let intent = ParsedIntent {
             action,
@@ -251,6 +500,10 @@ impl IntentParser {
             raw_text: original_text.to_string(),
             confidence,
             source: ParseSource::Llm,
+            language: detect_language(original_text),
+            slots: HashMap::new(),
+            missing_slots: Vec::new(),
+            alternatives,
         };
 
         if intent.confidence < self.confidence_threshold {
@@ -260,9 +513,11 @@ impl IntentParser {
             });
         }
 
+        let intent = self.fill_slots(intent);
         info!(
             action = %intent.action,
             confidence = intent.confidence,
+            missing_slots = intent.missing_slots.len(),
             "intent parsed via LLM fallback"
         );
         Ok(intent)
@@ -270,111 +525,71 @@ impl IntentParser {
 
     /// Attempt fast local pattern matching on the input text.
     ///
-    /// Returns `Some(ParsedIntent)` if a well-known pattern matches, or
-    /// `None` to signal that LLM fallback is needed.
-    fn try_fast_match(&self, text: &str) -> Option<ParsedIntent> {
+    /// Uses the trigger table for `language`.  Returns `Some(ParsedIntent)`
+    /// if a well-known pattern matches, or `None` to signal that LLM
+    /// fallback is needed -- always the case for languages without a table.
+    fn try_fast_match(&self, text: &str, language: Language) -> Option<ParsedIntent> {
         let lower = text.to_lowercase();
-        let words: Vec<&str> = lower.split_whitespace().collect();
-
-        if words.is_empty() {
-            return None;
-        }
-
-        // Simple keyword-based matching for common patterns.
-        match words[0] {
-            "read" | "cat" | "show" | "view" | "open" => {
-                if words.len() >= 2 {
-                    let mut entities = HashMap::new();
-                    entities.insert("path".into(), words[1..].join(" "));
-                    return Some(ParsedIntent {
-                        action: "fs_read_file".into(),
-                        entities,
-                        raw_text: text.into(),
-                        confidence: 0.85,
-                        source: ParseSource::Router,
-                    });
-                }
-            }
-            "write" | "save" | "create" => {
-                if words.len() >= 2 {
-                    let mut entities = HashMap::new();
-                    entities.insert("path".into(), words[1..].join(" "));
-                    return Some(ParsedIntent {
-                        action: "fs_write_file".into(),
-                        entities,
-                        raw_text: text.into(),
-                        confidence: 0.80,
-                        source: ParseSource::Router,
-                    });
-                }
-            }
-            "run" | "exec" | "execute" => {
-                if words.len() >= 2 {
-                    let mut entities = HashMap::new();
-                    entities.insert("command".into(), words[1..].join(" "));
-                    return Some(ParsedIntent {
-                        action: "shell_execute".into(),
-                        entities,
-                        raw_text: text.into(),
-                        confidence: 0.90,
-                        source: ParseSource::Router,
-                    });
-                }
-            }
-            "ls" | "list" | "dir" => {
-                let mut entities = HashMap::new();
-                let path = if words.len() >= 2 {
-                    words[1..].join(" ")
-                } else {
-                    ".".into()
-                };
-                entities.insert("path".into(), path);
-                return Some(ParsedIntent {
-                    action: "fs_list_directory".into(),
-                    entities,
-                    raw_text: text.into(),
-                    confidence: 0.90,
-                    source: ParseSource::Router,
-                });
-            }
-            "delete" | "rm" | "remove" => {
-                if words.len() >= 2 {
-                    let mut entities = HashMap::new();
-                    entities.insert("path".into(), words[1..].join(" "));
-                    return Some(ParsedIntent {
-                        action: "fs_delete".into(),
-                        entities,
-                        raw_text: text.into(),
-                        confidence: 0.85,
-                        source: ParseSource::Router,
-                    });
-                }
-            }
-            "help" => {
-                return Some(ParsedIntent {
-                    action: "help".into(),
-                    entities: HashMap::new(),
-                    raw_text: text.into(),
-                    confidence: 1.0,
-                    source: ParseSource::Router,
-                });
-            }
-            "status" => {
-                return Some(ParsedIntent {
-                    action: "system_status".into(),
-                    entities: HashMap::new(),
-                    raw_text: text.into(),
-                    confidence: 0.95,
-                    source: ParseSource::Router,
-                });
-            }
-            _ => {}
+        let TriggerMatch { trigger, rest } = self.triggers.find(&lower, language)?;
+
+        let mut entities = HashMap::new();
+        if let Some(entity) = &trigger.entity {
+            let value = match (rest.is_empty(), &trigger.default) {
+                (false, _) => rest,
+                (true, Some(default)) => default.clone(),
+                (true, None) => return None,
+            };
+            entities.insert(entity.clone(), value);
         }
 
-        None
+        Some(ParsedIntent {
+            action: trigger.action.clone(),
+            entities,
+            raw_text: text.into(),
+            confidence: trigger.confidence,
+            source: ParseSource::Router,
+            language,
+            slots: HashMap::new(),
+            missing_slots: Vec::new(),
+            alternatives: trigger.alternatives.clone(),
+        })
     }
 }
 
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Cache key for `text`: lowercased, with runs of whitespace collapsed.
+fn normalize_utterance(text: &str) -> String {
+    text.split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Read the `alternatives` array of an LLM response, dropping malformed
+/// entries and the chosen `action`, most confident first.
+fn parse_llm_alternatives(value: &serde_json::Value, action: &str) -> Vec<IntentAlternative> {
+    let mut alternatives: Vec<IntentAlternative> = value
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    Some(IntentAlternative {
+                        action: item["action"].as_str()?.to_string(),
+                        confidence: item["confidence"].as_f64()?.clamp(0.0, 1.0),
+                    })
+                })
+                .filter(|alt| alt.action != action)
+                .collect()
+        })
+        .unwrap_or_default();
+    alternatives.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    alternatives
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -382,10 +597,18 @@ impl IntentParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::slots::SlotType;
+
+    /// A parser with the trigger tables shipped in `config/default.toml`.
+    fn shipped_parser(confidence_threshold: f64) -> IntentParser {
+        let triggers =
+            TriggerTables::from_toml(include_str!("../../../config/default.toml")).unwrap();
+        IntentParser::new(confidence_threshold).with_triggers(triggers)
+    }
 
     #[tokio::test]
     async fn parse_read_file() {
-        let parser = IntentParser::new(0.7);
+        let parser = shipped_parser(0.7);
         let intent = parser.parse("read /etc/hosts").await.unwrap();
         assert_eq!(intent.action, "fs_read_file");
         assert_eq!(intent.entities.get("path").unwrap(), "/etc/hosts");
@@ -394,7 +617,7 @@ mod tests {
 
     #[tokio::test]
     async fn parse_execute_command() {
-        let parser = IntentParser::new(0.7);
+        let parser = shipped_parser(0.7);
         let intent = parser.parse("run git status").await.unwrap();
         assert_eq!(intent.action, "shell_execute");
         assert_eq!(intent.entities.get("command").unwrap(), "git status");
@@ -402,7 +625,7 @@ mod tests {
 
     #[tokio::test]
     async fn parse_list_directory() {
-        let parser = IntentParser::new(0.7);
+        let parser = shipped_parser(0.7);
         let intent = parser.parse("ls /tmp").await.unwrap();
         assert_eq!(intent.action, "fs_list_directory");
         assert_eq!(intent.entities.get("path").unwrap(), "/tmp");
@@ -410,7 +633,7 @@ mod tests {
 
     #[tokio::test]
     async fn parse_help() {
-        let parser = IntentParser::new(0.7);
+        let parser = shipped_parser(0.7);
         let intent = parser.parse("help").await.unwrap();
         assert_eq!(intent.action, "help");
         assert_eq!(intent.confidence, 1.0);
@@ -418,21 +641,21 @@ mod tests {
 
     #[tokio::test]
     async fn parse_empty_text_fails() {
-        let parser = IntentParser::new(0.7);
+        let parser = shipped_parser(0.7);
         let result = parser.parse("").await;
         assert!(result.is_err());
     }
 
     #[tokio::test]
     async fn parse_unknown_falls_back_to_llm() {
-        let parser = IntentParser::new(0.3);
+        let parser = shipped_parser(0.3);
         let intent = parser.parse("what is the meaning of life").await.unwrap();
         assert_eq!(intent.source, ParseSource::Llm);
     }
 
     #[tokio::test]
     async fn parse_with_no_llm_returns_low_confidence() {
-        let parser = IntentParser::new(0.7);
+        let parser = shipped_parser(0.7);
         // Unknown text without LLM should fail with LowConfidence.
         let result = parser.parse("what is the meaning of life").await;
         assert!(result.is_err());
@@ -449,7 +672,7 @@ mod tests {
 
     #[test]
     fn parse_llm_json_plain() {
-        let parser = IntentParser::new(0.5);
+        let parser = shipped_parser(0.5);
         let json =
             r#"{"action": "web_search", "entities": {"query": "rust lang"}, "confidence": 0.9}"#;
         let intent = parser
@@ -463,7 +686,7 @@ mod tests {
 
     #[test]
     fn parse_llm_json_with_code_fence() {
-        let parser = IntentParser::new(0.5);
+        let parser = shipped_parser(0.5);
         let json = "```json\n{\"action\": \"help\", \"entities\": {}, \"confidence\": 0.95}\n```";
         let intent = parser.parse_llm_json_response(json, "help me").unwrap();
         assert_eq!(intent.action, "help");
@@ -472,7 +695,7 @@ mod tests {
 
     #[test]
     fn parse_llm_json_low_confidence_rejected() {
-        let parser = IntentParser::new(0.8);
+        let parser = shipped_parser(0.8);
         let json = r#"{"action": "unknown", "entities": {}, "confidence": 0.3}"#;
         let result = parser.parse_llm_json_response(json, "gibberish");
         assert!(result.is_err());
@@ -480,8 +703,174 @@ mod tests {
 
     #[test]
     fn parse_llm_json_invalid() {
-        let parser = IntentParser::new(0.5);
+        let parser = shipped_parser(0.5);
         let result = parser.parse_llm_json_response("not json at all", "test");
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn missing_required_slot_is_reported() {
+        let parser = shipped_parser(0.7).with_definition(
+            IntentDefinition::new("fs_write_file")
+                .required("path", SlotType::Text)
+                .required("content", SlotType::Text),
+        );
+        let mut intent = parser.parse("write notes.txt").await.unwrap();
+        assert_eq!(
+            intent.slots.get("path"),
+            Some(&SlotValue::Text("notes.txt".into()))
+        );
+        assert_eq!(intent.missing_slots, vec!["content"]);
+        assert!(!intent.is_complete());
+
+        // The follow-up answer completes the intent.
+        parser
+            .fill_slot(&mut intent, "content", "hello world")
+            .unwrap();
+        assert!(intent.is_complete());
+        assert_eq!(intent.entities.get("content").unwrap(), "hello world");
+    }
+
+    #[test]
+    fn llm_entities_fill_typed_slots() {
+        let parser = shipped_parser(0.5).with_definition(
+            IntentDefinition::new("timer_set")
+                .required("minutes", SlotType::Integer)
+                .required("label", SlotType::Text),
+        );
+        let json = r#"{"action": "timer_set", "entities": {"minutes": 15}, "confidence": 0.9}"#;
+        let mut intent = parser
+            .parse_llm_json_response(json, "set a 15 minute timer")
+            .unwrap();
+        assert_eq!(intent.slots.get("minutes"), Some(&SlotValue::Integer(15)));
+        assert_eq!(intent.missing_slots, vec!["label"]);
+
+        assert!(parser.fill_slot(&mut intent, "minutes", "soon").is_err());
+        assert!(parser.fill_slot(&mut intent, "colour", "red").is_err());
+        assert_eq!(intent.slots.get("minutes"), Some(&SlotValue::Integer(15)));
+    }
+
+    #[test]
+    fn declared_actions_are_added_to_llm_prompt() {
+        let parser = shipped_parser(0.5).with_definition(
+            IntentDefinition::new("timer_set").required("minutes", SlotType::Integer),
+        );
+        assert!(
+            parser
+                .system_prompt()
+                .ends_with("- timer_set (entities: minutes)")
+        );
+        assert_eq!(shipped_parser(0.5).system_prompt(), LLM_SYSTEM_PROMPT);
+    }
+
+    #[tokio::test]
+    async fn ambiguous_fast_path_returns_ranked_alternatives() {
+        let parser = shipped_parser(0.7);
+        let intent = parser.parse("create reports").await.unwrap();
+        assert_eq!(intent.action, "fs_write_file");
+        assert_eq!(
+            intent.alternatives,
+            vec![IntentAlternative {
+                action: "fs_create_directory".into(),
+                confidence: 0.75,
+            }]
+        );
+        assert!(intent.is_ambiguous(0.1));
+
+        let clear = parser.parse("run cargo test").await.unwrap();
+        assert!(clear.alternatives.is_empty());
+        assert!(!clear.is_ambiguous(0.1));
+    }
+
+    #[test]
+    fn parse_llm_json_alternatives_are_ranked() {
+        let parser = shipped_parser(0.5);
+        let json = r#"{
+            "action": "web_search",
+            "entities": {"query": "rust"},
+            "confidence": 0.6,
+            "alternatives": [
+                {"action": "memory_search", "confidence": 0.3},
+                {"action": "web_search", "confidence": 0.6},
+                {"action": "fs_read_file"},
+                {"action": "web_fetch", "confidence": 0.55}
+            ]
+        }"#;
+        let intent = parser.parse_llm_json_response(json, "rust").unwrap();
+        let ranked: Vec<&str> = intent
+            .alternatives
+            .iter()
+            .map(|a| a.action.as_str())
+            .collect();
+        assert_eq!(ranked, vec!["web_fetch", "memory_search"]);
+        assert!(intent.is_ambiguous(0.1));
+    }
+
+    #[tokio::test]
+    async fn repeated_utterance_is_served_from_cache() {
+        use openintent_agent::{LlmClientConfig, ScriptedBackend};
+
+        let backend = Arc::new(ScriptedBackend::new([
+            LlmResponse::Text(
+                r#"{"action": "web_search", "entities": {"query": "weather"}, "confidence": 0.9}"#
+                    .into(),
+            ),
+            LlmResponse::Text(r#"{"action": "help", "entities": {}, "confidence": 0.9}"#.into()),
+        ]));
+        let llm = LlmClient::new(LlmClientConfig::anthropic("test-key", "test-model"))
+            .unwrap()
+            .with_backend(backend.clone());
+        let parser =
+            IntentParser::with_llm(0.5, Arc::new(llm), "test-model").with_cache(Default::default());
+
+        let first = parser.parse("What is the weather").await.unwrap();
+        let second = parser.parse("  what is   THE weather ").await.unwrap();
+        assert_eq!(backend.requests().len(), 1);
+        assert_eq!(second.action, first.action);
+        assert_eq!(second.entities, first.entities);
+        assert_eq!(second.raw_text, "what is   THE weather");
+        assert_eq!(parser.cache_stats().unwrap().hits(), 1);
+
+        // Bypassing the cache goes back to the LLM.
+        let fresh = parser.parse_uncached("what is the weather").await.unwrap();
+        assert_eq!(fresh.action, "help");
+        assert_eq!(backend.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn trigger_set_follows_input_language() {
+        let parser = shipped_parser(0.7);
+
+        let zh = parser.parse("打开 /etc/hosts").await.unwrap();
+        assert_eq!(zh.language, Language::Chinese);
+        assert_eq!(zh.action, "fs_read_file");
+        assert_eq!(zh.entities.get("path").unwrap(), "/etc/hosts");
+        assert_eq!(zh.source, ParseSource::Router);
+
+        let zh = parser.parse("运行git status").await.unwrap();
+        assert_eq!(zh.action, "shell_execute");
+        assert_eq!(zh.entities.get("command").unwrap(), "git status");
+
+        let en = parser.parse("open /etc/hosts").await.unwrap();
+        assert_eq!(en.language, Language::English);
+        assert_eq!(en.action, "fs_read_file");
+
+        // English keywords are not triggers in Chinese text, and vice versa.
+        assert!(parser.parse("open 文件").await.is_err());
+        assert!(parser.parse("帮助 me").await.is_ok());
+        assert!(parser.parse("please 帮助").await.is_err());
+
+        // Languages without a trigger table go to the LLM tier.
+        let parser = shipped_parser(0.3);
+        let ru = parser.parse("помощь").await.unwrap();
+        assert_eq!(ru.language, Language::Russian);
+        assert_eq!(ru.source, ParseSource::Llm);
+
+        let fr = parser
+            .parse("peux-tu ouvrir le fichier rapport.txt s'il te plaît")
+            .await
+            .unwrap();
+        assert_eq!(fr.language, Language::Unknown);
+        assert_eq!(fr.source, ParseSource::Llm);
+    }
 }
@@ -70,7 +70,7 @@ fn normalize_cron_expr(expr: &str) -> String {
 }
 
 /// Parse a cron expression string into a [`cron::Schedule`].
-fn parse_schedule(expr: &str) -> Result<cron::Schedule> {
+pub(crate) fn parse_schedule(expr: &str) -> Result<cron::Schedule> {
     let normalized = normalize_cron_expr(expr);
     cron::Schedule::from_str(&normalized).map_err(|e| IntentError::InvalidCronExpression {
         expression: expr.to_string(),
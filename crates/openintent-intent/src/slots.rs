@@ -0,0 +1,197 @@
+//! Slot declarations for intents.
+//!
+//! An [`IntentDefinition`] declares the typed slots an action needs.  After
+//! parsing, the extracted entities are converted into [`SlotValue`]s and any
+//! required slot that is absent (or does not convert to its declared type)
+//! is reported as missing, so the caller can ask a follow-up question and
+//! feed the answer back through [`IntentParser::fill_slot`].
+//!
+//! [`IntentParser::fill_slot`]: crate::parser::IntentParser::fill_slot
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// The type a slot value must convert to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlotType {
+    /// Free text, accepted as-is.
+    Text,
+    /// A signed integer.
+    Integer,
+    /// A floating-point number.
+    Number,
+    /// `true`/`false`, `yes`/`no`, or `on`/`off`.
+    Boolean,
+}
+
+/// A typed slot value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SlotValue {
+    Boolean(bool),
+    Integer(i64),
+    Number(f64),
+    Text(String),
+}
+
+/// Declaration of a single slot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlotSpec {
+    /// Slot name; matches the entity key produced by the parser.
+    pub name: String,
+    /// Type the raw entity value is converted to.
+    #[serde(rename = "type")]
+    pub slot_type: SlotType,
+    /// Whether the intent is incomplete without this slot.
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// The slots declared for one action.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntentDefinition {
+    /// The action these slots belong to (e.g. `"cron_create"`).
+    pub action: String,
+    /// Declared slots, in the order missing ones should be asked for.
+    #[serde(default)]
+    pub slots: Vec<SlotSpec>,
+}
+
+// ---------------------------------------------------------------------------
+// Implementation
+// ---------------------------------------------------------------------------
+
+impl SlotType {
+    /// Convert a raw entity value, returning `None` if it does not fit.
+    pub fn convert(self, raw: &str) -> Option<SlotValue> {
+        let raw = raw.trim();
+        if raw.is_empty() {
+            return None;
+        }
+        match self {
+            Self::Text => Some(SlotValue::Text(raw.to_string())),
+            Self::Integer => raw.parse().ok().map(SlotValue::Integer),
+            Self::Number => raw
+                .parse::<f64>()
+                .ok()
+                .filter(|n| n.is_finite())
+                .map(SlotValue::Number),
+            Self::Boolean => match raw.to_lowercase().as_str() {
+                "true" | "yes" | "on" => Some(SlotValue::Boolean(true)),
+                "false" | "no" | "off" => Some(SlotValue::Boolean(false)),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl IntentDefinition {
+    /// Create a definition with no slots.
+    pub fn new(action: impl Into<String>) -> Self {
+        Self {
+            action: action.into(),
+            slots: Vec::new(),
+        }
+    }
+
+    /// Declare a required slot.
+    pub fn required(mut self, name: impl Into<String>, slot_type: SlotType) -> Self {
+        self.slots.push(SlotSpec {
+            name: name.into(),
+            slot_type,
+            required: true,
+        });
+        self
+    }
+
+    /// Declare an optional slot.
+    pub fn optional(mut self, name: impl Into<String>, slot_type: SlotType) -> Self {
+        self.slots.push(SlotSpec {
+            name: name.into(),
+            slot_type,
+            required: false,
+        });
+        self
+    }
+
+    /// Look up a declared slot by name.
+    pub fn slot(&self, name: &str) -> Option<&SlotSpec> {
+        self.slots.iter().find(|s| s.name == name)
+    }
+
+    /// Convert `entities` into typed slots.
+    ///
+    /// Returns the filled slots and the names of required slots that are
+    /// absent or failed to convert, in declaration order.
+    pub fn fill(
+        &self,
+        entities: &HashMap<String, String>,
+    ) -> (HashMap<String, SlotValue>, Vec<String>) {
+        let mut filled = HashMap::new();
+        let mut missing = Vec::new();
+        for spec in &self.slots {
+            match entities
+                .get(&spec.name)
+                .and_then(|raw| spec.slot_type.convert(raw))
+            {
+                Some(value) => {
+                    filled.insert(spec.name.clone(), value);
+                }
+                None if spec.required => missing.push(spec.name.clone()),
+                None => {}
+            }
+        }
+        (filled, missing)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_by_type() {
+        assert_eq!(
+            SlotType::Integer.convert(" 42 "),
+            Some(SlotValue::Integer(42))
+        );
+        assert_eq!(SlotType::Integer.convert("4.2"), None);
+        assert_eq!(
+            SlotType::Number.convert("4.2"),
+            Some(SlotValue::Number(4.2))
+        );
+        assert_eq!(SlotType::Number.convert("NaN"), None);
+        assert_eq!(
+            SlotType::Boolean.convert("Yes"),
+            Some(SlotValue::Boolean(true))
+        );
+        assert_eq!(SlotType::Boolean.convert("maybe"), None);
+        assert_eq!(SlotType::Text.convert("   "), None);
+    }
+
+    #[test]
+    fn fill_reports_absent_and_invalid_required_slots() {
+        let def = IntentDefinition::new("timer_set")
+            .required("label", SlotType::Text)
+            .required("minutes", SlotType::Integer)
+            .optional("repeat", SlotType::Boolean);
+
+        let entities = HashMap::from([
+            ("minutes".to_string(), "soon".to_string()),
+            ("repeat".to_string(), "no".to_string()),
+        ]);
+        let (filled, missing) = def.fill(&entities);
+        assert_eq!(missing, vec!["label", "minutes"]);
+        assert_eq!(filled.get("repeat"), Some(&SlotValue::Boolean(false)));
+    }
+}
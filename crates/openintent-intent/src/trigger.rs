@@ -2,14 +2,30 @@
 //!
 //! Triggers determine how a workflow starts: manually, on a cron schedule,
 //! or in response to a system event.
+//!
+//! Event triggers can debounce bursts of identical events and deduplicate
+//! them by a payload field, so a storm of notifications about one logical
+//! change starts a single workflow run.
+//!
+//! Cron triggers remember when they last fired; on startup
+//! [`TriggerManager::catch_up`] applies each trigger's [`MisfirePolicy`] to
+//! the scheduled times missed while the process was down.
 
 use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::time::{Duration, Instant};
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use tracing::{debug, info};
+use serde_json::Value;
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
 use crate::error::{IntentError, Result};
+use crate::scheduler::parse_schedule;
+
+/// Upper bound on the runs [`MisfirePolicy::FireAll`] starts for one trigger.
+const MAX_CATCH_UP_RUNS: usize = 100;
 
 // ---------------------------------------------------------------------------
 // Types
@@ -33,21 +49,47 @@ pub enum TriggerType {
     Cron {
         /// Cron expression string.
         expression: String,
+        /// What to do about scheduled times missed while the process was
+        /// down.
+        #[serde(default)]
+        misfire: MisfirePolicy,
     },
 
     /// Triggered in response to a named system event.
     Event {
         /// The event name to listen for (e.g. "file_changed", "task_completed").
         event_name: String,
+        /// Coalesce events arriving within this many milliseconds of the
+        /// last run into that run.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        debounce_ms: Option<u64>,
+        /// Payload field identifying the logical change.  Events are only
+        /// coalesced with events carrying the same value; without
+        /// `debounce_ms`, each value fires at most once.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dedup_key: Option<String>,
     },
 }
 
+/// How a cron trigger handles scheduled times missed during downtime.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MisfirePolicy {
+    /// Drop missed runs and wait for the next scheduled time.
+    #[default]
+    Skip,
+    /// Start one run if any scheduled time was missed.
+    FireOnce,
+    /// Start one run per missed scheduled time.
+    FireAll,
+}
+
 impl std::fmt::Display for TriggerType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Manual => write!(f, "manual"),
-            Self::Cron { expression } => write!(f, "cron({expression})"),
-            Self::Event { event_name } => write!(f, "event({event_name})"),
+            Self::Cron { expression, .. } => write!(f, "cron({expression})"),
+            Self::Event { event_name, .. } => write!(f, "event({event_name})"),
         }
     }
 }
@@ -61,6 +103,10 @@ struct RegisteredTrigger {
     workflow_id: Uuid,
     /// Whether this trigger is currently active.
     active: bool,
+    /// Parsed schedule of a cron trigger.
+    schedule: Option<cron::Schedule>,
+    /// When the trigger last fired, if known.
+    last_fired: Option<DateTime<Utc>>,
 }
 
 // ---------------------------------------------------------------------------
@@ -75,6 +121,9 @@ struct RegisteredTrigger {
 pub struct TriggerManager {
     /// Registered triggers, keyed by a unique trigger ID.
     triggers: HashMap<Uuid, RegisteredTrigger>,
+    /// When debounced or deduplicated event triggers last fired, keyed by
+    /// trigger ID and dedup value.
+    recent_events: HashMap<(Uuid, String), Instant>,
 }
 
 impl TriggerManager {
@@ -82,6 +131,7 @@ impl TriggerManager {
     pub fn new() -> Self {
         Self {
             triggers: HashMap::new(),
+            recent_events: HashMap::new(),
         }
     }
 
@@ -89,10 +139,10 @@ impl TriggerManager {
     ///
     /// Returns the unique trigger ID.
     pub fn register(&mut self, workflow_id: Uuid, trigger: TriggerType) -> Result<Uuid> {
-        // Validate cron expressions (basic check for now).
-        if let TriggerType::Cron { ref expression } = trigger {
-            Self::validate_cron(expression)?;
-        }
+        let schedule = match trigger {
+            TriggerType::Cron { ref expression, .. } => Some(parse_schedule(expression)?),
+            _ => None,
+        };
 
         let trigger_id = Uuid::now_v7();
         info!(
@@ -108,6 +158,8 @@ impl TriggerManager {
                 trigger,
                 workflow_id,
                 active: true,
+                schedule,
+                last_fired: None,
             },
         );
 
@@ -149,33 +201,157 @@ impl TriggerManager {
         Ok(())
     }
 
+    /// Record that a trigger fired at `at`, e.g. when restoring persisted
+    /// state on startup.
+    pub fn record_fired(&mut self, trigger_id: &Uuid, at: DateTime<Utc>) -> Result<()> {
+        let trigger = self.triggers.get_mut(trigger_id).ok_or_else(|| {
+            IntentError::TriggerRegistrationFailed {
+                reason: format!("trigger {trigger_id} not found"),
+            }
+        })?;
+        trigger.last_fired = Some(at);
+        Ok(())
+    }
+
+    /// When a trigger last fired, if known.
+    pub fn last_fired(&self, trigger_id: &Uuid) -> Option<DateTime<Utc>> {
+        self.triggers.get(trigger_id)?.last_fired
+    }
+
+    /// Apply each active cron trigger's misfire policy to the scheduled
+    /// times between its last firing and `now`.
+    ///
+    /// Returns one workflow ID per run to start.  Triggers that have never
+    /// fired are left alone.  Every trigger that missed a time is marked as
+    /// fired at `now`, so calling this again does not repeat the catch-up.
+    pub fn catch_up(&mut self, now: DateTime<Utc>) -> Vec<Uuid> {
+        let mut workflow_ids = Vec::new();
+
+        for (trigger_id, registered) in &mut self.triggers {
+            if !registered.active {
+                continue;
+            }
+            let (TriggerType::Cron { misfire, .. }, Some(schedule), Some(last_fired)) = (
+                &registered.trigger,
+                &registered.schedule,
+                registered.last_fired,
+            ) else {
+                continue;
+            };
+
+            let missed = schedule
+                .after(&last_fired)
+                .take_while(|at| *at <= now)
+                .take(MAX_CATCH_UP_RUNS + 1)
+                .count();
+            if missed == 0 {
+                continue;
+            }
+
+            let runs = match misfire {
+                MisfirePolicy::Skip => 0,
+                MisfirePolicy::FireOnce => 1,
+                MisfirePolicy::FireAll => {
+                    if missed > MAX_CATCH_UP_RUNS {
+                        warn!(
+                            trigger_id = %trigger_id,
+                            limit = MAX_CATCH_UP_RUNS,
+                            "too many missed cron runs, catching up on the limit"
+                        );
+                    }
+                    missed.min(MAX_CATCH_UP_RUNS)
+                }
+            };
+            info!(
+                trigger_id = %trigger_id,
+                workflow_id = %registered.workflow_id,
+                missed,
+                runs,
+                policy = ?misfire,
+                "catching up missed cron runs"
+            );
+
+            workflow_ids.extend(std::iter::repeat_n(registered.workflow_id, runs));
+            registered.last_fired = Some(now);
+        }
+
+        workflow_ids
+    }
+
     /// Fire all triggers that match a given event name.
     ///
     /// Returns the list of workflow IDs that should be executed.
-    pub fn fire_event(&self, event_name: &str) -> Vec<Uuid> {
+    pub fn fire_event(&mut self, event_name: &str) -> Vec<Uuid> {
+        self.fire_event_with(event_name, &Value::Null)
+    }
+
+    /// Fire all triggers that match a given event, using `payload` to
+    /// resolve dedup keys.
+    ///
+    /// Triggers whose debounce window or dedup key suppresses the event do
+    /// not fire.  Returns the list of workflow IDs that should be executed.
+    pub fn fire_event_with(&mut self, event_name: &str, payload: &Value) -> Vec<Uuid> {
+        let now = Instant::now();
+        let triggers = &self.triggers;
+        self.recent_events
+            .retain(|(trigger_id, _), fired_at| match triggers.get(trigger_id) {
+                Some(registered) => debounce_window(&registered.trigger)
+                    .is_none_or(|window| now.duration_since(*fired_at) < window),
+                None => false,
+            });
+
         let mut workflow_ids = Vec::new();
 
         for (trigger_id, registered) in &self.triggers {
             if !registered.active {
                 continue;
             }
-            if let TriggerType::Event {
+            let TriggerType::Event {
                 event_name: ref name,
+                debounce_ms,
+                ref dedup_key,
             } = registered.trigger
-                && name == event_name
-            {
-                debug!(
-                    trigger_id = %trigger_id,
-                    workflow_id = %registered.workflow_id,
-                    event = event_name,
-                    "event trigger fired"
-                );
-                workflow_ids.push(registered.workflow_id);
+            else {
+                continue;
+            };
+            if name != event_name {
+                continue;
+            }
+
+            if debounce_ms.is_some() || dedup_key.is_some() {
+                let key = dedup_key
+                    .as_deref()
+                    .map(|field| dedup_value(payload, field))
+                    .unwrap_or_default();
+                // Entries outside their window were pruned above, so an
+                // existing entry means the event is coalesced.
+                match self.recent_events.entry((*trigger_id, key)) {
+                    Entry::Occupied(entry) => {
+                        debug!(
+                            trigger_id = %trigger_id,
+                            event = event_name,
+                            dedup = %entry.key().1,
+                            "event coalesced into earlier run"
+                        );
+                        continue;
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(now);
+                    }
+                }
             }
+
+            debug!(
+                trigger_id = %trigger_id,
+                workflow_id = %registered.workflow_id,
+                event = event_name,
+                "event trigger fired"
+            );
+            workflow_ids.push(registered.workflow_id);
         }
 
         if workflow_ids.is_empty() {
-            debug!(event = event_name, "no triggers matched event");
+            debug!(event = event_name, "no triggers fired for event");
         }
 
         workflow_ids
@@ -190,31 +366,22 @@ impl TriggerManager {
     pub fn active_count(&self) -> usize {
         self.triggers.values().filter(|t| t.active).count()
     }
+}
 
-    // -- Internals -----------------------------------------------------------
-
-    /// Validate a cron expression using the `cron` crate.
-    ///
-    /// Accepts standard 5-field expressions (which are normalized by
-    /// prepending a `0` seconds field) as well as 6- and 7-field formats
-    /// supported by the `cron` crate directly.
-    fn validate_cron(expression: &str) -> Result<()> {
-        use std::str::FromStr;
-
-        let schedule_str = if expression.split_whitespace().count() == 5 {
-            format!("0 {expression}")
-        } else {
-            expression.to_string()
-        };
-
-        cron::Schedule::from_str(&schedule_str).map_err(|e| {
-            IntentError::InvalidCronExpression {
-                expression: expression.to_string(),
-                reason: format!("invalid cron expression: {e}"),
-            }
-        })?;
+/// How long a firing suppresses later events, or `None` for indefinitely.
+fn debounce_window(trigger: &TriggerType) -> Option<Duration> {
+    match trigger {
+        TriggerType::Event { debounce_ms, .. } => debounce_ms.map(Duration::from_millis),
+        _ => None,
+    }
+}
 
-        Ok(())
+/// The dedup value of `payload`: the `field` value, with strings unquoted.
+fn dedup_value(payload: &Value, field: &str) -> String {
+    match payload.get(field) {
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+        None => String::new(),
     }
 }
 
@@ -252,6 +419,7 @@ mod tests {
             wf_id,
             TriggerType::Cron {
                 expression: "0 9 * * 1-5".into(),
+                misfire: MisfirePolicy::default(),
             },
         );
         assert!(result.is_ok());
@@ -265,6 +433,7 @@ mod tests {
             wf_id,
             TriggerType::Cron {
                 expression: "bad".into(),
+                misfire: MisfirePolicy::default(),
             },
         );
         assert!(result.is_err());
@@ -278,6 +447,8 @@ mod tests {
             wf_id,
             TriggerType::Event {
                 event_name: "file_changed".into(),
+                debounce_ms: None,
+                dedup_key: None,
             },
         )
         .unwrap();
@@ -299,6 +470,8 @@ mod tests {
                 wf_id,
                 TriggerType::Event {
                     event_name: "test".into(),
+                    debounce_ms: None,
+                    dedup_key: None,
                 },
             )
             .unwrap();
@@ -311,4 +484,116 @@ mod tests {
         let fired = mgr.fire_event("test");
         assert_eq!(fired.len(), 1);
     }
+
+    #[test]
+    fn burst_within_debounce_window_fires_once() {
+        let mut mgr = TriggerManager::new();
+        let wf_id = Uuid::now_v7();
+        mgr.register(
+            wf_id,
+            TriggerType::Event {
+                event_name: "file_changed".into(),
+                debounce_ms: Some(60_000),
+                dedup_key: None,
+            },
+        )
+        .unwrap();
+
+        let runs: usize = (0..5).map(|_| mgr.fire_event("file_changed").len()).sum();
+        assert_eq!(runs, 1);
+    }
+
+    #[test]
+    fn debounced_trigger_fires_again_after_window() {
+        let mut mgr = TriggerManager::new();
+        mgr.register(
+            Uuid::now_v7(),
+            TriggerType::Event {
+                event_name: "tick".into(),
+                debounce_ms: Some(20),
+                dedup_key: None,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(mgr.fire_event("tick").len(), 1);
+        assert!(mgr.fire_event("tick").is_empty());
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(mgr.fire_event("tick").len(), 1);
+    }
+
+    #[test]
+    fn dedup_key_fires_once_per_logical_change() {
+        let mut mgr = TriggerManager::new();
+        mgr.register(
+            Uuid::now_v7(),
+            TriggerType::Event {
+                event_name: "file_changed".into(),
+                debounce_ms: None,
+                dedup_key: Some("path".into()),
+            },
+        )
+        .unwrap();
+
+        let event = |path: &str| serde_json::json!({ "path": path, "kind": "modify" });
+        let mut runs = 0;
+        for path in ["a.txt", "a.txt", "b.txt", "a.txt", "b.txt"] {
+            runs += mgr.fire_event_with("file_changed", &event(path)).len();
+        }
+        assert_eq!(runs, 2);
+    }
+
+    #[test]
+    fn catch_up_applies_misfire_policy() {
+        use chrono::TimeZone;
+
+        let mut mgr = TriggerManager::new();
+        let last_fired = Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap();
+        // Down from just after the 1st's run until after the 3rd's: the
+        // runs on the 2nd and 3rd were missed.
+        let now = Utc.with_ymd_and_hms(2026, 3, 3, 10, 0, 0).unwrap();
+
+        let mut workflows = HashMap::new();
+        for misfire in [
+            MisfirePolicy::Skip,
+            MisfirePolicy::FireOnce,
+            MisfirePolicy::FireAll,
+        ] {
+            let wf_id = Uuid::now_v7();
+            let trigger_id = mgr
+                .register(
+                    wf_id,
+                    TriggerType::Cron {
+                        expression: "0 9 * * *".into(),
+                        misfire,
+                    },
+                )
+                .unwrap();
+            mgr.record_fired(&trigger_id, last_fired).unwrap();
+            workflows.insert(misfire, (wf_id, trigger_id));
+        }
+        // A trigger with no firing history has nothing to catch up on.
+        mgr.register(
+            Uuid::now_v7(),
+            TriggerType::Cron {
+                expression: "0 9 * * *".into(),
+                misfire: MisfirePolicy::FireAll,
+            },
+        )
+        .unwrap();
+
+        let fired = mgr.catch_up(now);
+        let runs = |policy| {
+            let (wf_id, _) = workflows[&policy];
+            fired.iter().filter(|id| **id == wf_id).count()
+        };
+        assert_eq!(runs(MisfirePolicy::Skip), 0);
+        assert_eq!(runs(MisfirePolicy::FireOnce), 1);
+        assert_eq!(runs(MisfirePolicy::FireAll), 2);
+        assert_eq!(fired.len(), 3);
+
+        let (_, trigger_id) = workflows[&MisfirePolicy::Skip];
+        assert_eq!(mgr.last_fired(&trigger_id), Some(now));
+        assert!(mgr.catch_up(now).is_empty());
+    }
 }
@@ -0,0 +1,191 @@
+//! Fast-path trigger tables.
+//!
+//! The parser's fast path resolves well-known commands from a leading
+//! keyword, without calling the LLM.  The keywords are user-facing text, so
+//! they live in the `[intent.triggers]` section of `config/default.toml`,
+//! one table per ISO 639-1 language code:
+//!
+//! ```toml
+//! [[intent.triggers.en]]
+//! keywords = ["ls", "list", "dir"]
+//! action = "fs_list_directory"
+//! entity = "path"
+//! default = "."
+//! confidence = 0.90
+//! ```
+//!
+//! Keywords match the first word of the input, or its start in languages
+//! written without spaces (see [`Language::separates_words`]).
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Deserialize;
+use tracing::warn;
+
+use crate::error::{IntentError, Result};
+use crate::language::Language;
+use crate::parser::IntentAlternative;
+
+/// Configuration file the default trigger tables are read from.
+const CONFIG_PATH: &str = "config/default.toml";
+
+/// A leading keyword (or set of synonyms) and the intent it produces.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Trigger {
+    /// Lowercase keywords that select this trigger.
+    pub keywords: Vec<String>,
+    /// Action of the resulting intent.
+    pub action: String,
+    /// Entity that receives the text after the keyword, if any.
+    #[serde(default)]
+    pub entity: Option<String>,
+    /// Entity value when nothing follows the keyword.  Without one, the
+    /// trigger only matches when text follows.
+    #[serde(default)]
+    pub default: Option<String>,
+    /// Confidence of the resulting intent.
+    pub confidence: f64,
+    /// Runner-up actions, for keywords that commonly mean more than one
+    /// thing (e.g. "show" a file or a directory).
+    #[serde(default)]
+    pub alternatives: Vec<IntentAlternative>,
+}
+
+/// A trigger that matched, and the text after its keyword.
+pub(crate) struct TriggerMatch<'a> {
+    pub trigger: &'a Trigger,
+    pub rest: String,
+}
+
+/// Trigger tables keyed by language code.
+#[derive(Debug, Clone, Default)]
+pub struct TriggerTables {
+    tables: HashMap<String, Vec<Trigger>>,
+}
+
+impl TriggerTables {
+    /// Read the `[intent.triggers]` section of a configuration file's
+    /// contents.  A file without the section yields no tables.
+    pub fn from_toml(content: &str) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct Root {
+            #[serde(default)]
+            intent: IntentToml,
+        }
+
+        #[derive(Deserialize, Default)]
+        struct IntentToml {
+            #[serde(default)]
+            triggers: HashMap<String, Vec<Trigger>>,
+        }
+
+        let root: Root =
+            toml::from_str(content).map_err(|e| IntentError::InvalidTriggerConfig {
+                reason: e.to_string(),
+            })?;
+        let mut tables = root.intent.triggers;
+        for trigger in tables.values_mut().flatten() {
+            if trigger.keywords.iter().any(|k| k.trim().is_empty()) {
+                return Err(IntentError::InvalidTriggerConfig {
+                    reason: format!("empty keyword in trigger for `{}`", trigger.action),
+                });
+            }
+            for keyword in &mut trigger.keywords {
+                *keyword = keyword.trim().to_lowercase();
+            }
+        }
+        Ok(Self { tables })
+    }
+
+    /// Read the trigger tables from a configuration file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content =
+            std::fs::read_to_string(path).map_err(|e| IntentError::InvalidTriggerConfig {
+                reason: format!("cannot read {}: {e}", path.display()),
+            })?;
+        Self::from_toml(&content)
+    }
+
+    /// Read the trigger tables from `config/default.toml`, or none when the
+    /// file is missing or malformed.
+    pub fn load_default() -> Self {
+        Self::load(Path::new(CONFIG_PATH)).unwrap_or_else(|e| {
+            warn!(error = %e, "no fast-path triggers loaded");
+            Self::default()
+        })
+    }
+
+    /// The triggers for `language`, empty when it has no table.
+    pub fn for_language(&self, language: Language) -> &[Trigger] {
+        self.tables.get(language.code()).map_or(&[], Vec::as_slice)
+    }
+
+    /// The first trigger whose keyword starts `lower`, which must already be
+    /// lowercased.
+    pub(crate) fn find(&self, lower: &str, language: Language) -> Option<TriggerMatch<'_>> {
+        self.for_language(language).iter().find_map(|trigger| {
+            trigger.keywords.iter().find_map(|keyword| {
+                let rest = lower.strip_prefix(keyword.as_str())?;
+                if language.separates_words()
+                    && !rest.is_empty()
+                    && !rest.starts_with(char::is_whitespace)
+                {
+                    return None;
+                }
+                Some(TriggerMatch {
+                    trigger,
+                    rest: rest.trim().to_string(),
+                })
+            })
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+        [[intent.triggers.en]]
+        keywords = ["LS", "list"]
+        action = "fs_list_directory"
+        entity = "path"
+        default = "."
+        confidence = 0.9
+
+        [[intent.triggers.zh]]
+        keywords = ["列出"]
+        action = "fs_list_directory"
+        entity = "path"
+        confidence = 0.9
+    "#;
+
+    #[test]
+    fn keywords_match_whole_words_or_prefixes() {
+        let tables = TriggerTables::from_toml(CONFIG).unwrap();
+
+        let m = tables.find("ls /tmp", Language::English).unwrap();
+        assert_eq!(m.trigger.action, "fs_list_directory");
+        assert_eq!(m.rest, "/tmp");
+        assert!(tables.find("lsof -i", Language::English).is_none());
+
+        let m = tables.find("列出/tmp", Language::Chinese).unwrap();
+        assert_eq!(m.rest, "/tmp");
+
+        assert!(tables.for_language(Language::Russian).is_empty());
+    }
+
+    #[test]
+    fn missing_section_yields_no_tables() {
+        let tables = TriggerTables::from_toml("[general]\nname = \"x\"").unwrap();
+        assert!(tables.for_language(Language::English).is_empty());
+
+        let err = TriggerTables::from_toml("[[intent.triggers.en]]\nkeywords = [\"ls\"]");
+        assert!(matches!(err, Err(IntentError::InvalidTriggerConfig { .. })));
+    }
+}
@@ -0,0 +1,128 @@
+//! Run checkpoints persisted to the [`WorkflowStore`].
+//!
+//! After every step the engine writes the workflow definition together with
+//! the run's [`Progress`], so a paused or interrupted run can continue from
+//! the first step that has not completed yet.
+
+use openintent_store::WorkflowStore;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{StepResult, Workflow, WorkflowStatus};
+use crate::condition::ConditionContext;
+use crate::error::{IntentError, Result};
+
+/// How far a workflow run has progressed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(super) struct Progress {
+    /// Index of the next step to execute.
+    pub next_step: usize,
+    /// Results of the steps executed so far, in execution order.
+    pub step_results: Vec<StepResult>,
+    /// Step results as seen by conditions and templates.
+    pub context: ConditionContext,
+    /// Whether an unhandled step failure has occurred.
+    pub had_failure: bool,
+}
+
+/// The progress of a workflow run, as of its last checkpoint.
+#[derive(Debug, Clone)]
+pub struct RunStatus {
+    /// The run being reported.
+    pub run_id: Uuid,
+    /// The workflow the run executes.
+    pub workflow_id: Uuid,
+    /// Name of the workflow.
+    pub workflow_name: String,
+    /// `Running`, `Paused`, `Completed`, or `Failed`.
+    pub status: WorkflowStatus,
+    /// Index of the next step to execute.
+    pub next_step: usize,
+    /// Number of steps in the workflow.
+    pub total_steps: usize,
+    /// Results of the steps executed so far, in execution order.
+    pub step_results: Vec<StepResult>,
+}
+
+#[derive(Serialize)]
+struct CheckpointRef<'a> {
+    workflow: &'a Workflow,
+    progress: &'a Progress,
+}
+
+#[derive(Deserialize)]
+struct Checkpoint {
+    workflow: Workflow,
+    progress: Progress,
+}
+
+/// The status label stored alongside a checkpoint.
+fn status_label(status: WorkflowStatus) -> &'static str {
+    match status {
+        WorkflowStatus::Idle => "idle",
+        WorkflowStatus::Running => "running",
+        WorkflowStatus::Completed => "completed",
+        WorkflowStatus::Failed => "failed",
+        WorkflowStatus::Cancelled => "cancelled",
+        WorkflowStatus::Skipped => "skipped",
+        WorkflowStatus::Paused => "paused",
+    }
+}
+
+/// Persist the state of run `run_id`, labelled with the workflow's status.
+pub(super) async fn save(
+    store: &WorkflowStore,
+    run_id: Uuid,
+    workflow: &Workflow,
+    progress: &Progress,
+) -> Result<()> {
+    let checkpoint = serde_json::to_value(CheckpointRef { workflow, progress })?;
+    store
+        .save_run(
+            &run_id.to_string(),
+            &workflow.id.to_string(),
+            status_label(workflow.status),
+            &checkpoint,
+        )
+        .await?;
+    Ok(())
+}
+
+/// Load the last checkpoint of run `run_id`, whatever its state.
+async fn load_any(store: &WorkflowStore, run_id: Uuid) -> Result<(String, Checkpoint)> {
+    let run = store.get_run(&run_id.to_string()).await?.ok_or_else(|| {
+        IntentError::InvalidWorkflowState {
+            reason: format!("workflow run {run_id} not found"),
+        }
+    })?;
+    Ok((run.status, serde_json::from_value(run.checkpoint)?))
+}
+
+/// Summarise the progress of run `run_id`.
+pub(super) async fn status(store: &WorkflowStore, run_id: Uuid) -> Result<RunStatus> {
+    let (_, Checkpoint { workflow, progress }) = load_any(store, run_id).await?;
+    Ok(RunStatus {
+        run_id,
+        workflow_id: workflow.id,
+        workflow_name: workflow.name,
+        status: workflow.status,
+        next_step: progress.next_step,
+        total_steps: workflow.steps.len(),
+        step_results: progress.step_results,
+    })
+}
+
+/// Load a run that can be resumed (one that is paused or was interrupted
+/// while running).
+pub(super) async fn load(store: &WorkflowStore, run_id: Uuid) -> Result<(Workflow, Progress)> {
+    let (status, Checkpoint { workflow, progress }) = load_any(store, run_id).await?;
+
+    let resumable = [WorkflowStatus::Paused, WorkflowStatus::Running].map(status_label);
+    if !resumable.contains(&status.as_str()) {
+        return Err(IntentError::InvalidWorkflowState {
+            reason: format!("cannot resume workflow run {run_id} in `{status}` state"),
+        });
+    }
+
+    Ok((workflow, progress))
+}
@@ -0,0 +1,947 @@
+//! Workflow engine — define and execute multi-step workflows.
+//!
+//! A workflow is an ordered sequence of steps, each of which invokes a tool
+//! on a specific adapter.  The engine handles execution, error propagation,
+//! and result chaining between steps.
+//!
+//! Steps run in order by default.  A step may carry a [`Condition`] over
+//! earlier results and `on_success` / `on_failure` targets naming a later
+//! step, which lets a workflow skip steps or branch.  Consecutive steps that
+//! share a `parallel_group` run concurrently and are joined before the
+//! workflow moves on.  Step parameters can reference earlier outputs with
+//! `${steps.<id>.output.<field>}` templates.
+//!
+//! With a [`WorkflowStore`] attached, every run is checkpointed after each
+//! step so it can be paused and later resumed, even after a restart.
+
+mod checkpoint;
+mod template;
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+
+use openintent_agent::runtime::ToolAdapter;
+use openintent_store::{StoredWorkflow, WorkflowStore};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinSet;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+pub use self::checkpoint::RunStatus;
+
+use self::checkpoint::Progress;
+use crate::condition::{Condition, ConditionContext};
+use crate::error::{IntentError, Result};
+use crate::trigger::TriggerType;
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+/// The current execution status of a workflow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkflowStatus {
+    /// The workflow is defined but has not been started.
+    Idle,
+    /// The workflow is currently executing.
+    Running,
+    /// The workflow completed successfully.
+    Completed,
+    /// The workflow failed during execution.
+    Failed,
+    /// The workflow was cancelled by the user.
+    Cancelled,
+    /// The step was not run because its condition was false.
+    Skipped,
+    /// The run was paused at a step boundary and can be resumed.
+    Paused,
+}
+
+/// What a parallel group does when one of its steps fails.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupFailurePolicy {
+    /// Cancel the group's other running steps as soon as one fails.
+    #[default]
+    FailFast,
+    /// Let every step of the group finish before reporting the failure.
+    Continue,
+}
+
+/// A single step within a workflow.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowStep {
+    /// Human-readable description of what this step does.
+    pub action: String,
+    /// The adapter that owns the tool (e.g. "filesystem", "shell").
+    pub adapter: String,
+    /// The tool to invoke on the adapter.
+    pub tool: String,
+    /// JSON parameters to pass to the tool.
+    pub params: serde_json::Value,
+    /// Identifier used by conditions and branch targets of other steps.
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Condition over earlier step results (see [`Condition`]).  When it is
+    /// false the step is skipped and execution follows `on_failure`.
+    #[serde(default)]
+    pub condition: Option<String>,
+    /// Id of a later step to continue with after this step succeeds.
+    /// Defaults to the next step.
+    #[serde(default)]
+    pub on_success: Option<String>,
+    /// Id of a later step to continue with after this step fails or is
+    /// skipped.  Defaults to the next step; a failure with a target set is
+    /// treated as handled and does not fail the workflow.
+    #[serde(default)]
+    pub on_failure: Option<String>,
+    /// Consecutive steps with the same group id run concurrently.  Grouped
+    /// steps cannot branch, and their conditions only see results from
+    /// before the group.
+    #[serde(default)]
+    pub parallel_group: Option<String>,
+}
+
+impl WorkflowStep {
+    /// Create a step that runs `tool` on `adapter` with `params`.
+    pub fn new(
+        action: impl Into<String>,
+        adapter: impl Into<String>,
+        tool: impl Into<String>,
+        params: serde_json::Value,
+    ) -> Self {
+        Self {
+            action: action.into(),
+            adapter: adapter.into(),
+            tool: tool.into(),
+            params,
+            id: None,
+            condition: None,
+            on_success: None,
+            on_failure: None,
+            parallel_group: None,
+        }
+    }
+
+    /// Set the identifier of this step.
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Only run this step when `condition` holds.
+    pub fn with_condition(mut self, condition: impl Into<String>) -> Self {
+        self.condition = Some(condition.into());
+        self
+    }
+
+    /// Continue with the step `id` after this step succeeds.
+    pub fn with_on_success(mut self, id: impl Into<String>) -> Self {
+        self.on_success = Some(id.into());
+        self
+    }
+
+    /// Continue with the step `id` after this step fails or is skipped.
+    pub fn with_on_failure(mut self, id: impl Into<String>) -> Self {
+        self.on_failure = Some(id.into());
+        self
+    }
+
+    /// Run this step concurrently with its neighbours in `group`.
+    pub fn with_parallel_group(mut self, group: impl Into<String>) -> Self {
+        self.parallel_group = Some(group.into());
+        self
+    }
+}
+
+/// A complete workflow definition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workflow {
+    /// Unique identifier.
+    pub id: Uuid,
+    /// Human-readable name.
+    pub name: String,
+    /// Optional description.
+    pub description: Option<String>,
+    /// The ordered sequence of steps to execute.
+    pub steps: Vec<WorkflowStep>,
+    /// How this workflow is triggered.
+    pub trigger: TriggerType,
+    /// Whether this workflow is enabled.
+    pub enabled: bool,
+    /// Current execution status.
+    pub status: WorkflowStatus,
+    /// Failure policy per parallel group; unlisted groups fail fast.
+    #[serde(default)]
+    pub group_policies: HashMap<String, GroupFailurePolicy>,
+}
+
+impl Workflow {
+    /// Create a new workflow with the given name and steps.
+    pub fn new(name: impl Into<String>, steps: Vec<WorkflowStep>) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            name: name.into(),
+            description: None,
+            steps,
+            trigger: TriggerType::Manual,
+            enabled: true,
+            status: WorkflowStatus::Idle,
+            group_policies: HashMap::new(),
+        }
+    }
+
+    /// Set the description for this workflow.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the trigger for this workflow.
+    pub fn with_trigger(mut self, trigger: TriggerType) -> Self {
+        self.trigger = trigger;
+        self
+    }
+
+    /// Set the failure policy of a parallel group.
+    pub fn with_group_policy(
+        mut self,
+        group: impl Into<String>,
+        policy: GroupFailurePolicy,
+    ) -> Self {
+        self.group_policies.insert(group.into(), policy);
+        self
+    }
+
+    /// Build a workflow from its persisted definition.
+    ///
+    /// A missing trigger means the workflow is started manually.
+    pub fn from_stored(stored: &StoredWorkflow) -> Result<Self> {
+        let id = Uuid::parse_str(&stored.id).map_err(|e| IntentError::InvalidWorkflowState {
+            reason: format!("invalid workflow id `{}`: {e}", stored.id),
+        })?;
+        let steps = serde_json::from_value(stored.steps.clone())?;
+        let trigger = stored
+            .trigger
+            .clone()
+            .map(serde_json::from_value)
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(Self {
+            id,
+            name: stored.name.clone(),
+            description: stored.description.clone(),
+            steps,
+            trigger,
+            enabled: stored.enabled,
+            status: WorkflowStatus::Idle,
+            group_policies: HashMap::new(),
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Workflow result
+// ---------------------------------------------------------------------------
+
+/// The result of executing a single workflow step.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepResult {
+    /// The index of this step in the workflow.
+    pub step_index: usize,
+    /// The tool that was executed.
+    pub tool: String,
+    /// Whether the step succeeded.
+    pub success: bool,
+    /// `Completed`, `Failed`, `Skipped`, or `Cancelled` (a parallel step
+    /// stopped by a fail-fast group).
+    pub status: WorkflowStatus,
+    /// The output returned by the tool.
+    pub output: serde_json::Value,
+}
+
+impl StepResult {
+    /// The result of a step that did not run.
+    fn not_run(index: usize, step: &WorkflowStep, status: WorkflowStatus) -> Self {
+        Self {
+            step_index: index,
+            tool: step.tool.clone(),
+            success: false,
+            status,
+            output: serde_json::Value::Null,
+        }
+    }
+
+    /// The result of a step that failed with `error`.
+    fn failed(index: usize, step: &WorkflowStep, error: String) -> Self {
+        Self {
+            step_index: index,
+            tool: step.tool.clone(),
+            success: false,
+            status: WorkflowStatus::Failed,
+            output: serde_json::json!({ "error": error }),
+        }
+    }
+
+    /// The value conditions see for this step under `steps.<id>`.
+    fn condition_value(&self) -> serde_json::Value {
+        serde_json::json!({
+            "success": self.success,
+            "status": self.status,
+            "output": self.output,
+        })
+    }
+}
+
+/// Conditions, branch targets and parallel groups of a workflow, resolved
+/// to step indices.
+struct ExecutionPlan {
+    conditions: Vec<Option<Condition>>,
+    on_success: Vec<Option<usize>>,
+    on_failure: Vec<Option<usize>>,
+    /// For the first step of a parallel group, the steps of the group.
+    groups: HashMap<usize, Range<usize>>,
+}
+
+impl ExecutionPlan {
+    /// Parse conditions, resolve branch targets and collect parallel groups.
+    /// Targets must name a later step, which guarantees that execution
+    /// terminates.
+    fn new(steps: &[WorkflowStep]) -> Result<Self> {
+        let mut ids = HashMap::new();
+        for (index, step) in steps.iter().enumerate() {
+            if let Some(id) = &step.id
+                && ids.insert(id.as_str(), index).is_some()
+            {
+                return Err(IntentError::InvalidWorkflowState {
+                    reason: format!("duplicate step id `{id}`"),
+                });
+            }
+        }
+
+        let resolve_id = |index: usize, target: &Option<String>| -> Result<Option<usize>> {
+            let Some(target) = target else {
+                return Ok(None);
+            };
+            match ids.get(target.as_str()) {
+                Some(&t) if t > index => Ok(Some(t)),
+                Some(_) => Err(IntentError::InvalidWorkflowState {
+                    reason: format!("step {index} branches backwards to `{target}`"),
+                }),
+                None => Err(IntentError::InvalidWorkflowState {
+                    reason: format!("step {index} branches to unknown step `{target}`"),
+                }),
+            }
+        };
+
+        let groups = Self::parallel_groups(steps)?;
+        let grouped = |index: usize| {
+            groups
+                .iter()
+                .find(|(_, range)| range.contains(&index))
+                .map(|(&start, _)| start)
+        };
+        let resolve = |index: usize, target: &Option<String>| -> Result<Option<usize>> {
+            let resolved = resolve_id(index, target)?;
+            if grouped(index).is_some() {
+                return match resolved {
+                    Some(_) => Err(IntentError::InvalidWorkflowState {
+                        reason: format!("step {index} is in a parallel group and cannot branch"),
+                    }),
+                    None => Ok(None),
+                };
+            }
+            if let Some(t) = resolved
+                && grouped(t).is_some_and(|start| start != t)
+            {
+                return Err(IntentError::InvalidWorkflowState {
+                    reason: format!("step {index} branches into the middle of a parallel group"),
+                });
+            }
+            Ok(resolved)
+        };
+
+        let mut plan = Self {
+            conditions: Vec::with_capacity(steps.len()),
+            on_success: Vec::with_capacity(steps.len()),
+            on_failure: Vec::with_capacity(steps.len()),
+            groups: groups.clone(),
+        };
+        for (index, step) in steps.iter().enumerate() {
+            plan.conditions.push(
+                step.condition
+                    .as_deref()
+                    .map(Condition::parse)
+                    .transpose()?,
+            );
+            plan.on_success.push(resolve(index, &step.on_success)?);
+            plan.on_failure.push(resolve(index, &step.on_failure)?);
+        }
+        Ok(plan)
+    }
+
+    /// Collect runs of consecutive steps sharing a `parallel_group`.
+    fn parallel_groups(steps: &[WorkflowStep]) -> Result<HashMap<usize, Range<usize>>> {
+        let mut groups = HashMap::new();
+        let mut seen = HashSet::new();
+        let mut index = 0;
+        while index < steps.len() {
+            let Some(group) = &steps[index].parallel_group else {
+                index += 1;
+                continue;
+            };
+            if !seen.insert(group.as_str()) {
+                return Err(IntentError::InvalidWorkflowState {
+                    reason: format!("steps of parallel group `{group}` are not consecutive"),
+                });
+            }
+            let end = steps[index..]
+                .iter()
+                .position(|s| s.parallel_group.as_ref() != Some(group))
+                .map_or(steps.len(), |len| index + len);
+            groups.insert(index, index..end);
+            index = end;
+        }
+        Ok(groups)
+    }
+}
+
+/// The result of executing an entire workflow.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowResult {
+    /// The workflow that was executed.
+    pub workflow_id: Uuid,
+    /// Identifies this run for [`WorkflowEngine::pause`] and
+    /// [`WorkflowEngine::resume`].
+    pub run_id: Uuid,
+    /// `Completed`, `Failed`, or `Paused`.
+    pub status: WorkflowStatus,
+    /// Whether all steps completed successfully.  Always `false` for a
+    /// paused run.
+    pub success: bool,
+    /// Per-step results in execution order.
+    pub step_results: Vec<StepResult>,
+}
+
+// ---------------------------------------------------------------------------
+// Workflow engine
+// ---------------------------------------------------------------------------
+
+/// Callback invoked with each step result as soon as the step finishes.
+pub type StepCallback = Arc<dyn Fn(&StepResult) + Send + Sync>;
+
+/// The workflow execution engine.
+///
+/// Executes workflows step-by-step, invoking adapter tools and collecting
+/// results.  Execution follows step conditions and branch targets, and runs
+/// parallel step groups concurrently.
+pub struct WorkflowEngine {
+    /// Registered tool adapters used to resolve and dispatch step calls.
+    adapters: Vec<Arc<dyn ToolAdapter>>,
+    /// When true, continue executing remaining steps after a failure instead
+    /// of aborting immediately.
+    continue_on_error: bool,
+    /// Where run checkpoints are written, if anywhere.
+    store: Option<WorkflowStore>,
+    /// Runs currently executing, mapped to whether a pause was requested.
+    active_runs: Mutex<HashMap<Uuid, bool>>,
+    /// Observer notified of every step result.
+    on_step: Option<StepCallback>,
+}
+
+impl WorkflowEngine {
+    /// Create a new workflow engine with the given adapters.
+    pub fn new(adapters: Vec<Arc<dyn ToolAdapter>>) -> Self {
+        Self {
+            adapters,
+            continue_on_error: false,
+            store: None,
+            active_runs: Mutex::new(HashMap::new()),
+            on_step: None,
+        }
+    }
+
+    /// Builder method to set the adapters on an existing engine.
+    pub fn with_adapters(mut self, adapters: Vec<Arc<dyn ToolAdapter>>) -> Self {
+        self.adapters = adapters;
+        self
+    }
+
+    /// Builder method to enable or disable continue-on-error behaviour.
+    ///
+    /// When enabled, the engine will keep executing remaining steps even if an
+    /// earlier step fails.  The final `WorkflowResult.success` will be `false`
+    /// if any step failed.
+    pub fn with_continue_on_error(mut self, continue_on_error: bool) -> Self {
+        self.continue_on_error = continue_on_error;
+        self
+    }
+
+    /// Builder method to checkpoint runs to `store`, enabling
+    /// [`resume`](Self::resume).
+    pub fn with_store(mut self, store: WorkflowStore) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Builder method to observe step results while a run executes.
+    pub fn with_step_callback(mut self, on_step: StepCallback) -> Self {
+        self.on_step = Some(on_step);
+        self
+    }
+
+    /// Report the progress of run `run_id` from its last checkpoint.
+    ///
+    /// Requires a store (see [`with_store`](Self::with_store)).
+    pub async fn status(&self, run_id: Uuid) -> Result<RunStatus> {
+        let store = self
+            .store
+            .as_ref()
+            .ok_or_else(|| IntentError::InvalidWorkflowState {
+                reason: "cannot report run status without a workflow store".into(),
+            })?;
+        checkpoint::status(store, run_id).await
+    }
+
+    /// Find the adapter whose `adapter_id()` matches `id`.
+    fn find_adapter(&self, id: &str) -> Option<&Arc<dyn ToolAdapter>> {
+        self.adapters.iter().find(|a| a.adapter_id() == id)
+    }
+
+    /// Execute a workflow, running each step in sequence.
+    ///
+    /// Returns a [`WorkflowResult`] summarising what happened.  If a step
+    /// fails without an `on_failure` target and `continue_on_error` is false
+    /// (the default), the workflow is aborted and remaining steps are
+    /// skipped.  Steps that are skipped or jumped over by a branch do not
+    /// fail the workflow.
+    pub async fn execute(&self, workflow: &mut Workflow) -> Result<WorkflowResult> {
+        self.run(workflow, Uuid::now_v7()).await
+    }
+
+    /// Execute a workflow as run `run_id`.
+    ///
+    /// Behaves like [`execute`](Self::execute); the caller-chosen run ID lets
+    /// another task [`pause`](Self::pause) the run while it executes.
+    pub async fn run(&self, workflow: &mut Workflow, run_id: Uuid) -> Result<WorkflowResult> {
+        if workflow.steps.is_empty() {
+            return Err(IntentError::InvalidWorkflowState {
+                reason: "workflow has no steps".into(),
+            });
+        }
+
+        info!(
+            workflow_id = %workflow.id,
+            %run_id,
+            name = %workflow.name,
+            steps = workflow.steps.len(),
+            "starting workflow execution"
+        );
+        self.drive(workflow, run_id, Progress::default()).await
+    }
+
+    /// Continue a paused or interrupted run from its last checkpoint.
+    ///
+    /// Steps that completed before the checkpoint are not executed again.
+    /// Requires a store (see [`with_store`](Self::with_store)).
+    pub async fn resume(&self, run_id: Uuid) -> Result<WorkflowResult> {
+        let store = self
+            .store
+            .as_ref()
+            .ok_or_else(|| IntentError::InvalidWorkflowState {
+                reason: "cannot resume without a workflow store".into(),
+            })?;
+        let (mut workflow, progress) = checkpoint::load(store, run_id).await?;
+
+        info!(
+            workflow_id = %workflow.id,
+            %run_id,
+            next_step = progress.next_step,
+            "resuming workflow execution"
+        );
+        self.drive(&mut workflow, run_id, progress).await
+    }
+
+    /// Ask run `run_id` to pause before its next step.
+    ///
+    /// The step in flight finishes first; the run then checkpoints and
+    /// returns a result with [`WorkflowStatus::Paused`].
+    pub fn pause(&self, run_id: Uuid) -> Result<()> {
+        let mut runs = self.lock_runs()?;
+        match runs.get_mut(&run_id) {
+            Some(pause_requested) => {
+                *pause_requested = true;
+                info!(%run_id, "workflow run pause requested");
+                Ok(())
+            }
+            None => Err(IntentError::InvalidWorkflowState {
+                reason: format!("workflow run {run_id} is not running"),
+            }),
+        }
+    }
+
+    fn lock_runs(&self) -> Result<std::sync::MutexGuard<'_, HashMap<Uuid, bool>>> {
+        self.active_runs
+            .lock()
+            .map_err(|_| IntentError::Internal("workflow run registry poisoned".into()))
+    }
+
+    /// Whether a pause was requested for `run_id`, clearing the request.
+    fn take_pause_request(&self, run_id: Uuid) -> Result<bool> {
+        Ok(self
+            .lock_runs()?
+            .get_mut(&run_id)
+            .is_some_and(std::mem::take))
+    }
+
+    /// Pass a step result to the step callback, if any.
+    fn notify(&self, result: &StepResult) {
+        if let Some(on_step) = &self.on_step {
+            on_step(result);
+        }
+    }
+
+    /// Write a checkpoint if a store is configured.
+    async fn checkpoint(
+        &self,
+        run_id: Uuid,
+        workflow: &Workflow,
+        progress: &Progress,
+    ) -> Result<()> {
+        match &self.store {
+            Some(store) => checkpoint::save(store, run_id, workflow, progress).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Run `workflow` from `progress` until it finishes or is paused.
+    async fn drive(
+        &self,
+        workflow: &mut Workflow,
+        run_id: Uuid,
+        mut progress: Progress,
+    ) -> Result<WorkflowResult> {
+        if self.adapters.is_empty() {
+            return Err(IntentError::InvalidWorkflowState {
+                reason: "no adapters configured".into(),
+            });
+        }
+
+        let plan = ExecutionPlan::new(&workflow.steps)?;
+
+        {
+            let mut runs = self.lock_runs()?;
+            if runs.contains_key(&run_id) {
+                return Err(IntentError::InvalidWorkflowState {
+                    reason: format!("workflow run {run_id} is already running"),
+                });
+            }
+            runs.insert(run_id, false);
+        }
+        let _active = ActiveRun {
+            runs: &self.active_runs,
+            run_id,
+        };
+
+        workflow.status = WorkflowStatus::Running;
+
+        while let Some(step) = workflow.steps.get(progress.next_step) {
+            if self.take_pause_request(run_id)? {
+                workflow.status = WorkflowStatus::Paused;
+                self.checkpoint(run_id, workflow, &progress).await?;
+                info!(
+                    workflow_id = %workflow.id,
+                    %run_id,
+                    next_step = progress.next_step,
+                    "workflow run paused"
+                );
+                return Ok(WorkflowResult {
+                    workflow_id: workflow.id,
+                    run_id,
+                    status: WorkflowStatus::Paused,
+                    success: false,
+                    step_results: progress.step_results,
+                });
+            }
+
+            let index = progress.next_step;
+            let context = &mut progress.context;
+            let failed = if let Some(range) = plan.groups.get(&index) {
+                let policy = step
+                    .parallel_group
+                    .as_ref()
+                    .and_then(|g| workflow.group_policies.get(g))
+                    .copied()
+                    .unwrap_or_default();
+                let results = self
+                    .run_group(&workflow.steps, range.clone(), &plan, context, policy)
+                    .await;
+                let group_failed = results.iter().any(|r| {
+                    matches!(r.status, WorkflowStatus::Failed | WorkflowStatus::Cancelled)
+                });
+                for result in results {
+                    record(context, &workflow.steps[result.step_index], &result);
+                    self.notify(&result);
+                    progress.step_results.push(result);
+                }
+                progress.next_step = range.end;
+                group_failed
+            } else {
+                let result = if is_skipped(&plan, index, context) {
+                    StepResult::not_run(index, step, WorkflowStatus::Skipped)
+                } else {
+                    run_step(self.find_adapter(&step.adapter), index, step, context).await
+                };
+
+                let next = match result.status {
+                    WorkflowStatus::Completed => plan.on_success[index],
+                    _ => plan.on_failure[index],
+                };
+                let unhandled_failure = result.status == WorkflowStatus::Failed && next.is_none();
+
+                record(context, step, &result);
+                self.notify(&result);
+                progress.step_results.push(result);
+                progress.next_step = next.unwrap_or(index + 1);
+                unhandled_failure
+            };
+
+            if failed {
+                progress.had_failure = true;
+                if !self.continue_on_error {
+                    break;
+                }
+            }
+            self.checkpoint(run_id, workflow, &progress).await?;
+        }
+
+        let all_success = !progress.had_failure;
+        workflow.status = if all_success {
+            WorkflowStatus::Completed
+        } else {
+            WorkflowStatus::Failed
+        };
+        self.checkpoint(run_id, workflow, &progress).await?;
+
+        info!(
+            workflow_id = %workflow.id,
+            %run_id,
+            success = all_success,
+            "workflow execution complete"
+        );
+
+        Ok(WorkflowResult {
+            workflow_id: workflow.id,
+            run_id,
+            status: workflow.status,
+            success: all_success,
+            step_results: progress.step_results,
+        })
+    }
+
+    /// Run the steps of a parallel group concurrently and join them.
+    ///
+    /// Results are returned in step order.  Under
+    /// [`GroupFailurePolicy::FailFast`] the first failure cancels the steps
+    /// still running.
+    async fn run_group(
+        &self,
+        steps: &[WorkflowStep],
+        range: Range<usize>,
+        plan: &ExecutionPlan,
+        context: &ConditionContext,
+        policy: GroupFailurePolicy,
+    ) -> Vec<StepResult> {
+        debug!(steps = ?range, ?policy, "executing parallel step group");
+
+        let mut results = Vec::with_capacity(range.len());
+        let mut tasks = JoinSet::new();
+        let mut pending = HashMap::new();
+        for index in range {
+            let step = &steps[index];
+            if is_skipped(plan, index, context) {
+                results.push(StepResult::not_run(index, step, WorkflowStatus::Skipped));
+                continue;
+            }
+            let params = match template::render(&step.params, context) {
+                Ok(params) => params,
+                Err(reason) => {
+                    results.push(StepResult::failed(index, step, reason));
+                    continue;
+                }
+            };
+            let adapter = self.find_adapter(&step.adapter).cloned();
+            let owned = step.clone();
+            let handle = tasks
+                .spawn(async move { run_step_with(adapter.as_ref(), index, &owned, params).await });
+            pending.insert(handle.id(), index);
+        }
+
+        while let Some(joined) = tasks.join_next_with_id().await {
+            let result = match joined {
+                Ok((id, result)) => {
+                    pending.remove(&id);
+                    result
+                }
+                Err(e) => {
+                    let Some(index) = pending.remove(&e.id()) else {
+                        continue;
+                    };
+                    StepResult::failed(index, &steps[index], format!("step task failed: {e}"))
+                }
+            };
+            let failed = result.status == WorkflowStatus::Failed;
+            results.push(result);
+
+            if failed && policy == GroupFailurePolicy::FailFast && !pending.is_empty() {
+                warn!(
+                    cancelled = pending.len(),
+                    "parallel step failed, cancelling the rest of its group"
+                );
+                tasks.abort_all();
+                for (_, index) in pending.drain() {
+                    results.push(StepResult::not_run(
+                        index,
+                        &steps[index],
+                        WorkflowStatus::Cancelled,
+                    ));
+                }
+                break;
+            }
+        }
+
+        results.sort_by_key(|r| r.step_index);
+        results
+    }
+
+    /// Cancel a running workflow.
+    pub fn cancel(&self, workflow: &mut Workflow) -> Result<()> {
+        if workflow.status != WorkflowStatus::Running {
+            return Err(IntentError::InvalidWorkflowState {
+                reason: format!("cannot cancel workflow in {:?} state", workflow.status),
+            });
+        }
+        warn!(workflow_id = %workflow.id, "cancelling workflow");
+        workflow.status = WorkflowStatus::Cancelled;
+        Ok(())
+    }
+}
+
+/// Removes a run from the active-run registry when execution ends.
+struct ActiveRun<'a> {
+    runs: &'a Mutex<HashMap<Uuid, bool>>,
+    run_id: Uuid,
+}
+
+impl Drop for ActiveRun<'_> {
+    fn drop(&mut self) {
+        let mut runs = self.runs.lock().unwrap_or_else(|e| e.into_inner());
+        runs.remove(&self.run_id);
+    }
+}
+
+/// Whether the condition of step `index` is false in `context`.
+fn is_skipped(plan: &ExecutionPlan, index: usize, context: &ConditionContext) -> bool {
+    let skipped = plan.conditions[index]
+        .as_ref()
+        .is_some_and(|condition| !condition.evaluate(context));
+    if skipped {
+        debug!(step = index, "workflow step condition is false, skipping");
+    }
+    skipped
+}
+
+/// Make a step's result visible to later conditions.
+fn record(context: &mut ConditionContext, step: &WorkflowStep, result: &StepResult) {
+    let value = result.condition_value();
+    if let Some(id) = &step.id {
+        context.insert(id.clone(), value.clone());
+    }
+    context.insert(result.step_index.to_string(), value);
+}
+
+/// Run a single step and capture its outcome.
+///
+/// Templates in the step's parameters are resolved against `context` first;
+/// an unresolved reference fails the step.
+async fn run_step(
+    adapter: Option<&Arc<dyn ToolAdapter>>,
+    index: usize,
+    step: &WorkflowStep,
+    context: &ConditionContext,
+) -> StepResult {
+    match template::render(&step.params, context) {
+        Ok(params) => run_step_with(adapter, index, step, params).await,
+        Err(reason) => {
+            warn!(step = index, %reason, "workflow step parameters could not be rendered");
+            StepResult::failed(index, step, reason)
+        }
+    }
+}
+
+/// Run a single step with already-rendered parameters.
+async fn run_step_with(
+    adapter: Option<&Arc<dyn ToolAdapter>>,
+    index: usize,
+    step: &WorkflowStep,
+    params: serde_json::Value,
+) -> StepResult {
+    debug!(
+        step = index,
+        adapter = %step.adapter,
+        tool = %step.tool,
+        "executing workflow step"
+    );
+
+    // Resolve the adapter by its ID.
+    let Some(adapter) = adapter else {
+        warn!(
+            step = index,
+            adapter = %step.adapter,
+            "adapter not found for workflow step"
+        );
+        return StepResult::failed(index, step, format!("adapter `{}` not found", step.adapter));
+    };
+
+    // Call the adapter with the step's tool and params.
+    match adapter.execute(&step.tool, params).await {
+        Ok(output_str) => {
+            // Try to parse the output as JSON; fall back to a string wrapper.
+            let output = serde_json::from_str::<serde_json::Value>(&output_str)
+                .unwrap_or_else(|_| serde_json::json!({ "result": output_str }));
+
+            StepResult {
+                step_index: index,
+                tool: step.tool.clone(),
+                success: true,
+                status: WorkflowStatus::Completed,
+                output,
+            }
+        }
+        Err(e) => {
+            warn!(
+                step = index,
+                tool = %step.tool,
+                error = %e,
+                "workflow step failed"
+            );
+            StepResult::failed(index, step, e.to_string())
+        }
+    }
+}
+
+impl Default for WorkflowEngine {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
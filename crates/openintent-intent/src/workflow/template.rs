@@ -0,0 +1,111 @@
+//! Step parameter templating.
+//!
+//! String values inside a step's `params` may reference earlier results as
+//! `${steps.<id>.output.<field>}`, using the same paths as step conditions.
+//! A string that consists of a single reference is replaced by the
+//! referenced JSON value, keeping its type; references embedded in longer
+//! strings are interpolated as text.
+
+use serde_json::Value;
+
+use crate::condition::{ConditionContext, lookup};
+
+/// Resolve every `${...}` reference in `params` against `steps`.
+///
+/// Returns a description of the first reference that cannot be resolved.
+pub(super) fn render(params: &Value, steps: &ConditionContext) -> Result<Value, String> {
+    match params {
+        Value::String(s) => render_str(s, steps),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| render(item, steps))
+            .collect::<Result<_, _>>()
+            .map(Value::Array),
+        Value::Object(map) => map
+            .iter()
+            .map(|(k, v)| Ok((k.clone(), render(v, steps)?)))
+            .collect::<Result<_, String>>()
+            .map(Value::Object),
+        other => Ok(other.clone()),
+    }
+}
+
+fn render_str(s: &str, steps: &ConditionContext) -> Result<Value, String> {
+    if !s.contains("${") {
+        return Ok(Value::String(s.to_string()));
+    }
+
+    // A lone reference keeps the type of the value it points at.
+    if let Some(path) = s.strip_prefix("${").and_then(|r| r.strip_suffix('}'))
+        && !path.contains('}')
+    {
+        return resolve(path, steps).cloned();
+    }
+
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find('}')
+            .ok_or_else(|| format!("unterminated reference in `{s}`"))?;
+        match resolve(&after[..end], steps)? {
+            Value::String(text) => out.push_str(text),
+            value => out.push_str(&value.to_string()),
+        }
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(Value::String(out))
+}
+
+fn resolve<'a>(path: &str, steps: &'a ConditionContext) -> Result<&'a Value, String> {
+    let segments: Vec<&str> = path.trim().split('.').collect();
+    match segments.split_first() {
+        Some((&"steps", rest)) => lookup(rest, steps),
+        _ => None,
+    }
+    .ok_or_else(|| format!("unresolved reference `${{{path}}}`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn context() -> ConditionContext {
+        ConditionContext::from([(
+            "fetch".to_string(),
+            json!({"success": true, "output": {"url": "https://x.test", "count": 3, "tags": ["a"]}}),
+        )])
+    }
+
+    #[test]
+    fn lone_reference_keeps_type_and_embedded_reference_interpolates() {
+        let params = json!({
+            "count": "${steps.fetch.output.count}",
+            "tags": ["${steps.fetch.output.tags}"],
+            "message": "got ${steps.fetch.output.count} from ${ steps.fetch.output.url }",
+            "untouched": 7,
+        });
+        assert_eq!(
+            render(&params, &context()).unwrap(),
+            json!({
+                "count": 3,
+                "tags": [["a"]],
+                "message": "got 3 from https://x.test",
+                "untouched": 7,
+            })
+        );
+    }
+
+    #[test]
+    fn unresolved_references_are_errors() {
+        let err = render(&json!({"u": "${steps.fetch.output.missing}"}), &context()).unwrap_err();
+        assert_eq!(err, "unresolved reference `${steps.fetch.output.missing}`");
+        assert!(render(&json!("${steps.other.output}"), &context()).is_err());
+        assert!(render(&json!("${fetch.output.url}"), &context()).is_err());
+        assert!(render(&json!("see ${steps.fetch.output.url"), &context()).is_err());
+    }
+}
@@ -0,0 +1,688 @@
+use super::*;
+use async_trait::async_trait;
+use openintent_agent::ToolDefinition;
+use serde_json::Value;
+
+// -- Mock adapter --------------------------------------------------------
+
+/// A mock adapter for testing the workflow engine.
+struct MockAdapter {
+    id: String,
+}
+
+#[async_trait]
+impl ToolAdapter for MockAdapter {
+    fn adapter_id(&self) -> &str {
+        &self.id
+    }
+
+    fn tool_definitions(&self) -> Vec<ToolDefinition> {
+        vec![ToolDefinition {
+            name: format!("{}_tool", self.id),
+            description: format!("Mock tool for {}", self.id),
+            input_schema: serde_json::json!({"type": "object"}),
+            destructive: false,
+        }]
+    }
+
+    async fn execute(&self, tool_name: &str, arguments: Value) -> openintent_agent::Result<String> {
+        Ok(serde_json::json!({
+            "adapter": self.id,
+            "tool": tool_name,
+            "args": arguments,
+        })
+        .to_string())
+    }
+}
+
+/// A mock adapter that always fails execution.
+struct FailingAdapter {
+    id: String,
+}
+
+#[async_trait]
+impl ToolAdapter for FailingAdapter {
+    fn adapter_id(&self) -> &str {
+        &self.id
+    }
+
+    fn tool_definitions(&self) -> Vec<ToolDefinition> {
+        vec![ToolDefinition {
+            name: format!("{}_tool", self.id),
+            description: format!("Failing tool for {}", self.id),
+            input_schema: serde_json::json!({"type": "object"}),
+            destructive: false,
+        }]
+    }
+
+    async fn execute(
+        &self,
+        tool_name: &str,
+        _arguments: Value,
+    ) -> openintent_agent::Result<String> {
+        Err(openintent_agent::AgentError::ToolExecutionFailed {
+            tool_name: tool_name.to_owned(),
+            reason: "simulated failure".into(),
+        })
+    }
+}
+
+/// A mock adapter that sleeps for the number of milliseconds given in the
+/// `delay_ms` argument before succeeding.
+struct SlowAdapter {
+    id: String,
+}
+
+#[async_trait]
+impl ToolAdapter for SlowAdapter {
+    fn adapter_id(&self) -> &str {
+        &self.id
+    }
+
+    fn tool_definitions(&self) -> Vec<ToolDefinition> {
+        Vec::new()
+    }
+
+    async fn execute(&self, tool_name: &str, arguments: Value) -> openintent_agent::Result<String> {
+        let delay = arguments["delay_ms"].as_u64().unwrap_or(0);
+        tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+        Ok(serde_json::json!({ "tool": tool_name, "slept_ms": delay }).to_string())
+    }
+}
+
+// -- Helper --------------------------------------------------------------
+
+fn sample_workflow() -> Workflow {
+    Workflow::new(
+        "test-workflow",
+        vec![
+            WorkflowStep::new(
+                "List home directory",
+                "filesystem",
+                "fs_list_directory",
+                serde_json::json!({"path": "/tmp"}),
+            ),
+            WorkflowStep::new(
+                "Show date",
+                "shell",
+                "shell_execute",
+                serde_json::json!({"command": "date"}),
+            ),
+        ],
+    )
+}
+
+// -- Tests ---------------------------------------------------------------
+
+#[tokio::test]
+async fn execute_workflow_with_adapters() {
+    let adapters: Vec<Arc<dyn ToolAdapter>> = vec![
+        Arc::new(MockAdapter {
+            id: "filesystem".into(),
+        }),
+        Arc::new(MockAdapter { id: "shell".into() }),
+    ];
+
+    let engine = WorkflowEngine::new(adapters);
+    let mut wf = sample_workflow();
+    let result = engine.execute(&mut wf).await.unwrap();
+
+    assert!(result.success);
+    assert_eq!(result.step_results.len(), 2);
+    assert!(result.step_results[0].success);
+    assert!(result.step_results[1].success);
+    assert_eq!(wf.status, WorkflowStatus::Completed);
+
+    // Verify the output contains what the mock returned.
+    let first_output = &result.step_results[0].output;
+    assert_eq!(first_output["adapter"], "filesystem");
+    assert_eq!(first_output["tool"], "fs_list_directory");
+}
+
+#[tokio::test]
+async fn execute_workflow_no_adapters_returns_error() {
+    let engine = WorkflowEngine::new(Vec::new());
+    let mut wf = sample_workflow();
+    let result = engine.execute(&mut wf).await;
+
+    assert!(result.is_err());
+    let err = result.unwrap_err();
+    assert!(err.to_string().contains("no adapters configured"));
+}
+
+#[tokio::test]
+async fn execute_workflow_missing_adapter_aborts() {
+    // Only provide the filesystem adapter, not shell.
+    let adapters: Vec<Arc<dyn ToolAdapter>> = vec![Arc::new(MockAdapter {
+        id: "filesystem".into(),
+    })];
+
+    let engine = WorkflowEngine::new(adapters);
+    let mut wf = sample_workflow();
+    let result = engine.execute(&mut wf).await.unwrap();
+
+    assert!(!result.success);
+    // First step succeeds, second aborts because "shell" adapter is missing.
+    assert_eq!(result.step_results.len(), 2);
+    assert!(result.step_results[0].success);
+    assert!(!result.step_results[1].success);
+    assert_eq!(wf.status, WorkflowStatus::Failed);
+}
+
+#[tokio::test]
+async fn execute_workflow_continue_on_error() {
+    let adapters: Vec<Arc<dyn ToolAdapter>> = vec![
+        Arc::new(FailingAdapter {
+            id: "filesystem".into(),
+        }),
+        Arc::new(MockAdapter { id: "shell".into() }),
+    ];
+
+    let engine = WorkflowEngine::new(adapters).with_continue_on_error(true);
+    let mut wf = sample_workflow();
+    let result = engine.execute(&mut wf).await.unwrap();
+
+    assert!(!result.success);
+    // Both steps attempted even though first failed.
+    assert_eq!(result.step_results.len(), 2);
+    assert!(!result.step_results[0].success);
+    assert!(result.step_results[1].success);
+    assert_eq!(wf.status, WorkflowStatus::Failed);
+}
+
+#[tokio::test]
+async fn execute_workflow_abort_on_error() {
+    let adapters: Vec<Arc<dyn ToolAdapter>> = vec![
+        Arc::new(FailingAdapter {
+            id: "filesystem".into(),
+        }),
+        Arc::new(MockAdapter { id: "shell".into() }),
+    ];
+
+    let engine = WorkflowEngine::new(adapters);
+    let mut wf = sample_workflow();
+    let result = engine.execute(&mut wf).await.unwrap();
+
+    assert!(!result.success);
+    // Only first step attempted; second was skipped due to abort.
+    assert_eq!(result.step_results.len(), 1);
+    assert!(!result.step_results[0].success);
+    assert_eq!(wf.status, WorkflowStatus::Failed);
+}
+
+#[tokio::test]
+async fn empty_workflow_fails() {
+    let engine = WorkflowEngine::new(vec![Arc::new(MockAdapter { id: "test".into() })]);
+    let mut wf = Workflow::new("empty", vec![]);
+    let result = engine.execute(&mut wf).await;
+    assert!(result.is_err());
+}
+
+#[test]
+fn cancel_idle_workflow_fails() {
+    let engine = WorkflowEngine::default();
+    let mut wf = sample_workflow();
+    let result = engine.cancel(&mut wf);
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn with_adapters_builder() {
+    let adapters: Vec<Arc<dyn ToolAdapter>> = vec![
+        Arc::new(MockAdapter {
+            id: "filesystem".into(),
+        }),
+        Arc::new(MockAdapter { id: "shell".into() }),
+    ];
+
+    let engine = WorkflowEngine::default().with_adapters(adapters);
+    let mut wf = sample_workflow();
+    let result = engine.execute(&mut wf).await.unwrap();
+    assert!(result.success);
+}
+
+fn mock_adapters() -> Vec<Arc<dyn ToolAdapter>> {
+    vec![
+        Arc::new(MockAdapter {
+            id: "filesystem".into(),
+        }),
+        Arc::new(MockAdapter { id: "shell".into() }),
+        Arc::new(FailingAdapter {
+            id: "broken".into(),
+        }),
+    ]
+}
+
+#[tokio::test]
+async fn false_condition_branches_to_target() {
+    let engine = WorkflowEngine::new(mock_adapters());
+    let mut wf = Workflow::new(
+        "branching",
+        vec![
+            WorkflowStep::new("List", "filesystem", "fs_list_directory", Value::Null)
+                .with_id("list"),
+            WorkflowStep::new(
+                "Only for shell output",
+                "shell",
+                "shell_execute",
+                Value::Null,
+            )
+            .with_id("check")
+            .with_condition("steps.list.output.adapter == 'shell'")
+            .with_on_failure("fallback"),
+            WorkflowStep::new(
+                "Skipped by the branch",
+                "shell",
+                "shell_execute",
+                Value::Null,
+            ),
+            WorkflowStep::new("Fallback", "shell", "shell_fallback", Value::Null)
+                .with_id("fallback"),
+        ],
+    );
+    let result = engine.execute(&mut wf).await.unwrap();
+
+    assert!(result.success);
+    let ran: Vec<(usize, WorkflowStatus)> = result
+        .step_results
+        .iter()
+        .map(|r| (r.step_index, r.status))
+        .collect();
+    assert_eq!(
+        ran,
+        vec![
+            (0, WorkflowStatus::Completed),
+            (1, WorkflowStatus::Skipped),
+            (3, WorkflowStatus::Completed),
+        ]
+    );
+    assert_eq!(result.step_results[2].output["tool"], "shell_fallback");
+}
+
+#[tokio::test]
+async fn on_failure_target_handles_failure() {
+    let engine = WorkflowEngine::new(mock_adapters());
+    let mut wf = Workflow::new(
+        "recovering",
+        vec![
+            WorkflowStep::new("Try", "broken", "broken_tool", Value::Null)
+                .with_id("try")
+                .with_on_success("done")
+                .with_on_failure("recover"),
+            WorkflowStep::new("Recover", "shell", "shell_execute", Value::Null)
+                .with_id("recover")
+                .with_condition("!steps.try.success"),
+            WorkflowStep::new("Done", "shell", "shell_execute", Value::Null).with_id("done"),
+        ],
+    );
+    let result = engine.execute(&mut wf).await.unwrap();
+
+    assert!(result.success);
+    assert_eq!(result.step_results.len(), 3);
+    assert_eq!(result.step_results[0].status, WorkflowStatus::Failed);
+    assert_eq!(result.step_results[1].status, WorkflowStatus::Completed);
+    assert_eq!(wf.status, WorkflowStatus::Completed);
+}
+
+#[tokio::test]
+async fn invalid_branch_targets_are_rejected() {
+    let engine = WorkflowEngine::new(mock_adapters());
+    let backwards = vec![
+        WorkflowStep::new("A", "shell", "shell_execute", Value::Null).with_id("a"),
+        WorkflowStep::new("B", "shell", "shell_execute", Value::Null).with_on_success("a"),
+    ];
+    let unknown =
+        vec![WorkflowStep::new("A", "shell", "shell_execute", Value::Null).with_on_failure("nope")];
+    let bad_condition =
+        vec![WorkflowStep::new("A", "shell", "shell_execute", Value::Null).with_condition("&&")];
+
+    for steps in [backwards, unknown, bad_condition] {
+        let mut wf = Workflow::new("invalid", steps);
+        assert!(matches!(
+            engine.execute(&mut wf).await,
+            Err(IntentError::InvalidWorkflowState { .. })
+        ));
+        assert_eq!(wf.status, WorkflowStatus::Idle);
+    }
+}
+
+fn parallel_workflow(first: &str, second: &str) -> Workflow {
+    Workflow::new(
+        "parallel",
+        vec![
+            WorkflowStep::new("A", first, "tool_a", serde_json::json!({"delay_ms": 200}))
+                .with_parallel_group("fetch"),
+            WorkflowStep::new("B", second, "tool_b", serde_json::json!({"delay_ms": 200}))
+                .with_parallel_group("fetch"),
+            WorkflowStep::new("After", "shell", "shell_execute", Value::Null)
+                .with_condition("steps.0.success && steps.1.success"),
+        ],
+    )
+}
+
+fn parallel_adapters() -> Vec<Arc<dyn ToolAdapter>> {
+    vec![
+        Arc::new(SlowAdapter { id: "slow".into() }),
+        Arc::new(MockAdapter { id: "shell".into() }),
+        Arc::new(FailingAdapter {
+            id: "broken".into(),
+        }),
+    ]
+}
+
+#[tokio::test]
+async fn parallel_group_runs_concurrently_and_joins() {
+    let engine = WorkflowEngine::new(parallel_adapters());
+    let mut wf = parallel_workflow("slow", "slow");
+
+    let started = tokio::time::Instant::now();
+    let result = engine.execute(&mut wf).await.unwrap();
+    let elapsed = started.elapsed();
+
+    assert!(result.success);
+    assert!(
+        elapsed < std::time::Duration::from_millis(380),
+        "group took {elapsed:?}, steps did not overlap"
+    );
+    let tools: Vec<&str> = result
+        .step_results
+        .iter()
+        .map(|r| r.tool.as_str())
+        .collect();
+    assert_eq!(tools, vec!["tool_a", "tool_b", "shell_execute"]);
+    assert_eq!(result.step_results[0].output["slept_ms"], 200);
+    assert_eq!(result.step_results[1].output["slept_ms"], 200);
+    assert_eq!(result.step_results[2].status, WorkflowStatus::Completed);
+}
+
+#[tokio::test]
+async fn fail_fast_group_cancels_running_steps() {
+    let engine = WorkflowEngine::new(parallel_adapters());
+    let mut wf = parallel_workflow("broken", "slow");
+
+    let result = engine.execute(&mut wf).await.unwrap();
+
+    assert!(!result.success);
+    let statuses: Vec<WorkflowStatus> = result.step_results.iter().map(|r| r.status).collect();
+    assert_eq!(
+        statuses,
+        vec![WorkflowStatus::Failed, WorkflowStatus::Cancelled]
+    );
+    assert_eq!(wf.status, WorkflowStatus::Failed);
+}
+
+#[tokio::test]
+async fn continue_group_lets_every_step_finish() {
+    let engine = WorkflowEngine::new(parallel_adapters()).with_continue_on_error(true);
+    let mut wf = parallel_workflow("broken", "slow")
+        .with_group_policy("fetch", GroupFailurePolicy::Continue);
+
+    let result = engine.execute(&mut wf).await.unwrap();
+
+    assert!(!result.success);
+    let statuses: Vec<WorkflowStatus> = result.step_results.iter().map(|r| r.status).collect();
+    assert_eq!(
+        statuses,
+        vec![
+            WorkflowStatus::Failed,
+            WorkflowStatus::Completed,
+            WorkflowStatus::Skipped,
+        ]
+    );
+}
+
+#[tokio::test]
+async fn invalid_parallel_groups_are_rejected() {
+    let engine = WorkflowEngine::new(parallel_adapters());
+    let step = |group: &str| {
+        WorkflowStep::new("S", "shell", "shell_execute", Value::Null).with_parallel_group(group)
+    };
+    let split = vec![
+        step("g"),
+        WorkflowStep::new("X", "shell", "x", Value::Null),
+        step("g"),
+    ];
+    let branching = vec![
+        step("g").with_on_success("end"),
+        step("g"),
+        WorkflowStep::new("End", "shell", "x", Value::Null).with_id("end"),
+    ];
+    let into_middle = vec![
+        WorkflowStep::new("Start", "shell", "x", Value::Null).with_on_success("mid"),
+        step("g"),
+        step("g").with_id("mid"),
+    ];
+
+    for steps in [split, branching, into_middle] {
+        let mut wf = Workflow::new("invalid", steps);
+        assert!(matches!(
+            engine.execute(&mut wf).await,
+            Err(IntentError::InvalidWorkflowState { .. })
+        ));
+    }
+}
+
+#[tokio::test]
+async fn step_params_consume_earlier_output() {
+    let engine = WorkflowEngine::new(mock_adapters());
+    let mut wf = Workflow::new(
+        "chained",
+        vec![
+            WorkflowStep::new(
+                "List",
+                "filesystem",
+                "fs_list_directory",
+                serde_json::json!({"path": "/tmp"}),
+            )
+            .with_id("list"),
+            WorkflowStep::new(
+                "Echo",
+                "shell",
+                "shell_execute",
+                serde_json::json!({
+                    "path": "${steps.list.output.args.path}",
+                    "command": "echo ${steps.list.output.tool} ${steps.0.success}",
+                }),
+            ),
+        ],
+    );
+    let result = engine.execute(&mut wf).await.unwrap();
+
+    assert!(result.success);
+    let args = &result.step_results[1].output["args"];
+    assert_eq!(args["path"], "/tmp");
+    assert_eq!(args["command"], "echo fs_list_directory true");
+}
+
+#[tokio::test]
+async fn unresolved_template_fails_the_step() {
+    let engine = WorkflowEngine::new(mock_adapters());
+    let mut wf = Workflow::new(
+        "unresolved",
+        vec![
+            WorkflowStep::new("List", "filesystem", "fs_list_directory", Value::Null)
+                .with_id("list"),
+            WorkflowStep::new(
+                "Echo",
+                "shell",
+                "shell_execute",
+                serde_json::json!({"path": "${steps.list.output.nope}"}),
+            ),
+        ],
+    );
+    let result = engine.execute(&mut wf).await.unwrap();
+
+    assert!(!result.success);
+    let failed = &result.step_results[1];
+    assert_eq!(failed.status, WorkflowStatus::Failed);
+    assert_eq!(
+        failed.output["error"],
+        "unresolved reference `${steps.list.output.nope}`"
+    );
+}
+
+/// Counts executions per tool, sleeping for `delay_ms` like [`SlowAdapter`].
+struct CountingAdapter {
+    calls: std::sync::Mutex<HashMap<String, usize>>,
+}
+
+#[async_trait]
+impl ToolAdapter for CountingAdapter {
+    fn adapter_id(&self) -> &str {
+        "counting"
+    }
+
+    fn tool_definitions(&self) -> Vec<ToolDefinition> {
+        Vec::new()
+    }
+
+    async fn execute(&self, tool_name: &str, arguments: Value) -> openintent_agent::Result<String> {
+        *self
+            .calls
+            .lock()
+            .unwrap()
+            .entry(tool_name.to_owned())
+            .or_default() += 1;
+        let delay = arguments["delay_ms"].as_u64().unwrap_or(0);
+        tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+        Ok(serde_json::json!({ "tool": tool_name }).to_string())
+    }
+}
+
+async fn run_store() -> WorkflowStore {
+    let db = openintent_store::Database::open_in_memory().unwrap();
+    db.run_migrations().await.unwrap();
+    WorkflowStore::new(db)
+}
+
+#[tokio::test]
+async fn paused_run_resumes_without_repeating_steps() {
+    let store = run_store().await;
+    let counter = Arc::new(CountingAdapter {
+        calls: std::sync::Mutex::new(HashMap::new()),
+    });
+    let adapters: Vec<Arc<dyn ToolAdapter>> = vec![counter.clone()];
+    let mut wf = Workflow::new(
+        "pausable",
+        vec![
+            WorkflowStep::new(
+                "First",
+                "counting",
+                "first",
+                serde_json::json!({"delay_ms": 200}),
+            )
+            .with_id("first"),
+            WorkflowStep::new(
+                "Second",
+                "counting",
+                "second",
+                serde_json::json!({"from": "${steps.first.output.tool}"}),
+            ),
+        ],
+    );
+
+    let engine = WorkflowEngine::new(adapters.clone()).with_store(store.clone());
+    let run_id = Uuid::now_v7();
+    let (paused, pause) = tokio::join!(engine.run(&mut wf, run_id), async {
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        engine.pause(run_id)
+    });
+    pause.unwrap();
+    let paused = paused.unwrap();
+    assert_eq!(paused.status, WorkflowStatus::Paused);
+    assert_eq!(paused.step_results.len(), 1);
+    assert_eq!(
+        store
+            .get_run(&run_id.to_string())
+            .await
+            .unwrap()
+            .unwrap()
+            .status,
+        "paused"
+    );
+
+    // A fresh engine over the same store stands in for a restart.
+    let engine = WorkflowEngine::new(adapters).with_store(store.clone());
+    let resumed = engine.resume(run_id).await.unwrap();
+    assert!(resumed.success);
+    assert_eq!(resumed.status, WorkflowStatus::Completed);
+    assert_eq!(resumed.step_results.len(), 2);
+    assert_eq!(resumed.step_results[1].output["tool"], "second");
+
+    let calls = counter.calls.lock().unwrap().clone();
+    assert_eq!(
+        calls,
+        HashMap::from([("first".into(), 1), ("second".into(), 1)])
+    );
+    assert_eq!(
+        store
+            .get_run(&run_id.to_string())
+            .await
+            .unwrap()
+            .unwrap()
+            .status,
+        "completed"
+    );
+
+    assert!(matches!(
+        engine.resume(run_id).await,
+        Err(IntentError::InvalidWorkflowState { .. })
+    ));
+}
+
+#[tokio::test]
+async fn pausing_an_unknown_run_fails() {
+    let engine = WorkflowEngine::new(mock_adapters());
+    assert!(matches!(
+        engine.pause(Uuid::now_v7()),
+        Err(IntentError::InvalidWorkflowState { .. })
+    ));
+    assert!(matches!(
+        engine.resume(Uuid::now_v7()).await,
+        Err(IntentError::InvalidWorkflowState { .. })
+    ));
+}
+
+#[tokio::test]
+async fn step_callback_sees_results_and_status_reports_progress() {
+    let store = run_store().await;
+    let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink = Arc::clone(&seen);
+    let engine = WorkflowEngine::new(mock_adapters())
+        .with_store(store.clone())
+        .with_step_callback(Arc::new(move |r: &StepResult| {
+            sink.lock().unwrap().push(r.tool.clone());
+        }));
+
+    let stored = store
+        .create(
+            "two-step",
+            None,
+            "",
+            serde_json::json!([
+                {"action": "A", "adapter": "filesystem", "tool": "read", "params": {}},
+                {"action": "B", "adapter": "shell", "tool": "run", "params": {}}
+            ]),
+            None,
+        )
+        .await
+        .unwrap();
+    let mut wf = Workflow::from_stored(&stored).unwrap();
+    assert_eq!(wf.id.to_string(), stored.id);
+    assert_eq!(wf.trigger, TriggerType::Manual);
+
+    let result = engine.execute(&mut wf).await.unwrap();
+    assert!(result.success);
+    assert_eq!(*seen.lock().unwrap(), vec!["read", "run"]);
+
+    let status = engine.status(result.run_id).await.unwrap();
+    assert_eq!(status.status, WorkflowStatus::Completed);
+    assert_eq!(status.workflow_name, "two-step");
+    assert_eq!((status.next_step, status.total_steps), (2, 2));
+    assert_eq!(status.step_results.len(), 2);
+
+    assert!(matches!(
+        engine.status(Uuid::now_v7()).await,
+        Err(IntentError::InvalidWorkflowState { .. })
+    ));
+}
@@ -36,6 +36,10 @@ pub enum KernelError {
     #[error("ipc subscribe failed: {reason}")]
     IpcSubscribeFailed { reason: String },
 
+    /// No response to an IPC request arrived before the timeout.
+    #[error("ipc request on `{topic}` timed out after {timeout_ms}ms")]
+    IpcRequestTimeout { topic: String, timeout_ms: u64 },
+
     // -- Router errors ------------------------------------------------------
     /// No route matched the given intent text at any level.
     #[error("no route matched intent: {intent}")]
@@ -58,6 +62,13 @@ pub enum KernelError {
     #[error("adapter unavailable: {adapter_id} (status: {status})")]
     AdapterUnavailable { adapter_id: String, status: String },
 
+    /// The adapter's circuit breaker is open after repeated failures.
+    #[error("circuit open for adapter {adapter_id}, retry after {retry_after_secs}s")]
+    CircuitOpen {
+        adapter_id: String,
+        retry_after_secs: u64,
+    },
+
     // -- Generic ------------------------------------------------------------
     /// Catch-all for unexpected internal errors that don't fit a specific
     /// variant.  Prefer a typed variant whenever possible.
@@ -25,15 +25,37 @@
 //! let event = rx.recv().await.unwrap();
 //! # }
 //! ```
+//!
+//! # Request/response
+//!
+//! [`IpcBus::request`] layers request/reply on top of the broadcast channel:
+//! it publishes an [`Event::Request`] tagged with a fresh correlation id and
+//! waits for the [`Event::Response`] carrying the same id.  Services answer
+//! with [`IpcBus::respond`].
+//!
+//! ```rust,no_run
+//! # use std::time::Duration;
+//! # use openintent_kernel::ipc::{IpcBus, Event};
+//! # async fn example(bus: IpcBus) {
+//! let reply = bus
+//!     .request("weather", r#"{"city":"Oslo"}"#, Duration::from_secs(5))
+//!     .await
+//!     .unwrap();
+//! if let Event::Response { payload, .. } = reply {
+//!     println!("{payload}");
+//! }
+//! # }
+//! ```
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use tokio::sync::broadcast;
 use uuid::Uuid;
 
-use crate::error::Result;
+use crate::error::{KernelError, Result};
 
 // ---------------------------------------------------------------------------
 // Event types
@@ -80,6 +102,16 @@ pub enum Event {
         timestamp: DateTime<Utc>,
     },
 
+    /// An adapter's health status changed (e.g. it lost its connection or
+    /// recovered).
+    AdapterHealthChanged {
+        /// The adapter whose health changed.
+        adapter_id: String,
+        /// New status as a string (e.g. "healthy", "degraded", "unhealthy").
+        status: String,
+        timestamp: DateTime<Utc>,
+    },
+
     /// An adapter or subsystem requires authentication before it can proceed.
     AuthRequired {
         /// The service that needs authentication.
@@ -89,6 +121,47 @@ pub enum Event {
         timestamp: DateTime<Utc>,
     },
 
+    /// A request addressed to whichever service handles `topic`.
+    Request {
+        /// Id the matching [`Event::Response`] must carry.
+        correlation_id: Uuid,
+        /// What is being asked for (e.g. "weather", "calendar.today").
+        topic: String,
+        /// JSON-serialized request body.
+        payload: String,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// The reply to an [`Event::Request`].
+    Response {
+        /// The `correlation_id` of the request being answered.
+        correlation_id: Uuid,
+        /// The request's topic.
+        topic: String,
+        /// JSON-serialized response body.
+        payload: String,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// The kernel is shutting down; services should wind down and reply
+    /// with [`Event::ShutdownAck`].
+    Shutdown {
+        /// Identifies this shutdown so stale acks can be ignored.
+        shutdown_id: Uuid,
+        /// How long services have to finish, in milliseconds.
+        deadline_ms: u64,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// A service has finished its shutdown work.
+    ShutdownAck {
+        /// The `shutdown_id` of the [`Event::Shutdown`] being acknowledged.
+        shutdown_id: Uuid,
+        /// Name the service registered under.
+        service: String,
+        timestamp: DateTime<Utc>,
+    },
+
     /// Generic system-level event for anything that does not fit the above.
     SystemEvent {
         /// A short, machine-readable event kind (e.g. "startup", "shutdown").
@@ -98,6 +171,25 @@ pub enum Event {
     },
 }
 
+impl Event {
+    /// Short topic name subscribers can filter on: `intent`, `task`,
+    /// `adapter`, `health`, `auth`, `request`, `response`, `shutdown` or
+    /// `system`.
+    pub fn topic(&self) -> &'static str {
+        match self {
+            Self::IntentReceived { .. } => "intent",
+            Self::TaskStatusChanged { .. } => "task",
+            Self::AdapterEvent { .. } => "adapter",
+            Self::AdapterHealthChanged { .. } => "health",
+            Self::AuthRequired { .. } => "auth",
+            Self::Request { .. } => "request",
+            Self::Response { .. } => "response",
+            Self::Shutdown { .. } | Self::ShutdownAck { .. } => "shutdown",
+            Self::SystemEvent { .. } => "system",
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // IPC Bus
 // ---------------------------------------------------------------------------
@@ -158,6 +250,89 @@ impl IpcBus {
         self.inner.sender.subscribe()
     }
 
+    /// Publish a request on `topic` and wait up to `timeout` for its
+    /// response.
+    ///
+    /// Returns the matching [`Event::Response`].  Fails with
+    /// [`KernelError::IpcPublishFailed`] if nobody else is subscribed to the
+    /// bus, and with [`KernelError::IpcRequestTimeout`] if no reply arrives
+    /// in time.
+    pub async fn request(
+        &self,
+        topic: impl Into<String>,
+        payload: impl Into<String>,
+        timeout: Duration,
+    ) -> Result<Event> {
+        let topic = topic.into();
+        let correlation_id = Uuid::now_v7();
+
+        // Subscribe first so a fast responder cannot reply before we listen.
+        let mut rx = self.subscribe();
+        let receivers = self.publish(Event::Request {
+            correlation_id,
+            topic: topic.clone(),
+            payload: payload.into(),
+            timestamp: Utc::now(),
+        })?;
+        if receivers <= 1 {
+            return Err(KernelError::IpcPublishFailed {
+                reason: format!("no subscribers to answer request on `{topic}`"),
+            });
+        }
+
+        let reply = async {
+            loop {
+                match rx.recv().await {
+                    Ok(event) => {
+                        if matches!(
+                            event.as_ref(),
+                            Event::Response { correlation_id: id, .. } if *id == correlation_id
+                        ) {
+                            return Ok(Arc::unwrap_or_clone(event));
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        tracing::warn!(missed, topic = %topic, "ipc requester lagged");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        return Err(KernelError::IpcSubscribeFailed {
+                            reason: "ipc bus closed".into(),
+                        });
+                    }
+                }
+            }
+        };
+        tokio::time::timeout(timeout, reply)
+            .await
+            .unwrap_or_else(|_| {
+                Err(KernelError::IpcRequestTimeout {
+                    topic: topic.clone(),
+                    timeout_ms: timeout.as_millis() as u64,
+                })
+            })
+    }
+
+    /// Answer `request` with `payload`.
+    ///
+    /// Does nothing and returns `Ok(0)` if `request` is not an
+    /// [`Event::Request`].
+    pub fn respond(&self, request: &Event, payload: impl Into<String>) -> Result<usize> {
+        let Event::Request {
+            correlation_id,
+            topic,
+            ..
+        } = request
+        else {
+            return Ok(0);
+        };
+        self.publish(Event::Response {
+            correlation_id: *correlation_id,
+            topic: topic.clone(),
+            payload: payload.into(),
+            timestamp: Utc::now(),
+        })
+    }
+
     /// Return the current number of active subscribers.
     pub fn subscriber_count(&self) -> usize {
         self.inner.sender.receiver_count()
@@ -266,4 +441,55 @@ mod tests {
             other => panic!("unexpected: {other:?}"),
         }
     }
+
+    #[tokio::test]
+    async fn request_receives_correlated_response() {
+        let bus = IpcBus::new(16);
+
+        // Echo responder: answers every request with its own payload.
+        let mut rx = bus.subscribe();
+        let responder_bus = bus.clone();
+        let responder = tokio::spawn(async move {
+            while let Ok(event) = rx.recv().await {
+                if let Event::Request { topic, payload, .. } = event.as_ref() {
+                    // A stray reply with an unrelated id must be ignored.
+                    responder_bus
+                        .publish(Event::Response {
+                            correlation_id: Uuid::now_v7(),
+                            topic: topic.clone(),
+                            payload: "stray".into(),
+                            timestamp: Utc::now(),
+                        })
+                        .unwrap();
+                    responder_bus
+                        .respond(&event, format!("echo: {payload}"))
+                        .unwrap();
+                }
+            }
+        });
+
+        let reply = bus
+            .request("echo", "ping", Duration::from_secs(1))
+            .await
+            .expect("request should be answered");
+        match reply {
+            Event::Response { topic, payload, .. } => {
+                assert_eq!(topic, "echo");
+                assert_eq!(payload, "echo: ping");
+            }
+            other => panic!("unexpected: {other:?}"),
+        }
+        responder.abort();
+    }
+
+    #[tokio::test]
+    async fn request_times_out_without_response() {
+        let bus = IpcBus::new(16);
+        let _silent = bus.subscribe();
+
+        let result = bus
+            .request("nobody-home", "{}", Duration::from_millis(20))
+            .await;
+        assert!(matches!(result, Err(KernelError::IpcRequestTimeout { .. })));
+    }
 }
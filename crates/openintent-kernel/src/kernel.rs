@@ -0,0 +1,290 @@
+//! Kernel handle and coordinated shutdown.
+//!
+//! [`Kernel`] bundles the scheduler, IPC bus, and adapter registry.  Services
+//! that must clean up before the process exits (adapters holding connections,
+//! background pollers, ...) call [`Kernel::register_service`] and keep the
+//! returned [`ShutdownListener`].
+//!
+//! [`Kernel::shutdown`] publishes [`Event::Shutdown`] on the bus, waits for
+//! every registered service to answer with [`Event::ShutdownAck`], and then
+//! drains the scheduler -- all within a single deadline.  Services that miss
+//! the deadline are reported rather than waited on forever.
+//!
+//! ```rust,no_run
+//! # use std::time::Duration;
+//! # use openintent_kernel::Kernel;
+//! # async fn example() {
+//! let kernel = Kernel::new(256);
+//! let mut listener = kernel.register_service("poller");
+//! tokio::spawn(async move {
+//!     let notice = listener.recv().await;
+//!     // ... stop polling, flush state ...
+//!     listener.ack(&notice).ok();
+//! });
+//!
+//! let report = kernel.shutdown(Duration::from_secs(5)).await;
+//! assert!(report.unacknowledged.is_empty());
+//! # }
+//! ```
+
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::Utc;
+use tokio::sync::broadcast;
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::ipc::{Event, IpcBus};
+use crate::registry::AdapterRegistry;
+use crate::scheduler::{Scheduler, TaskInfo};
+
+// ---------------------------------------------------------------------------
+// Public types
+// ---------------------------------------------------------------------------
+
+/// What happened during [`Kernel::shutdown`].
+#[derive(Debug, Clone)]
+pub struct ShutdownReport {
+    /// Services that acknowledged in time, sorted by name.
+    pub acknowledged: Vec<String>,
+    /// Services that did not acknowledge before the deadline, sorted by name.
+    pub unacknowledged: Vec<String>,
+    /// Scheduler tasks that never started (see [`Scheduler::shutdown`]).
+    pub unstarted_tasks: Vec<TaskInfo>,
+}
+
+/// A shutdown request as seen by a service.
+#[derive(Debug, Clone, Copy)]
+pub struct ShutdownNotice {
+    /// Identifies the shutdown being acknowledged.
+    pub shutdown_id: Uuid,
+    /// How long the service has to finish.
+    pub deadline: Duration,
+}
+
+/// A registered service's view of kernel shutdown.
+pub struct ShutdownListener {
+    service: String,
+    bus: IpcBus,
+    rx: broadcast::Receiver<Arc<Event>>,
+}
+
+impl ShutdownListener {
+    /// The name this listener was registered under.
+    pub fn service(&self) -> &str {
+        &self.service
+    }
+
+    /// Wait for the next [`Event::Shutdown`], skipping all other events.
+    pub async fn recv(&mut self) -> ShutdownNotice {
+        loop {
+            match self.rx.recv().await {
+                Ok(event) => {
+                    if let Event::Shutdown {
+                        shutdown_id,
+                        deadline_ms,
+                        ..
+                    } = event.as_ref()
+                    {
+                        return ShutdownNotice {
+                            shutdown_id: *shutdown_id,
+                            deadline: Duration::from_millis(*deadline_ms),
+                        };
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    tracing::warn!(service = %self.service, missed, "shutdown listener lagged");
+                }
+                // The listener holds a bus handle, so the channel stays open.
+                Err(broadcast::error::RecvError::Closed) => std::future::pending().await,
+            }
+        }
+    }
+
+    /// Tell the kernel this service has finished shutting down.
+    pub fn ack(&self, notice: &ShutdownNotice) -> Result<usize> {
+        tracing::debug!(service = %self.service, "shutdown acknowledged");
+        self.bus.publish(Event::ShutdownAck {
+            shutdown_id: notice.shutdown_id,
+            service: self.service.clone(),
+            timestamp: Utc::now(),
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Kernel
+// ---------------------------------------------------------------------------
+
+/// The kernel's core services and the set of services awaiting shutdown.
+pub struct Kernel {
+    scheduler: Scheduler,
+    bus: IpcBus,
+    registry: AdapterRegistry,
+    /// Names of services that must acknowledge shutdown.
+    services: Mutex<BTreeSet<String>>,
+}
+
+impl Kernel {
+    /// Create a kernel with default services and a bus of `bus_capacity`.
+    ///
+    /// The scheduler worker is not started; call
+    /// [`Scheduler::start`] on [`Kernel::scheduler`].
+    #[must_use]
+    pub fn new(bus_capacity: usize) -> Self {
+        Self::with_parts(
+            Scheduler::new(),
+            IpcBus::new(bus_capacity),
+            AdapterRegistry::new(),
+        )
+    }
+
+    /// Create a kernel from already-configured services.
+    ///
+    /// The scheduler publishes its task status changes on `bus` unless it
+    /// already has a bus of its own.
+    #[must_use]
+    pub fn with_parts(scheduler: Scheduler, bus: IpcBus, registry: AdapterRegistry) -> Self {
+        Self {
+            scheduler: scheduler.with_bus(bus.clone()),
+            bus,
+            registry,
+            services: Mutex::new(BTreeSet::new()),
+        }
+    }
+
+    /// The task scheduler.
+    pub fn scheduler(&self) -> &Scheduler {
+        &self.scheduler
+    }
+
+    /// The IPC bus.
+    pub fn bus(&self) -> &IpcBus {
+        &self.bus
+    }
+
+    /// The adapter registry.
+    pub fn registry(&self) -> &AdapterRegistry {
+        &self.registry
+    }
+
+    /// Register a service that [`Kernel::shutdown`] must wait for.
+    ///
+    /// The kernel waits for one ack per name, however often it is
+    /// registered.
+    pub fn register_service(&self, name: impl Into<String>) -> ShutdownListener {
+        let service = name.into();
+        self.lock_services().insert(service.clone());
+        tracing::debug!(service = %service, "service registered for shutdown");
+        ShutdownListener {
+            service,
+            bus: self.bus.clone(),
+            rx: self.bus.subscribe(),
+        }
+    }
+
+    /// Broadcast [`Event::Shutdown`], wait up to `deadline` for every
+    /// registered service to acknowledge, then drain the scheduler with
+    /// whatever time is left.
+    pub async fn shutdown(&self, deadline: Duration) -> ShutdownReport {
+        let started = Instant::now();
+        let shutdown_id = Uuid::now_v7();
+        let mut pending = self.lock_services().clone();
+        let mut acknowledged = Vec::new();
+
+        tracing::info!(services = pending.len(), "kernel shutdown requested");
+
+        // Subscribe before publishing so no ack can be missed.
+        let mut rx = self.bus.subscribe();
+        let _ = self.bus.publish(Event::Shutdown {
+            shutdown_id,
+            deadline_ms: deadline.as_millis() as u64,
+            timestamp: Utc::now(),
+        });
+
+        let collect = async {
+            while !pending.is_empty() {
+                match rx.recv().await {
+                    Ok(event) => {
+                        if let Event::ShutdownAck {
+                            shutdown_id: id,
+                            service,
+                            ..
+                        } = event.as_ref()
+                            && *id == shutdown_id
+                            && pending.remove(service)
+                        {
+                            acknowledged.push(service.clone());
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        tracing::warn!(missed, "kernel lagged while collecting shutdown acks");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        };
+        let _ = tokio::time::timeout(deadline, collect).await;
+
+        let unacknowledged: Vec<String> = pending.into_iter().collect();
+        if !unacknowledged.is_empty() {
+            tracing::warn!(services = ?unacknowledged, "services missed the shutdown deadline");
+        }
+
+        let remaining = deadline.saturating_sub(started.elapsed());
+        let unstarted_tasks = self.scheduler.shutdown(remaining).await;
+
+        acknowledged.sort();
+        tracing::info!(
+            acknowledged = acknowledged.len(),
+            unacknowledged = unacknowledged.len(),
+            unstarted_tasks = unstarted_tasks.len(),
+            "kernel shut down"
+        );
+        ShutdownReport {
+            acknowledged,
+            unacknowledged,
+            unstarted_tasks,
+        }
+    }
+
+    fn lock_services(&self) -> std::sync::MutexGuard<'_, BTreeSet<String>> {
+        self.services.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn services_observe_shutdown_and_ack() {
+        let kernel = Kernel::new(64);
+        let handle = kernel.scheduler().start();
+
+        for name in ["adapters", "poller"] {
+            let mut listener = kernel.register_service(name);
+            tokio::spawn(async move {
+                let notice = listener.recv().await;
+                assert_eq!(notice.deadline, Duration::from_millis(200));
+                listener.ack(&notice).expect("ack");
+            });
+        }
+        // Registered but never acknowledges.
+        let _silent = kernel.register_service("stuck");
+
+        let report = kernel.shutdown(Duration::from_millis(200)).await;
+        assert_eq!(report.acknowledged, vec!["adapters", "poller"]);
+        assert_eq!(report.unacknowledged, vec!["stuck"]);
+        assert!(report.unstarted_tasks.is_empty());
+
+        handle.await.expect("scheduler worker should exit");
+    }
+}
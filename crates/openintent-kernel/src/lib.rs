@@ -11,6 +11,8 @@
 //!   regex pattern match with named captures, and LLM fallback.
 //! - **[`registry`]** -- Concurrent adapter/service registry using [`DashMap`]
 //!   with health-check tracking and status management.
+//! - **[`kernel`]** -- [`Kernel`] handle bundling the services above, with
+//!   coordinated shutdown over the IPC bus.
 //! - **[`error`]** -- Unified kernel error types via [`thiserror`].
 //!
 //! All public types are `Send + Sync` and designed for use within a
@@ -18,6 +20,7 @@
 
 pub mod error;
 pub mod ipc;
+pub mod kernel;
 pub mod registry;
 pub mod router;
 pub mod scheduler;
@@ -25,8 +28,13 @@ pub mod scheduler;
 // Re-export the most commonly used types at the crate root for convenience.
 pub use error::{KernelError, Result};
 pub use ipc::{Event, IpcBus};
-pub use registry::{AdapterInfo, AdapterRegistry, AdapterStatus};
-pub use router::{IntentRouter, RouteResult};
+pub use kernel::{Kernel, ShutdownListener, ShutdownNotice, ShutdownReport};
+pub use registry::{
+    AdapterInfo, AdapterRegistry, AdapterStatus, CircuitBreakerConfig, CircuitBreakerInfo,
+    CircuitState,
+};
+pub use router::{IntentRouter, RouteResult, RouterMetrics};
 pub use scheduler::{
-    SchedulePolicy, Scheduler, TaskFn, TaskId, TaskInfo, TaskPriority, TaskStatus,
+    DeadLetter, SchedulePolicy, Scheduler, SchedulerConfig, TaskFn, TaskId, TaskInfo, TaskPriority,
+    TaskStatus,
 };
@@ -8,6 +8,16 @@
 //! concurrent reads and fine-grained write locking, making it safe to share
 //! across tasks without a global `RwLock`.
 //!
+//! # Circuit breaker
+//!
+//! A registry built with [`AdapterRegistry::with_circuit_breaker`] tracks
+//! consecutive call failures per adapter.  After
+//! [`CircuitBreakerConfig::failure_threshold`] failures in a row the circuit
+//! opens and [`AdapterRegistry::begin_call`] rejects calls with
+//! [`KernelError::CircuitOpen`] for the cooldown.  The first call after the
+//! cooldown is let through as a probe (half-open): success closes the
+//! circuit, failure opens it again.
+//!
 //! # Example
 //!
 //! ```rust
@@ -21,6 +31,7 @@
 //! ```
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
@@ -45,6 +56,46 @@ pub enum AdapterStatus {
     Error,
 }
 
+/// State of an adapter's circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CircuitState {
+    /// Calls pass through normally.
+    Closed,
+    /// Calls are rejected until the cooldown elapses.
+    Open,
+    /// A single probe call is in flight to test recovery.
+    HalfOpen,
+}
+
+/// Circuit breaker settings applied to every adapter in a registry.
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit.
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before a probe is allowed.
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Snapshot of an adapter's circuit breaker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircuitBreakerInfo {
+    /// Current breaker state.
+    pub state: CircuitState,
+    /// Failures since the last successful call.
+    pub consecutive_failures: u32,
+    /// When the circuit last opened or admitted a probe.
+    pub opened_at: Option<DateTime<Utc>>,
+}
+
 /// Metadata about a registered adapter.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdapterInfo {
@@ -60,6 +111,9 @@ pub struct AdapterInfo {
     pub last_health_check: Option<DateTime<Utc>>,
     /// If `status == Error`, contains a human-readable error message.
     pub last_error: Option<String>,
+    /// Circuit breaker state, when the registry has breakers enabled.
+    #[serde(default)]
+    pub breaker: Option<CircuitBreakerInfo>,
 }
 
 // ---------------------------------------------------------------------------
@@ -72,6 +126,7 @@ pub struct AdapterInfo {
 #[derive(Clone)]
 pub struct AdapterRegistry {
     inner: Arc<DashMap<String, AdapterInfo>>,
+    breaker: Option<CircuitBreakerConfig>,
 }
 
 impl AdapterRegistry {
@@ -80,9 +135,17 @@ impl AdapterRegistry {
     pub fn new() -> Self {
         Self {
             inner: Arc::new(DashMap::new()),
+            breaker: None,
         }
     }
 
+    /// Enable a circuit breaker for every adapter registered from now on.
+    #[must_use]
+    pub fn with_circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.breaker = Some(config);
+        self
+    }
+
     /// Register a new adapter.
     ///
     /// If an adapter with the same `id` already exists, it is overwritten.
@@ -101,6 +164,11 @@ impl AdapterRegistry {
                 registered_at: Utc::now(),
                 last_health_check: None,
                 last_error: None,
+                breaker: self.breaker.as_ref().map(|_| CircuitBreakerInfo {
+                    state: CircuitState::Closed,
+                    consecutive_failures: 0,
+                    opened_at: None,
+                }),
             },
         );
     }
@@ -188,6 +256,83 @@ impl AdapterRegistry {
         Ok(())
     }
 
+    /// Ask the circuit breaker whether a call to `id` may proceed.
+    ///
+    /// Returns [`KernelError::CircuitOpen`] while the circuit is open, or
+    /// while a half-open probe is still in flight.  Once the cooldown has
+    /// elapsed the call is admitted as the probe.  Always succeeds for
+    /// registered adapters when breakers are disabled.
+    pub fn begin_call(&self, id: &str) -> Result<()> {
+        let mut entry = self
+            .inner
+            .get_mut(id)
+            .ok_or_else(|| KernelError::AdapterNotFound {
+                adapter_id: id.to_string(),
+            })?;
+        let (Some(config), Some(breaker)) = (&self.breaker, entry.breaker.as_mut()) else {
+            return Ok(());
+        };
+        if breaker.state == CircuitState::Closed {
+            return Ok(());
+        }
+
+        let now = Utc::now();
+        let elapsed = breaker
+            .opened_at
+            .and_then(|at| (now - at).to_std().ok())
+            .unwrap_or(Duration::ZERO);
+        // A half-open probe that never reported back is replaced after
+        // another cooldown.
+        if elapsed >= config.cooldown {
+            breaker.state = CircuitState::HalfOpen;
+            breaker.opened_at = Some(now);
+            tracing::info!(adapter_id = %id, "circuit half-open, admitting probe");
+            return Ok(());
+        }
+        Err(KernelError::CircuitOpen {
+            adapter_id: id.to_string(),
+            retry_after_secs: (config.cooldown - elapsed).as_secs_f64().ceil() as u64,
+        })
+    }
+
+    /// Report the outcome of a call admitted by [`begin_call`](Self::begin_call).
+    pub fn record_call(&self, id: &str, success: bool) -> Result<()> {
+        let mut entry = self
+            .inner
+            .get_mut(id)
+            .ok_or_else(|| KernelError::AdapterNotFound {
+                adapter_id: id.to_string(),
+            })?;
+        let (Some(config), Some(breaker)) = (&self.breaker, entry.breaker.as_mut()) else {
+            return Ok(());
+        };
+
+        if success {
+            if breaker.state != CircuitState::Closed {
+                tracing::info!(adapter_id = %id, "circuit closed");
+            }
+            breaker.state = CircuitState::Closed;
+            breaker.consecutive_failures = 0;
+            breaker.opened_at = None;
+            return Ok(());
+        }
+
+        breaker.consecutive_failures += 1;
+        let trip = breaker.state == CircuitState::HalfOpen
+            || breaker.consecutive_failures >= config.failure_threshold;
+        if trip && breaker.state != CircuitState::Open {
+            breaker.state = CircuitState::Open;
+            breaker.opened_at = Some(Utc::now());
+            tracing::warn!(
+                adapter_id = %id,
+                failures = breaker.consecutive_failures,
+                cooldown_secs = config.cooldown.as_secs(),
+                "circuit opened"
+            );
+        }
+        Ok(())
+    }
+
     /// Return a list of all registered adapter IDs.
     pub fn list_ids(&self) -> Vec<String> {
         self.inner.iter().map(|e| e.key().clone()).collect()
@@ -350,4 +495,51 @@ mod tests {
         registry.set_status("x", AdapterStatus::Connected).unwrap();
         assert!(registry.is_available("x"));
     }
+
+    #[test]
+    fn circuit_breaker_trips_and_recovers() {
+        let registry = AdapterRegistry::new().with_circuit_breaker(CircuitBreakerConfig {
+            failure_threshold: 2,
+            cooldown: Duration::from_millis(50),
+        });
+        registry.register("flaky", "Flaky adapter");
+        let state = |r: &AdapterRegistry| r.get("flaky").unwrap().breaker.unwrap().state;
+
+        registry.begin_call("flaky").unwrap();
+        registry.record_call("flaky", false).unwrap();
+        assert_eq!(state(&registry), CircuitState::Closed);
+        registry.begin_call("flaky").unwrap();
+        registry.record_call("flaky", false).unwrap();
+        assert_eq!(state(&registry), CircuitState::Open);
+        assert!(matches!(
+            registry.begin_call("flaky"),
+            Err(KernelError::CircuitOpen { .. })
+        ));
+
+        // After the cooldown one probe is admitted; a failed probe re-opens.
+        std::thread::sleep(Duration::from_millis(60));
+        registry.begin_call("flaky").unwrap();
+        assert_eq!(state(&registry), CircuitState::HalfOpen);
+        assert!(registry.begin_call("flaky").is_err());
+        registry.record_call("flaky", false).unwrap();
+        assert_eq!(state(&registry), CircuitState::Open);
+
+        std::thread::sleep(Duration::from_millis(60));
+        registry.begin_call("flaky").unwrap();
+        registry.record_call("flaky", true).unwrap();
+        let breaker = registry.get("flaky").unwrap().breaker.unwrap();
+        assert_eq!(breaker.state, CircuitState::Closed);
+        assert_eq!(breaker.consecutive_failures, 0);
+    }
+
+    #[test]
+    fn breaker_disabled_by_default() {
+        let registry = AdapterRegistry::new();
+        registry.register("x", "X");
+        for _ in 0..10 {
+            registry.begin_call("x").unwrap();
+            registry.record_call("x", false).unwrap();
+        }
+        assert!(registry.get("x").unwrap().breaker.is_none());
+    }
 }
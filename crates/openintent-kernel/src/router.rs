@@ -6,13 +6,24 @@
 //! | Level | Technique | Typical Latency |
 //! |-------|-----------|-----------------|
 //! | 1 | Exact match via [`aho_corasick`] (SIMD-accelerated) | < 0.01 ms |
+//! | 1b | Optional typo-tolerant match against the exact phrases | < 0.1 ms |
 //! | 2 | Pattern match via compiled [`regex`] with named captures | < 0.1 ms |
 //! | 3 | Fallback marker indicating the intent should be sent to an LLM | N/A |
 //!
+//! The typo-tolerant level is off by default; enable it with
+//! [`IntentRouter::set_fuzzy_max_edits`].  It compares each exact phrase with
+//! same-sized word windows of the intent by Levenshtein distance, so
+//! "remid me" still reaches the handler for "remind me".
+//!
 //! The router is designed to be built incrementally: exact phrases and
 //! patterns can be added at runtime, and the internal automaton is rebuilt
 //! lazily on the next routing call.
 //!
+//! Every call to [`IntentRouter::route`] is counted; [`IntentRouter::metrics`]
+//! reports hits per level and the LLM-fallback rate over the most recent
+//! routes.  A high fallback rate usually means exact phrases or patterns are
+//! missing.
+//!
 //! # Example
 //!
 //! ```rust
@@ -30,7 +41,7 @@
 //! assert!(matches!(result, RouteResult::ExactMatch { .. }));
 //! ```
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
 use aho_corasick::AhoCorasick;
 use regex::Regex;
@@ -54,6 +65,19 @@ pub enum RouteResult {
         matched_phrase: String,
     },
 
+    /// Level 1b: the intent text matched an exact phrase after correcting
+    /// minor typos.
+    FuzzyMatch {
+        /// The handler identifier associated with the matched phrase.
+        handler: String,
+        /// The registered phrase that was matched.
+        matched_phrase: String,
+        /// Levenshtein distance between the phrase and the intent text.
+        distance: usize,
+        /// `1.0 - distance / phrase length`; higher is a closer match.
+        confidence: f32,
+    },
+
     /// Level 2: the intent text matched a regex pattern.  Named captures are
     /// provided as key/value pairs.
     PatternMatch {
@@ -71,6 +95,32 @@ pub enum RouteResult {
     },
 }
 
+/// Routing counters reported by [`IntentRouter::metrics`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RouterMetrics {
+    /// Intents resolved by level 1 (exact match).
+    pub exact_hits: u64,
+    /// Intents resolved by level 1b (typo-tolerant match).
+    pub fuzzy_hits: u64,
+    /// Intents resolved by level 2 (pattern match).
+    pub pattern_hits: u64,
+    /// Intents that fell through to level 3 (LLM fallback).
+    pub llm_fallbacks: u64,
+    /// Share of the last [`METRICS_WINDOW`] routes that fell back to the
+    /// LLM, from `0.0` to `1.0`.
+    pub recent_fallback_rate: f64,
+}
+
+impl RouterMetrics {
+    /// Total number of intents routed.
+    pub fn total(&self) -> u64 {
+        self.exact_hits + self.fuzzy_hits + self.pattern_hits + self.llm_fallbacks
+    }
+}
+
+/// Number of recent routes the fallback rate is computed over.
+pub const METRICS_WINDOW: usize = 100;
+
 /// A regex-based route with named captures.
 #[derive(Debug, Clone)]
 pub struct PatternRoute {
@@ -104,8 +154,17 @@ pub struct IntentRouter {
     /// build.
     automaton_dirty: bool,
 
+    /// Maximum edits tolerated by the typo-tolerant level (0 disables it).
+    fuzzy_max_edits: usize,
+
     /// Regex-based pattern routes, evaluated in registration order.
     patterns: Vec<PatternRoute>,
+
+    /// Lifetime hit counters (the fallback rate is filled in on read).
+    metrics: RouterMetrics,
+
+    /// Whether each of the last [`METRICS_WINDOW`] routes fell back.
+    recent_fallbacks: VecDeque<bool>,
 }
 
 impl IntentRouter {
@@ -116,7 +175,10 @@ impl IntentRouter {
             exact_phrases: Vec::new(),
             automaton: None,
             automaton_dirty: false,
+            fuzzy_max_edits: 0,
             patterns: Vec::new(),
+            metrics: RouterMetrics::default(),
+            recent_fallbacks: VecDeque::with_capacity(METRICS_WINDOW),
         }
     }
 
@@ -132,6 +194,15 @@ impl IntentRouter {
         self.automaton_dirty = true;
     }
 
+    /// Enable typo-tolerant matching of exact phrases, allowing up to
+    /// `max_edits` character edits.  `0` disables it.
+    ///
+    /// Short phrases tolerate fewer edits (one per four characters) so that
+    /// e.g. "hi" cannot match "ok".
+    pub fn set_fuzzy_max_edits(&mut self, max_edits: usize) {
+        self.fuzzy_max_edits = max_edits;
+    }
+
     /// Register a regex pattern route.
     ///
     /// The pattern may contain named captures (e.g. `(?P<name>...)`) which
@@ -179,10 +250,43 @@ impl IntentRouter {
 
     /// Route an intent string through the 3-level cascade.
     ///
-    /// 1. Exact match (Aho-Corasick SIMD)
+    /// 1. Exact match (Aho-Corasick SIMD), then typo-tolerant match if enabled
     /// 2. Pattern match (regex)
     /// 3. LLM fallback marker
     pub fn route(&mut self, intent: &str) -> RouteResult {
+        let result = self.resolve(intent);
+        self.record(&result);
+        result
+    }
+
+    /// Snapshot of the routing counters.
+    pub fn metrics(&self) -> RouterMetrics {
+        let fallbacks = self.recent_fallbacks.iter().filter(|&&f| f).count();
+        let recent_fallback_rate = if self.recent_fallbacks.is_empty() {
+            0.0
+        } else {
+            fallbacks as f64 / self.recent_fallbacks.len() as f64
+        };
+        RouterMetrics {
+            recent_fallback_rate,
+            ..self.metrics.clone()
+        }
+    }
+
+    /// Return the number of registered exact phrases.
+    pub fn exact_count(&self) -> usize {
+        self.exact_phrases.len()
+    }
+
+    /// Return the number of registered pattern routes.
+    pub fn pattern_count(&self) -> usize {
+        self.patterns.len()
+    }
+
+    // -- Private helpers ----------------------------------------------------
+
+    /// Run the cascade without touching the metrics.
+    fn resolve(&mut self, intent: &str) -> RouteResult {
         let lowered = intent.to_lowercase();
 
         // Level 1: Exact match.
@@ -191,6 +295,12 @@ impl IntentRouter {
             return result;
         }
 
+        // Level 1b: Typo-tolerant match.
+        if let Some(result) = self.try_fuzzy_match(&lowered) {
+            tracing::debug!(intent = %intent, handler = %result.handler(), "L1 fuzzy match");
+            return result;
+        }
+
         // Level 2: Pattern match.
         if let Some(result) = self.try_pattern_match(&lowered) {
             tracing::debug!(intent = %intent, handler = %result.handler(), "L2 pattern match");
@@ -204,18 +314,32 @@ impl IntentRouter {
         }
     }
 
-    /// Return the number of registered exact phrases.
-    pub fn exact_count(&self) -> usize {
-        self.exact_phrases.len()
-    }
-
-    /// Return the number of registered pattern routes.
-    pub fn pattern_count(&self) -> usize {
-        self.patterns.len()
+    /// Count a routing outcome.
+    fn record(&mut self, result: &RouteResult) {
+        let fallback = match result {
+            RouteResult::ExactMatch { .. } => {
+                self.metrics.exact_hits += 1;
+                false
+            }
+            RouteResult::FuzzyMatch { .. } => {
+                self.metrics.fuzzy_hits += 1;
+                false
+            }
+            RouteResult::PatternMatch { .. } => {
+                self.metrics.pattern_hits += 1;
+                false
+            }
+            RouteResult::LlmFallback { .. } => {
+                self.metrics.llm_fallbacks += 1;
+                true
+            }
+        };
+        if self.recent_fallbacks.len() == METRICS_WINDOW {
+            self.recent_fallbacks.pop_front();
+        }
+        self.recent_fallbacks.push_back(fallback);
     }
 
-    // -- Private helpers ----------------------------------------------------
-
     /// Rebuild the Aho-Corasick automaton if it is stale.
     fn ensure_automaton(&mut self) {
         if !self.automaton_dirty && self.automaton.is_some() {
@@ -273,6 +397,52 @@ impl IntentRouter {
         })
     }
 
+    /// Attempt a typo-tolerant match of the exact phrases.
+    ///
+    /// Each phrase is compared with every window of the intent holding the
+    /// same number of words, give or take one.  The closest match wins, and
+    /// the longer phrase breaks ties.
+    fn try_fuzzy_match(&self, lowered: &str) -> Option<RouteResult> {
+        if self.fuzzy_max_edits == 0 {
+            return None;
+        }
+        let words: Vec<&str> = lowered.split_whitespace().collect();
+
+        // (phrase index, distance, phrase length in chars)
+        let mut best: Option<(usize, usize, usize)> = None;
+        for (idx, (phrase, _)) in self.exact_phrases.iter().enumerate() {
+            let phrase_len = phrase.chars().count();
+            let max_edits = self.fuzzy_max_edits.min(phrase_len / 4);
+            if max_edits == 0 {
+                continue;
+            }
+            let phrase_words = phrase.split_whitespace().count();
+            for size in phrase_words.saturating_sub(1).max(1)..=phrase_words + 1 {
+                for window in words.windows(size) {
+                    let candidate = window.join(" ");
+                    let Some(distance) = bounded_levenshtein(phrase, &candidate, max_edits) else {
+                        continue;
+                    };
+                    let better = best.is_none_or(|(_, best_dist, best_len)| {
+                        distance < best_dist || (distance == best_dist && phrase_len > best_len)
+                    });
+                    if better {
+                        best = Some((idx, distance, phrase_len));
+                    }
+                }
+            }
+        }
+
+        let (idx, distance, phrase_len) = best?;
+        let (phrase, handler) = &self.exact_phrases[idx];
+        Some(RouteResult::FuzzyMatch {
+            handler: handler.clone(),
+            matched_phrase: phrase.clone(),
+            distance,
+            confidence: 1.0 - distance as f32 / phrase_len as f32,
+        })
+    }
+
     /// Attempt a pattern match against all registered regex routes.
     fn try_pattern_match(&self, lowered: &str) -> Option<RouteResult> {
         for route in &self.patterns {
@@ -304,12 +474,40 @@ impl RouteResult {
     /// Return the handler string for any variant (convenience accessor).
     pub fn handler(&self) -> &str {
         match self {
-            Self::ExactMatch { handler, .. } | Self::PatternMatch { handler, .. } => handler,
+            Self::ExactMatch { handler, .. }
+            | Self::FuzzyMatch { handler, .. }
+            | Self::PatternMatch { handler, .. } => handler,
             Self::LlmFallback { .. } => "",
         }
     }
 }
 
+/// Levenshtein distance between `a` and `b`, or `None` if it exceeds `max`.
+fn bounded_levenshtein(a: &str, b: &str, max: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != cb);
+            curr[j + 1] = substitution.min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        // Every later row is at least the current row's minimum.
+        if curr.iter().min().is_some_and(|&m| m > max) {
+            return None;
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    let distance = prev[b.len()];
+    (distance <= max).then_some(distance)
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -421,4 +619,83 @@ mod tests {
             RouteResult::ExactMatch { .. }
         ));
     }
+
+    #[test]
+    fn metrics_count_hits_per_level() {
+        let mut router = IntentRouter::new();
+        router.add_exact("check email", "adapter:email:check");
+        router
+            .add_pattern(r"remind me to (?P<task>.+)", "reminder:create")
+            .unwrap();
+        assert_eq!(router.metrics(), RouterMetrics::default());
+
+        router.route("check email");
+        router.route("please check email now");
+        router.route("remind me to water the plants");
+        router.route("what's the meaning of life");
+
+        let metrics = router.metrics();
+        assert_eq!(metrics.exact_hits, 2);
+        assert_eq!(metrics.pattern_hits, 1);
+        assert_eq!(metrics.llm_fallbacks, 1);
+        assert_eq!(metrics.total(), 4);
+        assert!((metrics.recent_fallback_rate - 0.25).abs() < f64::EPSILON);
+
+        // The rate only covers the most recent routes.
+        for _ in 0..METRICS_WINDOW {
+            router.route("check email");
+        }
+        let metrics = router.metrics();
+        assert_eq!(metrics.llm_fallbacks, 1);
+        assert_eq!(metrics.recent_fallback_rate, 0.0);
+    }
+
+    #[test]
+    fn fuzzy_match_tolerates_typos() {
+        let mut router = IntentRouter::new();
+        router.add_exact("remind me", "reminder:create");
+        router.add_exact("hi", "greet");
+
+        // Disabled by default.
+        assert!(matches!(
+            router.route("remid me to call bob"),
+            RouteResult::LlmFallback { .. }
+        ));
+
+        router.set_fuzzy_max_edits(2);
+        match router.route("remid me to call bob") {
+            RouteResult::FuzzyMatch {
+                handler,
+                matched_phrase,
+                distance,
+                confidence,
+            } => {
+                assert_eq!(handler, "reminder:create");
+                assert_eq!(matched_phrase, "remind me");
+                assert_eq!(distance, 1);
+                assert!((confidence - (1.0 - 1.0 / 9.0)).abs() < 1e-6);
+            }
+            other => panic!("expected FuzzyMatch, got {other:?}"),
+        }
+
+        // Too short to correct, and too far from anything registered.
+        assert!(matches!(
+            router.route("ho"),
+            RouteResult::LlmFallback { .. }
+        ));
+        assert!(matches!(
+            router.route("rewind tape"),
+            RouteResult::LlmFallback { .. }
+        ));
+        assert_eq!(router.metrics().fuzzy_hits, 1);
+    }
+
+    #[test]
+    fn bounded_levenshtein_distances() {
+        assert_eq!(bounded_levenshtein("remind", "remind", 2), Some(0));
+        assert_eq!(bounded_levenshtein("remind", "remid", 2), Some(1));
+        assert_eq!(bounded_levenshtein("kitten", "sitting", 3), Some(3));
+        assert_eq!(bounded_levenshtein("kitten", "sitting", 2), None);
+        assert_eq!(bounded_levenshtein("a", "abcd", 2), None);
+    }
 }
@@ -22,15 +22,40 @@
 //! Tasks may be cancelled at any point before they enter the `Running` state.
 //! Once running, cancellation is cooperative via the [`tokio::sync::CancellationToken`]
 //! mechanism exposed on each task's context (not yet wired -- reserved for v2).
+//!
+//! # Retries and dead letters
+//!
+//! A failed task is retried up to [`SchedulerConfig::max_retries`] times,
+//! waiting [`SchedulerConfig::retry_backoff`] times the attempt number before
+//! each retry.  A task that fails every attempt is moved to the dead-letter
+//! list, where [`Scheduler::dead_letters`] reports it with its last error and
+//! [`Scheduler::requeue`] runs it again.  When
+//! [`SchedulerConfig::dead_letter_path`] is set the list is also written to
+//! that JSON file and reloaded on startup, so failures survive a restart
+//! (the task's closure does not, so reloaded entries cannot be requeued).
+//!
+//! # Shutdown
+//!
+//! [`Scheduler::shutdown`] stops accepting work, lets the task that is
+//! already running finish (up to a timeout), and hands back every task that
+//! never started so the caller can persist it and submit it again later.
+//!
+//! # Status events
+//!
+//! With an [`IpcBus`] attached ([`Scheduler::with_bus`]), every status
+//! transition is also published as [`Event::TaskStatusChanged`].
+
+mod priority;
+mod retry;
 
 use std::collections::HashMap;
 use std::future::Future;
+use std::path::PathBuf;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
-use crossbeam::queue::SegQueue;
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
 use tokio::sync::Notify;
@@ -38,6 +63,10 @@ use tokio::task::JoinHandle;
 use uuid::Uuid;
 
 use crate::error::{KernelError, Result};
+use crate::ipc::{Event, IpcBus};
+
+use self::priority::PriorityQueues;
+use self::retry::{DeadLetterEntry, load_dead_letters};
 
 // ---------------------------------------------------------------------------
 // Public types
@@ -93,11 +122,10 @@ pub enum SchedulePolicy {
 /// The async closure that the scheduler will execute.
 ///
 /// We box the future so that callers can supply arbitrary async work without
-/// leaking concrete types into the scheduler.
+/// leaking concrete types into the scheduler.  The closure is called once per
+/// attempt, so retries and requeues start from a fresh future.
 pub type TaskFn = Box<
-    dyn FnOnce() -> Pin<Box<dyn Future<Output = std::result::Result<(), String>> + Send>>
-        + Send
-        + Sync,
+    dyn Fn() -> Pin<Box<dyn Future<Output = std::result::Result<(), String>> + Send>> + Send + Sync,
 >;
 
 /// Metadata snapshot of a task visible to external callers.
@@ -111,6 +139,41 @@ pub struct TaskInfo {
     pub started_at: Option<DateTime<Utc>>,
     pub completed_at: Option<DateTime<Utc>>,
     pub error: Option<String>,
+    /// Number of times the task has started running.
+    #[serde(default)]
+    pub attempts: u32,
+}
+
+/// A task that failed every attempt, kept for inspection and requeueing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    /// The task as it was when it was dead-lettered.
+    pub task: TaskInfo,
+    /// The error returned by the last attempt.
+    pub error: String,
+    /// When the last attempt failed.
+    pub failed_at: DateTime<Utc>,
+}
+
+/// Retry and dead-letter settings for a [`Scheduler`].
+#[derive(Debug, Clone)]
+pub struct SchedulerConfig {
+    /// How many times a failed task is retried before it is dead-lettered.
+    pub max_retries: u32,
+    /// Base delay before a retry; the n-th retry waits `n * retry_backoff`.
+    pub retry_backoff: Duration,
+    /// JSON file the dead-letter list is persisted to, if any.
+    pub dead_letter_path: Option<PathBuf>,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            retry_backoff: Duration::from_secs(1),
+            dead_letter_path: None,
+        }
+    }
 }
 
 /// Internal representation of a task that lives on the queue.
@@ -136,7 +199,7 @@ pub struct Scheduler {
 
 struct SchedulerInner {
     /// One lock-free queue per priority lane.
-    queues: [SegQueue<QueuedTask>; 4],
+    queues: PriorityQueues,
 
     /// Authoritative task metadata.  Updated atomically via `DashMap`.
     tasks: DashMap<TaskId, TaskInfo>,
@@ -144,8 +207,26 @@ struct SchedulerInner {
     /// Wakes the background worker when new work arrives.
     notify: Notify,
 
+    /// Retry and dead-letter settings.
+    config: SchedulerConfig,
+
+    /// Tasks that failed every attempt.
+    dead_letters: DashMap<TaskId, DeadLetterEntry>,
+
+    /// Serializes writes of the dead-letter file.
+    dead_letter_write: std::sync::Mutex<()>,
+
+    /// Number of tasks currently executing.
+    running: std::sync::atomic::AtomicUsize,
+
+    /// Signalled whenever a running task finishes.
+    idle: Notify,
+
     /// When `true` the scheduler will not accept new work.
     shutdown: std::sync::atomic::AtomicBool,
+
+    /// Bus that status transitions are published on, if any.
+    bus: std::sync::OnceLock<IpcBus>,
 }
 
 impl Scheduler {
@@ -154,21 +235,44 @@ impl Scheduler {
     /// Call [`Scheduler::start`] to spawn the worker onto the tokio runtime.
     #[must_use]
     pub fn new() -> Self {
+        Self::with_config(SchedulerConfig::default())
+    }
+
+    /// Create a scheduler with explicit retry and dead-letter settings,
+    /// loading any dead letters persisted at
+    /// [`SchedulerConfig::dead_letter_path`].
+    #[must_use]
+    pub fn with_config(config: SchedulerConfig) -> Self {
+        let dead_letters = DashMap::new();
+        if let Some(path) = &config.dead_letter_path {
+            for record in load_dead_letters(path) {
+                dead_letters.insert(record.task.id, DeadLetterEntry { record, work: None });
+            }
+        }
         Self {
             inner: Arc::new(SchedulerInner {
-                queues: [
-                    SegQueue::new(),
-                    SegQueue::new(),
-                    SegQueue::new(),
-                    SegQueue::new(),
-                ],
+                queues: PriorityQueues::new(),
                 tasks: DashMap::new(),
                 notify: Notify::new(),
+                config,
+                dead_letters,
+                dead_letter_write: std::sync::Mutex::new(()),
+                running: std::sync::atomic::AtomicUsize::new(0),
+                idle: Notify::new(),
                 shutdown: std::sync::atomic::AtomicBool::new(false),
+                bus: std::sync::OnceLock::new(),
             }),
         }
     }
 
+    /// Publish [`Event::TaskStatusChanged`] on `bus` whenever a task changes
+    /// state.  A scheduler that already has a bus keeps it.
+    #[must_use]
+    pub fn with_bus(self, bus: IpcBus) -> Self {
+        let _ = self.inner.bus.set(bus);
+        self
+    }
+
     /// Spawn the background worker that polls the queues and executes tasks.
     ///
     /// Returns a [`JoinHandle`] that resolves when the scheduler is shut down.
@@ -176,7 +280,7 @@ impl Scheduler {
         let inner = Arc::clone(&self.inner);
         tokio::spawn(async move {
             tracing::info!("scheduler worker started");
-            Self::worker_loop(&inner).await;
+            SchedulerInner::worker_loop(&inner).await;
             tracing::info!("scheduler worker stopped");
         })
     }
@@ -219,20 +323,32 @@ impl Scheduler {
             started_at: None,
             completed_at: None,
             error: None,
+            attempts: 0,
         };
         self.inner.tasks.insert(id, info);
+        self.inner.publish_status(id, &name, TaskStatus::Pending);
 
         tracing::debug!(task_id = %id, task_name = %name, ?priority, "task submitted");
 
         match policy {
             SchedulePolicy::Immediate => {
-                self.enqueue(id, name, priority, work);
+                self.inner.enqueue(QueuedTask {
+                    id,
+                    name,
+                    priority,
+                    work,
+                });
             }
             SchedulePolicy::Delayed { delay } => {
                 let scheduler = self.clone();
                 tokio::spawn(async move {
                     tokio::time::sleep(delay).await;
-                    scheduler.enqueue(id, name, priority, work);
+                    scheduler.inner.enqueue(QueuedTask {
+                        id,
+                        name,
+                        priority,
+                        work,
+                    });
                 });
             }
             SchedulePolicy::At { when } => {
@@ -243,7 +359,12 @@ impl Scheduler {
                         let delta = (when - now).to_std().unwrap_or(Duration::from_millis(0));
                         tokio::time::sleep(delta).await;
                     }
-                    scheduler.enqueue(id, name, priority, work);
+                    scheduler.inner.enqueue(QueuedTask {
+                        id,
+                        name,
+                        priority,
+                        work,
+                    });
                 });
             }
             SchedulePolicy::Cron { expression } => {
@@ -255,7 +376,12 @@ impl Scheduler {
                     cron = %expression,
                     "cron scheduling is not yet fully implemented; running once immediately"
                 );
-                self.enqueue(id, name, priority, work);
+                self.inner.enqueue(QueuedTask {
+                    id,
+                    name,
+                    priority,
+                    work,
+                });
             }
         }
 
@@ -277,6 +403,8 @@ impl Scheduler {
             TaskStatus::Pending | TaskStatus::Queued => {
                 entry.status = TaskStatus::Cancelled;
                 entry.completed_at = Some(Utc::now());
+                self.inner
+                    .publish_status(task_id, &entry.name, TaskStatus::Cancelled);
                 tracing::info!(task_id = %task_id, "task cancelled");
                 Ok(())
             }
@@ -305,53 +433,104 @@ impl Scheduler {
             .collect()
     }
 
-    /// Signal the scheduler to stop accepting new work and drain remaining
-    /// tasks.  The background worker will exit after the current task (if any)
-    /// finishes.
-    pub fn shutdown(&self) {
+    /// Stop accepting new work and wait up to `timeout` for running tasks to
+    /// finish.
+    ///
+    /// The background worker exits after the current task (if any) instead
+    /// of starting queued ones.  Tasks that never started -- queued, delayed,
+    /// or waiting for a retry -- are marked `Cancelled` and returned as they
+    /// were before shutdown, so callers can persist and resubmit them.  A
+    /// task still running when `timeout` elapses is abandoned.
+    pub async fn shutdown(&self, timeout: Duration) -> Vec<TaskInfo> {
         tracing::info!("scheduler shutdown requested");
         self.inner
             .shutdown
             .store(true, std::sync::atomic::Ordering::Release);
         self.inner.notify.notify_one();
+
+        let drained = async {
+            loop {
+                // Register for the wakeup before checking, so a task that
+                // finishes in between is not missed.
+                let idle = self.inner.idle.notified();
+                if self
+                    .inner
+                    .running
+                    .load(std::sync::atomic::Ordering::Acquire)
+                    == 0
+                {
+                    break;
+                }
+                idle.await;
+            }
+        };
+        if tokio::time::timeout(timeout, drained).await.is_err() {
+            tracing::warn!(
+                timeout_ms = timeout.as_millis() as u64,
+                "scheduler shutdown timed out with tasks still running"
+            );
+        }
+
+        // Empty the lanes so the dropped closures release their captures.
+        self.inner.queues.clear();
+
+        let mut unstarted = Vec::new();
+        for mut entry in self.inner.tasks.iter_mut() {
+            if matches!(entry.status, TaskStatus::Pending | TaskStatus::Queued) {
+                unstarted.push(entry.clone());
+                entry.status = TaskStatus::Cancelled;
+                entry.completed_at = Some(Utc::now());
+                self.inner
+                    .publish_status(entry.id, &entry.name, TaskStatus::Cancelled);
+            }
+        }
+        unstarted.sort_by_key(|t| (t.priority, t.created_at));
+        tracing::info!(unstarted = unstarted.len(), "scheduler shut down");
+        unstarted
     }
+}
 
-    // -- Private helpers ----------------------------------------------------
+impl SchedulerInner {
+    /// Publish a status transition on the attached bus, if any.
+    fn publish_status(&self, task_id: TaskId, task_name: &str, status: TaskStatus) {
+        let Some(bus) = self.bus.get() else {
+            return;
+        };
+        let _ = bus.publish(Event::TaskStatusChanged {
+            task_id,
+            task_name: task_name.to_string(),
+            new_status: format!("{status:?}"),
+            timestamp: Utc::now(),
+        });
+    }
 
     /// Move a task from `Pending` to `Queued` and push it onto the
     /// appropriate priority lane.
-    fn enqueue(&self, id: TaskId, name: String, priority: TaskPriority, work: TaskFn) {
+    fn enqueue(&self, task: QueuedTask) {
         // Update status to Queued.
-        if let Some(mut entry) = self.inner.tasks.get_mut(&id) {
+        if let Some(mut entry) = self.tasks.get_mut(&task.id) {
             // If the task was cancelled while waiting for its delay, skip.
             if entry.status == TaskStatus::Cancelled {
-                tracing::debug!(task_id = %id, "skipping enqueue for cancelled task");
+                tracing::debug!(task_id = %task.id, "skipping enqueue for cancelled task");
                 return;
             }
             entry.status = TaskStatus::Queued;
+            self.publish_status(task.id, &task.name, TaskStatus::Queued);
         }
 
-        let lane = priority as usize;
-        self.inner.queues[lane].push(QueuedTask {
-            id,
-            name,
-            priority,
-            work,
-        });
-        self.inner.notify.notify_one();
+        self.queues.push(task);
+        self.notify.notify_one();
     }
 
     /// Background worker loop.
-    async fn worker_loop(inner: &SchedulerInner) {
+    async fn worker_loop(inner: &Arc<Self>) {
         loop {
-            // Try to dequeue work in priority order.
-            let task = inner.queues[0]
-                .pop()
-                .or_else(|| inner.queues[1].pop())
-                .or_else(|| inner.queues[2].pop())
-                .or_else(|| inner.queues[3].pop());
-
-            match task {
+            // Leave queued work for `shutdown` to hand back to the caller.
+            if inner.shutdown.load(std::sync::atomic::Ordering::Acquire) {
+                break;
+            }
+
+            match inner.queues.pop() {
                 Some(queued) => {
                     // Check if cancelled while queued.
                     let should_run = inner
@@ -366,9 +545,14 @@ impl Scheduler {
                     }
 
                     // Transition to Running.
+                    inner
+                        .running
+                        .fetch_add(1, std::sync::atomic::Ordering::AcqRel);
                     if let Some(mut entry) = inner.tasks.get_mut(&queued.id) {
                         entry.status = TaskStatus::Running;
                         entry.started_at = Some(Utc::now());
+                        entry.attempts += 1;
+                        inner.publish_status(queued.id, &queued.name, TaskStatus::Running);
                     }
 
                     tracing::info!(
@@ -379,26 +563,26 @@ impl Scheduler {
                     );
 
                     let future = (queued.work)();
-                    let result = future.await;
-
-                    if let Some(mut entry) = inner.tasks.get_mut(&queued.id) {
-                        entry.completed_at = Some(Utc::now());
-                        match result {
-                            Ok(()) => {
+                    match future.await {
+                        Ok(()) => {
+                            if let Some(mut entry) = inner.tasks.get_mut(&queued.id) {
+                                entry.completed_at = Some(Utc::now());
                                 entry.status = TaskStatus::Completed;
-                                tracing::info!(task_id = %queued.id, "task completed");
-                            }
-                            Err(err) => {
-                                entry.status = TaskStatus::Failed;
-                                entry.error = Some(err.clone());
-                                tracing::error!(
-                                    task_id = %queued.id,
-                                    error = %err,
-                                    "task failed"
+                                entry.error = None;
+                                inner.publish_status(
+                                    queued.id,
+                                    &queued.name,
+                                    TaskStatus::Completed,
                                 );
+                                tracing::info!(task_id = %queued.id, "task completed");
                             }
                         }
+                        Err(err) => Self::handle_failure(inner, queued, err),
                     }
+                    inner
+                        .running
+                        .fetch_sub(1, std::sync::atomic::Ordering::AcqRel);
+                    inner.idle.notify_waiters();
                 }
                 None => {
                     // Nothing to do.  Check for shutdown before sleeping.
@@ -461,59 +645,10 @@ mod tests {
         assert_eq!(info.status, TaskStatus::Completed);
         assert_eq!(counter.load(Ordering::SeqCst), 1);
 
-        scheduler.shutdown();
+        scheduler.shutdown(Duration::from_secs(1)).await;
         handle.await.expect("worker should exit cleanly");
     }
 
-    #[tokio::test]
-    async fn priority_ordering() {
-        let scheduler = Scheduler::new();
-
-        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
-
-        // Submit low first, then critical -- critical should run first.
-        let o1 = Arc::clone(&order);
-        scheduler
-            .submit(
-                "low-task",
-                TaskPriority::Low,
-                Box::new(move || {
-                    let o = Arc::clone(&o1);
-                    Box::pin(async move {
-                        o.lock().unwrap().push("low");
-                        Ok(())
-                    })
-                }),
-            )
-            .expect("submit low");
-
-        let o2 = Arc::clone(&order);
-        scheduler
-            .submit(
-                "critical-task",
-                TaskPriority::Critical,
-                Box::new(move || {
-                    let o = Arc::clone(&o2);
-                    Box::pin(async move {
-                        o.lock().unwrap().push("critical");
-                        Ok(())
-                    })
-                }),
-            )
-            .expect("submit critical");
-
-        // Start the worker *after* both tasks are queued so ordering is
-        // deterministic.
-        let handle = scheduler.start();
-        tokio::time::sleep(Duration::from_millis(100)).await;
-
-        let result = order.lock().unwrap().clone();
-        assert_eq!(result, vec!["critical", "low"]);
-
-        scheduler.shutdown();
-        handle.await.expect("worker exit");
-    }
-
     #[tokio::test]
     async fn cancel_pending_task() {
         let scheduler = Scheduler::new();
@@ -554,14 +689,64 @@ mod tests {
         assert_eq!(info.status, TaskStatus::Failed);
         assert_eq!(info.error.as_deref(), Some("boom"));
 
-        scheduler.shutdown();
+        scheduler.shutdown(Duration::from_secs(1)).await;
+        handle.await.expect("worker exit");
+    }
+
+    #[tokio::test]
+    async fn status_changes_are_published_on_the_bus() {
+        let bus = IpcBus::new(16);
+        let mut rx = bus.subscribe();
+        let scheduler = Scheduler::new().with_bus(bus);
+        let handle = scheduler.start();
+
+        let ok = scheduler
+            .submit(
+                "ok-task",
+                TaskPriority::Normal,
+                Box::new(|| Box::pin(async { Ok(()) })),
+            )
+            .expect("submit");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let failing = scheduler
+            .submit(
+                "fail-task",
+                TaskPriority::Normal,
+                Box::new(|| Box::pin(async { Err("boom".to_string()) })),
+            )
+            .expect("submit");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let mut seen = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let Event::TaskStatusChanged {
+                task_id,
+                new_status,
+                ..
+            } = &*event
+            {
+                seen.push((*task_id, new_status.clone()));
+            }
+        }
+        let expected: Vec<_> = ["Pending", "Queued", "Running", "Completed"]
+            .into_iter()
+            .map(|s| (ok, s.to_string()))
+            .chain(
+                ["Pending", "Queued", "Running", "Failed"]
+                    .into_iter()
+                    .map(|s| (failing, s.to_string())),
+            )
+            .collect();
+        assert_eq!(seen, expected);
+
+        scheduler.shutdown(Duration::from_secs(1)).await;
         handle.await.expect("worker exit");
     }
 
     #[tokio::test]
     async fn shutdown_rejects_new_work() {
         let scheduler = Scheduler::new();
-        scheduler.shutdown();
+        scheduler.shutdown(Duration::from_secs(1)).await;
 
         let result = scheduler.submit(
             "late-task",
@@ -570,4 +755,60 @@ mod tests {
         );
         assert!(matches!(result, Err(KernelError::SchedulerShutdown)));
     }
+
+    #[tokio::test]
+    async fn shutdown_awaits_running_and_returns_unstarted() {
+        let scheduler = Scheduler::new();
+        let handle = scheduler.start();
+
+        let finished = Arc::new(AtomicU32::new(0));
+        let f = Arc::clone(&finished);
+        let running = scheduler
+            .submit(
+                "slow-task",
+                TaskPriority::Normal,
+                Box::new(move || {
+                    let f = Arc::clone(&f);
+                    Box::pin(async move {
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                        f.fetch_add(1, Ordering::SeqCst);
+                        Ok(())
+                    })
+                }),
+            )
+            .expect("submit slow");
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(
+            scheduler.status(running).unwrap().status,
+            TaskStatus::Running
+        );
+
+        let mut queued = Vec::new();
+        for priority in [TaskPriority::Low, TaskPriority::High] {
+            let id = scheduler
+                .submit(
+                    "queued-task",
+                    priority,
+                    Box::new(|| Box::pin(async { Ok(()) })),
+                )
+                .expect("submit queued");
+            queued.push(id);
+        }
+
+        let unstarted = scheduler.shutdown(Duration::from_secs(1)).await;
+        handle.await.expect("worker exit");
+
+        assert_eq!(finished.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            scheduler.status(running).unwrap().status,
+            TaskStatus::Completed
+        );
+        let ids: Vec<TaskId> = unstarted.iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![queued[1], queued[0]]);
+        assert!(unstarted.iter().all(|t| t.status == TaskStatus::Queued));
+        assert_eq!(
+            scheduler.status(queued[0]).unwrap().status,
+            TaskStatus::Cancelled
+        );
+    }
 }
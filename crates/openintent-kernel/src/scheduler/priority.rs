@@ -0,0 +1,99 @@
+//! Priority lanes.
+//!
+//! One lock-free queue per [`TaskPriority`](super::TaskPriority); the worker always takes from
+//! the most urgent non-empty lane, so bulk low-priority submissions never
+//! delay critical work.
+
+use crossbeam::queue::SegQueue;
+
+use super::QueuedTask;
+
+/// Number of lanes, one per `TaskPriority` variant.
+const LANES: usize = 4;
+
+/// The queued tasks, partitioned by priority.
+pub(super) struct PriorityQueues {
+    /// Indexed by `TaskPriority as usize`, most urgent first.
+    lanes: [SegQueue<QueuedTask>; LANES],
+}
+
+impl PriorityQueues {
+    pub(super) fn new() -> Self {
+        Self {
+            lanes: std::array::from_fn(|_| SegQueue::new()),
+        }
+    }
+
+    /// Add `task` to the back of its priority lane.
+    pub(super) fn push(&self, task: QueuedTask) {
+        let lane = task.priority as usize;
+        self.lanes[lane].push(task);
+    }
+
+    /// Take the oldest task from the most urgent non-empty lane.
+    pub(super) fn pop(&self) -> Option<QueuedTask> {
+        self.lanes.iter().find_map(SegQueue::pop)
+    }
+
+    /// Drop every queued task.
+    pub(super) fn clear(&self) {
+        while self.pop().is_some() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::scheduler::{Scheduler, TaskPriority};
+
+    #[tokio::test]
+    async fn priority_ordering() {
+        let scheduler = Scheduler::new();
+
+        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        // Submit low first, then critical -- critical should run first.
+        let o1 = Arc::clone(&order);
+        scheduler
+            .submit(
+                "low-task",
+                TaskPriority::Low,
+                Box::new(move || {
+                    let o = Arc::clone(&o1);
+                    Box::pin(async move {
+                        o.lock().unwrap().push("low");
+                        Ok(())
+                    })
+                }),
+            )
+            .expect("submit low");
+
+        let o2 = Arc::clone(&order);
+        scheduler
+            .submit(
+                "critical-task",
+                TaskPriority::Critical,
+                Box::new(move || {
+                    let o = Arc::clone(&o2);
+                    Box::pin(async move {
+                        o.lock().unwrap().push("critical");
+                        Ok(())
+                    })
+                }),
+            )
+            .expect("submit critical");
+
+        // Start the worker *after* both tasks are queued so ordering is
+        // deterministic.
+        let handle = scheduler.start();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let result = order.lock().unwrap().clone();
+        assert_eq!(result, vec!["critical", "low"]);
+
+        scheduler.shutdown(Duration::from_secs(1)).await;
+        handle.await.expect("worker exit");
+    }
+}
@@ -0,0 +1,317 @@
+//! Retries and the dead-letter list.
+//!
+//! A failed task goes back onto its lane after a linear backoff until
+//! [`SchedulerConfig::max_retries`](super::SchedulerConfig::max_retries) is
+//! used up, then moves to the dead-letter list, which is optionally
+//! persisted as JSON.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::Utc;
+
+use super::{
+    DeadLetter, QueuedTask, Scheduler, SchedulerInner, TaskFn, TaskId, TaskInfo, TaskStatus,
+};
+use crate::error::{KernelError, Result};
+
+/// A dead letter together with the work needed to requeue it.
+pub(super) struct DeadLetterEntry {
+    pub(super) record: DeadLetter,
+    /// `None` for entries reloaded from disk.
+    pub(super) work: Option<TaskFn>,
+}
+
+impl Scheduler {
+    /// Tasks that failed every attempt, oldest failure first.
+    pub fn dead_letters(&self) -> Vec<DeadLetter> {
+        let mut records: Vec<DeadLetter> = self
+            .inner
+            .dead_letters
+            .iter()
+            .map(|entry| entry.record.clone())
+            .collect();
+        records.sort_by_key(|r| r.failed_at);
+        records
+    }
+
+    /// Move a dead-lettered task back onto its queue with a fresh retry
+    /// budget.
+    ///
+    /// Fails with [`KernelError::TaskNotFound`] if the task is not in the
+    /// dead-letter list, and with [`KernelError::InvalidTaskState`] if it
+    /// was reloaded from disk and its work is no longer available.
+    pub fn requeue(&self, task_id: TaskId) -> Result<()> {
+        if self
+            .inner
+            .shutdown
+            .load(std::sync::atomic::Ordering::Acquire)
+        {
+            return Err(KernelError::SchedulerShutdown);
+        }
+        let (_, entry) = self
+            .inner
+            .dead_letters
+            .remove_if(&task_id, |_, entry| entry.work.is_some())
+            .ok_or_else(|| {
+                if self.inner.dead_letters.contains_key(&task_id) {
+                    KernelError::InvalidTaskState {
+                        task_id,
+                        reason: "task was reloaded from disk and its work is gone; \
+                                 submit it again"
+                            .into(),
+                    }
+                } else {
+                    KernelError::TaskNotFound { task_id }
+                }
+            })?;
+        self.inner.persist_dead_letters();
+
+        let DeadLetterEntry { record, work } = entry;
+        let Some(work) = work else {
+            return Err(KernelError::InvalidTaskState {
+                task_id,
+                reason: "task has no work to run".into(),
+            });
+        };
+        let task = record.task;
+        let mut info = TaskInfo {
+            status: TaskStatus::Pending,
+            started_at: None,
+            completed_at: None,
+            error: None,
+            attempts: 0,
+            ..task
+        };
+        info.created_at = Utc::now();
+        let (name, priority) = (info.name.clone(), info.priority);
+        self.inner.tasks.insert(task_id, info);
+        self.inner.publish_status(task_id, &name, TaskStatus::Pending);
+
+        tracing::info!(task_id = %task_id, task_name = %name, "dead-lettered task requeued");
+        self.inner.enqueue(QueuedTask {
+            id: task_id,
+            name,
+            priority,
+            work,
+        });
+        Ok(())
+    }
+}
+
+impl SchedulerInner {
+    /// Retry a failed task after its backoff, or dead-letter it once its
+    /// retries are used up.
+    pub(super) fn handle_failure(inner: &Arc<Self>, task: QueuedTask, err: String) {
+        let Some(mut entry) = inner.tasks.get_mut(&task.id) else {
+            return;
+        };
+        entry.error = Some(err.clone());
+
+        let attempts = entry.attempts;
+        if attempts <= inner.config.max_retries {
+            entry.status = TaskStatus::Pending;
+            drop(entry);
+            inner.publish_status(task.id, &task.name, TaskStatus::Pending);
+            let delay = inner.config.retry_backoff * attempts;
+            tracing::warn!(
+                task_id = %task.id,
+                error = %err,
+                attempt = attempts,
+                retry_in_ms = delay.as_millis() as u64,
+                "task failed, retrying"
+            );
+            let inner = Arc::clone(inner);
+            tokio::spawn(async move {
+                tokio::time::sleep(delay).await;
+                inner.enqueue(task);
+            });
+            return;
+        }
+
+        entry.status = TaskStatus::Failed;
+        entry.completed_at = Some(Utc::now());
+        let record = DeadLetter {
+            task: entry.clone(),
+            error: err.clone(),
+            failed_at: Utc::now(),
+        };
+        drop(entry);
+        inner.publish_status(task.id, &task.name, TaskStatus::Failed);
+        tracing::error!(
+            task_id = %task.id,
+            error = %err,
+            attempts,
+            "task failed, moved to dead-letter list"
+        );
+        inner.dead_letters.insert(
+            task.id,
+            DeadLetterEntry {
+                record,
+                work: Some(task.work),
+            },
+        );
+        inner.persist_dead_letters();
+    }
+
+    /// Write the dead-letter list to the configured file, if any.
+    ///
+    /// The write runs on the blocking pool when called from the runtime, so
+    /// a failing task never stalls a worker thread on disk I/O.
+    pub(super) fn persist_dead_letters(self: &Arc<Self>) {
+        if self.config.dead_letter_path.is_none() {
+            return;
+        }
+        let inner = Arc::clone(self);
+        let write = move || inner.write_dead_letters();
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn_blocking(write);
+            }
+            Err(_) => write(),
+        }
+    }
+
+    /// Snapshot the dead-letter list and write it out.  Blocking.
+    ///
+    /// Writes are serialized and each takes its snapshot under the lock, so
+    /// the last write to finish always holds the newest list.
+    fn write_dead_letters(&self) {
+        let Some(path) = &self.config.dead_letter_path else {
+            return;
+        };
+        let _guard = self
+            .dead_letter_write
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let mut records: Vec<DeadLetter> = self
+            .dead_letters
+            .iter()
+            .map(|entry| entry.record.clone())
+            .collect();
+        records.sort_by_key(|r| r.failed_at);
+        let result = serde_json::to_vec_pretty(&records)
+            .map_err(std::io::Error::other)
+            .and_then(|json| std::fs::write(path, json));
+        if let Err(e) = result {
+            tracing::warn!(path = %path.display(), error = %e, "failed to persist dead letters");
+        }
+    }
+}
+
+/// Read persisted dead letters, logging and skipping an unreadable file.
+pub(super) fn load_dead_letters(path: &Path) -> Vec<DeadLetter> {
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Vec::new(),
+        Err(e) => {
+            tracing::warn!(path = %path.display(), error = %e, "failed to read dead letters");
+            return Vec::new();
+        }
+    };
+    serde_json::from_slice(&data).unwrap_or_else(|e| {
+        tracing::warn!(path = %path.display(), error = %e, "failed to parse dead letters");
+        Vec::new()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::scheduler::{SchedulerConfig, TaskPriority};
+
+    #[tokio::test]
+    async fn exhausted_retries_dead_letter_and_requeue() {
+        let scheduler = Scheduler::with_config(SchedulerConfig {
+            max_retries: 2,
+            retry_backoff: Duration::from_millis(5),
+            dead_letter_path: None,
+        });
+        let handle = scheduler.start();
+
+        // Fails the first three calls, succeeds afterwards.
+        let calls = Arc::new(AtomicU32::new(0));
+        let c = Arc::clone(&calls);
+        let id = scheduler
+            .submit(
+                "flaky-task",
+                TaskPriority::Normal,
+                Box::new(move || {
+                    let n = c.fetch_add(1, Ordering::SeqCst) + 1;
+                    Box::pin(async move {
+                        if n <= 3 {
+                            Err(format!("attempt {n} failed"))
+                        } else {
+                            Ok(())
+                        }
+                    })
+                }),
+            )
+            .expect("submit");
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        let dead = scheduler.dead_letters();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].task.id, id);
+        assert_eq!(dead[0].task.attempts, 3);
+        assert_eq!(dead[0].error, "attempt 3 failed");
+        assert_eq!(scheduler.status(id).unwrap().status, TaskStatus::Failed);
+
+        scheduler.requeue(id).expect("requeue");
+        assert!(scheduler.dead_letters().is_empty());
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let info = scheduler.status(id).expect("task should exist");
+        assert_eq!(info.status, TaskStatus::Completed);
+        assert_eq!(info.attempts, 1);
+        assert!(matches!(
+            scheduler.requeue(id),
+            Err(KernelError::TaskNotFound { .. })
+        ));
+
+        scheduler.shutdown(Duration::from_secs(1)).await;
+        handle.await.expect("worker exit");
+    }
+
+    #[tokio::test]
+    async fn dead_letters_survive_restart() {
+        let path = std::env::temp_dir().join(format!("dead-letters-{}.json", Uuid::now_v7()));
+        let config = SchedulerConfig {
+            dead_letter_path: Some(path.clone()),
+            ..SchedulerConfig::default()
+        };
+
+        let scheduler = Scheduler::with_config(config.clone());
+        let handle = scheduler.start();
+        let id = scheduler
+            .submit(
+                "fail-task",
+                TaskPriority::Normal,
+                Box::new(|| Box::pin(async { Err("boom".to_string()) })),
+            )
+            .expect("submit");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        scheduler.shutdown(Duration::from_secs(1)).await;
+        handle.await.expect("worker exit");
+
+        let restarted = Scheduler::with_config(config);
+        let dead = restarted.dead_letters();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].task.id, id);
+        assert_eq!(dead[0].error, "boom");
+        // The closure is not persisted, so the task cannot be requeued.
+        assert!(matches!(
+            restarted.requeue(id),
+            Err(KernelError::InvalidTaskState { .. })
+        ));
+
+        let _ = std::fs::remove_file(path);
+    }
+}
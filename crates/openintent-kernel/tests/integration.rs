@@ -55,7 +55,7 @@ async fn scheduler_submit_multiple_tasks() {
         assert!(info.completed_at.is_some());
     }
 
-    scheduler.shutdown();
+    scheduler.shutdown(Duration::from_secs(1)).await;
     handle.await.unwrap();
 }
 
@@ -89,7 +89,7 @@ async fn scheduler_all_tasks_snapshot() {
     assert_eq!(info.name, "task-a");
     assert_eq!(info.priority, TaskPriority::High);
 
-    scheduler.shutdown();
+    scheduler.shutdown(Duration::from_secs(1)).await;
     handle.await.unwrap();
 }
 
@@ -112,7 +112,7 @@ async fn scheduler_failed_task_preserves_error() {
     assert_eq!(info.status, TaskStatus::Failed);
     assert_eq!(info.error.as_deref(), Some("something went wrong"));
 
-    scheduler.shutdown();
+    scheduler.shutdown(Duration::from_secs(1)).await;
     handle.await.unwrap();
 }
 
@@ -115,6 +115,7 @@ impl Adapter for PluginAdapter {
                 name: t.name.clone(),
                 description: t.description.clone(),
                 parameters: t.parameters_schema.clone(),
+                destructive: !t.capabilities.is_empty(),
             })
             .collect()
     }
@@ -187,6 +188,7 @@ mod tests {
                 name: "do_thing".to_owned(),
                 description: "Does a thing".to_owned(),
                 parameters_schema: serde_json::json!({"type": "object"}),
+                capabilities: Vec::new(),
             }],
         }
     }
@@ -342,11 +344,13 @@ mod tests {
                     name: "tool_a".to_owned(),
                     description: "First tool".to_owned(),
                     parameters_schema: serde_json::json!({"type": "string"}),
+                    capabilities: Vec::new(),
                 },
                 crate::plugin::PluginTool {
                     name: "tool_b".to_owned(),
                     description: "Second tool".to_owned(),
                     parameters_schema: serde_json::json!({"type": "number"}),
+                    capabilities: Vec::new(),
                 },
             ],
         };
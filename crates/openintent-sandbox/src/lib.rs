@@ -8,6 +8,8 @@
 //! - **[`error`]** -- [`SandboxError`] enumerates every failure mode.
 //! - **[`plugin`]** -- [`PluginInfo`], [`PluginTool`], and [`PluginRegistry`]
 //!   manage plugin metadata and lifecycle.
+//! - **[`manifest`]** -- [`PluginManifest`] persists the plugins installed
+//!   into a plugins directory.
 //! - **[`runtime`]** -- [`SandboxRuntime`] is the main entry point: load
 //!   `.wasm` bytes, invoke tools, enforce limits.
 //!
@@ -18,6 +20,7 @@ pub mod adapter;
 pub mod config;
 pub mod error;
 pub mod loader;
+pub mod manifest;
 pub mod plugin;
 pub mod runtime;
 
@@ -26,5 +29,6 @@ pub use adapter::PluginAdapter;
 pub use config::SandboxConfig;
 pub use error::{Result, SandboxError};
 pub use loader::PluginLoader;
-pub use plugin::{PluginInfo, PluginRegistry, PluginTool};
+pub use manifest::{InstalledPlugin, PluginManifest};
+pub use plugin::{Capability, PluginInfo, PluginRegistry, PluginTool};
 pub use runtime::SandboxRuntime;
@@ -0,0 +1,203 @@
+//! Installed plugin manifest.
+//!
+//! [`PluginManifest`] is the persistent record of the plugins installed into
+//! a plugins directory.  It lives next to the `.wasm` files as
+//! [`MANIFEST_FILE`] and stores each plugin's metadata, so installed plugins
+//! can be listed and inspected without compiling them.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::SandboxConfig;
+use crate::error::{Result, SandboxError};
+use crate::loader::PluginLoader;
+use crate::plugin::PluginInfo;
+
+/// File name of the manifest inside the plugins directory.
+pub const MANIFEST_FILE: &str = "plugins.json";
+
+/// A plugin recorded in the manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstalledPlugin {
+    /// Metadata captured when the plugin was installed.
+    pub info: PluginInfo,
+    /// File name of the `.wasm` module inside the plugins directory.
+    pub file: String,
+}
+
+impl InstalledPlugin {
+    /// The plugin ID (its registry name).
+    pub fn id(&self) -> &str {
+        &self.info.name
+    }
+}
+
+/// Persistent index of the plugins installed in a directory.
+pub struct PluginManifest {
+    /// Directory holding the `.wasm` files and the manifest.
+    plugins_dir: PathBuf,
+    /// Installed plugins, in installation order.
+    plugins: Vec<InstalledPlugin>,
+}
+
+impl PluginManifest {
+    /// Open the manifest in `plugins_dir`.
+    ///
+    /// A missing directory or manifest file yields an empty manifest.
+    pub fn open(plugins_dir: impl Into<PathBuf>) -> Result<Self> {
+        let plugins_dir = plugins_dir.into();
+        let path = plugins_dir.join(MANIFEST_FILE);
+        let plugins = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| SandboxError::Plugin {
+                reason: format!("invalid manifest {}: {e}", path.display()),
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            plugins_dir,
+            plugins,
+        })
+    }
+
+    /// Return the plugins directory.
+    pub fn plugins_dir(&self) -> &Path {
+        &self.plugins_dir
+    }
+
+    /// All installed plugins, in installation order.
+    pub fn list(&self) -> &[InstalledPlugin] {
+        &self.plugins
+    }
+
+    /// Look up an installed plugin by ID.
+    pub fn get(&self, id: &str) -> Option<&InstalledPlugin> {
+        self.plugins.iter().find(|p| p.id() == id)
+    }
+
+    /// Validate and install the `.wasm` module at `source`.
+    ///
+    /// The plugin ID is the file stem, matching the name [`PluginLoader`]
+    /// gives the module when it scans the plugins directory.  The module is
+    /// compiled and its metadata read before anything is copied, so a module
+    /// that fails to load leaves the directory untouched.
+    pub async fn install(
+        &mut self,
+        source: &Path,
+        config: SandboxConfig,
+    ) -> Result<&InstalledPlugin> {
+        let mut loader = PluginLoader::new(self.plugins_dir.clone(), config)?;
+        let info = loader.load_plugin(source).await?.plugin_info().clone();
+
+        let file = format!("{}.wasm", info.name);
+        let target = self.plugins_dir.join(&file);
+        if self.get(&info.name).is_some() || target.exists() {
+            return Err(SandboxError::Plugin {
+                reason: format!("plugin '{}' is already installed", info.name),
+            });
+        }
+
+        tokio::fs::create_dir_all(&self.plugins_dir).await?;
+        tokio::fs::copy(source, &target).await?;
+        self.plugins.push(InstalledPlugin { info, file });
+        if let Err(e) = self.save() {
+            self.plugins.pop();
+            let _ = std::fs::remove_file(&target);
+            return Err(e);
+        }
+
+        tracing::info!(plugin = %self.plugins[self.plugins.len() - 1].id(), "installed plugin");
+        Ok(&self.plugins[self.plugins.len() - 1])
+    }
+
+    /// Remove an installed plugin and delete its module file.
+    pub fn remove(&mut self, id: &str) -> Result<InstalledPlugin> {
+        let idx = self
+            .plugins
+            .iter()
+            .position(|p| p.id() == id)
+            .ok_or_else(|| SandboxError::Plugin {
+                reason: format!("plugin '{id}' is not installed"),
+            })?;
+        let removed = self.plugins.remove(idx);
+        self.save()?;
+
+        match std::fs::remove_file(self.plugins_dir.join(&removed.file)) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        tracing::info!(plugin = %id, "removed plugin");
+        Ok(removed)
+    }
+
+    /// Write the manifest atomically.
+    fn save(&self) -> Result<()> {
+        std::fs::create_dir_all(&self.plugins_dir)?;
+        let json = serde_json::to_vec_pretty(&self.plugins).map_err(|e| SandboxError::Plugin {
+            reason: format!("failed to serialize manifest: {e}"),
+        })?;
+        let path = self.plugins_dir.join(MANIFEST_FILE);
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minimal_wasm() -> Vec<u8> {
+        vec![0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00]
+    }
+
+    #[tokio::test]
+    async fn install_persists_and_remove_deletes() {
+        let src = tempfile::tempdir().expect("tempdir creation must succeed in tests");
+        let plugins = tempfile::tempdir().expect("tempdir creation must succeed in tests");
+        let wasm_path = src.path().join("hello.wasm");
+        std::fs::write(&wasm_path, minimal_wasm()).expect("write must succeed");
+
+        let mut manifest = PluginManifest::open(plugins.path()).expect("open must succeed");
+        let installed = manifest
+            .install(&wasm_path, SandboxConfig::default())
+            .await
+            .expect("install must succeed");
+        assert_eq!(installed.id(), "hello");
+        assert!(plugins.path().join("hello.wasm").exists());
+
+        let result = manifest.install(&wasm_path, SandboxConfig::default()).await;
+        assert!(result.is_err());
+
+        let mut reopened = PluginManifest::open(plugins.path()).expect("open must succeed");
+        assert_eq!(reopened.list().len(), 1);
+        reopened.remove("hello").expect("remove must succeed");
+        assert!(!plugins.path().join("hello.wasm").exists());
+        assert!(
+            PluginManifest::open(plugins.path())
+                .unwrap()
+                .list()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn install_rejects_invalid_module() {
+        let src = tempfile::tempdir().expect("tempdir creation must succeed in tests");
+        let plugins = src.path().join("plugins");
+        let bad = src.path().join("bad.wasm");
+        std::fs::write(&bad, b"not wasm").expect("write must succeed");
+
+        let mut manifest = PluginManifest::open(&plugins).expect("open must succeed");
+        assert!(
+            manifest
+                .install(&bad, SandboxConfig::default())
+                .await
+                .is_err()
+        );
+        assert!(!plugins.exists());
+    }
+}
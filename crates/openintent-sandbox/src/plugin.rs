@@ -22,6 +22,18 @@ pub struct PluginInfo {
     pub tools: Vec<PluginTool>,
 }
 
+impl PluginInfo {
+    /// Metadata for a module that does not describe itself.
+    pub(crate) fn placeholder(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            version: "0.0.0".to_owned(),
+            description: String::new(),
+            tools: Vec::new(),
+        }
+    }
+}
+
 /// A single tool exposed by a plugin.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PluginTool {
@@ -31,6 +43,31 @@ pub struct PluginTool {
     pub description: String,
     /// JSON Schema describing the expected input parameters.
     pub parameters_schema: serde_json::Value,
+    /// Host capabilities the tool needs beyond pure computation.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub capabilities: Vec<Capability>,
+}
+
+/// A host capability a plugin tool may require.
+///
+/// Each maps to a permission flag in [`SandboxConfig`](crate::SandboxConfig).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    /// Access to the host filesystem (`allow_fs`).
+    Filesystem,
+    /// Outbound network requests (`allow_network`).
+    Network,
+}
+
+impl Capability {
+    /// Stable lowercase name, as used in plugin metadata.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Filesystem => "filesystem",
+            Self::Network => "network",
+        }
+    }
 }
 
 /// A compiled Wasm module together with its metadata.
@@ -78,15 +115,18 @@ impl PluginRegistry {
 
         tracing::info!(plugin = name, "compiled wasm module");
 
-        // Build minimal metadata. A real implementation would call the
-        // module's `get_plugin_info` export to obtain richer metadata; for now
-        // we construct a placeholder that is sufficient for the registry API.
-        let info = PluginInfo {
-            name: name.to_owned(),
-            version: "0.0.0".to_owned(),
-            description: String::new(),
-            tools: Vec::new(),
-        };
+        // Metadata is not read here; see `SandboxRuntime::load_plugin`,
+        // which calls the module's `get_plugin_info` export.
+        self.insert(PluginInfo::placeholder(name), module)
+    }
+
+    /// Register an already compiled module under `info.name`.
+    pub(crate) fn insert(&mut self, info: PluginInfo, module: Module) -> Result<&PluginInfo> {
+        if self.plugins.iter().any(|p| p.info.name == info.name) {
+            return Err(SandboxError::Plugin {
+                reason: format!("plugin '{}' is already loaded", info.name),
+            });
+        }
 
         self.plugins.push(LoadedPlugin { info, module });
 
@@ -254,11 +294,13 @@ mod tests {
                 name: "do_thing".into(),
                 description: "Does a thing".into(),
                 parameters_schema: serde_json::json!({"type": "object"}),
+                capabilities: vec![Capability::Network],
             }],
         };
         assert_eq!(info.name, "test");
         assert_eq!(info.tools.len(), 1);
         assert_eq!(info.tools[0].name, "do_thing");
+        assert_eq!(info.tools[0].capabilities[0].as_str(), "network");
     }
 
     #[test]
@@ -4,12 +4,18 @@
 //! plugins.  It owns the wasmtime [`Engine`], the [`SandboxConfig`] resource
 //! limits, and the [`PluginRegistry`].
 
-use wasmtime::{AsContextMut, Engine, Instance, Linker, Store};
+use wasmtime::{AsContextMut, Engine, Instance, Linker, Module, Store};
 
 use crate::config::SandboxConfig;
 use crate::error::{Result, SandboxError};
 use crate::plugin::{PluginInfo, PluginRegistry};
 
+/// Optional guest export that reports plugin metadata.
+///
+/// Expected signature: `get_plugin_info() -> i32`.  The guest passes a JSON
+/// [`PluginInfo`] to `host_set_result` and returns `0`.
+const PLUGIN_INFO_EXPORT: &str = "get_plugin_info";
+
 /// Per-call state stored in the wasmtime [`Store`].
 ///
 /// This is the "host state" that wasmtime associates with every store
@@ -67,8 +73,76 @@ impl SandboxRuntime {
     }
 
     /// Load a Wasm plugin from raw bytes.
+    ///
+    /// If the module exports `get_plugin_info`, it is called once and the
+    /// metadata it reports is validated and kept; the plugin is registered
+    /// under `name` regardless of the name it declares.  Modules without the
+    /// export get placeholder metadata with no tools.
     pub fn load_plugin(&mut self, name: &str, wasm_bytes: &[u8]) -> Result<&PluginInfo> {
-        self.registry.load_plugin(name, wasm_bytes, &self.engine)
+        if self.registry.get_plugin(name).is_some() {
+            return Err(SandboxError::Plugin {
+                reason: format!("plugin '{name}' is already loaded"),
+            });
+        }
+
+        let module = Module::new(&self.engine, wasm_bytes)
+            .map_err(|e| SandboxError::Compilation(e.to_string()))?;
+        let info = match self.read_plugin_info(&module)? {
+            Some(info) => PluginInfo {
+                name: name.to_owned(),
+                ..info
+            },
+            None => PluginInfo::placeholder(name),
+        };
+
+        tracing::info!(
+            plugin = name,
+            tools = info.tools.len(),
+            "compiled wasm module"
+        );
+        self.registry.insert(info, module)
+    }
+
+    /// Call the module's `get_plugin_info` export, if it has one.
+    fn read_plugin_info(&self, module: &Module) -> Result<Option<PluginInfo>> {
+        if module.get_export(PLUGIN_INFO_EXPORT).is_none() {
+            return Ok(None);
+        }
+
+        let host_state = HostState {
+            input_json: Vec::new(),
+            output_json: Vec::new(),
+        };
+        let mut store = Store::new(&self.engine, host_state);
+        store
+            .set_fuel(self.config.max_fuel)
+            .map_err(|e| SandboxError::Execution(e.to_string()))?;
+
+        let mut linker: Linker<HostState> = Linker::new(&self.engine);
+        Self::define_host_functions(&mut linker)?;
+        let instance = linker
+            .instantiate(&mut store, module)
+            .map_err(|e| SandboxError::Instantiation(e.to_string()))?;
+
+        let info_fn = instance
+            .get_typed_func::<(), i32>(&mut store, PLUGIN_INFO_EXPORT)
+            .map_err(|e| SandboxError::Plugin {
+                reason: format!("invalid {PLUGIN_INFO_EXPORT} export: {e}"),
+            })?;
+        let code = info_fn
+            .call(&mut store, ())
+            .map_err(|e| SandboxError::Trap(e.to_string()))?;
+        if code != 0 {
+            return Err(SandboxError::Plugin {
+                reason: format!("{PLUGIN_INFO_EXPORT} returned non-zero code: {code}"),
+            });
+        }
+
+        serde_json::from_slice(&store.data().output_json)
+            .map(Some)
+            .map_err(|e| SandboxError::Plugin {
+                reason: format!("invalid plugin metadata: {e}"),
+            })
     }
 
     /// Execute a named tool from a loaded plugin.
@@ -356,6 +430,46 @@ mod tests {
         assert!(result.is_err());
     }
 
+    /// A module whose `get_plugin_info` reports `metadata`.
+    fn describing_wasm(metadata: &str) -> Vec<u8> {
+        format!(
+            r#"(module
+                (import "env" "host_set_result" (func $set (param i32 i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "{}")
+                (func (export "get_plugin_info") (result i32)
+                    (call $set (i32.const 0) (i32.const {}))
+                    (i32.const 0)))"#,
+            metadata.replace('"', "\\\""),
+            metadata.len()
+        )
+        .into_bytes()
+    }
+
+    #[test]
+    fn load_plugin_reads_declared_metadata() {
+        let mut rt = SandboxRuntime::with_defaults().unwrap();
+        let wasm = describing_wasm(
+            r#"{"name":"other","version":"1.2.0","description":"d","tools":[{"name":"fetch","description":"f","parameters_schema":{},"capabilities":["network"]}]}"#,
+        );
+        let info = rt.load_plugin("web", &wasm).unwrap();
+        assert_eq!(info.name, "web");
+        assert_eq!(info.version, "1.2.0");
+        assert_eq!(info.tools[0].name, "fetch");
+        assert_eq!(
+            info.tools[0].capabilities,
+            vec![crate::plugin::Capability::Network]
+        );
+    }
+
+    #[test]
+    fn load_plugin_rejects_malformed_metadata() {
+        let mut rt = SandboxRuntime::with_defaults().unwrap();
+        let result = rt.load_plugin("broken", &describing_wasm(r#"{"name":"broken"}"#));
+        assert!(matches!(result, Err(SandboxError::Plugin { .. })));
+        assert!(rt.list_plugins().is_empty());
+    }
+
     #[test]
     fn load_invalid_wasm_via_runtime() {
         let mut rt = SandboxRuntime::with_defaults().unwrap();
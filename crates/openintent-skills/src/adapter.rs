@@ -224,6 +224,7 @@ impl Adapter for SkillAdapter {
                         }
                     }
                 }),
+                destructive: true,
             })
             .collect()
     }
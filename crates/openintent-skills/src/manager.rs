@@ -11,6 +11,12 @@ use crate::parser::parse_skill_md;
 use crate::registry::RegistryClient;
 use crate::types::{ScriptInterpreter, SkillDefinition, SkillStatus};
 
+/// Marker file whose presence in a skill directory disables the skill.
+const DISABLED_MARKER: &str = ".disabled";
+
+/// Install-time source metadata, used by [`SkillManager::update`].
+const SOURCE_FILE: &str = ".source.json";
+
 /// Manages the local skill inventory.
 pub struct SkillManager {
     /// Base directory where skills are stored.
@@ -131,7 +137,7 @@ impl SkillManager {
             "installed_at": chrono::Utc::now().to_rfc3339(),
         });
         std::fs::write(
-            skill_dir.join(".source.json"),
+            skill_dir.join(SOURCE_FILE),
             serde_json::to_string_pretty(&source_meta)?,
         )?;
 
@@ -171,7 +177,7 @@ impl SkillManager {
             "installed_at": chrono::Utc::now().to_rfc3339(),
         });
         std::fs::write(
-            skill_dir.join(".source.json"),
+            skill_dir.join(SOURCE_FILE),
             serde_json::to_string_pretty(&source_meta)?,
         )?;
 
@@ -197,6 +203,104 @@ impl SkillManager {
         Ok(())
     }
 
+    /// Whether the skill called `name` is enabled.
+    ///
+    /// Skills are enabled unless disabled with [`set_enabled`](Self::set_enabled).
+    pub fn is_enabled(&self, name: &str) -> bool {
+        !self.skills_dir.join(name).join(DISABLED_MARKER).exists()
+    }
+
+    /// Enable or disable an installed skill.
+    ///
+    /// Disabled skills stay installed but are left out of the prompt
+    /// extension and of [`enabled_skills`](Self::enabled_skills).
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) -> Result<()> {
+        let skill_dir = self.skills_dir.join(name);
+        if !skill_dir.join("SKILL.md").exists() {
+            return Err(SkillError::NotFound(name.to_owned()));
+        }
+
+        let marker = skill_dir.join(DISABLED_MARKER);
+        if enabled {
+            match std::fs::remove_file(&marker) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        } else {
+            std::fs::write(&marker, b"")?;
+        }
+
+        tracing::info!(name = %name, enabled, "skill state changed");
+        Ok(())
+    }
+
+    /// Return the loaded skills that are enabled.
+    pub fn enabled_skills(&self) -> Vec<SkillDefinition> {
+        self.skills
+            .iter()
+            .filter(|s| self.is_enabled(&s.name))
+            .cloned()
+            .collect()
+    }
+
+    /// Re-install a skill from the registry slug or URL it was installed
+    /// from, keeping its enabled state.
+    ///
+    /// The previous version is restored if the download fails.
+    pub async fn update(&mut self, name: &str) -> Result<SkillDefinition> {
+        let skill_dir = self.skills_dir.join(name);
+        let source: serde_json::Value = match std::fs::read(skill_dir.join(SOURCE_FILE)) {
+            Ok(bytes) => serde_json::from_slice(&bytes)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(if skill_dir.join("SKILL.md").exists() {
+                    SkillError::Registry(format!("skill `{name}` has no recorded install source"))
+                } else {
+                    SkillError::NotFound(name.to_owned())
+                });
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let enabled = self.is_enabled(name);
+
+        // Move the current version aside so the install starts clean.
+        let backup = self.skills_dir.join(format!(".{name}.previous"));
+        if backup.exists() {
+            std::fs::remove_dir_all(&backup)?;
+        }
+        std::fs::rename(&skill_dir, &backup)?;
+        self.skills.retain(|s| s.name != name);
+
+        let installed = match (source["source"].as_str(), &source["slug"], &source["url"]) {
+            (Some("clawhub"), serde_json::Value::String(slug), _) => {
+                self.install_from_registry(slug).await
+            }
+            (Some("url"), _, serde_json::Value::String(url)) => self.install_from_url(url).await,
+            _ => Err(SkillError::Registry(format!(
+                "unrecognized install source for skill `{name}`"
+            ))),
+        };
+
+        match installed {
+            Ok(skill) => {
+                std::fs::remove_dir_all(&backup)?;
+                if !enabled {
+                    self.set_enabled(&skill.name, false)?;
+                }
+                tracing::info!(name = %skill.name, "skill updated");
+                Ok(skill)
+            }
+            Err(e) => {
+                if skill_dir.exists() {
+                    std::fs::remove_dir_all(&skill_dir)?;
+                }
+                std::fs::rename(&backup, &skill_dir)?;
+                self.skills.push(load_skill_from_dir(&skill_dir)?);
+                Err(e)
+            }
+        }
+    }
+
     /// Search the ClawHub registry.
     pub async fn search(
         &self,
@@ -227,6 +331,7 @@ impl SkillManager {
         let ready_skills: Vec<_> = self
             .skills
             .iter()
+            .filter(|s| self.is_enabled(&s.name))
             .filter(|s| check_requirements(s) != SkillStatus::Unavailable)
             .collect();
 
@@ -307,6 +412,57 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn disabled_skills_are_excluded() {
+        let tmp = tempfile::tempdir().unwrap();
+        for name in &["kept", "muted"] {
+            let dir = tmp.path().join(name);
+            std::fs::create_dir(&dir).unwrap();
+            std::fs::write(
+                dir.join("SKILL.md"),
+                format!("---\nname: {name}\ndescription: Skill {name}\n---\nDo {name} things."),
+            )
+            .unwrap();
+        }
+
+        let mut mgr = SkillManager::new(tmp.path().to_path_buf());
+        mgr.load_all().unwrap();
+        mgr.set_enabled("muted", false).unwrap();
+
+        assert!(!mgr.is_enabled("muted"));
+        let enabled: Vec<String> = mgr.enabled_skills().into_iter().map(|s| s.name).collect();
+        assert_eq!(enabled, vec!["kept"]);
+        assert!(!mgr.build_prompt_extension().contains("Do muted things."));
+
+        mgr.set_enabled("muted", true).unwrap();
+        assert!(mgr.is_enabled("muted"));
+        assert!(mgr.set_enabled("ghost", false).is_err());
+    }
+
+    #[tokio::test]
+    async fn update_without_source_keeps_skill() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("local");
+        std::fs::create_dir(&dir).unwrap();
+        std::fs::write(
+            dir.join("SKILL.md"),
+            "---\nname: local\ndescription: d\n---\nBody.",
+        )
+        .unwrap();
+
+        let mut mgr = SkillManager::new(tmp.path().to_path_buf());
+        mgr.load_all().unwrap();
+        assert!(matches!(
+            mgr.update("local").await,
+            Err(SkillError::Registry(_))
+        ));
+        assert!(matches!(
+            mgr.update("ghost").await,
+            Err(SkillError::NotFound(_))
+        ));
+        assert!(mgr.get("local").is_some());
+    }
+
     #[test]
     fn build_prompt_extension_empty() {
         let tmp = tempfile::tempdir().unwrap();
@@ -0,0 +1,449 @@
+//! Whole-store archives for backup and moving an install between machines.
+//!
+//! [`Database::export_archive`] writes users, sessions, session messages,
+//! semantic memories and workflows to one versioned JSON file, and
+//! [`Database::import_archive`] restores such a file into a migrated
+//! database in a single transaction.
+//!
+//! Rows are stored as column-name → value maps, so an archive taken on an
+//! older schema imports into a newer one (columns it lacks take their
+//! defaults).  Archives from a newer schema than the destination's are
+//! refused.  Blobs (memory embeddings) are stored as `{"base64": "..."}`.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chrono::Utc;
+use rusqlite::Connection;
+use rusqlite::types::{Value as SqliteValue, ValueRef};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tracing::{info, instrument, warn};
+
+use crate::db::Database;
+use crate::error::{StoreError, StoreResult};
+use crate::migration;
+
+/// Version of the archive file layout.
+pub const ARCHIVE_FORMAT_VERSION: u32 = 1;
+
+/// Archived tables, parents before children so foreign keys resolve on import.
+const ARCHIVE_TABLES: &[&str] = &[
+    "users",
+    "sessions",
+    "session_messages",
+    "memories",
+    "workflows",
+];
+
+/// Options for [`Database::export_archive`].
+#[derive(Debug, Clone)]
+pub struct ArchiveOptions {
+    /// Include user password hashes.  Without them, imported accounts
+    /// cannot log in until a password is set.
+    pub include_password_hashes: bool,
+}
+
+impl Default for ArchiveOptions {
+    fn default() -> Self {
+        Self {
+            include_password_hashes: true,
+        }
+    }
+}
+
+/// What an export or import covered.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ArchiveSummary {
+    /// Schema version the archive was taken at.
+    pub schema_version: u32,
+    /// Rows written or restored, per table.
+    pub rows: BTreeMap<String, usize>,
+}
+
+/// On-disk archive layout.
+#[derive(Serialize, Deserialize)]
+struct Archive {
+    format_version: u32,
+    schema_version: u32,
+    exported_at: i64,
+    tables: BTreeMap<String, Vec<Map<String, Value>>>,
+}
+
+impl Database {
+    /// Write every archived table to `path`.
+    #[instrument(skip(self, path), fields(path = %path.as_ref().display()))]
+    pub async fn export_archive(
+        &self,
+        path: impl AsRef<Path>,
+        options: &ArchiveOptions,
+    ) -> StoreResult<ArchiveSummary> {
+        let path = path.as_ref().to_path_buf();
+        let include_hashes = options.include_password_hashes;
+
+        self.execute_mut(move |conn| {
+            // One read transaction so the tables are mutually consistent.
+            let tx = conn.transaction()?;
+            let mut archive = Archive {
+                format_version: ARCHIVE_FORMAT_VERSION,
+                schema_version: migration::current_version(&tx)?,
+                exported_at: Utc::now().timestamp(),
+                tables: BTreeMap::new(),
+            };
+            for &table in ARCHIVE_TABLES {
+                let mut rows = dump_table(&tx, table)?;
+                if table == "users" && !include_hashes {
+                    for row in &mut rows {
+                        row.remove("password_hash");
+                    }
+                }
+                archive.tables.insert(table.to_string(), rows);
+            }
+            tx.commit()?;
+
+            std::fs::write(&path, serde_json::to_vec(&archive)?)?;
+            let summary = summarize(&archive);
+            info!(path = %path.display(), rows = ?summary.rows, "store archive exported");
+            Ok(summary)
+        })
+        .await
+    }
+
+    /// Restore an archive written by [`Database::export_archive`].
+    ///
+    /// The database must already be migrated.  All rows are inserted in one
+    /// transaction, so a conflict (for example an ID that already exists)
+    /// leaves the database untouched.
+    #[instrument(skip(self, path), fields(path = %path.as_ref().display()))]
+    pub async fn import_archive(&self, path: impl AsRef<Path>) -> StoreResult<ArchiveSummary> {
+        let path = path.as_ref().to_path_buf();
+
+        self.execute_mut(move |conn| {
+            let archive: Archive = serde_json::from_slice(&std::fs::read(&path)?)?;
+            if archive.format_version != ARCHIVE_FORMAT_VERSION {
+                return Err(StoreError::Archive(format!(
+                    "unsupported archive format v{} (expected v{ARCHIVE_FORMAT_VERSION})",
+                    archive.format_version
+                )));
+            }
+            let schema_version = migration::current_version(conn)?;
+            if archive.schema_version > schema_version {
+                return Err(StoreError::Archive(format!(
+                    "archive was taken at schema v{} but this database is at v{schema_version}; \
+                     upgrade before importing",
+                    archive.schema_version
+                )));
+            }
+            for table in archive.tables.keys() {
+                if !ARCHIVE_TABLES.contains(&table.as_str()) {
+                    warn!(table = %table, "skipping unknown table in archive");
+                }
+            }
+
+            let tx = conn.transaction()?;
+            for &table in ARCHIVE_TABLES {
+                let Some(rows) = archive.tables.get(table) else {
+                    continue;
+                };
+                let columns = table_columns(&tx, table)?;
+                for row in rows {
+                    restore_row(&tx, table, &columns, row)?;
+                }
+            }
+            tx.commit()?;
+
+            let summary = summarize(&archive);
+            info!(path = %path.display(), rows = ?summary.rows, "store archive imported");
+            Ok(summary)
+        })
+        .await
+    }
+}
+
+/// Read every row of `table` as a column-name → value map.
+fn dump_table(conn: &Connection, table: &str) -> StoreResult<Vec<Map<String, Value>>> {
+    let mut stmt = conn.prepare(&format!("SELECT * FROM \"{table}\" ORDER BY rowid"))?;
+    let names: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+    let rows = stmt
+        .query_map([], |row| {
+            names
+                .iter()
+                .enumerate()
+                .map(|(i, name)| Ok((name.clone(), to_json(row.get_ref(i)?))))
+                .collect::<rusqlite::Result<Map<_, _>>>()
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(rows)
+}
+
+/// Column names of `table` in the destination schema.
+fn table_columns(conn: &Connection, table: &str) -> StoreResult<HashSet<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info(\"{table}\")"))?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<rusqlite::Result<HashSet<_>>>()?;
+    Ok(columns)
+}
+
+/// Insert one archived row, checking its columns against the schema.
+fn restore_row(
+    conn: &Connection,
+    table: &str,
+    columns: &HashSet<String>,
+    row: &Map<String, Value>,
+) -> StoreResult<()> {
+    let mut names = Vec::with_capacity(row.len() + 1);
+    let mut values = Vec::with_capacity(row.len() + 1);
+    for (name, value) in row {
+        if !columns.contains(name) {
+            return Err(StoreError::Archive(format!(
+                "column {table}.{name} does not exist in this schema"
+            )));
+        }
+        names.push(format!("\"{name}\""));
+        values.push(from_json(value)?);
+    }
+    // Hashes may have been left out of the export; an empty hash never
+    // verifies, so the account stays locked until a password is set.
+    if table == "users" && !row.contains_key("password_hash") {
+        names.push("\"password_hash\"".into());
+        values.push(SqliteValue::Text(String::new()));
+    }
+
+    let placeholders = vec!["?"; names.len()].join(", ");
+    conn.execute(
+        &format!(
+            "INSERT INTO \"{table}\" ({}) VALUES ({placeholders})",
+            names.join(", ")
+        ),
+        rusqlite::params_from_iter(values),
+    )?;
+    Ok(())
+}
+
+fn summarize(archive: &Archive) -> ArchiveSummary {
+    ArchiveSummary {
+        schema_version: archive.schema_version,
+        rows: archive
+            .tables
+            .iter()
+            .map(|(table, rows)| (table.clone(), rows.len()))
+            .collect(),
+    }
+}
+
+fn to_json(value: ValueRef<'_>) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(i) => Value::from(i),
+        ValueRef::Real(f) => Value::from(f),
+        ValueRef::Text(t) => Value::String(String::from_utf8_lossy(t).into_owned()),
+        ValueRef::Blob(b) => serde_json::json!({ "base64": BASE64.encode(b) }),
+    }
+}
+
+fn from_json(value: &Value) -> StoreResult<SqliteValue> {
+    Ok(match value {
+        Value::Null => SqliteValue::Null,
+        Value::Bool(b) => SqliteValue::Integer(i64::from(*b)),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => SqliteValue::Integer(i),
+            None => SqliteValue::Real(n.as_f64().unwrap_or_default()),
+        },
+        Value::String(s) => SqliteValue::Text(s.clone()),
+        Value::Object(obj) => {
+            let encoded = obj.get("base64").and_then(Value::as_str).ok_or_else(|| {
+                StoreError::Archive("object values must be {\"base64\": ...} blobs".into())
+            })?;
+            let blob = BASE64
+                .decode(encoded)
+                .map_err(|e| StoreError::Archive(format!("invalid blob encoding: {e}")))?;
+            SqliteValue::Blob(blob)
+        }
+        Value::Array(_) => {
+            return Err(StoreError::Archive("array values are not supported".into()));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{MemoryCategory, NewMemory, SemanticMemory};
+    use crate::session::SessionStore;
+    use crate::user_store::{UserRole, UserStore};
+    use crate::workflow_store::WorkflowStore;
+
+    async fn setup() -> Database {
+        let db = Database::open_in_memory().unwrap();
+        db.run_migrations().await.unwrap();
+        db
+    }
+
+    async fn populate(db: &Database) {
+        let users = UserStore::new(db.clone());
+        users
+            .create("alice", Some("Alice"), "s3cret", UserRole::Admin)
+            .await
+            .unwrap();
+
+        let sessions = SessionStore::new(db.clone());
+        let session = sessions.create("chat", "model").await.unwrap();
+        for (role, content) in [("user", "hello"), ("assistant", "hi there")] {
+            sessions
+                .append_message(&session.id, role, content, None, None)
+                .await
+                .unwrap();
+        }
+
+        let memories = SemanticMemory::new(db.clone());
+        memories
+            .insert(NewMemory {
+                category: MemoryCategory::Preference,
+                content: "prefers dark mode".into(),
+                embedding: Some(vec![0.25, -1.5]),
+                importance: 0.7,
+                ttl: None,
+            })
+            .await
+            .unwrap();
+
+        WorkflowStore::new(db.clone())
+            .create("daily", None, "summarize mail", serde_json::json!([]), None)
+            .await
+            .unwrap();
+    }
+
+    async fn count(db: &Database, table: &'static str) -> i64 {
+        db.execute(move |conn| {
+            Ok(
+                conn.query_row(&format!("SELECT count(*) FROM {table}"), [], |row| {
+                    row.get(0)
+                })?,
+            )
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn export_then_import_restores_all_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("store.json");
+
+        let source = setup().await;
+        populate(&source).await;
+        let exported = source
+            .export_archive(&path, &ArchiveOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(exported.rows["session_messages"], 2);
+
+        let target = setup().await;
+        let imported = target.import_archive(&path).await.unwrap();
+        assert_eq!(imported.rows, exported.rows);
+        for &table in ARCHIVE_TABLES {
+            assert_eq!(
+                count(&source, table).await,
+                count(&target, table).await,
+                "{table}"
+            );
+        }
+
+        let users = UserStore::new(target.clone());
+        assert!(
+            users
+                .authenticate("alice", "s3cret")
+                .await
+                .unwrap()
+                .is_some()
+        );
+        let memory = SemanticMemory::new(target)
+            .list_all(None, 10)
+            .await
+            .unwrap()
+            .remove(0);
+        assert_eq!(memory.embedding, Some(vec![0.25, -1.5]));
+    }
+
+    #[tokio::test]
+    async fn export_can_omit_password_hashes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("store.json");
+
+        let source = setup().await;
+        populate(&source).await;
+        let options = ArchiveOptions {
+            include_password_hashes: false,
+        };
+        source.export_archive(&path, &options).await.unwrap();
+        assert!(
+            !std::fs::read_to_string(&path)
+                .unwrap()
+                .contains("password_hash")
+        );
+
+        let target = setup().await;
+        target.import_archive(&path).await.unwrap();
+        let users = UserStore::new(target);
+        assert_eq!(users.count().await.unwrap(), 1);
+        assert!(
+            users
+                .authenticate("alice", "s3cret")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn import_is_all_or_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("store.json");
+
+        let source = setup().await;
+        populate(&source).await;
+        source
+            .export_archive(&path, &ArchiveOptions::default())
+            .await
+            .unwrap();
+
+        // Only the workflow is already present, so the import fails on the
+        // last table after every other table has been inserted.
+        let target = setup().await;
+        target.import_archive(&path).await.unwrap();
+        target
+            .execute(|conn| {
+                conn.execute_batch(
+                    "DELETE FROM session_messages; DELETE FROM sessions; \
+                     DELETE FROM memories; DELETE FROM users;",
+                )?;
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        assert!(target.import_archive(&path).await.is_err());
+        for table in ["users", "sessions", "session_messages", "memories"] {
+            assert_eq!(count(&target, table).await, 0, "{table}");
+        }
+    }
+
+    #[tokio::test]
+    async fn import_rejects_newer_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("store.json");
+        let archive = Archive {
+            format_version: ARCHIVE_FORMAT_VERSION,
+            schema_version: u32::MAX,
+            exported_at: 0,
+            tables: BTreeMap::new(),
+        };
+        std::fs::write(&path, serde_json::to_vec(&archive).unwrap()).unwrap();
+
+        let err = setup().await.import_archive(&path).await.unwrap_err();
+        assert!(matches!(err, StoreError::Archive(_)), "{err}");
+    }
+}
@@ -0,0 +1,467 @@
+//! Backend-neutral query layer.
+//!
+//! The stores in this crate ([`SessionStore`](crate::SessionStore),
+//! [`UserStore`](crate::UserStore), ...) are written against
+//! [`StorageBackend`] instead of SQLite.  A backend runs closures against a
+//! [`SqlConnection`], on which stores execute parameterised SQL and read
+//! rows back as [`SqlValue`]s.  [`Database`](crate::Database) is the SQLite
+//! implementation.
+//!
+//! Statements are written in SQLite's dialect with numbered `?N`
+//! placeholders.  A backend for another engine (Postgres, Turso, ...) is
+//! responsible for translating them and for shipping its own schema
+//! migrations.
+//!
+//! ```ignore
+//! use openintent_store::{StorageBackend, sql_params};
+//!
+//! async fn count_sessions(db: &impl StorageBackend, model: String) -> StoreResult<i64> {
+//!     db.with_conn(move |conn| {
+//!         conn.query_row(
+//!             "SELECT COUNT(*) FROM sessions WHERE model = ?1",
+//!             sql_params![model],
+//!             |row| row.get(0),
+//!         )
+//!     })
+//!     .await
+//! }
+//! ```
+
+use std::future::Future;
+
+use crate::error::{StoreError, StoreResult};
+
+// ═══════════════════════════════════════════════════════════════════════
+//  Values
+// ═══════════════════════════════════════════════════════════════════════
+
+/// A single SQL parameter or column value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlValue {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+impl SqlValue {
+    /// The SQL type name, for error messages.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Null => "NULL",
+            Self::Integer(_) => "INTEGER",
+            Self::Real(_) => "REAL",
+            Self::Text(_) => "TEXT",
+            Self::Blob(_) => "BLOB",
+        }
+    }
+}
+
+/// Conversion of a Rust value into a statement parameter.
+pub trait ToSqlValue {
+    fn to_sql_value(&self) -> SqlValue;
+}
+
+/// Conversion of a column value into a Rust value.
+pub trait FromSqlValue: Sized {
+    fn from_sql_value(value: &SqlValue) -> StoreResult<Self>;
+}
+
+impl<T: ToSqlValue + ?Sized> ToSqlValue for &T {
+    fn to_sql_value(&self) -> SqlValue {
+        (**self).to_sql_value()
+    }
+}
+
+impl<T: ToSqlValue> ToSqlValue for Option<T> {
+    fn to_sql_value(&self) -> SqlValue {
+        self.as_ref()
+            .map_or(SqlValue::Null, ToSqlValue::to_sql_value)
+    }
+}
+
+impl ToSqlValue for SqlValue {
+    fn to_sql_value(&self) -> SqlValue {
+        self.clone()
+    }
+}
+
+impl ToSqlValue for str {
+    fn to_sql_value(&self) -> SqlValue {
+        SqlValue::Text(self.to_string())
+    }
+}
+
+impl ToSqlValue for String {
+    fn to_sql_value(&self) -> SqlValue {
+        SqlValue::Text(self.clone())
+    }
+}
+
+impl ToSqlValue for [u8] {
+    fn to_sql_value(&self) -> SqlValue {
+        SqlValue::Blob(self.to_vec())
+    }
+}
+
+impl ToSqlValue for Vec<u8> {
+    fn to_sql_value(&self) -> SqlValue {
+        SqlValue::Blob(self.clone())
+    }
+}
+
+impl ToSqlValue for bool {
+    fn to_sql_value(&self) -> SqlValue {
+        SqlValue::Integer(i64::from(*self))
+    }
+}
+
+impl ToSqlValue for f64 {
+    fn to_sql_value(&self) -> SqlValue {
+        SqlValue::Real(*self)
+    }
+}
+
+impl ToSqlValue for f32 {
+    fn to_sql_value(&self) -> SqlValue {
+        SqlValue::Real(f64::from(*self))
+    }
+}
+
+macro_rules! integer_to_sql {
+    ($($ty:ty),*) => {$(
+        impl ToSqlValue for $ty {
+            fn to_sql_value(&self) -> SqlValue {
+                SqlValue::Integer(*self as i64)
+            }
+        }
+    )*};
+}
+
+integer_to_sql!(i8, i16, i32, i64, u8, u16, u32, u64, usize);
+
+impl<T: FromSqlValue> FromSqlValue for Option<T> {
+    fn from_sql_value(value: &SqlValue) -> StoreResult<Self> {
+        match value {
+            SqlValue::Null => Ok(None),
+            other => T::from_sql_value(other).map(Some),
+        }
+    }
+}
+
+impl FromSqlValue for SqlValue {
+    fn from_sql_value(value: &SqlValue) -> StoreResult<Self> {
+        Ok(value.clone())
+    }
+}
+
+impl FromSqlValue for String {
+    fn from_sql_value(value: &SqlValue) -> StoreResult<Self> {
+        match value {
+            SqlValue::Text(s) => Ok(s.clone()),
+            other => Err(type_mismatch("TEXT", other)),
+        }
+    }
+}
+
+impl FromSqlValue for Vec<u8> {
+    fn from_sql_value(value: &SqlValue) -> StoreResult<Self> {
+        match value {
+            SqlValue::Blob(b) => Ok(b.clone()),
+            other => Err(type_mismatch("BLOB", other)),
+        }
+    }
+}
+
+impl FromSqlValue for f64 {
+    fn from_sql_value(value: &SqlValue) -> StoreResult<Self> {
+        match value {
+            SqlValue::Real(f) => Ok(*f),
+            SqlValue::Integer(i) => Ok(*i as f64),
+            other => Err(type_mismatch("REAL", other)),
+        }
+    }
+}
+
+impl FromSqlValue for bool {
+    fn from_sql_value(value: &SqlValue) -> StoreResult<Self> {
+        i64::from_sql_value(value).map(|i| i != 0)
+    }
+}
+
+impl FromSqlValue for i64 {
+    fn from_sql_value(value: &SqlValue) -> StoreResult<Self> {
+        match value {
+            SqlValue::Integer(i) => Ok(*i),
+            other => Err(type_mismatch("INTEGER", other)),
+        }
+    }
+}
+
+macro_rules! integer_from_sql {
+    ($($ty:ty),*) => {$(
+        impl FromSqlValue for $ty {
+            fn from_sql_value(value: &SqlValue) -> StoreResult<Self> {
+                let i = i64::from_sql_value(value)?;
+                <$ty>::try_from(i).map_err(|_| {
+                    StoreError::ColumnType(format!(
+                        "{i} out of range for {}",
+                        stringify!($ty)
+                    ))
+                })
+            }
+        }
+    )*};
+}
+
+integer_from_sql!(i32, u32, u64, usize);
+
+fn type_mismatch(expected: &str, found: &SqlValue) -> StoreError {
+    StoreError::ColumnType(format!("expected {expected}, found {}", found.type_name()))
+}
+
+/// Build a `&[SqlValue]` parameter list, like `rusqlite::params!`.
+///
+/// Each argument is borrowed and converted with [`ToSqlValue`].
+#[macro_export]
+macro_rules! sql_params {
+    () => {
+        &[] as &[$crate::backend::SqlValue]
+    };
+    ($($param:expr),+ $(,)?) => {
+        &[$($crate::backend::ToSqlValue::to_sql_value(&$param)),+] as &[$crate::backend::SqlValue]
+    };
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  Rows and connections
+// ═══════════════════════════════════════════════════════════════════════
+
+/// One result row.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SqlRow {
+    values: Vec<SqlValue>,
+}
+
+impl SqlRow {
+    /// Create a row from its column values, in select order.
+    pub fn new(values: Vec<SqlValue>) -> Self {
+        Self { values }
+    }
+
+    /// Read column `index` (0-based) as `T`.
+    pub fn get<T: FromSqlValue>(&self, index: usize) -> StoreResult<T> {
+        let value = self.values.get(index).ok_or_else(|| {
+            StoreError::ColumnType(format!(
+                "column {index} out of range ({} columns)",
+                self.values.len()
+            ))
+        })?;
+        T::from_sql_value(value)
+    }
+
+    /// Number of columns.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether the row has no columns.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// A connection to a backend, as seen by store code.
+///
+/// Implementations map engine errors onto [`StoreError`]; in particular
+/// unique and foreign-key violations become [`StoreError::Constraint`].
+pub trait SqlConnection {
+    /// Run one statement and return the number of rows it changed.
+    fn execute(&self, sql: &str, params: &[SqlValue]) -> StoreResult<usize>;
+
+    /// Run a query and collect every row it returns.
+    fn query(&self, sql: &str, params: &[SqlValue]) -> StoreResult<Vec<SqlRow>>;
+
+    /// Run several `;`-separated statements that take no parameters.
+    fn execute_batch(&self, sql: &str) -> StoreResult<()>;
+
+    /// Row ID of the most recent successful `INSERT` on this connection.
+    fn last_insert_id(&self) -> i64;
+}
+
+impl dyn SqlConnection + '_ {
+    /// Run a query and map every row with `f`.
+    pub fn query_map<T>(
+        &self,
+        sql: &str,
+        params: &[SqlValue],
+        f: impl FnMut(&SqlRow) -> StoreResult<T>,
+    ) -> StoreResult<Vec<T>> {
+        self.query(sql, params)?.iter().map(f).collect()
+    }
+
+    /// Run a query and map its first row, if any.
+    pub fn query_opt<T>(
+        &self,
+        sql: &str,
+        params: &[SqlValue],
+        f: impl FnOnce(&SqlRow) -> StoreResult<T>,
+    ) -> StoreResult<Option<T>> {
+        self.query(sql, params)?.first().map(f).transpose()
+    }
+
+    /// Run a query that must return a row (aggregates, `RETURNING`, ...).
+    pub fn query_row<T>(
+        &self,
+        sql: &str,
+        params: &[SqlValue],
+        f: impl FnOnce(&SqlRow) -> StoreResult<T>,
+    ) -> StoreResult<T> {
+        self.query_opt(sql, params, f)?.ok_or(StoreError::NoRows)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+//  StorageBackend
+// ═══════════════════════════════════════════════════════════════════════
+
+/// A persistence backend the stores can run on.
+///
+/// Closures run off the async runtime (SQLite uses the blocking pool), so
+/// they may block on I/O.
+pub trait StorageBackend: Clone + Send + Sync + 'static {
+    /// Short backend name for logs, e.g. `"sqlite"`.
+    fn name(&self) -> &'static str;
+
+    /// Bring the schema up to date.
+    fn run_migrations(&self) -> impl Future<Output = StoreResult<()>> + Send;
+
+    /// Run `f` against a connection.
+    fn with_conn<F, T>(&self, f: F) -> impl Future<Output = StoreResult<T>> + Send
+    where
+        F: FnOnce(&dyn SqlConnection) -> StoreResult<T> + Send + 'static,
+        T: Send + 'static;
+
+    /// Run `f` inside a transaction that commits if `f` returns `Ok` and
+    /// rolls back otherwise.
+    fn transaction<F, T>(&self, f: F) -> impl Future<Output = StoreResult<T>> + Send
+    where
+        F: FnOnce(&dyn SqlConnection) -> StoreResult<T> + Send + 'static,
+        T: Send + 'static;
+}
+
+// ── tests ────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+
+    async fn backend<B: StorageBackend>(db: B) -> B {
+        db.run_migrations().await.unwrap();
+        db
+    }
+
+    #[tokio::test]
+    async fn sqlite_implements_storage_backend() {
+        let db = backend(Database::open_in_memory().unwrap()).await;
+        assert_eq!(db.name(), "sqlite");
+
+        let id = db
+            .with_conn(|conn| {
+                conn.execute(
+                    "INSERT INTO bot_state (key, value) VALUES (?1, ?2)",
+                    sql_params!["offset", Some("42")],
+                )?;
+                Ok(conn.last_insert_id())
+            })
+            .await
+            .unwrap();
+        assert!(id > 0);
+
+        let (value, missing): (Option<String>, Option<String>) = db
+            .with_conn(|conn| {
+                let value = conn.query_row(
+                    "SELECT value FROM bot_state WHERE key = ?1",
+                    sql_params!["offset"],
+                    |row| row.get(0),
+                )?;
+                let missing = conn.query_opt(
+                    "SELECT value FROM bot_state WHERE key = ?1",
+                    sql_params!["nope"],
+                    |row| row.get(0),
+                )?;
+                Ok((value, missing))
+            })
+            .await
+            .unwrap();
+        assert_eq!(value.as_deref(), Some("42"));
+        assert_eq!(missing, None);
+    }
+
+    #[tokio::test]
+    async fn transaction_rolls_back_on_error() {
+        let db = backend(Database::open_in_memory().unwrap()).await;
+
+        let result: StoreResult<()> = db
+            .transaction(|conn| {
+                conn.execute(
+                    "INSERT INTO bot_state (key, value) VALUES ('a', 'b')",
+                    sql_params![],
+                )?;
+                Err(StoreError::InvalidArgument("abort".into()))
+            })
+            .await;
+        assert!(result.is_err());
+
+        let count: i64 = db
+            .with_conn(|conn| {
+                conn.query_row("SELECT COUNT(*) FROM bot_state", sql_params![], |row| {
+                    row.get(0)
+                })
+            })
+            .await
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[tokio::test]
+    async fn constraint_violations_are_neutral() {
+        let db = backend(Database::open_in_memory().unwrap()).await;
+        let mut results = Vec::new();
+        for _ in 0..2 {
+            let result = db
+                .with_conn(|conn| {
+                    conn.execute(
+                        "INSERT INTO bot_state (key, value) VALUES ('k', 'v')",
+                        sql_params![],
+                    )
+                })
+                .await;
+            results.push(result);
+        }
+        assert_eq!(*results[0].as_ref().unwrap(), 1);
+        let err = results.pop().unwrap().unwrap_err();
+        assert!(matches!(err, StoreError::Constraint(_)), "{err:?}");
+    }
+
+    #[test]
+    fn values_round_trip() {
+        let row = SqlRow::new(vec![
+            7_u32.to_sql_value(),
+            true.to_sql_value(),
+            None::<String>.to_sql_value(),
+            vec![1_u8, 2].to_sql_value(),
+        ]);
+        assert_eq!(row.get::<u32>(0).unwrap(), 7);
+        assert!(row.get::<bool>(1).unwrap());
+        assert_eq!(row.get::<Option<String>>(2).unwrap(), None);
+        assert_eq!(row.get::<Vec<u8>>(3).unwrap(), vec![1, 2]);
+        assert!(matches!(
+            row.get::<String>(0),
+            Err(StoreError::ColumnType(_))
+        ));
+        assert!(row.get::<i64>(9).is_err());
+    }
+}
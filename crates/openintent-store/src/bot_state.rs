@@ -6,18 +6,20 @@
 
 use tracing::{debug, instrument};
 
+use crate::backend::StorageBackend;
 use crate::db::Database;
 use crate::error::StoreResult;
+use crate::sql_params;
 
 /// Persistent key-value store for bot state.
 #[derive(Clone)]
-pub struct BotStateStore {
-    db: Database,
+pub struct BotStateStore<B = Database> {
+    db: B,
 }
 
-impl BotStateStore {
+impl<B: StorageBackend> BotStateStore<B> {
     /// Create a new bot state store backed by `db`.
-    pub fn new(db: Database) -> Self {
+    pub fn new(db: B) -> Self {
         Self { db }
     }
 
@@ -26,17 +28,12 @@ impl BotStateStore {
     pub async fn get(&self, key: &str) -> StoreResult<Option<String>> {
         let key = key.to_string();
         self.db
-            .execute(move |conn| {
-                let result = conn.query_row(
+            .with_conn(move |conn| {
+                conn.query_opt(
                     "SELECT value FROM bot_state WHERE key = ?1",
-                    rusqlite::params![key],
+                    sql_params![key],
                     |row| row.get(0),
-                );
-                match result {
-                    Ok(value) => Ok(Some(value)),
-                    Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-                    Err(e) => Err(e.into()),
-                }
+                )
             })
             .await
     }
@@ -47,11 +44,11 @@ impl BotStateStore {
         let key = key.to_string();
         let value = value.to_string();
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 conn.execute(
                     "INSERT INTO bot_state (key, value) VALUES (?1, ?2) \
                      ON CONFLICT(key) DO UPDATE SET value = excluded.value",
-                    rusqlite::params![key, value],
+                    sql_params![key, value],
                 )?;
                 debug!(key = %key, "bot state updated");
                 Ok(())
@@ -64,16 +61,31 @@ impl BotStateStore {
     pub async fn delete(&self, key: &str) -> StoreResult<bool> {
         let key = key.to_string();
         self.db
-            .execute(move |conn| {
-                let deleted = conn.execute(
-                    "DELETE FROM bot_state WHERE key = ?1",
-                    rusqlite::params![key],
-                )?;
+            .with_conn(move |conn| {
+                let deleted =
+                    conn.execute("DELETE FROM bot_state WHERE key = ?1", sql_params![key])?;
                 Ok(deleted > 0)
             })
             .await
     }
 
+    /// List every key starting with `prefix` together with its value,
+    /// ordered by key.
+    #[instrument(skip(self))]
+    pub async fn list_prefix(&self, prefix: &str) -> StoreResult<Vec<(String, String)>> {
+        let prefix = prefix.to_string();
+        self.db
+            .with_conn(move |conn| {
+                conn.query_map(
+                    "SELECT key, value FROM bot_state \
+                     WHERE substr(key, 1, length(?1)) = ?1 ORDER BY key",
+                    sql_params![prefix],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+            })
+            .await
+    }
+
     /// Get a value parsed as i64, returning `None` if not found or unparseable.
     pub async fn get_i64(&self, key: &str) -> StoreResult<Option<i64>> {
         let val = self.get(key).await?;
@@ -143,6 +155,28 @@ mod tests {
         assert!(!store.delete("missing").await.unwrap());
     }
 
+    #[tokio::test]
+    async fn list_prefix_matches_literally() {
+        let db = setup_db().await;
+        let store = BotStateStore::new(db);
+
+        store.set("a/2", "two").await.unwrap();
+        store.set("a/1", "one").await.unwrap();
+        store.set("a_x", "other").await.unwrap();
+        store.set("b/1", "b").await.unwrap();
+
+        assert_eq!(
+            store.list_prefix("a/").await.unwrap(),
+            vec![
+                ("a/1".to_string(), "one".to_string()),
+                ("a/2".to_string(), "two".to_string()),
+            ]
+        );
+        // `_` and `%` are not wildcards.
+        assert!(store.list_prefix("a%").await.unwrap().is_empty());
+        assert_eq!(store.list_prefix("").await.unwrap().len(), 4);
+    }
+
     #[tokio::test]
     async fn get_set_i64() {
         let db = setup_db().await;
@@ -0,0 +1,246 @@
+//! Persistence for recurring cron jobs.
+//!
+//! Stores the job registry of the cron adapter so schedules survive a
+//! restart.  Run bookkeeping (`last_run` / `next_run`) is updated in place
+//! as jobs fire.
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument};
+
+use crate::backend::{SqlRow, StorageBackend};
+use crate::db::Database;
+use crate::error::StoreResult;
+use crate::sql_params;
+
+/// A persisted cron job.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredCronJob {
+    /// Unique identifier (UUID v7).
+    pub id: String,
+    /// Human-readable job name.
+    pub name: String,
+    /// Cron expression as entered by the user.
+    pub schedule: String,
+    /// IANA timezone the schedule is evaluated in (e.g. `"Europe/Berlin"`).
+    pub timezone: String,
+    /// The command or intent to execute when the job fires.
+    pub command: String,
+    /// Whether the job is active.
+    pub enabled: bool,
+    /// Unix timestamp when the job was created.
+    pub created_at: i64,
+    /// Unix timestamp of the most recent execution, if any.
+    pub last_run: Option<i64>,
+    /// Unix timestamp of the next planned execution, if known.
+    pub next_run: Option<i64>,
+}
+
+/// CRUD operations on persisted cron jobs.
+#[derive(Clone)]
+pub struct CronJobStore<B = Database> {
+    db: B,
+}
+
+impl<B: StorageBackend> CronJobStore<B> {
+    /// Create a new cron job store backed by `db`.
+    pub fn new(db: B) -> Self {
+        Self { db }
+    }
+
+    /// Insert a job, or replace the stored copy if the ID already exists.
+    #[instrument(skip(self, job), fields(job_id = %job.id))]
+    pub async fn save(&self, job: &StoredCronJob) -> StoreResult<()> {
+        let job = job.clone();
+        self.db
+            .with_conn(move |conn| {
+                conn.execute(
+                    "INSERT INTO cron_jobs (id, name, schedule, timezone, command, enabled, created_at, last_run, next_run) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9) \
+                     ON CONFLICT(id) DO UPDATE SET name = excluded.name, schedule = excluded.schedule, \
+                     timezone = excluded.timezone, command = excluded.command, enabled = excluded.enabled, \
+                     last_run = excluded.last_run, next_run = excluded.next_run",
+                    sql_params![
+                        job.id,
+                        job.name,
+                        job.schedule,
+                        job.timezone,
+                        job.command,
+                        job.enabled,
+                        job.created_at,
+                        job.last_run,
+                        job.next_run
+                    ],
+                )?;
+                debug!(job_id = %job.id, "cron job saved");
+                Ok(())
+            })
+            .await
+    }
+
+    /// Fetch a single job by ID, returning `None` if not found.
+    #[instrument(skip(self))]
+    pub async fn get(&self, id: &str) -> StoreResult<Option<StoredCronJob>> {
+        let id = id.to_string();
+        self.db
+            .with_conn(move |conn| {
+                conn.query_opt(
+                    "SELECT id, name, schedule, timezone, command, enabled, created_at, last_run, next_run \
+                     FROM cron_jobs WHERE id = ?1",
+                    sql_params![id],
+                    row_to_job,
+                )
+            })
+            .await
+    }
+
+    /// List all jobs in creation order.
+    #[instrument(skip(self))]
+    pub async fn list(&self) -> StoreResult<Vec<StoredCronJob>> {
+        self.db
+            .with_conn(|conn| {
+                conn.query_map(
+                    "SELECT id, name, schedule, timezone, command, enabled, created_at, last_run, next_run \
+                     FROM cron_jobs ORDER BY created_at ASC, id ASC",
+                    sql_params![],
+                    row_to_job,
+                )
+            })
+            .await
+    }
+
+    /// Enable or disable a job.  Returns `true` if the job existed.
+    #[instrument(skip(self))]
+    pub async fn set_enabled(&self, id: &str, enabled: bool) -> StoreResult<bool> {
+        let id = id.to_string();
+        self.db
+            .with_conn(move |conn| {
+                let updated = conn.execute(
+                    "UPDATE cron_jobs SET enabled = ?2 WHERE id = ?1",
+                    sql_params![id, enabled],
+                )?;
+                Ok(updated > 0)
+            })
+            .await
+    }
+
+    /// Record run bookkeeping for a job.  Returns `true` if the job existed.
+    #[instrument(skip(self))]
+    pub async fn update_runs(
+        &self,
+        id: &str,
+        last_run: Option<i64>,
+        next_run: Option<i64>,
+    ) -> StoreResult<bool> {
+        let id = id.to_string();
+        self.db
+            .with_conn(move |conn| {
+                let updated = conn.execute(
+                    "UPDATE cron_jobs SET last_run = ?2, next_run = ?3 WHERE id = ?1",
+                    sql_params![id, last_run, next_run],
+                )?;
+                Ok(updated > 0)
+            })
+            .await
+    }
+
+    /// Delete a job.  Returns `true` if the job existed.
+    #[instrument(skip(self))]
+    pub async fn delete(&self, id: &str) -> StoreResult<bool> {
+        let id = id.to_string();
+        self.db
+            .with_conn(move |conn| {
+                let deleted =
+                    conn.execute("DELETE FROM cron_jobs WHERE id = ?1", sql_params![id])?;
+                Ok(deleted > 0)
+            })
+            .await
+    }
+}
+
+/// Map a `cron_jobs` row (in the canonical column order) to a job.
+fn row_to_job(row: &SqlRow) -> StoreResult<StoredCronJob> {
+    Ok(StoredCronJob {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        schedule: row.get(2)?,
+        timezone: row.get(3)?,
+        command: row.get(4)?,
+        enabled: row.get(5)?,
+        created_at: row.get(6)?,
+        last_run: row.get(7)?,
+        next_run: row.get(8)?,
+    })
+}
+
+// ── tests ────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_store() -> CronJobStore {
+        let db = Database::open_in_memory().unwrap();
+        db.run_migrations().await.unwrap();
+        CronJobStore::new(db)
+    }
+
+    fn sample_job(id: &str) -> StoredCronJob {
+        StoredCronJob {
+            id: id.to_string(),
+            name: "nightly report".to_string(),
+            schedule: "0 2 * * *".to_string(),
+            timezone: "Europe/Berlin".to_string(),
+            command: "send_report".to_string(),
+            enabled: true,
+            created_at: 1_700_000_000,
+            last_run: None,
+            next_run: Some(1_700_006_400),
+        }
+    }
+
+    #[tokio::test]
+    async fn save_get_and_list() {
+        let store = setup_store().await;
+        let job = sample_job("job-1");
+        store.save(&job).await.unwrap();
+
+        assert_eq!(store.get("job-1").await.unwrap(), Some(job.clone()));
+        assert!(store.get("missing").await.unwrap().is_none());
+        assert_eq!(store.list().await.unwrap(), vec![job]);
+    }
+
+    #[tokio::test]
+    async fn save_replaces_existing_job() {
+        let store = setup_store().await;
+        let mut job = sample_job("job-1");
+        store.save(&job).await.unwrap();
+
+        job.schedule = "*/5 * * * *".to_string();
+        store.save(&job).await.unwrap();
+
+        let jobs = store.list().await.unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].schedule, "*/5 * * * *");
+    }
+
+    #[tokio::test]
+    async fn enable_runs_and_delete() {
+        let store = setup_store().await;
+        store.save(&sample_job("job-1")).await.unwrap();
+
+        assert!(store.set_enabled("job-1", false).await.unwrap());
+        assert!(
+            store
+                .update_runs("job-1", Some(10), Some(20))
+                .await
+                .unwrap()
+        );
+        let job = store.get("job-1").await.unwrap().unwrap();
+        assert!(!job.enabled);
+        assert_eq!((job.last_run, job.next_run), (Some(10), Some(20)));
+
+        assert!(!store.set_enabled("missing", true).await.unwrap());
+        assert!(store.delete("job-1").await.unwrap());
+        assert!(!store.delete("job-1").await.unwrap());
+    }
+}
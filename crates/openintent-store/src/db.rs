@@ -3,16 +3,35 @@
 //! The [`Database`] struct wraps a `rusqlite::Connection` behind an
 //! `Arc<Mutex<>>` and exposes async methods that use
 //! `tokio::task::spawn_blocking` to avoid blocking the async runtime.
+//! It is the SQLite implementation of [`StorageBackend`].
+//!
+//! Other processes (the web server, a bot, another CLI) may hold the write
+//! lock.  SQLite waits up to the busy timeout for it; statements that still
+//! find the database locked are retried with exponential backoff before
+//! failing with [`StoreError::Busy`].
 
+use std::future::Future;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use rusqlite::Connection;
-use tracing::{debug, info};
+use rusqlite::types::{ToSql, ToSqlOutput, ValueRef};
+use tracing::{debug, info, warn};
 
+use crate::backend::{SqlConnection, SqlRow, SqlValue, StorageBackend};
 use crate::error::{StoreError, StoreResult};
 use crate::migration;
 
+/// How long SQLite waits for a locked database before a statement fails.
+pub const DEFAULT_BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How many times a statement that failed on a locked database is retried.
+const BUSY_RETRIES: u32 = 3;
+
+/// Pause before the first retry; doubled before each further one.
+const BUSY_BACKOFF: Duration = Duration::from_millis(100);
+
 /// Thread-safe handle to a SQLite database.
 ///
 /// All read/write operations go through [`Database::execute`] which
@@ -61,6 +80,16 @@ impl Database {
         Ok(db)
     }
 
+    /// Change how long SQLite waits for a locked database before a
+    /// statement fails (default [`DEFAULT_BUSY_TIMEOUT`]).
+    pub fn with_busy_timeout(self, timeout: Duration) -> StoreResult<Self> {
+        self.conn
+            .lock()
+            .map_err(|e| StoreError::TaskJoin(format!("mutex poisoned: {e}")))?
+            .busy_timeout(timeout)?;
+        Ok(self)
+    }
+
     /// Run all pending schema migrations.
     pub async fn run_migrations(&self) -> StoreResult<()> {
         let conn = Arc::clone(&self.conn);
@@ -75,8 +104,9 @@ impl Database {
 
     /// Execute an arbitrary closure against the connection on the blocking pool.
     ///
-    /// This is the primary way to interact with the database from async code.
-    /// The closure receives a `&Connection` and must return a `StoreResult<T>`.
+    /// The closure receives the raw `rusqlite` `&Connection` and must return
+    /// a `StoreResult<T>`.  Store code should prefer the backend-neutral
+    /// [`StorageBackend::with_conn`]; this is for SQLite-specific callers.
     ///
     /// # Example
     ///
@@ -97,7 +127,7 @@ impl Database {
             let conn = conn
                 .lock()
                 .map_err(|e| StoreError::TaskJoin(format!("mutex poisoned: {e}")))?;
-            f(&conn)
+            f(&conn).map_err(map_busy)
         })
         .await?
     }
@@ -116,7 +146,7 @@ impl Database {
             let mut conn = conn
                 .lock()
                 .map_err(|e| StoreError::TaskJoin(format!("mutex poisoned: {e}")))?;
-            f(&mut conn)
+            f(&mut conn).map_err(map_busy)
         })
         .await?
     }
@@ -148,13 +178,146 @@ impl Database {
         conn.pragma_update(None, "foreign_keys", "ON")?;
 
         // Busy timeout so concurrent writers wait instead of failing immediately.
-        conn.pragma_update(None, "busy_timeout", 5_000_i32)?;
+        conn.busy_timeout(DEFAULT_BUSY_TIMEOUT)?;
 
         info!("database pragmas applied (WAL, mmap 256MiB, cache 62MiB)");
         Ok(())
     }
 }
 
+// ── StorageBackend ───────────────────────────────────────────────────
+
+impl StorageBackend for Database {
+    fn name(&self) -> &'static str {
+        "sqlite"
+    }
+
+    fn run_migrations(&self) -> impl Future<Output = StoreResult<()>> + Send {
+        Database::run_migrations(self)
+    }
+
+    fn with_conn<F, T>(&self, f: F) -> impl Future<Output = StoreResult<T>> + Send
+    where
+        F: FnOnce(&dyn SqlConnection) -> StoreResult<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.execute(move |conn| f(conn))
+    }
+
+    fn transaction<F, T>(&self, f: F) -> impl Future<Output = StoreResult<T>> + Send
+    where
+        F: FnOnce(&dyn SqlConnection) -> StoreResult<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        self.execute_mut(move |conn| {
+            let tx = conn.transaction()?;
+            let out = f(&*tx)?;
+            tx.commit().map_err(map_sqlite_error)?;
+            Ok(out)
+        })
+    }
+}
+
+impl SqlConnection for Connection {
+    fn execute(&self, sql: &str, params: &[SqlValue]) -> StoreResult<usize> {
+        retry_busy(|| Connection::execute(self, sql, rusqlite::params_from_iter(params)))
+    }
+
+    fn query(&self, sql: &str, params: &[SqlValue]) -> StoreResult<Vec<SqlRow>> {
+        retry_busy(|| {
+            let mut stmt = self.prepare_cached(sql)?;
+            let columns = stmt.column_count();
+            stmt.query_map(rusqlite::params_from_iter(params), |row| {
+                (0..columns)
+                    .map(|i| row.get_ref(i).map(sql_value))
+                    .collect::<rusqlite::Result<Vec<_>>>()
+                    .map(SqlRow::new)
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()
+        })
+    }
+
+    fn execute_batch(&self, sql: &str) -> StoreResult<()> {
+        retry_busy(|| Connection::execute_batch(self, sql))
+    }
+
+    fn last_insert_id(&self) -> i64 {
+        self.last_insert_rowid()
+    }
+}
+
+impl ToSql for SqlValue {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::Borrowed(match self {
+            SqlValue::Null => ValueRef::Null,
+            SqlValue::Integer(i) => ValueRef::Integer(*i),
+            SqlValue::Real(f) => ValueRef::Real(*f),
+            SqlValue::Text(s) => ValueRef::Text(s.as_bytes()),
+            SqlValue::Blob(b) => ValueRef::Blob(b),
+        }))
+    }
+}
+
+/// Copy a borrowed SQLite value into an owned [`SqlValue`].
+fn sql_value(value: ValueRef<'_>) -> SqlValue {
+    match value {
+        ValueRef::Null => SqlValue::Null,
+        ValueRef::Integer(i) => SqlValue::Integer(i),
+        ValueRef::Real(f) => SqlValue::Real(f),
+        ValueRef::Text(t) => SqlValue::Text(String::from_utf8_lossy(t).into_owned()),
+        ValueRef::Blob(b) => SqlValue::Blob(b.to_vec()),
+    }
+}
+
+/// Run `op`, retrying with exponential backoff while the database is
+/// locked by another connection.
+fn retry_busy<T>(mut op: impl FnMut() -> rusqlite::Result<T>) -> StoreResult<T> {
+    let mut backoff = BUSY_BACKOFF;
+    let mut retries = 0;
+    loop {
+        match op() {
+            Err(e) if is_busy(&e) && retries < BUSY_RETRIES => {
+                retries += 1;
+                warn!(retry = retries, ?backoff, "database is locked; retrying");
+                std::thread::sleep(backoff);
+                backoff *= 2;
+            }
+            result => return result.map_err(map_sqlite_error),
+        }
+    }
+}
+
+/// Whether `err` means another connection holds a conflicting lock.
+fn is_busy(err: &rusqlite::Error) -> bool {
+    matches!(
+        err,
+        rusqlite::Error::SqliteFailure(e, _)
+            if matches!(e.code, rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked)
+    )
+}
+
+/// Report lock failures as [`StoreError::Busy`], whichever path they took.
+fn map_busy(err: StoreError) -> StoreError {
+    match err {
+        StoreError::Sqlite(e) if is_busy(&e) => StoreError::Busy,
+        other => other,
+    }
+}
+
+/// Report constraint violations as [`StoreError::Constraint`] and lock
+/// failures as [`StoreError::Busy`].
+fn map_sqlite_error(err: rusqlite::Error) -> StoreError {
+    match err {
+        ref e if is_busy(e) => StoreError::Busy,
+        rusqlite::Error::SqliteFailure(ref e, ref msg)
+            if e.code == rusqlite::ErrorCode::ConstraintViolation =>
+        {
+            StoreError::Constraint(msg.clone().unwrap_or_else(|| e.to_string()))
+        }
+        other => StoreError::Sqlite(other),
+    }
+}
+
 // ── tests ────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -205,4 +368,47 @@ mod tests {
             .unwrap();
         assert_eq!(count, 0);
     }
+
+    #[tokio::test]
+    async fn writer_retries_until_lock_is_released() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("locked.db");
+        let db = Database::open(&path)
+            .unwrap()
+            .with_busy_timeout(Duration::from_millis(50))
+            .unwrap();
+        db.run_migrations().await.unwrap();
+
+        // Another process holds the write lock for a while, then commits.
+        let (locked_tx, locked_rx) = std::sync::mpsc::channel();
+        let locker = std::thread::spawn({
+            let path = path.clone();
+            move || {
+                let conn = Connection::open(&path).unwrap();
+                conn.execute_batch("BEGIN EXCLUSIVE").unwrap();
+                locked_tx.send(()).unwrap();
+                std::thread::sleep(Duration::from_millis(300));
+                conn.execute_batch("COMMIT").unwrap();
+            }
+        });
+        locked_rx.recv().unwrap();
+
+        db.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO bot_state (key, value) VALUES (?1, ?2)",
+                crate::sql_params!["offset", "42"],
+            )
+        })
+        .await
+        .unwrap();
+        locker.join().unwrap();
+
+        let count: i64 = db
+            .execute(|conn| {
+                Ok(conn.query_row("SELECT count(*) FROM bot_state", [], |row| row.get(0))?)
+            })
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+    }
 }
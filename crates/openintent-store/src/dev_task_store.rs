@@ -11,8 +11,10 @@ use serde::{Deserialize, Serialize};
 use tracing::{debug, instrument};
 use uuid::Uuid;
 
+use crate::backend::{SqlRow, StorageBackend};
 use crate::db::Database;
 use crate::error::{StoreError, StoreResult};
+use crate::sql_params;
 
 // ═══════════════════════════════════════════════════════════════════════
 //  Types
@@ -72,13 +74,13 @@ pub struct DevTaskMessage {
 
 /// CRUD operations on development tasks and their messages.
 #[derive(Clone)]
-pub struct DevTaskStore {
-    db: Database,
+pub struct DevTaskStore<B = Database> {
+    db: B,
 }
 
-impl DevTaskStore {
+impl<B: StorageBackend> DevTaskStore<B> {
     /// Create a new dev task store backed by `db`.
-    pub fn new(db: Database) -> Self {
+    pub fn new(db: B) -> Self {
         Self { db }
     }
 
@@ -115,11 +117,11 @@ impl DevTaskStore {
         };
 
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 conn.execute(
                     "INSERT INTO dev_tasks (id, source, chat_id, intent, status, progress_log, retry_count, max_retries, created_at, updated_at) \
                      VALUES (?1, ?2, ?3, ?4, 'pending', '[]', 0, 3, ?5, ?5)",
-                    rusqlite::params![id, source, chat_id, intent, now],
+                    sql_params![id, source, chat_id, intent, now],
                 )?;
                 Ok(())
             })
@@ -134,36 +136,16 @@ impl DevTaskStore {
     pub async fn get(&self, id: &str) -> StoreResult<Option<DevTask>> {
         let id = id.to_string();
         self.db
-            .execute(move |conn| {
-                let result = conn.query_row(
+            .with_conn(move |conn| {
+                conn.query_opt(
                     "SELECT id, source, chat_id, intent, status, branch, pr_url, current_step, \
                      progress_log, error, retry_count, max_retries, created_at, updated_at \
                      FROM dev_tasks WHERE id = ?1",
-                    rusqlite::params![id],
-                    |row| {
-                        Ok(DevTaskRow {
-                            id: row.get(0)?,
-                            source: row.get(1)?,
-                            chat_id: row.get(2)?,
-                            intent: row.get(3)?,
-                            status: row.get(4)?,
-                            branch: row.get(5)?,
-                            pr_url: row.get(6)?,
-                            current_step: row.get(7)?,
-                            progress_log: row.get(8)?,
-                            error: row.get(9)?,
-                            retry_count: row.get(10)?,
-                            max_retries: row.get(11)?,
-                            created_at: row.get(12)?,
-                            updated_at: row.get(13)?,
-                        })
-                    },
-                );
-                match result {
-                    Ok(row) => row.into_dev_task().map(Some),
-                    Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-                    Err(e) => Err(StoreError::Sqlite(e)),
-                }
+                    sql_params![id],
+                    DevTaskRow::from_row,
+                )?
+                .map(|row| row.into_dev_task())
+                .transpose()
             })
             .await
     }
@@ -178,32 +160,14 @@ impl DevTaskStore {
     ) -> StoreResult<Vec<DevTask>> {
         let status = status.to_string();
         self.db
-            .execute(move |conn| {
-                let mut stmt = conn.prepare(
+            .with_conn(move |conn| {
+                let rows = conn.query_map(
                     "SELECT id, source, chat_id, intent, status, branch, pr_url, current_step, \
                      progress_log, error, retry_count, max_retries, created_at, updated_at \
                      FROM dev_tasks WHERE status = ?1 ORDER BY updated_at DESC LIMIT ?2 OFFSET ?3",
+                    sql_params![status, limit, offset],
+                    DevTaskRow::from_row,
                 )?;
-                let rows = stmt
-                    .query_map(rusqlite::params![status, limit, offset], |row| {
-                        Ok(DevTaskRow {
-                            id: row.get(0)?,
-                            source: row.get(1)?,
-                            chat_id: row.get(2)?,
-                            intent: row.get(3)?,
-                            status: row.get(4)?,
-                            branch: row.get(5)?,
-                            pr_url: row.get(6)?,
-                            current_step: row.get(7)?,
-                            progress_log: row.get(8)?,
-                            error: row.get(9)?,
-                            retry_count: row.get(10)?,
-                            max_retries: row.get(11)?,
-                            created_at: row.get(12)?,
-                            updated_at: row.get(13)?,
-                        })
-                    })?
-                    .collect::<Result<Vec<_>, _>>()?;
 
                 rows.into_iter().map(|r| r.into_dev_task()).collect()
             })
@@ -219,32 +183,14 @@ impl DevTaskStore {
         offset: i64,
     ) -> StoreResult<Vec<DevTask>> {
         self.db
-            .execute(move |conn| {
-                let mut stmt = conn.prepare(
+            .with_conn(move |conn| {
+                let rows = conn.query_map(
                     "SELECT id, source, chat_id, intent, status, branch, pr_url, current_step, \
                      progress_log, error, retry_count, max_retries, created_at, updated_at \
                      FROM dev_tasks WHERE chat_id = ?1 ORDER BY updated_at DESC LIMIT ?2 OFFSET ?3",
+                    sql_params![chat_id, limit, offset],
+                    DevTaskRow::from_row,
                 )?;
-                let rows = stmt
-                    .query_map(rusqlite::params![chat_id, limit, offset], |row| {
-                        Ok(DevTaskRow {
-                            id: row.get(0)?,
-                            source: row.get(1)?,
-                            chat_id: row.get(2)?,
-                            intent: row.get(3)?,
-                            status: row.get(4)?,
-                            branch: row.get(5)?,
-                            pr_url: row.get(6)?,
-                            current_step: row.get(7)?,
-                            progress_log: row.get(8)?,
-                            error: row.get(9)?,
-                            retry_count: row.get(10)?,
-                            max_retries: row.get(11)?,
-                            created_at: row.get(12)?,
-                            updated_at: row.get(13)?,
-                        })
-                    })?
-                    .collect::<Result<Vec<_>, _>>()?;
 
                 rows.into_iter().map(|r| r.into_dev_task()).collect()
             })
@@ -267,10 +213,10 @@ impl DevTaskStore {
         let now = Utc::now().timestamp();
 
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 let updated = conn.execute(
                     "UPDATE dev_tasks SET status = ?2, current_step = ?3, updated_at = ?4 WHERE id = ?1",
-                    rusqlite::params![id, status, current_step, now],
+                    sql_params![id, status, current_step, now],
                 )?;
                 if updated == 0 {
                     return Err(StoreError::NotFound {
@@ -291,10 +237,10 @@ impl DevTaskStore {
         let now = Utc::now().timestamp();
 
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 let updated = conn.execute(
                     "UPDATE dev_tasks SET branch = ?2, updated_at = ?3 WHERE id = ?1",
-                    rusqlite::params![id, branch, now],
+                    sql_params![id, branch, now],
                 )?;
                 if updated == 0 {
                     return Err(StoreError::NotFound {
@@ -315,10 +261,10 @@ impl DevTaskStore {
         let now = Utc::now().timestamp();
 
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 let updated = conn.execute(
                     "UPDATE dev_tasks SET pr_url = ?2, updated_at = ?3 WHERE id = ?1",
-                    rusqlite::params![id, pr_url, now],
+                    sql_params![id, pr_url, now],
                 )?;
                 if updated == 0 {
                     return Err(StoreError::NotFound {
@@ -339,10 +285,10 @@ impl DevTaskStore {
         let now = Utc::now().timestamp();
 
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 let updated = conn.execute(
                     "UPDATE dev_tasks SET error = ?2, updated_at = ?3 WHERE id = ?1",
-                    rusqlite::params![id, error, now],
+                    sql_params![id, error, now],
                 )?;
                 if updated == 0 {
                     return Err(StoreError::NotFound {
@@ -362,10 +308,10 @@ impl DevTaskStore {
         let now = Utc::now().timestamp();
 
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 let updated = conn.execute(
                     "UPDATE dev_tasks SET retry_count = retry_count + 1, updated_at = ?2 WHERE id = ?1",
-                    rusqlite::params![id, now],
+                    sql_params![id, now],
                 )?;
                 if updated == 0 {
                     return Err(StoreError::NotFound {
@@ -376,7 +322,7 @@ impl DevTaskStore {
 
                 let new_count: i32 = conn.query_row(
                     "SELECT retry_count FROM dev_tasks WHERE id = ?1",
-                    rusqlite::params![id],
+                    sql_params![id],
                     |row| row.get(0),
                 )?;
                 Ok(new_count)
@@ -392,20 +338,17 @@ impl DevTaskStore {
         let now = Utc::now().timestamp();
 
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 // Read the current progress_log JSON.
                 let current_log: String = conn
-                    .query_row(
+                    .query_opt(
                         "SELECT progress_log FROM dev_tasks WHERE id = ?1",
-                        rusqlite::params![id],
+                        sql_params![id],
                         |row| row.get(0),
-                    )
-                    .map_err(|e| match e {
-                        rusqlite::Error::QueryReturnedNoRows => StoreError::NotFound {
-                            entity: "dev_task",
-                            id: id.clone(),
-                        },
-                        other => StoreError::Sqlite(other),
+                    )?
+                    .ok_or_else(|| StoreError::NotFound {
+                        entity: "dev_task",
+                        id: id.clone(),
                     })?;
 
                 // Parse, append, and serialize back.
@@ -415,7 +358,7 @@ impl DevTaskStore {
 
                 conn.execute(
                     "UPDATE dev_tasks SET progress_log = ?2, updated_at = ?3 WHERE id = ?1",
-                    rusqlite::params![id, updated_log, now],
+                    sql_params![id, updated_log, now],
                 )?;
 
                 Ok(())
@@ -439,13 +382,13 @@ impl DevTaskStore {
         let now = Utc::now().timestamp();
 
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 conn.execute(
                     "INSERT INTO dev_task_messages (task_id, role, content, created_at) \
                      VALUES (?1, ?2, ?3, ?4)",
-                    rusqlite::params![task_id, role, content, now],
+                    sql_params![task_id, role, content, now],
                 )?;
-                let msg_id = conn.last_insert_rowid();
+                let msg_id = conn.last_insert_id();
                 Ok(msg_id)
             })
             .await
@@ -463,43 +406,22 @@ impl DevTaskStore {
     ) -> StoreResult<Vec<DevTaskMessage>> {
         let task_id = task_id.to_string();
         self.db
-            .execute(move |conn| {
-                let messages = match limit {
-                    Some(n) => {
-                        let mut stmt = conn.prepare(
-                            "SELECT id, task_id, role, content, created_at \
-                             FROM (SELECT * FROM dev_task_messages WHERE task_id = ?1 ORDER BY created_at DESC, id DESC LIMIT ?2) \
-                             ORDER BY created_at ASC, id ASC",
-                        )?;
-                        stmt.query_map(rusqlite::params![task_id, n], |row| {
-                            Ok(DevTaskMessage {
-                                id: row.get(0)?,
-                                task_id: row.get(1)?,
-                                role: row.get(2)?,
-                                content: row.get(3)?,
-                                created_at: row.get(4)?,
-                            })
-                        })?
-                        .collect::<Result<Vec<_>, _>>()?
-                    }
-                    None => {
-                        let mut stmt = conn.prepare(
-                            "SELECT id, task_id, role, content, created_at \
-                             FROM dev_task_messages WHERE task_id = ?1 ORDER BY created_at ASC, id ASC",
-                        )?;
-                        stmt.query_map(rusqlite::params![task_id], |row| {
-                            Ok(DevTaskMessage {
-                                id: row.get(0)?,
-                                task_id: row.get(1)?,
-                                role: row.get(2)?,
-                                content: row.get(3)?,
-                                created_at: row.get(4)?,
-                            })
-                        })?
-                        .collect::<Result<Vec<_>, _>>()?
-                    }
-                };
-                Ok(messages)
+            .with_conn(move |conn| {
+                match limit {
+                    Some(n) => conn.query_map(
+                        "SELECT id, task_id, role, content, created_at \
+                         FROM (SELECT * FROM dev_task_messages WHERE task_id = ?1 ORDER BY created_at DESC, id DESC LIMIT ?2) \
+                         ORDER BY created_at ASC, id ASC",
+                        sql_params![task_id, n],
+                        message_row,
+                    ),
+                    None => conn.query_map(
+                        "SELECT id, task_id, role, content, created_at \
+                         FROM dev_task_messages WHERE task_id = ?1 ORDER BY created_at ASC, id ASC",
+                        sql_params![task_id],
+                        message_row,
+                    ),
+                }
             })
             .await
     }
@@ -511,33 +433,15 @@ impl DevTaskStore {
     #[instrument(skip(self))]
     pub async fn list_recoverable(&self) -> StoreResult<Vec<DevTask>> {
         self.db
-            .execute(move |conn| {
-                let mut stmt = conn.prepare(
+            .with_conn(move |conn| {
+                let rows = conn.query_map(
                     "SELECT id, source, chat_id, intent, status, branch, pr_url, current_step, \
                      progress_log, error, retry_count, max_retries, created_at, updated_at \
                      FROM dev_tasks WHERE status IN ('branching', 'coding', 'testing') \
                      ORDER BY updated_at DESC",
+                    sql_params![],
+                    DevTaskRow::from_row,
                 )?;
-                let rows = stmt
-                    .query_map([], |row| {
-                        Ok(DevTaskRow {
-                            id: row.get(0)?,
-                            source: row.get(1)?,
-                            chat_id: row.get(2)?,
-                            intent: row.get(3)?,
-                            status: row.get(4)?,
-                            branch: row.get(5)?,
-                            pr_url: row.get(6)?,
-                            current_step: row.get(7)?,
-                            progress_log: row.get(8)?,
-                            error: row.get(9)?,
-                            retry_count: row.get(10)?,
-                            max_retries: row.get(11)?,
-                            created_at: row.get(12)?,
-                            updated_at: row.get(13)?,
-                        })
-                    })?
-                    .collect::<Result<Vec<_>, _>>()?;
 
                 rows.into_iter().map(|r| r.into_dev_task()).collect()
             })
@@ -551,10 +455,10 @@ impl DevTaskStore {
         let now = Utc::now().timestamp();
 
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 let updated = conn.execute(
                     "UPDATE dev_tasks SET status = 'cancelled', updated_at = ?2 WHERE id = ?1",
-                    rusqlite::params![id, now],
+                    sql_params![id, now],
                 )?;
                 if updated == 0 {
                     return Err(StoreError::NotFound {
@@ -572,9 +476,9 @@ impl DevTaskStore {
     pub async fn delete(&self, id: &str) -> StoreResult<()> {
         let id = id.to_string();
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 let deleted =
-                    conn.execute("DELETE FROM dev_tasks WHERE id = ?1", rusqlite::params![id])?;
+                    conn.execute("DELETE FROM dev_tasks WHERE id = ?1", sql_params![id])?;
                 if deleted == 0 {
                     return Err(StoreError::NotFound {
                         entity: "dev_task",
@@ -598,38 +502,18 @@ impl DevTaskStore {
     ) -> StoreResult<Option<DevTask>> {
         let intent = intent.to_string();
         self.db
-            .execute(move |conn| {
-                let result = conn.query_row(
+            .with_conn(move |conn| {
+                conn.query_opt(
                     "SELECT id, source, chat_id, intent, status, branch, pr_url, current_step, \
                      progress_log, error, retry_count, max_retries, created_at, updated_at \
                      FROM dev_tasks WHERE chat_id = ?1 AND intent = ?2 \
                      AND status NOT IN ('completed', 'failed', 'cancelled') \
                      ORDER BY created_at DESC LIMIT 1",
-                    rusqlite::params![chat_id, intent],
-                    |row| {
-                        Ok(DevTaskRow {
-                            id: row.get(0)?,
-                            source: row.get(1)?,
-                            chat_id: row.get(2)?,
-                            intent: row.get(3)?,
-                            status: row.get(4)?,
-                            branch: row.get(5)?,
-                            pr_url: row.get(6)?,
-                            current_step: row.get(7)?,
-                            progress_log: row.get(8)?,
-                            error: row.get(9)?,
-                            retry_count: row.get(10)?,
-                            max_retries: row.get(11)?,
-                            created_at: row.get(12)?,
-                            updated_at: row.get(13)?,
-                        })
-                    },
-                );
-                match result {
-                    Ok(row) => row.into_dev_task().map(Some),
-                    Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-                    Err(e) => Err(StoreError::Sqlite(e)),
-                }
+                    sql_params![chat_id, intent],
+                    DevTaskRow::from_row,
+                )?
+                .map(|row| row.into_dev_task())
+                .transpose()
             })
             .await
     }
@@ -639,10 +523,10 @@ impl DevTaskStore {
     pub async fn count_by_status(&self, status: &str) -> StoreResult<i64> {
         let status = status.to_string();
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 let count: i64 = conn.query_row(
                     "SELECT COUNT(*) FROM dev_tasks WHERE status = ?1",
-                    rusqlite::params![status],
+                    sql_params![status],
                     |row| row.get(0),
                 )?;
                 Ok(count)
@@ -655,11 +539,11 @@ impl DevTaskStore {
 //  Internal row mapping
 // ═══════════════════════════════════════════════════════════════════════
 
-/// Raw row data from SQLite before JSON deserialization.
+/// Raw row data before JSON deserialization.
 ///
-/// Keeps the `rusqlite` row-mapping closure simple (no fallible JSON
-/// parsing inside `|row| { ... }`), then converts to `DevTask`
-/// in a second step where we can return `StoreError::Json`.
+/// Keeps the row mapping simple (no JSON parsing inside
+/// [`DevTaskRow::from_row`]), then converts to `DevTask` in a second
+/// step where we can return `StoreError::Json`.
 struct DevTaskRow {
     id: String,
     source: String,
@@ -678,6 +562,26 @@ struct DevTaskRow {
 }
 
 impl DevTaskRow {
+    /// Map a `dev_tasks` row (in the canonical column order).
+    fn from_row(row: &SqlRow) -> StoreResult<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            source: row.get(1)?,
+            chat_id: row.get(2)?,
+            intent: row.get(3)?,
+            status: row.get(4)?,
+            branch: row.get(5)?,
+            pr_url: row.get(6)?,
+            current_step: row.get(7)?,
+            progress_log: row.get(8)?,
+            error: row.get(9)?,
+            retry_count: row.get(10)?,
+            max_retries: row.get(11)?,
+            created_at: row.get(12)?,
+            updated_at: row.get(13)?,
+        })
+    }
+
     /// Convert raw row strings into a fully deserialized `DevTask`.
     fn into_dev_task(self) -> StoreResult<DevTask> {
         let progress_log: serde_json::Value = serde_json::from_str(&self.progress_log)?;
@@ -701,6 +605,17 @@ impl DevTaskRow {
     }
 }
 
+/// Map a `dev_task_messages` row (in the canonical column order).
+fn message_row(row: &SqlRow) -> StoreResult<DevTaskMessage> {
+    Ok(DevTaskMessage {
+        id: row.get(0)?,
+        task_id: row.get(1)?,
+        role: row.get(2)?,
+        content: row.get(3)?,
+        created_at: row.get(4)?,
+    })
+}
+
 // ── tests ────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -15,6 +15,18 @@ pub enum StoreError {
     #[error("sqlite error: {0}")]
     Sqlite(#[from] rusqlite::Error),
 
+    /// A unique, foreign-key, or check constraint was violated.
+    #[error("constraint violated: {0}")]
+    Constraint(String),
+
+    /// A column value could not be converted to the requested type.
+    #[error("column type mismatch: {0}")]
+    ColumnType(String),
+
+    /// A query that must return a row returned none.
+    #[error("query returned no rows")]
+    NoRows,
+
     /// JSON serialization or deserialization failed.
     #[error("json error: {0}")]
     Json(#[from] serde_json::Error),
@@ -31,10 +43,29 @@ pub enum StoreError {
     #[error("invalid argument: {0}")]
     InvalidArgument(String),
 
+    /// The database stayed locked by another connection after retrying.
+    #[error(
+        "database is locked by another process; stop the other OpenIntentOS instance \
+         (CLI, web server or bot) using it and try again"
+    )]
+    Busy,
+
     /// A blocking task was cancelled or panicked.
     #[error("background task failed: {0}")]
     TaskJoin(String),
 
+    /// Encrypting or decrypting stored data failed.
+    #[error("encryption error: {0}")]
+    Crypto(String),
+
+    /// Reading or writing a file failed.
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// A store archive is malformed or incompatible with this database.
+    #[error("archive error: {0}")]
+    Archive(String),
+
     /// Cache operation failed.
     #[error("cache error: {0}")]
     Cache(String),
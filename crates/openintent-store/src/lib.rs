@@ -5,7 +5,7 @@
 //! Provides SQLite-backed persistence with WAL mode and mmap for
 //! microsecond reads, a 3-layer memory system (working / episodic /
 //! semantic), a lock-free hot cache via `moka`, and multi-user
-//! account management with PBKDF2 password hashing.
+//! account management with Argon2id / PBKDF2 password hashing.
 //!
 //! ## Architecture
 //!
@@ -17,12 +17,16 @@
 //! │  EpisodicMemory  (SQLite episodes)       │
 //! │  SemanticMemory  (SQLite memories + vec) │
 //! ├─────────────────────────────────────────┤
-//! │  UserStore     (multi-user, PBKDF2)      │
+//! │  UserStore     (multi-user, Argon2id)    │
 //! │  SessionStore  (conversation history)    │
 //! │  WorkflowStore (persistent workflows)    │
+//! │  CronJobStore  (recurring jobs)          │
+//! │  ToolAuditStore (tool-call audit trail)  │
 //! ├─────────────────────────────────────────┤
+//! │  StorageBackend (backend-neutral SQL)    │
 //! │  Database (rusqlite WAL + mmap)          │
 //! │  Migrations (versioned, transactional)   │
+//! │  Archives (export / import, JSON)        │
 //! └─────────────────────────────────────────┘
 //! ```
 //!
@@ -40,28 +44,37 @@
 //!     .build();
 //! ```
 
+pub mod archive;
+pub mod backend;
 pub mod bot_state;
 pub mod cache;
+pub mod cron_store;
 pub mod db;
 pub mod dev_task_store;
 pub mod error;
 pub mod memory;
 pub mod migration;
 pub mod session;
+pub mod tool_audit;
 pub mod user_store;
 pub mod workflow_store;
 
 // ── re-exports ───────────────────────────────────────────────────────
 
+pub use archive::{ARCHIVE_FORMAT_VERSION, ArchiveOptions, ArchiveSummary};
+pub use backend::{FromSqlValue, SqlConnection, SqlRow, SqlValue, StorageBackend, ToSqlValue};
 pub use bot_state::BotStateStore;
 pub use cache::{CacheLayer, CacheLayerBuilder, CacheStats};
+pub use cron_store::{CronJobStore, StoredCronJob};
 pub use db::Database;
 pub use dev_task_store::{DevTask, DevTaskMessage, DevTaskStore};
 pub use error::{StoreError, StoreResult};
 pub use memory::{
-    Episode, EpisodeKind, EpisodicMemory, Memory, MemoryCategory, NewMemory, SemanticMemory,
-    WorkingMemory,
+    ConsolidationReport, DecayPolicy, DecayReport, Episode, EpisodeKind, EpisodicMemory, Memory,
+    MemoryCategory, MemoryQuery, MergedCluster, MergedMemory, NewMemory, ScoredMemory,
+    SemanticMemory, WorkingMemory,
 };
-pub use session::{Session, SessionMessage, SessionStore};
-pub use user_store::{User, UserRole, UserStore};
-pub use workflow_store::{StoredWorkflow, WorkflowStore};
+pub use session::{SESSION_KEY_LEN, Session, SessionMessage, SessionStore};
+pub use tool_audit::{NewToolAudit, ToolAuditEntry, ToolAuditStore};
+pub use user_store::{Argon2Params, PasswordScheme, User, UserRole, UserStore};
+pub use workflow_store::{StoredWorkflow, StoredWorkflowRun, WorkflowStore};
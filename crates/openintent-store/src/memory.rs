@@ -4,19 +4,31 @@
 //! |----------|----------------|-------------|--------------|
 //! | Working  | `HashMap` RAM  | < 0.001 ms  | Single task  |
 //! | Episodic | SQLite `episodes` table | < 5 us | 30 days |
-//! | Semantic | SQLite `memories` table | < 1 ms | Permanent |
+//! | Semantic | SQLite `memories` table | < 1 ms | Until TTL or decay |
 //!
 //! Each layer has a clear, independent interface. Working memory is purely
-//! in-process; episodic and semantic memory are backed by the [`Database`].
+//! in-process; episodic and semantic memory are backed by a
+//! [`StorageBackend`], by default the SQLite [`Database`].
 
 use std::collections::HashMap;
+use std::time::Duration;
 
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, instrument};
 
+use crate::backend::{SqlRow, StorageBackend};
 use crate::db::Database;
 use crate::error::{StoreError, StoreResult};
+use crate::sql_params;
+
+mod consolidate;
+mod decay;
+mod search;
+
+pub use consolidate::{ConsolidationReport, MergedCluster, MergedMemory};
+pub use decay::{DecayPolicy, DecayReport};
+pub use search::{MemoryQuery, ScoredMemory};
 
 // ═══════════════════════════════════════════════════════════════════════
 //  Layer 1 — Working Memory (RAM, scoped to a single task)
@@ -131,13 +143,13 @@ pub struct Episode {
 
 /// CRUD operations on the `episodes` table.
 #[derive(Clone)]
-pub struct EpisodicMemory {
-    db: Database,
+pub struct EpisodicMemory<B = Database> {
+    db: B,
 }
 
-impl EpisodicMemory {
+impl<B: StorageBackend> EpisodicMemory<B> {
     /// Create a new episodic memory backed by `db`.
-    pub fn new(db: Database) -> Self {
+    pub fn new(db: B) -> Self {
         Self { db }
     }
 
@@ -155,12 +167,12 @@ impl EpisodicMemory {
         let now = Utc::now().timestamp();
 
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 conn.execute(
                     "INSERT INTO episodes (task_id, type, content, timestamp) VALUES (?1, ?2, ?3, ?4)",
-                    rusqlite::params![task_id, kind_str, content_str, now],
+                    sql_params![task_id, kind_str, content_str, now],
                 )?;
-                Ok(conn.last_insert_rowid())
+                Ok(conn.last_insert_id())
             })
             .await
     }
@@ -169,29 +181,15 @@ impl EpisodicMemory {
     #[instrument(skip(self))]
     pub async fn get(&self, id: i64) -> StoreResult<Episode> {
         self.db
-            .execute(move |conn| {
-                conn.query_row(
+            .with_conn(move |conn| {
+                conn.query_opt(
                     "SELECT id, task_id, type, content, timestamp FROM episodes WHERE id = ?1",
-                    [id],
-                    |row| {
-                        Ok((
-                            row.get::<_, i64>(0)?,
-                            row.get::<_, String>(1)?,
-                            row.get::<_, String>(2)?,
-                            row.get::<_, String>(3)?,
-                            row.get::<_, i64>(4)?,
-                        ))
-                    },
-                )
-                .map_err(StoreError::from)
-                .and_then(|(id, task_id, kind_str, content_str, ts)| {
-                    Ok(Episode {
-                        id,
-                        task_id,
-                        kind: EpisodeKind::from_str(&kind_str)?,
-                        content: serde_json::from_str(&content_str)?,
-                        timestamp: ts,
-                    })
+                    sql_params![id],
+                    episode_row,
+                )?
+                .ok_or(StoreError::NotFound {
+                    entity: "episode",
+                    id: id.to_string(),
                 })
             })
             .await
@@ -202,34 +200,13 @@ impl EpisodicMemory {
     pub async fn list_by_task(&self, task_id: &str) -> StoreResult<Vec<Episode>> {
         let task_id = task_id.to_string();
         self.db
-            .execute(move |conn| {
-                let mut stmt = conn.prepare(
+            .with_conn(move |conn| {
+                conn.query_map(
                     "SELECT id, task_id, type, content, timestamp \
                      FROM episodes WHERE task_id = ?1 ORDER BY timestamp ASC",
-                )?;
-                let rows = stmt
-                    .query_map([&task_id], |row| {
-                        Ok((
-                            row.get::<_, i64>(0)?,
-                            row.get::<_, String>(1)?,
-                            row.get::<_, String>(2)?,
-                            row.get::<_, String>(3)?,
-                            row.get::<_, i64>(4)?,
-                        ))
-                    })?
-                    .collect::<Result<Vec<_>, _>>()?;
-
-                let mut episodes = Vec::with_capacity(rows.len());
-                for (id, task_id, kind_str, content_str, ts) in rows {
-                    episodes.push(Episode {
-                        id,
-                        task_id,
-                        kind: EpisodeKind::from_str(&kind_str)?,
-                        content: serde_json::from_str(&content_str)?,
-                        timestamp: ts,
-                    });
-                }
-                Ok(episodes)
+                    sql_params![task_id],
+                    episode_row,
+                )
             })
             .await
     }
@@ -239,9 +216,11 @@ impl EpisodicMemory {
     pub async fn delete_by_task(&self, task_id: &str) -> StoreResult<usize> {
         let task_id = task_id.to_string();
         self.db
-            .execute(move |conn| {
-                let deleted =
-                    conn.execute("DELETE FROM episodes WHERE task_id = ?1", [&task_id])?;
+            .with_conn(move |conn| {
+                let deleted = conn.execute(
+                    "DELETE FROM episodes WHERE task_id = ?1",
+                    sql_params![task_id],
+                )?;
                 Ok(deleted)
             })
             .await
@@ -253,10 +232,10 @@ impl EpisodicMemory {
     #[instrument(skip(self))]
     pub async fn delete_before(&self, before_timestamp: i64) -> StoreResult<usize> {
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 let deleted = conn.execute(
                     "DELETE FROM episodes WHERE timestamp < ?1",
-                    [before_timestamp],
+                    sql_params![before_timestamp],
                 )?;
                 Ok(deleted)
             })
@@ -279,7 +258,8 @@ pub enum MemoryCategory {
 }
 
 impl MemoryCategory {
-    fn as_str(self) -> &'static str {
+    /// The name stored in the `category` column.
+    pub fn as_str(self) -> &'static str {
         match self {
             Self::Preference => "preference",
             Self::Knowledge => "knowledge",
@@ -314,6 +294,10 @@ pub struct Memory {
     pub access_count: i64,
     pub created_at: i64,
     pub updated_at: i64,
+    /// When the memory was last read through [`SemanticMemory::get`].
+    pub last_accessed: i64,
+    /// When the memory expires and becomes eligible for pruning, if ever.
+    pub expires_at: Option<i64>,
 }
 
 /// Input for creating a new memory.
@@ -322,17 +306,19 @@ pub struct NewMemory {
     pub content: String,
     pub embedding: Option<Vec<f32>>,
     pub importance: f64,
+    /// How long the memory lives; `None` keeps it until it decays away.
+    pub ttl: Option<Duration>,
 }
 
 /// CRUD operations on the `memories` table with vector storage support.
 #[derive(Clone)]
-pub struct SemanticMemory {
-    db: Database,
+pub struct SemanticMemory<B = Database> {
+    db: B,
 }
 
-impl SemanticMemory {
+impl<B: StorageBackend> SemanticMemory<B> {
     /// Create a new semantic memory layer backed by `db`.
-    pub fn new(db: Database) -> Self {
+    pub fn new(db: B) -> Self {
         Self { db }
     }
 
@@ -342,66 +328,54 @@ impl SemanticMemory {
         let now = Utc::now().timestamp();
         let category = input.category.as_str().to_string();
         let embedding_blob = input.embedding.map(embedding_to_blob);
+        let expires_at = input
+            .ttl
+            .map(|ttl| now.saturating_add(i64::try_from(ttl.as_secs()).unwrap_or(i64::MAX)));
 
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 conn.execute(
-                    "INSERT INTO memories (category, content, embedding, importance, access_count, created_at, updated_at) \
-                     VALUES (?1, ?2, ?3, ?4, 0, ?5, ?5)",
-                    rusqlite::params![
+                    "INSERT INTO memories (category, content, embedding, importance, access_count, \
+                     created_at, updated_at, last_accessed, expires_at) \
+                     VALUES (?1, ?2, ?3, ?4, 0, ?5, ?5, ?5, ?6)",
+                    sql_params![
                         category,
                         input.content,
                         embedding_blob,
                         input.importance,
                         now,
+                        expires_at,
                     ],
                 )?;
-                Ok(conn.last_insert_rowid())
+                Ok(conn.last_insert_id())
             })
             .await
     }
 
-    /// Fetch a single memory by ID, incrementing its access count.
+    /// Fetch a single memory by ID, incrementing its access count and
+    /// refreshing its last-access time.
     #[instrument(skip(self))]
     pub async fn get(&self, id: i64) -> StoreResult<Memory> {
         let now = Utc::now().timestamp();
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 // Bump access count.
                 conn.execute(
-                    "UPDATE memories SET access_count = access_count + 1, updated_at = ?2 WHERE id = ?1",
-                    rusqlite::params![id, now],
+                    "UPDATE memories SET access_count = access_count + 1, updated_at = ?2, \
+                     last_accessed = ?2 WHERE id = ?1",
+                    sql_params![id, now],
                 )?;
 
-                conn.query_row(
-                    "SELECT id, category, content, embedding, importance, access_count, created_at, updated_at \
+                conn.query_opt(
+                    "SELECT id, category, content, embedding, importance, access_count, created_at, updated_at, \
+                     last_accessed, expires_at \
                      FROM memories WHERE id = ?1",
-                    [id],
-                    |row| {
-                        Ok((
-                            row.get::<_, i64>(0)?,
-                            row.get::<_, String>(1)?,
-                            row.get::<_, String>(2)?,
-                            row.get::<_, Option<Vec<u8>>>(3)?,
-                            row.get::<_, f64>(4)?,
-                            row.get::<_, i64>(5)?,
-                            row.get::<_, i64>(6)?,
-                            row.get::<_, i64>(7)?,
-                        ))
-                    },
-                )
-                .map_err(StoreError::from)
-                .and_then(|(id, cat, content, emb, imp, ac, ca, ua)| {
-                    Ok(Memory {
-                        id,
-                        category: MemoryCategory::from_str(&cat)?,
-                        content,
-                        embedding: emb.map(blob_to_embedding),
-                        importance: imp,
-                        access_count: ac,
-                        created_at: ca,
-                        updated_at: ua,
-                    })
+                    sql_params![id],
+                    memory_row,
+                )?
+                .ok_or(StoreError::NotFound {
+                    entity: "memory",
+                    id: id.to_string(),
                 })
             })
             .await
@@ -416,40 +390,14 @@ impl SemanticMemory {
     ) -> StoreResult<Vec<Memory>> {
         let cat = category.as_str().to_string();
         self.db
-            .execute(move |conn| {
-                let mut stmt = conn.prepare(
-                    "SELECT id, category, content, embedding, importance, access_count, created_at, updated_at \
+            .with_conn(move |conn| {
+                conn.query_map(
+                    "SELECT id, category, content, embedding, importance, access_count, created_at, updated_at, \
+                     last_accessed, expires_at \
                      FROM memories WHERE category = ?1 ORDER BY importance DESC LIMIT ?2",
-                )?;
-                let rows = stmt
-                    .query_map(rusqlite::params![cat, limit], |row| {
-                        Ok((
-                            row.get::<_, i64>(0)?,
-                            row.get::<_, String>(1)?,
-                            row.get::<_, String>(2)?,
-                            row.get::<_, Option<Vec<u8>>>(3)?,
-                            row.get::<_, f64>(4)?,
-                            row.get::<_, i64>(5)?,
-                            row.get::<_, i64>(6)?,
-                            row.get::<_, i64>(7)?,
-                        ))
-                    })?
-                    .collect::<Result<Vec<_>, _>>()?;
-
-                let mut memories = Vec::with_capacity(rows.len());
-                for (id, cat, content, emb, imp, ac, ca, ua) in rows {
-                    memories.push(Memory {
-                        id,
-                        category: MemoryCategory::from_str(&cat)?,
-                        content,
-                        embedding: emb.map(blob_to_embedding),
-                        importance: imp,
-                        access_count: ac,
-                        created_at: ca,
-                        updated_at: ua,
-                    });
-                }
-                Ok(memories)
+                    sql_params![cat, limit],
+                    memory_row,
+                )
             })
             .await
     }
@@ -460,10 +408,10 @@ impl SemanticMemory {
         let now = Utc::now().timestamp();
         let blob = embedding_to_blob(embedding);
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 let updated = conn.execute(
                     "UPDATE memories SET embedding = ?2, updated_at = ?3 WHERE id = ?1",
-                    rusqlite::params![id, blob, now],
+                    sql_params![id, blob, now],
                 )?;
                 if updated == 0 {
                     return Err(StoreError::NotFound {
@@ -481,10 +429,10 @@ impl SemanticMemory {
     pub async fn update_importance(&self, id: i64, importance: f64) -> StoreResult<()> {
         let now = Utc::now().timestamp();
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 let updated = conn.execute(
                     "UPDATE memories SET importance = ?2, updated_at = ?3 WHERE id = ?1",
-                    rusqlite::params![id, importance, now],
+                    sql_params![id, importance, now],
                 )?;
                 if updated == 0 {
                     return Err(StoreError::NotFound {
@@ -501,8 +449,9 @@ impl SemanticMemory {
     #[instrument(skip(self))]
     pub async fn delete(&self, id: i64) -> StoreResult<()> {
         self.db
-            .execute(move |conn| {
-                let deleted = conn.execute("DELETE FROM memories WHERE id = ?1", [id])?;
+            .with_conn(move |conn| {
+                let deleted =
+                    conn.execute("DELETE FROM memories WHERE id = ?1", sql_params![id])?;
                 if deleted == 0 {
                     return Err(StoreError::NotFound {
                         entity: "memory",
@@ -528,69 +477,23 @@ impl SemanticMemory {
         let pattern = format!("%{query}%");
         let cat = category.map(|c| c.as_str().to_string());
         self.db
-            .execute(move |conn| {
-                let (sql, params_vec): (String, Vec<Box<dyn rusqlite::types::ToSql + Send>>) =
-                    match &cat {
-                        Some(cat_str) => (
-                            "SELECT id, category, content, embedding, importance, access_count, \
-                             created_at, updated_at FROM memories \
-                             WHERE content LIKE ?1 AND category = ?2 \
-                             ORDER BY importance DESC LIMIT ?3"
-                                .to_string(),
-                            vec![
-                                Box::new(pattern) as Box<dyn rusqlite::types::ToSql + Send>,
-                                Box::new(cat_str.clone()),
-                                Box::new(limit),
-                            ],
-                        ),
-                        None => (
-                            "SELECT id, category, content, embedding, importance, access_count, \
-                             created_at, updated_at FROM memories \
-                             WHERE content LIKE ?1 \
-                             ORDER BY importance DESC LIMIT ?2"
-                                .to_string(),
-                            vec![
-                                Box::new(pattern) as Box<dyn rusqlite::types::ToSql + Send>,
-                                Box::new(limit),
-                            ],
-                        ),
-                    };
-
-                let params_refs: Vec<&dyn rusqlite::types::ToSql> = params_vec
-                    .iter()
-                    .map(|p| p.as_ref() as &dyn rusqlite::types::ToSql)
-                    .collect();
-
-                let mut stmt = conn.prepare(&sql)?;
-                let rows = stmt
-                    .query_map(params_refs.as_slice(), |row| {
-                        Ok((
-                            row.get::<_, i64>(0)?,
-                            row.get::<_, String>(1)?,
-                            row.get::<_, String>(2)?,
-                            row.get::<_, Option<Vec<u8>>>(3)?,
-                            row.get::<_, f64>(4)?,
-                            row.get::<_, i64>(5)?,
-                            row.get::<_, i64>(6)?,
-                            row.get::<_, i64>(7)?,
-                        ))
-                    })?
-                    .collect::<Result<Vec<_>, _>>()?;
-
-                let mut memories = Vec::with_capacity(rows.len());
-                for (id, cat, content, emb, imp, ac, ca, ua) in rows {
-                    memories.push(Memory {
-                        id,
-                        category: MemoryCategory::from_str(&cat)?,
-                        content,
-                        embedding: emb.map(blob_to_embedding),
-                        importance: imp,
-                        access_count: ac,
-                        created_at: ca,
-                        updated_at: ua,
-                    });
-                }
-                Ok(memories)
+            .with_conn(move |conn| match &cat {
+                Some(cat_str) => conn.query_map(
+                    "SELECT id, category, content, embedding, importance, access_count, \
+                         created_at, updated_at, last_accessed, expires_at FROM memories \
+                         WHERE content LIKE ?1 AND category = ?2 \
+                         ORDER BY importance DESC LIMIT ?3",
+                    sql_params![pattern, cat_str, limit],
+                    memory_row,
+                ),
+                None => conn.query_map(
+                    "SELECT id, category, content, embedding, importance, access_count, \
+                         created_at, updated_at, last_accessed, expires_at FROM memories \
+                         WHERE content LIKE ?1 \
+                         ORDER BY importance DESC LIMIT ?2",
+                    sql_params![pattern, limit],
+                    memory_row,
+                ),
             })
             .await
     }
@@ -605,64 +508,22 @@ impl SemanticMemory {
     ) -> StoreResult<Vec<Memory>> {
         let cat = category.map(|c| c.as_str().to_string());
         self.db
-            .execute(move |conn| {
-                let (sql, params_vec): (String, Vec<Box<dyn rusqlite::types::ToSql + Send>>) =
-                    match &cat {
-                        Some(cat_str) => (
-                            "SELECT id, category, content, embedding, importance, access_count, \
-                             created_at, updated_at FROM memories \
-                             WHERE category = ?1 \
-                             ORDER BY importance DESC LIMIT ?2"
-                                .to_string(),
-                            vec![
-                                Box::new(cat_str.clone()) as Box<dyn rusqlite::types::ToSql + Send>,
-                                Box::new(limit),
-                            ],
-                        ),
-                        None => (
-                            "SELECT id, category, content, embedding, importance, access_count, \
-                             created_at, updated_at FROM memories \
-                             ORDER BY importance DESC LIMIT ?1"
-                                .to_string(),
-                            vec![Box::new(limit) as Box<dyn rusqlite::types::ToSql + Send>],
-                        ),
-                    };
-
-                let params_refs: Vec<&dyn rusqlite::types::ToSql> = params_vec
-                    .iter()
-                    .map(|p| p.as_ref() as &dyn rusqlite::types::ToSql)
-                    .collect();
-
-                let mut stmt = conn.prepare(&sql)?;
-                let rows = stmt
-                    .query_map(params_refs.as_slice(), |row| {
-                        Ok((
-                            row.get::<_, i64>(0)?,
-                            row.get::<_, String>(1)?,
-                            row.get::<_, String>(2)?,
-                            row.get::<_, Option<Vec<u8>>>(3)?,
-                            row.get::<_, f64>(4)?,
-                            row.get::<_, i64>(5)?,
-                            row.get::<_, i64>(6)?,
-                            row.get::<_, i64>(7)?,
-                        ))
-                    })?
-                    .collect::<Result<Vec<_>, _>>()?;
-
-                let mut memories = Vec::with_capacity(rows.len());
-                for (id, cat, content, emb, imp, ac, ca, ua) in rows {
-                    memories.push(Memory {
-                        id,
-                        category: MemoryCategory::from_str(&cat)?,
-                        content,
-                        embedding: emb.map(blob_to_embedding),
-                        importance: imp,
-                        access_count: ac,
-                        created_at: ca,
-                        updated_at: ua,
-                    });
-                }
-                Ok(memories)
+            .with_conn(move |conn| match &cat {
+                Some(cat_str) => conn.query_map(
+                    "SELECT id, category, content, embedding, importance, access_count, \
+                         created_at, updated_at, last_accessed, expires_at FROM memories \
+                         WHERE category = ?1 \
+                         ORDER BY importance DESC LIMIT ?2",
+                    sql_params![cat_str, limit],
+                    memory_row,
+                ),
+                None => conn.query_map(
+                    "SELECT id, category, content, embedding, importance, access_count, \
+                         created_at, updated_at, last_accessed, expires_at FROM memories \
+                         ORDER BY importance DESC LIMIT ?1",
+                    sql_params![limit],
+                    memory_row,
+                ),
             })
             .await
     }
@@ -670,15 +531,17 @@ impl SemanticMemory {
     /// Count all memories, optionally filtered by category.
     pub async fn count(&self, category: Option<MemoryCategory>) -> StoreResult<i64> {
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 let count: i64 = match category {
                     Some(cat) => conn.query_row(
                         "SELECT count(*) FROM memories WHERE category = ?1",
-                        [cat.as_str()],
+                        sql_params![cat.as_str()],
                         |row| row.get(0),
                     )?,
                     None => {
-                        conn.query_row("SELECT count(*) FROM memories", [], |row| row.get(0))?
+                        conn.query_row("SELECT count(*) FROM memories", sql_params![], |row| {
+                            row.get(0)
+                        })?
                     }
                 };
                 Ok(count)
@@ -687,6 +550,37 @@ impl SemanticMemory {
     }
 }
 
+// ── row mapping ──────────────────────────────────────────────────────
+
+/// Map an `episodes` row (in the canonical column order) to an episode.
+fn episode_row(row: &SqlRow) -> StoreResult<Episode> {
+    Ok(Episode {
+        id: row.get(0)?,
+        task_id: row.get(1)?,
+        kind: EpisodeKind::from_str(&row.get::<String>(2)?)?,
+        content: serde_json::from_str(&row.get::<String>(3)?)?,
+        timestamp: row.get(4)?,
+    })
+}
+
+/// Map a `memories` row (in the canonical column order) to a memory.
+fn memory_row(row: &SqlRow) -> StoreResult<Memory> {
+    let updated_at = row.get(7)?;
+    Ok(Memory {
+        id: row.get(0)?,
+        category: MemoryCategory::from_str(&row.get::<String>(1)?)?,
+        content: row.get(2)?,
+        embedding: row.get::<Option<Vec<u8>>>(3)?.map(blob_to_embedding),
+        importance: row.get(4)?,
+        access_count: row.get(5)?,
+        created_at: row.get(6)?,
+        updated_at,
+        // Rows restored from an archive predating access tracking have none.
+        last_accessed: row.get::<Option<i64>>(8)?.unwrap_or(updated_at),
+        expires_at: row.get(9)?,
+    })
+}
+
 // ── vector helpers ───────────────────────────────────────────────────
 
 /// Serialize a `Vec<f32>` into a byte blob (little-endian) for SQLite BLOB storage.
@@ -836,6 +730,7 @@ mod tests {
                 content: "Rust is a systems programming language".to_string(),
                 embedding: Some(vec![0.1, 0.2, 0.3]),
                 importance: 0.8,
+                ttl: None,
             })
             .await
             .unwrap();
@@ -857,6 +752,7 @@ mod tests {
             content: "Likes dark mode".to_string(),
             embedding: None,
             importance: 0.9,
+            ttl: None,
         })
         .await
         .unwrap();
@@ -866,6 +762,7 @@ mod tests {
             content: "Prefers verbose output".to_string(),
             embedding: None,
             importance: 0.5,
+            ttl: None,
         })
         .await
         .unwrap();
@@ -875,6 +772,7 @@ mod tests {
             content: "Unrelated".to_string(),
             embedding: None,
             importance: 1.0,
+            ttl: None,
         })
         .await
         .unwrap();
@@ -899,6 +797,7 @@ mod tests {
                 content: "test".to_string(),
                 embedding: None,
                 importance: 0.5,
+                ttl: None,
             })
             .await
             .unwrap();
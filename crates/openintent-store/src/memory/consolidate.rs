@@ -0,0 +1,336 @@
+//! Consolidation of near-duplicate semantic memories.
+//!
+//! Memories of the same category whose similarity meets a threshold are
+//! grouped (single-link, so A~B and B~C put A, B and C together) and folded
+//! into one canonical entry.  The folded entries are removed from `memories`
+//! and their content is kept in `memory_merges` as provenance.
+
+use std::collections::HashSet;
+
+use chrono::Utc;
+use serde::Serialize;
+use tracing::{info, instrument};
+
+use super::search::cosine_similarity;
+use super::{MemoryCategory, SemanticMemory, blob_to_embedding};
+use crate::backend::{SqlConnection, StorageBackend};
+use crate::error::{StoreError, StoreResult};
+use crate::sql_params;
+
+/// One group of memories folded into a canonical entry.
+#[derive(Debug, Clone, Serialize)]
+pub struct MergedCluster {
+    /// The memory that was kept.
+    pub canonical_id: i64,
+    /// The memories folded into it and removed.
+    pub merged_ids: Vec<i64>,
+}
+
+/// Outcome of [`SemanticMemory::consolidate`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConsolidationReport {
+    /// Number of memories examined.
+    pub examined: usize,
+    /// Number of memories removed by merging.
+    pub merged: usize,
+    /// The clusters that were collapsed.
+    pub clusters: Vec<MergedCluster>,
+}
+
+/// A memory that was folded into a canonical entry.
+#[derive(Debug, Clone, Serialize)]
+pub struct MergedMemory {
+    /// ID the memory had before it was merged.
+    pub source_id: i64,
+    pub content: String,
+    pub importance: f64,
+    pub created_at: i64,
+    pub merged_at: i64,
+}
+
+/// The fields of a memory needed to compare and merge it.
+struct Candidate {
+    id: i64,
+    category: MemoryCategory,
+    content: String,
+    embedding: Option<Vec<f32>>,
+    importance: f64,
+    access_count: i64,
+    created_at: i64,
+    tokens: HashSet<String>,
+}
+
+impl<B: StorageBackend> SemanticMemory<B> {
+    /// Merge clusters of memories whose similarity is at least `threshold`.
+    ///
+    /// Similarity is the cosine of the embeddings when both memories have
+    /// one, otherwise the Jaccard overlap of their lowercase words.  Only
+    /// memories of the same category are merged; `category` restricts the
+    /// run to one category.
+    ///
+    /// The canonical entry is the most important memory of the cluster
+    /// (then the most accessed, then the oldest).  It takes the highest
+    /// importance and the summed access count of the cluster.
+    #[instrument(skip(self))]
+    pub async fn consolidate(
+        &self,
+        threshold: f64,
+        category: Option<MemoryCategory>,
+    ) -> StoreResult<ConsolidationReport> {
+        if !(0.0..=1.0).contains(&threshold) {
+            return Err(StoreError::InvalidArgument(format!(
+                "similarity threshold must be between 0 and 1, got {threshold}"
+            )));
+        }
+        let now = Utc::now().timestamp();
+
+        self.db
+            .transaction(move |tx| {
+                let candidates = load_candidates(tx, category.map(|c| c.as_str()))?;
+                let clusters = cluster(&candidates, threshold);
+
+                let mut report = ConsolidationReport {
+                    examined: candidates.len(),
+                    ..Default::default()
+                };
+                for members in clusters {
+                    let mut members: Vec<&Candidate> =
+                        members.into_iter().map(|i| &candidates[i]).collect();
+                    members.sort_by(|a, b| {
+                        b.importance
+                            .total_cmp(&a.importance)
+                            .then(b.access_count.cmp(&a.access_count))
+                            .then(a.created_at.cmp(&b.created_at))
+                            .then(a.id.cmp(&b.id))
+                    });
+                    let (canonical, rest) = members.split_first().ok_or_else(|| {
+                        StoreError::InvalidArgument("empty memory cluster".into())
+                    })?;
+
+                    let access_count: i64 = members.iter().map(|m| m.access_count).sum();
+                    tx.execute(
+                        "UPDATE memories SET access_count = ?2, updated_at = ?3 WHERE id = ?1",
+                        sql_params![canonical.id, access_count, now],
+                    )?;
+                    for source in rest {
+                        tx.execute(
+                            "INSERT INTO memory_merges \
+                             (canonical_id, source_id, content, importance, created_at, merged_at) \
+                             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                            sql_params![
+                                canonical.id,
+                                source.id,
+                                source.content,
+                                source.importance,
+                                source.created_at,
+                                now
+                            ],
+                        )?;
+                        // Provenance already recorded against this entry moves
+                        // to the new canonical one.
+                        tx.execute(
+                            "UPDATE memory_merges SET canonical_id = ?1 WHERE canonical_id = ?2",
+                            sql_params![canonical.id, source.id],
+                        )?;
+                        tx.execute("DELETE FROM memories WHERE id = ?1", sql_params![source.id])?;
+                    }
+
+                    report.merged += rest.len();
+                    report.clusters.push(MergedCluster {
+                        canonical_id: canonical.id,
+                        merged_ids: rest.iter().map(|m| m.id).collect(),
+                    });
+                }
+
+                info!(
+                    examined = report.examined,
+                    merged = report.merged,
+                    clusters = report.clusters.len(),
+                    "memories consolidated"
+                );
+                Ok(report)
+            })
+            .await
+    }
+
+    /// Memories that were merged into `canonical_id`, oldest first.
+    #[instrument(skip(self))]
+    pub async fn merged_sources(&self, canonical_id: i64) -> StoreResult<Vec<MergedMemory>> {
+        self.db
+            .with_conn(move |conn| {
+                conn.query_map(
+                    "SELECT source_id, content, importance, created_at, merged_at \
+                     FROM memory_merges WHERE canonical_id = ?1 ORDER BY created_at, source_id",
+                    sql_params![canonical_id],
+                    |row| {
+                        Ok(MergedMemory {
+                            source_id: row.get(0)?,
+                            content: row.get(1)?,
+                            importance: row.get(2)?,
+                            created_at: row.get(3)?,
+                            merged_at: row.get(4)?,
+                        })
+                    },
+                )
+            })
+            .await
+    }
+}
+
+/// Load all memories (optionally of one category) for comparison.
+fn load_candidates(
+    conn: &dyn SqlConnection,
+    category: Option<&str>,
+) -> StoreResult<Vec<Candidate>> {
+    conn.query_map(
+        "SELECT id, category, content, embedding, importance, access_count, created_at \
+         FROM memories WHERE (?1 IS NULL OR category = ?1) ORDER BY id",
+        sql_params![category],
+        |row| {
+            let content: String = row.get(2)?;
+            Ok(Candidate {
+                id: row.get(0)?,
+                category: MemoryCategory::from_str(&row.get::<String>(1)?)?,
+                tokens: tokens(&content),
+                content,
+                embedding: row.get::<Option<Vec<u8>>>(3)?.map(blob_to_embedding),
+                importance: row.get(4)?,
+                access_count: row.get(5)?,
+                created_at: row.get(6)?,
+            })
+        },
+    )
+}
+
+/// Group candidates into clusters of two or more (indices into `candidates`).
+fn cluster(candidates: &[Candidate], threshold: f64) -> Vec<Vec<usize>> {
+    let mut parent: Vec<usize> = (0..candidates.len()).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    for i in 0..candidates.len() {
+        for j in (i + 1)..candidates.len() {
+            let (a, b) = (&candidates[i], &candidates[j]);
+            if a.category == b.category && similarity(a, b) >= threshold {
+                let (ra, rb) = (root(&mut parent, i), root(&mut parent, j));
+                if ra != rb {
+                    parent[rb] = ra;
+                }
+            }
+        }
+    }
+
+    let mut groups: Vec<Vec<usize>> = vec![Vec::new(); candidates.len()];
+    for i in 0..candidates.len() {
+        let r = root(&mut parent, i);
+        groups[r].push(i);
+    }
+    groups.retain(|g| g.len() > 1);
+    groups
+}
+
+/// Similarity of two memories in `[0, 1]` (cosine may dip below 0).
+fn similarity(a: &Candidate, b: &Candidate) -> f64 {
+    if let (Some(ea), Some(eb)) = (&a.embedding, &b.embedding) {
+        return cosine_similarity(ea, eb);
+    }
+    let union = a.tokens.union(&b.tokens).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.tokens.intersection(&b.tokens).count() as f64 / union as f64
+}
+
+/// Lowercase alphanumeric words of `text`.
+fn tokens(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::memory::NewMemory;
+
+    async fn setup() -> SemanticMemory {
+        let db = Database::open_in_memory().unwrap();
+        db.run_migrations().await.unwrap();
+        SemanticMemory::new(db)
+    }
+
+    async fn insert(sm: &SemanticMemory, category: MemoryCategory, content: &str, imp: f64) -> i64 {
+        sm.insert(NewMemory {
+            category,
+            content: content.into(),
+            embedding: None,
+            importance: imp,
+            ttl: None,
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn near_duplicates_collapse_into_one() {
+        let sm = setup().await;
+        let pref = MemoryCategory::Preference;
+        insert(&sm, pref, "User prefers dark mode in the editor", 0.5).await;
+        let keep = insert(&sm, pref, "user prefers dark mode in editor", 0.9).await;
+        insert(&sm, pref, "The user prefers dark mode in the editor.", 0.6).await;
+        insert(&sm, pref, "User likes tabs over spaces", 0.5).await;
+
+        let report = sm.consolidate(0.8, None).await.unwrap();
+        assert_eq!(report.examined, 4);
+        assert_eq!(report.merged, 2);
+        assert_eq!(report.clusters.len(), 1);
+        assert_eq!(report.clusters[0].canonical_id, keep);
+
+        assert_eq!(sm.count(Some(pref)).await.unwrap(), 2);
+        let sources = sm.merged_sources(keep).await.unwrap();
+        assert_eq!(sources.len(), 2);
+        assert!(sources.iter().all(|s| s.content.contains("dark mode")));
+    }
+
+    #[tokio::test]
+    async fn categories_are_never_merged_together() {
+        let sm = setup().await;
+        insert(&sm, MemoryCategory::Preference, "deploy on fridays", 0.5).await;
+        insert(&sm, MemoryCategory::Pattern, "deploy on fridays", 0.5).await;
+
+        let report = sm.consolidate(0.9, None).await.unwrap();
+        assert_eq!(report.merged, 0);
+        assert_eq!(sm.count(None).await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn provenance_follows_repeated_merges() {
+        let sm = setup().await;
+        let k = MemoryCategory::Knowledge;
+        let first = insert(&sm, k, "staging database lives in eu west", 0.5).await;
+        insert(&sm, k, "staging database lives in eu west", 0.4).await;
+        sm.consolidate(0.9, None).await.unwrap();
+
+        let newer = insert(&sm, k, "staging database lives in eu west", 0.8).await;
+        let report = sm.consolidate(0.9, Some(k)).await.unwrap();
+        assert_eq!(report.clusters[0].canonical_id, newer);
+        assert_eq!(report.clusters[0].merged_ids, vec![first]);
+        assert_eq!(sm.merged_sources(newer).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn rejects_out_of_range_threshold() {
+        let sm = setup().await;
+        assert!(matches!(
+            sm.consolidate(1.5, None).await,
+            Err(StoreError::InvalidArgument(_))
+        ));
+    }
+}
@@ -0,0 +1,239 @@
+//! Time-based decay and pruning of semantic memories.
+//!
+//! Memories inserted with a TTL are deleted once `expires_at` has passed.
+//! Memories nobody has read for a while lose importance in proportion to how
+//! long they have been unread, and are deleted once their importance fades
+//! below a floor.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use serde::Serialize;
+use tracing::{info, instrument};
+
+use super::SemanticMemory;
+use crate::backend::StorageBackend;
+use crate::error::{StoreError, StoreResult};
+use crate::sql_params;
+
+/// Parameters for [`SemanticMemory::decay_and_prune`].
+#[derive(Debug, Clone)]
+pub struct DecayPolicy {
+    /// Memories not accessed for this long are considered stale.
+    pub stale_after: Duration,
+    /// Multiplier applied to the importance of a stale memory for every
+    /// [`decay_period`](Self::decay_period) it stays stale, in `[0, 1]`.
+    pub decay_factor: f64,
+    /// Time over which a stale memory loses `1 - decay_factor` of its
+    /// importance.
+    pub decay_period: Duration,
+    /// Stale memories whose importance drops below this are deleted.
+    pub min_importance: f64,
+}
+
+impl Default for DecayPolicy {
+    fn default() -> Self {
+        Self {
+            stale_after: Duration::from_secs(30 * 24 * 60 * 60),
+            decay_factor: 0.9,
+            decay_period: Duration::from_secs(7 * 24 * 60 * 60),
+            min_importance: 0.05,
+        }
+    }
+}
+
+/// Outcome of [`SemanticMemory::decay_and_prune`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DecayReport {
+    /// Memories deleted because their TTL ran out.
+    pub expired: usize,
+    /// Stale memories whose importance was lowered.
+    pub decayed: usize,
+    /// Stale memories deleted because their importance fell below the floor.
+    pub faded: usize,
+}
+
+impl<B: StorageBackend> SemanticMemory<B> {
+    /// Delete expired memories and decay the importance of stale ones.
+    ///
+    /// Decay depends only on elapsed time: each run applies the decay owed
+    /// since the memory went stale or was last decayed, whichever is later,
+    /// so running this more or less often does not change how fast unused
+    /// memories fade.
+    #[instrument(skip(self))]
+    pub async fn decay_and_prune(&self, policy: &DecayPolicy) -> StoreResult<DecayReport> {
+        if !(0.0..=1.0).contains(&policy.decay_factor) {
+            return Err(StoreError::InvalidArgument(format!(
+                "decay factor must be between 0 and 1, got {}",
+                policy.decay_factor
+            )));
+        }
+        if policy.min_importance < 0.0 {
+            return Err(StoreError::InvalidArgument(format!(
+                "minimum importance must not be negative, got {}",
+                policy.min_importance
+            )));
+        }
+        if policy.decay_period.is_zero() {
+            return Err(StoreError::InvalidArgument(
+                "decay period must not be zero".into(),
+            ));
+        }
+        let now = Utc::now().timestamp();
+        let stale_secs = i64::try_from(policy.stale_after.as_secs()).unwrap_or(i64::MAX);
+        let stale_before = now.saturating_sub(stale_secs);
+        let (factor, floor) = (policy.decay_factor, policy.min_importance);
+        let period = policy.decay_period.as_secs_f64();
+
+        self.db
+            .transaction(move |tx| {
+                let expired = tx.execute(
+                    "DELETE FROM memories WHERE expires_at IS NOT NULL AND expires_at <= ?1",
+                    sql_params![now],
+                )?;
+                let stale = tx.query_map(
+                    "SELECT id, importance, last_accessed, last_decayed_at \
+                     FROM memories WHERE last_accessed < ?1",
+                    sql_params![stale_before],
+                    |row| {
+                        Ok((
+                            row.get::<i64>(0)?,
+                            row.get::<f64>(1)?,
+                            row.get::<i64>(2)?,
+                            row.get::<Option<i64>>(3)?,
+                        ))
+                    },
+                )?;
+                let mut decayed = 0;
+                for (id, importance, last_accessed, last_decayed_at) in stale {
+                    let stale_since = last_accessed.saturating_add(stale_secs);
+                    let since = last_decayed_at.map_or(stale_since, |t| t.max(stale_since));
+                    let elapsed = now.saturating_sub(since);
+                    if elapsed <= 0 {
+                        continue;
+                    }
+                    let importance = importance * factor.powf(elapsed as f64 / period);
+                    tx.execute(
+                        "UPDATE memories SET importance = ?2, last_decayed_at = ?3 WHERE id = ?1",
+                        sql_params![id, importance, now],
+                    )?;
+                    decayed += 1;
+                }
+                let faded = tx.execute(
+                    "DELETE FROM memories WHERE last_accessed < ?1 AND importance < ?2",
+                    sql_params![stale_before, floor],
+                )?;
+
+                let report = DecayReport {
+                    expired,
+                    decayed,
+                    faded,
+                };
+                info!(
+                    expired = report.expired,
+                    decayed = report.decayed,
+                    faded = report.faded,
+                    "memories decayed"
+                );
+                Ok(report)
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::memory::{MemoryCategory, NewMemory};
+
+    async fn setup() -> (Database, SemanticMemory) {
+        let db = Database::open_in_memory().unwrap();
+        db.run_migrations().await.unwrap();
+        (db.clone(), SemanticMemory::new(db))
+    }
+
+    async fn insert(sm: &SemanticMemory, content: &str, ttl: Option<Duration>) -> i64 {
+        sm.insert(NewMemory {
+            category: MemoryCategory::Knowledge,
+            content: content.into(),
+            embedding: None,
+            importance: 0.5,
+            ttl,
+        })
+        .await
+        .unwrap()
+    }
+
+    /// Pretend memory `id` was last read `days` ago.
+    async fn age(db: &Database, id: i64, days: i64) {
+        let last_accessed = Utc::now().timestamp() - days * 86_400;
+        db.with_conn(move |conn| {
+            conn.execute(
+                "UPDATE memories SET last_accessed = ?2 WHERE id = ?1",
+                sql_params![id, last_accessed],
+            )
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn expired_memory_is_pruned() {
+        let (_, sm) = setup().await;
+        let expired = insert(&sm, "one-off code", Some(Duration::ZERO)).await;
+        let kept = insert(&sm, "long-lived fact", Some(Duration::from_secs(3600))).await;
+        let forever = insert(&sm, "permanent fact", None).await;
+
+        let report = sm.decay_and_prune(&DecayPolicy::default()).await.unwrap();
+        assert_eq!(report.expired, 1);
+        assert_eq!(report.decayed, 0);
+
+        assert!(sm.get(expired).await.is_err());
+        assert!(sm.get(kept).await.unwrap().expires_at.is_some());
+        assert!(sm.get(forever).await.unwrap().expires_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn stale_memory_decays_by_elapsed_time_then_fades() {
+        let (db, sm) = setup().await;
+        let stale = insert(&sm, "old preference", None).await;
+        let ancient = insert(&sm, "ancient preference", None).await;
+        let fresh = insert(&sm, "new preference", None).await;
+        age(&db, stale, 60).await;
+        age(&db, ancient, 150).await;
+
+        let policy = DecayPolicy {
+            decay_factor: 0.5,
+            decay_period: Duration::from_secs(30 * 86_400),
+            min_importance: 0.1,
+            ..Default::default()
+        };
+        // Stale for 30 days: one period, 0.5 -> 0.25.  Stale for 120 days:
+        // four periods, 0.5 -> 0.03125, below the 0.1 floor.
+        let report = sm.decay_and_prune(&policy).await.unwrap();
+        assert_eq!((report.decayed, report.faded), (2, 1));
+        assert!((sm.get(stale).await.unwrap().importance - 0.25).abs() < 1e-3);
+
+        // Running again right away owes no further decay.
+        sm.decay_and_prune(&policy).await.unwrap();
+        sm.decay_and_prune(&policy).await.unwrap();
+        assert!((sm.get(stale).await.unwrap().importance - 0.25).abs() < 1e-3);
+
+        assert_eq!(sm.count(None).await.unwrap(), 2);
+        assert_eq!(sm.get(fresh).await.unwrap().importance, 0.5);
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_policy() {
+        let (_, sm) = setup().await;
+        let policy = DecayPolicy {
+            decay_factor: 1.5,
+            ..Default::default()
+        };
+        assert!(matches!(
+            sm.decay_and_prune(&policy).await,
+            Err(StoreError::InvalidArgument(_))
+        ));
+    }
+}
@@ -0,0 +1,346 @@
+//! Ranked search over semantic memory.
+//!
+//! Text queries run against the `memories_fts` FTS5 index and are ranked by
+//! BM25.  When the caller supplies a query embedding, memories that carry an
+//! embedding are ranked by cosine similarity instead.  Both paths share the
+//! category and creation-date filters.
+//!
+//! Either way the raw relevance is weighted by how recently and how often a
+//! memory has been accessed, so stale memories sink below ones still in use.
+
+use chrono::Utc;
+use serde::Serialize;
+use tracing::instrument;
+
+use super::{Memory, MemoryCategory, SemanticMemory, memory_row};
+use crate::backend::{SqlRow, StorageBackend};
+use crate::error::{StoreError, StoreResult};
+use crate::sql_params;
+
+/// Upper bound on `top_k`, matching the limits used by the list APIs.
+const MAX_TOP_K: u32 = 100;
+
+/// Text matches fetched per requested result, so recency weighting can
+/// promote a memory that BM25 alone would have cut off.
+const RERANK_POOL: u32 = 4;
+
+/// Days of idleness after which a memory's recency bonus has halved.
+const RECENCY_HALF_LIFE_DAYS: f64 = 30.0;
+
+/// Parameters for [`SemanticMemory::search`].
+#[derive(Debug, Clone, Default)]
+pub struct MemoryQuery {
+    /// Free-text query.  Each word is matched as a separate term.
+    pub text: String,
+    /// Query embedding; switches ranking to cosine similarity.
+    pub embedding: Option<Vec<f32>>,
+    /// Only return memories of this category.
+    pub category: Option<MemoryCategory>,
+    /// Only return memories created at or after this Unix timestamp.
+    pub created_after: Option<i64>,
+    /// Only return memories created strictly before this Unix timestamp.
+    pub created_before: Option<i64>,
+    /// Maximum number of results (clamped to 1..=100).
+    pub top_k: u32,
+}
+
+/// A memory together with its relevance to the query.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoredMemory {
+    pub memory: Memory,
+    /// Relevance in `[0, 1]` for text search (higher is better) or cosine
+    /// similarity in `[-1, 1]` for embedding search, scaled by the memory's
+    /// recency and access weight.
+    pub score: f64,
+}
+
+impl<B: StorageBackend> SemanticMemory<B> {
+    /// Search memories, best match first.
+    #[instrument(skip(self, query), fields(top_k = query.top_k, category = ?query.category))]
+    pub async fn search(&self, query: &MemoryQuery) -> StoreResult<Vec<ScoredMemory>> {
+        let query = query.clone();
+        let top_k = query.top_k.clamp(1, MAX_TOP_K);
+        let now = Utc::now().timestamp();
+
+        self.db
+            .with_conn(move |conn| {
+                let category = query.category.map(|c| c.as_str());
+                let Some(embedding) = query.embedding.as_deref() else {
+                    let fts_query = fts_query(&query.text)?;
+                    let rows = conn.query_map(
+                        "SELECT m.id, m.category, m.content, m.embedding, m.importance, \
+                         m.access_count, m.created_at, m.updated_at, m.last_accessed, \
+                         m.expires_at, bm25(memories_fts) \
+                         FROM memories_fts JOIN memories m ON m.id = memories_fts.rowid \
+                         WHERE memories_fts MATCH ?1 \
+                           AND (?2 IS NULL OR m.category = ?2) \
+                           AND (?3 IS NULL OR m.created_at >= ?3) \
+                           AND (?4 IS NULL OR m.created_at < ?4) \
+                         ORDER BY bm25(memories_fts), m.importance DESC LIMIT ?5",
+                        sql_params![
+                            fts_query,
+                            category,
+                            query.created_after,
+                            query.created_before,
+                            top_k * RERANK_POOL
+                        ],
+                        scored_row,
+                    )?;
+                    // BM25 is negative with lower meaning better; map it onto [0, 1).
+                    let scored = rows
+                        .into_iter()
+                        .map(|(memory, bm25)| {
+                            let relevance = (-bm25).max(0.0);
+                            let score =
+                                relevance / (1.0 + relevance) * recency_weight(&memory, now);
+                            ScoredMemory { memory, score }
+                        })
+                        .collect();
+                    return Ok(rank(scored, top_k));
+                };
+
+                let rows = conn.query_map(
+                    "SELECT id, category, content, embedding, importance, access_count, \
+                     created_at, updated_at, last_accessed, expires_at, 0.0 FROM memories \
+                     WHERE embedding IS NOT NULL \
+                       AND (?1 IS NULL OR category = ?1) \
+                       AND (?2 IS NULL OR created_at >= ?2) \
+                       AND (?3 IS NULL OR created_at < ?3)",
+                    sql_params![category, query.created_after, query.created_before],
+                    scored_row,
+                )?;
+
+                let mut scored = Vec::with_capacity(rows.len());
+                for (memory, _) in rows {
+                    let score = memory
+                        .embedding
+                        .as_deref()
+                        .map(|e| cosine_similarity(embedding, e))
+                        .unwrap_or(0.0)
+                        * recency_weight(&memory, now);
+                    scored.push(ScoredMemory { memory, score });
+                }
+                Ok(rank(scored, top_k))
+            })
+            .await
+    }
+}
+
+/// Build an FTS5 query that ORs the query's words, each quoted so user input
+/// can never be parsed as FTS syntax.
+fn fts_query(text: &str) -> StoreResult<String> {
+    let terms: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| format!("\"{t}\""))
+        .collect();
+    if terms.is_empty() {
+        return Err(StoreError::InvalidArgument(
+            "search query must contain at least one word".into(),
+        ));
+    }
+    Ok(terms.join(" OR "))
+}
+
+/// Cosine similarity of two vectors; 0 for mismatched or zero-length input.
+pub(super) fn cosine_similarity(a: &[f32], b: &[f32]) -> f64 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let (mut dot, mut norm_a, mut norm_b) = (0.0_f64, 0.0_f64, 0.0_f64);
+    for (x, y) in a.iter().zip(b) {
+        let (x, y) = (f64::from(*x), f64::from(*y));
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a.sqrt() * norm_b.sqrt())
+}
+
+/// Weight in `[0.6, 1]` favouring recently and frequently accessed memories.
+///
+/// Recency halves every [`RECENCY_HALF_LIFE_DAYS`] of idleness; the access
+/// bonus saturates as the count grows.
+fn recency_weight(memory: &Memory, now: i64) -> f64 {
+    let idle_days = (now - memory.last_accessed).max(0) as f64 / 86_400.0;
+    let recency = 0.5_f64.powf(idle_days / RECENCY_HALF_LIFE_DAYS);
+    let usage = 1.0 - 1.0 / (1.0 + memory.access_count.max(0) as f64);
+    0.6 + 0.3 * recency + 0.1 * usage
+}
+
+/// Sort best first and keep the top `top_k`.
+fn rank(mut scored: Vec<ScoredMemory>, top_k: u32) -> Vec<ScoredMemory> {
+    scored.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(b.memory.importance.total_cmp(&a.memory.importance))
+    });
+    scored.truncate(top_k as usize);
+    scored
+}
+
+/// Read a memory row followed by a raw score column.
+fn scored_row(row: &SqlRow) -> StoreResult<(Memory, f64)> {
+    Ok((memory_row(row)?, row.get(10)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use crate::memory::NewMemory;
+
+    async fn setup() -> SemanticMemory {
+        let db = Database::open_in_memory().unwrap();
+        db.run_migrations().await.unwrap();
+        SemanticMemory::new(db)
+    }
+
+    async fn insert(sm: &SemanticMemory, category: MemoryCategory, content: &str) -> i64 {
+        sm.insert(NewMemory {
+            category,
+            content: content.into(),
+            embedding: None,
+            importance: 0.5,
+            ttl: None,
+        })
+        .await
+        .unwrap()
+    }
+
+    #[test]
+    fn fts_query_quotes_terms() {
+        assert_eq!(
+            fts_query("rust \"OR\" NEAR(x)").unwrap(),
+            "\"rust\" OR \"OR\" OR \"NEAR\" OR \"x\""
+        );
+        assert!(fts_query("  ?! ").is_err());
+    }
+
+    #[test]
+    fn cosine_similarity_bounds() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-9);
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]).abs() < 1e-9);
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 2.0]), 0.0);
+    }
+
+    #[tokio::test]
+    async fn text_search_ranks_better_matches_first() {
+        let sm = setup().await;
+        insert(&sm, MemoryCategory::Knowledge, "tokio runtime basics").await;
+        let best = insert(
+            &sm,
+            MemoryCategory::Knowledge,
+            "tokio async runtime tuning for rust services",
+        )
+        .await;
+        insert(&sm, MemoryCategory::Knowledge, "gardening tips").await;
+
+        let results = sm
+            .search(&MemoryQuery {
+                text: "rust async runtime".into(),
+                top_k: 10,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].memory.id, best);
+        assert!(results[0].score > results[1].score);
+        assert!(results.iter().all(|r| r.score > 0.0 && r.score < 1.0));
+    }
+
+    #[tokio::test]
+    async fn filters_by_date_range_and_follows_deletes() {
+        let sm = setup().await;
+        let id = insert(&sm, MemoryCategory::Pattern, "deploys happen on friday").await;
+        let created_at = sm.get(id).await.unwrap().created_at;
+
+        let query = |after: Option<i64>, before: Option<i64>| MemoryQuery {
+            text: "friday".into(),
+            created_after: after,
+            created_before: before,
+            top_k: 5,
+            ..Default::default()
+        };
+        assert_eq!(
+            sm.search(&query(Some(created_at), None))
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(
+            sm.search(&query(None, Some(created_at)))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        sm.delete(id).await.unwrap();
+        assert!(sm.search(&query(None, None)).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn frequently_accessed_memory_outranks_stale_one() {
+        let sm = setup().await;
+        let stale = insert(&sm, MemoryCategory::Knowledge, "deploy checklist").await;
+        let used = insert(&sm, MemoryCategory::Knowledge, "deploy checklist").await;
+        for _ in 0..5 {
+            sm.get(used).await.unwrap();
+        }
+        let last_accessed = Utc::now().timestamp() - 90 * 86_400;
+        sm.db
+            .with_conn(move |conn| {
+                conn.execute(
+                    "UPDATE memories SET last_accessed = ?2 WHERE id = ?1",
+                    sql_params![stale, last_accessed],
+                )
+            })
+            .await
+            .unwrap();
+
+        let results = sm
+            .search(&MemoryQuery {
+                text: "deploy".into(),
+                top_k: 2,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].memory.id, used);
+        assert!(results[0].score > results[1].score);
+    }
+
+    #[tokio::test]
+    async fn embedding_search_uses_cosine_similarity() {
+        let sm = setup().await;
+        for (content, embedding) in [("north", vec![0.0, 1.0]), ("east", vec![1.0, 0.0])] {
+            sm.insert(NewMemory {
+                category: MemoryCategory::Knowledge,
+                content: content.into(),
+                embedding: Some(embedding),
+                importance: 0.5,
+                ttl: None,
+            })
+            .await
+            .unwrap();
+        }
+
+        let results = sm
+            .search(&MemoryQuery {
+                embedding: Some(vec![0.9, 0.1]),
+                top_k: 1,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].memory.content, "east");
+    }
+}
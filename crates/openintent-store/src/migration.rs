@@ -170,6 +170,131 @@ static MIGRATIONS: &[Migration] = &[
             );
         "#,
     },
+    Migration {
+        version: 6,
+        description: "cron_jobs — persistent recurring jobs for the cron adapter",
+        sql: r#"
+            CREATE TABLE cron_jobs (
+                id          TEXT PRIMARY KEY,
+                name        TEXT NOT NULL,
+                schedule    TEXT NOT NULL,
+                command     TEXT NOT NULL,
+                enabled     INTEGER NOT NULL DEFAULT 1,
+                created_at  INTEGER NOT NULL,
+                last_run    INTEGER,
+                next_run    INTEGER
+            );
+            CREATE INDEX idx_cron_jobs_next_run ON cron_jobs(next_run);
+        "#,
+    },
+    Migration {
+        version: 7,
+        description: "cron_jobs.timezone — IANA zone each schedule is evaluated in",
+        sql: r#"
+            ALTER TABLE cron_jobs ADD COLUMN timezone TEXT NOT NULL DEFAULT 'UTC';
+        "#,
+    },
+    Migration {
+        version: 8,
+        description: "memories_fts — FTS5 index over memory content, kept in sync by triggers",
+        sql: r#"
+            CREATE VIRTUAL TABLE memories_fts USING fts5(
+                content,
+                content = 'memories',
+                content_rowid = 'id',
+                tokenize = 'unicode61 remove_diacritics 2'
+            );
+            INSERT INTO memories_fts(memories_fts) VALUES ('rebuild');
+
+            CREATE TRIGGER memories_fts_insert AFTER INSERT ON memories BEGIN
+                INSERT INTO memories_fts(rowid, content) VALUES (new.id, new.content);
+            END;
+            CREATE TRIGGER memories_fts_delete AFTER DELETE ON memories BEGIN
+                INSERT INTO memories_fts(memories_fts, rowid, content)
+                VALUES ('delete', old.id, old.content);
+            END;
+            CREATE TRIGGER memories_fts_update AFTER UPDATE OF content ON memories BEGIN
+                INSERT INTO memories_fts(memories_fts, rowid, content)
+                VALUES ('delete', old.id, old.content);
+                INSERT INTO memories_fts(rowid, content) VALUES (new.id, new.content);
+            END;
+        "#,
+    },
+    Migration {
+        version: 9,
+        description: "memory_merges — provenance of memories folded into a canonical entry",
+        sql: r#"
+            CREATE TABLE memory_merges (
+                id            INTEGER PRIMARY KEY AUTOINCREMENT,
+                canonical_id  INTEGER NOT NULL REFERENCES memories(id) ON DELETE CASCADE,
+                source_id     INTEGER NOT NULL,
+                content       TEXT NOT NULL,
+                importance    REAL NOT NULL,
+                created_at    INTEGER NOT NULL,
+                merged_at     INTEGER NOT NULL
+            );
+            CREATE INDEX idx_memory_merges_canonical ON memory_merges(canonical_id);
+        "#,
+    },
+    Migration {
+        version: 10,
+        description: "workflow_runs — checkpoints of in-progress and paused workflow runs",
+        sql: r#"
+            CREATE TABLE workflow_runs (
+                id           TEXT PRIMARY KEY,
+                workflow_id  TEXT NOT NULL,
+                status       TEXT NOT NULL,
+                checkpoint   TEXT NOT NULL,
+                created_at   INTEGER NOT NULL,
+                updated_at   INTEGER NOT NULL
+            );
+            CREATE INDEX idx_workflow_runs_status ON workflow_runs(status);
+        "#,
+    },
+    Migration {
+        version: 11,
+        description: "memories.last_accessed / expires_at — recency tracking and TTL for semantic memory",
+        sql: r#"
+            ALTER TABLE memories ADD COLUMN last_accessed INTEGER;
+            ALTER TABLE memories ADD COLUMN expires_at INTEGER;
+            UPDATE memories SET last_accessed = updated_at;
+            CREATE INDEX idx_memories_expires ON memories(expires_at);
+        "#,
+    },
+    Migration {
+        version: 12,
+        description: "tool_audit — append-only record of every tool call the agent made",
+        sql: r#"
+            CREATE TABLE tool_audit (
+                id           INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id   TEXT,
+                task_id      TEXT NOT NULL,
+                tool_name    TEXT NOT NULL,
+                adapter_id   TEXT,
+                arguments    TEXT NOT NULL,
+                success      INTEGER NOT NULL,
+                result       TEXT NOT NULL,
+                duration_ms  INTEGER NOT NULL,
+                created_at   INTEGER NOT NULL
+            );
+            CREATE INDEX idx_tool_audit_session ON tool_audit(session_id);
+            CREATE INDEX idx_tool_audit_task ON tool_audit(task_id);
+        "#,
+    },
+    Migration {
+        version: 13,
+        description: "session_messages.encrypted — whether content is sealed with the session key",
+        sql: r#"
+            ALTER TABLE session_messages ADD COLUMN encrypted INTEGER NOT NULL DEFAULT 0;
+        "#,
+    },
+    Migration {
+        version: 14,
+        description: "memories.last_decayed_at — how far importance decay has been applied",
+        sql: r#"
+            ALTER TABLE memories ADD COLUMN last_decayed_at INTEGER;
+        "#,
+    },
 ];
 
 // ── public API ───────────────────────────────────────────────────────
@@ -320,7 +445,7 @@ mod tests {
     }
 
     /// The expected latest migration version (update when adding migrations).
-    const LATEST_VERSION: u32 = 5;
+    const LATEST_VERSION: u32 = 14;
 
     #[test]
     fn run_all_on_fresh_db() {
@@ -372,6 +497,13 @@ mod tests {
         // v4 tables
         assert!(tables.contains(&"dev_tasks".to_string()));
         assert!(tables.contains(&"dev_task_messages".to_string()));
+        // v6 tables
+        assert!(tables.contains(&"cron_jobs".to_string()));
+        // v8 tables
+        assert!(tables.contains(&"memories_fts".to_string()));
+        // v9 tables
+        assert!(tables.contains(&"memory_merges".to_string()));
+        assert!(tables.contains(&"workflow_runs".to_string()));
     }
 
     #[test]
@@ -4,14 +4,32 @@
 //! messages. Each session tracks the model used, message count, and
 //! approximate token usage. Messages within a session are ordered by
 //! creation time and can be compacted via summarization.
+//!
+//! Message content can optionally be encrypted at rest with AES-256-GCM
+//! (see [`SessionStore::with_encryption`]).  Encryption is transparent to
+//! callers: content is sealed on insert and opened on read, and each row's
+//! `encrypted` column records which, so rows written before encryption was
+//! enabled stay readable.  Because the database only sees ciphertext,
+//! [`SessionStore::search_messages`] falls back to decrypting and filtering
+//! messages in memory when a key is set.
+
+use std::sync::Arc;
 
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, instrument};
 use uuid::Uuid;
 
+use crate::backend::{SqlRow, StorageBackend};
 use crate::db::Database;
 use crate::error::{StoreError, StoreResult};
+use crate::sql_params;
+
+mod crypto;
+
+pub use crypto::SESSION_KEY_LEN;
+
+use self::crypto::ContentCipher;
 
 // ═══════════════════════════════════════════════════════════════════════
 //  Types
@@ -61,14 +79,32 @@ pub struct SessionMessage {
 
 /// CRUD operations on conversation sessions and their messages.
 #[derive(Clone)]
-pub struct SessionStore {
-    db: Database,
+pub struct SessionStore<B = Database> {
+    db: B,
+    /// Set when message content is encrypted at rest.
+    cipher: Option<Arc<ContentCipher>>,
 }
 
-impl SessionStore {
+impl<B: StorageBackend> SessionStore<B> {
     /// Create a new session store backed by `db`.
-    pub fn new(db: Database) -> Self {
-        Self { db }
+    pub fn new(db: B) -> Self {
+        Self { db, cipher: None }
+    }
+
+    /// Encrypt message content at rest with the [`SESSION_KEY_LEN`]-byte
+    /// `key`.
+    ///
+    /// The key usually comes from the credential vault.  Messages stored
+    /// without encryption remain readable; messages stored with a key cannot
+    /// be read without it.
+    pub fn with_encryption(mut self, key: &[u8]) -> StoreResult<Self> {
+        self.cipher = Some(Arc::new(ContentCipher::new(key)?));
+        Ok(self)
+    }
+
+    /// Whether message content is encrypted at rest.
+    pub fn is_encrypted(&self) -> bool {
+        self.cipher.is_some()
     }
 
     /// Create a new session with the given name and model.
@@ -90,11 +126,11 @@ impl SessionStore {
         };
 
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 conn.execute(
                     "INSERT INTO sessions (id, name, model, message_count, token_count, created_at, updated_at) \
                      VALUES (?1, ?2, ?3, 0, 0, ?4, ?4)",
-                    rusqlite::params![id, name, model, now],
+                    sql_params![id, name, model, now],
                 )?;
                 Ok(())
             })
@@ -109,29 +145,16 @@ impl SessionStore {
     pub async fn get(&self, id: &str) -> StoreResult<Session> {
         let id = id.to_string();
         self.db
-            .execute(move |conn| {
-                conn.query_row(
+            .with_conn(move |conn| {
+                conn.query_opt(
                     "SELECT id, name, model, message_count, token_count, created_at, updated_at \
                      FROM sessions WHERE id = ?1",
-                    rusqlite::params![id],
-                    |row| {
-                        Ok(Session {
-                            id: row.get(0)?,
-                            name: row.get(1)?,
-                            model: row.get(2)?,
-                            message_count: row.get(3)?,
-                            token_count: row.get(4)?,
-                            created_at: row.get(5)?,
-                            updated_at: row.get(6)?,
-                        })
-                    },
-                )
-                .map_err(|e| match e {
-                    rusqlite::Error::QueryReturnedNoRows => StoreError::NotFound {
-                        entity: "session",
-                        id: id.clone(),
-                    },
-                    other => StoreError::Sqlite(other),
+                    sql_params![id],
+                    session_row,
+                )?
+                .ok_or(StoreError::NotFound {
+                    entity: "session",
+                    id,
                 })
             })
             .await
@@ -141,25 +164,13 @@ impl SessionStore {
     #[instrument(skip(self))]
     pub async fn list(&self, limit: u32, offset: u32) -> StoreResult<Vec<Session>> {
         self.db
-            .execute(move |conn| {
-                let mut stmt = conn.prepare(
+            .with_conn(move |conn| {
+                conn.query_map(
                     "SELECT id, name, model, message_count, token_count, created_at, updated_at \
                      FROM sessions ORDER BY updated_at DESC LIMIT ?1 OFFSET ?2",
-                )?;
-                let rows = stmt
-                    .query_map(rusqlite::params![limit, offset], |row| {
-                        Ok(Session {
-                            id: row.get(0)?,
-                            name: row.get(1)?,
-                            model: row.get(2)?,
-                            message_count: row.get(3)?,
-                            token_count: row.get(4)?,
-                            created_at: row.get(5)?,
-                            updated_at: row.get(6)?,
-                        })
-                    })?
-                    .collect::<Result<Vec<_>, _>>()?;
-                Ok(rows)
+                    sql_params![limit, offset],
+                    session_row,
+                )
             })
             .await
     }
@@ -168,30 +179,102 @@ impl SessionStore {
     #[instrument(skip(self))]
     pub async fn get_latest(&self) -> StoreResult<Option<Session>> {
         self.db
-            .execute(move |conn| {
-                let result = conn.query_row(
+            .with_conn(move |conn| {
+                conn.query_opt(
                     "SELECT id, name, model, message_count, token_count, created_at, updated_at \
                      FROM sessions ORDER BY updated_at DESC LIMIT 1",
-                    [],
-                    |row| {
-                        Ok(Session {
-                            id: row.get(0)?,
-                            name: row.get(1)?,
-                            model: row.get(2)?,
-                            message_count: row.get(3)?,
-                            token_count: row.get(4)?,
-                            created_at: row.get(5)?,
-                            updated_at: row.get(6)?,
-                        })
+                    sql_params![],
+                    session_row,
+                )
+            })
+            .await
+    }
+
+    /// Find the most recently updated session named `name`, if any.
+    #[instrument(skip(self))]
+    pub async fn find_by_name(&self, name: &str) -> StoreResult<Option<Session>> {
+        let name = name.to_string();
+        self.db
+            .with_conn(move |conn| {
+                conn.query_opt(
+                    "SELECT id, name, model, message_count, token_count, created_at, updated_at \
+                     FROM sessions WHERE name = ?1 ORDER BY updated_at DESC LIMIT 1",
+                    sql_params![name],
+                    session_row,
+                )
+            })
+            .await
+    }
+
+    /// Recreate an exported session under `name` with a fresh ID.
+    ///
+    /// The session's model, token count, and timestamps are preserved, as
+    /// are each message's role, content, tool data, and timestamp.  The
+    /// session and its messages are inserted in one transaction.
+    #[instrument(skip(self, session, messages), fields(messages = messages.len()))]
+    pub async fn import(
+        &self,
+        name: &str,
+        session: &Session,
+        messages: &[SessionMessage],
+    ) -> StoreResult<Session> {
+        let imported = Session {
+            id: Uuid::now_v7().to_string(),
+            name: name.to_string(),
+            message_count: messages.len() as i64,
+            ..session.clone()
+        };
+        let messages = messages
+            .iter()
+            .map(|msg| {
+                let (content, encrypted) = self.seal(&imported.id, &msg.content)?;
+                Ok((
+                    SessionMessage {
+                        content,
+                        ..msg.clone()
                     },
-                );
-                match result {
-                    Ok(session) => Ok(Some(session)),
-                    Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-                    Err(e) => Err(StoreError::Sqlite(e)),
+                    encrypted,
+                ))
+            })
+            .collect::<StoreResult<Vec<_>>>()?;
+
+        let row = imported.clone();
+        self.db
+            .transaction(move |tx| {
+                tx.execute(
+                    "INSERT INTO sessions (id, name, model, message_count, token_count, created_at, updated_at) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    sql_params![
+                        row.id,
+                        row.name,
+                        row.model,
+                        row.message_count,
+                        row.token_count,
+                        row.created_at,
+                        row.updated_at
+                    ],
+                )?;
+                for (msg, encrypted) in &messages {
+                    tx.execute(
+                        "INSERT INTO session_messages (session_id, role, content, tool_calls, tool_call_id, created_at, encrypted) \
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                        sql_params![
+                            row.id,
+                            msg.role,
+                            msg.content,
+                            msg.tool_calls,
+                            msg.tool_call_id,
+                            msg.created_at,
+                            *encrypted
+                        ],
+                    )?;
                 }
+                Ok(())
             })
-            .await
+            .await?;
+
+        debug!(session_id = %imported.id, "session imported");
+        Ok(imported)
     }
 
     /// Delete a session and all its messages (cascade).
@@ -199,9 +282,9 @@ impl SessionStore {
     pub async fn delete(&self, id: &str) -> StoreResult<()> {
         let id = id.to_string();
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 let deleted =
-                    conn.execute("DELETE FROM sessions WHERE id = ?1", rusqlite::params![id])?;
+                    conn.execute("DELETE FROM sessions WHERE id = ?1", sql_params![id])?;
                 if deleted == 0 {
                     return Err(StoreError::NotFound {
                         entity: "session",
@@ -226,25 +309,25 @@ impl SessionStore {
         tool_calls: Option<&str>,
         tool_call_id: Option<&str>,
     ) -> StoreResult<i64> {
+        let (content, encrypted) = self.seal(session_id, content)?;
         let session_id = session_id.to_string();
         let role = role.to_string();
-        let content = content.to_string();
         let tool_calls = tool_calls.map(|s| s.to_string());
         let tool_call_id = tool_call_id.map(|s| s.to_string());
         let now = Utc::now().timestamp();
 
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 conn.execute(
-                    "INSERT INTO session_messages (session_id, role, content, tool_calls, tool_call_id, created_at) \
-                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                    rusqlite::params![session_id, role, content, tool_calls, tool_call_id, now],
+                    "INSERT INTO session_messages (session_id, role, content, tool_calls, tool_call_id, created_at, encrypted) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    sql_params![session_id, role, content, tool_calls, tool_call_id, now, encrypted],
                 )?;
-                let msg_id = conn.last_insert_rowid();
+                let msg_id = conn.last_insert_id();
 
                 conn.execute(
                     "UPDATE sessions SET message_count = message_count + 1, updated_at = ?2 WHERE id = ?1",
-                    rusqlite::params![session_id, now],
+                    sql_params![session_id, now],
                 )?;
 
                 Ok(msg_id)
@@ -263,51 +346,83 @@ impl SessionStore {
         limit: Option<u32>,
     ) -> StoreResult<Vec<SessionMessage>> {
         let session_id = session_id.to_string();
-        self.db
-            .execute(move |conn| {
-                let messages = match limit {
-                    Some(n) => {
-                        // Subquery to get the most recent N, then re-order ascending.
-                        let mut stmt = conn.prepare(
-                            "SELECT id, session_id, role, content, tool_calls, tool_call_id, created_at \
-                             FROM (SELECT * FROM session_messages WHERE session_id = ?1 ORDER BY created_at DESC, id DESC LIMIT ?2) \
-                             ORDER BY created_at ASC, id ASC",
-                        )?;
-                        stmt.query_map(rusqlite::params![session_id, n], |row| {
-                            Ok(SessionMessage {
-                                id: row.get(0)?,
-                                session_id: row.get(1)?,
-                                role: row.get(2)?,
-                                content: row.get(3)?,
-                                tool_calls: row.get(4)?,
-                                tool_call_id: row.get(5)?,
-                                created_at: row.get(6)?,
-                            })
-                        })?
-                        .collect::<Result<Vec<_>, _>>()?
-                    }
-                    None => {
-                        let mut stmt = conn.prepare(
-                            "SELECT id, session_id, role, content, tool_calls, tool_call_id, created_at \
-                             FROM session_messages WHERE session_id = ?1 ORDER BY created_at ASC, id ASC",
-                        )?;
-                        stmt.query_map(rusqlite::params![session_id], |row| {
-                            Ok(SessionMessage {
-                                id: row.get(0)?,
-                                session_id: row.get(1)?,
-                                role: row.get(2)?,
-                                content: row.get(3)?,
-                                tool_calls: row.get(4)?,
-                                tool_call_id: row.get(5)?,
-                                created_at: row.get(6)?,
-                            })
-                        })?
-                        .collect::<Result<Vec<_>, _>>()?
-                    }
-                };
-                Ok(messages)
+        let messages = self
+            .db
+            .with_conn(move |conn| {
+                match limit {
+                    // Subquery to get the most recent N, then re-order ascending.
+                    Some(n) => conn.query_map(
+                        "SELECT id, session_id, role, content, tool_calls, tool_call_id, created_at, encrypted \
+                         FROM (SELECT * FROM session_messages WHERE session_id = ?1 ORDER BY created_at DESC, id DESC LIMIT ?2) \
+                         ORDER BY created_at ASC, id ASC",
+                        sql_params![session_id, n],
+                        message_row,
+                    ),
+                    None => conn.query_map(
+                        "SELECT id, session_id, role, content, tool_calls, tool_call_id, created_at, encrypted \
+                         FROM session_messages WHERE session_id = ?1 ORDER BY created_at ASC, id ASC",
+                        sql_params![session_id],
+                        message_row,
+                    ),
+                }
             })
-            .await
+            .await?;
+        self.open_all(messages)
+    }
+
+    /// Find messages whose content contains `query`, newest first.
+    ///
+    /// Matching ignores ASCII case only, like SQLite's `LIKE`, and treats
+    /// `%` and `_` in `query` literally.  Plaintext stores match in SQL.
+    /// When content is encrypted the database cannot see it, so every
+    /// message is decrypted and filtered in memory instead, which is slower
+    /// on large histories.
+    #[instrument(skip(self))]
+    pub async fn search_messages(
+        &self,
+        query: &str,
+        limit: u32,
+    ) -> StoreResult<Vec<SessionMessage>> {
+        if self.cipher.is_none() {
+            let pattern = format!("%{}%", escape_like(query));
+            let messages = self
+                .db
+                .with_conn(move |conn| {
+                    conn.query_map(
+                        "SELECT id, session_id, role, content, tool_calls, tool_call_id, created_at, encrypted \
+                         FROM session_messages WHERE encrypted = 0 AND content LIKE ?1 ESCAPE '\\' \
+                         ORDER BY created_at DESC, id DESC LIMIT ?2",
+                        sql_params![pattern, limit],
+                        message_row,
+                    )
+                })
+                .await?;
+            return self.open_all(messages);
+        }
+
+        let messages = self
+            .db
+            .with_conn(move |conn| {
+                conn.query_map(
+                    "SELECT id, session_id, role, content, tool_calls, tool_call_id, created_at, encrypted \
+                     FROM session_messages ORDER BY created_at DESC, id DESC",
+                    sql_params![],
+                    message_row,
+                )
+            })
+            .await?;
+        let needle = query.to_ascii_lowercase();
+        let mut found = Vec::new();
+        for row in messages {
+            let msg = self.open(row)?;
+            if msg.content.to_ascii_lowercase().contains(&needle) {
+                found.push(msg);
+                if found.len() >= limit as usize {
+                    break;
+                }
+            }
+        }
+        Ok(found)
     }
 
     /// Get the message count for a session.
@@ -315,10 +430,10 @@ impl SessionStore {
     pub async fn get_message_count(&self, session_id: &str) -> StoreResult<i64> {
         let session_id = session_id.to_string();
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 let count: i64 = conn.query_row(
                     "SELECT COUNT(*) FROM session_messages WHERE session_id = ?1",
-                    rusqlite::params![session_id],
+                    sql_params![session_id],
                     |row| row.get(0),
                 )?;
                 Ok(count)
@@ -332,10 +447,10 @@ impl SessionStore {
         let session_id = session_id.to_string();
         let now = Utc::now().timestamp();
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 let updated = conn.execute(
                     "UPDATE sessions SET token_count = ?2, updated_at = ?3 WHERE id = ?1",
-                    rusqlite::params![session_id, tokens, now],
+                    sql_params![session_id, tokens, now],
                 )?;
                 if updated == 0 {
                     return Err(StoreError::NotFound {
@@ -360,12 +475,12 @@ impl SessionStore {
         summary: &str,
         keep_recent: usize,
     ) -> StoreResult<()> {
+        let (summary, encrypted) = self.seal(session_id, summary)?;
         let session_id = session_id.to_string();
-        let summary = summary.to_string();
         let now = Utc::now().timestamp();
 
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 // Find the cutoff: get the ID of the message at position `keep_recent`
                 // from the end. Everything before that gets deleted.
                 let keep_recent_i64 = keep_recent as i64;
@@ -377,10 +492,9 @@ impl SessionStore {
                             SELECT id FROM session_messages WHERE session_id = ?1 \
                             ORDER BY created_at DESC, id DESC LIMIT ?2\
                         )",
-                        rusqlite::params![session_id, keep_recent_i64],
+                        sql_params![session_id, keep_recent_i64],
                         |row| row.get(0),
-                    )
-                    .map_err(StoreError::from)?;
+                    )?;
 
                 let cutoff_id = match cutoff_id {
                     Some(id) => id,
@@ -390,33 +504,33 @@ impl SessionStore {
                 // Delete all messages older than the cutoff.
                 conn.execute(
                     "DELETE FROM session_messages WHERE session_id = ?1 AND id < ?2",
-                    rusqlite::params![session_id, cutoff_id],
+                    sql_params![session_id, cutoff_id],
                 )?;
 
                 // Insert the summary as a system message with a timestamp just before
                 // the earliest remaining message so it sorts first.
                 let earliest_ts: i64 = conn.query_row(
                     "SELECT MIN(created_at) FROM session_messages WHERE session_id = ?1",
-                    rusqlite::params![session_id],
+                    sql_params![session_id],
                     |row| row.get(0),
                 )?;
                 let summary_ts = earliest_ts - 1;
 
                 conn.execute(
-                    "INSERT INTO session_messages (session_id, role, content, tool_calls, tool_call_id, created_at) \
-                     VALUES (?1, 'system', ?2, NULL, NULL, ?3)",
-                    rusqlite::params![session_id, summary, summary_ts],
+                    "INSERT INTO session_messages (session_id, role, content, tool_calls, tool_call_id, created_at, encrypted) \
+                     VALUES (?1, 'system', ?2, NULL, NULL, ?3, ?4)",
+                    sql_params![session_id, summary, summary_ts, encrypted],
                 )?;
 
                 // Update the session's message_count to reflect reality.
                 let new_count: i64 = conn.query_row(
                     "SELECT COUNT(*) FROM session_messages WHERE session_id = ?1",
-                    rusqlite::params![session_id],
+                    sql_params![session_id],
                     |row| row.get(0),
                 )?;
                 conn.execute(
                     "UPDATE sessions SET message_count = ?2, updated_at = ?3 WHERE id = ?1",
-                    rusqlite::params![session_id, new_count, now],
+                    sql_params![session_id, new_count, now],
                 )?;
 
                 debug!(
@@ -429,360 +543,79 @@ impl SessionStore {
             })
             .await
     }
-}
-
-// ── tests ────────────────────────────────────────────────────────────
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    /// Create an in-memory database with session tables for testing.
-    async fn setup_db() -> Database {
-        let db = Database::open_in_memory()
-            .map_err(|e| panic!("failed to open db: {e}"))
-            .unwrap();
-        db.run_migrations().await.unwrap();
-
-        // Create session tables manually (migration v2 may not be applied
-        // in the test migration set yet, so we create them explicitly).
-        db.execute(|conn| {
-            conn.execute_batch(
-                "CREATE TABLE IF NOT EXISTS sessions (
-                    id TEXT PRIMARY KEY,
-                    name TEXT NOT NULL,
-                    model TEXT NOT NULL DEFAULT '',
-                    message_count INTEGER DEFAULT 0,
-                    token_count INTEGER DEFAULT 0,
-                    created_at INTEGER NOT NULL,
-                    updated_at INTEGER NOT NULL
-                );
-                CREATE TABLE IF NOT EXISTS session_messages (
-                    id INTEGER PRIMARY KEY AUTOINCREMENT,
-                    session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
-                    role TEXT NOT NULL,
-                    content TEXT NOT NULL,
-                    tool_calls TEXT,
-                    tool_call_id TEXT,
-                    created_at INTEGER NOT NULL
-                );
-                CREATE INDEX IF NOT EXISTS idx_session_messages_session ON session_messages(session_id);",
-            )?;
-            Ok(())
-        })
-        .await
-        .unwrap();
-
-        db
-    }
 
-    #[tokio::test]
-    async fn create_and_get_session() {
-        let db = setup_db().await;
-        let store = SessionStore::new(db);
-
-        let session = store.create("test session", "gpt-4").await.unwrap();
-        assert_eq!(session.name, "test session");
-        assert_eq!(session.model, "gpt-4");
-        assert_eq!(session.message_count, 0);
-        assert_eq!(session.token_count, 0);
-
-        let fetched = store.get(&session.id).await.unwrap();
-        assert_eq!(fetched.id, session.id);
-        assert_eq!(fetched.name, "test session");
-        assert_eq!(fetched.model, "gpt-4");
-    }
-
-    #[tokio::test]
-    async fn get_nonexistent_session_returns_not_found() {
-        let db = setup_db().await;
-        let store = SessionStore::new(db);
+    // ── content encryption ───────────────────────────────────────────
 
-        let result = store.get("nonexistent-id").await;
-        assert!(result.is_err());
-        match result.unwrap_err() {
-            StoreError::NotFound { entity, .. } => assert_eq!(entity, "session"),
-            other => panic!("expected NotFound, got: {other}"),
+    /// Encrypt `content` for storage in `session_id`, if encryption is on.
+    /// Returns the value to store and whether it is encrypted.
+    fn seal(&self, session_id: &str, content: &str) -> StoreResult<(String, bool)> {
+        match &self.cipher {
+            Some(cipher) => Ok((cipher.encrypt(session_id, content)?, true)),
+            None => Ok((content.to_string(), false)),
         }
     }
 
-    #[tokio::test]
-    async fn list_sessions_with_pagination() {
-        let db = setup_db().await;
-        let store = SessionStore::new(db);
-
-        store.create("session 1", "model-a").await.unwrap();
-        store.create("session 2", "model-b").await.unwrap();
-        store.create("session 3", "model-c").await.unwrap();
-
-        let all = store.list(10, 0).await.unwrap();
-        assert_eq!(all.len(), 3);
-
-        let page = store.list(2, 0).await.unwrap();
-        assert_eq!(page.len(), 2);
-
-        let page2 = store.list(2, 2).await.unwrap();
-        assert_eq!(page2.len(), 1);
-    }
-
-    #[tokio::test]
-    async fn get_latest_session() {
-        let db = setup_db().await;
-        let store = SessionStore::new(db);
-
-        let none = store.get_latest().await.unwrap();
-        assert!(none.is_none());
-
-        let first = store.create("first", "model").await.unwrap();
-        let second = store.create("second", "model").await.unwrap();
-
-        let latest = store.get_latest().await.unwrap();
-        assert!(latest.is_some());
-        let latest = latest.unwrap();
-        // Both sessions may share the same updated_at (second-precision),
-        // so either could be returned as "latest". Just verify it is one
-        // of the sessions we created.
-        assert!(
-            latest.id == first.id || latest.id == second.id,
-            "expected latest to be one of the created sessions"
-        );
-    }
-
-    #[tokio::test]
-    async fn delete_session() {
-        let db = setup_db().await;
-        let store = SessionStore::new(db);
-
-        let session = store.create("to delete", "model").await.unwrap();
-        store.delete(&session.id).await.unwrap();
-
-        let result = store.get(&session.id).await;
-        assert!(result.is_err());
-    }
-
-    #[tokio::test]
-    async fn delete_nonexistent_session_returns_not_found() {
-        let db = setup_db().await;
-        let store = SessionStore::new(db);
-
-        let result = store.delete("nonexistent").await;
-        assert!(result.is_err());
-    }
-
-    #[tokio::test]
-    async fn append_and_get_messages() {
-        let db = setup_db().await;
-        let store = SessionStore::new(db);
-
-        let session = store.create("chat", "model").await.unwrap();
-
-        let msg1_id = store
-            .append_message(&session.id, "user", "Hello!", None, None)
-            .await
-            .unwrap();
-        assert!(msg1_id > 0);
-
-        let msg2_id = store
-            .append_message(
-                &session.id,
-                "assistant",
-                "Hi there!",
-                Some(r#"[{"name":"greet"}]"#),
-                None,
-            )
-            .await
-            .unwrap();
-        assert!(msg2_id > msg1_id);
-
-        let messages = store.get_messages(&session.id, None).await.unwrap();
-        assert_eq!(messages.len(), 2);
-        assert_eq!(messages[0].role, "user");
-        assert_eq!(messages[0].content, "Hello!");
-        assert_eq!(messages[1].role, "assistant");
-        assert_eq!(
-            messages[1].tool_calls.as_deref(),
-            Some(r#"[{"name":"greet"}]"#)
-        );
-
-        // Session message_count should be updated.
-        let updated = store.get(&session.id).await.unwrap();
-        assert_eq!(updated.message_count, 2);
-    }
-
-    #[tokio::test]
-    async fn get_messages_with_limit() {
-        let db = setup_db().await;
-        let store = SessionStore::new(db);
-
-        let session = store.create("chat", "model").await.unwrap();
-
-        for i in 0..5 {
-            store
-                .append_message(&session.id, "user", &format!("msg {i}"), None, None)
-                .await
-                .unwrap();
+    /// Decrypt a message read from the database, if it is encrypted.
+    fn open(&self, (mut msg, encrypted): (SessionMessage, bool)) -> StoreResult<SessionMessage> {
+        if !encrypted {
+            return Ok(msg);
         }
-
-        let recent = store.get_messages(&session.id, Some(3)).await.unwrap();
-        assert_eq!(recent.len(), 3);
-        // Should be the 3 most recent messages, in ascending order.
-        assert_eq!(recent[0].content, "msg 2");
-        assert_eq!(recent[1].content, "msg 3");
-        assert_eq!(recent[2].content, "msg 4");
-    }
-
-    #[tokio::test]
-    async fn get_message_count() {
-        let db = setup_db().await;
-        let store = SessionStore::new(db);
-
-        let session = store.create("chat", "model").await.unwrap();
-        assert_eq!(store.get_message_count(&session.id).await.unwrap(), 0);
-
-        store
-            .append_message(&session.id, "user", "hi", None, None)
-            .await
-            .unwrap();
-        assert_eq!(store.get_message_count(&session.id).await.unwrap(), 1);
+        let cipher = self.cipher.as_ref().ok_or_else(|| {
+            StoreError::Crypto(format!(
+                "message {} is encrypted but no session key is configured",
+                msg.id
+            ))
+        })?;
+        msg.content = cipher.decrypt(&msg.session_id, &msg.content)?;
+        Ok(msg)
     }
 
-    #[tokio::test]
-    async fn update_token_count() {
-        let db = setup_db().await;
-        let store = SessionStore::new(db);
-
-        let session = store.create("chat", "model").await.unwrap();
-        assert_eq!(session.token_count, 0);
-
-        store.update_token_count(&session.id, 1500).await.unwrap();
-
-        let updated = store.get(&session.id).await.unwrap();
-        assert_eq!(updated.token_count, 1500);
-    }
-
-    #[tokio::test]
-    async fn update_token_count_nonexistent_session() {
-        let db = setup_db().await;
-        let store = SessionStore::new(db);
-
-        let result = store.update_token_count("nonexistent", 100).await;
-        assert!(result.is_err());
+    fn open_all(&self, rows: Vec<(SessionMessage, bool)>) -> StoreResult<Vec<SessionMessage>> {
+        rows.into_iter().map(|row| self.open(row)).collect()
     }
+}
 
-    #[tokio::test]
-    async fn compact_messages() {
-        let db = setup_db().await;
-        let store = SessionStore::new(db);
-
-        let session = store.create("chat", "model").await.unwrap();
-
-        // Add 10 messages.
-        for i in 0..10 {
-            store
-                .append_message(&session.id, "user", &format!("message {i}"), None, None)
-                .await
-                .unwrap();
+/// Escape `LIKE` wildcards in `text` for use with `ESCAPE '\'`.
+fn escape_like(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
         }
-
-        // Compact: keep 3 recent messages, summarize the rest.
-        store
-            .compact_messages(&session.id, "Summary of first 7 messages", 3)
-            .await
-            .unwrap();
-
-        let messages = store.get_messages(&session.id, None).await.unwrap();
-        // Should be: 1 summary + 3 kept = 4 messages.
-        assert_eq!(messages.len(), 4);
-
-        // First message should be the summary.
-        assert_eq!(messages[0].role, "system");
-        assert_eq!(messages[0].content, "Summary of first 7 messages");
-
-        // Last 3 should be the most recent original messages.
-        assert_eq!(messages[1].content, "message 7");
-        assert_eq!(messages[2].content, "message 8");
-        assert_eq!(messages[3].content, "message 9");
-
-        // Session message_count should reflect the compacted state.
-        let updated = store.get(&session.id).await.unwrap();
-        assert_eq!(updated.message_count, 4);
-    }
-
-    #[tokio::test]
-    async fn compact_empty_session_is_noop() {
-        let db = setup_db().await;
-        let store = SessionStore::new(db);
-
-        let session = store.create("chat", "model").await.unwrap();
-
-        // Compacting an empty session should not fail.
-        store
-            .compact_messages(&session.id, "no-op summary", 5)
-            .await
-            .unwrap();
-
-        let messages = store.get_messages(&session.id, None).await.unwrap();
-        assert!(messages.is_empty());
-    }
-
-    #[tokio::test]
-    async fn tool_result_message_fields() {
-        let db = setup_db().await;
-        let store = SessionStore::new(db);
-
-        let session = store.create("chat", "model").await.unwrap();
-
-        store
-            .append_message(
-                &session.id,
-                "tool_result",
-                r#"{"output":"42"}"#,
-                None,
-                Some("call_abc123"),
-            )
-            .await
-            .unwrap();
-
-        let messages = store.get_messages(&session.id, None).await.unwrap();
-        assert_eq!(messages.len(), 1);
-        assert_eq!(messages[0].role, "tool_result");
-        assert_eq!(messages[0].tool_call_id.as_deref(), Some("call_abc123"));
+        escaped.push(c);
     }
+    escaped
+}
 
-    #[tokio::test]
-    async fn delete_session_cascades_messages() {
-        let db = setup_db().await;
-        let store = SessionStore::new(db);
-
-        let session = store.create("chat", "model").await.unwrap();
-        store
-            .append_message(&session.id, "user", "hello", None, None)
-            .await
-            .unwrap();
-        store
-            .append_message(&session.id, "assistant", "hi", None, None)
-            .await
-            .unwrap();
+/// Map a `sessions` row (in the canonical column order) to a session.
+fn session_row(row: &SqlRow) -> StoreResult<Session> {
+    Ok(Session {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        model: row.get(2)?,
+        message_count: row.get(3)?,
+        token_count: row.get(4)?,
+        created_at: row.get(5)?,
+        updated_at: row.get(6)?,
+    })
+}
 
-        let count_before = store.get_message_count(&session.id).await.unwrap();
-        assert_eq!(count_before, 2);
+/// Map a `session_messages` row (in the canonical column order, followed
+/// by `encrypted`) to a message and whether its content is encrypted.
+fn message_row(row: &SqlRow) -> StoreResult<(SessionMessage, bool)> {
+    let msg = SessionMessage {
+        id: row.get(0)?,
+        session_id: row.get(1)?,
+        role: row.get(2)?,
+        content: row.get(3)?,
+        tool_calls: row.get(4)?,
+        tool_call_id: row.get(5)?,
+        created_at: row.get(6)?,
+    };
+    Ok((msg, row.get(7)?))
+}
 
-        store.delete(&session.id).await.unwrap();
+// ── tests ────────────────────────────────────────────────────────────
 
-        // Messages should be gone too (CASCADE).
-        let session_id = session.id.clone();
-        let orphan_count: i64 = store
-            .db
-            .execute(move |conn| {
-                let count: i64 = conn.query_row(
-                    "SELECT COUNT(*) FROM session_messages WHERE session_id = ?1",
-                    rusqlite::params![session_id],
-                    |row| row.get(0),
-                )?;
-                Ok(count)
-            })
-            .await
-            .unwrap();
-        assert_eq!(orphan_count, 0);
-    }
-}
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,96 @@
+//! AES-256-GCM sealing of session message content.
+//!
+//! Sealed values are `base64(nonce || ciphertext || tag)`.  Whether a row is
+//! sealed is recorded in its `encrypted` column, never in the content
+//! itself, so plaintext content can hold any text.  The session ID is bound
+//! in as associated data, so a sealed message cannot be moved to another
+//! session without failing to decrypt.
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use ring::aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::error::{StoreError, StoreResult};
+
+/// Length in bytes of a session encryption key.
+pub const SESSION_KEY_LEN: usize = 32;
+
+/// Seals and opens message content with one key.
+pub(super) struct ContentCipher {
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl ContentCipher {
+    pub(super) fn new(key: &[u8]) -> StoreResult<Self> {
+        let key = UnboundKey::new(&AES_256_GCM, key).map_err(|_| {
+            StoreError::InvalidArgument(format!(
+                "session encryption key must be {SESSION_KEY_LEN} bytes, got {}",
+                key.len()
+            ))
+        })?;
+        Ok(Self {
+            key: LessSafeKey::new(key),
+            rng: SystemRandom::new(),
+        })
+    }
+
+    pub(super) fn encrypt(&self, session_id: &str, plaintext: &str) -> StoreResult<String> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| StoreError::Crypto("failed to generate nonce".into()))?;
+
+        let mut sealed = plaintext.as_bytes().to_vec();
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(session_id.as_bytes()),
+                &mut sealed,
+            )
+            .map_err(|_| StoreError::Crypto("failed to encrypt message".into()))?;
+
+        let mut blob = nonce.to_vec();
+        blob.extend_from_slice(&sealed);
+        Ok(BASE64.encode(blob))
+    }
+
+    pub(super) fn decrypt(&self, session_id: &str, sealed: &str) -> StoreResult<String> {
+        let mut blob = BASE64
+            .decode(sealed)
+            .map_err(|e| StoreError::Crypto(format!("malformed encrypted message: {e}")))?;
+        if blob.len() < NONCE_LEN {
+            return Err(StoreError::Crypto("encrypted message is truncated".into()));
+        }
+        let (nonce, ciphertext) = blob.split_at_mut(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|_| StoreError::Crypto("invalid nonce".into()))?;
+        let plaintext = self
+            .key
+            .open_in_place(nonce, Aad::from(session_id.as_bytes()), ciphertext)
+            .map_err(|_| {
+                StoreError::Crypto("failed to decrypt message (wrong key or corrupted data)".into())
+            })?;
+        String::from_utf8(plaintext.to_vec())
+            .map_err(|_| StoreError::Crypto("decrypted message is not UTF-8".into()))
+    }
+}
+
+// ── tests ────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sealed_content_is_bound_to_its_session() {
+        let cipher = ContentCipher::new(&[9u8; SESSION_KEY_LEN]).unwrap();
+        let sealed = cipher.encrypt("session-a", "hello").unwrap();
+        assert_eq!(cipher.decrypt("session-a", &sealed).unwrap(), "hello");
+        assert!(matches!(
+            cipher.decrypt("session-b", &sealed),
+            Err(StoreError::Crypto(_))
+        ));
+    }
+}
@@ -0,0 +1,195 @@
+//! Audit trail of the tools the agent invoked.
+//!
+//! The agent runtime appends one entry per tool call, including calls that
+//! were denied, with the adapter's redacted arguments, a preview of the
+//! result and how long the call took.  Entries are never updated.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, instrument};
+
+use crate::backend::{SqlRow, StorageBackend};
+use crate::db::Database;
+use crate::error::StoreResult;
+use crate::sql_params;
+
+/// A tool call to record.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewToolAudit {
+    /// Session the call was made in, if the run belongs to one.
+    pub session_id: Option<String>,
+    /// Agent run (task) the call was made in.
+    pub task_id: String,
+    /// Name of the tool that was called.
+    pub tool_name: String,
+    /// Adapter that owns the tool, if one was found.
+    pub adapter_id: Option<String>,
+    /// Call arguments, with sensitive values already redacted.
+    pub arguments: serde_json::Value,
+    /// Whether the call succeeded.
+    pub success: bool,
+    /// The result or error message, possibly truncated.
+    pub result: String,
+    /// How long the call took, in milliseconds.
+    pub duration_ms: u64,
+}
+
+/// A recorded tool call.  Fields not documented here mirror [`NewToolAudit`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolAuditEntry {
+    /// Auto-incremented ID; entries are recorded in ID order.
+    pub id: i64,
+    pub session_id: Option<String>,
+    pub task_id: String,
+    pub tool_name: String,
+    pub adapter_id: Option<String>,
+    pub arguments: serde_json::Value,
+    pub success: bool,
+    pub result: String,
+    pub duration_ms: u64,
+    /// Unix timestamp when the entry was recorded.
+    pub created_at: i64,
+}
+
+/// Append-only store for the tool-call audit trail.
+#[derive(Clone)]
+pub struct ToolAuditStore<B = Database> {
+    db: B,
+}
+
+impl<B: StorageBackend> ToolAuditStore<B> {
+    /// Create a new audit store backed by `db`.
+    pub fn new(db: B) -> Self {
+        Self { db }
+    }
+
+    /// Record a tool call and return its entry ID.
+    #[instrument(skip(self, entry), fields(tool = %entry.tool_name))]
+    pub async fn record(&self, entry: NewToolAudit) -> StoreResult<i64> {
+        let arguments = serde_json::to_string(&entry.arguments)?;
+        let now = Utc::now().timestamp();
+
+        self.db
+            .with_conn(move |conn| {
+                conn.execute(
+                    "INSERT INTO tool_audit (session_id, task_id, tool_name, adapter_id, arguments, \
+                     success, result, duration_ms, created_at) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                    sql_params![
+                        entry.session_id,
+                        entry.task_id,
+                        entry.tool_name,
+                        entry.adapter_id,
+                        arguments,
+                        entry.success,
+                        entry.result,
+                        entry.duration_ms,
+                        now
+                    ],
+                )?;
+                let id = conn.last_insert_id();
+                debug!(id, tool = %entry.tool_name, "tool call audited");
+                Ok(id)
+            })
+            .await
+    }
+
+    /// All calls made in `session_id`, in the order they were recorded.
+    #[instrument(skip(self))]
+    pub async fn list_for_session(&self, session_id: &str) -> StoreResult<Vec<ToolAuditEntry>> {
+        let session_id = session_id.to_string();
+        self.db
+            .with_conn(move |conn| {
+                conn.query_map(
+                    "SELECT id, session_id, task_id, tool_name, adapter_id, arguments, success, \
+                     result, duration_ms, created_at \
+                     FROM tool_audit WHERE session_id = ?1 ORDER BY id ASC",
+                    sql_params![session_id],
+                    row_to_entry,
+                )
+            })
+            .await
+    }
+
+    /// All calls made in agent run `task_id`, in the order they were recorded.
+    #[instrument(skip(self))]
+    pub async fn list_for_task(&self, task_id: &str) -> StoreResult<Vec<ToolAuditEntry>> {
+        let task_id = task_id.to_string();
+        self.db
+            .with_conn(move |conn| {
+                conn.query_map(
+                    "SELECT id, session_id, task_id, tool_name, adapter_id, arguments, success, \
+                     result, duration_ms, created_at \
+                     FROM tool_audit WHERE task_id = ?1 ORDER BY id ASC",
+                    sql_params![task_id],
+                    row_to_entry,
+                )
+            })
+            .await
+    }
+}
+
+/// Map a `tool_audit` row (in the canonical column order) to an entry.
+fn row_to_entry(row: &SqlRow) -> StoreResult<ToolAuditEntry> {
+    let arguments: String = row.get(5)?;
+    Ok(ToolAuditEntry {
+        id: row.get(0)?,
+        session_id: row.get(1)?,
+        task_id: row.get(2)?,
+        tool_name: row.get(3)?,
+        adapter_id: row.get(4)?,
+        arguments: serde_json::from_str(&arguments)?,
+        success: row.get(6)?,
+        result: row.get(7)?,
+        duration_ms: row.get(8)?,
+        created_at: row.get(9)?,
+    })
+}
+
+// ── tests ────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup_store() -> ToolAuditStore {
+        let db = Database::open_in_memory().unwrap();
+        db.run_migrations().await.unwrap();
+        ToolAuditStore::new(db)
+    }
+
+    fn call(session_id: Option<&str>, tool_name: &str) -> NewToolAudit {
+        NewToolAudit {
+            session_id: session_id.map(String::from),
+            task_id: "task-1".into(),
+            tool_name: tool_name.into(),
+            adapter_id: Some("filesystem".into()),
+            arguments: serde_json::json!({"path": "notes.txt"}),
+            success: true,
+            result: "ok".into(),
+            duration_ms: 12,
+        }
+    }
+
+    #[tokio::test]
+    async fn lists_calls_by_session_in_order() {
+        let store = setup_store().await;
+        for (session_id, tool_name) in [
+            (Some("s1"), "fs_read_file"),
+            (Some("s2"), "fs_list"),
+            (Some("s1"), "fs_write_file"),
+            (None, "web_search"),
+        ] {
+            store.record(call(session_id, tool_name)).await.unwrap();
+        }
+
+        let entries = store.list_for_session("s1").await.unwrap();
+        let tools: Vec<_> = entries.iter().map(|e| e.tool_name.as_str()).collect();
+        assert_eq!(tools, ["fs_read_file", "fs_write_file"]);
+        assert_eq!(entries[0].arguments["path"], "notes.txt");
+        assert_eq!(entries[0].duration_ms, 12);
+        assert!(entries[0].success);
+
+        assert_eq!(store.list_for_task("task-1").await.unwrap().len(), 4);
+    }
+}
@@ -1,21 +1,27 @@
 //! Multi-user persistence for OpenIntentOS.
 //!
-//! Provides SQLite-backed storage for user accounts with password
-//! hashing via PBKDF2-HMAC-SHA256 (ring). Passwords are stored as
-//! `base64(salt):base64(hash)` strings, using 600,000 iterations
-//! per OWASP 2023 recommendations.
+//! Provides SQLite-backed storage for user accounts with salted password
+//! hashing.  New passwords are hashed with Argon2id by default, or with
+//! PBKDF2-HMAC-SHA256 (ring) at a configurable cost; see [`PasswordScheme`].
+//! Each stored hash carries a prefix naming its scheme, so accounts hashed
+//! under different schemes (including legacy unprefixed PBKDF2 hashes) keep
+//! working side by side.
 
-use base64::Engine;
-use base64::engine::general_purpose::STANDARD as BASE64;
 use chrono::Utc;
-use ring::pbkdf2;
-use ring::rand::{SecureRandom, SystemRandom};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, instrument};
 use uuid::Uuid;
 
+use crate::backend::{SqlRow, StorageBackend};
 use crate::db::Database;
 use crate::error::{StoreError, StoreResult};
+use crate::sql_params;
+
+mod password;
+
+pub use password::{Argon2Params, PasswordScheme};
+
+use self::password::verify_password;
 
 // ═══════════════════════════════════════════════════════════════════════
 //  Types
@@ -81,93 +87,39 @@ impl std::fmt::Display for UserRole {
     }
 }
 
-// ═══════════════════════════════════════════════════════════════════════
-//  Password hashing
-// ═══════════════════════════════════════════════════════════════════════
-
-/// PBKDF2-HMAC-SHA256 with 600,000 iterations (OWASP 2023).
-const PBKDF2_ITERATIONS: u32 = 600_000;
-
-/// Salt length in bytes.
-const SALT_LEN: usize = 32;
-
-/// Derived key length in bytes.
-const KEY_LEN: usize = 32;
-
-/// PBKDF2 algorithm.
-static PBKDF2_ALG: pbkdf2::Algorithm = pbkdf2::PBKDF2_HMAC_SHA256;
-
-/// Hash a password and return a storable string of the form `base64(salt):base64(hash)`.
-fn hash_password(password: &str) -> StoreResult<String> {
-    let rng = SystemRandom::new();
-
-    let mut salt = [0u8; SALT_LEN];
-    rng.fill(&mut salt)
-        .map_err(|_| StoreError::InvalidArgument("failed to generate random salt".into()))?;
-
-    let mut hash = [0u8; KEY_LEN];
-    let iterations =
-        std::num::NonZeroU32::new(PBKDF2_ITERATIONS).expect("PBKDF2_ITERATIONS is non-zero");
-    pbkdf2::derive(
-        PBKDF2_ALG,
-        iterations,
-        &salt,
-        password.as_bytes(),
-        &mut hash,
-    );
-
-    let encoded = format!("{}:{}", BASE64.encode(salt), BASE64.encode(hash));
-    Ok(encoded)
-}
-
-/// Verify a password against a stored hash string (`base64(salt):base64(hash)`).
-fn verify_password(password: &str, stored: &str) -> StoreResult<bool> {
-    let parts: Vec<&str> = stored.splitn(2, ':').collect();
-    if parts.len() != 2 {
-        return Err(StoreError::InvalidArgument(
-            "malformed password hash".into(),
-        ));
-    }
-
-    let salt = BASE64
-        .decode(parts[0])
-        .map_err(|e| StoreError::InvalidArgument(format!("invalid salt encoding: {e}")))?;
-    let expected_hash = BASE64
-        .decode(parts[1])
-        .map_err(|e| StoreError::InvalidArgument(format!("invalid hash encoding: {e}")))?;
-
-    let iterations =
-        std::num::NonZeroU32::new(PBKDF2_ITERATIONS).expect("PBKDF2_ITERATIONS is non-zero");
-
-    Ok(pbkdf2::verify(
-        PBKDF2_ALG,
-        iterations,
-        &salt,
-        password.as_bytes(),
-        &expected_hash,
-    )
-    .is_ok())
-}
-
 // ═══════════════════════════════════════════════════════════════════════
 //  UserStore
 // ═══════════════════════════════════════════════════════════════════════
 
 /// CRUD operations on user accounts with password management.
 #[derive(Clone)]
-pub struct UserStore {
-    db: Database,
+pub struct UserStore<B = Database> {
+    db: B,
+    /// Scheme for newly hashed passwords.
+    scheme: PasswordScheme,
 }
 
-impl UserStore {
+impl<B: StorageBackend> UserStore<B> {
     /// Create a new user store backed by `db`.
-    pub fn new(db: Database) -> Self {
-        Self { db }
+    pub fn new(db: B) -> Self {
+        Self {
+            db,
+            scheme: PasswordScheme::default(),
+        }
+    }
+
+    /// Hash new and changed passwords with `scheme`.
+    ///
+    /// Existing hashes keep verifying under whatever scheme made them.
+    pub fn with_password_scheme(mut self, scheme: PasswordScheme) -> Self {
+        self.scheme = scheme;
+        self
     }
 
     /// Create a new user account.
     ///
-    /// The password is hashed with PBKDF2-HMAC-SHA256 before storage.
+    /// The password is hashed with the store's [`PasswordScheme`] before
+    /// storage.
     /// Returns an error if the username is already taken.
     #[instrument(skip(self, password))]
     pub async fn create(
@@ -195,7 +147,7 @@ impl UserStore {
         let now = Utc::now().timestamp();
 
         // Hash the password (CPU-intensive, done inside spawn_blocking via db.execute)
-        let password_hash = hash_password(password)?;
+        let password_hash = self.scheme.hash(password)?;
 
         let user = User {
             id: id.clone(),
@@ -208,20 +160,17 @@ impl UserStore {
         };
 
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 conn.execute(
                     "INSERT INTO users (id, username, display_name, password_hash, role, active, created_at, updated_at) \
                      VALUES (?1, ?2, ?3, ?4, ?5, 1, ?6, ?6)",
-                    rusqlite::params![id, username, display_name, password_hash, role_str, now],
+                    sql_params![id, username, display_name, password_hash, role_str, now],
                 )
-                .map_err(|e| {
-                    if let rusqlite::Error::SqliteFailure(ref err, _) = e
-                        && err.code == rusqlite::ErrorCode::ConstraintViolation {
-                            return StoreError::InvalidArgument(format!(
-                                "username already taken: {username}"
-                            ));
-                        }
-                    StoreError::Sqlite(e)
+                .map_err(|e| match e {
+                    StoreError::Constraint(_) => {
+                        StoreError::InvalidArgument(format!("username already taken: {username}"))
+                    }
+                    other => other,
                 })?;
                 Ok(())
             })
@@ -236,28 +185,15 @@ impl UserStore {
     pub async fn get(&self, id: &str) -> StoreResult<Option<User>> {
         let id = id.to_string();
         self.db
-            .execute(move |conn| {
-                let result = conn.query_row(
+            .with_conn(move |conn| {
+                conn.query_opt(
                     "SELECT id, username, display_name, role, active, created_at, updated_at \
                      FROM users WHERE id = ?1",
-                    rusqlite::params![id],
-                    |row| {
-                        Ok(UserRow {
-                            id: row.get(0)?,
-                            username: row.get(1)?,
-                            display_name: row.get(2)?,
-                            role: row.get(3)?,
-                            active: row.get(4)?,
-                            created_at: row.get(5)?,
-                            updated_at: row.get(6)?,
-                        })
-                    },
-                );
-                match result {
-                    Ok(row) => row.into_user().map(Some),
-                    Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-                    Err(e) => Err(StoreError::Sqlite(e)),
-                }
+                    sql_params![id],
+                    UserRow::from_row,
+                )?
+                .map(UserRow::into_user)
+                .transpose()
             })
             .await
     }
@@ -267,28 +203,15 @@ impl UserStore {
     pub async fn get_by_username(&self, username: &str) -> StoreResult<Option<User>> {
         let username = username.to_string();
         self.db
-            .execute(move |conn| {
-                let result = conn.query_row(
+            .with_conn(move |conn| {
+                conn.query_opt(
                     "SELECT id, username, display_name, role, active, created_at, updated_at \
                      FROM users WHERE username = ?1",
-                    rusqlite::params![username],
-                    |row| {
-                        Ok(UserRow {
-                            id: row.get(0)?,
-                            username: row.get(1)?,
-                            display_name: row.get(2)?,
-                            role: row.get(3)?,
-                            active: row.get(4)?,
-                            created_at: row.get(5)?,
-                            updated_at: row.get(6)?,
-                        })
-                    },
-                );
-                match result {
-                    Ok(row) => row.into_user().map(Some),
-                    Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-                    Err(e) => Err(StoreError::Sqlite(e)),
-                }
+                    sql_params![username],
+                    UserRow::from_row,
+                )?
+                .map(UserRow::into_user)
+                .transpose()
             })
             .await
     }
@@ -304,11 +227,11 @@ impl UserStore {
         let password = password.to_string();
 
         self.db
-            .execute(move |conn| {
-                let result = conn.query_row(
+            .with_conn(move |conn| {
+                let row = conn.query_opt(
                     "SELECT id, username, display_name, password_hash, role, active, created_at, updated_at \
                      FROM users WHERE username = ?1",
-                    rusqlite::params![username],
+                    sql_params![username],
                     |row| {
                         Ok(AuthRow {
                             id: row.get(0)?,
@@ -321,33 +244,27 @@ impl UserStore {
                             updated_at: row.get(7)?,
                         })
                     },
-                );
-
-                match result {
-                    Ok(row) => {
-                        // Inactive users cannot authenticate.
-                        if !row.active {
-                            return Ok(None);
-                        }
-
-                        let valid = verify_password(&password, &row.password_hash)?;
-                        if valid {
-                            let user = UserRow {
-                                id: row.id,
-                                username: row.username,
-                                display_name: row.display_name,
-                                role: row.role,
-                                active: row.active,
-                                created_at: row.created_at,
-                                updated_at: row.updated_at,
-                            };
-                            user.into_user().map(Some)
-                        } else {
-                            Ok(None)
-                        }
-                    }
-                    Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-                    Err(e) => Err(StoreError::Sqlite(e)),
+                )?;
+
+                // Unknown and inactive users cannot authenticate.
+                let Some(row) = row.filter(|row| row.active) else {
+                    return Ok(None);
+                };
+
+                let valid = verify_password(&password, &row.password_hash)?;
+                if valid {
+                    let user = UserRow {
+                        id: row.id,
+                        username: row.username,
+                        display_name: row.display_name,
+                        role: row.role,
+                        active: row.active,
+                        created_at: row.created_at,
+                        updated_at: row.updated_at,
+                    };
+                    user.into_user().map(Some)
+                } else {
+                    Ok(None)
                 }
             })
             .await
@@ -357,24 +274,13 @@ impl UserStore {
     #[instrument(skip(self))]
     pub async fn list(&self, limit: i64, offset: i64) -> StoreResult<Vec<User>> {
         self.db
-            .execute(move |conn| {
-                let mut stmt = conn.prepare(
+            .with_conn(move |conn| {
+                let rows = conn.query_map(
                     "SELECT id, username, display_name, role, active, created_at, updated_at \
                      FROM users ORDER BY created_at ASC LIMIT ?1 OFFSET ?2",
+                    sql_params![limit, offset],
+                    UserRow::from_row,
                 )?;
-                let rows = stmt
-                    .query_map(rusqlite::params![limit, offset], |row| {
-                        Ok(UserRow {
-                            id: row.get(0)?,
-                            username: row.get(1)?,
-                            display_name: row.get(2)?,
-                            role: row.get(3)?,
-                            active: row.get(4)?,
-                            created_at: row.get(5)?,
-                            updated_at: row.get(6)?,
-                        })
-                    })?
-                    .collect::<Result<Vec<_>, _>>()?;
 
                 rows.into_iter().map(|r| r.into_user()).collect()
             })
@@ -395,10 +301,10 @@ impl UserStore {
         let now = Utc::now().timestamp();
 
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 let updated = conn.execute(
                     "UPDATE users SET display_name = ?2, role = ?3, updated_at = ?4 WHERE id = ?1",
-                    rusqlite::params![id, display_name, role_str, now],
+                    sql_params![id, display_name, role_str, now],
                 )?;
                 if updated == 0 {
                     return Err(StoreError::NotFound { entity: "user", id });
@@ -410,7 +316,7 @@ impl UserStore {
 
     /// Change a user's password.
     ///
-    /// The new password is hashed with PBKDF2 before storage.
+    /// The new password is hashed with the store's [`PasswordScheme`].
     #[instrument(skip(self, new_password))]
     pub async fn change_password(&self, id: &str, new_password: &str) -> StoreResult<()> {
         if new_password.is_empty() {
@@ -420,14 +326,14 @@ impl UserStore {
         }
 
         let id = id.to_string();
-        let password_hash = hash_password(new_password)?;
+        let password_hash = self.scheme.hash(new_password)?;
         let now = Utc::now().timestamp();
 
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 let updated = conn.execute(
                     "UPDATE users SET password_hash = ?2, updated_at = ?3 WHERE id = ?1",
-                    rusqlite::params![id, password_hash, now],
+                    sql_params![id, password_hash, now],
                 )?;
                 if updated == 0 {
                     return Err(StoreError::NotFound { entity: "user", id });
@@ -444,10 +350,10 @@ impl UserStore {
         let now = Utc::now().timestamp();
 
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 let updated = conn.execute(
                     "UPDATE users SET active = ?2, updated_at = ?3 WHERE id = ?1",
-                    rusqlite::params![id, active, now],
+                    sql_params![id, active, now],
                 )?;
                 if updated == 0 {
                     return Err(StoreError::NotFound { entity: "user", id });
@@ -462,9 +368,8 @@ impl UserStore {
     pub async fn delete(&self, id: &str) -> StoreResult<()> {
         let id = id.to_string();
         self.db
-            .execute(move |conn| {
-                let deleted =
-                    conn.execute("DELETE FROM users WHERE id = ?1", rusqlite::params![id])?;
+            .with_conn(move |conn| {
+                let deleted = conn.execute("DELETE FROM users WHERE id = ?1", sql_params![id])?;
                 if deleted == 0 {
                     return Err(StoreError::NotFound { entity: "user", id });
                 }
@@ -477,9 +382,11 @@ impl UserStore {
     #[instrument(skip(self))]
     pub async fn count(&self) -> StoreResult<i64> {
         self.db
-            .execute(|conn| {
+            .with_conn(|conn| {
                 let count: i64 =
-                    conn.query_row("SELECT COUNT(*) FROM users", [], |row| row.get(0))?;
+                    conn.query_row("SELECT COUNT(*) FROM users", sql_params![], |row| {
+                        row.get(0)
+                    })?;
                 Ok(count)
             })
             .await
@@ -502,6 +409,19 @@ struct UserRow {
 }
 
 impl UserRow {
+    /// Map a `users` row (without the password hash) in select order.
+    fn from_row(row: &SqlRow) -> StoreResult<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            username: row.get(1)?,
+            display_name: row.get(2)?,
+            role: row.get(3)?,
+            active: row.get(4)?,
+            created_at: row.get(5)?,
+            updated_at: row.get(6)?,
+        })
+    }
+
     fn into_user(self) -> StoreResult<User> {
         let role = UserRole::from_str(&self.role)?;
         Ok(User {
@@ -921,14 +841,22 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn password_hash_is_different_for_same_password() {
-        // Verify that each hash has a unique salt.
-        let hash1 = hash_password("same-password").unwrap();
-        let hash2 = hash_password("same-password").unwrap();
-        assert_ne!(hash1, hash2, "hashes should differ due to random salt");
-
-        // But both verify correctly.
-        assert!(verify_password("same-password", &hash1).unwrap());
-        assert!(verify_password("same-password", &hash2).unwrap());
+    async fn schemes_coexist_after_switching() {
+        let db = setup_db().await;
+        let pbkdf2 = UserStore::new(db.clone())
+            .with_password_scheme(PasswordScheme::Pbkdf2 { iterations: 1_000 });
+        pbkdf2
+            .create("old", None, "old-pw", UserRole::User)
+            .await
+            .unwrap();
+
+        let argon = UserStore::new(db);
+        argon
+            .create("new", None, "new-pw", UserRole::User)
+            .await
+            .unwrap();
+
+        assert!(argon.authenticate("old", "old-pw").await.unwrap().is_some());
+        assert!(argon.authenticate("new", "new-pw").await.unwrap().is_some());
     }
 }
@@ -0,0 +1,243 @@
+//! Password hashing schemes.
+//!
+//! Hashes are self-describing: Argon2id hashes are PHC strings, PBKDF2
+//! hashes carry a `pbkdf2-sha256$<iterations>$` prefix, and legacy
+//! unprefixed PBKDF2 hashes are verified at the old fixed cost.
+
+use std::num::NonZeroU32;
+
+use argon2::Argon2;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::error::{StoreError, StoreResult};
+
+/// PBKDF2-HMAC-SHA256 with 600,000 iterations (OWASP 2023).
+const PBKDF2_ITERATIONS: u32 = 600_000;
+
+/// Salt length in bytes.
+const SALT_LEN: usize = 32;
+
+/// Derived key length in bytes.
+const KEY_LEN: usize = 32;
+
+/// PBKDF2 algorithm.
+static PBKDF2_ALG: pbkdf2::Algorithm = pbkdf2::PBKDF2_HMAC_SHA256;
+
+/// Prefix of PBKDF2 hashes that record their iteration count.
+const PBKDF2_PREFIX: &str = "pbkdf2-sha256$";
+
+/// Prefix of Argon2id hashes (PHC string format).
+const ARGON2ID_PREFIX: &str = "$argon2id$";
+
+/// Cost parameters for Argon2id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Argon2Params {
+    /// Memory size in KiB.
+    pub memory_kib: u32,
+    /// Number of passes over memory.
+    pub iterations: u32,
+    /// Degree of parallelism.
+    pub parallelism: u32,
+}
+
+impl Default for Argon2Params {
+    /// 19 MiB, 2 passes, 1 lane (OWASP 2023).
+    fn default() -> Self {
+        Self {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+/// How new and changed passwords are hashed.
+///
+/// The scheme and its cost are recorded in each stored hash, so hashes made
+/// under any scheme keep verifying after the configured scheme changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordScheme {
+    /// PBKDF2-HMAC-SHA256, stored as
+    /// `pbkdf2-sha256$<iterations>$base64(salt)$base64(hash)`.
+    Pbkdf2 { iterations: u32 },
+    /// Argon2id, stored as a PHC string (`$argon2id$v=19$m=...`).
+    Argon2id { params: Argon2Params },
+}
+
+impl Default for PasswordScheme {
+    fn default() -> Self {
+        Self::Argon2id {
+            params: Argon2Params::default(),
+        }
+    }
+}
+
+impl PasswordScheme {
+    /// Hash a password into a storable string.
+    pub(super) fn hash(&self, password: &str) -> StoreResult<String> {
+        let mut salt = [0u8; SALT_LEN];
+        SystemRandom::new()
+            .fill(&mut salt)
+            .map_err(|_| StoreError::InvalidArgument("failed to generate random salt".into()))?;
+
+        match *self {
+            Self::Pbkdf2 { iterations } => {
+                let hash = pbkdf2_derive(iterations, &salt, password)?;
+                Ok(format!(
+                    "{PBKDF2_PREFIX}{iterations}${}${}",
+                    BASE64.encode(salt),
+                    BASE64.encode(hash)
+                ))
+            }
+            Self::Argon2id { params } => {
+                let salt = SaltString::encode_b64(&salt)
+                    .map_err(|e| StoreError::InvalidArgument(format!("invalid salt: {e}")))?;
+                argon2id(params)?
+                    .hash_password(password.as_bytes(), &salt)
+                    .map(|hash| hash.to_string())
+                    .map_err(|e| StoreError::InvalidArgument(format!("argon2 hashing failed: {e}")))
+            }
+        }
+    }
+}
+
+fn pbkdf2_derive(iterations: u32, salt: &[u8], password: &str) -> StoreResult<[u8; KEY_LEN]> {
+    let iterations = NonZeroU32::new(iterations)
+        .ok_or_else(|| StoreError::InvalidArgument("PBKDF2 iterations must be non-zero".into()))?;
+    let mut hash = [0u8; KEY_LEN];
+    pbkdf2::derive(PBKDF2_ALG, iterations, salt, password.as_bytes(), &mut hash);
+    Ok(hash)
+}
+
+fn argon2id(params: Argon2Params) -> StoreResult<Argon2<'static>> {
+    let params = argon2::Params::new(
+        params.memory_kib,
+        params.iterations,
+        params.parallelism,
+        Some(KEY_LEN),
+    )
+    .map_err(|e| StoreError::InvalidArgument(format!("invalid argon2 parameters: {e}")))?;
+    Ok(Argon2::new(
+        argon2::Algorithm::Argon2id,
+        argon2::Version::V0x13,
+        params,
+    ))
+}
+
+/// Verify a password against a stored hash string.
+///
+/// Dispatches on the hash's prefix: Argon2id PHC strings, prefixed PBKDF2
+/// hashes, and legacy unprefixed `base64(salt):base64(hash)` PBKDF2 hashes
+/// made with [`PBKDF2_ITERATIONS`].  An empty stored hash (an account
+/// imported without its hash) never matches.
+pub(super) fn verify_password(password: &str, stored: &str) -> StoreResult<bool> {
+    if stored.is_empty() {
+        return Ok(false);
+    }
+
+    if stored.starts_with(ARGON2ID_PREFIX) {
+        let hash = PasswordHash::new(stored)
+            .map_err(|e| StoreError::InvalidArgument(format!("malformed argon2 hash: {e}")))?;
+        // The cost parameters are read from the hash itself.
+        return Ok(Argon2::default()
+            .verify_password(password.as_bytes(), &hash)
+            .is_ok());
+    }
+
+    let (iterations, salt, expected) = match stored.strip_prefix(PBKDF2_PREFIX) {
+        Some(rest) => {
+            let mut parts = rest.splitn(3, '$');
+            let (Some(iterations), Some(salt), Some(hash)) =
+                (parts.next(), parts.next(), parts.next())
+            else {
+                return Err(StoreError::InvalidArgument(
+                    "malformed password hash".into(),
+                ));
+            };
+            let iterations = iterations.parse().map_err(|e| {
+                StoreError::InvalidArgument(format!("invalid iteration count: {e}"))
+            })?;
+            (iterations, salt, hash)
+        }
+        None => {
+            let Some((salt, hash)) = stored.split_once(':') else {
+                return Err(StoreError::InvalidArgument(
+                    "malformed password hash".into(),
+                ));
+            };
+            (PBKDF2_ITERATIONS, salt, hash)
+        }
+    };
+
+    let salt = BASE64
+        .decode(salt)
+        .map_err(|e| StoreError::InvalidArgument(format!("invalid salt encoding: {e}")))?;
+    let expected_hash = BASE64
+        .decode(expected)
+        .map_err(|e| StoreError::InvalidArgument(format!("invalid hash encoding: {e}")))?;
+    let iterations = NonZeroU32::new(iterations)
+        .ok_or_else(|| StoreError::InvalidArgument("PBKDF2 iterations must be non-zero".into()))?;
+
+    Ok(pbkdf2::verify(
+        PBKDF2_ALG,
+        iterations,
+        &salt,
+        password.as_bytes(),
+        &expected_hash,
+    )
+    .is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn password_hash_is_different_for_same_password() {
+        // Verify that each hash has a unique salt.
+        let scheme = PasswordScheme::default();
+        let hash1 = scheme.hash("same-password").unwrap();
+        let hash2 = scheme.hash("same-password").unwrap();
+        assert_ne!(hash1, hash2, "hashes should differ due to random salt");
+
+        // But both verify correctly.
+        assert!(verify_password("same-password", &hash1).unwrap());
+        assert!(verify_password("same-password", &hash2).unwrap());
+    }
+
+    #[test]
+    fn argon2id_hash_verifies() {
+        let hash = PasswordScheme::default().hash("hunter2").unwrap();
+        assert!(
+            hash.starts_with("$argon2id$v=19$m=19456,t=2,p=1$"),
+            "{hash}"
+        );
+        assert!(verify_password("hunter2", &hash).unwrap());
+        assert!(!verify_password("hunter3", &hash).unwrap());
+    }
+
+    #[test]
+    fn legacy_pbkdf2_hash_still_verifies() {
+        // Hashes written before schemes were recorded: `base64(salt):base64(hash)`.
+        let salt = [7u8; SALT_LEN];
+        let hash = pbkdf2_derive(PBKDF2_ITERATIONS, &salt, "hunter2").unwrap();
+        let legacy = format!("{}:{}", BASE64.encode(salt), BASE64.encode(hash));
+
+        assert!(verify_password("hunter2", &legacy).unwrap());
+        assert!(!verify_password("hunter3", &legacy).unwrap());
+    }
+
+    #[test]
+    fn pbkdf2_hash_records_iterations() {
+        let hash = PasswordScheme::Pbkdf2 { iterations: 1_000 }
+            .hash("hunter2")
+            .unwrap();
+        assert!(hash.starts_with("pbkdf2-sha256$1000$"), "{hash}");
+        assert!(verify_password("hunter2", &hash).unwrap());
+        assert!(!verify_password("hunter3", &hash).unwrap());
+    }
+}
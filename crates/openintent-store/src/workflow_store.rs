@@ -4,14 +4,19 @@
 //! pagination, enable/disable toggles, and name-based lookups.
 //! Each workflow stores its step definitions and trigger configuration
 //! as JSON, supporting cron, event, and manual triggers.
+//!
+//! Workflow runs are checkpointed here as well, so a paused or interrupted
+//! run can be resumed after a restart.
 
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, instrument};
 use uuid::Uuid;
 
+use crate::backend::{SqlRow, StorageBackend};
 use crate::db::Database;
 use crate::error::{StoreError, StoreResult};
+use crate::sql_params;
 
 // ═══════════════════════════════════════════════════════════════════════
 //  Types
@@ -40,19 +45,36 @@ pub struct StoredWorkflow {
     pub updated_at: i64,
 }
 
+/// A checkpoint of a single workflow run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredWorkflowRun {
+    /// Unique run identifier.
+    pub id: String,
+    /// The workflow being run.
+    pub workflow_id: String,
+    /// Run status as reported by the workflow engine (e.g. `"paused"`).
+    pub status: String,
+    /// Engine-defined JSON snapshot of the run's progress.
+    pub checkpoint: serde_json::Value,
+    /// Unix timestamp when the run was first checkpointed.
+    pub created_at: i64,
+    /// Unix timestamp of the latest checkpoint.
+    pub updated_at: i64,
+}
+
 // ═══════════════════════════════════════════════════════════════════════
 //  WorkflowStore
 // ═══════════════════════════════════════════════════════════════════════
 
 /// CRUD operations on workflow definitions.
 #[derive(Clone)]
-pub struct WorkflowStore {
-    db: Database,
+pub struct WorkflowStore<B = Database> {
+    db: B,
 }
 
-impl WorkflowStore {
+impl<B: StorageBackend> WorkflowStore<B> {
     /// Create a new workflow store backed by `db`.
-    pub fn new(db: Database) -> Self {
+    pub fn new(db: B) -> Self {
         Self { db }
     }
 
@@ -90,11 +112,11 @@ impl WorkflowStore {
         };
 
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 conn.execute(
                     "INSERT INTO workflows (id, name, description, intent_raw, steps, trigger, enabled, created_at, updated_at) \
                      VALUES (?1, ?2, ?3, ?4, ?5, ?6, 1, ?7, ?7)",
-                    rusqlite::params![id, name, description, intent_raw, steps_json, trigger_json, now],
+                    sql_params![id, name, description, intent_raw, steps_json, trigger_json, now],
                 )?;
                 Ok(())
             })
@@ -109,30 +131,15 @@ impl WorkflowStore {
     pub async fn get(&self, id: &str) -> StoreResult<Option<StoredWorkflow>> {
         let id = id.to_string();
         self.db
-            .execute(move |conn| {
-                let result = conn.query_row(
+            .with_conn(move |conn| {
+                conn.query_opt(
                     "SELECT id, name, description, intent_raw, steps, trigger, enabled, created_at, updated_at \
                      FROM workflows WHERE id = ?1",
-                    rusqlite::params![id],
-                    |row| {
-                        Ok(WorkflowRow {
-                            id: row.get(0)?,
-                            name: row.get(1)?,
-                            description: row.get(2)?,
-                            intent_raw: row.get(3)?,
-                            steps: row.get(4)?,
-                            trigger: row.get(5)?,
-                            enabled: row.get(6)?,
-                            created_at: row.get(7)?,
-                            updated_at: row.get(8)?,
-                        })
-                    },
-                );
-                match result {
-                    Ok(row) => row.into_stored_workflow().map(Some),
-                    Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-                    Err(e) => Err(StoreError::Sqlite(e)),
-                }
+                    sql_params![id],
+                    WorkflowRow::from_row,
+                )?
+                .map(|row| row.into_stored_workflow())
+                .transpose()
             })
             .await
     }
@@ -142,30 +149,15 @@ impl WorkflowStore {
     pub async fn get_by_name(&self, name: &str) -> StoreResult<Option<StoredWorkflow>> {
         let name = name.to_string();
         self.db
-            .execute(move |conn| {
-                let result = conn.query_row(
+            .with_conn(move |conn| {
+                conn.query_opt(
                     "SELECT id, name, description, intent_raw, steps, trigger, enabled, created_at, updated_at \
                      FROM workflows WHERE name = ?1",
-                    rusqlite::params![name],
-                    |row| {
-                        Ok(WorkflowRow {
-                            id: row.get(0)?,
-                            name: row.get(1)?,
-                            description: row.get(2)?,
-                            intent_raw: row.get(3)?,
-                            steps: row.get(4)?,
-                            trigger: row.get(5)?,
-                            enabled: row.get(6)?,
-                            created_at: row.get(7)?,
-                            updated_at: row.get(8)?,
-                        })
-                    },
-                );
-                match result {
-                    Ok(row) => row.into_stored_workflow().map(Some),
-                    Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-                    Err(e) => Err(StoreError::Sqlite(e)),
-                }
+                    sql_params![name],
+                    WorkflowRow::from_row,
+                )?
+                .map(|row| row.into_stored_workflow())
+                .transpose()
             })
             .await
     }
@@ -174,26 +166,13 @@ impl WorkflowStore {
     #[instrument(skip(self))]
     pub async fn list(&self, limit: i64, offset: i64) -> StoreResult<Vec<StoredWorkflow>> {
         self.db
-            .execute(move |conn| {
-                let mut stmt = conn.prepare(
+            .with_conn(move |conn| {
+                let rows = conn.query_map(
                     "SELECT id, name, description, intent_raw, steps, trigger, enabled, created_at, updated_at \
                      FROM workflows ORDER BY updated_at DESC LIMIT ?1 OFFSET ?2",
+                    sql_params![limit, offset],
+                    WorkflowRow::from_row,
                 )?;
-                let rows = stmt
-                    .query_map(rusqlite::params![limit, offset], |row| {
-                        Ok(WorkflowRow {
-                            id: row.get(0)?,
-                            name: row.get(1)?,
-                            description: row.get(2)?,
-                            intent_raw: row.get(3)?,
-                            steps: row.get(4)?,
-                            trigger: row.get(5)?,
-                            enabled: row.get(6)?,
-                            created_at: row.get(7)?,
-                            updated_at: row.get(8)?,
-                        })
-                    })?
-                    .collect::<Result<Vec<_>, _>>()?;
 
                 rows.into_iter()
                     .map(|r| r.into_stored_workflow())
@@ -206,26 +185,13 @@ impl WorkflowStore {
     #[instrument(skip(self))]
     pub async fn list_enabled(&self) -> StoreResult<Vec<StoredWorkflow>> {
         self.db
-            .execute(move |conn| {
-                let mut stmt = conn.prepare(
+            .with_conn(move |conn| {
+                let rows = conn.query_map(
                     "SELECT id, name, description, intent_raw, steps, trigger, enabled, created_at, updated_at \
                      FROM workflows WHERE enabled = 1 ORDER BY updated_at DESC",
+                    sql_params![],
+                    WorkflowRow::from_row,
                 )?;
-                let rows = stmt
-                    .query_map([], |row| {
-                        Ok(WorkflowRow {
-                            id: row.get(0)?,
-                            name: row.get(1)?,
-                            description: row.get(2)?,
-                            intent_raw: row.get(3)?,
-                            steps: row.get(4)?,
-                            trigger: row.get(5)?,
-                            enabled: row.get(6)?,
-                            created_at: row.get(7)?,
-                            updated_at: row.get(8)?,
-                        })
-                    })?
-                    .collect::<Result<Vec<_>, _>>()?;
 
                 rows.into_iter()
                     .map(|r| r.into_stored_workflow())
@@ -255,11 +221,11 @@ impl WorkflowStore {
         let trigger_json = trigger.as_ref().map(serde_json::to_string).transpose()?;
 
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 let updated = conn.execute(
                     "UPDATE workflows SET name = ?2, description = ?3, steps = ?4, trigger = ?5, updated_at = ?6 \
                      WHERE id = ?1",
-                    rusqlite::params![id, name, description, steps_json, trigger_json, now],
+                    sql_params![id, name, description, steps_json, trigger_json, now],
                 )?;
                 if updated == 0 {
                     return Err(StoreError::NotFound {
@@ -279,10 +245,10 @@ impl WorkflowStore {
         let now = Utc::now().timestamp();
 
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 let updated = conn.execute(
                     "UPDATE workflows SET enabled = ?2, updated_at = ?3 WHERE id = ?1",
-                    rusqlite::params![id, enabled, now],
+                    sql_params![id, enabled, now],
                 )?;
                 if updated == 0 {
                     return Err(StoreError::NotFound {
@@ -300,9 +266,9 @@ impl WorkflowStore {
     pub async fn delete(&self, id: &str) -> StoreResult<()> {
         let id = id.to_string();
         self.db
-            .execute(move |conn| {
+            .with_conn(move |conn| {
                 let deleted =
-                    conn.execute("DELETE FROM workflows WHERE id = ?1", rusqlite::params![id])?;
+                    conn.execute("DELETE FROM workflows WHERE id = ?1", sql_params![id])?;
                 if deleted == 0 {
                     return Err(StoreError::NotFound {
                         entity: "workflow",
@@ -318,24 +284,107 @@ impl WorkflowStore {
     #[instrument(skip(self))]
     pub async fn count(&self) -> StoreResult<i64> {
         self.db
-            .execute(|conn| {
+            .with_conn(|conn| {
                 let count: i64 =
-                    conn.query_row("SELECT COUNT(*) FROM workflows", [], |row| row.get(0))?;
+                    conn.query_row("SELECT COUNT(*) FROM workflows", sql_params![], |row| {
+                        row.get(0)
+                    })?;
                 Ok(count)
             })
             .await
     }
+
+    /// Write a checkpoint for a workflow run, replacing any earlier one.
+    #[instrument(skip(self, checkpoint))]
+    pub async fn save_run(
+        &self,
+        run_id: &str,
+        workflow_id: &str,
+        status: &str,
+        checkpoint: &serde_json::Value,
+    ) -> StoreResult<()> {
+        let run_id = run_id.to_string();
+        let workflow_id = workflow_id.to_string();
+        let status = status.to_string();
+        let checkpoint_json = serde_json::to_string(checkpoint)?;
+        let now = Utc::now().timestamp();
+
+        self.db
+            .with_conn(move |conn| {
+                conn.execute(
+                    "INSERT INTO workflow_runs (id, workflow_id, status, checkpoint, created_at, updated_at) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?5) \
+                     ON CONFLICT(id) DO UPDATE SET status = excluded.status, \
+                     checkpoint = excluded.checkpoint, updated_at = excluded.updated_at",
+                    sql_params![run_id, workflow_id, status, checkpoint_json, now],
+                )?;
+                debug!(run_id = %run_id, status = %status, "workflow run checkpointed");
+                Ok(())
+            })
+            .await
+    }
+
+    /// Fetch a workflow run checkpoint, returning `None` if not found.
+    #[instrument(skip(self))]
+    pub async fn get_run(&self, run_id: &str) -> StoreResult<Option<StoredWorkflowRun>> {
+        let run_id = run_id.to_string();
+        self.db
+            .with_conn(move |conn| {
+                conn.query_opt(
+                    "SELECT id, workflow_id, status, checkpoint, created_at, updated_at \
+                     FROM workflow_runs WHERE id = ?1",
+                    sql_params![run_id],
+                    run_row,
+                )?
+                .map(|row| row.into_stored_run())
+                .transpose()
+            })
+            .await
+    }
+
+    /// List workflow runs with the given status, oldest first.
+    #[instrument(skip(self))]
+    pub async fn list_runs(&self, status: &str) -> StoreResult<Vec<StoredWorkflowRun>> {
+        let status = status.to_string();
+        self.db
+            .with_conn(move |conn| {
+                let rows = conn.query_map(
+                    "SELECT id, workflow_id, status, checkpoint, created_at, updated_at \
+                     FROM workflow_runs WHERE status = ?1 ORDER BY created_at ASC, id ASC",
+                    sql_params![status],
+                    run_row,
+                )?;
+
+                rows.into_iter().map(|r| r.into_stored_run()).collect()
+            })
+            .await
+    }
+
+    /// Delete a workflow run checkpoint.  Returns `true` if it existed.
+    #[instrument(skip(self))]
+    pub async fn delete_run(&self, run_id: &str) -> StoreResult<bool> {
+        let run_id = run_id.to_string();
+        self.db
+            .with_conn(move |conn| {
+                let deleted = conn.execute(
+                    "DELETE FROM workflow_runs WHERE id = ?1",
+                    sql_params![run_id],
+                )?;
+                Ok(deleted > 0)
+            })
+            .await
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════
 //  Internal row mapping
 // ═══════════════════════════════════════════════════════════════════════
 
-/// Raw row data from SQLite before JSON deserialization.
+/// Raw row data before JSON deserialization.
 ///
-/// Keeps the `rusqlite` row-mapping closure simple (no fallible JSON
-/// parsing inside `|row| { ... }`), then converts to `StoredWorkflow`
-/// in a second step where we can return `StoreError::Json`.
+/// Keeps the row mapping simple (no JSON parsing inside
+/// [`WorkflowRow::from_row`]), then converts to `StoredWorkflow` in a
+/// second step where we can return `StoreError::Json`.
 struct WorkflowRow {
     id: String,
     name: String,
@@ -349,6 +398,21 @@ struct WorkflowRow {
 }
 
 impl WorkflowRow {
+    /// Map a `workflows` row (in the canonical column order).
+    fn from_row(row: &SqlRow) -> StoreResult<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            description: row.get(2)?,
+            intent_raw: row.get(3)?,
+            steps: row.get(4)?,
+            trigger: row.get(5)?,
+            enabled: row.get(6)?,
+            created_at: row.get(7)?,
+            updated_at: row.get(8)?,
+        })
+    }
+
     /// Convert raw row strings into a fully deserialized `StoredWorkflow`.
     fn into_stored_workflow(self) -> StoreResult<StoredWorkflow> {
         let steps: serde_json::Value = serde_json::from_str(&self.steps)?;
@@ -369,6 +433,42 @@ impl WorkflowRow {
     }
 }
 
+/// Raw `workflow_runs` row before the checkpoint JSON is parsed.
+struct WorkflowRunRow {
+    id: String,
+    workflow_id: String,
+    status: String,
+    checkpoint: String,
+    created_at: i64,
+    updated_at: i64,
+}
+
+/// Map a `workflow_runs` row (in the canonical column order).
+fn run_row(row: &SqlRow) -> StoreResult<WorkflowRunRow> {
+    Ok(WorkflowRunRow {
+        id: row.get(0)?,
+        workflow_id: row.get(1)?,
+        status: row.get(2)?,
+        checkpoint: row.get(3)?,
+        created_at: row.get(4)?,
+        updated_at: row.get(5)?,
+    })
+}
+
+impl WorkflowRunRow {
+    /// Parse the checkpoint JSON into a `StoredWorkflowRun`.
+    fn into_stored_run(self) -> StoreResult<StoredWorkflowRun> {
+        Ok(StoredWorkflowRun {
+            id: self.id,
+            workflow_id: self.workflow_id,
+            status: self.status,
+            checkpoint: serde_json::from_str(&self.checkpoint)?,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        })
+    }
+}
+
 // ── tests ────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -721,4 +821,30 @@ mod tests {
         // The updated workflow should be first.
         assert_eq!(all[0].id, w1.id);
     }
+
+    #[tokio::test]
+    async fn run_checkpoints_roundtrip() {
+        let db = setup_db().await;
+        let store = WorkflowStore::new(db);
+
+        store
+            .save_run("run-1", "wf-1", "running", &json!({"next_step": 1}))
+            .await
+            .unwrap();
+        store
+            .save_run("run-1", "wf-1", "paused", &json!({"next_step": 2}))
+            .await
+            .unwrap();
+
+        let run = store.get_run("run-1").await.unwrap().unwrap();
+        assert_eq!(run.status, "paused");
+        assert_eq!(run.checkpoint["next_step"], 2);
+        assert!(store.get_run("missing").await.unwrap().is_none());
+
+        assert_eq!(store.list_runs("paused").await.unwrap().len(), 1);
+        assert!(store.list_runs("running").await.unwrap().is_empty());
+
+        assert!(store.delete_run("run-1").await.unwrap());
+        assert!(!store.delete_run("run-1").await.unwrap());
+    }
 }
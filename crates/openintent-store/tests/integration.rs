@@ -179,6 +179,7 @@ async fn memory_full_lifecycle() {
             content: "Rust is a systems programming language".to_string(),
             embedding: None,
             importance: 0.8,
+            ttl: None,
         })
         .await
         .unwrap();
@@ -237,6 +238,7 @@ async fn memory_search_with_category_filter() {
             content: "Rust has zero-cost abstractions".to_string(),
             embedding: None,
             importance: 0.7,
+            ttl: None,
         })
         .await
         .unwrap();
@@ -247,6 +249,7 @@ async fn memory_search_with_category_filter() {
             content: "User prefers Rust over C++".to_string(),
             embedding: None,
             importance: 0.6,
+            ttl: None,
         })
         .await
         .unwrap();
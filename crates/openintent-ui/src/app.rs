@@ -1,17 +1,21 @@
 //! Main iced desktop application.
 //!
 //! Implements the three-panel chat layout using iced 0.13's builder-pattern
-//! API.  The application communicates with a background agent task via
-//! [`tokio::sync::mpsc`] channels, following the same architecture as the
-//! TUI crate.
+//! API.  Each submitted message runs [`react_loop`] on a background task,
+//! which reports streamed text and tool calls through a [`ChannelRenderer`]
+//! over a [`tokio::sync::mpsc`] channel, following the same architecture as
+//! the TUI crate.
 
 use std::sync::Arc;
 
 use iced::widget::{button, column, container, row, scrollable, text, text_input};
 use iced::{Color, Element, Length, Subscription, Task, Theme};
+use openintent_agent::{AgentConfig, AgentContext, LlmClient, ToolAdapter, react_loop};
+use serde_json::Value;
 use tokio::sync::{Mutex, mpsc};
 
 use crate::chat::{ChatMessage, MessageRole};
+use crate::render::{Renderer, attach_renderer};
 use crate::theme;
 
 // ---------------------------------------------------------------------------
@@ -21,6 +25,8 @@ use crate::theme;
 /// Events sent from a background agent task to the UI.
 #[derive(Debug, Clone)]
 pub enum AgentEvent {
+    /// A chunk of the response was streamed.
+    Delta(String),
     /// The agent produced a text response.
     Response(String),
     /// A tool invocation has started.
@@ -31,6 +37,40 @@ pub enum AgentEvent {
     Error(String),
 }
 
+/// A [`Renderer`] that forwards agent output to the UI as [`AgentEvent`]s.
+#[derive(Debug, Clone)]
+pub struct ChannelRenderer {
+    tx: mpsc::UnboundedSender<AgentEvent>,
+}
+
+impl ChannelRenderer {
+    /// Create a renderer sending events to `tx`.
+    pub fn new(tx: mpsc::UnboundedSender<AgentEvent>) -> Self {
+        Self { tx }
+    }
+}
+
+impl Renderer for ChannelRenderer {
+    fn render_text(&self, delta: &str) {
+        if !delta.is_empty() {
+            let _ = self.tx.send(AgentEvent::Delta(delta.to_owned()));
+        }
+    }
+
+    fn render_tool_start(&self, tool_name: &str, _arguments: &Value) {
+        let _ = self.tx.send(AgentEvent::ToolStart(tool_name.to_owned()));
+    }
+}
+
+/// Everything needed to run the agent for the desktop UI.
+#[derive(Clone)]
+pub struct AgentSession {
+    pub llm: Arc<LlmClient>,
+    pub adapters: Vec<Arc<dyn ToolAdapter>>,
+    pub config: AgentConfig,
+    pub system_prompt: String,
+}
+
 // ---------------------------------------------------------------------------
 // Application messages
 // ---------------------------------------------------------------------------
@@ -60,6 +100,12 @@ struct OpenIntentApp {
     input: String,
     /// Whether the agent is processing a request.
     thinking: bool,
+    /// Whether the last chat message is an assistant reply still streaming.
+    streaming: bool,
+    /// The agent to run; `None` when no LLM is configured.
+    session: Option<AgentSession>,
+    /// Conversation so far, as sent to the LLM.
+    history: Vec<openintent_agent::Message>,
     /// Sender half — kept for spawning agent tasks.
     event_tx: mpsc::UnboundedSender<AgentEvent>,
     /// Receiver half, wrapped in Arc<Mutex> so the subscription can share it.
@@ -68,7 +114,7 @@ struct OpenIntentApp {
 
 impl OpenIntentApp {
     /// Initialize the application state and return any startup tasks.
-    fn new() -> (Self, Task<Message>) {
+    fn new(session: Option<AgentSession>) -> (Self, Task<Message>) {
         let (event_tx, event_rx) = mpsc::unbounded_channel();
 
         let app = Self {
@@ -77,6 +123,9 @@ impl OpenIntentApp {
             )],
             input: String::new(),
             thinking: false,
+            streaming: false,
+            session,
+            history: Vec::new(),
             event_tx,
             event_rx: Arc::new(Mutex::new(event_rx)),
         };
@@ -102,28 +151,39 @@ impl OpenIntentApp {
                 self.input.clear();
                 self.thinking = true;
 
-                // Since the agent is not wired yet, simulate a placeholder
-                // response after a short delay.
                 let tx = self.event_tx.clone();
-                Task::perform(
-                    async move {
-                        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
-                        let _ = tx.send(AgentEvent::Response(
-                            "Agent not connected yet. This is a placeholder response.".to_owned(),
-                        ));
-                    },
-                    |()| Message::Tick,
-                )
+                let Some(session) = self.session.clone() else {
+                    let _ = tx.send(AgentEvent::Error("no agent is connected".to_owned()));
+                    return Task::none();
+                };
+                let history = self.history.clone();
+                self.history
+                    .push(openintent_agent::Message::user(trimmed.as_str()));
+                Task::perform(run_agent(session, history, trimmed, tx), |()| Message::Tick)
             }
             Message::AgentEvent(event) => {
                 match event {
+                    AgentEvent::Delta(delta) => match self.messages.last_mut() {
+                        Some(last) if self.streaming => last.content.push_str(&delta),
+                        _ => {
+                            self.messages.push(ChatMessage::assistant(delta));
+                            self.streaming = true;
+                        }
+                    },
                     AgentEvent::Response(resp) => {
                         tracing::debug!("agent response received");
-                        self.messages.push(ChatMessage::assistant(resp));
+                        self.history
+                            .push(openintent_agent::Message::assistant(resp.as_str()));
+                        match self.messages.last_mut() {
+                            Some(last) if self.streaming => last.content = resp,
+                            _ => self.messages.push(ChatMessage::assistant(resp)),
+                        }
+                        self.streaming = false;
                         self.thinking = false;
                     }
                     AgentEvent::ToolStart(name) => {
                         tracing::debug!(tool = %name, "tool invocation started");
+                        self.streaming = false;
                         self.messages
                             .push(ChatMessage::tool(format!("Calling tool: {name}")));
                     }
@@ -139,6 +199,7 @@ impl OpenIntentApp {
                         tracing::warn!(error = %msg, "agent error");
                         self.messages
                             .push(ChatMessage::error(format!("Error: {msg}")));
+                        self.streaming = false;
                         self.thinking = false;
                     }
                 }
@@ -258,6 +319,27 @@ impl OpenIntentApp {
     }
 }
 
+/// Run the ReAct loop for one user message, streaming its output to `tx`
+/// and finishing with a [`AgentEvent::Response`] or [`AgentEvent::Error`].
+async fn run_agent(
+    session: AgentSession,
+    history: Vec<openintent_agent::Message>,
+    input: String,
+    tx: mpsc::UnboundedSender<AgentEvent>,
+) {
+    let mut ctx = AgentContext::new(session.llm, session.adapters, session.config)
+        .with_system_prompt(session.system_prompt);
+    ctx.messages.extend(history);
+    ctx = ctx.with_user_message(input);
+    attach_renderer(&mut ctx, Arc::new(ChannelRenderer::new(tx.clone())));
+
+    let event = match react_loop(&mut ctx).await {
+        Ok(response) => AgentEvent::Response(response.text),
+        Err(e) => AgentEvent::Error(e.to_string()),
+    };
+    let _ = tx.send(event);
+}
+
 // ---------------------------------------------------------------------------
 // Public entry point
 // ---------------------------------------------------------------------------
@@ -265,11 +347,12 @@ impl OpenIntentApp {
 /// Launch the iced desktop UI application.
 ///
 /// This is the main entry point called from the CLI or other binary crates.
-pub fn run_desktop_ui() -> iced::Result {
+/// Messages are answered by the agent described by `session`.
+pub fn run_desktop_ui(session: AgentSession) -> iced::Result {
     iced::application("OpenIntentOS", OpenIntentApp::update, OpenIntentApp::view)
         .subscription(OpenIntentApp::subscription)
         .window_size((900.0, 650.0))
-        .run_with(OpenIntentApp::new)
+        .run_with(move || OpenIntentApp::new(Some(session)))
 }
 
 // ---------------------------------------------------------------------------
@@ -282,7 +365,7 @@ mod tests {
 
     #[test]
     fn new_app_has_welcome_message() {
-        let (app, _task) = OpenIntentApp::new();
+        let (app, _task) = OpenIntentApp::new(None);
         assert_eq!(app.messages.len(), 1);
         assert_eq!(app.messages[0].role, MessageRole::System);
         assert!(app.messages[0].content.contains("Welcome"));
@@ -290,26 +373,26 @@ mod tests {
 
     #[test]
     fn new_app_is_not_thinking() {
-        let (app, _task) = OpenIntentApp::new();
+        let (app, _task) = OpenIntentApp::new(None);
         assert!(!app.thinking);
     }
 
     #[test]
     fn new_app_has_empty_input() {
-        let (app, _task) = OpenIntentApp::new();
+        let (app, _task) = OpenIntentApp::new(None);
         assert!(app.input.is_empty());
     }
 
     #[test]
     fn input_changed_updates_input() {
-        let (mut app, _task) = OpenIntentApp::new();
+        let (mut app, _task) = OpenIntentApp::new(None);
         let _ = app.update(Message::InputChanged("hello".to_owned()));
         assert_eq!(app.input, "hello");
     }
 
     #[test]
     fn submit_with_empty_input_does_nothing() {
-        let (mut app, _task) = OpenIntentApp::new();
+        let (mut app, _task) = OpenIntentApp::new(None);
         let _ = app.update(Message::Submit);
         assert_eq!(app.messages.len(), 1);
         assert!(!app.thinking);
@@ -317,7 +400,7 @@ mod tests {
 
     #[test]
     fn submit_with_whitespace_only_does_nothing() {
-        let (mut app, _task) = OpenIntentApp::new();
+        let (mut app, _task) = OpenIntentApp::new(None);
         let _ = app.update(Message::InputChanged("   ".to_owned()));
         let _ = app.update(Message::Submit);
         assert_eq!(app.messages.len(), 1);
@@ -326,7 +409,7 @@ mod tests {
 
     #[test]
     fn submit_adds_user_message_and_sets_thinking() {
-        let (mut app, _task) = OpenIntentApp::new();
+        let (mut app, _task) = OpenIntentApp::new(None);
         let _ = app.update(Message::InputChanged("Hello agent".to_owned()));
         let _ = app.update(Message::Submit);
         assert_eq!(app.messages.len(), 2);
@@ -338,7 +421,7 @@ mod tests {
 
     #[test]
     fn submit_while_thinking_is_ignored() {
-        let (mut app, _task) = OpenIntentApp::new();
+        let (mut app, _task) = OpenIntentApp::new(None);
         let _ = app.update(Message::InputChanged("first".to_owned()));
         let _ = app.update(Message::Submit);
         let _ = app.update(Message::InputChanged("second".to_owned()));
@@ -348,7 +431,7 @@ mod tests {
 
     #[test]
     fn agent_response_adds_assistant_message_and_clears_thinking() {
-        let (mut app, _task) = OpenIntentApp::new();
+        let (mut app, _task) = OpenIntentApp::new(None);
         app.thinking = true;
         let _ = app.update(Message::AgentEvent(AgentEvent::Response(
             "Hello!".to_owned(),
@@ -361,7 +444,7 @@ mod tests {
 
     #[test]
     fn agent_error_adds_error_message_and_clears_thinking() {
-        let (mut app, _task) = OpenIntentApp::new();
+        let (mut app, _task) = OpenIntentApp::new(None);
         app.thinking = true;
         let _ = app.update(Message::AgentEvent(AgentEvent::Error("timeout".to_owned())));
         assert_eq!(app.messages.len(), 2);
@@ -372,7 +455,7 @@ mod tests {
 
     #[test]
     fn tool_start_adds_tool_message() {
-        let (mut app, _task) = OpenIntentApp::new();
+        let (mut app, _task) = OpenIntentApp::new(None);
         let _ = app.update(Message::AgentEvent(AgentEvent::ToolStart(
             "filesystem".to_owned(),
         )));
@@ -383,7 +466,7 @@ mod tests {
 
     #[test]
     fn tool_end_truncates_long_results() {
-        let (mut app, _task) = OpenIntentApp::new();
+        let (mut app, _task) = OpenIntentApp::new(None);
         let long_result = "x".repeat(600);
         let _ = app.update(Message::AgentEvent(AgentEvent::ToolEnd(long_result)));
         assert_eq!(app.messages.len(), 2);
@@ -394,14 +477,43 @@ mod tests {
 
     #[test]
     fn tool_end_short_result_not_truncated() {
-        let (mut app, _task) = OpenIntentApp::new();
+        let (mut app, _task) = OpenIntentApp::new(None);
         let _ = app.update(Message::AgentEvent(AgentEvent::ToolEnd("done".to_owned())));
         assert_eq!(app.messages[1].content, "done");
     }
 
+    #[test]
+    fn streamed_deltas_update_displayed_text() {
+        let (mut app, _task) = OpenIntentApp::new(None);
+        app.thinking = true;
+        let _ = app.update(Message::AgentEvent(AgentEvent::Delta("Hel".to_owned())));
+        let _ = app.update(Message::AgentEvent(AgentEvent::Delta("lo".to_owned())));
+        assert_eq!(app.messages.len(), 2);
+        assert_eq!(app.messages[1].role, MessageRole::Assistant);
+        assert_eq!(app.messages[1].content, "Hello");
+        assert!(app.thinking);
+
+        let _ = app.update(Message::AgentEvent(AgentEvent::Response(
+            "Hello!".to_owned(),
+        )));
+        assert_eq!(app.messages.len(), 2);
+        assert_eq!(app.messages[1].content, "Hello!");
+        assert!(!app.thinking);
+    }
+
+    #[test]
+    fn channel_renderer_forwards_deltas_and_tool_starts() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let renderer = ChannelRenderer::new(tx);
+        renderer.render_text("Hi");
+        renderer.render_tool_start("web_search", &Value::Null);
+        assert!(matches!(rx.try_recv(), Ok(AgentEvent::Delta(d)) if d == "Hi"));
+        assert!(matches!(rx.try_recv(), Ok(AgentEvent::ToolStart(t)) if t == "web_search"));
+    }
+
     #[test]
     fn tick_message_is_noop() {
-        let (mut app, _task) = OpenIntentApp::new();
+        let (mut app, _task) = OpenIntentApp::new(None);
         let msg_count = app.messages.len();
         let _ = app.update(Message::Tick);
         assert_eq!(app.messages.len(), msg_count);
@@ -409,7 +521,7 @@ mod tests {
 
     #[test]
     fn event_tx_can_send() {
-        let (app, _task) = OpenIntentApp::new();
+        let (app, _task) = OpenIntentApp::new(None);
         let result = app.event_tx.send(AgentEvent::Response("test".to_owned()));
         assert!(result.is_ok());
     }
@@ -8,7 +8,9 @@ pub mod app;
 pub mod chat;
 pub mod error;
 pub mod launcher;
+pub mod render;
 pub mod theme;
 
-pub use app::run_desktop_ui;
+pub use app::{AgentSession, ChannelRenderer, run_desktop_ui};
 pub use error::{Result, UiError};
+pub use render::{Renderer, StdoutRenderer, attach_renderer, renderer_callbacks};
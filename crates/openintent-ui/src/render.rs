@@ -0,0 +1,259 @@
+//! Front-end-agnostic rendering of agent output.
+//!
+//! The agent runtime reports progress through two callbacks on
+//! [`AgentContext`]: [`TextDeltaCallback`] for streamed response text and
+//! [`ToolStartCallback`] for tool invocations.  A [`Renderer`] receives the
+//! same events through one interface, so the CLI, TUI, desktop, and web
+//! front ends only implement presentation.  Tools that need the user mid-run
+//! (e.g. to confirm a deletion) ask through [`Renderer::prompt`].
+//! [`attach_renderer`] wires a renderer into a context.
+
+use std::io::{self, BufRead, Write};
+use std::sync::{Arc, Mutex};
+
+use openintent_agent::{AgentContext, TextDeltaCallback, ToolStartCallback};
+use serde_json::Value;
+
+/// Receives agent output as it is produced.
+pub trait Renderer: Send + Sync {
+    /// Render a chunk of streamed response text.
+    fn render_text(&self, delta: &str);
+
+    /// Render the start of a tool invocation.
+    fn render_tool_start(&self, tool_name: &str, arguments: &Value);
+
+    /// Ask the user `question` and return their answer, or `None` if this
+    /// front end cannot take input.  May block: the runtime calls it on the
+    /// blocking thread pool.
+    fn prompt(&self, _question: &str) -> Option<String> {
+        None
+    }
+}
+
+/// Adapt `renderer` into the runtime's streaming callbacks.
+pub fn renderer_callbacks(renderer: Arc<dyn Renderer>) -> (TextDeltaCallback, ToolStartCallback) {
+    let text = Arc::clone(&renderer);
+    (
+        Arc::new(Mutex::new(move |delta: &str| text.render_text(delta))),
+        Arc::new(move |name: &str, args: &Value| renderer.render_tool_start(name, args)),
+    )
+}
+
+/// Route the streamed output and tool prompts of `ctx` to `renderer`,
+/// replacing any callbacks already installed.
+pub fn attach_renderer(ctx: &mut AgentContext, renderer: Arc<dyn Renderer>) {
+    let prompter = Arc::clone(&renderer);
+    let (on_text_delta, on_tool_start) = renderer_callbacks(renderer);
+    ctx.on_text_delta = Some(on_text_delta);
+    ctx.on_tool_start = Some(on_tool_start);
+    ctx.on_prompt = Some(Arc::new(move |question: &str| prompter.prompt(question)));
+}
+
+/// Writes response text to stdout as it streams, with tool invocations on
+/// their own `[calling tool X]` lines.
+#[derive(Debug, Default)]
+pub struct StdoutRenderer {
+    /// Whether the cursor is in the middle of a line of streamed text.
+    mid_line: Mutex<bool>,
+    /// Held for a whole question and answer, so tools running in parallel
+    /// take turns at the terminal.
+    prompting: Mutex<()>,
+}
+
+impl StdoutRenderer {
+    /// Create a stdout renderer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Renderer for StdoutRenderer {
+    fn render_text(&self, delta: &str) {
+        if delta.is_empty() {
+            return;
+        }
+        let mut mid_line = self.mid_line.lock().unwrap_or_else(|e| e.into_inner());
+        *mid_line = !delta.ends_with('\n');
+        let mut out = io::stdout().lock();
+        let _ = out.write_all(delta.as_bytes());
+        let _ = out.flush();
+    }
+
+    fn render_tool_start(&self, tool_name: &str, _arguments: &Value) {
+        let mut mid_line = self.mid_line.lock().unwrap_or_else(|e| e.into_inner());
+        let mut out = io::stdout().lock();
+        if *mid_line {
+            let _ = writeln!(out);
+            *mid_line = false;
+        }
+        let _ = writeln!(out, "  [calling tool {tool_name}]");
+        let _ = out.flush();
+    }
+
+    fn prompt(&self, question: &str) -> Option<String> {
+        let _turn = self.prompting.lock().unwrap_or_else(|e| e.into_inner());
+        {
+            let mut mid_line = self.mid_line.lock().unwrap_or_else(|e| e.into_inner());
+            let mut out = io::stdout().lock();
+            if *mid_line {
+                let _ = writeln!(out);
+                *mid_line = false;
+            }
+            let _ = write!(out, "  {question} ");
+            let _ = out.flush();
+        }
+        let mut answer = String::new();
+        match io::stdin().lock().read_line(&mut answer) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(answer.trim_end().to_owned()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use openintent_agent::{
+        AgentConfig, LlmClient, LlmClientConfig, LlmResponse, PromptFn, ScriptedBackend,
+        ToolAdapter, ToolCall, ToolDefinition, confirm, react_loop,
+    };
+
+    use super::*;
+
+    /// Records every event it is asked to render and answers every prompt
+    /// with `answer`.
+    #[derive(Default)]
+    struct RecordingRenderer {
+        events: Mutex<Vec<String>>,
+        answer: Option<&'static str>,
+    }
+
+    impl Renderer for RecordingRenderer {
+        fn render_text(&self, delta: &str) {
+            self.events.lock().unwrap().push(format!("text:{delta}"));
+        }
+
+        fn render_tool_start(&self, tool_name: &str, _arguments: &Value) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("tool:{tool_name}"));
+        }
+
+        fn prompt(&self, question: &str) -> Option<String> {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("prompt:{question}"));
+            self.answer.map(String::from)
+        }
+    }
+
+    /// A destructive tool that deletes only after the user confirms.
+    struct DeleteFilesAdapter;
+
+    #[async_trait]
+    impl ToolAdapter for DeleteFilesAdapter {
+        fn adapter_id(&self) -> &str {
+            "filesystem"
+        }
+
+        fn tool_definitions(&self) -> Vec<ToolDefinition> {
+            vec![ToolDefinition {
+                name: "fs_delete".into(),
+                description: "Delete files".into(),
+                input_schema: serde_json::json!({"type": "object"}),
+                destructive: true,
+            }]
+        }
+
+        async fn execute(
+            &self,
+            _tool_name: &str,
+            _arguments: Value,
+        ) -> openintent_agent::Result<String> {
+            Ok("cancelled: no confirmation available".into())
+        }
+
+        async fn execute_with_prompt(
+            &self,
+            _tool_name: &str,
+            _arguments: Value,
+            prompt: Option<PromptFn>,
+        ) -> openintent_agent::Result<String> {
+            if confirm(prompt.as_ref(), "Delete 3 files? [y/N]").await {
+                Ok("deleted 3 files".into())
+            } else {
+                Ok("cancelled by user".into())
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn render_text_receives_streamed_deltas() {
+        let backend = Arc::new(ScriptedBackend::new([LlmResponse::Text(
+            "Hello from the agent.".into(),
+        )]));
+        let llm = LlmClient::new(LlmClientConfig::anthropic("test-key", "test-model"))
+            .unwrap()
+            .with_backend(backend);
+        let renderer = Arc::new(RecordingRenderer::default());
+
+        let mut ctx = AgentContext::new(Arc::new(llm), vec![], AgentConfig::default())
+            .with_user_message("Say hello");
+        attach_renderer(&mut ctx, renderer.clone());
+        let response = react_loop(&mut ctx).await.unwrap();
+
+        assert_eq!(response.text, "Hello from the agent.");
+        assert_eq!(
+            *renderer.events.lock().unwrap(),
+            ["text:Hello from the agent."]
+        );
+
+        let on_tool_start = ctx.on_tool_start.expect("tool callback must be attached");
+        on_tool_start("shell_exec", &Value::Null);
+        assert_eq!(renderer.events.lock().unwrap()[1], "tool:shell_exec");
+    }
+
+    #[tokio::test]
+    async fn confirmed_prompt_lets_destructive_tool_proceed() {
+        let backend = Arc::new(ScriptedBackend::new([
+            LlmResponse::ToolCalls(vec![ToolCall {
+                id: "call_1".into(),
+                name: "fs_delete".into(),
+                arguments: Value::Null,
+            }]),
+            LlmResponse::Text("Done.".into()),
+        ]));
+        let llm = LlmClient::new(LlmClientConfig::anthropic("test-key", "test-model"))
+            .unwrap()
+            .with_backend(backend.clone());
+        let renderer = Arc::new(RecordingRenderer {
+            answer: Some("y"),
+            ..RecordingRenderer::default()
+        });
+
+        let mut ctx = AgentContext::new(
+            Arc::new(llm),
+            vec![Arc::new(DeleteFilesAdapter)],
+            AgentConfig::default(),
+        )
+        .with_user_message("Clean up the temp files");
+        attach_renderer(&mut ctx, renderer.clone());
+        react_loop(&mut ctx).await.unwrap();
+
+        assert!(
+            renderer
+                .events
+                .lock()
+                .unwrap()
+                .contains(&"prompt:Delete 3 files? [y/N]".to_owned())
+        );
+        let tool_result = &backend.requests()[1].messages;
+        assert!(
+            tool_result
+                .iter()
+                .any(|m| m.content.contains("deleted 3 files"))
+        );
+    }
+}
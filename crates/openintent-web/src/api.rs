@@ -15,6 +15,7 @@ use serde_json::{Value, json};
 
 use openintent_agent::{AgentConfig, AgentContext, react_loop};
 
+use crate::auth::AuthUser;
 use crate::state::AppState;
 use crate::ws::AdapterBridge;
 
@@ -282,6 +283,7 @@ pub struct ChatBody {
 ///   `MAX_RETRIES` attempts on the same provider.
 pub async fn chat(
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
     Json(body): Json<ChatBody>,
 ) -> (StatusCode, Json<Value>) {
     const MAX_RETRIES: u32 = 3;
@@ -302,6 +304,9 @@ pub async fn chat(
         let mut ctx = AgentContext::new(Arc::clone(&state.llm), tool_adapters, config)
             .with_system_prompt(&system_prompt)
             .with_user_message(&body.message);
+        if let Some(role) = user.role {
+            ctx = ctx.with_user_role(role);
+        }
 
         match react_loop(&mut ctx).await {
             Ok(response) => {
@@ -0,0 +1,164 @@
+//! Who is calling the API.
+//!
+//! Once any account exists in the user store, the chat endpoints require
+//! HTTP Basic credentials for an active user, and the agent then acts with
+//! that user's [`UserRole`] (see [`openintent_agent::check_role`]).  Without
+//! accounts the server is a single-user install and chats run without role
+//! restrictions.
+
+use std::sync::Arc;
+
+use axum::Json;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use base64::Engine;
+use serde_json::json;
+
+use openintent_store::{UserRole, UserStore};
+
+use crate::state::AppState;
+
+/// The user a request acts for, extracted from its `Authorization` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuthUser {
+    /// Role of the authenticated user; `None` when the store has no
+    /// accounts.
+    pub role: Option<UserRole>,
+}
+
+impl FromRequestParts<Arc<AppState>> for AuthUser {
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        let users = UserStore::new(state.db.clone());
+        let Some((username, password)) = basic_credentials(parts) else {
+            let accounts = users.list(1, 0).await.map_err(internal_error)?;
+            if accounts.is_empty() {
+                return Ok(Self { role: None });
+            }
+            return Err(unauthorized("sign in with a user account"));
+        };
+        match users
+            .authenticate(&username, &password)
+            .await
+            .map_err(internal_error)?
+        {
+            Some(user) => Ok(Self {
+                role: Some(user.role),
+            }),
+            None => Err(unauthorized("invalid username or password")),
+        }
+    }
+}
+
+/// Username and password from a `Basic` `Authorization` header.
+fn basic_credentials(parts: &Parts) -> Option<(String, String)> {
+    let value = parts.headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, encoded) = value.split_once(' ')?;
+    if !scheme.eq_ignore_ascii_case("basic") {
+        return None;
+    }
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .ok()?;
+    let decoded = String::from_utf8(decoded).ok()?;
+    let (username, password) = decoded.split_once(':')?;
+    Some((username.to_string(), password.to_string()))
+}
+
+/// `401 Unauthorized` asking the browser for Basic credentials.
+fn unauthorized(reason: &str) -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        [(header::WWW_AUTHENTICATE, r#"Basic realm="OpenIntentOS""#)],
+        Json(json!({ "error": reason })),
+    )
+        .into_response()
+}
+
+fn internal_error(e: openintent_store::StoreError) -> Response {
+    tracing::error!(error = %e, "failed to authenticate request");
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({ "error": "failed to authenticate request" })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::Router;
+    use axum::routing::get;
+    use openintent_agent::{LlmClient, LlmClientConfig};
+    use openintent_kernel::IpcBus;
+    use openintent_store::{Database, SessionStore};
+    use tokio::sync::RwLock;
+
+    use super::*;
+    use crate::WebConfig;
+
+    async fn role(user: AuthUser) -> String {
+        format!("{:?}", user.role)
+    }
+
+    /// Serve a route that reports the caller's role; returns its URL.
+    async fn serve(db: Database) -> String {
+        let state = Arc::new(AppState {
+            llm: Arc::new(LlmClient::new(LlmClientConfig::anthropic("test", "test")).unwrap()),
+            adapters: Vec::new(),
+            config: WebConfig::default(),
+            sessions: Arc::new(SessionStore::new(db.clone())),
+            db,
+            system_prompt: Arc::new(RwLock::new(String::new())),
+            evolution: None,
+            bus: IpcBus::new(16),
+        });
+        let router = Router::new().route("/role", get(role)).with_state(state);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        format!("http://{addr}/role")
+    }
+
+    #[tokio::test]
+    async fn role_comes_from_the_authenticated_user() {
+        let db = Database::open_in_memory().unwrap();
+        db.run_migrations().await.unwrap();
+        let url = serve(db.clone()).await;
+        let client = reqwest::Client::new();
+
+        // No accounts yet: a single-user install runs unrestricted.
+        let response = client.get(&url).send().await.unwrap();
+        assert_eq!(response.text().await.unwrap(), "None");
+
+        UserStore::new(db)
+            .create("vera", None, "s3cret", UserRole::Viewer)
+            .await
+            .unwrap();
+
+        let response = client.get(&url).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 401);
+        assert!(response.headers().contains_key("www-authenticate"));
+
+        let response = client
+            .get(&url)
+            .basic_auth("vera", Some("wrong"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 401);
+
+        let response = client
+            .get(&url)
+            .basic_auth("vera", Some("s3cret"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), "Some(Viewer)");
+    }
+}
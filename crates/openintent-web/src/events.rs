@@ -0,0 +1,138 @@
+//! Server-sent events stream of kernel bus events.
+//!
+//! `GET /api/events` forwards events published on the server's
+//! [`IpcBus`] as SSE frames, so dashboards get a live view without
+//! polling.  Each frame's `event` field is the event's
+//! [topic](openintent_kernel::Event::topic) and its `data` the event as
+//! JSON.  `?topics=health,task` restricts the stream to those topics; by
+//! default adapter health changes and task status changes are sent.
+
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use axum::extract::{Query, State};
+use axum::response::sse::{self, KeepAlive, Sse};
+use futures::Stream;
+use openintent_kernel::Event;
+use serde::Deserialize;
+use tokio::sync::broadcast;
+
+use crate::state::AppState;
+
+/// Topics streamed when the client does not choose any.
+const DEFAULT_TOPICS: &[&str] = &["health", "task"];
+
+/// Query parameters for `GET /api/events`.
+#[derive(Debug, Default, Deserialize)]
+pub struct EventsQuery {
+    /// Comma-separated topics to stream.
+    pub topics: Option<String>,
+}
+
+/// Stream bus events as server-sent events.
+pub async fn events(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<EventsQuery>,
+) -> Sse<impl Stream<Item = Result<sse::Event, Infallible>>> {
+    let topics: Vec<String> = match query.topics {
+        Some(topics) => topics
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(String::from)
+            .collect(),
+        None => DEFAULT_TOPICS.iter().map(|t| t.to_string()).collect(),
+    };
+
+    // Subscribe before returning so no event published after the response
+    // headers are sent can be missed.
+    let rx = state.bus.subscribe();
+    let stream = futures::stream::unfold((rx, topics), |(mut rx, topics)| async move {
+        loop {
+            match rx.recv().await {
+                Ok(event) if topics.iter().any(|t| t == event.topic()) => {
+                    let frame = sse_frame(&event);
+                    return Some((Ok(frame), (rx, topics)));
+                }
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    tracing::warn!(missed, "SSE client lagged behind the event bus");
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Encode a bus event as an SSE frame named after its topic.
+fn sse_frame(event: &Event) -> sse::Event {
+    let frame = sse::Event::default().event(event.topic());
+    match frame.clone().json_data(event) {
+        Ok(frame) => frame,
+        Err(e) => {
+            tracing::warn!(error = %e, "failed to encode event for SSE");
+            frame.data("{}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::Utc;
+    use openintent_agent::{LlmClient, LlmClientConfig};
+    use openintent_store::Database;
+
+    use super::*;
+    use crate::{WebConfig, WebServer};
+
+    #[tokio::test]
+    async fn streams_health_change_published_on_bus() {
+        let llm = Arc::new(LlmClient::new(LlmClientConfig::anthropic("test", "test")).unwrap());
+        let server = WebServer::new(
+            WebConfig::default(),
+            llm,
+            Vec::new(),
+            Database::open_in_memory().unwrap(),
+        );
+        let bus = server.bus().clone();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let router = server.router();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let mut response = reqwest::get(format!("http://{addr}/api/events?topics=health"))
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+        // Filtered out by the topic parameter.
+        bus.publish(Event::SystemEvent {
+            kind: "startup".into(),
+            message: "ignored".into(),
+        })
+        .unwrap();
+        bus.publish(Event::AdapterHealthChanged {
+            adapter_id: "slack".into(),
+            status: "unhealthy".into(),
+            timestamp: Utc::now(),
+        })
+        .unwrap();
+
+        let mut body = String::new();
+        while !body.contains("\n\n") {
+            let chunk = tokio::time::timeout(Duration::from_secs(5), response.chunk())
+                .await
+                .expect("no SSE frame within 5s")
+                .unwrap()
+                .expect("stream ended");
+            body.push_str(&String::from_utf8_lossy(&chunk));
+        }
+        assert!(body.contains("event: health"), "{body}");
+        assert!(body.contains(r#""adapter_id":"slack""#), "{body}");
+        assert!(!body.contains("startup"), "{body}");
+    }
+}
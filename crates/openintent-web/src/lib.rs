@@ -5,15 +5,24 @@
 //!
 //! - A REST API for system status and adapter/tool discovery.
 //! - A WebSocket endpoint for real-time streaming of agent output.
-//! - An embedded single-page HTML frontend served at `/`.
+//! - A server-sent events stream of adapter health and task status.
+//! - The embedded frontend (`dist/`) served at `/`, with SPA fallback.
 //! - An MCP (Model Context Protocol) endpoint for tool exposure to LLMs.
+//! - Optional HTTPS, with a generated self-signed certificate by default.
+//! - Per-client rate limiting and a request body size limit.
+//! - HTTP Basic sign-in against the user store once accounts exist; the
+//!   agent acts with the signed-in user's role.
 
 pub mod api;
+pub mod auth;
+pub mod events;
 pub mod frontend;
+pub mod limits;
 pub mod mcp;
 pub mod server;
 pub mod setup;
 pub mod state;
+pub mod tls;
 pub mod ws;
 
 pub use mcp::McpServer;
@@ -23,6 +32,7 @@ pub use setup::{
     is_onboarding_done, serve_setup, write_onboarding_env, write_setup_env,
 };
 pub use state::AppState;
+pub use tls::TlsConfig;
 
 /// Web server configuration.
 #[derive(Debug, Clone)]
@@ -31,6 +41,18 @@ pub struct WebConfig {
     pub bind_addr: String,
     /// The port to listen on.
     pub port: u16,
+    /// Serve HTTPS with this certificate instead of plain HTTP.
+    pub tls: Option<TlsConfig>,
+    /// Origins (`https://app.example.com`) whose pages may call the API
+    /// from the browser.  Empty allows same-origin requests only.
+    pub allowed_origins: Vec<String>,
+    /// Requests each client (IP address or bearer token) may make per
+    /// minute before getting `429 Too Many Requests`; `None` disables the
+    /// limit.
+    pub rate_limit_per_minute: Option<u32>,
+    /// Largest accepted request body, in bytes; larger ones get
+    /// `413 Payload Too Large`.
+    pub max_body_bytes: usize,
 }
 
 impl Default for WebConfig {
@@ -38,6 +60,10 @@ impl Default for WebConfig {
         Self {
             bind_addr: "127.0.0.1".into(),
             port: 23517,
+            tls: None,
+            allowed_origins: Vec::new(),
+            rate_limit_per_minute: Some(300),
+            max_body_bytes: 2 * 1024 * 1024,
         }
     }
 }
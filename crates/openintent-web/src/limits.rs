@@ -0,0 +1,227 @@
+//! Per-client rate limiting for the web API.
+//!
+//! Every client gets a token bucket holding [`WebConfig::rate_limit_per_minute`]
+//! requests that refills continuously.  Clients are keyed by IP address (by
+//! /64 prefix for IPv6, since one host usually owns the whole prefix); the
+//! `Authorization` header is not trusted for this because the API does not
+//! validate it, and rotating tokens would otherwise reset the budget.  A
+//! request that finds its bucket empty is answered with `429 Too Many
+//! Requests` and a `Retry-After` header.
+//!
+//! At most 10,000 buckets are kept: full buckets are dropped first, then
+//! the least recently used ones.
+//!
+//! Request bodies larger than [`WebConfig::max_body_bytes`] are refused with
+//! `413 Payload Too Large` by a separate body-limit layer in the router.
+//!
+//! [`WebConfig::rate_limit_per_minute`]: crate::WebConfig::rate_limit_per_minute
+//! [`WebConfig::max_body_bytes`]: crate::WebConfig::max_body_bytes
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+/// Most buckets kept at once.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// Token buckets for every client seen recently.
+#[derive(Debug)]
+pub struct ClientRateLimiter {
+    per_minute: u32,
+    max_clients: usize,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl ClientRateLimiter {
+    /// Allow each client `per_minute` requests per minute, in bursts of up
+    /// to `per_minute`.
+    pub fn new(per_minute: u32) -> Self {
+        Self {
+            per_minute: per_minute.max(1),
+            max_clients: MAX_TRACKED_CLIENTS,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Take a token for `client`, or return how long until one is available.
+    pub fn check(&self, client: &str) -> Result<(), Duration> {
+        let capacity = f64::from(self.per_minute);
+        let per_second = capacity / 60.0;
+        let now = Instant::now();
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if !buckets.contains_key(client) && buckets.len() >= self.max_clients {
+            // A full bucket behaves exactly like a missing one.
+            buckets.retain(|_, b| {
+                b.tokens + now.duration_since(b.refilled_at).as_secs_f64() * per_second < capacity
+            });
+            if buckets.len() >= self.max_clients
+                && let Some(oldest) = buckets
+                    .iter()
+                    .min_by_key(|(_, b)| b.refilled_at)
+                    .map(|(key, _)| key.clone())
+            {
+                buckets.remove(&oldest);
+            }
+        }
+        let bucket = buckets.entry(client.to_owned()).or_insert(Bucket {
+            tokens: capacity,
+            refilled_at: now,
+        });
+
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * per_second).min(capacity);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / per_second))
+        }
+    }
+}
+
+/// Middleware refusing requests from clients over their rate limit.
+pub async fn rate_limit(
+    State(limiter): State<Arc<ClientRateLimiter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let client = client_key(&request);
+    match limiter.check(&client) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => {
+            tracing::warn!(client = %client, "rate limit exceeded");
+            let secs = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+            let mut response = (
+                StatusCode::TOO_MANY_REQUESTS,
+                "rate limit exceeded; slow down and retry later",
+            )
+                .into_response();
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(secs));
+            response
+        }
+    }
+}
+
+/// Identify the client by its IP address, or its /64 prefix for IPv6.
+fn client_key(request: &Request) -> String {
+    match request.extensions().get::<ConnectInfo<SocketAddr>>() {
+        Some(ConnectInfo(addr)) => match addr.ip().to_canonical() {
+            IpAddr::V4(ip) => format!("ip:{ip}"),
+            IpAddr::V6(ip) => {
+                let prefix = u128::from(ip) & !(u128::from(u64::MAX));
+                format!("ip:{}/64", Ipv6Addr::from(prefix))
+            }
+        },
+        None => "ip:unknown".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::Router;
+    use axum::routing::post;
+    use tower_http::limit::RequestBodyLimitLayer;
+
+    use super::*;
+
+    /// Serve an echo route behind both limits; returns its URL.
+    async fn serve_limited(per_minute: u32, max_body_bytes: usize) -> String {
+        let limiter = Arc::new(ClientRateLimiter::new(per_minute));
+        let router = Router::new()
+            .route("/api/echo", post(|body: String| async move { body }))
+            .layer(RequestBodyLimitLayer::new(max_body_bytes))
+            .layer(axum::middleware::from_fn_with_state(limiter, rate_limit));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(
+                listener,
+                router.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+            .unwrap()
+        });
+        format!("http://{addr}/api/echo")
+    }
+
+    #[tokio::test]
+    async fn exceeding_rate_returns_429_with_retry_after() {
+        let url = serve_limited(3, 1024).await;
+        let client = reqwest::Client::new();
+
+        for _ in 0..3 {
+            let response = client.post(&url).body("hi").send().await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+        let limited = client.post(&url).body("hi").send().await.unwrap();
+        assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+        let retry_after: u64 = limited.headers()[header::RETRY_AFTER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((1..=20).contains(&retry_after), "{retry_after}");
+    }
+
+    #[tokio::test]
+    async fn rotating_bearer_tokens_share_the_ip_budget() {
+        let url = serve_limited(3, 1024).await;
+        let client = reqwest::Client::new();
+
+        let mut statuses = Vec::new();
+        for i in 0..4 {
+            let response = client
+                .post(&url)
+                .bearer_auth(format!("token-{i}"))
+                .body("hi")
+                .send()
+                .await
+                .unwrap();
+            statuses.push(response.status());
+        }
+        assert_eq!(statuses[..3], [StatusCode::OK; 3]);
+        assert_eq!(statuses[3], StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[test]
+    fn tracked_clients_are_bounded() {
+        let limiter = ClientRateLimiter {
+            max_clients: 3,
+            ..ClientRateLimiter::new(1)
+        };
+        for i in 0..10 {
+            assert!(limiter.check(&format!("ip:10.0.0.{i}")).is_ok());
+            assert!(limiter.buckets.lock().unwrap().len() <= 3);
+        }
+        // The most recent client is still tracked and still limited.
+        assert!(limiter.check("ip:10.0.0.9").is_err());
+    }
+
+    #[tokio::test]
+    async fn oversized_body_returns_413() {
+        let url = serve_limited(100, 16).await;
+        let client = reqwest::Client::new();
+
+        let small = client.post(&url).body("small").send().await.unwrap();
+        assert_eq!(small.status(), StatusCode::OK);
+        let large = client.post(&url).body("x".repeat(17)).send().await.unwrap();
+        assert_eq!(large.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
@@ -14,7 +14,10 @@ use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 
 use openintent_adapters::Adapter;
+use openintent_agent::{ToolPermission, check_role};
+use openintent_store::UserRole;
 
+use crate::auth::AuthUser;
 use crate::state::AppState;
 
 // ---------------------------------------------------------------------------
@@ -200,12 +203,23 @@ impl McpToolResult {
 /// MCP protocol server that exposes adapters as tools.
 pub struct McpServer {
     adapters: Vec<Arc<dyn Adapter>>,
+    /// Role tool calls are checked against; `None` allows every tool.
+    user_role: Option<UserRole>,
 }
 
 impl McpServer {
     /// Create a new MCP server backed by the given adapters.
     pub fn new(adapters: Vec<Arc<dyn Adapter>>) -> Self {
-        Self { adapters }
+        Self {
+            adapters,
+            user_role: None,
+        }
+    }
+
+    /// Only run the tools a user with `role` may call.
+    pub fn with_user_role(mut self, role: UserRole) -> Self {
+        self.user_role = Some(role);
+        self
     }
 
     /// Handle a single JSON-RPC request and return a response.
@@ -327,6 +341,20 @@ impl McpServer {
             None => return Err(format!("unknown tool: {name}")),
         };
 
+        if let Some(role) = self.user_role
+            && let Some(tool) = adapter.tools().into_iter().find(|t| t.name == name)
+        {
+            let tool = openintent_agent::ToolDefinition {
+                name: tool.name,
+                description: tool.description,
+                input_schema: tool.parameters,
+                destructive: tool.destructive,
+            };
+            if let ToolPermission::Deny(reason) = check_role(role, &tool) {
+                return Err(format!("tool call denied: {reason}"));
+            }
+        }
+
         match adapter.execute_tool(name, arguments).await {
             Ok(value) => {
                 // Convert the JSON result to a text content block.
@@ -351,8 +379,15 @@ impl McpServer {
 ///
 /// Accepts `POST /mcp` with a JSON body that is either a single JSON-RPC
 /// request object or an array of request objects (batch mode).
-pub async fn handle_mcp_request(State(state): State<Arc<AppState>>, body: String) -> Json<Value> {
-    let mcp = McpServer::new(state.adapters.clone());
+pub async fn handle_mcp_request(
+    State(state): State<Arc<AppState>>,
+    user: AuthUser,
+    body: String,
+) -> Json<Value> {
+    let mut mcp = McpServer::new(state.adapters.clone());
+    if let Some(role) = user.role {
+        mcp = mcp.with_user_role(role);
+    }
 
     // Try to parse as an array first (batch request), then as a single request.
     if let Ok(batch) = serde_json::from_str::<Vec<JsonRpcRequest>>(&body) {
@@ -469,6 +504,7 @@ mod tests {
                 },
                 "required": ["input"]
             }),
+            destructive: false,
         }
     }
 
@@ -688,6 +724,30 @@ mod tests {
         assert!(text.contains("tool execution failed"));
     }
 
+    #[tokio::test]
+    async fn test_tools_call_denied_for_role() {
+        let mut tool = mock_tool("mock_echo", "Echoes input back");
+        tool.destructive = true;
+        let adapters: Vec<Arc<dyn Adapter>> = vec![Arc::new(MockAdapter::new("mock1", vec![tool]))];
+        let call = || {
+            make_request(
+                json!(7),
+                "tools/call",
+                json!({ "name": "mock_echo", "arguments": { "input": "hi" } }),
+            )
+        };
+
+        let viewer = McpServer::new(adapters.clone()).with_user_role(UserRole::Viewer);
+        let result = viewer.handle_request(call()).await.result.unwrap();
+        assert_eq!(result["isError"], true);
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("denied"), "{text}");
+
+        let user = McpServer::new(adapters).with_user_role(UserRole::User);
+        let result = user.handle_request(call()).await.result.unwrap();
+        assert!(result.get("isError").is_none(), "{result}");
+    }
+
     #[tokio::test]
     async fn test_tools_call_unknown_tool() {
         let server = McpServer::new(mock_adapters());
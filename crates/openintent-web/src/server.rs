@@ -1,35 +1,49 @@
 //! Main web server setup and startup.
 //!
 //! [`WebServer`] composes the Axum router, registers all routes, and starts
-//! the HTTP listener.  It also spawns a background file watcher that
+//! the HTTP (or, with a [`TlsConfig`](crate::TlsConfig), HTTPS) listener.  It also spawns a background file watcher that
 //! hot-reloads `config/IDENTITY.md` whenever the file changes on disk.
+//!
+//! With a kernel [`ShutdownListener`] attached ([`WebServer::with_shutdown`]),
+//! the server stops accepting connections on kernel shutdown, lets open
+//! requests finish, and then acknowledges.
 
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 
 use axum::Router;
+use axum::middleware;
 use axum::http::{HeaderValue, Method, header};
-use axum::response::{Html, IntoResponse, Response};
+use axum::response::{IntoResponse, Response};
 use axum::routing::{delete, get, post};
 use tokio::sync::RwLock;
-use tower_http::cors::CorsLayer;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tower_http::limit::RequestBodyLimitLayer;
 
 use openintent_adapters::Adapter;
+use openintent_adapters::HealthStatus;
 use openintent_agent::LlmClient;
+use openintent_kernel::{Event, IpcBus, ShutdownListener};
 use openintent_store::{Database, SessionStore};
 
 use crate::WebConfig;
 use crate::api;
-use crate::frontend::INDEX_HTML;
+use crate::events;
+use crate::limits::{self, ClientRateLimiter};
+use crate::frontend;
 use crate::mcp;
 use crate::state::AppState;
+use crate::tls;
 use crate::ws;
 
 /// The OpenIntentOS web server.
 pub struct WebServer {
     config: WebConfig,
     state: Arc<AppState>,
+    /// Stops the server on kernel shutdown; it runs until killed otherwise.
+    shutdown: Option<ShutdownListener>,
 }
 
 impl WebServer {
@@ -58,8 +72,39 @@ impl WebServer {
             sessions,
             system_prompt: Arc::new(RwLock::new(system_prompt)),
             evolution,
+            bus: IpcBus::new(256),
         });
-        Self { config, state }
+        Self {
+            config,
+            state,
+            shutdown: None,
+        }
+    }
+
+    /// Stream events from `bus` (for example the kernel's) to `/api/events`
+    /// instead of the server's own bus.
+    pub fn with_bus(mut self, bus: IpcBus) -> Self {
+        Arc::make_mut(&mut self.state).bus = bus;
+        self
+    }
+
+    /// Serve sessions from `sessions` instead of a plaintext store on the
+    /// server's database, e.g. one with encryption at rest enabled.
+    pub fn with_sessions(mut self, sessions: SessionStore) -> Self {
+        Arc::make_mut(&mut self.state).sessions = Arc::new(sessions);
+        self
+    }
+
+    /// Shut down gracefully when the kernel that issued `listener` does,
+    /// acknowledging once open requests have finished.
+    pub fn with_shutdown(mut self, listener: ShutdownListener) -> Self {
+        self.shutdown = Some(listener);
+        self
+    }
+
+    /// The bus streamed to `/api/events`; publish on it to reach SSE clients.
+    pub fn bus(&self) -> &IpcBus {
+        &self.state.bus
     }
 
     /// Return the `host:port` string this server will bind to.
@@ -68,15 +113,10 @@ impl WebServer {
     }
 
     /// Build the Axum router with all routes registered.
-    fn router(&self) -> Router {
-        let cors = CorsLayer::new()
-            .allow_origin("*".parse::<HeaderValue>().unwrap())
-            .allow_methods([Method::GET, Method::POST, Method::DELETE])
-            .allow_headers(tower_http::cors::Any);
-
-        Router::new()
-            // Embedded frontend.
-            .route("/", get(|| async { Html(INDEX_HTML) }))
+    pub(crate) fn router(&self) -> Router {
+        let cors = cors_layer(&self.config.allowed_origins);
+
+        let mut router = Router::new()
             // Static assets.
             .route("/assets/logo.png", get(serve_logo))
             // REST API.
@@ -84,6 +124,7 @@ impl WebServer {
             .route("/api/health", get(api::status)) // Health check alias
             .route("/api/adapters", get(api::adapters))
             .route("/api/chat", post(api::chat))
+            .route("/api/events", get(events::events))
             // Session management.
             .route("/api/sessions", get(api::list_sessions))
             .route("/api/sessions", post(api::create_session))
@@ -97,19 +138,27 @@ impl WebServer {
             .route("/mcp", post(mcp::handle_mcp_request))
             // WebSocket.
             .route("/ws", get(ws::ws_handler))
-            .layer(cors)
-            .with_state(Arc::clone(&self.state))
+            // Embedded frontend, with SPA fallback to index.html.
+            .fallback(frontend::serve_frontend)
+            .layer(RequestBodyLimitLayer::new(self.config.max_body_bytes));
+        if let Some(per_minute) = self.config.rate_limit_per_minute {
+            let limiter = Arc::new(ClientRateLimiter::new(per_minute));
+            router = router.layer(middleware::from_fn_with_state(limiter, limits::rate_limit));
+        }
+        router.layer(cors).with_state(Arc::clone(&self.state))
     }
 
     /// Start the server and block until it is shut down.
     ///
-    /// A background task watches `config/IDENTITY.md` for changes and
+    /// Without [`with_shutdown`](Self::with_shutdown) it serves until the
+    /// process exits.  A background task watches `config/IDENTITY.md` for changes and
     /// hot-reloads the system prompt into [`AppState::system_prompt`].
     /// Also initializes startup time tracking for health monitoring.
     ///
     /// # Errors
     ///
-    /// Returns an error if the TCP listener cannot be bound.
+    /// Returns an error if the TCP listener cannot be bound or the TLS
+    /// certificate cannot be loaded.
     pub async fn start(self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let addr = self.addr();
         let router = self.router();
@@ -129,13 +178,60 @@ impl WebServer {
 
         tracing::info!(addr = %addr, "starting web server with self-healing capabilities");
 
-        let listener = tokio::net::TcpListener::bind(&addr).await?;
-        axum::serve(listener, router).await?;
+        // Hand the shutdown notice back once serving has stopped, so the
+        // ack is only sent after open requests have finished.
+        let (stopped_tx, stopped_rx) = tokio::sync::oneshot::channel();
+        let listener = self.shutdown;
+        let signal = async move {
+            let Some(mut listener) = listener else {
+                return std::future::pending().await;
+            };
+            let notice = listener.recv().await;
+            tracing::info!("web server shutting down");
+            let _ = stopped_tx.send((listener, notice));
+        };
+        tls::serve(&addr, router, self.config.tls.as_ref(), signal).await?;
 
+        if let Ok((listener, notice)) = stopped_rx.await
+            && let Err(e) = listener.ack(&notice)
+        {
+            tracing::warn!(error = %e, "failed to acknowledge shutdown");
+        }
         Ok(())
     }
 }
 
+/// CORS policy letting pages from `allowed_origins` call the API with
+/// credentials.  With no origins, no CORS headers are sent and browsers
+/// only allow same-origin requests.  Preflight `OPTIONS` requests are
+/// answered by the layer itself.
+///
+/// An origin of `*` allows every origin, but without credentials: browsers
+/// never send cookies or `Authorization` headers to a wildcard origin, and
+/// tower-http refuses to combine the two.
+fn cors_layer(allowed_origins: &[String]) -> CorsLayer {
+    let layer = CorsLayer::new()
+        .allow_methods([Method::GET, Method::POST, Method::DELETE, Method::OPTIONS])
+        .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE]);
+    if allowed_origins.iter().any(|origin| origin == "*") {
+        tracing::warn!("CORS allows any origin; credentials are not allowed cross-origin");
+        return layer.allow_origin(AllowOrigin::any());
+    }
+
+    let origins: Vec<HeaderValue> = allowed_origins
+        .iter()
+        .filter_map(|origin| match origin.parse() {
+            Ok(value) => Some(value),
+            Err(_) => {
+                tracing::warn!(origin = %origin, "ignoring invalid CORS origin");
+                None
+            }
+        })
+        .collect();
+
+    layer.allow_origin(origins).allow_credentials(true)
+}
+
 // ── config loading ──────────────────────────────────────────────────
 
 /// Load the system prompt from `config/IDENTITY.md`, falling back to a
@@ -245,9 +341,12 @@ fn watch_config_files(system_prompt: Arc<RwLock<String>>) {
 async fn health_monitor_task(state: Arc<AppState>) {
     let mut interval = tokio::time::interval(Duration::from_secs(30)); // Check every 30 seconds
     let mut consecutive_failures = 0;
+    let mut adapter_health = HashMap::new();
     
     loop {
         interval.tick().await;
+
+        publish_adapter_health_changes(&state, &mut adapter_health).await;
         
         let health_checks = api::perform_health_checks(&state).await;
         let is_healthy = health_checks.database && health_checks.adapters && health_checks.llm_connection;
@@ -305,6 +404,31 @@ async fn health_monitor_task(state: Arc<AppState>) {
     }
 }
 
+/// Check every adapter and publish [`Event::AdapterHealthChanged`] for each
+/// whose status differs from `last`.  Adapters seen for the first time are
+/// reported only when they are not healthy.
+async fn publish_adapter_health_changes(
+    state: &AppState,
+    last: &mut HashMap<String, HealthStatus>,
+) {
+    for adapter in &state.adapters {
+        let status = adapter
+            .health_check()
+            .await
+            .unwrap_or(HealthStatus::Unhealthy);
+        let previous = last
+            .insert(adapter.id().to_owned(), status)
+            .unwrap_or(HealthStatus::Healthy);
+        if previous != status {
+            let _ = state.bus.publish(Event::AdapterHealthChanged {
+                adapter_id: adapter.id().to_owned(),
+                status: status.to_string(),
+                timestamp: chrono::Utc::now(),
+            });
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Static asset: logo
 // ---------------------------------------------------------------------------
@@ -323,3 +447,106 @@ async fn serve_logo() -> Response {
     )
         .into_response()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serve a one-route router behind `cors_layer(allowed)`; returns its URL.
+    async fn serve_with_cors(allowed: &[String]) -> String {
+        let router = Router::new()
+            .route("/api/status", get(|| async { "ok" }))
+            .layer(cors_layer(allowed));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        format!("http://{addr}/api/status")
+    }
+
+    #[tokio::test]
+    async fn cors_headers_only_for_allowed_origins() {
+        let url = serve_with_cors(&["https://app.example.com".into()]).await;
+        let client = reqwest::Client::new();
+
+        let allowed = client
+            .get(&url)
+            .header(header::ORIGIN, "https://app.example.com")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            allowed.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://app.example.com"
+        );
+        assert_eq!(
+            allowed.headers()[header::ACCESS_CONTROL_ALLOW_CREDENTIALS],
+            "true"
+        );
+
+        let preflight = client
+            .request(Method::OPTIONS, &url)
+            .header(header::ORIGIN, "https://app.example.com")
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .send()
+            .await
+            .unwrap();
+        assert!(preflight.status().is_success());
+        assert!(
+            preflight.headers()[header::ACCESS_CONTROL_ALLOW_METHODS]
+                .to_str()
+                .unwrap()
+                .contains("POST")
+        );
+
+        let disallowed = client
+            .get(&url)
+            .header(header::ORIGIN, "https://evil.example.com")
+            .send()
+            .await
+            .unwrap();
+        assert!(
+            !disallowed
+                .headers()
+                .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+        );
+    }
+
+    #[tokio::test]
+    async fn wildcard_origin_allows_any_without_credentials() {
+        let url = serve_with_cors(&["*".into()]).await;
+
+        let response = reqwest::Client::new()
+            .get(&url)
+            .header(header::ORIGIN, "https://anywhere.example.com")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+        assert!(
+            !response
+                .headers()
+                .contains_key(header::ACCESS_CONTROL_ALLOW_CREDENTIALS)
+        );
+    }
+
+    #[tokio::test]
+    async fn kernel_shutdown_stops_the_server() {
+        let kernel = openintent_kernel::Kernel::new(16);
+        let config = WebConfig {
+            bind_addr: "127.0.0.1".into(),
+            port: 0,
+            ..WebConfig::default()
+        };
+        let llm = Arc::new(
+            LlmClient::new(openintent_agent::LlmClientConfig::anthropic("test", "test")).unwrap(),
+        );
+        let db = Database::open_in_memory().unwrap();
+        let server = WebServer::new(config, llm, Vec::new(), db)
+            .with_shutdown(kernel.register_service("web"));
+        let serving = tokio::spawn(server.start());
+
+        let report = kernel.shutdown(Duration::from_secs(5)).await;
+        assert_eq!(report.acknowledged, vec!["web"]);
+        serving.await.unwrap().unwrap();
+    }
+}
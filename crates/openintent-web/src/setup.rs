@@ -15,6 +15,8 @@ use axum::routing::{get, post};
 use tokio::time::timeout;
 use tracing::{info, warn};
 
+use crate::TlsConfig;
+
 // ── public types ────────────────────────────────────────────────────────────
 
 /// Response for `/api/setup/status`.
@@ -294,14 +296,18 @@ async fn root_handler() -> Redirect {
 /// with the newly written `.env` file.
 ///
 /// When setup is done but onboarding has not been completed, the root `/`
-/// redirects to `/onboarding` instead.
+/// redirects to `/onboarding` instead.  With `tls`, the wizard is served
+/// over HTTPS so the API keys entered in it never cross the network in
+/// clear text.
 ///
 /// # Errors
 ///
-/// Returns an error if the TCP listener cannot be bound.
+/// Returns an error if the TCP listener cannot be bound or the TLS
+/// certificate cannot be loaded.
 pub async fn serve_setup(
     bind: &str,
     port: u16,
+    tls: Option<&TlsConfig>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let app = Router::new()
         .route("/", get(root_handler))
@@ -315,12 +321,11 @@ pub async fn serve_setup(
 
     println!();
     println!("  OpenIntentOS \u{2014} First Run Setup");
-    println!("  Open your browser: http://localhost:{port}");
+    let scheme = if tls.is_some() { "https" } else { "http" };
+    println!("  Open your browser: {scheme}://localhost:{port}");
     println!();
 
-    let listener = tokio::net::TcpListener::bind(&addr).await?;
-    axum::serve(listener, app).await?;
-    Ok(())
+    crate::tls::serve(&addr, app, tls, std::future::pending()).await
 }
 
 // ── embedded HTML wizard ─────────────────────────────────────────────────────
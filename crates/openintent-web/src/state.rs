@@ -2,7 +2,7 @@
 //!
 //! [`AppState`] is wrapped in an `Arc` and shared across all request handlers
 //! and WebSocket connections.  It holds references to the LLM client, adapters,
-//! session store, and database.
+//! session store, database, and the event bus streamed to `/api/events`.
 //!
 //! The `system_prompt` field supports hot-reload: when `config/IDENTITY.md`
 //! changes on disk the file watcher updates this value and all subsequent
@@ -13,6 +13,7 @@ use std::sync::Arc;
 use openintent_adapters::Adapter;
 use openintent_agent::LlmClient;
 use openintent_agent::evolution::EvolutionEngine;
+use openintent_kernel::IpcBus;
 use openintent_store::{Database, SessionStore};
 use tokio::sync::{Mutex, RwLock};
 
@@ -42,4 +43,7 @@ pub struct AppState {
 
     /// Optional self-evolution engine for auto-filing unhandled intent issues.
     pub evolution: Option<Arc<Mutex<EvolutionEngine>>>,
+
+    /// Event bus whose health and task events are streamed to SSE clients.
+    pub bus: IpcBus,
 }
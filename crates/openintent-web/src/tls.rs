@@ -0,0 +1,184 @@
+//! HTTPS support for the web server.
+//!
+//! [`TlsConfig`] points at a PEM certificate chain and private key.  When
+//! the operator has none, [`TlsConfig::self_signed`] generates a
+//! self-signed pair under the data directory on first use and reuses it on
+//! later starts, so browsers only have to trust it once.
+
+use std::io::BufReader;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use axum::Router;
+use axum_server::tls_rustls::RustlsConfig;
+use rustls::ServerConfig;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Certificate and key used to serve HTTPS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsConfig {
+    /// PEM file holding the certificate chain, leaf first.
+    pub cert_path: PathBuf,
+    /// PEM file holding the private key.
+    pub key_path: PathBuf,
+}
+
+impl TlsConfig {
+    /// Serve HTTPS with an existing certificate and key.
+    pub fn new(cert_path: impl Into<PathBuf>, key_path: impl Into<PathBuf>) -> Self {
+        Self {
+            cert_path: cert_path.into(),
+            key_path: key_path.into(),
+        }
+    }
+
+    /// Serve HTTPS with the self-signed certificate in `data_dir/tls`,
+    /// generating it for `hosts` (DNS names or IP addresses) if it does not
+    /// exist yet.
+    pub fn self_signed(data_dir: &Path, hosts: &[String]) -> Result<Self, BoxError> {
+        let dir = data_dir.join("tls");
+        let config = Self::new(dir.join("cert.pem"), dir.join("key.pem"));
+        if config.cert_path.exists() && config.key_path.exists() {
+            return Ok(config);
+        }
+
+        let (cert_pem, key_pem) = generate_self_signed(hosts)?;
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(&config.cert_path, cert_pem)?;
+        write_private_key(&config.key_path, key_pem.as_bytes())?;
+
+        tracing::info!(path = %config.cert_path.display(), "generated self-signed TLS certificate");
+        Ok(config)
+    }
+
+    /// Load the certificate and key into a rustls server configuration.
+    pub fn server_config(&self) -> Result<ServerConfig, BoxError> {
+        let certs =
+            rustls_pemfile::certs(&mut BufReader::new(std::fs::File::open(&self.cert_path)?))
+                .collect::<Result<Vec<_>, _>>()?;
+        if certs.is_empty() {
+            return Err(format!("no certificate found in {}", self.cert_path.display()).into());
+        }
+        let key =
+            rustls_pemfile::private_key(&mut BufReader::new(std::fs::File::open(&self.key_path)?))?
+                .ok_or_else(|| format!("no private key found in {}", self.key_path.display()))?;
+
+        let mut config =
+            ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+                .with_safe_default_protocol_versions()?
+                .with_no_client_auth()
+                .with_single_cert(certs, key)?;
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        Ok(config)
+    }
+}
+
+/// Write a private key that only its owner can read.
+///
+/// On Unix the file is created with mode 0600, and an existing file is
+/// restricted before the key is written, so the key is never readable by
+/// others, not even briefly.
+fn write_private_key(path: &Path, pem: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(pem)
+}
+
+/// Generate a self-signed certificate valid for `hosts`; returns the
+/// certificate and private key as PEM.
+pub fn generate_self_signed(hosts: &[String]) -> Result<(String, String), BoxError> {
+    let rcgen::CertifiedKey { cert, key_pair } = rcgen::generate_simple_self_signed(hosts)?;
+    Ok((cert.pem(), key_pair.serialize_pem()))
+}
+
+/// Serve `router` on `addr`, over HTTPS when `tls` is set, until `shutdown`
+/// resolves; open connections are then allowed to finish.  Handlers can
+/// extract the peer address as `ConnectInfo<SocketAddr>`.
+pub(crate) async fn serve(
+    addr: &str,
+    router: Router,
+    tls: Option<&TlsConfig>,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<(), BoxError> {
+    match tls {
+        Some(tls) => {
+            let config = RustlsConfig::from_config(Arc::new(tls.server_config()?));
+            let addr = tokio::net::lookup_host(addr)
+                .await?
+                .next()
+                .ok_or_else(|| format!("cannot resolve {addr}"))?;
+            let handle = axum_server::Handle::new();
+            let stopper = handle.clone();
+            tokio::spawn(async move {
+                shutdown.await;
+                stopper.graceful_shutdown(None);
+            });
+            axum_server::bind_rustls(addr, config)
+                .handle(handle)
+                .serve(router.into_make_service_with_connect_info::<SocketAddr>())
+                .await?;
+        }
+        None => {
+            let listener = tokio::net::TcpListener::bind(addr).await?;
+            axum::serve(
+                listener,
+                router.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .with_graceful_shutdown(shutdown)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_certificate_loads_into_rustls() {
+        let dir = tempfile::tempdir().unwrap();
+        let hosts = vec!["localhost".to_string(), "127.0.0.1".to_string()];
+
+        let tls = TlsConfig::self_signed(dir.path(), &hosts).unwrap();
+        assert_eq!(tls.cert_path, dir.path().join("tls/cert.pem"));
+        let config = tls.server_config().unwrap();
+        assert!(config.alpn_protocols.contains(&b"http/1.1".to_vec()));
+
+        // A second start reuses the stored pair instead of regenerating it.
+        let cert = std::fs::read(&tls.cert_path).unwrap();
+        TlsConfig::self_signed(dir.path(), &hosts).unwrap();
+        assert_eq!(std::fs::read(&tls.cert_path).unwrap(), cert);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn private_key_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key.pem");
+        std::fs::write(&path, "old").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_private_key(&path, b"secret").unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(std::fs::read(&path).unwrap(), b"secret");
+    }
+}
@@ -17,9 +17,12 @@ use serde_json::Value;
 use openintent_adapters::Adapter;
 use openintent_agent::runtime::ToolAdapter;
 use openintent_agent::{
-    AgentConfig, ChatRequest, LlmResponse, ToolDefinition, compact_messages, needs_compaction,
+    AgentConfig, ChatRequest, LlmResponse, ToolDefinition, ToolPermission, check_role,
+    compact_messages, needs_compaction,
 };
+use openintent_store::UserRole;
 
+use crate::auth::AuthUser;
 use crate::state::AppState;
 
 // ---------------------------------------------------------------------------
@@ -45,19 +48,21 @@ impl ToolAdapter for AdapterBridge {
                 name: t.name,
                 description: t.description,
                 input_schema: t.parameters,
+                destructive: t.destructive,
             })
             .collect()
     }
 
     async fn execute(&self, tool_name: &str, arguments: Value) -> openintent_agent::Result<String> {
+        if let Some(def) = self.tool_definitions().iter().find(|t| t.name == tool_name) {
+            def.validate_arguments(&arguments)?;
+        }
+
         let result = self
             .0
             .execute_tool(tool_name, arguments)
             .await
-            .map_err(|e| openintent_agent::AgentError::ToolExecutionFailed {
-                tool_name: tool_name.to_owned(),
-                reason: e.to_string(),
-            })?;
+            .map_err(openintent_agent::AgentError::from)?;
         Ok(serde_json::to_string(&result)?)
     }
 }
@@ -159,16 +164,18 @@ impl OutboundMessage {
 // Handler
 // ---------------------------------------------------------------------------
 
-/// Axum handler that upgrades the HTTP connection to a WebSocket.
+/// Axum handler that upgrades the HTTP connection to a WebSocket.  Tool
+/// calls on the connection run with the signed-in user's role.
 pub async fn ws_handler(
     ws: WebSocketUpgrade,
     State(state): State<Arc<AppState>>,
+    user: AuthUser,
 ) -> impl IntoResponse {
-    ws.on_upgrade(|socket| handle_socket(socket, state))
+    ws.on_upgrade(move |socket| handle_socket(socket, state, user.role))
 }
 
 /// Process a single WebSocket connection.
-async fn handle_socket(mut socket: WebSocket, state: Arc<AppState>) {
+async fn handle_socket(mut socket: WebSocket, state: Arc<AppState>, role: Option<UserRole>) {
     tracing::info!("WebSocket client connected");
 
     while let Some(Ok(msg)) = socket.recv().await {
@@ -197,8 +204,14 @@ async fn handle_socket(mut socket: WebSocket, state: Arc<AppState>) {
 
         let session_id = inbound.session_id.clone();
 
-        if let Err(e) =
-            handle_chat_message(&mut socket, &state, session_id.as_deref(), &inbound.content).await
+        if let Err(e) = handle_chat_message(
+            &mut socket,
+            &state,
+            role,
+            session_id.as_deref(),
+            &inbound.content,
+        )
+        .await
         {
             let _ = send(&mut socket, &OutboundMessage::error(e.to_string())).await;
         }
@@ -214,6 +227,7 @@ async fn handle_socket(mut socket: WebSocket, state: Arc<AppState>) {
 async fn handle_chat_message(
     socket: &mut WebSocket,
     state: &Arc<AppState>,
+    role: Option<UserRole>,
     session_id: Option<&str>,
     user_message: &str,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -267,7 +281,7 @@ async fn handle_chat_message(
 
     for _turn in 0..max_turns {
         // Check if context compaction is needed before the LLM call.
-        if needs_compaction(&messages, &compaction_config) {
+        if needs_compaction(&messages, &config.model, &compaction_config) {
             tracing::info!(
                 message_count = messages.len(),
                 "WebSocket handler: context compaction triggered"
@@ -362,12 +376,24 @@ async fn handle_chat_message(
                         .iter()
                         .find(|a| a.tool_definitions().iter().any(|td| td.name == call.name));
 
-                    let result_str = match adapter {
-                        Some(a) => match a.execute(&call.name, call.arguments.clone()).await {
-                            Ok(r) => r,
-                            Err(e) => format!("Error: {e}"),
-                        },
-                        None => format!("Error: unknown tool `{}`", call.name),
+                    let permission = match (role, tools.iter().find(|t| t.name == call.name)) {
+                        (Some(role), Some(tool)) => check_role(role, tool),
+                        _ => ToolPermission::Allow,
+                    };
+
+                    let result_str = match (adapter, permission) {
+                        (_, ToolPermission::Deny(reason)) => {
+                            format!("Error: tool `{}` denied by policy: {reason}", call.name)
+                        }
+                        (Some(a), ToolPermission::Allow) => {
+                            match a.execute(&call.name, call.arguments.clone()).await {
+                                Ok(r) => r,
+                                Err(e) => format!("Error: {e}"),
+                            }
+                        }
+                        (None, ToolPermission::Allow) => {
+                            format!("Error: unknown tool `{}`", call.name)
+                        }
                     };
 
                     send(socket, &OutboundMessage::tool_end(&result_str)).await?;
@@ -11,6 +11,9 @@ fn web_config_defaults() {
     let config = WebConfig::default();
     assert_eq!(config.bind_addr, "127.0.0.1");
     assert_eq!(config.port, 23517);
+    assert!(config.tls.is_none());
+    assert!(config.allowed_origins.is_empty());
+    assert_eq!(config.rate_limit_per_minute, Some(300));
 }
 
 #[test]
@@ -18,6 +21,10 @@ fn web_config_custom() {
     let config = WebConfig {
         bind_addr: "0.0.0.0".into(),
         port: 8080,
+        tls: None,
+        allowed_origins: vec!["https://app.example.com".into()],
+        rate_limit_per_minute: None,
+        max_body_bytes: 1024,
     };
     assert_eq!(config.bind_addr, "0.0.0.0");
     assert_eq!(config.port, 8080);
@@ -2,6 +2,18 @@ use anyhow::Result;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
+
+mod sources;
+mod storage;
+
+pub use sources::{parse_github, parse_hackernews, Fetch, HttpFetch};
+
+/// Search query used when none is configured.
+const DEFAULT_QUERY: &str = "openai";
+
+/// Minimum change in sentiment score reported as a `SentimentShift`.
+const SENTIMENT_SHIFT_THRESHOLD: f32 = 0.3;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OSINTItem {
@@ -17,7 +29,7 @@ pub struct OSINTItem {
     pub metadata: HashMap<String, String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum Source {
     OpenAIBlog,
     TwitterX,
@@ -36,7 +48,7 @@ pub struct ChangeDetection {
     pub detected_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ChangeType {
     NewPost,
     Update,
@@ -66,6 +78,8 @@ pub struct SentimentSummary {
 pub struct Collector {
     pub sources: Vec<Source>,
     pub storage_path: String,
+    pub query: String,
+    fetch: Arc<dyn Fetch>,
 }
 
 impl Collector {
@@ -73,9 +87,23 @@ impl Collector {
         Self {
             sources,
             storage_path,
+            query: DEFAULT_QUERY.to_string(),
+            fetch: Arc::new(HttpFetch::new()),
         }
     }
 
+    /// Search for `query` instead of the default.
+    pub fn with_query(mut self, query: impl Into<String>) -> Self {
+        self.query = query.into();
+        self
+    }
+
+    /// Route HTTP requests through `fetch` (e.g. fixtures in tests).
+    pub fn with_fetch(mut self, fetch: Arc<dyn Fetch>) -> Self {
+        self.fetch = fetch;
+        self
+    }
+
     pub async fn collect(&self) -> Result<Vec<OSINTItem>> {
         let mut all_items = Vec::new();
         
@@ -105,13 +133,11 @@ impl Collector {
     }
     
     async fn collect_github(&self) -> Result<Vec<OSINTItem>> {
-        // TODO: Implement GitHub API for OpenAI repos
-        Ok(vec![])
+        sources::fetch_github(self.fetch.as_ref(), &self.query).await
     }
     
     async fn collect_hackernews(&self) -> Result<Vec<OSINTItem>> {
-        // TODO: Implement HackerNews API for OpenAI mentions
-        Ok(vec![])
+        sources::fetch_hackernews(self.fetch.as_ref(), &self.query).await
     }
     
     pub async fn analyze_sentiment(&self, items: &[OSINTItem]) -> Result<Vec<OSINTItem>> {
@@ -119,9 +145,58 @@ impl Collector {
         Ok(items.to_vec())
     }
     
+    /// Diff `new_items` against `previous_items` by id.
+    ///
+    /// New and changed items are reported in `new_items` order, followed by
+    /// deletions in `previous_items` order.  An item can be both updated and
+    /// have its sentiment shift.
     pub async fn detect_changes(&self, new_items: &[OSINTItem], previous_items: &[OSINTItem]) -> Result<Vec<ChangeDetection>> {
-        // TODO: Implement change detection
-        Ok(vec![])
+        let detected_at = Utc::now();
+        let previous: HashMap<&str, &OSINTItem> =
+            previous_items.iter().map(|item| (item.id.as_str(), item)).collect();
+        let current: HashMap<&str, &OSINTItem> =
+            new_items.iter().map(|item| (item.id.as_str(), item)).collect();
+
+        let change = |item: &OSINTItem, change_type, old_value, new_value| ChangeDetection {
+            item_id: item.id.clone(),
+            change_type,
+            old_value,
+            new_value,
+            detected_at,
+        };
+
+        let mut changes = Vec::new();
+        for item in new_items {
+            let Some(old) = previous.get(item.id.as_str()) else {
+                changes.push(change(item, ChangeType::NewPost, None, item.title.clone()));
+                continue;
+            };
+            if old.content != item.content {
+                changes.push(change(
+                    item,
+                    ChangeType::Update,
+                    Some(old.content.clone()),
+                    item.content.clone(),
+                ));
+            }
+            if let (Some(before), Some(after)) = (old.sentiment_score, item.sentiment_score) {
+                if (after - before).abs() >= SENTIMENT_SHIFT_THRESHOLD {
+                    changes.push(change(
+                        item,
+                        ChangeType::SentimentShift,
+                        Some(format!("{before:.2}")),
+                        format!("{after:.2}"),
+                    ));
+                }
+            }
+        }
+        for old in previous_items {
+            if !current.contains_key(old.id.as_str()) {
+                changes.push(change(old, ChangeType::Deletion, Some(old.title.clone()), String::new()));
+            }
+        }
+
+        Ok(changes)
     }
     
     pub async fn generate_daily_summary(&self, items: &[OSINTItem], changes: &[ChangeDetection]) -> Result<DailySummary> {
@@ -141,14 +216,20 @@ impl Collector {
         })
     }
     
+    /// Store `items` as today's snapshot under `storage_path`.
     pub async fn save_items(&self, items: &[OSINTItem]) -> Result<()> {
-        // TODO: Implement storage
+        storage::save_snapshot(
+            std::path::Path::new(&self.storage_path),
+            Utc::now().date_naive(),
+            items,
+        )
+        .await?;
         Ok(())
     }
     
+    /// Load the most recent snapshot, or nothing if none is readable.
     pub async fn load_previous_items(&self) -> Result<Vec<OSINTItem>> {
-        // TODO: Implement loading
-        Ok(vec![])
+        storage::load_latest_snapshot(std::path::Path::new(&self.storage_path)).await
     }
 }
 
@@ -215,4 +296,102 @@ pub async fn execute_collector(args: &str) -> Result<String> {
     } else {
         Ok(format!("Unknown command: {}. Available commands: collect, summary, setup", args))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn item(id: &str, content: &str, sentiment: Option<f32>) -> OSINTItem {
+        OSINTItem {
+            id: id.to_string(),
+            source: Source::HackerNews,
+            title: format!("title {id}"),
+            content: content.to_string(),
+            url: format!("https://example.com/{id}"),
+            published_at: Utc::now(),
+            collected_at: Utc::now(),
+            sentiment_score: sentiment,
+            categories: vec![],
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn detect_changes_classifies_diff() {
+        let collector = Collector::new(vec![], String::new());
+        let before = vec![
+            item("same", "unchanged", Some(0.1)),
+            item("edited", "first draft", None),
+            item("mood", "steady", Some(-0.4)),
+            item("gone", "removed later", None),
+        ];
+        let after = vec![
+            item("same", "unchanged", Some(0.2)),
+            item("edited", "second draft", None),
+            item("mood", "steady", Some(0.5)),
+            item("fresh", "brand new", None),
+        ];
+
+        let changes = collector.detect_changes(&after, &before).await.unwrap();
+        let kinds: Vec<(&str, ChangeType)> = changes
+            .iter()
+            .map(|c| (c.item_id.as_str(), c.change_type.clone()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("edited", ChangeType::Update),
+                ("mood", ChangeType::SentimentShift),
+                ("fresh", ChangeType::NewPost),
+                ("gone", ChangeType::Deletion),
+            ]
+        );
+
+        assert_eq!(changes[0].old_value.as_deref(), Some("first draft"));
+        assert_eq!(changes[0].new_value, "second draft");
+        assert_eq!(changes[1].old_value.as_deref(), Some("-0.40"));
+        assert_eq!(changes[1].new_value, "0.50");
+        assert_eq!(changes[2].old_value, None);
+        assert_eq!(changes[3].old_value.as_deref(), Some("title gone"));
+    }
+
+    #[tokio::test]
+    async fn saved_items_load_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = dir.path().join("osint");
+        let collector = Collector::new(vec![], storage.to_string_lossy().into_owned());
+        assert!(collector.load_previous_items().await.unwrap().is_empty());
+
+        let mut items = vec![item("a", "alpha", Some(0.25)), item("b", "beta", None)];
+        items[0].metadata.insert("points".into(), "10".into());
+        items[1].categories.push("story".into());
+        collector.save_items(&items).await.unwrap();
+
+        let loaded = collector.load_previous_items().await.unwrap();
+        assert_eq!(
+            serde_json::to_value(&loaded).unwrap(),
+            serde_json::to_value(&items).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn latest_snapshot_wins_and_corruption_is_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let day = |d| NaiveDate::from_ymd_opt(2026, 10, d).unwrap();
+        storage::save_snapshot(dir.path(), day(1), &[item("old", "x", None)])
+            .await
+            .unwrap();
+        storage::save_snapshot(dir.path(), day(2), &[item("new", "y", None)])
+            .await
+            .unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "not a snapshot").unwrap();
+
+        let loaded = storage::load_latest_snapshot(dir.path()).await.unwrap();
+        assert_eq!(loaded[0].id, "new");
+
+        std::fs::write(dir.path().join("items-2026-10-03.json"), "{ truncated").unwrap();
+        assert!(storage::load_latest_snapshot(dir.path()).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,291 @@
+//! Network-backed collectors for the OSINT skill.
+//!
+//! All HTTP goes through [`Fetch`], so tests can serve recorded responses
+//! instead of hitting the HackerNews and GitHub APIs.
+
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use url::Url;
+
+use super::{OSINTItem, Source};
+
+const HACKERNEWS_SEARCH_URL: &str = "https://hn.algolia.com/api/v1/search_by_date";
+const HACKERNEWS_ITEM_URL: &str = "https://news.ycombinator.com/item?id=";
+const GITHUB_SEARCH_URL: &str = "https://api.github.com/search/repositories";
+const USER_AGENT: &str = "openintent-collector";
+const PAGE_SIZE: &str = "30";
+
+/// Fetches a JSON document.
+#[async_trait]
+pub trait Fetch: Send + Sync {
+    async fn get_json(&self, url: &Url) -> Result<Value>;
+}
+
+/// [`Fetch`] implementation backed by `reqwest`.
+pub struct HttpFetch {
+    client: reqwest::Client,
+}
+
+impl HttpFetch {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+impl Default for HttpFetch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Fetch for HttpFetch {
+    async fn get_json(&self, url: &Url) -> Result<Value> {
+        let response = self
+            .client
+            .get(url.clone())
+            .header(reqwest::header::USER_AGENT, USER_AGENT)
+            .header(reqwest::header::ACCEPT, "application/json")
+            .send()
+            .await
+            .with_context(|| format!("request to {} failed", url.host_str().unwrap_or("?")))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow!("{} returned HTTP {}", url.path(), status));
+        }
+        Ok(response.json().await?)
+    }
+}
+
+// -- HackerNews -------------------------------------------------------------
+
+#[derive(Deserialize)]
+struct HackerNewsResponse {
+    hits: Vec<HackerNewsHit>,
+}
+
+#[derive(Deserialize)]
+struct HackerNewsHit {
+    #[serde(rename = "objectID")]
+    object_id: String,
+    title: Option<String>,
+    url: Option<String>,
+    story_text: Option<String>,
+    author: Option<String>,
+    points: Option<i64>,
+    num_comments: Option<i64>,
+    created_at_i: i64,
+    #[serde(rename = "_tags", default)]
+    tags: Vec<String>,
+}
+
+/// Recent HackerNews stories matching `query`, newest first.
+pub async fn fetch_hackernews(fetch: &dyn Fetch, query: &str) -> Result<Vec<OSINTItem>> {
+    let url = Url::parse_with_params(
+        HACKERNEWS_SEARCH_URL,
+        [
+            ("query", query),
+            ("tags", "story"),
+            ("hitsPerPage", PAGE_SIZE),
+        ],
+    )?;
+    parse_hackernews(fetch.get_json(&url).await?, Utc::now())
+}
+
+/// Map an Algolia search response to items.  Hits without a title are
+/// dropped; stories without an external link point at their HN thread.
+pub fn parse_hackernews(body: Value, collected_at: DateTime<Utc>) -> Result<Vec<OSINTItem>> {
+    let response: HackerNewsResponse =
+        serde_json::from_value(body).context("unexpected HackerNews response")?;
+
+    let mut items = Vec::with_capacity(response.hits.len());
+    for hit in response.hits {
+        let Some(title) = hit.title.filter(|t| !t.is_empty()) else {
+            continue;
+        };
+        let published_at = Utc
+            .timestamp_opt(hit.created_at_i, 0)
+            .single()
+            .ok_or_else(|| anyhow!("invalid timestamp on HN item {}", hit.object_id))?;
+        let discussion_url = format!("{HACKERNEWS_ITEM_URL}{}", hit.object_id);
+
+        let mut metadata = HashMap::from([("discussion_url".to_string(), discussion_url.clone())]);
+        if let Some(author) = hit.author {
+            metadata.insert("author".into(), author);
+        }
+        if let Some(points) = hit.points {
+            metadata.insert("points".into(), points.to_string());
+        }
+        if let Some(comments) = hit.num_comments {
+            metadata.insert("num_comments".into(), comments.to_string());
+        }
+
+        items.push(OSINTItem {
+            id: format!("hn-{}", hit.object_id),
+            source: Source::HackerNews,
+            content: hit.story_text.unwrap_or_else(|| title.clone()),
+            title,
+            url: hit.url.filter(|u| !u.is_empty()).unwrap_or(discussion_url),
+            published_at,
+            collected_at,
+            sentiment_score: None,
+            categories: hit.tags.into_iter().filter(|t| !t.contains('_')).collect(),
+            metadata,
+        });
+    }
+    Ok(items)
+}
+
+// -- GitHub -----------------------------------------------------------------
+
+#[derive(Deserialize)]
+struct GitHubResponse {
+    items: Vec<GitHubRepo>,
+}
+
+#[derive(Deserialize)]
+struct GitHubRepo {
+    id: u64,
+    full_name: String,
+    description: Option<String>,
+    html_url: String,
+    pushed_at: Option<DateTime<Utc>>,
+    updated_at: DateTime<Utc>,
+    stargazers_count: Option<u64>,
+    language: Option<String>,
+    #[serde(default)]
+    topics: Vec<String>,
+}
+
+/// Repositories matching `query`, most recently updated first.
+pub async fn fetch_github(fetch: &dyn Fetch, query: &str) -> Result<Vec<OSINTItem>> {
+    let url = Url::parse_with_params(
+        GITHUB_SEARCH_URL,
+        [
+            ("q", query),
+            ("sort", "updated"),
+            ("order", "desc"),
+            ("per_page", PAGE_SIZE),
+        ],
+    )?;
+    parse_github(fetch.get_json(&url).await?, Utc::now())
+}
+
+/// Map a GitHub repository search response to items, dated by last push.
+pub fn parse_github(body: Value, collected_at: DateTime<Utc>) -> Result<Vec<OSINTItem>> {
+    let response: GitHubResponse =
+        serde_json::from_value(body).context("unexpected GitHub response")?;
+
+    Ok(response
+        .items
+        .into_iter()
+        .map(|repo| {
+            let mut metadata = HashMap::new();
+            if let Some(stars) = repo.stargazers_count {
+                metadata.insert("stars".to_string(), stars.to_string());
+            }
+            if let Some(language) = repo.language {
+                metadata.insert("language".to_string(), language);
+            }
+            OSINTItem {
+                id: format!("github-{}", repo.id),
+                source: Source::GitHub,
+                content: repo.description.unwrap_or_default(),
+                title: repo.full_name,
+                url: repo.html_url,
+                published_at: repo.pushed_at.unwrap_or(repo.updated_at),
+                collected_at,
+                sentiment_score: None,
+                categories: repo.topics,
+                metadata,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Serves a fixed body and records the requested URLs.
+    struct FixtureFetch {
+        body: &'static str,
+        requested: Mutex<Vec<Url>>,
+    }
+
+    impl FixtureFetch {
+        fn new(body: &'static str) -> Self {
+            Self {
+                body,
+                requested: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Fetch for FixtureFetch {
+        async fn get_json(&self, url: &Url) -> Result<Value> {
+            self.requested.lock().unwrap().push(url.clone());
+            Ok(serde_json::from_str(self.body)?)
+        }
+    }
+
+    #[tokio::test]
+    async fn hackernews_fixture_parses_into_items() {
+        let fetch = FixtureFetch::new(include_str!("fixtures/hackernews.json"));
+        let items = fetch_hackernews(&fetch, "openai").await.unwrap();
+
+        let requested = fetch.requested.lock().unwrap();
+        assert_eq!(requested[0].host_str(), Some("hn.algolia.com"));
+        assert!(requested[0].query().unwrap().contains("query=openai"));
+
+        // The comment-only hit without a title is dropped.
+        assert_eq!(items.len(), 2);
+        let first = &items[0];
+        assert_eq!(first.id, "hn-41234567");
+        assert_eq!(first.source, Source::HackerNews);
+        assert_eq!(first.title, "OpenAI releases new reasoning model");
+        assert_eq!(first.url, "https://openai.com/index/new-model");
+        assert_eq!(first.published_at.timestamp(), 1_760_000_000);
+        assert_eq!(first.metadata["points"], "412");
+        assert_eq!(first.categories, vec!["story"]);
+
+        let ask = &items[1];
+        assert_eq!(ask.url, "https://news.ycombinator.com/item?id=41234001");
+        assert_eq!(ask.content, "Curious what people are building.");
+    }
+
+    #[test]
+    fn github_response_parses_into_items() {
+        let body = serde_json::json!({
+            "total_count": 1,
+            "items": [{
+                "id": 123,
+                "full_name": "openai/openai-python",
+                "description": "The official Python library",
+                "html_url": "https://github.com/openai/openai-python",
+                "pushed_at": "2026-10-01T12:00:00Z",
+                "updated_at": "2026-10-02T08:00:00Z",
+                "stargazers_count": 25000,
+                "language": "Python",
+                "topics": ["openai", "python"]
+            }]
+        });
+        let items = parse_github(body, Utc::now()).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].id, "github-123");
+        assert_eq!(items[0].url, "https://github.com/openai/openai-python");
+        assert_eq!(
+            items[0].published_at.to_rfc3339(),
+            "2026-10-01T12:00:00+00:00"
+        );
+        assert_eq!(items[0].metadata["stars"], "25000");
+    }
+}
@@ -0,0 +1,76 @@
+//! Dated JSON snapshots of collected items.
+//!
+//! Each run's items are written to `items-YYYY-MM-DD.json` under the
+//! collector's storage path; a later run on the same day replaces that
+//! day's snapshot.
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use super::OSINTItem;
+
+const SNAPSHOT_PREFIX: &str = "items-";
+const SNAPSHOT_EXTENSION: &str = ".json";
+
+fn snapshot_path(dir: &Path, date: NaiveDate) -> PathBuf {
+    dir.join(format!("{SNAPSHOT_PREFIX}{date}{SNAPSHOT_EXTENSION}"))
+}
+
+/// The date encoded in a snapshot file name.
+fn snapshot_date(file_name: &str) -> Option<NaiveDate> {
+    let date = file_name
+        .strip_prefix(SNAPSHOT_PREFIX)?
+        .strip_suffix(SNAPSHOT_EXTENSION)?;
+    date.parse().ok()
+}
+
+/// Write `items` as the snapshot for `date`, replacing any existing one.
+pub async fn save_snapshot(dir: &Path, date: NaiveDate, items: &[OSINTItem]) -> Result<PathBuf> {
+    tokio::fs::create_dir_all(dir)
+        .await
+        .with_context(|| format!("failed to create {}", dir.display()))?;
+
+    let path = snapshot_path(dir, date);
+    // Write beside the target and rename, so a crash never leaves a
+    // truncated snapshot behind.
+    let tmp = path.with_extension("json.tmp");
+    tokio::fs::write(&tmp, serde_json::to_vec_pretty(items)?)
+        .await
+        .with_context(|| format!("failed to write {}", tmp.display()))?;
+    tokio::fs::rename(&tmp, &path)
+        .await
+        .with_context(|| format!("failed to replace {}", path.display()))?;
+    Ok(path)
+}
+
+/// Load the most recent snapshot in `dir`.
+///
+/// A missing directory, no snapshots, or an unreadable or corrupt latest
+/// snapshot all yield an empty set.
+pub async fn load_latest_snapshot(dir: &Path) -> Result<Vec<OSINTItem>> {
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e).with_context(|| format!("failed to read {}", dir.display())),
+    };
+
+    let mut latest: Option<(NaiveDate, PathBuf)> = None;
+    while let Some(entry) = entries.next_entry().await? {
+        let Some(date) = entry.file_name().to_str().and_then(snapshot_date) else {
+            continue;
+        };
+        if latest.as_ref().is_none_or(|(d, _)| date > *d) {
+            latest = Some((date, entry.path()));
+        }
+    }
+
+    let Some((_, path)) = latest else {
+        return Ok(vec![]);
+    };
+    let Ok(bytes) = tokio::fs::read(&path).await else {
+        return Ok(vec![]);
+    };
+    Ok(serde_json::from_slice(&bytes).unwrap_or_default())
+}
@@ -0,0 +1,219 @@
+//! IMAP connection check using the XOAUTH2 SASL mechanism.
+//!
+//! After the OAuth flow the skill logs in to the provider's IMAP server with
+//! the fresh access token, so a misconfigured OAuth app (wrong scopes, IMAP
+//! disabled) is reported during setup rather than on first use.
+
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose, Engine as _};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::time::{timeout, Duration};
+use tokio_rustls::TlsConnector;
+
+/// IMAP endpoint (host, TLS port) per provider.
+const IMAP_HOSTS: &[(&str, &str, u16)] = &[
+    ("gmail", "imap.gmail.com", 993),
+    ("outlook", "outlook.office365.com", 993),
+    ("yahoo", "imap.mail.yahoo.com", 993),
+];
+
+/// Upper bound on connecting and on waiting for each server reply.
+const IMAP_TIMEOUT_SECS: u64 = 30;
+
+/// The IMAP host and port for `provider`.
+pub fn imap_endpoint(provider: &str) -> Option<(&'static str, u16)> {
+    IMAP_HOSTS
+        .iter()
+        .find(|(name, _, _)| *name == provider)
+        .map(|(_, host, port)| (*host, *port))
+}
+
+/// The base64 XOAUTH2 initial client response for `user` and `access_token`.
+pub fn xoauth2_initial_response(user: &str, access_token: &str) -> String {
+    general_purpose::STANDARD.encode(format!("user={user}\x01auth=Bearer {access_token}\x01\x01"))
+}
+
+/// Connect to `host:port` over TLS and authenticate with XOAUTH2.
+pub async fn check_connection(host: &str, port: u16, user: &str, access_token: &str) -> Result<()> {
+    let roots = rustls::RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let config = rustls::ClientConfig::builder()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    let server_name = rustls::pki_types::ServerName::try_from(host.to_owned())
+        .map_err(|e| anyhow!("invalid IMAP host '{host}': {e}"))?;
+
+    let limit = Duration::from_secs(IMAP_TIMEOUT_SECS);
+    let tcp = timeout(limit, TcpStream::connect((host, port)))
+        .await
+        .map_err(|_| anyhow!("connecting to {host}:{port} timed out"))?
+        .with_context(|| format!("connecting to {host}:{port} failed"))?;
+    let tls = timeout(
+        limit,
+        TlsConnector::from(Arc::new(config)).connect(server_name, tcp),
+    )
+    .await
+    .map_err(|_| anyhow!("TLS handshake with {host} timed out"))?
+    .with_context(|| format!("TLS handshake with {host} failed"))?;
+
+    authenticate_xoauth2(tls, user, access_token).await
+}
+
+/// Run the XOAUTH2 handshake on an established IMAP connection and log out.
+///
+/// Fails with the server's reason when the token is rejected.
+pub async fn authenticate_xoauth2<S>(stream: S, user: &str, access_token: &str) -> Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let (read_half, mut write_half) = tokio::io::split(stream);
+    let mut reader = BufReader::new(read_half);
+
+    let greeting = read_line(&mut reader).await?;
+    if !greeting.starts_with("* OK") {
+        return Err(anyhow!("unexpected IMAP greeting: {greeting}"));
+    }
+
+    let command = format!(
+        "A001 AUTHENTICATE XOAUTH2 {}\r\n",
+        xoauth2_initial_response(user, access_token)
+    );
+    write_half.write_all(command.as_bytes()).await?;
+
+    let mut detail = None;
+    loop {
+        let line = read_line(&mut reader).await?;
+        if let Some(challenge) = line.strip_prefix("+ ") {
+            // On failure the server sends a base64 JSON error as a
+            // continuation; an empty reply makes it finish with NO.
+            detail = general_purpose::STANDARD
+                .decode(challenge.trim())
+                .ok()
+                .and_then(|bytes| String::from_utf8(bytes).ok());
+            write_half.write_all(b"\r\n").await?;
+        } else if let Some(status) = line.strip_prefix("A001 ") {
+            if status.starts_with("OK") {
+                break;
+            }
+            let reason = detail.map_or_else(|| status.to_string(), |d| format!("{status} ({d})"));
+            return Err(anyhow!("IMAP XOAUTH2 login rejected: {reason}"));
+        }
+    }
+
+    write_half.write_all(b"A002 LOGOUT\r\n").await?;
+    Ok(())
+}
+
+async fn read_line<R>(reader: &mut R) -> Result<String>
+where
+    R: AsyncBufReadExt + Unpin,
+{
+    let mut line = String::new();
+    let n = timeout(
+        Duration::from_secs(IMAP_TIMEOUT_SECS),
+        reader.read_line(&mut line),
+    )
+    .await
+    .map_err(|_| anyhow!("IMAP server did not respond"))??;
+    if n == 0 {
+        return Err(anyhow!("IMAP server closed the connection"));
+    }
+    Ok(line.trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    /// Accept one connection, expect the XOAUTH2 command and answer it with
+    /// `replies`.  Returns every line the client sent.
+    async fn mock_imap_server(
+        listener: TcpListener,
+        replies: &'static [&'static str],
+    ) -> Vec<String> {
+        let (stream, _) = listener.accept().await.unwrap();
+        let (read_half, mut write_half) = tokio::io::split(stream);
+        let mut reader = BufReader::new(read_half);
+        write_half
+            .write_all(b"* OK IMAP4rev1 ready\r\n")
+            .await
+            .unwrap();
+
+        let mut received = vec![read_line(&mut reader).await.unwrap()];
+        for reply in replies {
+            write_half.write_all(reply.as_bytes()).await.unwrap();
+            if reply.starts_with('+') {
+                received.push(read_line(&mut reader).await.unwrap());
+            }
+        }
+        if let Ok(line) = read_line(&mut reader).await {
+            received.push(line);
+        }
+        received
+    }
+
+    #[test]
+    fn providers_map_to_imap_hosts() {
+        assert_eq!(imap_endpoint("gmail"), Some(("imap.gmail.com", 993)));
+        assert_eq!(
+            imap_endpoint("outlook"),
+            Some(("outlook.office365.com", 993))
+        );
+        assert_eq!(imap_endpoint("custom"), None);
+    }
+
+    #[tokio::test]
+    async fn xoauth2_handshake_succeeds() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(mock_imap_server(
+            listener,
+            &["A001 OK AUTHENTICATE completed\r\n"],
+        ));
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        authenticate_xoauth2(stream, "user@gmail.com", "ya29.token")
+            .await
+            .unwrap();
+
+        let received = server.await.unwrap();
+        let encoded = received[0]
+            .strip_prefix("A001 AUTHENTICATE XOAUTH2 ")
+            .unwrap();
+        let decoded = general_purpose::STANDARD.decode(encoded).unwrap();
+        assert_eq!(
+            decoded,
+            b"user=user@gmail.com\x01auth=Bearer ya29.token\x01\x01"
+        );
+        assert_eq!(received[1], "A002 LOGOUT");
+    }
+
+    #[tokio::test]
+    async fn rejected_token_reports_server_reason() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(mock_imap_server(
+            listener,
+            &[
+                // base64 of {"status":"400","schemes":"Bearer","scope":"https://mail.google.com/"}
+                "+ eyJzdGF0dXMiOiI0MDAiLCJzY2hlbWVzIjoiQmVhcmVyIiwic2NvcGUiOiJodHRwczovL21haWwuZ29vZ2xlLmNvbS8ifQ==\r\n",
+                "A001 NO [AUTHENTICATIONFAILED] Invalid credentials (Failure)\r\n",
+            ],
+        ));
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let err = authenticate_xoauth2(stream, "user@gmail.com", "expired")
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("AUTHENTICATIONFAILED"), "{err}");
+        assert!(err.contains("\"status\":\"400\""), "{err}");
+
+        let received = server.await.unwrap();
+        assert_eq!(received[1], "", "client must answer the error challenge");
+    }
+}
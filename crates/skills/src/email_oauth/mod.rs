@@ -1,11 +1,34 @@
 use crate::SkillResult;
 use anyhow::{anyhow, Result};
+use openintent_auth_engine::{CallbackServer, OAuthConfig, OAuthFlow, OAuthProviderRegistry};
+use openintent_vault::store::{CredentialType, Vault};
+use openintent_vault::VaultError;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::path::Path;
 use std::process::Command;
-use tokio::time::{sleep, Duration};
+use std::sync::{Arc, Mutex};
 use url::Url;
 
+mod imap;
+
+/// Port of the local listener that receives the OAuth redirect.
+const CALLBACK_PORT: u16 = 8400;
+
+/// How long to wait for the user to finish authorizing in the browser.
+const CALLBACK_TIMEOUT_SECS: u64 = 300;
+
+/// Config file whose `[oauth_providers]` section overrides the built-in
+/// provider definitions.
+const PROVIDER_CONFIG_PATH: &str = "config/default.toml";
+
+/// Vault key prefix shared with the auth engine's OAuth token entries.
+const TOKEN_KEY_PREFIX: &str = "oauth_tokens:";
+
+/// The vault key under which tokens for `email` at `provider` are stored.
+pub fn credential_key(provider: &str, email: &str) -> String {
+    format!("{TOKEN_KEY_PREFIX}{provider}:{email}")
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EmailOAuthConfig {
     pub provider: String,
@@ -24,11 +47,25 @@ pub struct OAuthTokens {
     pub token_type: String,
 }
 
-pub struct EmailOAuthSkill;
+pub struct EmailOAuthSkill {
+    vault: Arc<Mutex<Vault>>,
+    providers: OAuthProviderRegistry,
+}
 
 impl EmailOAuthSkill {
-    pub fn new() -> Self {
-        Self
+    /// Create the skill, storing obtained tokens in `vault` and using the
+    /// built-in provider definitions.
+    pub fn new(vault: Arc<Mutex<Vault>>) -> Self {
+        Self {
+            vault,
+            providers: OAuthProviderRegistry::builtin(),
+        }
+    }
+
+    /// Use `providers` instead of the built-in provider definitions.
+    pub fn with_providers(mut self, providers: OAuthProviderRegistry) -> Self {
+        self.providers = providers;
+        self
     }
 
     /// Setup OAuth for an email account with bot confirmation
@@ -73,67 +110,23 @@ impl EmailOAuthSkill {
         }
     }
 
-    /// Get OAuth configuration for provider
+    /// Get OAuth configuration for provider from the provider registry
     fn get_oauth_config(&self, provider: &str, email: &str) -> Result<EmailOAuthConfig> {
-        let configs = self.get_provider_configs();
-        
-        let (auth_url, token_url, scopes, client_id) = match provider {
-            "gmail" => (
-                configs.get("gmail_auth_url").unwrap(),
-                configs.get("gmail_token_url").unwrap(),
-                configs.get("gmail_scopes").unwrap(),
-                configs.get("gmail_client_id").unwrap(),
-            ),
-            "outlook" => (
-                configs.get("outlook_auth_url").unwrap(),
-                configs.get("outlook_token_url").unwrap(),
-                configs.get("outlook_scopes").unwrap(),
-                configs.get("outlook_client_id").unwrap(),
-            ),
-            "yahoo" => (
-                configs.get("yahoo_auth_url").unwrap(),
-                configs.get("yahoo_token_url").unwrap(),
-                configs.get("yahoo_scopes").unwrap(),
-                configs.get("yahoo_client_id").unwrap(),
-            ),
-            _ => return Err(anyhow!("Unsupported provider: {}", provider)),
-        };
+        let oauth = self
+            .providers
+            .provider_config(provider)
+            .ok_or_else(|| anyhow!("Unsupported provider: {}", provider))?;
 
         Ok(EmailOAuthConfig {
             provider: provider.to_string(),
             email: email.to_string(),
-            auth_url: auth_url.clone(),
-            token_url: token_url.clone(),
-            scopes: scopes.clone(),
-            client_id: client_id.clone(),
+            auth_url: oauth.auth_url,
+            token_url: oauth.token_url,
+            scopes: oauth.scopes.join(" "),
+            client_id: oauth.client_id,
         })
     }
 
-    /// Get provider OAuth configurations
-    fn get_provider_configs(&self) -> HashMap<String, String> {
-        let mut configs = HashMap::new();
-        
-        // Gmail
-        configs.insert("gmail_auth_url".to_string(), "https://accounts.google.com/o/oauth2/v2/auth".to_string());
-        configs.insert("gmail_token_url".to_string(), "https://oauth2.googleapis.com/token".to_string());
-        configs.insert("gmail_scopes".to_string(), "https://mail.google.com/".to_string());
-        configs.insert("gmail_client_id".to_string(), "your-gmail-client-id.googleusercontent.com".to_string());
-        
-        // Outlook/Office 365
-        configs.insert("outlook_auth_url".to_string(), "https://login.microsoftonline.com/common/oauth2/v2.0/authorize".to_string());
-        configs.insert("outlook_token_url".to_string(), "https://login.microsoftonline.com/common/oauth2/v2.0/token".to_string());
-        configs.insert("outlook_scopes".to_string(), "https://outlook.office.com/IMAP.AccessAsUser.All https://outlook.office.com/SMTP.Send offline_access".to_string());
-        configs.insert("outlook_client_id".to_string(), "your-outlook-client-id".to_string());
-        
-        // Yahoo
-        configs.insert("yahoo_auth_url".to_string(), "https://api.login.yahoo.com/oauth2/request_auth".to_string());
-        configs.insert("yahoo_token_url".to_string(), "https://api.login.yahoo.com/oauth2/get_token".to_string());
-        configs.insert("yahoo_scopes".to_string(), "mail-r mail-w".to_string());
-        configs.insert("yahoo_client_id".to_string(), "your-yahoo-client-id".to_string());
-        
-        configs
-    }
-
     /// Launch OAuth authorization flow
     async fn launch_oauth_flow(&self, config: &EmailOAuthConfig) -> SkillResult {
         // Generate PKCE challenge
@@ -141,7 +134,8 @@ impl EmailOAuthSkill {
         let code_challenge = self.generate_code_challenge(&code_verifier);
         
         // Build authorization URL
-        let redirect_uri = "http://127.0.0.1:8400/callback";
+        let redirect_uri = format!("http://127.0.0.1:{CALLBACK_PORT}/callback");
+        let redirect_uri = redirect_uri.as_str();
         let state = self.generate_state();
         
         let mut auth_url = Url::parse(&config.auth_url)?;
@@ -163,16 +157,13 @@ impl EmailOAuthSkill {
         self.open_browser(&auth_url_str)?;
 
         // Start callback server and wait for authorization
-        let auth_code = self.wait_for_callback().await?;
+        let auth_code = self.wait_for_callback(&state).await?;
 
-        // Exchange code for tokens
-        let tokens = self.exchange_code_for_tokens(config, &auth_code, &code_verifier, redirect_uri).await?;
-
-        // Store tokens securely
-        self.store_tokens(config, &tokens).await?;
+        // Exchange code for tokens and store them securely
+        let tokens = self.finish_authorization(config, &auth_code, &code_verifier, redirect_uri).await?;
 
         // Test connection
-        self.test_email_connection(config).await?;
+        self.test_email_connection(config, &tokens).await?;
 
         Ok(format!(
             "✅ **OAuth Setup Complete!**\n\n\
@@ -184,7 +175,7 @@ impl EmailOAuthSkill {
             🎉 Your email is now configured for secure, passwordless access!",
             config.email,
             config.provider,
-            &tokens.access_token[..20],
+            tokens.access_token.chars().take(20).collect::<String>(),
             if tokens.refresh_token.is_some() { "✅ Available" } else { "❌ Not provided" },
             tokens.expires_at
         ))
@@ -237,61 +228,243 @@ impl EmailOAuthSkill {
         Ok(())
     }
 
-    /// Wait for OAuth callback (simplified - would implement actual HTTP server)
-    async fn wait_for_callback(&self) -> Result<String> {
-        // In a real implementation, this would:
-        // 1. Start an HTTP server on localhost:8400
-        // 2. Wait for the OAuth callback with the authorization code
-        // 3. Return the code
-        
-        // For now, simulate waiting and return a dummy code
+    /// Wait for the OAuth redirect on the local callback port and return
+    /// the authorization code, rejecting a mismatched `state`.
+    async fn wait_for_callback(&self, expected_state: &str) -> Result<String> {
         println!("🔄 Waiting for OAuth callback...");
-        sleep(Duration::from_secs(2)).await;
-        
-        // This would be the actual authorization code from the callback
-        Ok("dummy_auth_code_123".to_string())
+        let (code, state) = CallbackServer::start(CALLBACK_PORT, CALLBACK_TIMEOUT_SECS).await?;
+        if state != expected_state {
+            return Err(anyhow!("OAuth callback state mismatch"));
+        }
+        Ok(code)
     }
 
-    /// Exchange authorization code for tokens
+    /// Exchange authorization code for tokens at the provider's token
+    /// endpoint, proving possession of the PKCE verifier.
     async fn exchange_code_for_tokens(
         &self,
         config: &EmailOAuthConfig,
-        _auth_code: &str,
-        _code_verifier: &str,
-        _redirect_uri: &str,
+        auth_code: &str,
+        code_verifier: &str,
+        redirect_uri: &str,
     ) -> Result<OAuthTokens> {
-        // In a real implementation, this would make an HTTP POST to the token endpoint
-        // For now, return dummy tokens
-        
-        let expires_at = chrono::Utc::now().timestamp() + 3600; // 1 hour from now
-        
+        let flow = OAuthFlow::new(OAuthConfig {
+            client_id: config.client_id.clone(),
+            client_secret: None,
+            auth_url: config.auth_url.clone(),
+            token_url: config.token_url.clone(),
+            redirect_uri: redirect_uri.to_string(),
+            scopes: config.scopes.split_whitespace().map(String::from).collect(),
+        });
+        let tokens = flow.exchange_code(auth_code, code_verifier).await?;
+
         Ok(OAuthTokens {
-            access_token: format!("access_token_for_{}", config.email),
-            refresh_token: Some(format!("refresh_token_for_{}", config.email)),
-            expires_at,
-            token_type: "Bearer".to_string(),
+            access_token: tokens.access_token,
+            refresh_token: tokens.refresh_token,
+            // 0 when the provider did not report a lifetime.
+            expires_at: tokens.expires_at.unwrap_or(0),
+            token_type: tokens.token_type,
         })
     }
 
-    /// Store tokens in OpenIntentOS vault
-    async fn store_tokens(&self, config: &EmailOAuthConfig, _tokens: &OAuthTokens) -> Result<()> {
-        // In a real implementation, this would use the vault adapter
-        println!("🔒 Storing tokens in vault for {}", config.email);
+    /// Exchange the authorization code and store the resulting tokens.
+    async fn finish_authorization(
+        &self,
+        config: &EmailOAuthConfig,
+        auth_code: &str,
+        code_verifier: &str,
+        redirect_uri: &str,
+    ) -> Result<OAuthTokens> {
+        let tokens = self
+            .exchange_code_for_tokens(config, auth_code, code_verifier, redirect_uri)
+            .await?;
+        self.store_tokens(config, &tokens)?;
+        Ok(tokens)
+    }
+
+    /// Store tokens encrypted in the vault under [`credential_key`],
+    /// replacing any tokens from an earlier setup.
+    fn store_tokens(&self, config: &EmailOAuthConfig, tokens: &OAuthTokens) -> Result<()> {
+        let key = credential_key(&config.provider, &config.email);
+        let data = serde_json::to_value(tokens)?;
+        let scopes: Vec<String> = config.scopes.split_whitespace().map(String::from).collect();
+        let expires_at = Some(tokens.expires_at)
+            .filter(|ts| *ts > 0)
+            .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0));
+
+        let vault = self
+            .vault
+            .lock()
+            .map_err(|e| anyhow!("vault lock poisoned: {e}"))?;
+        match vault.store_credential(
+            &key,
+            CredentialType::OAuth,
+            &data,
+            Some(scopes.as_slice()),
+            Some(&config.email),
+            expires_at,
+        ) {
+            Ok(()) => {}
+            Err(VaultError::CredentialAlreadyExists { .. }) => {
+                vault.update_credential(&key, &data, expires_at)?;
+            }
+            Err(e) => return Err(e.into()),
+        }
+        println!("🔒 Stored tokens in vault for {}", config.email);
         Ok(())
     }
 
-    /// Test email connection
-    async fn test_email_connection(&self, config: &EmailOAuthConfig) -> Result<()> {
-        // In a real implementation, this would test IMAP/SMTP connection with the tokens
-        println!("🧪 Testing email connection for {}", config.email);
-        sleep(Duration::from_secs(1)).await;
+    /// Test email connection by logging in to the provider's IMAP server
+    /// with the new access token
+    async fn test_email_connection(&self, config: &EmailOAuthConfig, tokens: &OAuthTokens) -> Result<()> {
+        let (host, port) = imap::imap_endpoint(&config.provider)
+            .ok_or_else(|| anyhow!("No IMAP server known for provider: {}", config.provider))?;
+        println!("🧪 Testing email connection for {} via {}", config.email, host);
+        imap::check_connection(host, port, &config.email, &tokens.access_token).await?;
         println!("✅ Email connection test successful!");
         Ok(())
     }
 }
 
-/// Execute email OAuth setup skill
-pub async fn execute_email_oauth_setup(email: &str) -> SkillResult {
-    let skill = EmailOAuthSkill::new();
+/// Execute email OAuth setup skill, storing tokens in `vault`.  Provider
+/// overrides (e.g. client IDs) are read from `config/default.toml`.
+pub async fn execute_email_oauth_setup(email: &str, vault: Arc<Mutex<Vault>>) -> SkillResult {
+    let providers = OAuthProviderRegistry::load(Path::new(PROVIDER_CONFIG_PATH))?;
+    let skill = EmailOAuthSkill::new(vault).with_providers(providers);
     skill.setup_oauth(email).await
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve one token request, answering with `payload`, and return the
+    /// request body.
+    async fn mock_token_endpoint(listener: TcpListener, payload: &'static str) -> String {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 4096];
+        let body = loop {
+            let n = stream.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&request).into_owned();
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let length = head
+                    .lines()
+                    .find_map(|l| {
+                        l.to_ascii_lowercase()
+                            .strip_prefix("content-length:")
+                            .map(|v| v.trim().parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                if body.len() >= length {
+                    break body.to_string();
+                }
+            }
+        };
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            payload.len(),
+            payload
+        );
+        stream.write_all(response.as_bytes()).await.unwrap();
+        body
+    }
+
+    fn skill() -> (EmailOAuthSkill, Arc<Mutex<Vault>>) {
+        let vault = Arc::new(Mutex::new(Vault::open_in_memory(&[7u8; 32]).unwrap()));
+        (EmailOAuthSkill::new(vault.clone()), vault)
+    }
+
+    fn gmail_config(token_url: String) -> EmailOAuthConfig {
+        EmailOAuthConfig {
+            provider: "gmail".into(),
+            email: "user@gmail.com".into(),
+            auth_url: "https://accounts.google.com/o/oauth2/v2/auth".into(),
+            token_url,
+            scopes: "https://mail.google.com/".into(),
+            client_id: "client-123".into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn exchange_code_parses_token_payload() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(mock_token_endpoint(
+            listener,
+            r#"{"access_token":"ya29.real-access-token","refresh_token":"1//refresh","expires_in":3599,"token_type":"Bearer","scope":"https://mail.google.com/"}"#,
+        ));
+
+        let config = gmail_config(format!("http://127.0.0.1:{port}/token"));
+        let (skill, _) = skill();
+        let verifier = skill.generate_code_verifier();
+        let before = chrono::Utc::now().timestamp();
+        let tokens = skill
+            .exchange_code_for_tokens(
+                &config,
+                "auth-code-xyz",
+                &verifier,
+                "http://127.0.0.1:8400/callback",
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(tokens.access_token, "ya29.real-access-token");
+        assert_eq!(tokens.refresh_token.as_deref(), Some("1//refresh"));
+        assert_eq!(tokens.token_type, "Bearer");
+        assert!(tokens.expires_at >= before + 3599);
+
+        let body = server.await.unwrap();
+        let form: HashMap<String, String> = url::form_urlencoded::parse(body.as_bytes())
+            .into_owned()
+            .collect();
+        assert_eq!(form["grant_type"], "authorization_code");
+        assert_eq!(form["code"], "auth-code-xyz");
+        assert_eq!(form["code_verifier"], verifier);
+        assert_eq!(form["client_id"], "client-123");
+    }
+
+    #[tokio::test]
+    async fn authorization_stores_tokens_in_vault() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(mock_token_endpoint(
+            listener,
+            r#"{"access_token":"ya29.stored","refresh_token":"1//stored","expires_in":3600,"token_type":"Bearer"}"#,
+        ));
+
+        let config = gmail_config(format!("http://127.0.0.1:{port}/token"));
+        let (skill, vault) = skill();
+        skill
+            .finish_authorization(&config, "code", "verifier", "http://127.0.0.1:8400/callback")
+            .await
+            .unwrap();
+        server.await.unwrap();
+
+        let credential = vault
+            .lock()
+            .unwrap()
+            .get_credential(&credential_key("gmail", "user@gmail.com"))
+            .unwrap();
+        assert_eq!(credential.credential_type, CredentialType::OAuth);
+        assert_eq!(credential.user_label.as_deref(), Some("user@gmail.com"));
+        assert_eq!(credential.data["access_token"], "ya29.stored");
+        assert_eq!(credential.data["refresh_token"], "1//stored");
+        assert!(credential.expires_at.is_some());
+
+        // A second setup for the same account replaces the stored tokens.
+        let mut tokens: OAuthTokens = serde_json::from_value(credential.data).unwrap();
+        tokens.access_token = "ya29.rotated".into();
+        skill.store_tokens(&config, &tokens).unwrap();
+        let credential = vault
+            .lock()
+            .unwrap()
+            .get_credential(&credential_key("gmail", "user@gmail.com"))
+            .unwrap();
+        assert_eq!(credential.data["access_token"], "ya29.rotated");
+    }
+}
@@ -1,3 +1,4 @@
+pub mod collector;
 pub mod email_oauth;
 
 pub use email_oauth::execute_email_oauth_setup;